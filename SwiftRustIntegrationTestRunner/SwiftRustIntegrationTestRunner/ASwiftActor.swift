@@ -0,0 +1,20 @@
+//
+//  ASwiftActor.swift
+//  SwiftRustIntegrationTestRunner
+//
+
+import Foundation
+
+public actor ASwiftActor {
+    private var count: UInt32 = 0
+
+    init() {}
+
+    func increment(amount: UInt32) {
+        count += amount
+    }
+
+    func count_value() -> UInt32 {
+        count
+    }
+}
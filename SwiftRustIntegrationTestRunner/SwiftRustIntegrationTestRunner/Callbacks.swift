@@ -55,3 +55,9 @@ class SwiftMethodCallbackTester {
         callback(5)
     }
 }
+
+func swift_reflect_u8_with_completion_handler(arg: UInt8, completion: @escaping (UInt8) -> ()) {
+    DispatchQueue.global().async {
+        completion(arg)
+    }
+}
@@ -40,9 +40,73 @@ class AsyncTests: XCTestCase {
         XCTAssertEqual(num, 567)
     }
 
+    /// Verify that we can create a Rust type using an async initializer.
+    func testSwiftCallsRustAsyncInitializer() async throws {
+        let test = await TestRustAsyncInit(789)
+        XCTAssertEqual(test.value(), 789)
+    }
+
     
     func testSwiftCallsRustAsyncFnRetStruct() async throws {
         let _: AsyncRustFnReturnStruct = await rust_async_return_struct()
     }
+
+    /// Verify that a cancellable async Rust function returns its value when it isn't cancelled.
+    func testSwiftCallsRustCancellableAsyncFn() async throws {
+        let num = try await rust_async_cancellable_reflect_u8(123)
+        XCTAssertEqual(num, 123)
+    }
+
+    /// Verify that cancelling the Swift task drops the Rust future and throws a
+    /// `CancellationError`.
+    func testCancellingTaskDropsRustFuture() async throws {
+        let task = Task {
+            try await rust_async_cancellable_pending()
+        }
+        task.cancel()
+
+        do {
+            try await task.value
+            XCTFail("The task should have been cancelled")
+        } catch is CancellationError {
+        }
+
+        XCTAssertTrue(rust_async_cancellable_pending_was_dropped())
+    }
+
+    /// Verify that Rust can await a Swift function's completion handler.
+    func testRustAwaitsSwiftCompletionHandler() async throws {
+        let num = await rust_async_awaits_swift_completion_handler(123)
+        XCTAssertEqual(num, 123)
+    }
+
+    /// Verify that an `AsyncStream` yields the values sent to the Rust channel and finishes
+    /// once the Rust sender is dropped.
+    func testAsyncStreamYieldsChannelValues() async throws {
+        let source = AsyncStreamTestSource()
+        let values = source.values()
+
+        XCTAssertTrue(source.send(1))
+        XCTAssertTrue(source.send(2))
+        source.close()
+
+        var received: [UInt32] = []
+        for await value in values.stream() {
+            received.append(value)
+        }
+        XCTAssertEqual(received, [1, 2])
+    }
+
+    /// Verify that dropping the stream closes the Rust channel.
+    func testDroppingAsyncStreamClosesChannel() async throws {
+        let source = AsyncStreamTestSource()
+        var values: AsyncStreamTestValues? = source.values()
+
+        XCTAssertTrue(source.send(1))
+        values = nil
+        XCTAssertNil(values)
+
+        XCTAssertFalse(source.send(2))
+    }
 }
 
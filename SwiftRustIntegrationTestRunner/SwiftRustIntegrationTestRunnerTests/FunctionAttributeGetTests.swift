@@ -10,7 +10,7 @@ import Foundation
 import XCTest
 @testable import SwiftRustIntegrationTestRunner
 
-/// Tests the #[swift_bridge(get(...))] attribute.
+/// Tests the #[swift_bridge(get(...))] and #[swift_bridge(set(...))] attributes.
 class FunctionAttributeGetTests: XCTestCase {
 
     override func setUpWithError() throws {
@@ -26,5 +26,16 @@ class FunctionAttributeGetTests: XCTestCase {
         let val = SomeTypeGet()
         XCTAssertEqual(val.my_opt_static_str()!.toString(), "world")
     }
-}
 
+    /// Verify that the `swift_bridge(set(...))` and `swift_bridge(set_with(...))` attributes
+    /// update the field.
+    func testSetField() throws {
+        let val = SomeTypeSet()
+
+        val.set_my_u8(5)
+        XCTAssertEqual(val.my_u8(), 5)
+
+        val.set_my_i16(10)
+        XCTAssertEqual(val.my_i16(), 10)
+    }
+}
@@ -24,4 +24,14 @@ class FunctionAttributeTests: XCTestCase {
     func testSwiftNameAttribute() throws {
         XCTAssertEqual(testCallRustFromSwiftByNameAttribute().toString(), "StringFromRust")
     }
+
+    /// Verify that we can await `swift_bridge(blocking)` functions and methods.
+    func testBlockingAttribute() async throws {
+        let sum = await blocking_sum_to(100)
+        XCTAssertEqual(sum, 5050)
+
+        let summer = BlockingTestSummer()
+        let methodSum = await summer.sum_to(10)
+        XCTAssertEqual(methodSum, 55)
+    }
 }
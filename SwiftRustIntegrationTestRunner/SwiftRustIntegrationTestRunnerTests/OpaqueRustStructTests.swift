@@ -34,6 +34,43 @@ class OpaqueRustStructTests: XCTestCase {
         XCTAssertEqual(stack.len(), 1)
     }
     
+    /// Verify that an opaque Rust type that Swift owns is counted as live until Swift releases it.
+    func testLiveObjectCountsOfOpaqueRustStruct() throws {
+        let stackType = "swift_integration_tests::expose_opaque_rust_type::ARustStack"
+        let before = rustLiveObjectCounts()[stackType, default: 0]
+
+        do {
+            let stack = ARustStack()
+            let otherStack = ARustStack()
+            stack.push(5)
+            otherStack.push(10)
+            XCTAssertEqual(rustLiveObjectCounts()[stackType, default: 0], before + 2)
+        }
+
+        XCTAssertEqual(rustLiveObjectCounts()[stackType, default: 0], before)
+    }
+    
+    /// Verify that we can pass an already boxed opaque Rust type between Rust and Swift, and that
+    /// Swift frees it when it is done with it.
+    func testBoxedOpaqueRustStruct() throws {
+        let stackType = "swift_integration_tests::expose_opaque_rust_type::ARustStack"
+        let before = rustLiveObjectCounts()[stackType, default: 0]
+
+        do {
+            let stack = make_boxed_rust_stack(5)
+            XCTAssertEqual(stack.as_slice()[0], 5)
+            XCTAssertEqual(rustLiveObjectCounts()[stackType, default: 0], before + 1)
+
+            stack.push(10)
+            XCTAssertEqual(boxed_rust_stack_len(stack), 2)
+
+            XCTAssertEqual(maybe_make_boxed_rust_stack(6)!.len(), 1)
+            XCTAssertNil(maybe_make_boxed_rust_stack(nil))
+        }
+
+        XCTAssertEqual(rustLiveObjectCounts()[stackType, default: 0], before)
+    }
+    
     /// Verify that when we de-alocate a class instance that is wrapping a type that was returned to us from
     /// Rust by reference we do not free the Rust type's memory (like we do with owned values).
     ///
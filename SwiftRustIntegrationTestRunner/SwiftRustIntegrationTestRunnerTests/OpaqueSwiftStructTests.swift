@@ -22,4 +22,9 @@ class OpaqueSwiftStructTests: XCTestCase {
     func testOpaqueSwiftStruct() throws {
         run_opaque_swift_class_tests()
     }
+
+    /// Run the Rust tests that call the methods of a Swift actor.
+    func testSwiftActor() throws {
+        run_swift_actor_tests()
+    }
 }
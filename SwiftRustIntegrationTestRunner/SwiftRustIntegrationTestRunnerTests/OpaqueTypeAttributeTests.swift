@@ -42,6 +42,66 @@ class OpaqueTypeAttributeTests: XCTestCase {
         XCTAssert(AlreadyDeclaredCopyTypeTest.an_associated_function())
     }
 
+    /// Verify that we can subclass an opaque Rust type that uses the `open` attribute and
+    /// override its methods.
+    /// See crates/swift-integration-tests/src/opaque_type_attributes/open.rs
+    func testExternRustOpenOpaqueRustType() throws {
+        class DoublingCounter: OpenTypeTest {
+            override func increment() {
+                super.increment()
+                super.increment()
+            }
+        }
+
+        let counter = DoublingCounter(5)
+        counter.increment()
+
+        XCTAssertEqual(counter.count(), 7)
+    }
+
+    /// Verify that the `withLock` method of a `Mutex` type locks the Rust mutex while its closure
+    /// runs, and unlocks it even if the closure throws.
+    /// See crates/swift-integration-tests/src/opaque_type_attributes/lock.rs
+    func testExternRustMutexType() throws {
+        struct SomeError: Error {}
+
+        let counter = new_mutex_test_counter()
+
+        let count = counter.withLock { value -> UInt32 in
+            XCTAssert(mutex_test_counter_is_locked(counter))
+            value.increment()
+            return value.count()
+        }
+        XCTAssertEqual(count, 1)
+        XCTAssertFalse(mutex_test_counter_is_locked(counter))
+
+        XCTAssertThrowsError(try counter.withLock { value in
+            value.increment()
+            throw SomeError()
+        })
+        XCTAssertFalse(mutex_test_counter_is_locked(counter))
+        XCTAssertEqual(counter.withLock { $0.count() }, 2)
+    }
+
+    /// Verify that the `withReadLock` and `withWriteLock` methods of an `RwLock` type lock the Rust
+    /// lock while their closures run.
+    /// See crates/swift-integration-tests/src/opaque_type_attributes/lock.rs
+    func testExternRustRwLockType() throws {
+        let counter = new_rw_lock_test_counter()
+
+        counter.withWriteLock { value in
+            XCTAssert(rw_lock_test_counter_is_locked(counter))
+            value.increment()
+        }
+
+        let count = counter.withReadLock { value -> UInt32 in
+            XCTAssert(rw_lock_test_counter_is_locked(counter))
+            return value.count()
+        }
+        XCTAssertEqual(count, 1)
+        XCTAssertFalse(rw_lock_test_counter_is_locked(counter))
+    }
+
     
     func testPerformanceExample() throws {
         // This is an example of a performance test case.
@@ -30,5 +30,18 @@ class PrimitiveTests: XCTestCase {
         XCTAssertEqual(rust_negate_bool(true), false);
         XCTAssertEqual(rust_negate_bool(false), true);
     }
+    
+    #if compiler(>=6.0)
+    /// Verify that we can pass 128 bit integers, including values that don't fit in 64 bits.
+    func testSwiftCallsRust128BitIntegers() throws {
+        guard #available(macOS 15.0, iOS 18.0, *) else {
+            throw XCTSkip("UInt128 and Int128 require macOS 15 or iOS 18")
+        }
+        
+        XCTAssertEqual(rust_double_u128(UInt128(UInt64.max)), UInt128(UInt64.max) * 2);
+        XCTAssertEqual(rust_double_i128(-10), -20);
+        XCTAssertEqual(rust_double_i128(Int128(Int64.min)), Int128(Int64.min) * 2);
+    }
+    #endif
 }
 
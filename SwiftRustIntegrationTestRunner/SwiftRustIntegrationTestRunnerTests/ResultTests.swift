@@ -33,4 +33,55 @@ class ResultTests: XCTestCase {
             .Err(ResultTestOpaqueSwiftType(val: 666))
         )
     }
+    
+    /// Verify that we can pass a Result<SharedStruct, OpaqueRust> from Swift -> Rust
+    func testSwiftCallRustResultSharedStruct() throws {
+        rust_func_takes_result_shared_struct(
+            .Ok(ResultTestSharedStruct(field: 333))
+        )
+        rust_func_takes_result_shared_struct(
+            .Err(ResultTestOpaqueRustType(444))
+        )
+    }
+
+    /// Verify that we can pass a Result<SharedStruct, OpaqueRust> whose shared struct is too large
+    /// to be passed by value from Swift -> Rust -> Swift
+    func testSwiftCallRustResultLargeSharedStruct() throws {
+        let large = ResultTestLargeSharedStruct(a: 1, b: 2, c: 3, d: 4, e: 5, f: 6, g: 7, h: 8)
+
+        let ok = rust_func_reflect_result_large_shared_struct(.Ok(large)).ok()!
+        XCTAssertEqual(ok.a, 1)
+        XCTAssertEqual(ok.h, 8)
+
+        let err = rust_func_reflect_result_large_shared_struct(.Err(ResultTestOpaqueRustType(999)))
+        XCTAssertEqual(err.err()!.val(), 999)
+    }
+
+    /// Verify that we can pass a Result<Primitive, String> from Swift -> Rust
+    func testSwiftCallRustResultPrimitiveAndString() throws {
+        XCTAssertEqual(rust_func_takes_result_primitive_and_string(.Ok(123)), 123)
+        XCTAssertEqual(rust_func_takes_result_primitive_and_string(.Err("four")), 4)
+    }
+
+    /// Verify that we can return a Result<OpaqueRust, Primitive> from Rust -> Swift
+    func testRustReturnsResultOpaqueRustOrPrimitive() throws {
+        XCTAssertEqual(rust_func_returns_result_opaque_rust_or_primitive(true).ok()!.val(), 777)
+        XCTAssertEqual(rust_func_returns_result_opaque_rust_or_primitive(false).err(), 88)
+    }
+
+    /// Verify that we can return a Result<(), Primitive> from Rust -> Swift
+    func testRustReturnsResultNullOrPrimitive() throws {
+        XCTAssertNotNil(rust_func_returns_result_null_or_f64(true).ok())
+        XCTAssertEqual(rust_func_returns_result_null_or_f64(false).err(), 1.5)
+    }
+
+    /// Verify that a throwing initializer in a `#[swift_bridge(panic = "throw")]` module throws
+    /// a RustPanic when its Rust function panics.
+    func testThrowingInitializerThrowsRustPanic() throws {
+        XCTAssertEqual(try PanicTestType(5).val(), 5)
+
+        XCTAssertThrowsError(try PanicTestType(0)) { error in
+            XCTAssertEqual((error as! RustPanic).message, "val was zero")
+        }
+    }
 }
@@ -42,4 +42,14 @@ class SharedStructTests: XCTestCase {
         XCTAssertEqual(val._0, reflected._0)
         XCTAssertEqual(val._1, reflected._1)
     }
+    
+    /// Verify that a `#[repr(C, packed)]` struct has the same size in Swift as it does in Rust.
+    func testPackedStruct() {
+        let val = swift_calls_rust_packed_struct(StructReprPacked(tag: 7, length: 1234))
+        XCTAssertEqual(val.tag, 7)
+        XCTAssertEqual(val.length, 1234)
+        
+        XCTAssertEqual(MemoryLayout<__swift_bridge__$StructReprPacked>.size, 5)
+        XCTAssertEqual(rust_packed_struct_ffi_size(), 5)
+    }
 }
@@ -46,5 +46,70 @@ class StringTests: XCTestCase {
             "hi"
         )
     }
+
+    /// Verify that a RustStr can be inspected without converting it into a String.
+    func testRustStrWithoutConvertingToString() throws {
+        let rustString: RustString = create_string("hello world")
+        let str: RustStr = rustString.as_str()
+
+        XCTAssertTrue(str == "hello world")
+        XCTAssertTrue("hello world" == str)
+        XCTAssertTrue(str != "hello")
+        XCTAssertEqual(str, rustString.trim())
+
+        XCTAssertTrue(str.hasPrefix("hello"))
+        XCTAssertFalse(str.hasPrefix("world"))
+        XCTAssertTrue(str.hasSuffix("world"))
+        XCTAssertFalse(str.hasSuffix("hello"))
+        XCTAssertTrue(str.contains("o w"))
+        XCTAssertTrue(str.contains(""))
+        XCTAssertFalse(str.contains("hello world!"))
+
+        let byteCount = str.withUnsafeBufferPointer { $0.count }
+        XCTAssertEqual(byteCount, 11)
+    }
+
+    /// Verify that a string literal can be used where a RustString is expected.
+    func testRustStringFromStringLiteral() throws {
+        let rustString: RustString = "hello"
+
+        XCTAssertEqual(rustString.toString(), "hello")
+        XCTAssertEqual(rustString.utf8Count, 5)
+        XCTAssertEqual("\(rustString) world", "hello world")
+    }
+
+    /// Verify that an owned String moves between Rust and Swift without its buffer being copied
+    /// or reallocated.
+    func testStringMovesWithoutCopying() throws {
+        let rustString = create_string_with_capacity("hello", 100)
+        let start = rustString.as_str().start
+
+        let reflected = reflect_string(rustString)
+        XCTAssertEqual(reflected.as_str().start, start)
+        XCTAssertEqual(reflected.toString(), "hello")
+        XCTAssertEqual(string_capacity(reflected), 100)
+
+        let swiftCreated = RustString("world")
+        let swiftCreatedStart = swiftCreated.as_str().start
+        XCTAssertEqual(reflect_string(swiftCreated).as_str().start, swiftCreatedStart)
+    }
+
+    /// Verify that an empty Swift String is passed to Rust as an empty string.
+    func testEmptyStringToRust() throws {
+        XCTAssertEqual(create_string("").len(), 0)
+        XCTAssertEqual(reflect_string("").len(), 0)
+    }
+
+    /// Verify that RustStrings are compared and hashed by their contents.
+    func testRustStringEquatableHashable() throws {
+        let rustString: RustString = create_string("hello")
+
+        XCTAssertEqual(rustString, "hello")
+        XCTAssertNotEqual(rustString, "hello world")
+        XCTAssertEqual(rustString.hashValue, RustString("hello").hashValue)
+
+        let set: Set<RustString> = [rustString, "hello", "world"]
+        XCTAssertEqual(set.count, 2)
+    }
 }
 
@@ -48,7 +48,63 @@ class VecTests: XCTestCase {
         }
         XCTAssertEqual(iterations, 2)
     }
-    
+    func testRustVecU8Collection() throws {
+        let vec = RustVec<UInt8>()
+        XCTAssertTrue(vec.isEmpty)
+        vec.push(value: 1)
+        vec.push(value: 2)
+        vec.push(value: 3)
+
+        XCTAssertFalse(vec.isEmpty)
+        XCTAssertEqual(vec.count, 3)
+        XCTAssertEqual(vec.indices, 0..<3)
+        XCTAssertEqual(vec.first, 1)
+        XCTAssertEqual(vec.last, 3)
+        XCTAssertEqual(vec.map { $0 * 2 }, [2, 4, 6])
+        XCTAssertEqual(vec.filter { $0 != 2 }, [1, 3])
+        XCTAssertEqual(Array(vec.reversed()), [3, 2, 1])
+        XCTAssertEqual(Array(vec[1...]), [2, 3])
+        XCTAssertEqual(vec.firstIndex(of: 3), 2)
+    }
+    func testRustVecU8InsertRemove() throws {
+        let vec = RustVec<UInt8>()
+        vec.push(value: 1)
+        vec.push(value: 3)
+
+        vec.insert(2, at: 1)
+        vec.insert(0, at: 0)
+        XCTAssertEqual(Array(vec), [0, 1, 2, 3])
+
+        XCTAssertEqual(vec.remove(at: 1), 1)
+        XCTAssertEqual(Array(vec), [0, 2, 3])
+    }
+    func testRustVecU8ReserveCapacity() throws {
+        let vec = RustVec<UInt8>()
+        vec.reserveCapacity(100)
+        XCTAssertGreaterThanOrEqual(vec.capacity, 100)
+
+        let capacity = vec.capacity
+        for value in 0..<100 {
+            vec.push(value: UInt8(value))
+        }
+        XCTAssertEqual(vec.capacity, capacity)
+    }
+
+    /// Verify that we can read a RustVec of primitives in bulk through a buffer pointer.
+    func testRustVecWithUnsafeBufferPointer() throws {
+        let vec = rust_make_vec_f32(10_000)
+
+        let sum = vec.withUnsafeBufferPointer { buffer -> Double in
+            XCTAssertEqual(buffer.count, 10_000)
+            XCTAssertEqual(buffer[9_999], 9_999)
+            return buffer.reduce(0) { $0 + Double($1) }
+        }
+        XCTAssertEqual(sum, 49_995_000)
+        XCTAssertEqual(vec.as_ptr()[1], 1)
+
+        XCTAssertEqual(RustVec<Double>().withUnsafeBufferPointer { $0.count }, 0)
+    }
+
     func testVecOfOpaqueRustTypeLen() throws {
         let vec = RustVec<ARustTypeInsideVecT>()
         XCTAssertEqual(vec.len(), 0)
@@ -69,6 +125,26 @@ class VecTests: XCTestCase {
         XCTAssertEqual(popped?.text().toString(), "hello world")
         XCTAssertEqual(vec.len(), 0)
     }
+    func testVecOfOpaqueRustTypeInsertRemove() throws {
+        let vec: RustVec<ARustTypeInsideVecT> = RustVec()
+        vec.push(value: ARustTypeInsideVecT("world"))
+        vec.insert(ARustTypeInsideVecT("hello"), at: 0)
+
+        XCTAssertEqual(vec.len(), 2)
+        let removed = vec.remove(at: 0)
+        XCTAssertEqual(removed.text().toString(), "hello")
+        XCTAssertEqual(vec.get(index: 0)!.text().toString(), "world")
+    }
+    func testVecOfTransparentEnumInsertRemove() throws {
+        let vec: RustVec<TransparentEnumInsideVecT> = RustVec()
+        vec.reserveCapacity(2)
+        vec.push(value: TransparentEnumInsideVecT.VariantB)
+        vec.insert(TransparentEnumInsideVecT.VariantA, at: 0)
+
+        XCTAssertGreaterThanOrEqual(vec.capacity, 2)
+        XCTAssertEqual(vec.remove(at: 1), TransparentEnumInsideVecT.VariantB)
+        XCTAssertEqual(vec.get(index: 0)!, TransparentEnumInsideVecT.VariantA)
+    }
     
     /// Verify that a Vec<T> of opaque Rust types can be used as an argument and return
     /// type for extern "Rust" functions.
@@ -112,6 +188,9 @@ class VecTests: XCTestCase {
         XCTAssertEqual(RustVec<Int64>().len(), 0);
         XCTAssertEqual(RustVec<Int>().len(), 0);
         
+        XCTAssertEqual(RustVec<Float>().len(), 0);
+        XCTAssertEqual(RustVec<Double>().len(), 0);
+        
         XCTAssertEqual(RustVec<Bool>().len(), 0);
     }
 }
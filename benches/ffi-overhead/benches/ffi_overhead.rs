@@ -0,0 +1,196 @@
+//! Measures the Rust side of calling the functions in `src/lib.rs` from Swift.
+//!
+//! Each benchmark makes the same calls into the exported `extern "C"` functions that the generated
+//! Swift code makes, such as creating a `RustString` before passing a Swift `String` to Rust and
+//! freeing the value that Rust returns. This leaves out the cost of the Swift side of the call,
+//! which `swift/main.swift` measures.
+//!
+//! ```sh
+//! cargo bench -p ffi-overhead
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::ffi::c_void;
+use swift_bridge::option::OptionU64;
+use swift_bridge::string::RustStr;
+
+// Link the library that exports the functions that we're benchmarking.
+use ffi_overhead as _;
+
+extern "C" {
+    #[link_name = "__ffi_overhead__$bench_noop"]
+    fn bench_noop();
+    #[link_name = "__ffi_overhead__$bench_reflect_u64"]
+    fn bench_reflect_u64(value: u64) -> u64;
+    #[link_name = "__ffi_overhead__$bench_reflect_f64"]
+    fn bench_reflect_f64(value: f64) -> f64;
+    #[link_name = "__ffi_overhead__$bench_reflect_bool"]
+    fn bench_reflect_bool(value: bool) -> bool;
+
+    #[link_name = "__ffi_overhead__$bench_str_len"]
+    fn bench_str_len(string: RustStr) -> usize;
+    #[link_name = "__ffi_overhead__$bench_reflect_string"]
+    fn bench_reflect_string(string: *mut c_void) -> *mut c_void;
+
+    #[link_name = "__ffi_overhead__$bench_make_vec"]
+    fn bench_make_vec(len: usize) -> *mut c_void;
+    #[link_name = "__ffi_overhead__$bench_sum_vec"]
+    fn bench_sum_vec(vec: *mut c_void) -> u64;
+
+    #[link_name = "__ffi_overhead__$bench_reflect_option_u64"]
+    fn bench_reflect_option_u64(value: OptionU64) -> OptionU64;
+    #[link_name = "__ffi_overhead__$bench_reflect_option_string"]
+    fn bench_reflect_option_string(value: *mut c_void) -> *mut c_void;
+
+    #[link_name = "__ffi_overhead__$BenchCounter$new"]
+    fn bench_counter_new() -> *mut c_void;
+    #[link_name = "__ffi_overhead__$BenchCounter$increment"]
+    fn bench_counter_increment(this: *mut c_void);
+    #[link_name = "__ffi_overhead__$BenchCounter$value"]
+    fn bench_counter_value(this: *mut c_void) -> u64;
+    #[link_name = "__ffi_overhead__$BenchCounter$_free"]
+    fn bench_counter_free(this: *mut c_void);
+
+    #[link_name = "__swift_bridge__$RustString$new_with_str"]
+    fn rust_string_new_with_str(str: RustStr) -> *mut c_void;
+    #[link_name = "__swift_bridge__$RustString$as_str"]
+    fn rust_string_as_str(this: *mut c_void) -> RustStr;
+    #[link_name = "__swift_bridge__$RustString$_free"]
+    fn rust_string_free(this: *mut c_void);
+
+    #[link_name = "__swift_bridge__$Vec_u8$new"]
+    fn vec_u8_new() -> *mut c_void;
+    #[link_name = "__swift_bridge__$Vec_u8$push"]
+    fn vec_u8_push(vec: *mut c_void, val: u8);
+    #[link_name = "__swift_bridge__$Vec_u8$len"]
+    fn vec_u8_len(vec: *mut c_void) -> usize;
+    #[link_name = "__swift_bridge__$Vec_u8$as_ptr"]
+    fn vec_u8_as_ptr(vec: *mut c_void) -> *const u8;
+    #[link_name = "__swift_bridge__$Vec_u8$_free"]
+    fn vec_u8_free(vec: *mut c_void);
+}
+
+const SHORT_STRING: &str = "hello world";
+const VEC_LEN: usize = 1024;
+
+fn rust_str(string: &str) -> RustStr {
+    RustStr {
+        start: string.as_ptr(),
+        len: string.len(),
+    }
+}
+
+fn primitives(c: &mut Criterion) {
+    let mut group = c.benchmark_group("primitives");
+
+    group.bench_function("noop", |b| b.iter(|| unsafe { bench_noop() }));
+    group.bench_function("u64", |b| {
+        b.iter(|| unsafe { bench_reflect_u64(black_box(123)) })
+    });
+    group.bench_function("f64", |b| {
+        b.iter(|| unsafe { bench_reflect_f64(black_box(1.5)) })
+    });
+    group.bench_function("bool", |b| {
+        b.iter(|| unsafe { bench_reflect_bool(black_box(true)) })
+    });
+
+    group.finish();
+}
+
+fn strings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strings");
+
+    // Swift passes a `&str` as a pointer into its own UTF-8 buffer, without calling Rust.
+    group.bench_function("&str arg", |b| {
+        b.iter(|| unsafe { bench_str_len(rust_str(black_box(SHORT_STRING))) })
+    });
+    // Swift copies the `String` into a new `RustString`, then copies the returned `RustString`
+    // back into a Swift `String` before freeing it.
+    group.bench_function("String arg and return", |b| {
+        b.iter(|| unsafe {
+            let string = rust_string_new_with_str(rust_str(black_box(SHORT_STRING)));
+            let returned = bench_reflect_string(string);
+            let str = rust_string_as_str(returned);
+            black_box(std::slice::from_raw_parts(str.start, str.len));
+            rust_string_free(returned);
+        })
+    });
+
+    group.finish();
+}
+
+fn vecs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vecs");
+
+    // Swift builds a `RustVec` one element at a time, then hands it over to Rust.
+    group.bench_function("Vec<u8> arg", |b| {
+        b.iter(|| unsafe {
+            let vec = vec_u8_new();
+            for _ in 0..VEC_LEN {
+                vec_u8_push(vec, black_box(1));
+            }
+            bench_sum_vec(vec)
+        })
+    });
+    // Swift reads the returned `RustVec` through its buffer pointer, then frees it.
+    group.bench_function("Vec<u8> return", |b| {
+        b.iter(|| unsafe {
+            let vec = bench_make_vec(black_box(VEC_LEN));
+            let len = vec_u8_len(vec);
+            black_box(std::slice::from_raw_parts(vec_u8_as_ptr(vec), len));
+            vec_u8_free(vec);
+        })
+    });
+
+    group.finish();
+}
+
+fn options(c: &mut Criterion) {
+    let mut group = c.benchmark_group("options");
+
+    group.bench_function("Option<u64>", |b| {
+        b.iter(|| unsafe {
+            bench_reflect_option_u64(OptionU64 {
+                val: black_box(123),
+                is_some: black_box(true),
+            })
+        })
+    });
+    group.bench_function("Option<String> Some", |b| {
+        b.iter(|| unsafe {
+            let string = rust_string_new_with_str(rust_str(black_box(SHORT_STRING)));
+            let returned = bench_reflect_option_string(string);
+            rust_string_free(returned);
+        })
+    });
+    group.bench_function("Option<String> None", |b| {
+        b.iter(|| unsafe { bench_reflect_option_string(black_box(std::ptr::null_mut())) })
+    });
+
+    group.finish();
+}
+
+fn opaque_types(c: &mut Criterion) {
+    let mut group = c.benchmark_group("opaque types");
+
+    group.bench_function("init and free", |b| {
+        b.iter(|| unsafe {
+            let counter = bench_counter_new();
+            bench_counter_free(black_box(counter));
+        })
+    });
+
+    let counter = unsafe { bench_counter_new() };
+    group.bench_function("&mut self method", |b| {
+        b.iter(|| unsafe { bench_counter_increment(black_box(counter)) })
+    });
+    group.bench_function("&self method", |b| {
+        b.iter(|| unsafe { bench_counter_value(black_box(counter)) })
+    });
+    unsafe { bench_counter_free(counter) };
+
+    group.finish();
+}
+
+criterion_group!(benches, primitives, strings, vecs, options, opaque_types);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+//! Functions that measure the overhead of calling Rust from Swift.
+//!
+//! Each function does as little work as possible, so that the time that it takes to call it is
+//! dominated by the generated FFI glue code.
+//! They're called from the criterion benchmarks in `benches/ffi_overhead.rs`, which go through
+//! the same exported functions that the generated Swift code calls, and from the Swift harness in
+//! `swift/main.swift`.
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn bench_noop();
+        fn bench_reflect_u64(value: u64) -> u64;
+        fn bench_reflect_f64(value: f64) -> f64;
+        fn bench_reflect_bool(value: bool) -> bool;
+
+        fn bench_str_len(string: &str) -> usize;
+        fn bench_reflect_string(string: String) -> String;
+
+        fn bench_make_vec(len: usize) -> Vec<u8>;
+        fn bench_sum_vec(vec: Vec<u8>) -> u64;
+
+        fn bench_reflect_option_u64(value: Option<u64>) -> Option<u64>;
+        fn bench_reflect_option_string(value: Option<String>) -> Option<String>;
+    }
+
+    extern "Rust" {
+        type BenchCounter;
+
+        #[swift_bridge(init)]
+        fn new() -> BenchCounter;
+
+        fn increment(&mut self);
+        fn value(&self) -> u64;
+    }
+}
+
+fn bench_noop() {}
+
+fn bench_reflect_u64(value: u64) -> u64 {
+    value
+}
+
+fn bench_reflect_f64(value: f64) -> f64 {
+    value
+}
+
+fn bench_reflect_bool(value: bool) -> bool {
+    value
+}
+
+fn bench_str_len(string: &str) -> usize {
+    string.len()
+}
+
+fn bench_reflect_string(string: String) -> String {
+    string
+}
+
+fn bench_make_vec(len: usize) -> Vec<u8> {
+    vec![1; len]
+}
+
+fn bench_sum_vec(vec: Vec<u8>) -> u64 {
+    vec.iter().map(|byte| *byte as u64).sum()
+}
+
+fn bench_reflect_option_u64(value: Option<u64>) -> Option<u64> {
+    value
+}
+
+fn bench_reflect_option_string(value: Option<String>) -> Option<String> {
+    value
+}
+
+/// An opaque Rust type, for measuring the overhead of creating, calling methods on and freeing
+/// Swift classes that wrap Rust values.
+pub struct BenchCounter {
+    count: u64,
+}
+
+impl BenchCounter {
+    fn new() -> Self {
+        BenchCounter { count: 0 }
+    }
+
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    fn value(&self) -> u64 {
+        self.count
+    }
+}
@@ -0,0 +1,7 @@
+#ifndef BridgingHeader_h
+#define BridgingHeader_h
+
+#include "../generated/SwiftBridgeCore.h"
+#include "../generated/ffi-overhead/ffi-overhead.h"
+
+#endif
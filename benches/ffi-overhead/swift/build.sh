@@ -0,0 +1,18 @@
+#!/bin/bash
+
+# Builds the Swift benchmark harness against an optimized build of the Rust library.
+# Run it with `./swift/bench`.
+
+set -e
+
+THISDIR=$(dirname $0)
+cd $THISDIR
+
+cargo build --release -p ffi-overhead
+
+swiftc -O \
+  -L ../../../target/release \
+  -lffi_overhead \
+  -import-objc-header bridging-header.h \
+  -o bench \
+  main.swift ../generated/SwiftBridgeCore.swift ../generated/ffi-overhead/ffi-overhead.swift
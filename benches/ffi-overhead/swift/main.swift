@@ -0,0 +1,72 @@
+import Foundation
+
+// Measures the time that it takes Swift to call the Rust functions in `src/lib.rs`, including the
+// generated Swift code's conversions.
+//
+// ./build.sh && ./bench
+
+let iterations = 1_000_000
+let vecLen = 1024
+
+/// Keeps the optimizer from removing the calls that we're measuring.
+@inline(never)
+func blackHole<T>(_ value: T) {}
+
+/// Prints the average time that one call to `body` takes.
+func bench(_ name: String, iterations: Int = iterations, _ body: () -> Void) {
+    // Warm up.
+    for _ in 0..<(iterations / 10) {
+        body()
+    }
+
+    let start = DispatchTime.now().uptimeNanoseconds
+    for _ in 0..<iterations {
+        body()
+    }
+    let elapsed = DispatchTime.now().uptimeNanoseconds - start
+
+    let nanosPerCall = Double(elapsed) / Double(iterations)
+    print("\(name.padding(toLength: 36, withPad: " ", startingAt: 0)) \(String(format: "%10.2f", nanosPerCall)) ns")
+}
+
+print("primitives")
+bench("  noop") { bench_noop() }
+bench("  u64") { blackHole(bench_reflect_u64(123)) }
+bench("  f64") { blackHole(bench_reflect_f64(1.5)) }
+bench("  bool") { blackHole(bench_reflect_bool(true)) }
+
+print("strings")
+bench("  &str arg") { blackHole(bench_str_len("hello world")) }
+bench("  String arg and return") { blackHole(bench_reflect_string("hello world").toString()) }
+
+print("vecs")
+bench("  Vec<u8> arg", iterations: iterations / 100) {
+    let vec = RustVec<UInt8>()
+    for _ in 0..<vecLen {
+        vec.push(value: 1)
+    }
+    blackHole(bench_sum_vec(vec))
+}
+bench("  Vec<u8> return") { blackHole(bench_make_vec(UInt(vecLen)).len()) }
+let returnedVec = bench_make_vec(UInt(vecLen))
+bench("  Vec<u8> read per value", iterations: iterations / 100) {
+    var sum = 0
+    for value in returnedVec {
+        sum += Int(value)
+    }
+    blackHole(sum)
+}
+bench("  Vec<u8> read in bulk", iterations: iterations / 100) {
+    blackHole(returnedVec.withUnsafeBufferPointer { $0.reduce(0) { $0 + Int($1) } })
+}
+
+print("options")
+bench("  Option<u64>") { blackHole(bench_reflect_option_u64(123)) }
+bench("  Option<String> Some") { blackHole(bench_reflect_option_string("hello world")?.toString()) }
+bench("  Option<String> None") { blackHole(bench_reflect_option_string(Optional<String>.none)) }
+
+print("opaque types")
+bench("  init and free") { blackHole(BenchCounter()) }
+let counter = BenchCounter()
+bench("  &mut self method") { counter.increment() }
+bench("  &self method") { blackHole(counter.value()) }
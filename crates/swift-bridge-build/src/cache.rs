@@ -0,0 +1,306 @@
+//! Cache the code generated for each Rust source file, so that files that haven't changed since
+//! the last build don't need to be parsed again.
+
+use crate::collisions::DeclaredItem;
+use crate::{BridgeCrate, GeneratedFromSwiftBridgeModule};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Bump this when the generated code changes without the version of swift-bridge changing, such
+/// as while working on swift-bridge itself, so that entries from older builds aren't used.
+const CODEGEN_VERSION: u32 = 1;
+
+/// The generated code for Rust source files, stored in a directory.
+///
+/// Each source file gets a `{hash}` directory that holds an `{index}_{module_name}.h`, an
+/// `{index}_{module_name}.swift` and an `{index}_{module_name}.names` file for every bridge module
+/// in the source file.
+///
+/// Failing to read or write the cache is not an error, the file just gets parsed again.
+pub(crate) struct GenerationCache {
+    dir: PathBuf,
+    /// The entries that were read or written since the cache was opened.
+    used: HashSet<String>,
+}
+
+impl GenerationCache {
+    pub(crate) fn new(dir: &Path) -> Self {
+        let _ = std::fs::create_dir_all(dir);
+
+        GenerationCache {
+            dir: dir.to_path_buf(),
+            used: HashSet::new(),
+        }
+    }
+
+    /// Get the previously generated code for a file with these contents.
+    pub(crate) fn get(
+        &mut self,
+        file: &str,
+        file_stem: &str,
+        bridge_crate: &BridgeCrate,
+    ) -> Option<Vec<GeneratedFromSwiftBridgeModule>> {
+        let key = cache_key(file, bridge_crate);
+        let entry = std::fs::read_dir(self.dir.join(&key)).ok()?;
+
+        let mut modules = vec![];
+        for module_file in entry {
+            let module_file = module_file.ok()?.path();
+            if module_file.extension()? != "h" {
+                continue;
+            }
+
+            let name = module_file.file_stem()?.to_str()?;
+            let (index, module_name) = name.split_once('_')?;
+            let index: usize = index.parse().ok()?;
+
+            modules.push((
+                index,
+                GeneratedFromSwiftBridgeModule {
+                    file_stem: file_stem.to_string(),
+                    module_name: module_name.to_string(),
+                    c_header: std::fs::read_to_string(&module_file).ok()?,
+                    swift: std::fs::read_to_string(module_file.with_extension("swift")).ok()?,
+                    declared_items: DeclaredItem::from_lines(
+                        &std::fs::read_to_string(module_file.with_extension("names")).ok()?,
+                    )?,
+                },
+            ));
+        }
+        modules.sort_by_key(|(index, _)| *index);
+
+        self.used.insert(key);
+        Some(modules.into_iter().map(|(_, module)| module).collect())
+    }
+
+    /// Store the code generated for a file with these contents.
+    pub(crate) fn insert(
+        &mut self,
+        file: &str,
+        bridge_crate: &BridgeCrate,
+        generated: &[GeneratedFromSwiftBridgeModule],
+    ) {
+        let key = cache_key(file, bridge_crate);
+
+        // Write to a temporary directory first so that an interrupted build never leaves behind
+        // an entry that is missing some of its modules.
+        let tmp = self.dir.join(format!("{}.tmp", key));
+        let _ = std::fs::remove_dir_all(&tmp);
+        let written = std::fs::create_dir_all(&tmp).and_then(|_| {
+            for (index, module) in generated.iter().enumerate() {
+                let name = format!("{}_{}", index, module.module_name);
+                std::fs::write(tmp.join(format!("{}.h", name)), &module.c_header)?;
+                std::fs::write(tmp.join(format!("{}.swift", name)), &module.swift)?;
+                std::fs::write(
+                    tmp.join(format!("{}.names", name)),
+                    DeclaredItem::to_lines(&module.declared_items),
+                )?;
+            }
+            std::fs::rename(&tmp, self.dir.join(&key))
+        });
+        if written.is_err() {
+            let _ = std::fs::remove_dir_all(&tmp);
+        }
+
+        self.used.insert(key);
+    }
+
+    /// Remove the entries for files that have changed or are no longer being parsed.
+    pub(crate) fn remove_unused(&self) {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let key = name.trim_end_matches(".tmp");
+
+            // Only remove directories that look like our own entries.
+            let is_entry = key.len() == 16 && key.chars().all(|c| c.is_ascii_hexdigit());
+            if is_entry && !self.used.contains(&name) {
+                let _ = std::fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+}
+
+/// Everything that the generated code for a file depends on.
+fn cache_key(file: &str, bridge_crate: &BridgeCrate) -> String {
+    let mut hasher = CacheKeyHasher::new();
+
+    // The generated code changes between versions of swift-bridge.
+    hasher.write_str(env!("CARGO_PKG_VERSION"));
+    hasher.write_bytes(&CODEGEN_VERSION.to_le_bytes());
+    hasher.write_str(file);
+    hasher.write_option_str(bridge_crate.symbol_prefix.as_deref());
+    hasher.write_option_str(bridge_crate.private_type_prefix.as_deref());
+    hasher.write_option_str(bridge_crate.swift_access.as_deref());
+    hasher.write_bytes(&[bridge_crate.library_evolution as u8]);
+
+    match bridge_crate.enabled_features.as_ref() {
+        Some(enabled_features) => hasher.write_strs(enabled_features),
+        None => {
+            let mut feature_env_vars: Vec<String> = std::env::vars()
+                .map(|(name, _)| name)
+                .filter(|name| name.starts_with("CARGO_FEATURE_"))
+                .collect();
+            feature_env_vars.sort();
+            hasher.write_strs(&feature_env_vars);
+        }
+    };
+
+    format!("{:016x}", hasher.finish())
+}
+
+/// A 64 bit FNV-1a hash.
+///
+/// Unlike `DefaultHasher`, whose algorithm can change between Rust versions, this always gives
+/// the same key for the same input, so a newer toolchain still finds the entries.
+struct CacheKeyHasher(u64);
+
+impl CacheKeyHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        CacheKeyHasher(Self::OFFSET_BASIS)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Write the length before the string, so that `("ab", "c")` and `("a", "bc")` hash
+    /// differently.
+    fn write_str(&mut self, string: &str) {
+        self.write_bytes(&(string.len() as u64).to_le_bytes());
+        self.write_bytes(string.as_bytes());
+    }
+
+    fn write_option_str(&mut self, string: Option<&str>) {
+        match string {
+            Some(string) => {
+                self.write_bytes(&[1]);
+                self.write_str(string);
+            }
+            None => self.write_bytes(&[0]),
+        }
+    }
+
+    fn write_strs(&mut self, strings: &[String]) {
+        self.write_bytes(&(strings.len() as u64).to_le_bytes());
+        for string in strings {
+            self.write_str(string);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::bridge_crate;
+
+    fn generated(module_name: &str) -> GeneratedFromSwiftBridgeModule {
+        GeneratedFromSwiftBridgeModule {
+            file_stem: "lib".to_string(),
+            module_name: module_name.to_string(),
+            c_header: format!("// {} header", module_name),
+            swift: format!("// {} swift", module_name),
+            declared_items: vec![DeclaredItem {
+                item: "type `Foo`".to_string(),
+                swift_name: None,
+                symbol: "__swift_bridge__$Foo".to_string(),
+                line: 3,
+                column: 5,
+            }],
+        }
+    }
+
+    /// Verify that we get back the modules that were generated for a file with the same contents,
+    /// in the same order.
+    #[test]
+    fn cache_hit() {
+        let dir = tempfile::tempdir().unwrap();
+        let bridge_crate = bridge_crate();
+
+        let mut cache = GenerationCache::new(dir.path());
+        cache.insert(
+            "mod ffi {}",
+            &bridge_crate,
+            &[generated("a"), generated("b")],
+        );
+
+        let mut cache = GenerationCache::new(dir.path());
+        let modules = cache.get("mod ffi {}", "lib", &bridge_crate).unwrap();
+
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].module_name, "a");
+        assert_eq!(modules[0].c_header, "// a header");
+        assert_eq!(modules[0].swift, "// a swift");
+        assert_eq!(modules[0].declared_items[0].symbol, "__swift_bridge__$Foo");
+        assert_eq!(modules[1].module_name, "b");
+    }
+
+    /// Verify that we don't use an entry once the file or the crate's configuration changes.
+    #[test]
+    fn cache_miss_when_input_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let bridge_crate = bridge_crate();
+
+        let mut cache = GenerationCache::new(dir.path());
+        cache.insert("mod ffi {}", &bridge_crate, &[generated("a")]);
+
+        assert!(cache.get("mod ffi { }", "lib", &bridge_crate).is_none());
+
+        let prefixed = BridgeCrate {
+            symbol_prefix: Some("my_prefix".to_string()),
+            ..bridge_crate
+        };
+        assert!(cache.get("mod ffi {}", "lib", &prefixed).is_none());
+    }
+
+    /// Verify that we remove the entries that weren't read or written since the cache was
+    /// opened, and leave other directories alone.
+    #[test]
+    fn remove_unused_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let bridge_crate = bridge_crate();
+        std::fs::create_dir(dir.path().join("not_an_entry")).unwrap();
+
+        let mut cache = GenerationCache::new(dir.path());
+        cache.insert("mod a {}", &bridge_crate, &[generated("a")]);
+        cache.insert("mod b {}", &bridge_crate, &[generated("b")]);
+
+        let mut cache = GenerationCache::new(dir.path());
+        assert!(cache.get("mod a {}", "lib", &bridge_crate).is_some());
+        cache.remove_unused();
+
+        let mut cache = GenerationCache::new(dir.path());
+        assert!(cache.get("mod a {}", "lib", &bridge_crate).is_some());
+        assert!(cache.get("mod b {}", "lib", &bridge_crate).is_none());
+        assert!(dir.path().join("not_an_entry").exists());
+    }
+
+    /// Verify that the hash matches the published FNV-1a test vectors, so that keys don't change
+    /// between builds.
+    #[test]
+    fn cache_key_hasher_is_fnv_1a() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = CacheKeyHasher::new();
+            hasher.write_bytes(bytes);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(b""), 0xcbf29ce484222325);
+        assert_eq!(hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(hash(b"foobar"), 0x85944171f73967e8);
+    }
+}
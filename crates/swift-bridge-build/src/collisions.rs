@@ -0,0 +1,322 @@
+//! Detect bridge modules in the same crate that declare colliding types or functions.
+//!
+//! Each `#[swift_bridge::bridge]` macro only sees its own module, so two modules that both
+//! declare `type Config` would otherwise only fail when the generated Swift is compiled or the
+//! library is linked, with an error about duplicate symbols.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use swift_bridge_ir::DeclaredName;
+
+/// A type or free function that a bridge module declares.
+#[derive(Debug, Clone)]
+pub(crate) struct DeclaredItem {
+    /// Such as "type `Config`".
+    pub item: String,
+    pub swift_name: Option<String>,
+    pub symbol: String,
+    /// The 1-based line that the item was declared on.
+    pub line: usize,
+    /// The 1-based column that the item was declared on.
+    pub column: usize,
+}
+
+impl From<DeclaredName> for DeclaredItem {
+    fn from(name: DeclaredName) -> Self {
+        let start = name.span.start();
+
+        DeclaredItem {
+            item: name.item,
+            swift_name: name.swift_name,
+            symbol: name.symbol,
+            line: start.line,
+            column: start.column + 1,
+        }
+    }
+}
+
+impl DeclaredItem {
+    /// Whether the item is a type, as opposed to a free function.
+    pub(crate) fn is_type(&self) -> bool {
+        !self.item.starts_with("function ")
+    }
+
+    /// Whether the C header uses this type, such as a function that takes it as an argument or
+    /// the `__swift_bridge__$Option$SomeStruct` that holds it.
+    pub(crate) fn is_used_by_c_header(&self, c_header: &str) -> bool {
+        let option_symbol = self
+            .symbol
+            .rsplit_once('$')
+            .map(|(prefix, name)| format!("{}$Option${}", prefix, name));
+        let tag_symbol = format!("{}Tag", self.symbol);
+        let member_prefix = format!("{}$", self.symbol);
+
+        c_header
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+            .any(|identifier| {
+                identifier == self.symbol
+                    || identifier == tag_symbol
+                    || identifier.starts_with(&member_prefix)
+                    || Some(identifier) == option_symbol.as_deref()
+            })
+    }
+
+    /// Write the items to a string that can be read back using [`DeclaredItem::from_lines`], one
+    /// item per line.
+    pub(crate) fn to_lines(items: &[DeclaredItem]) -> String {
+        items
+            .iter()
+            .map(|item| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\n",
+                    item.line,
+                    item.column,
+                    item.symbol,
+                    item.swift_name.as_deref().unwrap_or(""),
+                    item.item
+                )
+            })
+            .collect()
+    }
+
+    /// Read the items that were written using [`DeclaredItem::to_lines`].
+    pub(crate) fn from_lines(lines: &str) -> Option<Vec<DeclaredItem>> {
+        lines
+            .lines()
+            .map(|line| {
+                let mut parts = line.splitn(5, '\t');
+
+                Some(DeclaredItem {
+                    line: parts.next()?.parse().ok()?,
+                    column: parts.next()?.parse().ok()?,
+                    symbol: parts.next()?.to_string(),
+                    swift_name: Some(parts.next()?.to_string()).filter(|name| !name.is_empty()),
+                    item: parts.next()?.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Where an item was declared.
+struct Declaration {
+    file: PathBuf,
+    module_name: String,
+    item: DeclaredItem,
+}
+
+impl Declaration {
+    fn location(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.file.display(),
+            self.item.line,
+            self.item.column
+        )
+    }
+}
+
+/// The Swift names and symbols that the crate's bridge modules have declared so far.
+#[derive(Default)]
+pub(crate) struct DeclaredNames {
+    swift_names: HashMap<String, Declaration>,
+    symbols: HashMap<String, Declaration>,
+}
+
+impl DeclaredNames {
+    /// Add a module's items, returning an error that names both declarations if one of them
+    /// collides with an item that was already added.
+    pub(crate) fn insert(
+        &mut self,
+        file: &Path,
+        module_name: &str,
+        items: &[DeclaredItem],
+    ) -> Result<(), String> {
+        for item in items {
+            let declaration = || Declaration {
+                file: file.to_path_buf(),
+                module_name: module_name.to_string(),
+                item: item.clone(),
+            };
+
+            if let Some(swift_name) = item.swift_name.as_ref() {
+                if let Some(existing) = self.swift_names.get(swift_name) {
+                    return Err(collision_error(
+                        existing,
+                        &declaration(),
+                        &format!("the Swift name `{}`", swift_name),
+                        r#"Give one of them a different `#[swift_bridge(swift_name = "...")]`"#,
+                    ));
+                }
+                self.swift_names.insert(swift_name.clone(), declaration());
+            }
+
+            if let Some(existing) = self.symbols.get(&item.symbol) {
+                return Err(collision_error(
+                    existing,
+                    &declaration(),
+                    &format!("the symbol `{}`", item.symbol),
+                    "Rename one of them",
+                ));
+            }
+            self.symbols.insert(item.symbol.clone(), declaration());
+        }
+
+        Ok(())
+    }
+}
+
+fn collision_error(existing: &Declaration, new: &Declaration, name: &str, fix: &str) -> String {
+    format!(
+        r#"
+{new_location}: {new_item} in bridge module `{new_module}` collides with {existing_item} in bridge module `{existing_module}`
+{existing_location}: {existing_item} is declared here
+Both use {name}. {fix}, or use `#[swift_bridge(already_declared)]` if they are the same type.
+"#,
+        new_location = new.location(),
+        new_item = new.item.item,
+        new_module = new.module_name,
+        existing_location = existing.location(),
+        existing_item = existing.item.item,
+        existing_module = existing.module_name,
+        name = name,
+        fix = fix,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declared_item(item: &str, swift_name: Option<&str>, symbol: &str) -> DeclaredItem {
+        DeclaredItem {
+            item: item.to_string(),
+            swift_name: swift_name.map(|name| name.to_string()),
+            symbol: symbol.to_string(),
+            line: 3,
+            column: 5,
+        }
+    }
+
+    /// Verify that we report two items with the same Swift name, naming both of their
+    /// declarations.
+    #[test]
+    fn swift_name_collision() {
+        let mut names = DeclaredNames::default();
+        names
+            .insert(
+                Path::new("src/a.rs"),
+                "ffi",
+                &[declared_item(
+                    "type `Config`",
+                    Some("Config"),
+                    "__swift_bridge__$Config",
+                )],
+            )
+            .unwrap();
+
+        let error = names
+            .insert(
+                Path::new("src/b.rs"),
+                "other_ffi",
+                &[declared_item(
+                    "struct `Config`",
+                    Some("Config"),
+                    "__other__$Config",
+                )],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            r#"
+src/b.rs:3:5: struct `Config` in bridge module `other_ffi` collides with type `Config` in bridge module `ffi`
+src/a.rs:3:5: type `Config` is declared here
+Both use the Swift name `Config`. Give one of them a different `#[swift_bridge(swift_name = "...")]`, or use `#[swift_bridge(already_declared)]` if they are the same type.
+"#
+        );
+    }
+
+    /// Verify that we report two items with the same symbol, even if only one of them has a
+    /// Swift name, and that items that don't collide are fine.
+    #[test]
+    fn symbol_collision() {
+        let mut names = DeclaredNames::default();
+        names
+            .insert(
+                Path::new("src/a.rs"),
+                "ffi",
+                &[
+                    declared_item("function `run`", Some("run"), "__swift_bridge__$run"),
+                    declared_item("function `stop`", None, "__swift_bridge__$stop"),
+                ],
+            )
+            .unwrap();
+
+        let error = names
+            .insert(
+                Path::new("src/b.rs"),
+                "other_ffi",
+                &[declared_item(
+                    "function `run`",
+                    None,
+                    "__swift_bridge__$run",
+                )],
+            )
+            .unwrap_err();
+
+        assert!(error
+            .contains("Both use the symbol `__swift_bridge__$run`. Rename one of them, or use"));
+    }
+
+    /// Verify that items read back from their lines are the items that were written.
+    #[test]
+    fn items_round_trip_through_lines() {
+        let items = vec![
+            declared_item("type `Config`", Some("MyConfig"), "__swift_bridge__$Config"),
+            declared_item("function `run`", None, "__swift_bridge__$run"),
+        ];
+
+        let read = DeclaredItem::from_lines(&DeclaredItem::to_lines(&items)).unwrap();
+
+        assert_eq!(read.len(), 2);
+        for (read, written) in read.iter().zip(&items) {
+            assert_eq!(read.item, written.item);
+            assert_eq!(read.swift_name, written.swift_name);
+            assert_eq!(read.symbol, written.symbol);
+            assert_eq!((read.line, read.column), (written.line, written.column));
+        }
+        assert!(DeclaredItem::from_lines("not an item").is_none());
+    }
+
+    /// Verify that we find the uses of a type in a C header, including the types that are named
+    /// after it, but not other types that start with its name.
+    #[test]
+    fn type_used_by_c_header() {
+        let item = declared_item(
+            "struct `SomeStruct`",
+            Some("SomeStruct"),
+            "__swift_bridge__$SomeStruct",
+        );
+
+        for c_header in [
+            "void f(struct __swift_bridge__$SomeStruct arg);",
+            "void f(struct __swift_bridge__$Option$SomeStruct arg);",
+            "__swift_bridge__$SomeStructTag f(void);",
+            "void f(union __swift_bridge__$SomeStruct$Fields arg);",
+        ] {
+            assert!(item.is_used_by_c_header(c_header), "{}", c_header);
+        }
+
+        for c_header in [
+            "void f(struct __swift_bridge__$SomeStructTwo arg);",
+            "void f(struct __swift_bridge__$Option$SomeStructTwo arg);",
+            "void __swift_bridge__$Other$SomeStruct(void);",
+        ] {
+            assert!(!item.is_used_by_c_header(c_header), "{}", c_header);
+        }
+
+        assert!(item.is_type());
+        assert!(!declared_item("function `run`", None, "__swift_bridge__$run").is_type());
+    }
+}
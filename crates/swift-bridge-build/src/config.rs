@@ -0,0 +1,205 @@
+//! Generate code as configured by a crate's `swift-bridge.toml`.
+
+use crate::discover::discover_bridge_files_with_features;
+use crate::{
+    try_parse_bridges_with_crate, BridgeCrate, DepFileFormat, SwiftFormatting, SwiftcCheck,
+};
+use std::path::{Path, PathBuf};
+use swift_bridge_ir::{
+    ConfigFormatting, ConfigOutput, ConfigSwiftcCheck, SwiftBridgeConfig, CONFIG_FILE_NAME,
+};
+
+/// Generate the Swift and C code for the crate's bridge modules as configured by the
+/// `swift-bridge.toml` in the crate's directory.
+///
+/// The bridge modules are found using [`crate::discover_bridge_files`].
+///
+/// ```no_run
+/// // build.rs
+///
+/// fn main() {
+///     println!("cargo:rerun-if-changed=src");
+///     println!("cargo:rerun-if-changed=swift-bridge.toml");
+///
+///     swift_bridge_build::generate_from_config(env!("CARGO_MANIFEST_DIR"));
+/// }
+/// ```
+pub fn generate_from_config(crate_dir: impl AsRef<Path>) {
+    let crate_dir = crate_dir.as_ref();
+
+    let config = SwiftBridgeConfig::load(crate_dir)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .unwrap_or_else(|| panic!("{:?} does not have a {}", crate_dir, CONFIG_FILE_NAME));
+
+    generate_with_config(crate_dir, &config);
+}
+
+/// Generate the Swift and C code for the crate's bridge modules as configured by the given
+/// config, such as a crate's `swift-bridge.toml` with some options overridden.
+pub fn generate_with_config(crate_dir: impl AsRef<Path>, config: &SwiftBridgeConfig) {
+    try_generate_with_config(crate_dir.as_ref(), config).unwrap_or_else(|e| panic!("{}", e));
+}
+
+/// Generate the code, returning the directory that it was written to.
+pub(crate) fn try_generate_with_config(
+    crate_dir: &Path,
+    config: &SwiftBridgeConfig,
+) -> Result<PathBuf, String> {
+    let out_dir = config
+        .out_dir
+        .as_ref()
+        .map(|out_dir| crate_dir.join(out_dir))
+        .ok_or_else(|| format!("{} must set out_dir", CONFIG_FILE_NAME))?;
+
+    let crate_name = crate::read_crate_name(crate_dir);
+    let bridge_crate = BridgeCrate {
+        enabled_features: config.features.clone(),
+        symbol_prefix: config.symbol_prefix.clone(),
+        private_type_prefix: config.private_type_prefix.clone(),
+        swift_access: config.swift_access.clone(),
+        library_evolution: config.library_evolution,
+    };
+
+    let discovered =
+        discover_bridge_files_with_features(crate_dir, &config.exclude, config.features.as_deref());
+    let mut generated =
+        try_parse_bridges_with_crate(&discovered.bridge_files, &bridge_crate, None)?;
+
+    generated = generated.format_swift(match config.format {
+        ConfigFormatting::None => SwiftFormatting::None,
+        ConfigFormatting::LineBreaks => SwiftFormatting::LineBreaks,
+        ConfigFormatting::SwiftFormat => SwiftFormatting::SwiftFormat(
+            config
+                .swift_format_path
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("swift-format")),
+        ),
+    });
+    if let Some(module_name) = config.module_map.as_ref() {
+        generated = generated.module_map(module_name);
+    }
+    if config.foundation_free {
+        generated = generated.foundation_free();
+    }
+    let swiftc = config
+        .swiftc_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("swiftc"));
+    generated = generated.check_with_swiftc(match config.swiftc_check {
+        ConfigSwiftcCheck::None => SwiftcCheck::None,
+        ConfigSwiftcCheck::Parse => SwiftcCheck::Parse(swiftc),
+        ConfigSwiftcCheck::Typecheck => SwiftcCheck::Typecheck(swiftc),
+    });
+
+    match config.output {
+        ConfigOutput::Concatenated => generated.write_all_concatenated(&out_dir, &crate_name),
+        ConfigOutput::PerModule => generated.write_per_module(&out_dir, &crate_name),
+    };
+
+    if let Some(dep_file) = config.dep_file.as_ref() {
+        let dep_file = crate_dir.join(dep_file);
+
+        // Files without bridge modules can still declare new modules, so depend on all of them.
+        generated.source_files = discovered.module_files;
+        let config_file = crate_dir.join(CONFIG_FILE_NAME);
+        if config_file.exists() {
+            generated.source_files.push(config_file);
+        }
+
+        generated.write_dep_file(&dep_file, &out_dir, DepFileFormat::from_path(&dep_file));
+    }
+
+    Ok(out_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{dir_with_files, BRIDGE_MODULE};
+
+    /// A crate with a bridge module in `src/ffi.rs`.
+    fn crate_dir(swift_bridge_toml: &str) -> tempfile::TempDir {
+        dir_with_files(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"my-crate\"\nversion = \"0.1.0\"\n",
+            ),
+            ("src/lib.rs", "mod ffi;"),
+            ("src/ffi.rs", BRIDGE_MODULE),
+            (CONFIG_FILE_NAME, swift_bridge_toml),
+        ])
+    }
+
+    /// Verify that we generate the code for the bridge modules that are found in the crate, as
+    /// configured, and write the dep-file.
+    #[test]
+    fn generate_as_configured() {
+        let swift_bridge_toml = r#"
+out_dir = "Generated"
+output = "per_module"
+symbol_prefix = "__my_crate__"
+module_map = "RustBridge"
+dep_file = "target/swift-bridge.d"
+"#;
+        let dir = crate_dir(swift_bridge_toml);
+        let config = SwiftBridgeConfig::parse(swift_bridge_toml).unwrap();
+
+        let out_dir = try_generate_with_config(dir.path(), &config).unwrap();
+
+        assert_eq!(out_dir, dir.path().join("Generated"));
+        let header = std::fs::read_to_string(out_dir.join("my-crate/ffi_ffi.h")).unwrap();
+        assert!(header.contains("void __my_crate__$some_function(void);"));
+        assert!(out_dir.join("my-crate/ffi_ffi.swift").exists());
+        assert!(out_dir.join("module.modulemap").exists());
+
+        let dep_file = std::fs::read_to_string(dir.path().join("target/swift-bridge.d")).unwrap();
+        for file in ["src/lib.rs", "src/ffi.rs", CONFIG_FILE_NAME] {
+            let file = dir.path().join(file);
+            assert!(
+                dep_file.contains(&format!("\n{}:\n", file.display())),
+                "{}",
+                dep_file
+            );
+        }
+    }
+
+    /// Verify that the config must say where to write the code.
+    #[test]
+    fn out_dir_is_required() {
+        let dir = crate_dir("");
+
+        assert_eq!(
+            try_generate_with_config(dir.path(), &SwiftBridgeConfig::default()),
+            Err(format!("{} must set out_dir", CONFIG_FILE_NAME))
+        );
+    }
+
+    /// Verify that the code generated from the config is up to date until a bridge module
+    /// changes.
+    #[test]
+    fn verify_code_generated_from_config() {
+        let swift_bridge_toml = "out_dir = \"Generated\"\n";
+        let dir = crate_dir(swift_bridge_toml);
+        let config = SwiftBridgeConfig::parse(swift_bridge_toml).unwrap();
+
+        generate_with_config(dir.path(), &config);
+        assert_eq!(crate::verify_with_config(dir.path(), &config), vec![]);
+
+        std::fs::write(
+            dir.path().join("src/ffi.rs"),
+            BRIDGE_MODULE.replace("some_function", "renamed_function"),
+        )
+        .unwrap();
+        let diffs = crate::verify_with_config(dir.path(), &config);
+        assert_eq!(
+            diffs
+                .iter()
+                .map(|diff| diff.path.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("my-crate/my-crate.h"),
+                PathBuf::from("my-crate/my-crate.swift"),
+            ]
+        );
+    }
+}
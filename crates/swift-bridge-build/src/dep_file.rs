@@ -0,0 +1,270 @@
+//! Write the Rust source files that the generated code came from to a dep-file, so that build
+//! systems other than Cargo know when to generate the code again.
+
+use crate::{write_if_changed, GeneratedCode};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// The format of a dep-file written by [`GeneratedCode::write_dep_file`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DepFileFormat {
+    /// A Makefile rule, like the ones that `gcc -MD -MP` writes.
+    ///
+    /// ```make
+    /// generated: \
+    ///   src/lib.rs \
+    ///   src/ffi.rs
+    ///
+    /// src/lib.rs:
+    ///
+    /// src/ffi.rs:
+    /// ```
+    Makefile,
+    /// A JSON object with the target, a fingerprint of the generated code and the sources.
+    ///
+    /// ```json
+    /// {
+    ///   "target": "generated",
+    ///   "fingerprint": "3f9a0c1e5b7d2468",
+    ///   "sources": [
+    ///     "src/lib.rs",
+    ///     "src/ffi.rs"
+    ///   ]
+    /// }
+    /// ```
+    Json,
+}
+
+impl DepFileFormat {
+    /// [`DepFileFormat::Json`] for `.json` files, otherwise [`DepFileFormat::Makefile`].
+    pub fn from_path(dep_file: impl AsRef<Path>) -> Self {
+        match dep_file.as_ref().extension() {
+            Some(extension) if extension == "json" => DepFileFormat::Json,
+            _ => DepFileFormat::Makefile,
+        }
+    }
+}
+
+impl GeneratedCode {
+    /// The Rust source files that the code was generated from.
+    pub fn source_files(&self) -> &[PathBuf] {
+        &self.source_files
+    }
+
+    /// A hash of the generated code, which only changes when the code that gets written changes.
+    ///
+    /// It is the same across runs with the same version of `swift-bridge-build`.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        for gen in &self.generated {
+            gen.module_name.hash(&mut hasher);
+            gen.c_header.hash(&mut hasher);
+            gen.swift.hash(&mut hasher);
+        }
+        self.swift_format.hash(&mut hasher);
+        self.module_map.hash(&mut hasher);
+        self.foundation_free.hash(&mut hasher);
+        self.library_evolution.hash(&mut hasher);
+        self.private_type_prefix.hash(&mut hasher);
+
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Write a dep-file that lists the Rust source files that the code was generated from, so
+    /// that build systems such as Bazel, Buck or Ninja only generate the code again when one of
+    /// them changes.
+    ///
+    /// The `target` is what the build system builds from the sources, usually the directory that
+    /// the code gets written to. The dep-file is only rewritten when its contents change.
+    ///
+    /// ```no_run
+    /// use swift_bridge_build::DepFileFormat;
+    ///
+    /// let generated = swift_bridge_build::parse_bridges(vec!["src/lib.rs", "src/ffi.rs"]);
+    /// generated.write_all_concatenated("./generated", env!("CARGO_PKG_NAME"));
+    /// generated.write_dep_file("./generated.d", "./generated", DepFileFormat::Makefile);
+    /// ```
+    pub fn write_dep_file(
+        &self,
+        dep_file: impl AsRef<Path>,
+        target: impl AsRef<Path>,
+        format: DepFileFormat,
+    ) {
+        let dep_file = dep_file.as_ref();
+        let target = target.as_ref();
+
+        let contents = match format {
+            DepFileFormat::Makefile => self.makefile_dep_file(target),
+            DepFileFormat::Json => self.json_dep_file(target),
+        };
+
+        if let Some(parent) = dep_file.parent() {
+            std::fs::create_dir_all(parent).expect("Couldn't create dep-file directory");
+        }
+        write_if_changed(dep_file, contents)
+            .unwrap_or_else(|e| panic!("Failed to write {:?}: {}", dep_file, e));
+    }
+
+    fn makefile_dep_file(&self, target: &Path) -> String {
+        let mut dep_file = format!("{}:", escape_makefile_path(target));
+        for source in &self.source_files {
+            dep_file += &format!(" \\\n  {}", escape_makefile_path(source));
+        }
+        dep_file += "\n";
+
+        // An empty rule for each source means that deleting one doesn't break the build.
+        for source in &self.source_files {
+            dep_file += &format!("\n{}:\n", escape_makefile_path(source));
+        }
+
+        dep_file
+    }
+
+    fn json_dep_file(&self, target: &Path) -> String {
+        let sources: Vec<String> = self
+            .source_files
+            .iter()
+            .map(|source| format!("    {}", json_string(source)))
+            .collect();
+
+        format!(
+            r#"{{
+  "target": {target},
+  "fingerprint": "{fingerprint}",
+  "sources": [
+{sources}
+  ]
+}}
+"#,
+            target = json_string(target),
+            fingerprint = self.fingerprint(),
+            sources = sources.join(",\n")
+        )
+    }
+}
+
+fn escape_makefile_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace(' ', "\\ ")
+        .replace('#', "\\#")
+        .replace('$', "$$")
+}
+
+fn json_string(path: &Path) -> String {
+    let mut json = "\"".to_string();
+    for c in path.to_string_lossy().chars() {
+        match c {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            c if (c as u32) < 0x20 => json += &format!("\\u{:04x}", c as u32),
+            c => json.push(c),
+        }
+    }
+    json += "\"";
+
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generated_code, BRIDGE_MODULE};
+
+    fn generated_from(source_files: &[&str]) -> GeneratedCode {
+        let mut generated = generated_code(BRIDGE_MODULE);
+        generated.source_files = source_files.iter().map(PathBuf::from).collect();
+
+        generated
+    }
+
+    /// Verify that we write a Makefile rule with an empty rule for each source, escaping the
+    /// characters that Make treats specially.
+    #[test]
+    fn makefile_dep_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let dep_file = dir.path().join("deps/generated.d");
+
+        generated_from(&["src/lib.rs", "src/my ffi#1$.rs"]).write_dep_file(
+            &dep_file,
+            "generated",
+            DepFileFormat::Makefile,
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(&dep_file).unwrap(),
+            r#"generated: \
+  src/lib.rs \
+  src/my\ ffi\#1$$.rs
+
+src/lib.rs:
+
+src/my\ ffi\#1$$.rs:
+"#
+        );
+    }
+
+    /// Verify that we write a JSON object with the fingerprint, escaping the paths.
+    #[test]
+    fn json_dep_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let dep_file = dir.path().join("generated.json");
+
+        let generated = generated_from(&["src/lib.rs", "src/\"quoted\"\\.rs"]);
+        generated.write_dep_file(&dep_file, "generated", DepFileFormat::Json);
+
+        assert_eq!(
+            std::fs::read_to_string(&dep_file).unwrap(),
+            format!(
+                r#"{{
+  "target": "generated",
+  "fingerprint": "{}",
+  "sources": [
+    "src/lib.rs",
+    "src/\"quoted\"\\.rs"
+  ]
+}}
+"#,
+                generated.fingerprint()
+            )
+        );
+    }
+
+    /// Verify that the format depends on the dep-file's extension.
+    #[test]
+    fn dep_file_format_from_path() {
+        assert_eq!(
+            DepFileFormat::from_path("target/deps.json"),
+            DepFileFormat::Json
+        );
+        assert_eq!(
+            DepFileFormat::from_path("target/deps.d"),
+            DepFileFormat::Makefile
+        );
+        assert_eq!(
+            DepFileFormat::from_path("target/deps"),
+            DepFileFormat::Makefile
+        );
+    }
+
+    /// Verify that the fingerprint only changes when the code that gets written changes.
+    #[test]
+    fn fingerprint_changes_with_generated_code() {
+        let fingerprint = generated_code(BRIDGE_MODULE).fingerprint();
+
+        assert_eq!(generated_from(&["src/other.rs"]).fingerprint(), fingerprint);
+        assert_ne!(
+            generated_code(&BRIDGE_MODULE.replace("some_function", "other_function")).fingerprint(),
+            fingerprint
+        );
+        assert_ne!(
+            generated_code(BRIDGE_MODULE)
+                .module_map("RustBridge")
+                .fingerprint(),
+            fingerprint
+        );
+    }
+}
@@ -0,0 +1,386 @@
+//! Find the source files that contain `#[swift_bridge::bridge]` modules by following a crate's
+//! `mod` declarations.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use syn::__private::ToTokens;
+use syn::{Attribute, Item, ItemMod, Lit, Meta, NestedMeta};
+
+/// Find the files in the crate that contain a `#[swift_bridge::bridge]` module, so that they
+/// don't need to be listed one by one.
+///
+/// Starting from `src/lib.rs`, or `src/main.rs` if the crate doesn't have a `lib.rs`, every
+/// `mod my_module;` declaration is followed to its file the same way that `rustc` does, including
+/// `#[path = "..."]` attributes and modules declared inside of inline modules.
+///
+/// Modules behind a `#[cfg(...)]` that is disabled are skipped. The `cfg` is evaluated using the
+/// `CARGO_FEATURE_*` and `CARGO_CFG_*` environment variables that Cargo sets when running build
+/// scripts. Outside of a build script every feature is disabled and every other `cfg`, such as
+/// `target_os = "ios"`, is treated as enabled, since the target isn't known.
+///
+/// Files in `exclude`, such as `src/generated.rs`, are not read, so neither they nor the modules
+/// that they declare are returned. The paths are relative to the crate's directory.
+///
+/// ```no_run
+/// // build.rs
+///
+/// let bridges = swift_bridge_build::discover_bridge_files(".", &[]);
+///
+/// swift_bridge_build::parse_bridges(bridges)
+///     .write_all_concatenated("./generated", env!("CARGO_PKG_NAME"));
+/// ```
+pub fn discover_bridge_files(crate_dir: impl AsRef<Path>, exclude: &[PathBuf]) -> Vec<PathBuf> {
+    discover_bridge_files_with_features(crate_dir.as_ref(), exclude, None).bridge_files
+}
+
+/// The files found by [`discover_bridge_files_with_features`].
+pub(crate) struct DiscoveredFiles {
+    /// The files that contain bridge modules.
+    pub(crate) bridge_files: Vec<PathBuf>,
+    /// Every module file that was read, in the order that they were read. Changing any of them
+    /// can change which bridge files are found.
+    pub(crate) module_files: Vec<PathBuf>,
+}
+
+/// Find the bridge files, evaluating `#[cfg(feature = "...")]` using the given features instead of
+/// Cargo's `CARGO_FEATURE_*` environment variables.
+pub(crate) fn discover_bridge_files_with_features(
+    crate_dir: &Path,
+    exclude: &[PathBuf],
+    enabled_features: Option<&[String]>,
+) -> DiscoveredFiles {
+    let src = crate_dir.join("src");
+
+    let crate_root = if src.join("lib.rs").exists() {
+        src.join("lib.rs")
+    } else {
+        src.join("main.rs")
+    };
+
+    let mut discovery = Discovery {
+        exclude: exclude.iter().map(|path| crate_dir.join(path)).collect(),
+        enabled_features: enabled_features.map(|features| features.to_vec()),
+        // Cargo sets `CARGO_CFG_TARGET_OS` for every build script.
+        in_build_script: std::env::var("CARGO_CFG_TARGET_OS").is_ok(),
+        visited: HashSet::new(),
+        module_files: vec![],
+        bridge_files: vec![],
+    };
+    discovery.visit_file(&crate_root, &src);
+
+    DiscoveredFiles {
+        bridge_files: discovery.bridge_files,
+        module_files: discovery.module_files,
+    }
+}
+
+struct Discovery {
+    exclude: Vec<PathBuf>,
+    enabled_features: Option<Vec<String>>,
+    in_build_script: bool,
+    visited: HashSet<PathBuf>,
+    module_files: Vec<PathBuf>,
+    bridge_files: Vec<PathBuf>,
+}
+
+impl Discovery {
+    /// Visit a module's file.
+    ///
+    /// `module_dir` is the directory that the module's child modules are in, such as `src` for
+    /// `src/lib.rs` or `src/foo` for `src/foo.rs`.
+    fn visit_file(&mut self, file: &Path, module_dir: &Path) {
+        if self.exclude.iter().any(|excluded| excluded == file) {
+            return;
+        }
+        if !self.visited.insert(file.to_path_buf()) {
+            return;
+        }
+        self.module_files.push(file.to_path_buf());
+
+        let contents = std::fs::read_to_string(file)
+            .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", file, e));
+        let parsed: syn::File = syn::parse_str(&contents).unwrap_or_else(|e| {
+            panic!(
+                "Failed to parse {:?}: {}. Add it to the excluded files to skip it.",
+                file, e
+            )
+        });
+
+        if parsed.items.iter().any(is_bridge_module) {
+            self.bridge_files.push(file.to_path_buf());
+        }
+
+        let file_dir = file.parent().unwrap();
+        self.visit_items(&parsed.items, module_dir, file_dir);
+    }
+
+    /// Visit the modules declared in a file or in an inline module.
+    ///
+    /// `path_attr_dir` is the directory that `#[path = "..."]` attributes are relative to.
+    fn visit_items(&mut self, items: &[Item], module_dir: &Path, path_attr_dir: &Path) {
+        for item in items {
+            let module = match item {
+                Item::Mod(module) => module,
+                _ => continue,
+            };
+            if !self.cfg_enabled(&module.attrs) {
+                continue;
+            }
+
+            let name = module.ident.to_string();
+
+            match (&module.content, path_attr(module)) {
+                (Some((_, items)), path) => {
+                    let dir = match path {
+                        Some(path) => path_attr_dir.join(path),
+                        None => module_dir.join(&name),
+                    };
+                    self.visit_items(items, &dir, &dir);
+                }
+                (None, Some(path)) => {
+                    let file = path_attr_dir.join(path);
+                    let dir = file.parent().unwrap().to_path_buf();
+                    self.visit_file(&file, &dir);
+                }
+                (None, None) => {
+                    let file = module_dir.join(format!("{}.rs", name));
+                    if file.exists() {
+                        self.visit_file(&file, &module_dir.join(&name));
+                    } else {
+                        let dir = module_dir.join(&name);
+                        self.visit_file(&dir.join("mod.rs"), &dir);
+                    }
+                }
+            };
+        }
+    }
+}
+
+fn is_bridge_module(item: &Item) -> bool {
+    match item {
+        Item::Mod(module) => module.attrs.iter().any(|a| {
+            let attrib = a.path.to_token_stream().to_string();
+            attrib == "swift_bridge :: bridge" || attrib == "swift_bridge_macro :: bridge"
+        }),
+        _ => false,
+    }
+}
+
+/// The path in a `#[path = "..."]` attribute.
+fn path_attr(module: &ItemMod) -> Option<String> {
+    module
+        .attrs
+        .iter()
+        .find_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(name_value)) if name_value.path.is_ident("path") => {
+                match name_value.lit {
+                    Lit::Str(path) => Some(path.value()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+}
+
+impl Discovery {
+    /// Whether every `#[cfg(...)]` attribute is enabled.
+    fn cfg_enabled(&self, attrs: &[Attribute]) -> bool {
+        attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .all(|attr| match attr.parse_meta() {
+                Ok(Meta::List(list)) => match list.nested.first() {
+                    Some(NestedMeta::Meta(predicate)) => self.eval_cfg(predicate),
+                    _ => false,
+                },
+                _ => false,
+            })
+    }
+
+    fn eval_cfg(&self, predicate: &Meta) -> bool {
+        let name = predicate
+            .path()
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+
+        match predicate {
+            Meta::Path(_) => !self.in_build_script || std::env::var(cfg_env_var(&name)).is_ok(),
+            Meta::NameValue(name_value) => {
+                let value = match &name_value.lit {
+                    Lit::Str(value) => value.value(),
+                    _ => return false,
+                };
+
+                if name == "feature" {
+                    return match self.enabled_features.as_ref() {
+                        Some(enabled_features) => enabled_features.contains(&value),
+                        None => {
+                            let feature = value.replace('-', "_").to_uppercase();
+                            std::env::var(format!("CARGO_FEATURE_{}", feature)).is_ok()
+                        }
+                    };
+                }
+
+                if !self.in_build_script {
+                    return true;
+                }
+                std::env::var(cfg_env_var(&name))
+                    .map(|values| values.split(',').any(|v| v == value))
+                    .unwrap_or(false)
+            }
+            Meta::List(list) => {
+                let mut nested = list.nested.iter().map(|nested| match nested {
+                    NestedMeta::Meta(predicate) => self.eval_cfg(predicate),
+                    NestedMeta::Lit(_) => false,
+                });
+
+                match name.as_str() {
+                    "all" => nested.all(|enabled| enabled),
+                    "any" => nested.any(|enabled| enabled),
+                    "not" => !nested.next().unwrap_or(false),
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+/// The environment variable that Cargo sets for a `cfg` in build scripts.
+fn cfg_env_var(cfg_name: &str) -> String {
+    format!("CARGO_CFG_{}", cfg_name.to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::dir_with_files;
+
+    const BRIDGE: &str = "#[swift_bridge::bridge]\nmod ffi {}\n";
+
+    fn relative(dir: &tempfile::TempDir, files: Vec<PathBuf>) -> Vec<PathBuf> {
+        files
+            .into_iter()
+            .map(|file| file.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect()
+    }
+
+    /// Verify that we follow `mod` declarations to `foo.rs`, `foo/mod.rs` and `#[path]` files,
+    /// including the ones declared inside of inline modules and inside of other module files.
+    #[test]
+    fn follows_nested_module_declarations() {
+        let dir = dir_with_files(&[
+            (
+                "src/lib.rs",
+                r#"
+mod plain;
+mod dir_module;
+mod outer {
+    mod inner;
+}
+#[path = "custom/renamed.rs"]
+mod renamed;
+
+#[swift_bridge::bridge]
+mod ffi {}
+"#,
+            ),
+            ("src/plain.rs", "mod child;"),
+            ("src/plain/child.rs", BRIDGE),
+            ("src/dir_module/mod.rs", BRIDGE),
+            ("src/outer/inner.rs", BRIDGE),
+            ("src/custom/renamed.rs", "mod sibling;"),
+            ("src/custom/sibling.rs", BRIDGE),
+            ("src/not_declared.rs", BRIDGE),
+        ]);
+
+        let discovered = discover_bridge_files_with_features(dir.path(), &[], Some(&[]));
+
+        assert_eq!(
+            relative(&dir, discovered.bridge_files),
+            vec![
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("src/plain/child.rs"),
+                PathBuf::from("src/dir_module/mod.rs"),
+                PathBuf::from("src/outer/inner.rs"),
+                PathBuf::from("src/custom/sibling.rs"),
+            ]
+        );
+        assert_eq!(
+            relative(&dir, discovered.module_files),
+            vec![
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("src/plain.rs"),
+                PathBuf::from("src/plain/child.rs"),
+                PathBuf::from("src/dir_module/mod.rs"),
+                PathBuf::from("src/outer/inner.rs"),
+                PathBuf::from("src/custom/renamed.rs"),
+                PathBuf::from("src/custom/sibling.rs"),
+            ]
+        );
+    }
+
+    /// Verify that we don't read excluded files or the modules behind a disabled feature, so
+    /// neither they nor the modules that they declare are returned, even if they don't parse.
+    #[test]
+    fn skips_excluded_files_and_disabled_modules() {
+        let dir = dir_with_files(&[
+            (
+                "src/lib.rs",
+                r#"
+mod excluded;
+#[cfg(feature = "enabled")]
+mod enabled;
+#[cfg(feature = "disabled")]
+mod disabled;
+#[cfg(not(feature = "enabled"))]
+mod not_enabled;
+#[cfg(all(feature = "enabled", feature = "disabled"))]
+mod all_disabled;
+#[cfg(any(feature = "enabled", feature = "disabled"))]
+mod any_enabled;
+"#,
+            ),
+            ("src/excluded.rs", "mod behind_excluded; not rust"),
+            ("src/excluded/behind_excluded.rs", BRIDGE),
+            ("src/enabled.rs", BRIDGE),
+            ("src/disabled.rs", "not rust"),
+            ("src/not_enabled.rs", "not rust"),
+            ("src/all_disabled.rs", "not rust"),
+            ("src/any_enabled.rs", BRIDGE),
+        ]);
+
+        let discovered = discover_bridge_files_with_features(
+            dir.path(),
+            &[PathBuf::from("src/excluded.rs")],
+            Some(&["enabled".to_string()]),
+        );
+
+        assert_eq!(
+            relative(&dir, discovered.bridge_files),
+            vec![
+                PathBuf::from("src/enabled.rs"),
+                PathBuf::from("src/any_enabled.rs"),
+            ]
+        );
+        assert_eq!(
+            relative(&dir, discovered.module_files),
+            vec![
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("src/enabled.rs"),
+                PathBuf::from("src/any_enabled.rs"),
+            ]
+        );
+    }
+
+    /// Verify that we start from `src/main.rs` when the crate doesn't have a `src/lib.rs`.
+    #[test]
+    fn starts_from_main_rs_without_lib_rs() {
+        let dir = dir_with_files(&[("src/main.rs", "mod bridge;"), ("src/bridge.rs", BRIDGE)]);
+
+        assert_eq!(
+            relative(&dir, discover_bridge_files(dir.path(), &[])),
+            vec![PathBuf::from("src/bridge.rs")]
+        );
+    }
+}
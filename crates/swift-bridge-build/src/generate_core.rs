@@ -2,30 +2,68 @@ use crate::generate_core::boxed_fn_support::{
     C_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN, SWIFT_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN,
 };
 use crate::generate_core::result_support::{C_RESULT_SUPPORT, SWIFT_RUST_RESULT};
+use crate::{wrap_c_header, GeneratedWriter};
 use std::path::{Path, PathBuf};
+use swift_bridge_ir::PRIVATE_TYPE_PREFIX;
 
 const RUST_STRING_SWIFT: &'static str = include_str!("./generate_core/rust_string.swift");
 const RUST_STRING_C: &'static str = include_str!("./generate_core/rust_string.c.h");
 
 const STRING_SWIFT: &'static str = include_str!("./generate_core/string.swift");
 const RUST_VEC_SWIFT: &'static str = include_str!("./generate_core/rust_vec.swift");
+const ACTOR_SUPPORT_SWIFT: &'static str = include_str!("./generate_core/actor_support.swift");
+const INT128_SUPPORT_SWIFT: &'static str = include_str!("./generate_core/int128_support.swift");
+const PANIC_SUPPORT_SWIFT: &'static str = include_str!("./generate_core/panic_support.swift");
+const DIAGNOSTICS_SUPPORT_SWIFT: &'static str =
+    include_str!("./generate_core/diagnostics_support.swift");
 
 mod boxed_fn_support;
 mod result_support;
 
-pub(super) fn write_core_swift_and_c(out_dir: &Path) {
-    let core_swift_out = out_dir.join("SwiftBridgeCore.swift");
-    let mut swift = core_swift();
-    swift += "\n";
-    swift += &RUST_STRING_SWIFT;
-    swift += "\n";
-    swift += &SWIFT_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN;
-    swift += "\n";
-    swift += &SWIFT_RUST_RESULT;
+pub(super) fn write_core_swift_and_c(
+    writer: &mut dyn GeneratedWriter,
+    swift_imports: &str,
+    foundation_free: bool,
+    library_evolution: bool,
+    private_type_prefix: &str,
+) -> std::io::Result<()> {
+    let mut swift = swift_imports.to_string();
+    if foundation_free {
+        // The actor support code blocks on a `DispatchSemaphore`, which `Foundation` would
+        // otherwise re-export.
+        swift += "import Dispatch\n\n";
+    } else {
+        swift += "import Foundation\n\n";
+    }
+
+    let mut core_swift = core_swift();
+    core_swift += "\n";
+    core_swift += &RUST_STRING_SWIFT;
+    core_swift += "\n";
+    core_swift += &SWIFT_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN;
+    core_swift += "\n";
+    if library_evolution {
+        // Code outside of the framework switches over `RustResult`, so freeze it like the shared
+        // enums.
+        core_swift += &SWIFT_RUST_RESULT.replacen(
+            "public enum RustResult",
+            "@frozen public enum RustResult",
+            1,
+        );
+    } else {
+        core_swift += &SWIFT_RUST_RESULT;
+    }
+    core_swift += "\n";
+    core_swift += &ACTOR_SUPPORT_SWIFT;
+    core_swift += "\n";
+    core_swift += &INT128_SUPPORT_SWIFT;
+    core_swift += "\n";
+    core_swift += &PANIC_SUPPORT_SWIFT;
+    core_swift += &DIAGNOSTICS_SUPPORT_SWIFT;
+    swift += &with_private_type_prefix(&core_swift, private_type_prefix);
 
-    std::fs::write(core_swift_out, swift).unwrap();
+    writer.write(Path::new("SwiftBridgeCore.swift"), &swift)?;
 
-    let core_c_header_out = out_dir.join("SwiftBridgeCore.h");
     let mut c_header = core_c_header().to_string();
     c_header += "\n";
     c_header += &RUST_STRING_C;
@@ -33,8 +71,19 @@ pub(super) fn write_core_swift_and_c(out_dir: &Path) {
     c_header += &C_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN;
     c_header += "\n";
     c_header += &C_RESULT_SUPPORT;
+    let c_header = with_private_type_prefix(&c_header, private_type_prefix);
 
-    std::fs::write(core_c_header_out, c_header).unwrap();
+    writer.write(
+        Path::new("SwiftBridgeCore.h"),
+        &wrap_c_header("SwiftBridgeCore.h", "", &c_header),
+    )
+}
+
+/// Name the types that the core code shares with the bridge modules, which the core code
+/// declares with the default `__private__` prefix, using the `private_type_prefix` from the
+/// crate's `swift-bridge.toml`.
+fn with_private_type_prefix(core_code: &str, private_type_prefix: &str) -> String {
+    core_code.replace(PRIVATE_TYPE_PREFIX, private_type_prefix)
 }
 
 fn core_swift() -> String {
@@ -66,6 +115,9 @@ fn core_swift() -> String {
         ("Int64", "i64"),
         ("Int", "isize"),
         //
+        ("Float", "f32"),
+        ("Double", "f64"),
+        //
         ("Bool", "bool"),
     ] {
         core_swift += &conform_to_vectorizable(swift_ty, rust_ty);
@@ -83,6 +135,11 @@ fn core_c_header() -> String {
 typedef struct RustStr { uint8_t* const start; uintptr_t len; } RustStr;
 typedef struct __private__FfiSlice { void* const start; uintptr_t len; } __private__FfiSlice;
 void* __swift_bridge__null_pointer(void);
+void* __swift_bridge__$take_panic_message(void);
+void* __swift_bridge__$live_object_counts(void);
+void* __swift_bridge__$AsyncCancellation$new(void);
+void __swift_bridge__$AsyncCancellation$cancel(void* cancellation);
+void __swift_bridge__$AsyncCancellation$free(void* cancellation);
 
 typedef struct __private__OptionU8 { uint8_t val; bool is_some; } __private__OptionU8;
 typedef struct __private__OptionI8 { int8_t val; bool is_some; } __private__OptionI8;
@@ -95,8 +152,11 @@ typedef struct __private__OptionI64 { int64_t val; bool is_some; } __private__Op
 typedef struct __private__OptionUsize { uintptr_t val; bool is_some; } __private__OptionUsize;
 typedef struct __private__OptionIsize { intptr_t val; bool is_some; } __private__OptionIsize;
 typedef struct __private__OptionF32 { float val; bool is_some; } __private__OptionF32;
-typedef struct __private__OptionF64 { double val; bool is_some; } __private__OptionDouble;
+typedef struct __private__OptionF64 { double val; bool is_some; } __private__OptionF64;
 typedef struct __private__OptionBool { bool val; bool is_some; } __private__OptionBool;
+
+typedef struct __private__U128 { uint64_t low; uint64_t high; } __private__U128;
+typedef struct __private__I128 { uint64_t low; int64_t high; } __private__I128;
 "#
     .to_string();
 
@@ -113,24 +173,72 @@ typedef struct __private__OptionBool { bool val; bool is_some; } __private__Opti
         ("i64", "int64_t"),
         ("isize", "intptr_t"),
         //
+        ("f32", "float"),
+        ("f64", "double"),
+        //
         ("bool", "bool"),
     ] {
         header += &vec_of_primitive_headers(rust_ty, c_ty);
     }
 
+    for (rust_ty, c_ty) in vec![
+        ("u8", "uint8_t"),
+        ("u16", "uint16_t"),
+        ("u32", "uint32_t"),
+        ("u64", "uint64_t"),
+        ("usize", "uintptr_t"),
+        //
+        ("i8", "int8_t"),
+        ("i16", "int16_t"),
+        ("i32", "int32_t"),
+        ("i64", "int64_t"),
+        ("isize", "intptr_t"),
+        //
+        ("f32", "float"),
+        ("f64", "double"),
+        //
+        ("bool", "bool"),
+    ] {
+        header += &option_of_primitive_helpers(rust_ty, c_ty);
+    }
+
     header
 }
 
-/// Headers for Vec<T> where T is a primitive such as u8, i32, bool
-fn vec_of_primitive_headers(rust_ty: &str, c_ty: &str) -> String {
+/// The name of the FFI struct for Option<T> where T is a primitive, such as `__private__OptionU8`.
+fn primitive_option_ty(rust_ty: &str) -> String {
     let mut chars = rust_ty.chars();
 
     // u8 -> U8, bool -> Bool, etc...
     let capatilized_first_letter =
         chars.next().unwrap().to_string().to_uppercase() + chars.as_str();
 
-    // __private__OptionU8 ... etc
-    let option_ty = format!("{}{}", "__private__Option", capatilized_first_letter);
+    format!("{}{}", "__private__Option", capatilized_first_letter)
+}
+
+/// `static inline` constructors for Option<T> where T is a primitive such as u8, i32, bool.
+///
+/// The generated Swift code uses these to pass an `Optional` to Rust. Since they're defined in
+/// the header the Swift compiler can inline them.
+fn option_of_primitive_helpers(rust_ty: &str, c_ty: &str) -> String {
+    let option_ty = primitive_option_ty(rust_ty);
+    // __private__OptionU8 -> OptionU8
+    let helper_prefix = option_ty.trim_start_matches("__private__");
+
+    format!(
+        r#"
+static inline struct {option_ty} __swift_bridge__${helper_prefix}$some({c_ty} val) {{ return (struct {option_ty}){{ .val = val, .is_some = true }}; }}
+static inline struct {option_ty} __swift_bridge__${helper_prefix}$none(void) {{ return (struct {option_ty}){{ .is_some = false }}; }}
+"#,
+        option_ty = option_ty,
+        helper_prefix = helper_prefix,
+        c_ty = c_ty
+    )
+}
+
+/// Headers for Vec<T> where T is a primitive such as u8, i32, bool
+fn vec_of_primitive_headers(rust_ty: &str, c_ty: &str) -> String {
+    let option_ty = primitive_option_ty(rust_ty);
 
     format!(
         r#"
@@ -142,6 +250,10 @@ void __swift_bridge__$Vec_{rust_ty}$push(void* const vec, {c_ty} val);
 {option_ty} __swift_bridge__$Vec_{rust_ty}$get(void* const vec, uintptr_t index);
 {option_ty} __swift_bridge__$Vec_{rust_ty}$get_mut(void* const vec, uintptr_t index);
 {c_ty} const * __swift_bridge__$Vec_{rust_ty}$as_ptr(void* const vec);
+void __swift_bridge__$Vec_{rust_ty}$insert(void* const vec, uintptr_t index, {c_ty} val);
+{c_ty} __swift_bridge__$Vec_{rust_ty}$remove(void* const vec, uintptr_t index);
+void __swift_bridge__$Vec_{rust_ty}$reserve(void* const vec, uintptr_t additional);
+uintptr_t __swift_bridge__$Vec_{rust_ty}$capacity(void* const vec);
 "#,
         rust_ty = rust_ty,
         c_ty = c_ty,
@@ -152,7 +264,7 @@ void __swift_bridge__$Vec_{rust_ty}$push(void* const vec, {c_ty} val);
 fn conform_to_vectorizable(swift_ty: &str, rust_ty: &str) -> String {
     format!(
         r#"
-extension {swift_ty}: Vectorizable {{
+extension {swift_ty}: VectorizablePrimitive {{
     public static func vecOfSelfNew() -> UnsafeMutableRawPointer {{
         __swift_bridge__$Vec_{rust_ty}$new()
     }}
@@ -195,6 +307,26 @@ extension {swift_ty}: Vectorizable {{
     public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {{
         __swift_bridge__$Vec_{rust_ty}$len(vecPtr)
     }}
+
+    public static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: Self) {{
+        __swift_bridge__$Vec_{rust_ty}$insert(vecPtr, index, value)
+    }}
+
+    public static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self {{
+        __swift_bridge__$Vec_{rust_ty}$remove(vecPtr, index)
+    }}
+
+    public static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt) {{
+        __swift_bridge__$Vec_{rust_ty}$reserve(vecPtr, additional)
+    }}
+
+    public static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt {{
+        __swift_bridge__$Vec_{rust_ty}$capacity(vecPtr)
+    }}
+
+    public static func vecOfSelfAsPtr(vecPtr: UnsafeMutableRawPointer) -> UnsafePointer<Self> {{
+        __swift_bridge__$Vec_{rust_ty}$as_ptr(vecPtr)
+    }}
 }}
     "#,
         rust_ty = rust_ty,
@@ -218,3 +350,77 @@ fn generic_copy_type_ffi_repr() -> &'static str {
 protocol SwiftBridgeGenericCopyTypeFfiRepr {}
 "#
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryWriter;
+
+    fn core_swift_file(foundation_free: bool) -> String {
+        let mut writer = InMemoryWriter::new();
+        write_core_swift_and_c(&mut writer, "", foundation_free, false, PRIVATE_TYPE_PREFIX)
+            .unwrap();
+        writer.files[Path::new("SwiftBridgeCore.swift")].clone()
+    }
+
+    /// Verify that the core Swift code imports `Dispatch` instead of `Foundation` when it has to
+    /// be Foundation free, since the actor support code uses `DispatchSemaphore`.
+    #[test]
+    fn foundation_free_core_swift_imports_dispatch() {
+        let swift = core_swift_file(true);
+        assert!(swift.contains("DispatchSemaphore"));
+        assert!(swift.starts_with("import Dispatch\n"));
+        assert!(!swift.contains("import Foundation"));
+
+        let swift = core_swift_file(false);
+        assert!(swift.starts_with("import Foundation\n"));
+        assert!(!swift.contains("import Dispatch"));
+    }
+
+    /// Verify that the core Swift and C code name the types that they share with the bridge
+    /// modules using the configured private type prefix.
+    #[test]
+    fn core_code_uses_private_type_prefix() {
+        let mut writer = InMemoryWriter::new();
+        write_core_swift_and_c(&mut writer, "", false, false, "__my_crate__private__").unwrap();
+        let swift = &writer.files[Path::new("SwiftBridgeCore.swift")];
+        let header = &writer.files[Path::new("SwiftBridgeCore.h")];
+
+        assert!(header.contains("typedef struct __my_crate__private__OptionU8 {"));
+        assert!(header.contains("struct __my_crate__private__ResultBits64 {"));
+        assert!(header.contains(
+            "static inline struct __my_crate__private__OptionU8 __swift_bridge__$OptionU8$some(uint8_t val)"
+        ));
+        assert!(swift.contains("extension __my_crate__private__U128 {"));
+        assert!(swift.contains("public class __my_crate__private__RustFnOnceCallbackNoArgsNoRet {"));
+
+        for code in [swift, header] {
+            assert_eq!(
+                code.matches("__private__").count(),
+                code.matches("__my_crate__private__").count()
+            );
+        }
+    }
+
+    /// Verify that the core C header defines `static inline` constructors for the primitive
+    /// option structs, after the structs that they return.
+    #[test]
+    fn core_c_header_defines_inline_option_constructors() {
+        let header = core_c_header();
+
+        let some = "static inline struct __private__OptionU8 __swift_bridge__$OptionU8$some(uint8_t val) { return (struct __private__OptionU8){ .val = val, .is_some = true }; }";
+        let none = "static inline struct __private__OptionU8 __swift_bridge__$OptionU8$none(void) { return (struct __private__OptionU8){ .is_some = false }; }";
+        let typedef = "typedef struct __private__OptionU8 {";
+
+        assert!(header.contains(some));
+        assert!(header.contains(none));
+        assert!(header.find(typedef).unwrap() < header.find(some).unwrap());
+
+        assert!(header.contains(
+            "static inline struct __private__OptionUsize __swift_bridge__$OptionUsize$some(uintptr_t val)"
+        ));
+        assert!(header.contains(
+            "static inline struct __private__OptionF64 __swift_bridge__$OptionF64$none(void)"
+        ));
+    }
+}
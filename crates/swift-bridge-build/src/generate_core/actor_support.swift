@@ -0,0 +1,21 @@
+/// Runs an actor-isolated call and blocks the calling thread until it completes, so that
+/// synchronous Rust code can call methods on a Swift actor.
+///
+/// The calling thread must not be one that the actor needs in order to make progress, such as
+/// the main thread for a `@MainActor` actor.
+public func __swift_bridge__awaitActor<T>(_ operation: @escaping () async -> T) -> T {
+    let result = __private__ActorResult<T>()
+    let semaphore = DispatchSemaphore(value: 0)
+
+    Task {
+        result.value = await operation()
+        semaphore.signal()
+    }
+    semaphore.wait()
+
+    return result.value!
+}
+
+final class __private__ActorResult<T>: @unchecked Sendable {
+    var value: T?
+}
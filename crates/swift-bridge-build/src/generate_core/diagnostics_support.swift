@@ -0,0 +1,16 @@
+
+/// The number of live boxed opaque Rust types, `RustString`s and `RustVec`s that were handed to
+/// Swift, keyed by their Rust type name.
+///
+/// This is only counted when the Rust crate enables `swift-bridge`'s `leak-detection` feature.
+/// Otherwise it is always empty.
+public func rustLiveObjectCounts() -> [String: Int] {
+    let lines = RustString(ptr: __swift_bridge__$live_object_counts()).toString()
+
+    var counts: [String: Int] = [:]
+    for line in lines.split(separator: "\n") {
+        let fields = line.split(separator: "\t")
+        counts[String(fields[0])] = Int(fields[1])!
+    }
+    return counts
+}
@@ -0,0 +1,35 @@
+// `u128` and `i128` are passed over FFI as two 64 bit halves, since C has no portable 128 bit
+// integer type. `UInt128` and `Int128` are available starting with Swift 6.
+#if compiler(>=6.0)
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+extension __private__U128 {
+    @inline(__always)
+    func intoSwiftRepr() -> UInt128 {
+        (UInt128(self.high) << 64) | UInt128(self.low)
+    }
+}
+
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+extension UInt128 {
+    @inline(__always)
+    func intoFfiRepr() -> __private__U128 {
+        __private__U128(low: UInt64(truncatingIfNeeded: self), high: UInt64(truncatingIfNeeded: self >> 64))
+    }
+}
+
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+extension __private__I128 {
+    @inline(__always)
+    func intoSwiftRepr() -> Int128 {
+        (Int128(self.high) << 64) | Int128(self.low)
+    }
+}
+
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+extension Int128 {
+    @inline(__always)
+    func intoFfiRepr() -> __private__I128 {
+        __private__I128(low: UInt64(truncatingIfNeeded: self), high: Int64(truncatingIfNeeded: self >> 64))
+    }
+}
+#endif
@@ -0,0 +1,24 @@
+/// The error that a throwing initializer in a `#[swift_bridge(panic = "throw")]` bridge module
+/// throws if its Rust function panics.
+public struct RustPanic: Error, CustomStringConvertible {
+    /// The message that the Rust function panicked with.
+    public let message: String
+
+    public var description: String {
+        message
+    }
+}
+
+/// Throw the panic that the Rust function that was just called panicked with, if it panicked.
+func throwIfRustPanicked() throws {
+    if let message = __swift_bridge__$take_panic_message() {
+        throw RustPanic(message: RustString(ptr: message).toString())
+    }
+}
+
+/// Called by Rust functions in `#[swift_bridge(panic = "fatal_error")]` bridge modules when they
+/// panic.
+@_cdecl("__swift_bridge__$rust_panic_fatal_error")
+func __swift_bridge__rust_panic_fatal_error(_ message: RustStr) {
+    fatalError("Rust panicked: \(message.toString())")
+}
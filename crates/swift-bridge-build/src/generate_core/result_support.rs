@@ -33,8 +33,44 @@ extension RustResult {
         }
     }
 }
+
+// Read and write the FFI representations that can be stored in the `ok_or_err` bits of a
+// `__private__ResultBits64`.
+func __private__fromResultBits(_ bits: UInt64) -> UInt8 { UInt8(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> UInt16 { UInt16(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> UInt32 { UInt32(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> UInt64 { bits }
+func __private__fromResultBits(_ bits: UInt64) -> UInt { UInt(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> Int8 { Int8(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> Int16 { Int16(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> Int32 { Int32(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> Int64 { Int64(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> Int { Int(truncatingIfNeeded: bits) }
+func __private__fromResultBits(_ bits: UInt64) -> Bool { bits != 0 }
+func __private__fromResultBits(_ bits: UInt64) -> Float { Float(bitPattern: UInt32(truncatingIfNeeded: bits)) }
+func __private__fromResultBits(_ bits: UInt64) -> Double { Double(bitPattern: bits) }
+// Rust only stores non-null pointers, such as a boxed opaque type, in the bits.
+func __private__fromResultBits(_ bits: UInt64) -> UnsafeMutableRawPointer { unsafeBitCast(UInt(truncatingIfNeeded: bits), to: UnsafeMutableRawPointer.self) }
+
+func __private__toResultBits(_ value: UInt8) -> UInt64 { UInt64(value) }
+func __private__toResultBits(_ value: UInt16) -> UInt64 { UInt64(value) }
+func __private__toResultBits(_ value: UInt32) -> UInt64 { UInt64(value) }
+func __private__toResultBits(_ value: UInt64) -> UInt64 { value }
+func __private__toResultBits(_ value: UInt) -> UInt64 { UInt64(value) }
+func __private__toResultBits(_ value: Int8) -> UInt64 { UInt64(truncatingIfNeeded: value) }
+func __private__toResultBits(_ value: Int16) -> UInt64 { UInt64(truncatingIfNeeded: value) }
+func __private__toResultBits(_ value: Int32) -> UInt64 { UInt64(truncatingIfNeeded: value) }
+func __private__toResultBits(_ value: Int64) -> UInt64 { UInt64(truncatingIfNeeded: value) }
+func __private__toResultBits(_ value: Int) -> UInt64 { UInt64(truncatingIfNeeded: value) }
+func __private__toResultBits(_ value: Bool) -> UInt64 { value ? 1 : 0 }
+func __private__toResultBits(_ value: Float) -> UInt64 { UInt64(value.bitPattern) }
+func __private__toResultBits(_ value: Double) -> UInt64 { value.bitPattern }
+func __private__toResultBits(_ value: UnsafeMutableRawPointer) -> UInt64 { UInt64(UInt(bitPattern: value)) }
 "#;
 
 pub const C_RESULT_SUPPORT: &'static str = r#"
 struct __private__ResultPtrAndPtr { bool is_ok; void* ok_or_err; };
+struct __private__ResultBits64 { bool is_ok; uint64_t ok_or_err; };
+void* __swift_bridge__$Result$_alloc(uintptr_t size, uintptr_t align);
+void __swift_bridge__$Result$_free(void* ptr, uintptr_t size, uintptr_t align);
 "#;
@@ -10,6 +10,10 @@ void* __swift_bridge__$Vec_RustString$get(void* vec_ptr, uintptr_t index);
 void* __swift_bridge__$Vec_RustString$get_mut(void* vec_ptr, uintptr_t index);
 uintptr_t __swift_bridge__$Vec_RustString$len(void* vec_ptr);
 void* __swift_bridge__$Vec_RustString$as_ptr(void* vec_ptr);
+void __swift_bridge__$Vec_RustString$insert(void* vec_ptr, uintptr_t index, void* item_ptr);
+void* __swift_bridge__$Vec_RustString$remove(void* vec_ptr, uintptr_t index);
+void __swift_bridge__$Vec_RustString$reserve(void* vec_ptr, uintptr_t additional);
+uintptr_t __swift_bridge__$Vec_RustString$capacity(void* vec_ptr);
 
 void* __swift_bridge__$RustString$new(void);
 void* __swift_bridge__$RustString$new_with_str(struct RustStr str);
@@ -5,6 +5,14 @@ public class RustString: RustStringRefMut {
         super.init(ptr: ptr)
     }
 
+    /// Allows a string literal to be used wherever a `RustString` is expected.
+    ///
+    /// Swift requires `ExpressibleByStringLiteral`'s initializer to be declared inside of the class
+    /// since `RustString` isn't final.
+    public required convenience init(stringLiteral value: String) {
+        self.init(value)
+    }
+
     deinit {
         if isOwned {
             __swift_bridge__$RustString$_free(ptr)
@@ -90,4 +98,20 @@ extension RustString: Vectorizable {
     public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {
         __swift_bridge__$Vec_RustString$len(vecPtr)
     }
+
+    public static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: RustString) {
+        __swift_bridge__$Vec_RustString$insert(vecPtr, index, {value.isOwned = false; return value.ptr;}())
+    }
+
+    public static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self {
+        (RustString(ptr: __swift_bridge__$Vec_RustString$remove(vecPtr, index)) as! Self)
+    }
+
+    public static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt) {
+        __swift_bridge__$Vec_RustString$reserve(vecPtr, additional)
+    }
+
+    public static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt {
+        __swift_bridge__$Vec_RustString$capacity(vecPtr)
+    }
 }
\ No newline at end of file
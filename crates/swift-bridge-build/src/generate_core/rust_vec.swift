@@ -1,6 +1,3 @@
-// TODO:
-//  Implement iterator https://developer.apple.com/documentation/swift/iteratorprotocol
-
 public class RustVec<T: Vectorizable> {
     var ptr: UnsafeMutableRawPointer
     var isOwned: Bool = true
@@ -32,6 +29,36 @@ public class RustVec<T: Vectorizable> {
         Int(T.vecOfSelfLen(vecPtr: ptr))
     }
 
+    /// Insert a value at the index, shifting the values after it to the right.
+    ///
+    /// The index must be less than or equal to `len()`.
+    public func insert(_ value: T, at index: Int) {
+        precondition(index >= 0 && index <= self.len(), "Index out of range")
+        T.vecOfSelfInsert(vecPtr: ptr, index: UInt(index), value: value)
+    }
+
+    /// Remove and return the value at the index, shifting the values after it to the left.
+    ///
+    /// The index must be less than `len()`.
+    public func remove(at index: Int) -> T {
+        precondition(index >= 0 && index < self.len(), "Index out of range")
+        return T.vecOfSelfRemove(vecPtr: ptr, index: UInt(index))
+    }
+
+    /// Make sure that the vector can hold at least `minimumCapacity` values without reallocating,
+    /// so that pushing many values only allocates once.
+    public func reserveCapacity(_ minimumCapacity: Int) {
+        let additional = minimumCapacity - self.len()
+        if additional > 0 {
+            T.vecOfSelfReserve(vecPtr: ptr, additional: UInt(additional))
+        }
+    }
+
+    /// The number of values that the vector can hold without reallocating.
+    public var capacity: Int {
+        Int(T.vecOfSelfCapacity(vecPtr: ptr))
+    }
+
     deinit {
         if isOwned {
             T.vecOfSelfFree(vecPtr: ptr)
@@ -62,13 +89,16 @@ public struct RustVecIterator<T: Vectorizable>: IteratorProtocol {
 
 extension RustVec: Collection {
     public typealias Index = Int
+    public typealias Element = T.SelfRef
+    public typealias Indices = Range<Int>
 
     public func index(after i: Int) -> Int {
         i + 1
     }
 
     public subscript(position: Int) -> T.SelfRef {
-        self.get(index: UInt(position))!
+        precondition(position >= 0 && position < self.len(), "Index out of range")
+        return self.get(index: UInt(position))!
     }
 
     public var startIndex: Int {
@@ -78,9 +108,58 @@ extension RustVec: Collection {
     public var endIndex: Int {
         self.len()
     }
+
+    public var indices: Range<Int> {
+        0..<self.len()
+    }
+
+    /// The length is stored by the Rust `Vec`, so this doesn't need to walk the elements.
+    public var count: Int {
+        self.len()
+    }
+
+    public var isEmpty: Bool {
+        self.len() == 0
+    }
+}
+
+extension RustVec: BidirectionalCollection {
+    public func index(before i: Int) -> Int {
+        i - 1
+    }
 }
 
 extension RustVec: RandomAccessCollection {
+    public func index(_ i: Int, offsetBy distance: Int) -> Int {
+        i + distance
+    }
+
+    public func distance(from start: Int, to end: Int) -> Int {
+        end - start
+    }
+}
+
+extension RustVec where T: VectorizablePrimitive {
+    /// A pointer to the vector's first value.
+    ///
+    /// The pointer is only valid until the vector is modified or freed.
+    public func as_ptr() -> UnsafePointer<T> {
+        T.vecOfSelfAsPtr(vecPtr: ptr)
+    }
+
+    /// Call the closure with a buffer pointer to the vector's values, so that they can be read in
+    /// bulk instead of with one call to Rust per value.
+    ///
+    /// The buffer pointer must not be used after the closure returns.
+    ///
+    /// ```
+    /// let sum = rustVec.withUnsafeBufferPointer { $0.reduce(0, +) }
+    /// ```
+    public func withUnsafeBufferPointer<R>(_ body: (UnsafeBufferPointer<T>) throws -> R) rethrows -> R {
+        try withExtendedLifetime(self) {
+            try body(UnsafeBufferPointer(start: self.as_ptr(), count: self.len()))
+        }
+    }
 }
 
 extension UnsafeBufferPointer {
@@ -122,4 +201,18 @@ public protocol Vectorizable {
     static func vecOfSelfGetMut(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Optional<SelfRefMut>
 
     static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt
+
+    static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: Self)
+
+    static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self
+
+    static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt)
+
+    static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt
+}
+
+/// A `Vectorizable` type that a Rust `Vec` stores the same way that Swift does, such as `UInt8`
+/// or `Float`, so a `RustVec` of it can be read through a buffer pointer.
+public protocol VectorizablePrimitive: Vectorizable {
+    static func vecOfSelfAsPtr(vecPtr: UnsafeMutableRawPointer) -> UnsafePointer<Self>
 }
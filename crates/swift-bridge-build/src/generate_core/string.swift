@@ -1,5 +1,3 @@
-import Foundation
-
 extension RustString {
     public func toString() -> String {
         let str = self.as_str()
@@ -9,6 +7,33 @@ extension RustString {
     }
 }
 
+extension RustString: ExpressibleByStringLiteral {}
+
+extension RustStringRef {
+    /// The number of UTF-8 bytes in the string.
+    ///
+    /// This is stored by the Rust `String`, so unlike `toString().utf8.count` it doesn't need to
+    /// copy the string.
+    public var utf8Count: Int {
+        Int(self.len())
+    }
+}
+extension RustStringRef: CustomStringConvertible {
+    public var description: String {
+        self.as_str().toString()
+    }
+}
+extension RustStringRef: Equatable {
+    public static func == (lhs: RustStringRef, rhs: RustStringRef) -> Bool {
+        lhs.as_str().toBufferPointer().elementsEqual(rhs.as_str().toBufferPointer())
+    }
+}
+extension RustStringRef: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(bytes: UnsafeRawBufferPointer(self.as_str().toBufferPointer()))
+    }
+}
+
 extension RustStr {
     func toBufferPointer() -> UnsafeBufferPointer<UInt8> {
         let bytes = UnsafeBufferPointer(start: self.start, count: Int(self.len))
@@ -17,7 +42,40 @@ extension RustStr {
 
     public func toString() -> String {
         let bytes = self.toBufferPointer()
-        return String(bytes: bytes, encoding: .utf8)!
+        return String(decoding: bytes, as: UTF8.self)
+    }
+
+    /// Call the closure with the string's UTF-8 bytes without copying them.
+    ///
+    /// The buffer pointer is only valid for as long as the Rust `&str` is.
+    public func withUnsafeBufferPointer<T>(_ body: (UnsafeBufferPointer<UInt8>) throws -> T) rethrows -> T {
+        try body(self.toBufferPointer())
+    }
+
+    /// Whether the string starts with the prefix's UTF-8 bytes.
+    public func hasPrefix(_ prefix: String) -> Bool {
+        self.toBufferPointer().starts(with: prefix.utf8)
+    }
+
+    /// Whether the string ends with the suffix's UTF-8 bytes.
+    public func hasSuffix(_ suffix: String) -> Bool {
+        self.toBufferPointer().reversed().starts(with: suffix.utf8.reversed())
+    }
+
+    /// Whether the string contains the other string's UTF-8 bytes.
+    public func contains(_ other: String) -> Bool {
+        let bytes = self.toBufferPointer()
+        let otherCount = other.utf8.count
+        if otherCount > bytes.count {
+            return false
+        }
+
+        for start in 0...(bytes.count - otherCount) {
+            if bytes[start..<start + otherCount].elementsEqual(other.utf8) {
+                return true
+            }
+        }
+        return false
     }
 }
 extension RustStr: Identifiable {
@@ -27,10 +85,24 @@ extension RustStr: Identifiable {
 }
 extension RustStr: Equatable {
     public static func == (lhs: RustStr, rhs: RustStr) -> Bool {
-        // TODO: Rather than compare Strings, we can avoid allocating by calling a function
-        // on the Rust side that compares the underlying byte slices.
-        return
-            lhs.toString() == rhs.toString()
+        lhs.toBufferPointer().elementsEqual(rhs.toBufferPointer())
+    }
+
+    /// Compare with a Swift `String` without copying the `RustStr` into a `String`.
+    public static func == (lhs: RustStr, rhs: String) -> Bool {
+        lhs.toBufferPointer().elementsEqual(rhs.utf8)
+    }
+
+    public static func == (lhs: String, rhs: RustStr) -> Bool {
+        rhs == lhs
+    }
+
+    public static func != (lhs: RustStr, rhs: String) -> Bool {
+        !(lhs == rhs)
+    }
+
+    public static func != (lhs: String, rhs: RustStr) -> Bool {
+        !(lhs == rhs)
     }
 }
 
@@ -78,18 +150,25 @@ func optionalStringIntoRustString<S: IntoRustString>(_ string: Optional<S>) -> R
 extension String: ToRustStr {
     /// Safely get a scoped pointer to the String and then call the callback with a RustStr
     /// that uses that pointer.
+    ///
+    /// The pointer points into the String's own UTF-8 storage, so unlike `utf8CString` this
+    /// doesn't copy the String unless it isn't stored contiguously (such as a bridged `NSString`).
     public func toRustStr<T> (_ withUnsafeRustStr: (RustStr) -> T) -> T {
-        return self.utf8CString.withUnsafeBufferPointer({ bufferPtr in
+        var string = self
+        return string.withUTF8({ bufferPtr in
             let rustStr = RustStr(
-                start: UnsafeMutableRawPointer(mutating: bufferPtr.baseAddress!).assumingMemoryBound(to: UInt8.self),
-                // Subtract 1 because of the null termination character at the end
-                len: UInt(bufferPtr.count - 1)
+                start: UnsafeMutablePointer(mutating: bufferPtr.baseAddress ?? emptyRustStrStart),
+                len: UInt(bufferPtr.count)
             )
             return withUnsafeRustStr(rustStr)
         })
     }
 }
 
+/// Where an empty String's RustStr points if the String has no buffer, since a RustStr with a
+/// null pointer is treated as `None`.
+private let emptyRustStrStart = UnsafePointer(UnsafeMutablePointer<UInt8>.allocate(capacity: 1))
+
 extension RustStr: ToRustStr {
     public func toRustStr<T> (_ withUnsafeRustStr: (RustStr) -> T) -> T {
         return withUnsafeRustStr(self)
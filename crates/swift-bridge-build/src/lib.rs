@@ -3,65 +3,450 @@
 
 #![deny(missing_docs)]
 
+mod cache;
+mod collisions;
+mod config;
+mod dep_file;
+mod discover;
 mod package;
+mod rust_crate;
+mod swiftc_check;
+mod universal;
+mod verify;
+mod watch;
+mod windows;
+mod writer;
+mod xcode;
+use crate::cache::GenerationCache;
+use crate::collisions::{DeclaredItem, DeclaredNames};
 use crate::generate_core::write_core_swift_and_c;
+pub use config::*;
+pub use dep_file::*;
+pub use discover::*;
 pub use package::*;
-use std::path::Path;
-use swift_bridge_ir::{CodegenConfig, SwiftBridgeModule};
+pub use rust_crate::*;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use swift_bridge_ir::{
+    break_long_swift_lines, CodegenConfig, SwiftBridgeModule, PRIVATE_TYPE_PREFIX,
+};
+pub use swift_bridge_ir::{
+    ConfigFormatting, ConfigOutput, ConfigSwiftcCheck, SwiftBridgeConfig, CONFIG_FILE_NAME,
+};
+pub use swiftc_check::*;
 use syn::__private::ToTokens;
 use syn::{File, Item};
+pub use universal::*;
+pub use verify::*;
+pub use watch::*;
+pub use windows::*;
+pub use writer::*;
+pub use xcode::*;
 
 mod generate_core;
 
+#[cfg(test)]
+mod test_utils;
+
 /// Parse rust sources files for `#\[swift_bridge::bridge\]` headers and generate the corresponding
 /// Swift files.
+///
+/// The `symbol_prefix`, `swift_access` and `features` in the crate's `swift-bridge.toml` are
+/// used if it has one.
 pub fn parse_bridges(
     rust_source_files: impl IntoIterator<Item = impl AsRef<Path>>,
 ) -> GeneratedCode {
+    let bridge_crate = BridgeCrate::from_build_script_env();
+
+    parse_bridges_with_crate(rust_source_files, &bridge_crate, None)
+}
+
+/// Parse rust sources files for `#\[swift_bridge::bridge\]` headers and generate the corresponding
+/// Swift files, reusing the code that was generated for the files that haven't changed since the
+/// last build.
+///
+/// The code generated for each file is cached in `cache_dir`, keyed by a hash of the file's
+/// contents and the crate's enabled features. Entries for files that changed or are no longer
+/// parsed are removed, so `cache_dir` should only be used for this cache.
+///
+/// ```no_run
+/// let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+///
+/// swift_bridge_build::parse_bridges_cached(vec!["src/lib.rs"], out_dir.join("swift-bridge-cache"))
+///     .write_all_concatenated("./generated", env!("CARGO_PKG_NAME"));
+/// ```
+pub fn parse_bridges_cached(
+    rust_source_files: impl IntoIterator<Item = impl AsRef<Path>>,
+    cache_dir: impl AsRef<Path>,
+) -> GeneratedCode {
+    let bridge_crate = BridgeCrate::from_build_script_env();
+
+    parse_bridges_with_crate(rust_source_files, &bridge_crate, Some(cache_dir.as_ref()))
+}
+
+/// Parse rust sources files for `#\[swift_bridge::bridge\]` headers and generate the corresponding
+/// Swift files, outside of a build script.
+///
+/// Modules and items behind a `#[cfg(feature = "...")]` are only generated if their feature is in
+/// `enabled_features`.
+///
+/// The crate's `swift-bridge.toml` is not read. Use [`generate_with_config`] for that.
+///
+/// ```no_run
+/// swift_bridge_build::parse_crate_bridges(vec!["my-crate/src/lib.rs"], &[])
+///     .write_all_concatenated("./generated", "my-crate");
+/// ```
+pub fn parse_crate_bridges(
+    rust_source_files: impl IntoIterator<Item = impl AsRef<Path>>,
+    enabled_features: &[String],
+) -> GeneratedCode {
+    let bridge_crate = BridgeCrate {
+        enabled_features: Some(enabled_features.to_vec()),
+        symbol_prefix: None,
+        private_type_prefix: None,
+        swift_access: None,
+        library_evolution: false,
+    };
+
+    parse_bridges_with_crate(rust_source_files, &bridge_crate, None)
+}
+
+/// The crate that the parsed bridge modules are in.
+struct BridgeCrate {
+    /// The enabled crate features, or `None` to read them from Cargo's `CARGO_FEATURE_*`
+    /// environment variables.
+    enabled_features: Option<Vec<String>>,
+    /// The `symbol_prefix` from the crate's `swift-bridge.toml`.
+    symbol_prefix: Option<String>,
+    /// The `private_type_prefix` from the crate's `swift-bridge.toml`.
+    private_type_prefix: Option<String>,
+    /// The `swift_access` from the crate's `swift-bridge.toml`.
+    swift_access: Option<String>,
+    /// The `library_evolution` from the crate's `swift-bridge.toml`.
+    library_evolution: bool,
+}
+
+impl BridgeCrate {
+    /// The crate whose build script is running, configured by its `swift-bridge.toml`.
+    fn from_build_script_env() -> Self {
+        // Cargo sets these when running the build script of the crate that the modules are in.
+        let config = std::env::var("CARGO_MANIFEST_DIR")
+            .ok()
+            .and_then(|crate_dir| {
+                SwiftBridgeConfig::load(crate_dir).unwrap_or_else(|e| panic!("{}", e))
+            })
+            .unwrap_or_default();
+
+        BridgeCrate {
+            enabled_features: config.features,
+            symbol_prefix: config.symbol_prefix,
+            private_type_prefix: config.private_type_prefix,
+            swift_access: config.swift_access,
+            library_evolution: config.library_evolution,
+        }
+    }
+}
+
+fn parse_bridges_with_crate(
+    rust_source_files: impl IntoIterator<Item = impl AsRef<Path>>,
+    bridge_crate: &BridgeCrate,
+    cache_dir: Option<&Path>,
+) -> GeneratedCode {
+    // TODO: Return an error...
+    try_parse_bridges_with_crate(rust_source_files, bridge_crate, cache_dir)
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+fn try_parse_bridges_with_crate(
+    rust_source_files: impl IntoIterator<Item = impl AsRef<Path>>,
+    bridge_crate: &BridgeCrate,
+    cache_dir: Option<&Path>,
+) -> Result<GeneratedCode, String> {
     let mut generated_code = GeneratedCode::new();
+    generated_code.library_evolution = bridge_crate.library_evolution;
+    if let Some(prefix) = bridge_crate.private_type_prefix.as_ref() {
+        generated_code.private_type_prefix = prefix.clone();
+    }
+    let mut cache = cache_dir.map(GenerationCache::new);
+    let mut declared_names = DeclaredNames::default();
 
     for rust_file in rust_source_files.into_iter() {
         let rust_file: &Path = rust_file.as_ref();
 
-        let file = std::fs::read_to_string(rust_file).unwrap();
-        let gen = match parse_file_contents(&file) {
-            Ok(generated) => generated,
-            Err(e) => {
-                // TODO: Return an error...
-                panic!(
-                    r#"
+        let file = std::fs::read_to_string(rust_file)
+            .map_err(|e| format!("Failed to read {:?}: {}", rust_file, e))?;
+        let file_stem = rust_file.file_stem().unwrap().to_str().unwrap();
+        generated_code.source_files.push(rust_file.to_path_buf());
+
+        if let Some(cache) = cache.as_mut() {
+            if let Some(gen) = cache.get(&file, file_stem, bridge_crate) {
+                for module in gen.iter() {
+                    declared_names.insert(
+                        rust_file,
+                        &module.module_name,
+                        &module.declared_items,
+                    )?;
+                }
+                generated_code.generated.extend(gen);
+                continue;
+            }
+        }
+
+        let gen = parse_file_contents(&file, file_stem, bridge_crate).map_err(|e| {
+            format!(
+                r#"
 Error while parsing {:?}
 {}
 "#,
-                    rust_file, e
-                )
-            }
-        };
+                rust_file, e
+            )
+        })?;
+
+        if let Some(cache) = cache.as_mut() {
+            cache.insert(&file, bridge_crate, &gen);
+        }
+
+        for module in gen.iter() {
+            declared_names.insert(rust_file, &module.module_name, &module.declared_items)?;
+        }
+        generated_code.generated.extend(gen);
+    }
 
-        generated_code.generated.push(gen);
+    if let Some(cache) = cache {
+        cache.remove_unused();
     }
 
-    generated_code
+    Ok(generated_code)
 }
 
 /// Generated Swift files and C headers.
 pub struct GeneratedCode {
     generated: Vec<GeneratedFromSwiftBridgeModule>,
+    /// The Rust source files that were parsed.
+    source_files: Vec<PathBuf>,
+    swift_format: Option<PathBuf>,
+    module_map: Option<String>,
+    foundation_free: bool,
+    library_evolution: bool,
+    /// The prefix of the C types that the core header shares with the bridge modules.
+    private_type_prefix: String,
+    swiftc_check: SwiftcCheck,
+}
+
+/// How to format the generated Swift code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwiftFormatting {
+    /// Leave the generated Swift code as is.
+    None,
+    /// Break long single-line blocks, such as the closures that convert `Option` values, onto
+    /// multiple indented lines.
+    LineBreaks,
+    /// Break long lines and then run `swift-format format` on the generated Swift files before
+    /// they are written.
+    ///
+    /// The path is the `swift-format` executable, such as `PathBuf::from("swift-format")`.
+    SwiftFormat(PathBuf),
+}
+
+impl Default for SwiftFormatting {
+    fn default() -> Self {
+        SwiftFormatting::None
+    }
 }
 
 impl GeneratedCode {
     fn new() -> Self {
-        GeneratedCode { generated: vec![] }
+        GeneratedCode {
+            generated: vec![],
+            source_files: vec![],
+            swift_format: None,
+            module_map: None,
+            foundation_free: false,
+            library_evolution: false,
+            private_type_prefix: PRIVATE_TYPE_PREFIX.to_string(),
+            swiftc_check: SwiftcCheck::None,
+        }
     }
 }
 
 impl GeneratedCode {
+    /// Format the generated Swift code, which is otherwise emitted with some long single-line
+    /// blocks.
+    ///
+    /// ```no_run
+    /// # use swift_bridge_build::SwiftFormatting;
+    /// swift_bridge_build::parse_bridges(vec!["src/lib.rs"])
+    ///     .format_swift(SwiftFormatting::LineBreaks)
+    ///     .write_all_concatenated("./generated", env!("CARGO_PKG_NAME"));
+    /// ```
+    pub fn format_swift(mut self, formatting: SwiftFormatting) -> Self {
+        match formatting {
+            SwiftFormatting::None => {
+                self.swift_format = None;
+            }
+            SwiftFormatting::LineBreaks => {
+                self.break_long_swift_lines();
+                self.swift_format = None;
+            }
+            SwiftFormatting::SwiftFormat(swift_format) => {
+                self.break_long_swift_lines();
+                self.swift_format = Some(swift_format);
+            }
+        }
+
+        self
+    }
+
+    fn break_long_swift_lines(&mut self) {
+        for gen in self.generated.iter_mut() {
+            gen.swift = break_long_swift_lines(&gen.swift);
+        }
+    }
+
+    /// Run `swift-format` on the generated Swift code, if [`SwiftFormatting::SwiftFormat`] was
+    /// used.
+    fn run_swift_format(&self, swift: String) -> String {
+        let swift_format = match &self.swift_format {
+            Some(swift_format) => swift_format,
+            None => return swift,
+        };
+
+        let mut child = Command::new(swift_format)
+            .arg("format")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|e| panic!("Failed to run {:?}: {}", swift_format, e));
+
+        // Write from another thread so that a full stdout pipe can't block us.
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = std::thread::spawn(move || stdin.write_all(swift.as_bytes()));
+        let output = child
+            .wait_with_output()
+            .unwrap_or_else(|e| panic!("Failed to run {:?}: {}", swift_format, e));
+        let _ = writer.join();
+
+        if !output.status.success() {
+            panic!(
+                r#"
+{:?} failed to format the generated Swift code.
+{}
+"#,
+                swift_format,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    /// Write a `module.modulemap` that declares a Clang module with the given name for the
+    /// generated C headers, so that Swift can `import` them without a bridging header.
+    ///
+    /// The generated Swift files `import` the module.
+    ///
+    /// ```no_run
+    /// swift_bridge_build::parse_bridges(vec!["src/lib.rs"])
+    ///     .module_map("RustBridge")
+    ///     .write_all_concatenated("./generated", env!("CARGO_PKG_NAME"));
+    /// ```
+    pub fn module_map(mut self, module_name: &str) -> Self {
+        assert_valid_module_name(module_name);
+
+        self.module_map = Some(module_name.to_string());
+        self
+    }
+
+    /// Don't `import Foundation` in the generated `SwiftBridgeCore.swift`, so that the generated
+    /// code compiles with only the Swift standard library and `Dispatch`, such as on Linux.
+    ///
+    /// The generated code for `#[swift_bridge(main_actor)]` functions and for `objc`,
+    /// `ObservableObject` and `Publisher` types uses Apple frameworks, so bridge modules that use
+    /// them still need Foundation.
+    ///
+    /// ```no_run
+    /// swift_bridge_build::parse_bridges(vec!["src/lib.rs"])
+    ///     .foundation_free()
+    ///     .write_all_concatenated("./generated", env!("CARGO_PKG_NAME"));
+    /// ```
+    pub fn foundation_free(mut self) -> Self {
+        self.foundation_free = true;
+        self
+    }
+
+    /// The `import` of the Clang module that the generated Swift files depend on, if
+    /// [`Self::module_map`] was used.
+    fn swift_imports(&self) -> String {
+        match &self.module_map {
+            Some(module_name) => format!("import {}\n", module_name),
+            None => "".to_string(),
+        }
+    }
+
+    /// Write the `module.modulemap` for the given headers, which are relative to the generated
+    /// code directory, if [`Self::module_map`] was used.
+    fn write_module_map(
+        &self,
+        writer: &mut dyn GeneratedWriter,
+        headers: &[String],
+    ) -> std::io::Result<()> {
+        let module_name = match &self.module_map {
+            Some(module_name) => module_name,
+            None => return Ok(()),
+        };
+
+        let mut module_map = format!("module {} {{\n", module_name);
+        module_map += "    header \"SwiftBridgeCore.h\"\n";
+        for header in headers {
+            module_map += &format!("    header \"{}\"\n", header);
+        }
+        module_map += "    export *\n}\n";
+
+        writer.write(Path::new("module.modulemap"), &module_map)
+    }
+
     /// Write all of the generated Swift to a single Swift file and all of the generated C headers
     /// to a single header file.
     pub fn write_all_concatenated(&self, swift_bridge_out_dir: impl AsRef<Path>, crate_name: &str) {
         let swift_bridge_out_dir = swift_bridge_out_dir.as_ref();
 
-        let mut concatenated_swift = "".to_string();
+        self.write_all_concatenated_to(
+            &mut FileSystemWriter::new(swift_bridge_out_dir),
+            crate_name,
+        )
+        .unwrap_or_else(|e| panic!("Failed to write to {:?}: {}", swift_bridge_out_dir, e));
+
+        self.run_swiftc_check(
+            swift_bridge_out_dir,
+            crate_name,
+            &[
+                PathBuf::from("SwiftBridgeCore.swift"),
+                Path::new(crate_name).join(format!("{}.swift", crate_name)),
+            ],
+        );
+    }
+
+    /// Write the same files as [`Self::write_all_concatenated`] using the given writer.
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use swift_bridge_build::InMemoryWriter;
+    ///
+    /// let mut writer = InMemoryWriter::new();
+    /// swift_bridge_build::parse_bridges(vec!["src/lib.rs"])
+    ///     .write_all_concatenated_to(&mut writer, "my_crate")
+    ///     .unwrap();
+    ///
+    /// let swift = &writer.files[Path::new("my_crate/my_crate.swift")];
+    /// ```
+    pub fn write_all_concatenated_to(
+        &self,
+        writer: &mut dyn GeneratedWriter,
+        crate_name: &str,
+    ) -> std::io::Result<()> {
+        let mut concatenated_swift = self.swift_imports();
         let mut concatenated_c = "".to_string();
 
         for gen in &self.generated {
@@ -69,20 +454,147 @@ impl GeneratedCode {
             concatenated_c += &gen.c_header;
         }
 
-        let out = swift_bridge_out_dir.join(&crate_name);
-        match std::fs::create_dir_all(&out) {
-            Ok(_) => {}
-            Err(_) => {}
-        };
+        let out = Path::new(crate_name);
 
-        std::fs::write(out.join(format!("{}.h", crate_name)), concatenated_c).unwrap();
-        std::fs::write(
-            out.join(format!("{}.swift", crate_name)),
-            concatenated_swift,
-        )
-        .unwrap();
+        let header_name = format!("{}.h", crate_name);
+        writer.write(
+            &out.join(&header_name),
+            &wrap_c_header(&header_name, "", &concatenated_c),
+        )?;
+        writer.write(
+            &out.join(format!("{}.swift", crate_name)),
+            &self.run_swift_format(concatenated_swift),
+        )?;
+
+        write_core_swift_and_c(
+            writer,
+            &self.swift_imports(),
+            self.foundation_free,
+            self.library_evolution,
+            &self.private_type_prefix,
+        )?;
+        self.write_module_map(writer, &[format!("{}/{}.h", crate_name, crate_name)])
+    }
+
+    /// Write the generated Swift and C header for each bridge module to their own files, so that
+    /// changing one bridge module only requires Swift to recompile that module's Swift file.
+    ///
+    /// The files are named after the Rust source file and the bridge module, such as
+    /// `lib_ffi.swift` and `lib_ffi.h` for a `mod ffi` in `src/lib.rs`.
+    ///
+    /// A `{crate_name}.h` header that includes every bridge module's header is written alongside
+    /// them, so it can be used in place of the header that [`Self::write_all_concatenated`]
+    /// writes.
+    pub fn write_per_module(&self, swift_bridge_out_dir: impl AsRef<Path>, crate_name: &str) {
+        let swift_bridge_out_dir = swift_bridge_out_dir.as_ref();
+
+        self.write_per_module_to(&mut FileSystemWriter::new(swift_bridge_out_dir), crate_name)
+            .unwrap_or_else(|e| panic!("Failed to write to {:?}: {}", swift_bridge_out_dir, e));
+
+        let mut swift_files = vec![PathBuf::from("SwiftBridgeCore.swift")];
+        swift_files.extend(
+            self.per_module_file_names(crate_name)
+                .into_iter()
+                .map(|file_name| Path::new(crate_name).join(format!("{}.swift", file_name))),
+        );
+        self.run_swiftc_check(swift_bridge_out_dir, crate_name, &swift_files);
+    }
+
+    /// The name of each bridge module's files, without their extension, for
+    /// [`Self::write_per_module`].
+    fn per_module_file_names(&self, crate_name: &str) -> Vec<String> {
+        let mut file_names: Vec<String> = vec![crate_name.to_string()];
+
+        for gen in &self.generated {
+            let mut file_name = format!("{}_{}", gen.file_stem, gen.module_name);
+            let mut suffix = 2;
+            while file_names.contains(&file_name) {
+                file_name = format!("{}_{}_{}", gen.file_stem, gen.module_name, suffix);
+                suffix += 1;
+            }
+
+            file_names.push(file_name);
+        }
+
+        file_names.split_off(1)
+    }
+
+    /// Write the same files as [`Self::write_per_module`] using the given writer.
+    pub fn write_per_module_to(
+        &self,
+        writer: &mut dyn GeneratedWriter,
+        crate_name: &str,
+    ) -> std::io::Result<()> {
+        let out = Path::new(crate_name);
+
+        let mut umbrella_header = "// File automatically generated by swift-bridge.\n".to_string();
+        let mut headers = vec![];
 
-        write_core_swift_and_c(swift_bridge_out_dir.as_ref());
+        let file_names = self.per_module_file_names(crate_name);
+        for (index, (gen, file_name)) in self.generated.iter().zip(&file_names).enumerate() {
+            let header_name = format!("{}.h", file_name);
+            writer.write(
+                &out.join(&header_name),
+                &wrap_c_header(
+                    &header_name,
+                    &self.per_module_includes(index, &file_names),
+                    &gen.c_header,
+                ),
+            )?;
+            writer.write(
+                &out.join(format!("{}.swift", file_name)),
+                &self.run_swift_format(format!("{}{}", self.swift_imports(), gen.swift)),
+            )?;
+            headers.push(format!("{}/{}.h", crate_name, file_name));
+
+            umbrella_header += &format!("#include \"{}.h\"\n", file_name);
+        }
+
+        let header_name = format!("{}.h", crate_name);
+        writer.write(
+            &out.join(&header_name),
+            &wrap_c_header(&header_name, "", &umbrella_header),
+        )?;
+
+        write_core_swift_and_c(
+            writer,
+            &self.swift_imports(),
+            self.foundation_free,
+            self.library_evolution,
+            &self.private_type_prefix,
+        )?;
+        self.write_module_map(writer, &headers)
+    }
+
+    /// The `#include`s for a bridge module's header in [`Self::write_per_module`], so that it can
+    /// be used on its own.
+    ///
+    /// Each header includes `SwiftBridgeCore.h` and the headers of the other bridge modules whose
+    /// types it uses, such as a struct that it declares using `#[swift_bridge(already_declared)]`.
+    fn per_module_includes(&self, module_index: usize, file_names: &[String]) -> String {
+        // `SwiftBridgeCore.h` is in the parent directory of the bridge modules' headers, unless
+        // they were all copied into one include directory, such as an XCFramework's.
+        let mut includes = r#"#if __has_include("../SwiftBridgeCore.h")
+#include "../SwiftBridgeCore.h"
+#else
+#include "SwiftBridgeCore.h"
+#endif
+"#
+        .to_string();
+
+        let c_header = &self.generated[module_index].c_header;
+        for (index, (gen, file_name)) in self.generated.iter().zip(file_names).enumerate() {
+            let uses_module_types = index != module_index
+                && gen
+                    .declared_items
+                    .iter()
+                    .any(|item| item.is_type() && item.is_used_by_c_header(c_header));
+            if uses_module_types {
+                includes += &format!("#include \"{}.h\"\n", file_name);
+            }
+        }
+
+        includes
     }
 
     /// Concatenate all of the generated Swift code into one file.
@@ -108,13 +620,96 @@ impl GeneratedCode {
     }
 }
 
-fn parse_file_contents(file: &str) -> syn::Result<GeneratedFromSwiftBridgeModule> {
+/// Write the file, unless it already has these contents.
+///
+/// Rewriting a file that didn't change would update its modification time, which would make
+/// Xcode and `swiftc` recompile it.
+pub(crate) fn write_if_changed(
+    path: impl AsRef<Path>,
+    contents: impl AsRef<[u8]>,
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let contents = contents.as_ref();
+
+    let unchanged = std::fs::read(path)
+        .map(|existing| existing == contents)
+        .unwrap_or(false);
+    if unchanged {
+        return Ok(());
+    }
+
+    std::fs::write(path, contents)
+}
+
+/// Panic if the name can't be used as the name of a Clang module.
+pub(crate) fn assert_valid_module_name(module_name: &str) {
+    let is_valid_module_name = module_name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && module_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_valid_module_name {
+        panic!(
+            "{:?} is not a valid Clang module name. Module names must be identifiers.",
+            module_name
+        );
+    }
+}
+
+/// Wrap a generated C header in an include guard and an `extern "C"` block, so that it can be
+/// included more than once and from C++.
+///
+/// The `includes` go inside the include guard but outside of the `extern "C"` block.
+pub(crate) fn wrap_c_header(header_name: &str, includes: &str, header: &str) -> String {
+    let guard: String = header_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let guard = format!("SWIFT_BRIDGE_{}", guard);
+
+    format!(
+        r#"#ifndef {guard}
+#define {guard}
+
+{includes}#ifdef __cplusplus
+extern "C" {{
+#endif
+
+{header}
+
+#ifdef __cplusplus
+}}
+#endif
+
+#endif /* {guard} */
+"#,
+        guard = guard,
+        includes = if includes.is_empty() {
+            "".to_string()
+        } else {
+            format!("{}\n\n", includes.trim_end())
+        },
+        header = header.trim_end()
+    )
+}
+
+fn parse_file_contents(
+    file: &str,
+    file_stem: &str,
+    bridge_crate: &BridgeCrate,
+) -> syn::Result<Vec<GeneratedFromSwiftBridgeModule>> {
     let file: File = syn::parse_str(file)?;
 
-    let mut generated = GeneratedFromSwiftBridgeModule {
-        c_header: "".to_string(),
-        swift: "".to_string(),
-    };
+    let mut generated = vec![];
 
     for item in file.items {
         match item {
@@ -125,10 +720,38 @@ fn parse_file_contents(file: &str) -> syn::Result<GeneratedFromSwiftBridgeModule
                     let attrib = a.path.to_token_stream().to_string();
                     attrib == "swift_bridge :: bridge" || attrib == "swift_bridge_macro :: bridge"
                 }) {
-                    let module: SwiftBridgeModule = syn::parse2(module.to_token_stream())?;
+                    let module_name = module.ident.to_string();
+
+                    // The module's own `swift_access` attribute comes after this one, so it
+                    // takes precedence.
+                    let mut module = module;
+                    if let Some(access) = bridge_crate.swift_access.as_ref() {
+                        module.attrs.insert(
+                            0,
+                            syn::parse_quote!(#[swift_bridge(swift_access = #access)]),
+                        );
+                    }
+
+                    let mut module: SwiftBridgeModule = syn::parse2(module.to_token_stream())?;
 
+                    // Use the same prefix as the `#[swift_bridge::bridge]` macro.
+                    if let Some(prefix) = bridge_crate.symbol_prefix.as_ref() {
+                        module.set_configured_symbol_prefix(prefix);
+                    }
+                    if let Some(prefix) = bridge_crate.private_type_prefix.as_ref() {
+                        module.set_private_type_prefix(prefix);
+                    }
+                    if bridge_crate.library_evolution {
+                        module.set_library_evolution();
+                    }
+
+                    let enabled_features = bridge_crate.enabled_features.clone();
                     let config = CodegenConfig {
-                        crate_feature_lookup: Box::new(|feature_name| {
+                        crate_feature_lookup: Box::new(move |feature_name| {
+                            if let Some(enabled_features) = enabled_features.as_ref() {
+                                return enabled_features.iter().any(|f| f == feature_name);
+                            }
+
                             let normalized_feature_name = feature_name.replace("-", "_");
                             let normalized_feature_name = normalized_feature_name.to_uppercase();
 
@@ -136,14 +759,20 @@ fn parse_file_contents(file: &str) -> syn::Result<GeneratedFromSwiftBridgeModule
                             std::env::var(env_var_name).is_ok()
                         }),
                     };
+                    let declared_items = module
+                        .declared_names(&config)
+                        .into_iter()
+                        .map(DeclaredItem::from)
+                        .collect();
                     let swift_and_c = module.generate_swift_code_and_c_header(config);
 
-                    generated.c_header += &swift_and_c.c_header;
-                    generated.c_header += "\n\n";
-
-                    let swift = &swift_and_c.swift;
-                    generated.swift += &swift;
-                    generated.swift += "\n\n";
+                    generated.push(GeneratedFromSwiftBridgeModule {
+                        file_stem: file_stem.to_string(),
+                        module_name,
+                        c_header: format!("{}\n\n", swift_and_c.c_header),
+                        swift: format!("{}\n\n", swift_and_c.swift),
+                        declared_items,
+                    });
                 }
             }
             _ => {}
@@ -155,6 +784,114 @@ fn parse_file_contents(file: &str) -> syn::Result<GeneratedFromSwiftBridgeModule
 
 #[derive(Debug)]
 struct GeneratedFromSwiftBridgeModule {
+    /// The name of the Rust source file that the module was declared in, without its extension.
+    file_stem: String,
+    module_name: String,
     c_header: String,
     swift: String,
+    /// The module's types and free functions, used to detect collisions with other modules.
+    declared_items: Vec<DeclaredItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::generated_code;
+
+    /// Verify that we wrap a header in an include guard that is named after the header and an
+    /// `extern "C"` block, with the includes between them.
+    #[test]
+    fn wrap_c_header_in_include_guard() {
+        let header = wrap_c_header(
+            "my-crate.h",
+            "#include \"other.h\"\n",
+            "void some_function(void);\n\n",
+        );
+
+        assert_eq!(
+            header,
+            r#"#ifndef SWIFT_BRIDGE_MY_CRATE_H
+#define SWIFT_BRIDGE_MY_CRATE_H
+
+#include "other.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void some_function(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SWIFT_BRIDGE_MY_CRATE_H */
+"#
+        );
+
+        assert!(wrap_c_header("my-crate.h", "", "").starts_with(
+            "#ifndef SWIFT_BRIDGE_MY_CRATE_H\n#define SWIFT_BRIDGE_MY_CRATE_H\n\n#ifdef __cplusplus\n"
+        ));
+    }
+
+    /// Verify that each bridge module's header includes `SwiftBridgeCore.h` and the headers of the
+    /// other bridge modules whose types it uses, but not the headers of the ones it doesn't use.
+    #[test]
+    fn per_module_headers_include_the_headers_they_use() {
+        let generated = generated_code(
+            r#"
+#[swift_bridge::bridge]
+mod structs {
+    #[swift_bridge(swift_repr = "struct")]
+    struct SomeStruct {
+        field: u8,
+    }
+}
+
+#[swift_bridge::bridge]
+mod enums {
+    enum SomeEnum {
+        Variant,
+    }
+}
+
+#[swift_bridge::bridge]
+mod uses_struct {
+    #[swift_bridge(already_declared)]
+    struct SomeStruct;
+
+    extern "Rust" {
+        fn some_function(arg: Option<SomeStruct>);
+    }
+}
+"#,
+        );
+
+        let mut writer = InMemoryWriter::new();
+        generated
+            .write_per_module_to(&mut writer, "my_crate")
+            .unwrap();
+
+        let core_include = r#"#if __has_include("../SwiftBridgeCore.h")
+#include "../SwiftBridgeCore.h"
+#else
+#include "SwiftBridgeCore.h"
+#endif
+"#;
+
+        let uses_struct = &writer.files[Path::new("my_crate/lib_uses_struct.h")];
+        assert!(uses_struct.contains(core_include));
+        assert!(uses_struct.contains("#include \"lib_structs.h\"\n"));
+        assert!(!uses_struct.contains("#include \"lib_enums.h\""));
+        assert!(
+            uses_struct.find("#include \"lib_structs.h\"").unwrap()
+                < uses_struct.find("extern \"C\"").unwrap()
+        );
+
+        for header in ["my_crate/lib_structs.h", "my_crate/lib_enums.h"] {
+            let header = &writer.files[Path::new(header)];
+            assert!(header.contains(core_include));
+            assert!(!header.contains("#include \"lib_"));
+        }
+    }
 }
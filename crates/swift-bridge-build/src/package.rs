@@ -2,8 +2,6 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::fs::OpenOptions;
-use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::tempdir;
@@ -18,6 +16,9 @@ pub struct CreatePackageConfig {
     pub out_dir: PathBuf,
     /// The name for the Swift package
     pub package_name: String,
+    /// The system libraries and frameworks that the Rust library needs to be linked against.
+    /// e.g. `SwiftLinkerSetting::LinkedFramework("Security".to_string())`
+    pub linker_settings: Vec<SwiftLinkerSetting>,
 }
 
 impl CreatePackageConfig {
@@ -33,6 +34,29 @@ impl CreatePackageConfig {
             paths,
             out_dir,
             package_name,
+            linker_settings: vec![],
+        }
+    }
+}
+
+/// A library or framework that the Swift Package's target links against.
+///
+/// Rust static libraries don't carry their system dependencies with them, so any system library
+/// or framework that the Rust code uses needs to be linked by the Swift Package.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum SwiftLinkerSetting {
+    /// A system library, such as `resolv` for `-lresolv`.
+    LinkedLibrary(String),
+    /// A framework, such as `Security` or `SystemConfiguration`.
+    LinkedFramework(String),
+}
+
+impl SwiftLinkerSetting {
+    /// The `Package.swift` linker setting. e.g. `.linkedFramework("Security")`
+    pub fn package_swift_setting(&self) -> String {
+        match self {
+            SwiftLinkerSetting::LinkedLibrary(name) => format!(".linkedLibrary({:?})", name),
+            SwiftLinkerSetting::LinkedFramework(name) => format!(".linkedFramework({:?})", name),
         }
     }
 }
@@ -52,18 +76,32 @@ pub enum ApplePlatform {
     /// no official Rust target for this platform
     MacCatalyst,
     /// `aarch64-apple-tvos`
-    /// `x86_64-apple-tvos`
     TvOS,
-    /// no official Rust target for this platform
+    /// `aarch64-apple-tvos-sim`
+    TvOSSimulator,
+    /// `arm64_32-apple-watchos`
+    /// `aarch64-apple-watchos`
     WatchOS,
-    /// no official Rust target for this platform
+    /// `aarch64-apple-watchos-sim`
     WatchOSSimulator,
+    /// `aarch64-apple-visionos`
+    VisionOS,
+    /// `aarch64-apple-visionos-sim`
+    VisionOSSimulator,
     /// no official Rust target for this platform
     CarPlayOS,
     /// no official Rust target for this platform
     CarPlayOSSimulator,
 }
 
+/// Rust targets that rustup doesn't have a prebuilt standard library for, so they get built with
+/// `-Zbuild-std`, which needs a nightly toolchain.
+pub(crate) const BUILD_STD_TARGETS: &[&str] = &[
+    "arm64_32-apple-watchos",
+    "x86_64-apple-tvos",
+    "x86_64-apple-watchos-sim",
+];
+
 impl ApplePlatform {
     /// The directory name inside of the xcframework for the specified platform.
     pub fn dir_name(&self) -> &str {
@@ -73,13 +111,55 @@ impl ApplePlatform {
             ApplePlatform::MacOS => "macos",
             ApplePlatform::MacCatalyst => "mac-catalyst",
             ApplePlatform::TvOS => "tvos",
+            ApplePlatform::TvOSSimulator => "tvos-simulator",
             ApplePlatform::WatchOS => "watchos",
             ApplePlatform::WatchOSSimulator => "watchos-simulator",
+            ApplePlatform::VisionOS => "visionos",
+            ApplePlatform::VisionOSSimulator => "visionos-simulator",
             ApplePlatform::CarPlayOS => "carplay",
             ApplePlatform::CarPlayOSSimulator => "carplay-simulator",
         }
     }
 
+    /// The Rust targets that get built for the platform by [`build_package`].
+    ///
+    /// The libraries of platforms with more than one target get combined into one universal
+    /// library using `lipo`.
+    ///
+    /// `arm64_32-apple-watchos`, for Apple Watches with 32 bit pointers, doesn't have a prebuilt
+    /// standard library, so building for [`ApplePlatform::WatchOS`] needs a nightly toolchain.
+    pub fn rust_targets(&self) -> &'static [&'static str] {
+        match self {
+            ApplePlatform::IOS => &["aarch64-apple-ios"],
+            ApplePlatform::Simulator => &["aarch64-apple-ios-sim", "x86_64-apple-ios"],
+            ApplePlatform::MacOS => &["aarch64-apple-darwin", "x86_64-apple-darwin"],
+            ApplePlatform::TvOS => &["aarch64-apple-tvos"],
+            ApplePlatform::TvOSSimulator => &["aarch64-apple-tvos-sim"],
+            ApplePlatform::WatchOS => &["arm64_32-apple-watchos", "aarch64-apple-watchos"],
+            ApplePlatform::WatchOSSimulator => &["aarch64-apple-watchos-sim"],
+            ApplePlatform::VisionOS => &["aarch64-apple-visionos"],
+            ApplePlatform::VisionOSSimulator => &["aarch64-apple-visionos-sim"],
+            ApplePlatform::MacCatalyst
+            | ApplePlatform::CarPlayOS
+            | ApplePlatform::CarPlayOSSimulator => &[],
+        }
+    }
+
+    /// The name of the platform's SDK, as in `xcrun --sdk {sdk_name}`.
+    pub fn sdk_name(&self) -> &'static str {
+        match self {
+            ApplePlatform::IOS | ApplePlatform::CarPlayOS => "iphoneos",
+            ApplePlatform::Simulator | ApplePlatform::CarPlayOSSimulator => "iphonesimulator",
+            ApplePlatform::MacOS | ApplePlatform::MacCatalyst => "macosx",
+            ApplePlatform::TvOS => "appletvos",
+            ApplePlatform::TvOSSimulator => "appletvsimulator",
+            ApplePlatform::WatchOS => "watchos",
+            ApplePlatform::WatchOSSimulator => "watchsimulator",
+            ApplePlatform::VisionOS => "xros",
+            ApplePlatform::VisionOSSimulator => "xrsimulator",
+        }
+    }
+
     /// Array containing all `ApplePlatform` variants
     pub const ALL: &'static [Self] = &[
         ApplePlatform::IOS,
@@ -87,13 +167,168 @@ impl ApplePlatform {
         ApplePlatform::MacOS,
         ApplePlatform::MacCatalyst,
         ApplePlatform::TvOS,
+        ApplePlatform::TvOSSimulator,
         ApplePlatform::WatchOS,
         ApplePlatform::WatchOSSimulator,
+        ApplePlatform::VisionOS,
+        ApplePlatform::VisionOSSimulator,
         ApplePlatform::CarPlayOS,
         ApplePlatform::CarPlayOSSimulator,
     ];
 }
 
+/// Config for building a Rust crate into a Swift Package
+pub struct BuildPackageConfig {
+    /// The directory containing the crate's `Cargo.toml`
+    pub crate_dir: PathBuf,
+    /// The platforms to build the crate for
+    pub platforms: Vec<ApplePlatform>,
+    /// Whether to build the crate using `--release`
+    pub release: bool,
+    /// The crate features to build and generate code for
+    pub features: Vec<String>,
+    /// The directory where the package will be saved
+    pub out_dir: PathBuf,
+    /// The name for the Swift package
+    pub package_name: String,
+    /// The system libraries and frameworks that the Rust library needs to be linked against.
+    pub linker_settings: Vec<SwiftLinkerSetting>,
+}
+
+/// Builds the crate's static library for every platform, generates the Swift and C code for its
+/// bridge modules and then generates an xcframework embedded in a Swift Package from them.
+///
+/// The crate must use `crate-type = ["staticlib"]`. Its libraries are built into
+/// `{crate_dir}/target`. The `symbol_prefix` and `swift_access` in the crate's `swift-bridge.toml`
+/// are used if it has one.
+///
+/// ```no_run
+/// use swift_bridge_build::{build_package, ApplePlatform, BuildPackageConfig};
+///
+/// build_package(BuildPackageConfig {
+///     crate_dir: "./my-rust-lib".into(),
+///     platforms: vec![ApplePlatform::IOS, ApplePlatform::Simulator, ApplePlatform::MacOS],
+///     release: true,
+///     features: vec![],
+///     out_dir: "./MySwiftPackage".into(),
+///     package_name: "MySwiftPackage".to_string(),
+///     linker_settings: vec![],
+/// });
+/// ```
+pub fn build_package(config: BuildPackageConfig) {
+    let crate_dir: &Path = config.crate_dir.as_ref();
+    let crate_name = crate::read_crate_name(crate_dir);
+    let target_dir = crate_dir.join("target");
+    let profile = if config.release { "release" } else { "debug" };
+
+    let swift_bridge_config = crate::SwiftBridgeConfig::load(crate_dir)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .unwrap_or_default();
+    let bridge_crate = crate::BridgeCrate {
+        enabled_features: Some(config.features.clone()),
+        symbol_prefix: swift_bridge_config.symbol_prefix,
+        private_type_prefix: swift_bridge_config.private_type_prefix,
+        swift_access: swift_bridge_config.swift_access,
+        library_evolution: swift_bridge_config.library_evolution,
+    };
+
+    let bridge_dir = tempdir().expect("Couldn't create temporary directory");
+    crate::parse_bridges_with_crate(crate::crate_source_files(crate_dir), &bridge_crate, None)
+        .write_all_concatenated(bridge_dir.path(), &crate_name);
+
+    let mut paths = HashMap::new();
+    for platform in config.platforms.iter() {
+        let targets = platform.rust_targets();
+        if targets.is_empty() {
+            panic!(
+                "There is no Rust target for {:?}. Build its library yourself and use `create_package`.",
+                platform
+            );
+        }
+
+        let mut libs = vec![];
+        for target in targets {
+            cargo_build_lib(
+                crate_dir,
+                &target_dir,
+                target,
+                config.release,
+                &config.features,
+            );
+            libs.push(
+                target_dir
+                    .join(target)
+                    .join(profile)
+                    .join(crate::static_library_file_name(&crate_name, target)),
+            );
+        }
+
+        let lib = if libs.len() == 1 {
+            libs.pop().unwrap()
+        } else {
+            let universal_lib = target_dir
+                .join(format!("universal-{}", platform.dir_name()))
+                .join(profile)
+                .join(libs[0].file_name().unwrap());
+            crate::create_universal_library(crate::UniversalLibraryConfig {
+                libraries: libs,
+                output: universal_lib.clone(),
+                bridge_dir: Some(bridge_dir.path().to_path_buf()),
+            });
+            universal_lib
+        };
+
+        paths.insert(*platform, lib);
+    }
+
+    create_package(CreatePackageConfig {
+        bridge_dir: bridge_dir.path().to_path_buf(),
+        paths,
+        out_dir: config.out_dir,
+        package_name: config.package_name,
+        linker_settings: config.linker_settings,
+    });
+}
+
+/// `cargo build --lib --target {target}` the crate.
+pub(crate) fn cargo_build_lib(
+    crate_dir: &Path,
+    target_dir: &Path,
+    target: &str,
+    release: bool,
+    features: &[String],
+) {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command
+        .arg("build")
+        .arg("--lib")
+        .arg("--manifest-path")
+        .arg(crate_dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(target_dir)
+        .arg("--target")
+        .arg(target)
+        // An `SDKROOT` inherited from an Xcode build is for one platform, so let rustc and the
+        // `cc` crate find the SDK of each target instead.
+        .env_remove("SDKROOT");
+    if BUILD_STD_TARGETS.contains(&target) {
+        command.arg("-Zbuild-std");
+    }
+    if release {
+        command.arg("--release");
+    }
+    if !features.is_empty() {
+        command.arg("--features").arg(features.join(","));
+    }
+
+    let status = command
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to run cargo build for {}: {}", target, e));
+    if !status.success() {
+        panic!("cargo build failed for {}", target);
+    }
+}
+
 /// Generates an xcframework embedded in a Swift Package from the Rust project.
 ///
 /// - Also see the [relevant book chapter](https://chinedufn.github.io/swift-bridge/building/swift-packages/index.html)
@@ -105,14 +340,119 @@ pub fn create_package(config: CreatePackageConfig) {
     }
 
     // Generate RustXcframework //
-    gen_xcframework(&output_dir, &config);
+    gen_xcframework(
+        &output_dir,
+        config.bridge_dir.as_ref(),
+        &config.paths,
+        "RustXcframework",
+    );
 
     // Generate Swift Package //
     gen_package(&output_dir, &config);
 }
 
-/// Generates the RustXcframework
-fn gen_xcframework(output_dir: &Path, config: &CreatePackageConfig) {
+/// Config for generating an xcframework
+pub struct CreateXcframeworkConfig {
+    /// The directory containing the generated bridges
+    pub bridge_dir: PathBuf,
+    /// Libraries per platform. e.g. `(ApplePlatform::Simulator, vec!["target/aarch64-apple-ios-sim/debug/libmy_rust_lib.a", "target/x86_64-apple-ios/debug/libmy_rust_lib.a"])`
+    ///
+    /// The libraries of a platform with more than one library get combined into one universal
+    /// library using `lipo`.
+    pub libraries: HashMap<ApplePlatform, Vec<PathBuf>>,
+    /// The directory where the xcframework will be saved
+    pub out_dir: PathBuf,
+    /// The name of the xcframework and of the Clang module for its headers
+    pub module_name: String,
+}
+
+/// Generates an xcframework from per-platform static libraries and the generated C headers, and
+/// returns its path.
+///
+/// The xcframework is written to `{out_dir}/{module_name}.xcframework` and contains a library
+/// for each platform along with the generated headers and a `module.modulemap` for them.
+///
+/// The generated Swift can't be part of an xcframework of static libraries. Add the generated
+/// Swift files to the Swift target that links the xcframework, and `import {module_name}` at the
+/// top of them.
+///
+/// ```no_run
+/// use std::collections::HashMap;
+/// use swift_bridge_build::{create_xcframework, ApplePlatform, CreateXcframeworkConfig};
+///
+/// create_xcframework(CreateXcframeworkConfig {
+///     bridge_dir: "./generated".into(),
+///     libraries: HashMap::from([
+///         (
+///             ApplePlatform::IOS,
+///             vec!["target/aarch64-apple-ios/debug/libmy_rust_lib.a".into()],
+///         ),
+///         (
+///             ApplePlatform::Simulator,
+///             vec![
+///                 "target/aarch64-apple-ios-sim/debug/libmy_rust_lib.a".into(),
+///                 "target/x86_64-apple-ios/debug/libmy_rust_lib.a".into(),
+///             ],
+///         ),
+///     ]),
+///     out_dir: "./build".into(),
+///     module_name: "MyRustLib".to_string(),
+/// });
+/// ```
+pub fn create_xcframework(config: CreateXcframeworkConfig) -> PathBuf {
+    crate::assert_valid_module_name(&config.module_name);
+
+    let output_dir: &Path = config.out_dir.as_ref();
+    if !&output_dir.exists() {
+        fs::create_dir_all(&output_dir).expect("Couldn't create output directory");
+    }
+
+    let temp_dir = tempdir().expect("Couldn't create temporary directory");
+
+    let mut paths = HashMap::new();
+    for (platform, libs) in config.libraries.iter() {
+        for lib in libs {
+            if !lib.exists() {
+                panic!("The {:?} library {:?} does not exist", platform, lib);
+            }
+        }
+
+        let lib = match libs.as_slice() {
+            [] => panic!("There are no libraries for {:?}", platform),
+            [lib] => lib.clone(),
+            _ => {
+                let universal_lib = temp_dir
+                    .path()
+                    .join(platform.dir_name())
+                    .join(libs[0].file_name().unwrap());
+                crate::create_universal_library(crate::UniversalLibraryConfig {
+                    libraries: libs.clone(),
+                    output: universal_lib.clone(),
+                    bridge_dir: Some(config.bridge_dir.clone()),
+                });
+                universal_lib
+            }
+        };
+        paths.insert(*platform, lib);
+    }
+
+    gen_xcframework(
+        output_dir,
+        config.bridge_dir.as_ref(),
+        &paths,
+        &config.module_name,
+    );
+
+    output_dir.join(format!("{}.xcframework", config.module_name))
+}
+
+/// Generates the xcframework
+fn gen_xcframework(
+    output_dir: &Path,
+    bridge_dir: &Path,
+    paths: &HashMap<ApplePlatform, PathBuf>,
+    module_name: &str,
+) {
     // Create directories
     let temp_dir = tempdir().expect("Couldn't create temporary directory");
     let tmp_framework_path = &temp_dir.path().join("swiftbridge._tmp_framework");
@@ -123,67 +463,20 @@ fn gen_xcframework(output_dir: &Path, config: &CreatePackageConfig) {
         fs::create_dir(&include_dir).expect("Couldn't create inlcude directory for xcframework");
     }
 
+    let bridge_project_header = copy_headers(bridge_dir, &include_dir);
+
     // Create modulemap
-    let modulemap_path = include_dir.join("module.modulemap");
     fs::write(
-        &modulemap_path,
-        "module RustXcframework {\n    header \"SwiftBridgeCore.h\"\n",
+        include_dir.join("module.modulemap"),
+        format!(
+            "module {} {{\n    header \"SwiftBridgeCore.h\"\n    header \"{}\"\n    export *\n}}\n",
+            module_name, bridge_project_header
+        ),
     )
     .expect("Couldn't write modulemap file");
-    let mut modulemap_file = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .open(&modulemap_path)
-        .expect("Couldn't open modulemap file for writing");
-
-    // copy headers
-    let bridge_dir: &Path = config.bridge_dir.as_ref();
-    fs::copy(
-        bridge_dir.join("SwiftBridgeCore.h"),
-        &include_dir.join("SwiftBridgeCore.h"),
-    )
-    .expect("Couldn't copy SwiftBirdgeCore header file");
-    let bridge_project_dir = fs::read_dir(&bridge_dir)
-        .expect("Couldn't read generated directory")
-        .find_map(|file| {
-            let file = file.unwrap().path();
-            if file.is_dir() {
-                Some(file)
-            } else {
-                None
-            }
-        })
-        .expect("Couldn't find project directory inside of generated directory");
-    let bridge_project_header_dir = fs::read_dir(&bridge_project_dir)
-        .expect("Couldn't read generated directory")
-        .find_map(|file| {
-            let file = file.unwrap().path();
-            if file.extension().unwrap() == "h" {
-                Some(file)
-            } else {
-                None
-            }
-        })
-        .expect("Couldn't find project's header file");
-    fs::copy(
-        &bridge_project_header_dir,
-        &include_dir.join(&bridge_project_header_dir.file_name().unwrap()),
-    )
-    .expect("Couldn't copy project's header file");
-    writeln!(
-        modulemap_file,
-        "    header \"{}\"",
-        bridge_project_header_dir
-            .file_name()
-            .unwrap()
-            .to_str()
-            .unwrap()
-    )
-    .expect("Couldn't write to modulemap");
-    writeln!(modulemap_file, "    export *\n}}").expect("Couldn't write to modulemap");
 
     // Copy libraries
-    for platform in &config.paths {
+    for platform in paths {
         let platform_path = &tmp_framework_path.join(platform.0.dir_name());
         if !platform_path.exists() {
             fs::create_dir(&platform_path).expect(&format!(
@@ -200,7 +493,7 @@ fn gen_xcframework(output_dir: &Path, config: &CreatePackageConfig) {
     }
 
     // build xcframework
-    let xcframework_dir = output_dir.join("RustXcframework.xcframework");
+    let xcframework_dir = output_dir.join(format!("{}.xcframework", module_name));
     if xcframework_dir.exists() {
         fs::remove_dir_all(&xcframework_dir).expect("Couldn't delete previous xcframework file");
     }
@@ -208,7 +501,7 @@ fn gen_xcframework(output_dir: &Path, config: &CreatePackageConfig) {
 
     let mut args: Vec<String> = Vec::new();
     args.push("-create-xcframework".to_string());
-    for platform in &config.paths {
+    for platform in paths {
         let file_path = Path::new(platform.0.dir_name())
             .join((platform.1.as_ref() as &Path).file_name().unwrap());
 
@@ -249,67 +542,136 @@ fn gen_xcframework(output_dir: &Path, config: &CreatePackageConfig) {
     }
 }
 
-/// Generates the Swift Package.
-///
-/// We copy the Swift files from our generated bridge dir into the Swift Package's Sources
-/// directory. We prepend `import RustXcframework` at the top of all of the Swift files inside of
-/// the package, since without this they'll all error due to not being able to see the Rust code
-/// that they depend on.
-/// The alternative would be to use something like `@_exported import RustXcframework`, but this
-/// would make the Rust xcframework (i.e. methods like __swift_bridge__$some_method) available to
-/// the Swift Package's consumer, which we don't want.
-fn gen_package(output_dir: &Path, config: &CreatePackageConfig) {
-    let sources_dir = output_dir.join("Sources").join(&config.package_name);
-    if !sources_dir.exists() {
-        fs::create_dir_all(&sources_dir).expect("Couldn't create directory for source files");
+/// Copy `SwiftBridgeCore.h` and the project's headers from the generated bridge dir to the
+/// include directory, and return the file name of the project's header.
+pub(crate) fn copy_headers(bridge_dir: &Path, include_dir: &Path) -> String {
+    fs::copy(
+        bridge_dir.join("SwiftBridgeCore.h"),
+        &include_dir.join("SwiftBridgeCore.h"),
+    )
+    .expect("Couldn't copy SwiftBirdgeCore header file");
+    let bridge_project_dir = bridge_project_dir(bridge_dir);
+    // There is one header per bridge module when the bridges were written using
+    // `GeneratedCode::write_per_module`, which are all included by the project's header.
+    let mut bridge_project_headers: Vec<PathBuf> = fs::read_dir(&bridge_project_dir)
+        .expect("Couldn't read generated directory")
+        .filter_map(|file| {
+            let file = file.unwrap().path();
+            if file.extension().unwrap() == "h" {
+                Some(file)
+            } else {
+                None
+            }
+        })
+        .collect();
+    bridge_project_headers.sort();
+    for header in bridge_project_headers.iter() {
+        fs::copy(header, &include_dir.join(header.file_name().unwrap()))
+            .expect("Couldn't copy project's header file");
     }
+    let bridge_project_header = bridge_project_headers
+        .iter()
+        .find(|header| header.file_stem() == bridge_project_dir.file_name())
+        .or(bridge_project_headers.first())
+        .expect("Couldn't find project's header file");
 
-    // Copy bridge `.swift` files and append import statements
-    let bridge_dir: &Path = config.bridge_dir.as_ref();
+    bridge_project_header
+        .file_name()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Copy the generated `.swift` files to the sources directory, prepending `import {module_name}`
+/// to each of them so that they can see the Rust library's C headers.
+pub(crate) fn copy_swift_sources(bridge_dir: &Path, sources_dir: &Path, module_name: &str) {
     fs::write(
         sources_dir.join("SwiftBridgeCore.swift"),
         format!(
-            "import RustXcframework\n{}",
+            "import {}\n{}",
+            module_name,
             fs::read_to_string(&bridge_dir.join("SwiftBridgeCore.swift"))
                 .expect("Couldn't read core bridging swift file")
         ),
     )
     .expect("Couldn't write core bridging swift file");
 
-    let bridge_project_dir = fs::read_dir(&bridge_dir)
+    // There is one Swift file per bridge module when the bridges were written using
+    // `GeneratedCode::write_per_module`.
+    let bridge_project_swift_files: Vec<PathBuf> = fs::read_dir(&bridge_project_dir(bridge_dir))
         .expect("Couldn't read generated directory")
-        .find_map(|file| {
+        .filter_map(|file| {
             let file = file.unwrap().path();
-            if file.is_dir() {
+            if file.extension().unwrap() == "swift" {
                 Some(file)
             } else {
                 None
             }
         })
-        .expect("Couldn't find project directory inside of generated directory");
-    let bridge_project_swift_dir = fs::read_dir(&bridge_project_dir)
+        .collect();
+    if bridge_project_swift_files.is_empty() {
+        panic!("Couldn't find project's bridging swift file");
+    }
+    for swift_file in bridge_project_swift_files.iter() {
+        fs::write(
+            sources_dir.join(swift_file.file_name().unwrap()),
+            format!(
+                "import {}\n{}",
+                module_name,
+                fs::read_to_string(swift_file)
+                    .expect("Couldn't read project's bridging swift file")
+            ),
+        )
+        .expect("Couldn't copy project's bridging swift file to the package");
+    }
+}
+
+/// The `{crate_name}` directory inside of the generated bridge dir.
+fn bridge_project_dir(bridge_dir: &Path) -> PathBuf {
+    fs::read_dir(&bridge_dir)
         .expect("Couldn't read generated directory")
         .find_map(|file| {
             let file = file.unwrap().path();
-            if file.extension().unwrap() == "swift" {
+            if file.is_dir() {
                 Some(file)
             } else {
                 None
             }
         })
-        .expect("Couldn't find project's bridging swift file");
-    fs::write(
-        sources_dir.join(&bridge_project_swift_dir.file_name().unwrap()),
-        format!(
-            "import RustXcframework\n{}",
-            fs::read_to_string(&bridge_project_swift_dir)
-                .expect("Couldn't read project's bridging swift file")
-        ),
-    )
-    .expect("Couldn't copy project's bridging swift file to the package");
+        .expect("Couldn't find project directory inside of generated directory")
+}
+
+/// Generates the Swift Package.
+///
+/// We copy the Swift files from our generated bridge dir into the Swift Package's Sources
+/// directory. We prepend `import RustXcframework` at the top of all of the Swift files inside of
+/// the package, since without this they'll all error due to not being able to see the Rust code
+/// that they depend on.
+/// The alternative would be to use something like `@_exported import RustXcframework`, but this
+/// would make the Rust xcframework (i.e. methods like __swift_bridge__$some_method) available to
+/// the Swift Package's consumer, which we don't want.
+fn gen_package(output_dir: &Path, config: &CreatePackageConfig) {
+    let sources_dir = output_dir.join("Sources").join(&config.package_name);
+    if !sources_dir.exists() {
+        fs::create_dir_all(&sources_dir).expect("Couldn't create directory for source files");
+    }
+
+    // Copy bridge `.swift` files and append import statements
+    copy_swift_sources(config.bridge_dir.as_ref(), &sources_dir, "RustXcframework");
 
     // Generate Package.swift
     let package_name = &config.package_name;
+    let linker_settings = if config.linker_settings.is_empty() {
+        "".to_string()
+    } else {
+        let settings: Vec<String> = config
+            .linker_settings
+            .iter()
+            .map(|setting| setting.package_swift_setting())
+            .collect();
+        format!(",\n\t\t\tlinkerSettings: [{}]", settings.join(", "))
+    };
     let package_swift = format!(
         r#"// swift-tools-version:5.5.0
 import PackageDescription
@@ -328,7 +690,7 @@ let package = Package(
 		),
 		.target(
 			name: "{package_name}",
-			dependencies: ["RustXcframework"])
+			dependencies: ["RustXcframework"]{linker_settings})
 	]
 )
 	"#
@@ -337,3 +699,91 @@ let package = Package(
     fs::write(output_dir.join("Package.swift"), package_swift)
         .expect("Couldn't write Package.swift file");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generated_code, BRIDGE_MODULE};
+
+    /// Verify that we write the linker settings the way that `Package.swift` expects them.
+    #[test]
+    fn linker_settings() {
+        assert_eq!(
+            SwiftLinkerSetting::LinkedLibrary("resolv".to_string()).package_swift_setting(),
+            r#".linkedLibrary("resolv")"#
+        );
+        assert_eq!(
+            SwiftLinkerSetting::LinkedFramework("Security".to_string()).package_swift_setting(),
+            r#".linkedFramework("Security")"#
+        );
+    }
+
+    /// Verify that we copy every bridge module's header and Swift file when the code was written
+    /// per module, and return the header that includes all of them.
+    #[test]
+    fn copy_per_module_headers_and_swift_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let bridge_dir = dir.path().join("generated");
+        generated_code(&format!(
+            "{}\n{}",
+            BRIDGE_MODULE,
+            BRIDGE_MODULE.replace("mod ffi", "mod other")
+        ))
+        .write_per_module(&bridge_dir, "my_crate");
+
+        let include_dir = dir.path().join("include");
+        let sources_dir = dir.path().join("Sources");
+        fs::create_dir_all(&include_dir).unwrap();
+        fs::create_dir_all(&sources_dir).unwrap();
+
+        assert_eq!(copy_headers(&bridge_dir, &include_dir), "my_crate.h");
+        copy_swift_sources(&bridge_dir, &sources_dir, "RustXcframework");
+
+        for header in [
+            "SwiftBridgeCore.h",
+            "my_crate.h",
+            "lib_ffi.h",
+            "lib_other.h",
+        ] {
+            assert!(include_dir.join(header).exists(), "{}", header);
+        }
+        for swift_file in ["SwiftBridgeCore.swift", "lib_ffi.swift", "lib_other.swift"] {
+            let swift = fs::read_to_string(sources_dir.join(swift_file)).unwrap();
+            assert!(
+                swift.starts_with("import RustXcframework\n"),
+                "{}",
+                swift_file
+            );
+        }
+    }
+
+    /// Verify that the package's target depends on the xcframework and links the configured
+    /// libraries and frameworks.
+    #[test]
+    fn package_swift_links_libraries() {
+        let dir = tempfile::tempdir().unwrap();
+        let bridge_dir = dir.path().join("generated");
+        generated_code(BRIDGE_MODULE).write_all_concatenated(&bridge_dir, "my_crate");
+
+        let out_dir = dir.path().join("MySwiftPackage");
+        let mut config = CreatePackageConfig::new(
+            bridge_dir,
+            HashMap::new(),
+            out_dir.clone(),
+            "MySwiftPackage".to_string(),
+        );
+        config.linker_settings = vec![
+            SwiftLinkerSetting::LinkedLibrary("resolv".to_string()),
+            SwiftLinkerSetting::LinkedFramework("Security".to_string()),
+        ];
+        gen_package(&out_dir, &config);
+
+        let package_swift = fs::read_to_string(out_dir.join("Package.swift")).unwrap();
+        assert!(package_swift.contains(
+            "dependencies: [\"RustXcframework\"],\n\t\t\tlinkerSettings: [.linkedLibrary(\"resolv\"), .linkedFramework(\"Security\")])"
+        ));
+        assert!(out_dir
+            .join("Sources/MySwiftPackage/my_crate.swift")
+            .exists());
+    }
+}
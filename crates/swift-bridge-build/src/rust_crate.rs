@@ -0,0 +1,141 @@
+//! Find the name and the source files of a Rust crate, for generating code outside of the crate's
+//! build script.
+
+use std::path::{Path, PathBuf};
+
+/// Read the package name from the `Cargo.toml` in the crate's directory.
+///
+/// ```no_run
+/// let crate_name = swift_bridge_build::read_crate_name("./my-crate");
+/// ```
+pub fn read_crate_name(crate_dir: impl AsRef<Path>) -> String {
+    let cargo_toml_path = crate_dir.as_ref().join("Cargo.toml");
+    let cargo_toml = std::fs::read_to_string(&cargo_toml_path)
+        .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", cargo_toml_path, e));
+
+    package_name(&cargo_toml)
+        .unwrap_or_else(|| panic!("{:?} does not have a package name", cargo_toml_path))
+}
+
+/// The file name of the static library that `cargo build` writes for the crate and Rust target,
+/// such as `libmy_crate.a` for `aarch64-apple-ios` and `my_crate.lib` for
+/// `x86_64-pc-windows-msvc`.
+///
+/// ```
+/// use swift_bridge_build::static_library_file_name;
+///
+/// assert_eq!(static_library_file_name("my-crate", "aarch64-apple-ios"), "libmy_crate.a");
+/// assert_eq!(static_library_file_name("my-crate", "x86_64-pc-windows-msvc"), "my_crate.lib");
+/// ```
+pub fn static_library_file_name(crate_name: &str, rust_target: &str) -> String {
+    let lib_name = crate_name.replace('-', "_");
+
+    if rust_target.ends_with("-windows-msvc") {
+        format!("{}.lib", lib_name)
+    } else {
+        format!("lib{}.a", lib_name)
+    }
+}
+
+/// Every `.rs` file in the crate's `src` directory, sorted by path.
+pub fn crate_source_files(crate_dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    let mut rust_files = vec![];
+    collect_rust_files(&crate_dir.as_ref().join("src"), &mut rust_files);
+    rust_files.sort();
+
+    rust_files
+}
+
+/// Get the `name` from the `[package]` section of a Cargo.toml.
+fn package_name(cargo_toml: &str) -> Option<String> {
+    let mut in_package_section = false;
+
+    for line in cargo_toml.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            in_package_section = line == "[package]";
+            continue;
+        }
+        if !in_package_section {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == "name" {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+
+    None
+}
+
+/// Recursively collect the `.rs` files in a directory.
+fn collect_rust_files(dir: &Path, rust_files: &mut Vec<PathBuf>) {
+    let entries =
+        std::fs::read_dir(dir).unwrap_or_else(|e| panic!("Failed to read {:?}: {}", dir, e));
+
+    for entry in entries {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            collect_rust_files(&path, rust_files);
+        } else if path.extension().map(|ext| ext == "rs").unwrap_or(false) {
+            rust_files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::dir_with_files;
+
+    /// Verify that we read the name from the `[package]` section and not from other sections.
+    #[test]
+    fn read_package_name() {
+        let dir = dir_with_files(&[(
+            "Cargo.toml",
+            r#"
+[workspace]
+name = "not-the-package"
+
+[package]
+version = "0.1.0"
+name = "my-crate"
+
+[dependencies]
+name = "not-the-package-either"
+"#,
+        )]);
+
+        assert_eq!(read_crate_name(dir.path()), "my-crate");
+    }
+
+    /// Verify that we find every `.rs` file in `src`, sorted by path.
+    #[test]
+    fn source_files_are_sorted() {
+        let dir = dir_with_files(&[
+            ("src/lib.rs", ""),
+            ("src/b/mod.rs", ""),
+            ("src/a.rs", ""),
+            ("src/README.md", ""),
+            ("tests/test.rs", ""),
+        ]);
+
+        let files: Vec<PathBuf> = crate_source_files(dir.path())
+            .into_iter()
+            .map(|file| file.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect();
+
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("src/a.rs"),
+                PathBuf::from("src/b/mod.rs"),
+                PathBuf::from("src/lib.rs"),
+            ]
+        );
+    }
+}
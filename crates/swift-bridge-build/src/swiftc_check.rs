@@ -0,0 +1,284 @@
+//! Check the written Swift code with `swiftc`, so that invalid generated code fails the Cargo
+//! build instead of the Xcode build.
+
+use crate::GeneratedCode;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::tempdir;
+
+/// How to check the generated Swift code with `swiftc` after it is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwiftcCheck {
+    /// Don't check the generated Swift code.
+    None,
+    /// Check the syntax of the generated Swift code using `swiftc -parse`.
+    ///
+    /// The path is the `swiftc` executable, such as `PathBuf::from("swiftc")`.
+    Parse(PathBuf),
+    /// Type check the generated Swift code against the generated C headers using
+    /// `swiftc -typecheck`.
+    ///
+    /// The path is the `swiftc` executable, such as `PathBuf::from("swiftc")`.
+    Typecheck(PathBuf),
+}
+
+impl Default for SwiftcCheck {
+    fn default() -> Self {
+        SwiftcCheck::None
+    }
+}
+
+impl GeneratedCode {
+    /// Check the generated Swift code with `swiftc` after [`Self::write_all_concatenated`] or
+    /// [`Self::write_per_module`] writes it, and panic with the errors and the bridge items that
+    /// they are in if it doesn't compile.
+    ///
+    /// The code is checked for the host, so it doesn't need the SDK of the platform that Cargo is
+    /// building for. Code written using a [`crate::GeneratedWriter`] isn't checked.
+    ///
+    /// ```no_run
+    /// # use std::path::PathBuf;
+    /// # use swift_bridge_build::SwiftcCheck;
+    /// swift_bridge_build::parse_bridges(vec!["src/lib.rs"])
+    ///     .check_with_swiftc(SwiftcCheck::Typecheck(PathBuf::from("swiftc")))
+    ///     .write_all_concatenated("./generated", env!("CARGO_PKG_NAME"));
+    /// ```
+    pub fn check_with_swiftc(mut self, check: SwiftcCheck) -> Self {
+        self.swiftc_check = check;
+        self
+    }
+
+    /// Run the [`SwiftcCheck`] on the Swift files, which are relative to the generated code
+    /// directory.
+    pub(crate) fn run_swiftc_check(&self, dir: &Path, crate_name: &str, swift_files: &[PathBuf]) {
+        let (swiftc, typecheck) = match &self.swiftc_check {
+            SwiftcCheck::None => return,
+            SwiftcCheck::Parse(swiftc) => (swiftc, false),
+            SwiftcCheck::Typecheck(swiftc) => (swiftc, true),
+        };
+
+        let mut command = Command::new(swiftc);
+        command
+            .arg(if typecheck { "-typecheck" } else { "-parse" })
+            .args(swift_files.iter().map(|file| dir.join(file)))
+            // An `SDKROOT` inherited from Cargo or Xcode is for the platform being built, such as
+            // iOS, which doesn't match the host that we check the code for.
+            .env_remove("SDKROOT");
+
+        let temp_dir = tempdir().expect("Couldn't create temporary directory");
+        if typecheck {
+            match &self.module_map {
+                // The generated Swift imports the module.
+                Some(_) => {
+                    command.arg("-I").arg(dir);
+                }
+                None => {
+                    let bridging_header = temp_dir.path().join("bridging-header.h");
+                    std::fs::write(
+                        &bridging_header,
+                        format!(
+                            "#include \"{}\"\n#include \"{}\"\n",
+                            dir.join("SwiftBridgeCore.h").display(),
+                            dir.join(crate_name)
+                                .join(format!("{}.h", crate_name))
+                                .display()
+                        ),
+                    )
+                    .expect("Couldn't write bridging header");
+                    command.arg("-import-objc-header").arg(&bridging_header);
+                }
+            }
+        }
+
+        let output = command
+            .output()
+            .unwrap_or_else(|e| panic!("Failed to run {:?}: {}", swiftc, e));
+        if output.status.success() {
+            return;
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut message = format!("\n{:?} found errors in the generated Swift code.\n", swiftc);
+        for error in stderr.lines().filter_map(SwiftcError::parse) {
+            message += &format!(
+                "\nerror: {}\n  --> {}:{}\n",
+                error.message, error.file, error.line
+            );
+            if let Some(location) = self.generated_item_location(&error) {
+                message += &format!("  {}\n", location);
+            }
+        }
+        message += &format!("\n{}", stderr);
+
+        panic!("{}", message);
+    }
+
+    /// Where the Swift code with the error was generated from, such as
+    /// ``in `SomeType`, generated for the `ffi` bridge module in lib.rs``.
+    fn generated_item_location(&self, error: &SwiftcError) -> Option<String> {
+        let swift = std::fs::read_to_string(error.file).ok()?;
+
+        // The closest declaration that isn't indented, such as the class or the `@_cdecl`
+        // function that the error is in.
+        let (declaration, item) = swift
+            .lines()
+            .take(error.line)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .filter(|line| !line.starts_with(char::is_whitespace))
+            .find_map(|line| declared_item(line).map(|item| (line, item)))?;
+
+        let module = self
+            .generated
+            .iter()
+            .find(|gen| gen.swift.lines().any(|line| line == declaration));
+
+        Some(match module {
+            Some(gen) => format!(
+                "in `{}`, generated for the `{}` bridge module in {}.rs",
+                item, gen.module_name, gen.file_stem
+            ),
+            None => format!("in `{}`", item),
+        })
+    }
+}
+
+/// An `error:` line from `swiftc`'s output, such as
+/// `generated/my_crate/my_crate.swift:12:5: error: cannot find 'x' in scope`.
+struct SwiftcError<'a> {
+    file: &'a str,
+    line: usize,
+    message: &'a str,
+}
+
+impl<'a> SwiftcError<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let (location, message) = line.split_once(": error: ")?;
+
+        // Windows paths contain a `:`, so split from the end.
+        let mut location = location.rsplitn(3, ':');
+        let _column = location.next()?;
+        let line = location.next()?.parse().ok()?;
+        let file = location.next()?;
+
+        Some(SwiftcError {
+            file,
+            line,
+            message,
+        })
+    }
+}
+
+/// The name of the item that a line of Swift declares, such as `SomeType` for
+/// `public class SomeType: SomeTypeRefMut {`.
+fn declared_item(line: &str) -> Option<&str> {
+    let mut words = line.split_whitespace();
+    words.find(|word| {
+        matches!(
+            *word,
+            "func" | "class" | "struct" | "enum" | "extension" | "protocol" | "typealias"
+        )
+    })?;
+
+    let name = words.next()?;
+    let end = name
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(name.len());
+
+    Some(&name[..end]).filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generated_code, BRIDGE_MODULE};
+
+    /// Verify that we parse the location and message of `swiftc`'s errors, including ones in
+    /// files with a Windows path.
+    #[test]
+    fn parse_swiftc_error() {
+        let error =
+            SwiftcError::parse("generated/my_crate/my_crate.swift:12:5: error: cannot find 'x'")
+                .unwrap();
+        assert_eq!(error.file, "generated/my_crate/my_crate.swift");
+        assert_eq!(error.line, 12);
+        assert_eq!(error.message, "cannot find 'x'");
+
+        let error =
+            SwiftcError::parse(r"C:\generated\my_crate.swift:3:1: error: expected '}'").unwrap();
+        assert_eq!(error.file, r"C:\generated\my_crate.swift");
+        assert_eq!(error.line, 3);
+
+        assert!(SwiftcError::parse("my_crate.swift:12:5: warning: unused").is_none());
+        assert!(SwiftcError::parse("error: no input files").is_none());
+    }
+
+    /// Verify that we find the name of the item that a line of Swift declares.
+    #[test]
+    fn item_declared_by_line() {
+        assert_eq!(
+            declared_item("public class SomeType: SomeTypeRefMut {"),
+            Some("SomeType")
+        );
+        assert_eq!(
+            declared_item("@_cdecl(\"__swift_bridge__$f\") func __swift_bridge__f () {"),
+            Some("__swift_bridge__f")
+        );
+        assert_eq!(
+            declared_item("extension SomeType: Vectorizable {"),
+            Some("SomeType")
+        );
+        assert_eq!(declared_item("let x = 5"), None);
+        assert_eq!(declared_item("func"), None);
+    }
+
+    /// Verify that a failed check panics with `swiftc`'s errors and the bridge module that the
+    /// Swift code with the error was generated for.
+    #[cfg(unix)]
+    #[test]
+    fn failed_check_names_bridge_module() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+
+        // Reports an error on the line that declares `some_function` in the last file.
+        let swiftc = dir.path().join("swiftc");
+        std::fs::write(
+            &swiftc,
+            r#"#!/bin/sh
+for swift_file; do :; done
+line=$(grep -n "func some_function" "$swift_file" | cut -d: -f1)
+echo "$swift_file:$line:5: error: cannot find 'x' in scope" 1>&2
+exit 1
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(&swiftc, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let out_dir = dir.path().join("generated");
+        let generated = generated_code(BRIDGE_MODULE).check_with_swiftc(SwiftcCheck::Parse(swiftc));
+
+        let panic = std::panic::catch_unwind(|| {
+            generated.write_all_concatenated(&out_dir, "my_crate");
+        })
+        .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+
+        assert!(message.contains("found errors in the generated Swift code."));
+        assert!(message.contains("\nerror: cannot find 'x' in scope\n"));
+        assert!(message.contains("my_crate/my_crate.swift:"));
+        assert!(message
+            .contains("  in `some_function`, generated for the `ffi` bridge module in lib.rs\n"));
+    }
+
+    /// Verify that the Swift code isn't checked by default.
+    #[test]
+    fn no_check_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let generated = generated_code(BRIDGE_MODULE);
+
+        assert_eq!(generated.swiftc_check, SwiftcCheck::None);
+        generated.write_all_concatenated(dir.path(), "my_crate");
+    }
+}
@@ -0,0 +1,46 @@
+//! Helpers for the tests.
+
+use crate::{parse_file_contents, BridgeCrate, GeneratedCode};
+use tempfile::TempDir;
+
+/// A crate without a `swift-bridge.toml` and without any enabled features.
+pub(crate) fn bridge_crate() -> BridgeCrate {
+    BridgeCrate {
+        enabled_features: Some(vec![]),
+        symbol_prefix: None,
+        private_type_prefix: None,
+        swift_access: None,
+        library_evolution: false,
+    }
+}
+
+/// The code generated for the bridge modules in a `lib.rs` with the given contents.
+pub(crate) fn generated_code(lib_rs: &str) -> GeneratedCode {
+    let mut generated = GeneratedCode::new();
+    generated.generated = parse_file_contents(lib_rs, "lib", &bridge_crate()).unwrap();
+
+    generated
+}
+
+/// A bridge module with one function, named `some_function`.
+pub(crate) const BRIDGE_MODULE: &str = r#"
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn some_function();
+    }
+}
+"#;
+
+/// A new directory with the given files in it, such as a crate's `Cargo.toml` and `src/lib.rs`.
+pub(crate) fn dir_with_files(files: &[(&str, &str)]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+
+    for (path, contents) in files {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    dir
+}
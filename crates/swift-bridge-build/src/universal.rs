@@ -0,0 +1,279 @@
+//! Combine the static libraries of different architectures into one universal library.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Config for creating a universal library
+pub struct UniversalLibraryConfig {
+    /// The static libraries to combine, such as the `aarch64-apple-ios-sim` and `x86_64-apple-ios`
+    /// builds of a crate.
+    pub libraries: Vec<PathBuf>,
+    /// Where to write the universal library.
+    pub output: PathBuf,
+    /// The directory containing the generated bridges. If set, every library must define every
+    /// function that the generated C headers declare.
+    pub bridge_dir: Option<PathBuf>,
+}
+
+/// Combine static libraries that were built for different architectures into one universal
+/// library using `lipo`.
+///
+/// A universal library holds one slice per architecture, so the device and simulator builds
+/// (which are both `arm64`) need to go in separate universal libraries. This panics if two of the
+/// libraries contain the same architecture, or if a library is missing a function that the
+/// generated headers declare, which happens when it was built from different bridge modules than
+/// the headers were generated from.
+///
+/// ```no_run
+/// use swift_bridge_build::{create_universal_library, UniversalLibraryConfig};
+///
+/// create_universal_library(UniversalLibraryConfig {
+///     libraries: vec![
+///         "target/aarch64-apple-ios-sim/release/libmy_rust_lib.a".into(),
+///         "target/x86_64-apple-ios/release/libmy_rust_lib.a".into(),
+///     ],
+///     output: "target/universal-ios-simulator/release/libmy_rust_lib.a".into(),
+///     bridge_dir: Some("./generated".into()),
+/// });
+/// ```
+pub fn create_universal_library(config: UniversalLibraryConfig) {
+    if config.libraries.is_empty() {
+        panic!("There are no libraries to combine into {:?}", config.output);
+    }
+
+    let mut seen_archs: Vec<(String, &PathBuf)> = vec![];
+    for lib in config.libraries.iter() {
+        if !lib.exists() {
+            panic!("The library {:?} does not exist", lib);
+        }
+
+        for arch in library_archs(lib) {
+            if let Some((_, other)) = seen_archs.iter().find(|(seen, _)| seen == &arch) {
+                panic!(
+                    "{:?} and {:?} both contain {}. A universal library can only contain one library per architecture, so device and simulator libraries need to be combined separately.",
+                    other, lib, arch
+                );
+            }
+            seen_archs.push((arch, lib));
+        }
+    }
+
+    if let Some(bridge_dir) = config.bridge_dir.as_ref() {
+        let declared = declared_functions(bridge_dir);
+        for lib in config.libraries.iter() {
+            assert_defines_functions(lib, &declared);
+        }
+    }
+
+    if let Some(parent) = config.output.parent() {
+        std::fs::create_dir_all(parent).expect("Couldn't create universal library directory");
+    }
+
+    let output = Command::new("lipo")
+        .args(&config.libraries)
+        .arg("-create")
+        .arg("-output")
+        .arg(&config.output)
+        .output()
+        .expect("Failed to execute lipo");
+    if !output.status.success() {
+        let stderr = std::str::from_utf8(&output.stderr).unwrap();
+        panic!("{}", stderr);
+    }
+}
+
+/// The architectures in a library, such as `["arm64"]`.
+fn library_archs(lib: &Path) -> Vec<String> {
+    let output = Command::new("lipo")
+        .arg("-archs")
+        .arg(lib)
+        .output()
+        .expect("Failed to execute lipo");
+    if !output.status.success() {
+        let stderr = std::str::from_utf8(&output.stderr).unwrap();
+        panic!("{}", stderr);
+    }
+
+    std::str::from_utf8(&output.stdout)
+        .unwrap()
+        .split_whitespace()
+        .map(|arch| arch.to_string())
+        .collect()
+}
+
+/// The names of the functions that the generated C headers in the bridge directory declare.
+fn declared_functions(bridge_dir: &Path) -> HashSet<String> {
+    let mut headers = vec![];
+    collect_headers(bridge_dir, &mut headers);
+
+    let mut declared = HashSet::new();
+    for header in headers {
+        let header = std::fs::read_to_string(&header)
+            .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", header, e));
+        declared.extend(header.lines().filter_map(declared_function));
+    }
+
+    declared
+}
+
+/// The name of the function that a line of a generated C header declares, such as
+/// `__swift_bridge__$some_function` for
+/// `void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper));`.
+fn declared_function(line: &str) -> Option<String> {
+    let line = line.trim_end();
+    if !line.ends_with(");") || line.starts_with('#') || line.starts_with("typedef") {
+        return None;
+    }
+
+    // The first name followed by `(` is the function, later ones are callback parameters.
+    let name_end = line.find('(')?;
+    let name_start = line[..name_end]
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let name = &line[name_start..name_end];
+
+    // Every function that swift-bridge generates has a `$` in its name.
+    if name.contains('$') {
+        Some(name.to_string())
+    } else {
+        None
+    }
+}
+
+/// Panic if the library doesn't define one of the functions.
+fn assert_defines_functions(lib: &Path, declared: &HashSet<String>) {
+    let output = Command::new("nm")
+        .arg("-g")
+        .arg(lib)
+        .output()
+        .expect("Failed to execute nm");
+    if !output.status.success() {
+        let stderr = std::str::from_utf8(&output.stderr).unwrap();
+        panic!("{}", stderr);
+    }
+
+    // Lines look like `0000000000000000 T ___swift_bridge__$some_function`. Mach-O symbol names
+    // start with an extra `_`.
+    let defined: HashSet<&str> = std::str::from_utf8(&output.stdout)
+        .unwrap()
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace().rev();
+            let name = columns.next()?;
+            let kind = columns.next()?;
+            if kind == "U" {
+                None
+            } else {
+                Some(name)
+            }
+        })
+        .collect();
+
+    let mut missing: Vec<&String> = declared
+        .iter()
+        .filter(|name| {
+            !defined.contains(name.as_str()) && !defined.contains(format!("_{}", name).as_str())
+        })
+        .collect();
+    if !missing.is_empty() {
+        missing.sort();
+        panic!(
+            "{:?} does not define {} of the functions that the generated headers declare, such as {}. Was it built from the same bridge modules that the headers were generated from?",
+            lib,
+            missing.len(),
+            missing[0]
+        );
+    }
+}
+
+fn collect_headers(dir: &Path, headers: &mut Vec<PathBuf>) {
+    let entries =
+        std::fs::read_dir(dir).unwrap_or_else(|e| panic!("Failed to read {:?}: {}", dir, e));
+
+    for entry in entries {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            collect_headers(&path, headers);
+        } else if path.extension().map(|ext| ext == "h").unwrap_or(false) {
+            headers.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generated_code, BRIDGE_MODULE};
+    use crate::FileSystemWriter;
+
+    /// Verify that we find the function that a line declares, and skip everything else.
+    #[test]
+    fn function_declared_by_line() {
+        assert_eq!(
+            declared_function("void __swift_bridge__$some_function(void);"),
+            Some("__swift_bridge__$some_function".to_string())
+        );
+        assert_eq!(
+            declared_function(
+                "void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper));"
+            ),
+            Some("__swift_bridge__$some_function".to_string())
+        );
+        assert_eq!(
+            declared_function("struct RustStr __swift_bridge__$SomeType$name(void* self);  "),
+            Some("__swift_bridge__$SomeType$name".to_string())
+        );
+
+        assert_eq!(declared_function("void some_c_function(void);"), None);
+        assert_eq!(declared_function("#include \"SwiftBridgeCore.h\""), None);
+        assert_eq!(
+            declared_function("typedef void (*__swift_bridge__$Callback)(void);"),
+            None
+        );
+        assert_eq!(
+            declared_function("typedef struct __swift_bridge__$SomeStruct {"),
+            None
+        );
+    }
+
+    /// Verify that we find the functions that the generated headers declare.
+    #[test]
+    fn functions_declared_by_generated_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        generated_code(BRIDGE_MODULE)
+            .write_all_concatenated_to(&mut FileSystemWriter::new(dir.path()), "my_crate")
+            .unwrap();
+
+        let declared = declared_functions(dir.path());
+
+        assert!(declared.contains("__swift_bridge__$some_function"));
+        assert!(declared.contains("__swift_bridge__$RustString$new"));
+    }
+
+    /// Verify that we don't run `lipo` without any libraries.
+    #[test]
+    #[should_panic(expected = "There are no libraries to combine")]
+    fn no_libraries() {
+        create_universal_library(UniversalLibraryConfig {
+            libraries: vec![],
+            output: PathBuf::from("libmy_crate.a"),
+            bridge_dir: None,
+        });
+    }
+
+    /// Verify that we check that the libraries exist before running `lipo`.
+    #[test]
+    #[should_panic(expected = "does not exist")]
+    fn missing_library() {
+        let dir = tempfile::tempdir().unwrap();
+
+        create_universal_library(UniversalLibraryConfig {
+            libraries: vec![dir.path().join("libmissing.a")],
+            output: dir.path().join("libmy_crate.a"),
+            bridge_dir: None,
+        });
+    }
+}
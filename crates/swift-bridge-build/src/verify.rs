@@ -0,0 +1,398 @@
+//! Check that checked-in generated code is up to date with the bridge modules.
+
+use crate::config::try_generate_with_config;
+use crate::{GeneratedCode, InMemoryWriter};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use swift_bridge_ir::SwiftBridgeConfig;
+use tempfile::tempdir;
+
+/// A generated file that doesn't match the code that the bridge modules generate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFileDiff {
+    /// The file's path, relative to the generated code directory.
+    pub path: PathBuf,
+    /// How the file differs.
+    pub kind: GeneratedFileDiffKind,
+}
+
+/// How a generated file differs from the code that the bridge modules generate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeneratedFileDiffKind {
+    /// The file should exist, but it doesn't.
+    Missing,
+    /// The file is no longer generated, such as the files of a bridge module that was removed.
+    Unexpected,
+    /// The file's contents are out of date.
+    Changed {
+        /// The first line that differs, starting at 1.
+        line: usize,
+        /// The line that should be there, or `None` if the file should end before it.
+        expected: Option<String>,
+        /// The line that is there, or `None` if the file ends before it.
+        actual: Option<String>,
+    },
+}
+
+impl Display for GeneratedFileDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            GeneratedFileDiffKind::Missing => write!(f, "{} is missing", self.path.display()),
+            GeneratedFileDiffKind::Unexpected => {
+                write!(f, "{} is no longer generated", self.path.display())
+            }
+            GeneratedFileDiffKind::Changed {
+                line,
+                expected,
+                actual,
+            } => {
+                writeln!(f, "{} is out of date at line {}", self.path.display(), line)?;
+                writeln!(f, "- {}", actual.as_deref().unwrap_or("<end of file>"))?;
+                write!(f, "+ {}", expected.as_deref().unwrap_or("<end of file>"))
+            }
+        }
+    }
+}
+
+/// Generate the Swift and C code for the bridge modules in the given files and compare it to the
+/// code in `generated_dir`, which was written using
+/// [`GeneratedCode::write_all_concatenated`].
+///
+/// Nothing in `generated_dir` is changed. An empty list means that it is up to date. This is
+/// meant for crates that check in their generated code, so that their build can fail when the
+/// checked-in code is stale. It must be called from a build script, since the crate name is read
+/// from `CARGO_PKG_NAME`.
+///
+/// ```no_run
+/// // build.rs
+///
+/// fn main() {
+///     let diffs = swift_bridge_build::verify(vec!["src/lib.rs"], "./Generated");
+///     if !diffs.is_empty() {
+///         for diff in diffs {
+///             eprintln!("{}", diff);
+///         }
+///         panic!("The generated code is out of date.");
+///     }
+/// }
+/// ```
+pub fn verify(
+    rust_source_files: impl IntoIterator<Item = impl AsRef<Path>>,
+    generated_dir: impl AsRef<Path>,
+) -> Vec<GeneratedFileDiff> {
+    let crate_name = std::env::var("CARGO_PKG_NAME")
+        .expect("verify must be called from a build script. Use GeneratedCode::verify_all_concatenated instead.");
+
+    crate::parse_bridges(rust_source_files).verify_all_concatenated(generated_dir, &crate_name)
+}
+
+/// Generate the code as configured by the given config and compare it to the code in the
+/// config's `out_dir`, without changing it.
+///
+/// See [`crate::generate_with_config`].
+pub fn verify_with_config(
+    crate_dir: impl AsRef<Path>,
+    config: &SwiftBridgeConfig,
+) -> Vec<GeneratedFileDiff> {
+    let crate_dir = crate_dir.as_ref();
+    let generated_dir = config
+        .out_dir
+        .as_ref()
+        .map(|out_dir| crate_dir.join(out_dir))
+        .unwrap_or_else(|| panic!("{} must set out_dir", swift_bridge_ir::CONFIG_FILE_NAME));
+
+    let expected_dir = tempdir().expect("Couldn't create temporary directory");
+    let mut config = config.clone();
+    config.out_dir = Some(expected_dir.path().to_path_buf());
+    config.dep_file = None;
+    try_generate_with_config(crate_dir, &config).unwrap_or_else(|e| panic!("{}", e));
+
+    let mut expected_files = vec![];
+    collect_files(expected_dir.path(), Path::new(""), &mut expected_files);
+    let expected = expected_files
+        .into_iter()
+        .map(|file| {
+            let contents = std::fs::read_to_string(expected_dir.path().join(&file)).unwrap();
+            (file, contents)
+        })
+        .collect();
+
+    diff_files(&expected, &generated_dir)
+}
+
+impl GeneratedCode {
+    /// Compare the code to the code in `generated_dir`, which was written using
+    /// [`GeneratedCode::write_all_concatenated`], without changing it.
+    pub fn verify_all_concatenated(
+        &self,
+        generated_dir: impl AsRef<Path>,
+        crate_name: &str,
+    ) -> Vec<GeneratedFileDiff> {
+        let mut expected = InMemoryWriter::new();
+        self.write_all_concatenated_to(&mut expected, crate_name)
+            .unwrap();
+
+        diff_files(&expected.files, generated_dir.as_ref())
+    }
+
+    /// Compare the code to the code in `generated_dir`, which was written using
+    /// [`GeneratedCode::write_per_module`], without changing it.
+    pub fn verify_per_module(
+        &self,
+        generated_dir: impl AsRef<Path>,
+        crate_name: &str,
+    ) -> Vec<GeneratedFileDiff> {
+        let mut expected = InMemoryWriter::new();
+        self.write_per_module_to(&mut expected, crate_name).unwrap();
+
+        diff_files(&expected.files, generated_dir.as_ref())
+    }
+}
+
+/// Compare every expected file to the same file in `actual_dir`.
+///
+/// Files in `actual_dir` that aren't expected are only reported if they look like
+/// generated code and are in a directory that has generated code, so that other files that are
+/// kept next to the generated code aren't reported.
+fn diff_files(expected: &BTreeMap<PathBuf, String>, actual_dir: &Path) -> Vec<GeneratedFileDiff> {
+    let expected_files: Vec<&PathBuf> = expected.keys().collect();
+
+    let mut diffs = vec![];
+    for (file, expected) in expected.iter() {
+        let kind = match std::fs::read_to_string(actual_dir.join(file)) {
+            Ok(actual) => match first_difference(expected, &actual) {
+                Some(kind) => kind,
+                None => continue,
+            },
+            Err(_) => GeneratedFileDiffKind::Missing,
+        };
+        diffs.push(GeneratedFileDiff {
+            path: file.clone(),
+            kind,
+        });
+    }
+
+    let mut generated_dirs: Vec<&Path> = expected_files
+        .iter()
+        .map(|file| file.parent().unwrap())
+        .collect();
+    generated_dirs.dedup();
+
+    for dir in generated_dirs {
+        let entries = match std::fs::read_dir(actual_dir.join(dir)) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        let mut unexpected: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| dir.join(entry.file_name()))
+            .filter(|file| looks_generated(file) && !expected_files.contains(&file))
+            .collect();
+        unexpected.sort();
+
+        diffs.extend(unexpected.into_iter().map(|path| GeneratedFileDiff {
+            path,
+            kind: GeneratedFileDiffKind::Unexpected,
+        }));
+    }
+
+    diffs
+}
+
+fn first_difference(expected: &str, actual: &str) -> Option<GeneratedFileDiffKind> {
+    if expected == actual {
+        return None;
+    }
+
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+
+    loop {
+        let expected = expected_lines.next();
+        let actual = actual_lines.next();
+
+        if expected != actual || (expected.is_none() && actual.is_none()) {
+            return Some(GeneratedFileDiffKind::Changed {
+                line,
+                expected: expected.map(|line| line.to_string()),
+                actual: actual.map(|line| line.to_string()),
+            });
+        }
+
+        line += 1;
+    }
+}
+
+fn looks_generated(file: &Path) -> bool {
+    match file.extension().and_then(|extension| extension.to_str()) {
+        Some("swift") | Some("h") | Some("modulemap") => true,
+        _ => false,
+    }
+}
+
+fn collect_files(dir: &Path, relative_dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries =
+        std::fs::read_dir(dir).unwrap_or_else(|e| panic!("Failed to read {:?}: {}", dir, e));
+
+    for entry in entries {
+        let entry = entry.unwrap();
+        let relative_path = relative_dir.join(entry.file_name());
+
+        if entry.path().is_dir() {
+            collect_files(&entry.path(), &relative_path, files);
+        } else {
+            files.push(relative_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generated_code, BRIDGE_MODULE};
+    use crate::FileSystemWriter;
+
+    /// Write the code for the bridge module to a new directory.
+    fn written_code(generated: &GeneratedCode) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        generated
+            .write_all_concatenated_to(&mut FileSystemWriter::new(dir.path()), "my_crate")
+            .unwrap();
+
+        dir
+    }
+
+    /// Verify that there are no differences right after the code was written, and that files
+    /// that don't look generated are ignored.
+    #[test]
+    fn up_to_date_code_has_no_diffs() {
+        let generated = generated_code(BRIDGE_MODULE);
+        let dir = written_code(&generated);
+        std::fs::write(dir.path().join("my_crate/notes.txt"), "Not generated").unwrap();
+
+        assert_eq!(
+            generated.verify_all_concatenated(dir.path(), "my_crate"),
+            vec![]
+        );
+    }
+
+    /// Verify that we report the first line that differs in an out of date file, along with the
+    /// line that should be there.
+    #[test]
+    fn reports_first_changed_line() {
+        let generated = generated_code(BRIDGE_MODULE);
+        let dir = written_code(&generated);
+
+        let swift_file = dir.path().join("my_crate/my_crate.swift");
+        let swift = std::fs::read_to_string(&swift_file).unwrap();
+        let changed_line = swift
+            .lines()
+            .position(|line| line.contains("func some_function"))
+            .unwrap();
+        let expected = swift.lines().nth(changed_line).unwrap().to_string();
+        std::fs::write(
+            &swift_file,
+            swift.replace("func some_function", "func renamed_function"),
+        )
+        .unwrap();
+
+        let diffs = generated.verify_all_concatenated(dir.path(), "my_crate");
+
+        assert_eq!(
+            diffs,
+            vec![GeneratedFileDiff {
+                path: PathBuf::from("my_crate/my_crate.swift"),
+                kind: GeneratedFileDiffKind::Changed {
+                    line: changed_line + 1,
+                    expected: Some(expected.clone()),
+                    actual: Some(expected.replace("some_function", "renamed_function")),
+                },
+            }]
+        );
+        assert_eq!(
+            diffs[0].to_string(),
+            format!(
+                "my_crate/my_crate.swift is out of date at line {}\n- {}\n+ {}",
+                changed_line + 1,
+                expected.replace("some_function", "renamed_function"),
+                expected
+            )
+        );
+    }
+
+    /// Verify that we report a file that ends early, a missing file and a file that is no longer
+    /// generated.
+    #[test]
+    fn reports_truncated_missing_and_unexpected_files() {
+        let generated = generated_code(BRIDGE_MODULE);
+        let dir = written_code(&generated);
+
+        let header_file = dir.path().join("my_crate/my_crate.h");
+        let header = std::fs::read_to_string(&header_file).unwrap();
+        let first_line = header.lines().next().unwrap();
+        std::fs::write(&header_file, format!("{}\n", first_line)).unwrap();
+        std::fs::remove_file(dir.path().join("SwiftBridgeCore.swift")).unwrap();
+        std::fs::write(dir.path().join("my_crate/removed_module.swift"), "").unwrap();
+
+        let diffs = generated.verify_all_concatenated(dir.path(), "my_crate");
+
+        assert_eq!(
+            diffs,
+            vec![
+                GeneratedFileDiff {
+                    path: PathBuf::from("SwiftBridgeCore.swift"),
+                    kind: GeneratedFileDiffKind::Missing,
+                },
+                GeneratedFileDiff {
+                    path: PathBuf::from("my_crate/my_crate.h"),
+                    kind: GeneratedFileDiffKind::Changed {
+                        line: 2,
+                        expected: header.lines().nth(1).map(|line| line.to_string()),
+                        actual: None,
+                    },
+                },
+                GeneratedFileDiff {
+                    path: PathBuf::from("my_crate/removed_module.swift"),
+                    kind: GeneratedFileDiffKind::Unexpected,
+                },
+            ]
+        );
+        assert_eq!(diffs[0].to_string(), "SwiftBridgeCore.swift is missing");
+        assert_eq!(
+            diffs[1].to_string(),
+            format!(
+                "my_crate/my_crate.h is out of date at line 2\n- <end of file>\n+ {}",
+                header.lines().nth(1).unwrap()
+            )
+        );
+        assert_eq!(
+            diffs[2].to_string(),
+            "my_crate/removed_module.swift is no longer generated"
+        );
+    }
+
+    /// Verify that code written per module is compared to the per module files.
+    #[test]
+    fn verify_per_module() {
+        let generated = generated_code(BRIDGE_MODULE);
+        let dir = tempfile::tempdir().unwrap();
+        generated
+            .write_per_module_to(&mut FileSystemWriter::new(dir.path()), "my_crate")
+            .unwrap();
+
+        assert_eq!(generated.verify_per_module(dir.path(), "my_crate"), vec![]);
+
+        let diffs = generated.verify_all_concatenated(dir.path(), "my_crate");
+        assert_eq!(
+            diffs.last(),
+            Some(&GeneratedFileDiff {
+                path: PathBuf::from("my_crate/lib_ffi.swift"),
+                kind: GeneratedFileDiffKind::Unexpected,
+            })
+        );
+    }
+}
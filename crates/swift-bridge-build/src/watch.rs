@@ -0,0 +1,136 @@
+//! Regenerate the Swift and C code whenever a bridge source file changes.
+
+use crate::config::try_generate_with_config;
+use crate::{try_parse_bridges_with_crate, BridgeCrate};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use swift_bridge_ir::{SwiftBridgeConfig, CONFIG_FILE_NAME};
+
+/// How often to check the source files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Generate the Swift and C code for the bridge modules in the given files, and then regenerate
+/// it every time that one of the files changes.
+///
+/// This runs until the process is killed. Errors in the bridge modules are printed instead of
+/// stopping the watch, so you can fix them and save again.
+///
+/// The code is written using [`crate::GeneratedCode::write_all_concatenated`]. The crate name is
+/// used for the default symbol prefix, so it must be the name of the crate that the bridge
+/// modules are in.
+///
+/// ```no_run
+/// let files = swift_bridge_build::crate_source_files("./my-crate");
+/// swift_bridge_build::watch(files, "./Generated", "my-crate", &[]);
+/// ```
+pub fn watch(
+    rust_source_files: Vec<PathBuf>,
+    swift_bridge_out_dir: impl AsRef<Path>,
+    crate_name: &str,
+    enabled_features: &[String],
+) -> ! {
+    let swift_bridge_out_dir = swift_bridge_out_dir.as_ref();
+    let bridge_crate = BridgeCrate {
+        enabled_features: Some(enabled_features.to_vec()),
+        symbol_prefix: None,
+        private_type_prefix: None,
+        swift_access: None,
+        library_evolution: false,
+    };
+
+    watch_files(rust_source_files.clone(), || {
+        try_parse_bridges_with_crate(&rust_source_files, &bridge_crate, None).map(|generated| {
+            generated.write_all_concatenated(swift_bridge_out_dir, crate_name);
+            swift_bridge_out_dir.to_path_buf()
+        })
+    })
+}
+
+/// Generate the Swift and C code as configured by the given config, and then regenerate it every
+/// time that one of the crate's source files or its `swift-bridge.toml` changes.
+///
+/// The code is generated using [`crate::generate_with_config`].
+///
+/// ```no_run
+/// let config = swift_bridge_build::SwiftBridgeConfig::load("./my-crate")
+///     .unwrap()
+///     .unwrap_or_default();
+/// swift_bridge_build::watch_with_config("./my-crate", &config);
+/// ```
+pub fn watch_with_config(crate_dir: impl AsRef<Path>, config: &SwiftBridgeConfig) -> ! {
+    let crate_dir = crate_dir.as_ref();
+
+    let mut watched_files = crate::crate_source_files(crate_dir);
+    watched_files.push(crate_dir.join(CONFIG_FILE_NAME));
+
+    watch_files(watched_files, || {
+        try_generate_with_config(crate_dir, config)
+    })
+}
+
+/// Call `generate` whenever one of the files changes. `generate` returns the directory that the
+/// code was written to.
+fn watch_files(files: Vec<PathBuf>, mut generate: impl FnMut() -> Result<PathBuf, String>) -> ! {
+    let mut last_modified = None;
+
+    loop {
+        let modified = modified_times(&files);
+
+        if last_modified.as_ref() != Some(&modified) {
+            match generate() {
+                Ok(out_dir) => {
+                    eprintln!("swift-bridge: generated code in {:?}", out_dir);
+                }
+                Err(e) => {
+                    eprintln!("swift-bridge: {}", e);
+                }
+            };
+
+            last_modified = Some(modified);
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The last time that each file was modified, or `None` if the file can't be read.
+fn modified_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| {
+            std::fs::metadata(file)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the modified times change when a file is changed, and that a file that can't
+    /// be read doesn't stop the watch.
+    #[test]
+    fn modified_times_change_with_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "mod ffi {}").unwrap();
+        let files = vec![file.clone(), dir.path().join("missing.rs")];
+
+        let long_ago = SystemTime::UNIX_EPOCH + Duration::from_secs(60 * 60 * 24);
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(long_ago)
+            .unwrap();
+        assert_eq!(modified_times(&files), vec![Some(long_ago), None]);
+
+        std::fs::write(&file, "mod ffi { }").unwrap();
+        let modified = modified_times(&files);
+        assert_ne!(modified[0], Some(long_ago));
+        assert!(modified[0].is_some());
+        assert_eq!(modified[1], None);
+    }
+}
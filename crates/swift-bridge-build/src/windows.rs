@@ -0,0 +1,359 @@
+//! Generate a Swift Package that links a Rust static library on Windows.
+//!
+//! xcframeworks only exist on Apple platforms, so on Windows the generated headers and the Rust
+//! library get exposed to Swift through a system library target instead.
+
+use crate::package::{cargo_build_lib, copy_headers, copy_swift_sources};
+use crate::SwiftLinkerSetting;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+/// The name of the Clang module for the Rust library's headers, which the generated Swift files
+/// `import`.
+const RUST_LIB_MODULE_NAME: &str = "RustLib";
+
+/// The system libraries that the Rust standard library uses on Windows, which Swift needs to link
+/// since Rust static libraries don't carry their dependencies with them.
+///
+/// This is what `rustc --print native-static-libs` prints for the MSVC targets across recent Rust
+/// versions.
+pub const WINDOWS_SYSTEM_LIBRARIES: &[&str] = &[
+    "advapi32",
+    "bcrypt",
+    "kernel32",
+    "ntdll",
+    "userenv",
+    "ws2_32",
+    "synchronization",
+    "dbghelp",
+];
+
+/// The Windows architectures that [`build_windows_package`] can build for.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum WindowsPlatform {
+    /// `x86_64-pc-windows-msvc`
+    X86_64,
+    /// `aarch64-pc-windows-msvc`
+    Arm64,
+}
+
+impl WindowsPlatform {
+    /// The Rust target that gets built for the platform by [`build_windows_package`].
+    ///
+    /// The Swift toolchain for Windows links with the MSVC linker, so these are the MSVC targets.
+    pub fn rust_target(&self) -> &'static str {
+        match self {
+            WindowsPlatform::X86_64 => "x86_64-pc-windows-msvc",
+            WindowsPlatform::Arm64 => "aarch64-pc-windows-msvc",
+        }
+    }
+}
+
+/// Config for generating a Swift Package for Windows
+pub struct CreateWindowsPackageConfig {
+    /// The directory containing the generated bridges
+    pub bridge_dir: PathBuf,
+    /// The Rust static library. e.g. `target/x86_64-pc-windows-msvc/debug/my_rust_lib.lib`
+    pub library: PathBuf,
+    /// The directory where the package will be saved
+    pub out_dir: PathBuf,
+    /// The name for the Swift package
+    pub package_name: String,
+    /// The system libraries that the Rust library needs to be linked against, in addition to
+    /// [`WINDOWS_SYSTEM_LIBRARIES`].
+    pub linker_settings: Vec<SwiftLinkerSetting>,
+}
+
+/// Generates a Swift Package from a Rust static library that was built for Windows.
+///
+/// The package has a `RustLib` system library target, which holds the generated C headers and
+/// links the Rust library, and a `{package_name}` target with the generated Swift code. The Rust
+/// library is copied to the package's `lib` directory.
+///
+/// The `{package_name}` target passes the `lib` directory to the linker using `unsafeFlags`, so
+/// the package can be used as a local dependency but not as a versioned one.
+///
+/// ```no_run
+/// use swift_bridge_build::{create_windows_package, CreateWindowsPackageConfig};
+///
+/// create_windows_package(CreateWindowsPackageConfig {
+///     bridge_dir: "./generated".into(),
+///     library: "target/x86_64-pc-windows-msvc/debug/my_rust_lib.lib".into(),
+///     out_dir: "./MySwiftPackage".into(),
+///     package_name: "MySwiftPackage".to_string(),
+///     linker_settings: vec![],
+/// });
+/// ```
+pub fn create_windows_package(config: CreateWindowsPackageConfig) {
+    if !config.library.exists() {
+        panic!("The library {:?} does not exist", config.library);
+    }
+    let library_file_name = config.library.file_name().unwrap().to_str().unwrap();
+    let library_name = library_file_name
+        .strip_suffix(".lib")
+        .or_else(|| {
+            library_file_name
+                .strip_prefix("lib")
+                .and_then(|name| name.strip_suffix(".a"))
+        })
+        .unwrap_or_else(|| panic!("{:?} is not a static library", config.library));
+
+    let output_dir: &Path = config.out_dir.as_ref();
+    let rust_lib_dir = output_dir.join("Sources").join(RUST_LIB_MODULE_NAME);
+    let sources_dir = output_dir.join("Sources").join(&config.package_name);
+    let lib_dir = output_dir.join("lib");
+    for dir in [&rust_lib_dir, &sources_dir, &lib_dir] {
+        fs::create_dir_all(dir).expect("Couldn't create package directory");
+    }
+
+    // The system library target, which links the Rust library through the module map.
+    let bridge_project_header = copy_headers(&config.bridge_dir, &rust_lib_dir);
+    fs::write(
+        rust_lib_dir.join("module.modulemap"),
+        format!(
+            r#"module {RUST_LIB_MODULE_NAME} {{
+    header "SwiftBridgeCore.h"
+    header "{bridge_project_header}"
+    link "{library_name}"
+    export *
+}}
+"#
+        ),
+    )
+    .expect("Couldn't write modulemap file");
+
+    fs::copy(&config.library, lib_dir.join(library_file_name))
+        .expect("Couldn't copy the Rust library to the package");
+
+    copy_swift_sources(&config.bridge_dir, &sources_dir, RUST_LIB_MODULE_NAME);
+
+    // Generate Package.swift
+    let package_name = &config.package_name;
+    let mut linker_settings = vec![r#".unsafeFlags(["-L", packageDir + "lib"])"#.to_string()];
+    linker_settings.extend(
+        WINDOWS_SYSTEM_LIBRARIES
+            .iter()
+            .map(|lib| SwiftLinkerSetting::LinkedLibrary(lib.to_string()))
+            .chain(config.linker_settings.iter().cloned())
+            .map(|setting| setting.package_swift_setting()),
+    );
+    let linker_settings = linker_settings.join(", ");
+    let package_swift = format!(
+        r#"// swift-tools-version:5.5.0
+import PackageDescription
+
+// The directory containing this Package.swift, so that the linker can find the Rust library no
+// matter where the package gets built from.
+let packageDir = String(#filePath.dropLast("Package.swift".count))
+
+let package = Package(
+	name: "{package_name}",
+	products: [
+		.library(
+			name: "{package_name}",
+			targets: ["{package_name}"]),
+	],
+	dependencies: [],
+	targets: [
+		.systemLibrary(
+			name: "{RUST_LIB_MODULE_NAME}",
+			path: "Sources/{RUST_LIB_MODULE_NAME}"
+		),
+		.target(
+			name: "{package_name}",
+			dependencies: ["{RUST_LIB_MODULE_NAME}"],
+			linkerSettings: [{linker_settings}])
+	]
+)
+"#
+    );
+
+    fs::write(output_dir.join("Package.swift"), package_swift)
+        .expect("Couldn't write Package.swift file");
+}
+
+/// Config for building a Rust crate into a Swift Package for Windows
+pub struct BuildWindowsPackageConfig {
+    /// The directory containing the crate's `Cargo.toml`
+    pub crate_dir: PathBuf,
+    /// The architecture to build the crate for
+    pub platform: WindowsPlatform,
+    /// Whether to build the crate using `--release`
+    pub release: bool,
+    /// The crate features to build and generate code for
+    pub features: Vec<String>,
+    /// The directory where the package will be saved
+    pub out_dir: PathBuf,
+    /// The name for the Swift package
+    pub package_name: String,
+    /// The system libraries that the Rust library needs to be linked against, in addition to
+    /// [`WINDOWS_SYSTEM_LIBRARIES`].
+    pub linker_settings: Vec<SwiftLinkerSetting>,
+}
+
+/// Builds the crate's static library for Windows, generates the Swift and C code for its bridge
+/// modules and then generates a Swift Package from them using [`create_windows_package`].
+///
+/// The crate must use `crate-type = ["staticlib"]`. Its library is built into
+/// `{crate_dir}/target`. The `symbol_prefix` and `swift_access` in the crate's `swift-bridge.toml`
+/// are used if it has one.
+///
+/// ```no_run
+/// use swift_bridge_build::{build_windows_package, BuildWindowsPackageConfig, WindowsPlatform};
+///
+/// build_windows_package(BuildWindowsPackageConfig {
+///     crate_dir: "./my-rust-lib".into(),
+///     platform: WindowsPlatform::X86_64,
+///     release: true,
+///     features: vec![],
+///     out_dir: "./MySwiftPackage".into(),
+///     package_name: "MySwiftPackage".to_string(),
+///     linker_settings: vec![],
+/// });
+/// ```
+pub fn build_windows_package(config: BuildWindowsPackageConfig) {
+    let crate_dir: &Path = config.crate_dir.as_ref();
+    let crate_name = crate::read_crate_name(crate_dir);
+    let target_dir = crate_dir.join("target");
+    let target = config.platform.rust_target();
+    let profile = if config.release { "release" } else { "debug" };
+
+    let swift_bridge_config = crate::SwiftBridgeConfig::load(crate_dir)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .unwrap_or_default();
+    let bridge_crate = crate::BridgeCrate {
+        enabled_features: Some(config.features.clone()),
+        symbol_prefix: swift_bridge_config.symbol_prefix,
+        private_type_prefix: swift_bridge_config.private_type_prefix,
+        swift_access: swift_bridge_config.swift_access,
+        library_evolution: swift_bridge_config.library_evolution,
+    };
+
+    let bridge_dir = tempdir().expect("Couldn't create temporary directory");
+    let mut generated =
+        crate::parse_bridges_with_crate(crate::crate_source_files(crate_dir), &bridge_crate, None);
+    if swift_bridge_config.foundation_free {
+        generated = generated.foundation_free();
+    }
+    generated.write_all_concatenated(bridge_dir.path(), &crate_name);
+
+    cargo_build_lib(
+        crate_dir,
+        &target_dir,
+        target,
+        config.release,
+        &config.features,
+    );
+
+    create_windows_package(CreateWindowsPackageConfig {
+        bridge_dir: bridge_dir.path().to_path_buf(),
+        library: target_dir
+            .join(target)
+            .join(profile)
+            .join(crate::static_library_file_name(&crate_name, target)),
+        out_dir: config.out_dir,
+        package_name: config.package_name,
+        linker_settings: config.linker_settings,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generated_code, BRIDGE_MODULE};
+
+    /// Write the generated code and a placeholder library with the given file name.
+    fn bridge_dir_and_library(dir: &Path, library_file_name: &str) -> (PathBuf, PathBuf) {
+        let bridge_dir = dir.join("generated");
+        generated_code(BRIDGE_MODULE).write_all_concatenated(&bridge_dir, "my_crate");
+
+        let library = dir.join("target").join(library_file_name);
+        fs::create_dir_all(library.parent().unwrap()).unwrap();
+        fs::write(&library, "not really a library").unwrap();
+
+        (bridge_dir, library)
+    }
+
+    /// Verify that the package has a system library target that links the Rust library and a
+    /// target with the generated Swift code, which links the Windows system libraries.
+    #[test]
+    fn create_package_for_msvc_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bridge_dir, library) = bridge_dir_and_library(dir.path(), "my_crate.lib");
+        let out_dir = dir.path().join("MySwiftPackage");
+
+        create_windows_package(CreateWindowsPackageConfig {
+            bridge_dir,
+            library,
+            out_dir: out_dir.clone(),
+            package_name: "MySwiftPackage".to_string(),
+            linker_settings: vec![SwiftLinkerSetting::LinkedLibrary("crypt32".to_string())],
+        });
+
+        assert_eq!(
+            fs::read_to_string(out_dir.join("Sources/RustLib/module.modulemap")).unwrap(),
+            r#"module RustLib {
+    header "SwiftBridgeCore.h"
+    header "my_crate.h"
+    link "my_crate"
+    export *
+}
+"#
+        );
+        assert!(out_dir.join("Sources/RustLib/SwiftBridgeCore.h").exists());
+        assert!(out_dir.join("Sources/RustLib/my_crate.h").exists());
+        assert!(out_dir.join("lib/my_crate.lib").exists());
+
+        let swift =
+            fs::read_to_string(out_dir.join("Sources/MySwiftPackage/my_crate.swift")).unwrap();
+        assert!(swift.starts_with("import RustLib\n"));
+        assert!(out_dir
+            .join("Sources/MySwiftPackage/SwiftBridgeCore.swift")
+            .exists());
+
+        let package_swift = fs::read_to_string(out_dir.join("Package.swift")).unwrap();
+        assert!(package_swift.contains(
+            r#"linkerSettings: [.unsafeFlags(["-L", packageDir + "lib"]), .linkedLibrary("advapi32"), "#
+        ));
+        assert!(package_swift.contains(r#".linkedLibrary("dbghelp"), .linkedLibrary("crypt32")])"#));
+    }
+
+    /// Verify that a `lib{name}.a` library, such as one built by the GNU toolchain, is linked
+    /// by its name without the prefix and extension.
+    #[test]
+    fn create_package_for_gnu_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bridge_dir, library) = bridge_dir_and_library(dir.path(), "libmy_crate.a");
+        let out_dir = dir.path().join("MySwiftPackage");
+
+        create_windows_package(CreateWindowsPackageConfig {
+            bridge_dir,
+            library,
+            out_dir: out_dir.clone(),
+            package_name: "MySwiftPackage".to_string(),
+            linker_settings: vec![],
+        });
+
+        let module_map =
+            fs::read_to_string(out_dir.join("Sources/RustLib/module.modulemap")).unwrap();
+        assert!(module_map.contains("    link \"my_crate\"\n"));
+        assert!(out_dir.join("lib/libmy_crate.a").exists());
+    }
+
+    /// Verify that we don't package a library that isn't a static library.
+    #[test]
+    #[should_panic(expected = "is not a static library")]
+    fn reject_dynamic_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bridge_dir, library) = bridge_dir_and_library(dir.path(), "my_crate.dll");
+
+        create_windows_package(CreateWindowsPackageConfig {
+            bridge_dir,
+            library,
+            out_dir: dir.path().join("MySwiftPackage"),
+            package_name: "MySwiftPackage".to_string(),
+            linker_settings: vec![],
+        });
+    }
+}
@@ -0,0 +1,142 @@
+//! Where the generated Swift and C code gets written to.
+
+use crate::write_if_changed;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Receives the generated files from [`crate::GeneratedCode::write_all_concatenated_to`] and
+/// [`crate::GeneratedCode::write_per_module_to`].
+///
+/// Implement it to send the generated code somewhere other than a directory, such as a remote
+/// cache or a monorepo's own layout.
+///
+/// ```
+/// use std::path::Path;
+/// use swift_bridge_build::GeneratedWriter;
+///
+/// /// Writes the Swift files to one directory and the C headers to another.
+/// struct SplitWriter;
+///
+/// impl GeneratedWriter for SplitWriter {
+///     fn write(&mut self, path: &Path, contents: &str) -> std::io::Result<()> {
+///         let dir = if path.extension().map(|ext| ext == "swift").unwrap_or(false) {
+///             Path::new("Sources/Generated")
+///         } else {
+///             Path::new("include")
+///         };
+///         let path = dir.join(path);
+///
+///         std::fs::create_dir_all(path.parent().unwrap())?;
+///         std::fs::write(path, contents)
+///     }
+/// }
+/// ```
+pub trait GeneratedWriter {
+    /// Write a generated file. The path is relative to the generated code directory, such as
+    /// `SwiftBridgeCore.swift` or `my_crate/my_crate.h`.
+    fn write(&mut self, path: &Path, contents: &str) -> std::io::Result<()>;
+}
+
+/// Writes the generated files to a directory, creating it if needed.
+///
+/// Files that already have the same contents aren't rewritten, so that Xcode and `swiftc` don't
+/// recompile them.
+pub struct FileSystemWriter {
+    dir: PathBuf,
+}
+
+impl FileSystemWriter {
+    /// Write the generated files to the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileSystemWriter { dir: dir.into() }
+    }
+}
+
+impl GeneratedWriter for FileSystemWriter {
+    fn write(&mut self, path: &Path, contents: &str) -> std::io::Result<()> {
+        let path = self.dir.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        write_if_changed(path, contents)
+    }
+}
+
+/// Keeps the generated files in memory, such as for tests.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InMemoryWriter {
+    /// The generated files, by their path relative to the generated code directory.
+    pub files: BTreeMap<PathBuf, String>,
+}
+
+impl InMemoryWriter {
+    /// An empty writer.
+    pub fn new() -> Self {
+        InMemoryWriter::default()
+    }
+}
+
+impl GeneratedWriter for InMemoryWriter {
+    fn write(&mut self, path: &Path, contents: &str) -> std::io::Result<()> {
+        self.files.insert(path.to_path_buf(), contents.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn modified(path: &Path) -> SystemTime {
+        std::fs::metadata(path).unwrap().modified().unwrap()
+    }
+
+    /// Verify that we create the file's directories, and that we only rewrite a file when its
+    /// contents change.
+    #[test]
+    fn file_system_writer_only_rewrites_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = FileSystemWriter::new(dir.path());
+        let path = dir.path().join("my_crate/my_crate.h");
+
+        writer
+            .write(Path::new("my_crate/my_crate.h"), "// header")
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "// header");
+
+        let long_ago = SystemTime::UNIX_EPOCH + Duration::from_secs(60 * 60 * 24);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(long_ago)
+            .unwrap();
+
+        writer
+            .write(Path::new("my_crate/my_crate.h"), "// header")
+            .unwrap();
+        assert_eq!(modified(&path), long_ago);
+
+        writer
+            .write(Path::new("my_crate/my_crate.h"), "// changed header")
+            .unwrap();
+        assert_ne!(modified(&path), long_ago);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "// changed header");
+    }
+
+    /// Verify that the in memory writer keeps the latest contents of each file.
+    #[test]
+    fn in_memory_writer_keeps_latest_contents() {
+        let mut writer = InMemoryWriter::new();
+
+        writer.write(Path::new("a.swift"), "first").unwrap();
+        writer.write(Path::new("a.swift"), "second").unwrap();
+        writer.write(Path::new("b/b.h"), "header").unwrap();
+
+        assert_eq!(writer.files.len(), 2);
+        assert_eq!(writer.files[Path::new("a.swift")], "second");
+        assert_eq!(writer.files[Path::new("b/b.h")], "header");
+    }
+}
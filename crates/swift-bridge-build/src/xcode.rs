@@ -0,0 +1,489 @@
+//! Generate the files that an Xcode project needs in order to build and link a Rust crate.
+
+use crate::write_if_changed;
+use std::path::{Component, Path, PathBuf};
+use swift_bridge_ir::SwiftBridgeConfig;
+
+/// The name of the build script that the Xcode "Run Script" build phase calls.
+pub const XCODE_BUILD_SCRIPT_NAME: &str = "build-rust.sh";
+
+/// The name of the xcconfig that holds the build settings for linking the Rust library.
+pub const XCODE_XCCONFIG_NAME: &str = "swift-bridge.xcconfig";
+
+const BRIDGING_HEADER_BEGIN: &str =
+    "// swift-bridge imports begin. Automatically updated, don't edit.";
+const BRIDGING_HEADER_END: &str = "// swift-bridge imports end.";
+
+/// Config for generating the Xcode build script and xcconfig
+pub struct XcodeConfig {
+    /// The directory containing the `.xcodeproj`. The files are written to this directory.
+    pub project_dir: PathBuf,
+    /// The directory containing the crate's `Cargo.toml`
+    pub crate_dir: PathBuf,
+    /// The crate features to build
+    pub features: Vec<String>,
+    /// The directory containing the generated Swift and C code. Defaults to the `out_dir` in the
+    /// crate's `swift-bridge.toml`.
+    pub generated_dir: Option<PathBuf>,
+    /// An Objective-C bridging header to add the generated headers to using
+    /// [`update_bridging_header`].
+    pub bridging_header: Option<PathBuf>,
+}
+
+/// Write a `build-rust.sh` that builds the crate for the platform and architectures that Xcode is
+/// building for, and a `swift-bridge.xcconfig` with the build settings for linking to it.
+///
+/// The build script reads the `PLATFORM_NAME`, `ARCHS` and `CONFIGURATION` that Xcode sets, builds
+/// the crate for the matching Rust targets using `--release` for every configuration other than
+/// `Debug`, and combines the libraries using `lipo`.
+///
+/// The files are only rewritten when their contents change, so this can be called from the
+/// crate's `build.rs` to keep them up to date.
+///
+/// If there's a `bridging_header`, it gets updated after the code is generated.
+///
+/// ```no_run
+/// use swift_bridge_build::{write_xcode_files, XcodeConfig};
+///
+/// write_xcode_files(XcodeConfig {
+///     project_dir: "./MyApp".into(),
+///     crate_dir: ".".into(),
+///     features: vec![],
+///     generated_dir: Some("./MyApp/Generated".into()),
+///     bridging_header: None,
+/// });
+/// ```
+pub fn write_xcode_files(config: XcodeConfig) {
+    let project_dir: &Path = config.project_dir.as_ref();
+    let crate_dir: &Path = config.crate_dir.as_ref();
+
+    let crate_name = crate::read_crate_name(crate_dir);
+    let lib_name = crate_name.replace('-', "_");
+
+    let generated_dir = config.generated_dir.clone().or_else(|| {
+        SwiftBridgeConfig::load(crate_dir)
+            .unwrap_or_else(|e| panic!("{}", e))
+            .and_then(|swift_bridge_config| swift_bridge_config.out_dir)
+            .map(|out_dir| crate_dir.join(out_dir))
+    });
+
+    if let Some(bridging_header) = config.bridging_header.as_ref() {
+        let generated_dir = generated_dir.as_ref().unwrap_or_else(|| {
+            panic!("Updating the bridging header requires a generated_dir or a swift-bridge.toml out_dir")
+        });
+        update_bridging_header(bridging_header, generated_dir);
+    }
+
+    let crate_dir = relative_path(project_dir, crate_dir);
+    let generated_dir = generated_dir.map(|dir| relative_path(project_dir, &dir));
+
+    let features = if config.features.is_empty() {
+        "".to_string()
+    } else {
+        format!(" --features {}", config.features.join(","))
+    };
+
+    let build_script = build_script(&crate_name, &lib_name, &crate_dir, &features);
+    let build_script_path = project_dir.join(XCODE_BUILD_SCRIPT_NAME);
+    write_if_changed(&build_script_path, build_script).expect("Couldn't write Xcode build script");
+    make_executable(&build_script_path);
+
+    let xcconfig = xcconfig(&lib_name, &crate_dir, generated_dir.as_deref());
+    write_if_changed(project_dir.join(XCODE_XCCONFIG_NAME), xcconfig)
+        .expect("Couldn't write xcconfig");
+}
+
+/// Add an `#import` for every generated C header to an Objective-C bridging header, creating it if
+/// it doesn't exist.
+///
+/// The imports go in a block that gets replaced every time this is called, so headers that are no
+/// longer generated get removed and everything else in the bridging header is left as is. Call
+/// this after the code has been generated, since it imports the headers that are in
+/// `generated_dir` at the time.
+///
+/// ```no_run
+/// swift_bridge_build::update_bridging_header("MyApp/BridgingHeader.h", "MyApp/Generated");
+/// ```
+///
+/// ```objc
+/// // swift-bridge imports begin. Automatically updated, don't edit.
+/// #import "Generated/SwiftBridgeCore.h"
+/// #import "Generated/my_crate/my_crate.h"
+/// // swift-bridge imports end.
+/// ```
+pub fn update_bridging_header(bridging_header: impl AsRef<Path>, generated_dir: impl AsRef<Path>) {
+    let bridging_header = bridging_header.as_ref();
+    let generated_dir = generated_dir.as_ref();
+
+    let mut headers = vec![];
+    collect_headers(generated_dir, Path::new(""), &mut headers);
+    // The crate's headers use the types in SwiftBridgeCore.h, so it goes first.
+    headers.sort_by_key(|header| (header != Path::new("SwiftBridgeCore.h"), header.clone()));
+
+    let header_dir = match bridging_header.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(header_dir).expect("Couldn't create bridging header directory");
+    let generated_dir = relative_path(header_dir, generated_dir);
+
+    let mut imports = format!("{}\n", BRIDGING_HEADER_BEGIN);
+    for header in headers {
+        let path: Vec<String> = generated_dir
+            .join(header)
+            .components()
+            .filter(|component| *component != Component::CurDir)
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect();
+        imports += &format!("#import \"{}\"\n", path.join("/"));
+    }
+    imports += BRIDGING_HEADER_END;
+
+    let existing = std::fs::read_to_string(bridging_header).unwrap_or_default();
+    let updated = match (
+        existing.find(BRIDGING_HEADER_BEGIN),
+        existing.find(BRIDGING_HEADER_END),
+    ) {
+        (Some(begin), Some(end)) if begin < end => format!(
+            "{}{}{}",
+            &existing[..begin],
+            imports,
+            &existing[end + BRIDGING_HEADER_END.len()..]
+        ),
+        _ if existing.trim().is_empty() => format!("{}\n", imports),
+        _ => {
+            let separator = if existing.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            format!("{}{}{}\n", existing, separator, imports)
+        }
+    };
+
+    write_if_changed(bridging_header, updated).expect("Couldn't write bridging header");
+}
+
+/// The paths of the C headers in the directory, relative to the directory.
+fn collect_headers(dir: &Path, relative_dir: &Path, headers: &mut Vec<PathBuf>) {
+    let entries =
+        std::fs::read_dir(dir).unwrap_or_else(|e| panic!("Failed to read {:?}: {}", dir, e));
+
+    for entry in entries {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        let relative_path = relative_dir.join(entry.file_name());
+
+        if path.is_dir() {
+            collect_headers(&path, &relative_path, headers);
+        } else if path.extension().map(|ext| ext == "h").unwrap_or(false) {
+            headers.push(relative_path);
+        }
+    }
+}
+
+fn build_script(crate_name: &str, lib_name: &str, crate_dir: &Path, features: &str) -> String {
+    format!(
+        r#"#!/bin/bash
+# File automatically generated by swift-bridge.
+#
+# Builds the `{crate_name}` crate for the platform and architectures that Xcode is building for.
+# Call it from a "Run Script" build phase that comes before "Compile Sources":
+#
+#   "$PROJECT_DIR/{build_script_name}"
+
+set -euo pipefail
+
+CRATE_DIR="$PROJECT_DIR/{crate_dir}"
+LIB_NAME="lib{lib_name}.a"
+
+export PATH="$HOME/.cargo/bin:$PATH"
+
+# Xcode's environment points the linker at the target's SDK, which breaks linking build scripts
+# and proc macros for the host.
+if [[ -n "${{DEVELOPER_SDK_DIR:-}}" ]]; then
+    export LIBRARY_PATH="${{DEVELOPER_SDK_DIR}}/MacOSX.sdk/usr/lib:${{LIBRARY_PATH:-}}"
+fi
+
+rust_target() {{
+    case "$1-$2" in
+        iphoneos-arm64) echo "aarch64-apple-ios" ;;
+        iphonesimulator-arm64) echo "aarch64-apple-ios-sim" ;;
+        iphonesimulator-x86_64) echo "x86_64-apple-ios" ;;
+        maccatalyst-arm64) echo "aarch64-apple-ios-macabi" ;;
+        maccatalyst-x86_64) echo "x86_64-apple-ios-macabi" ;;
+        macosx-arm64) echo "aarch64-apple-darwin" ;;
+        macosx-x86_64) echo "x86_64-apple-darwin" ;;
+        appletvos-arm64) echo "aarch64-apple-tvos" ;;
+        appletvsimulator-arm64) echo "aarch64-apple-tvos-sim" ;;
+        appletvsimulator-x86_64) echo "x86_64-apple-tvos" ;;
+        watchos-arm64) echo "aarch64-apple-watchos" ;;
+        watchos-arm64_32) echo "arm64_32-apple-watchos" ;;
+        watchsimulator-arm64) echo "aarch64-apple-watchos-sim" ;;
+        watchsimulator-x86_64) echo "x86_64-apple-watchos-sim" ;;
+        xros-arm64) echo "aarch64-apple-visionos" ;;
+        xrsimulator-arm64) echo "aarch64-apple-visionos-sim" ;;
+        *)
+            echo "swift-bridge: there is no Rust target for $1 $2" 1>&2
+            exit 1
+            ;;
+    esac
+}}
+
+PLATFORM="$PLATFORM_NAME"
+if [[ "${{IS_MACCATALYST:-NO}}" == "YES" ]]; then
+    PLATFORM="maccatalyst"
+fi
+
+if [[ "$CONFIGURATION" == "Debug" ]]; then
+    PROFILE="debug"
+    RELEASE_FLAG=""
+else
+    PROFILE="release"
+    RELEASE_FLAG="--release"
+fi
+
+# The same directory name that Xcode uses for its build products, such as `Debug-iphonesimulator`.
+OUT_DIR="$CRATE_DIR/target/xcode/${{CONFIGURATION}}${{EFFECTIVE_PLATFORM_NAME:-}}"
+mkdir -p "$OUT_DIR"
+
+LIBS=()
+for ARCH in $ARCHS; do
+    TARGET="$(rust_target "$PLATFORM" "$ARCH")"
+
+    # Targets without a prebuilt standard library need a nightly toolchain.
+    BUILD_STD_FLAG=""
+    case "$TARGET" in
+        {build_std_targets}) BUILD_STD_FLAG="-Zbuild-std" ;;
+    esac
+
+    cargo build --lib --manifest-path "$CRATE_DIR/Cargo.toml" --target-dir "$CRATE_DIR/target" \
+        --target "$TARGET" $RELEASE_FLAG $BUILD_STD_FLAG{features}
+
+    LIBS+=("$CRATE_DIR/target/$TARGET/$PROFILE/$LIB_NAME")
+done
+
+lipo -create "${{LIBS[@]}}" -output "$OUT_DIR/$LIB_NAME"
+"#,
+        crate_name = crate_name,
+        build_script_name = XCODE_BUILD_SCRIPT_NAME,
+        crate_dir = crate_dir.display(),
+        lib_name = lib_name,
+        features = features,
+        build_std_targets = crate::package::BUILD_STD_TARGETS.join("|"),
+    )
+}
+
+fn xcconfig(lib_name: &str, crate_dir: &Path, generated_dir: Option<&Path>) -> String {
+    let mut xcconfig = format!(
+        r#"// File automatically generated by swift-bridge.
+//
+// Use it as your target's base configuration, or `#include "{xcconfig_name}"` from your own
+// xcconfig.
+
+LIBRARY_SEARCH_PATHS = $(inherited) "$(PROJECT_DIR)/{crate_dir}/target/xcode/$(CONFIGURATION)$(EFFECTIVE_PLATFORM_NAME)"
+OTHER_LDFLAGS = $(inherited) -l{lib_name}
+"#,
+        xcconfig_name = XCODE_XCCONFIG_NAME,
+        crate_dir = crate_dir.display(),
+        lib_name = lib_name,
+    );
+
+    if let Some(generated_dir) = generated_dir {
+        xcconfig += &format!(
+            r#"HEADER_SEARCH_PATHS = $(inherited) "$(PROJECT_DIR)/{generated_dir}"
+SWIFT_INCLUDE_PATHS = $(inherited) "$(PROJECT_DIR)/{generated_dir}"
+"#,
+            generated_dir = generated_dir.display()
+        );
+    }
+
+    xcconfig
+}
+
+/// The path of `to`, relative to `from`. e.g. `..` for a crate that contains the Xcode project.
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from = from
+        .canonicalize()
+        .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", from, e));
+    let to = std::fs::create_dir_all(to)
+        .and_then(|_| to.canonicalize())
+        .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", to, e));
+
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..from.len() {
+        relative.push("..");
+    }
+    for component in &to[common..] {
+        relative.push(component);
+    }
+
+    if relative.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        relative
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = std::fs::metadata(path)
+        .expect("Couldn't read Xcode build script")
+        .permissions();
+    permissions.set_mode(0o755);
+    std::fs::set_permissions(path, permissions)
+        .expect("Couldn't make Xcode build script executable");
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{dir_with_files, generated_code, BRIDGE_MODULE};
+
+    /// Write the generated code for a crate named `my_crate` to the directory.
+    fn write_generated_code(generated_dir: &Path) {
+        generated_code(BRIDGE_MODULE).write_all_concatenated(generated_dir, "my_crate");
+    }
+
+    /// Verify that we write a build script and an xcconfig that point at the crate and the
+    /// generated code relative to the project, and add the generated headers to the bridging
+    /// header.
+    #[test]
+    fn write_xcode_files_for_crate() {
+        let dir = dir_with_files(&[(
+            "Cargo.toml",
+            "[package]\nname = \"my-crate\"\nversion = \"0.1.0\"\n",
+        )]);
+        let project_dir = dir.path().join("MyApp");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        write_generated_code(&project_dir.join("Generated"));
+
+        write_xcode_files(XcodeConfig {
+            project_dir: project_dir.clone(),
+            crate_dir: dir.path().to_path_buf(),
+            features: vec!["ios".to_string(), "logging".to_string()],
+            generated_dir: Some(project_dir.join("Generated")),
+            bridging_header: Some(project_dir.join("BridgingHeader.h")),
+        });
+
+        let build_script =
+            std::fs::read_to_string(project_dir.join(XCODE_BUILD_SCRIPT_NAME)).unwrap();
+        assert!(build_script.contains("CRATE_DIR=\"$PROJECT_DIR/..\"\n"));
+        assert!(build_script.contains("LIB_NAME=\"libmy_crate.a\"\n"));
+        assert!(build_script.contains("$BUILD_STD_FLAG --features ios,logging\n"));
+
+        let xcconfig = std::fs::read_to_string(project_dir.join(XCODE_XCCONFIG_NAME)).unwrap();
+        assert!(xcconfig.contains("OTHER_LDFLAGS = $(inherited) -lmy_crate\n"));
+        assert!(xcconfig.contains(
+            "LIBRARY_SEARCH_PATHS = $(inherited) \"$(PROJECT_DIR)/../target/xcode/$(CONFIGURATION)$(EFFECTIVE_PLATFORM_NAME)\"\n"
+        ));
+        assert!(
+            xcconfig.contains("HEADER_SEARCH_PATHS = $(inherited) \"$(PROJECT_DIR)/Generated\"\n")
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(project_dir.join("BridgingHeader.h")).unwrap(),
+            format!(
+                "{}\n#import \"Generated/SwiftBridgeCore.h\"\n#import \"Generated/my_crate/my_crate.h\"\n{}\n",
+                BRIDGING_HEADER_BEGIN, BRIDGING_HEADER_END
+            )
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(project_dir.join(XCODE_BUILD_SCRIPT_NAME))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    /// Verify that we replace the block of imports in an existing bridging header, and leave the
+    /// rest of it alone.
+    #[test]
+    fn update_existing_bridging_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let generated_dir = dir.path().join("Generated");
+        write_generated_code(&generated_dir);
+        let bridging_header = dir.path().join("BridgingHeader.h");
+
+        std::fs::write(
+            &bridging_header,
+            format!(
+                "#import \"MyHeader.h\"\n\n{}\n#import \"Generated/removed.h\"\n{}\n#import \"Other.h\"\n",
+                BRIDGING_HEADER_BEGIN, BRIDGING_HEADER_END
+            ),
+        )
+        .unwrap();
+        update_bridging_header(&bridging_header, &generated_dir);
+
+        assert_eq!(
+            std::fs::read_to_string(&bridging_header).unwrap(),
+            format!(
+                "#import \"MyHeader.h\"\n\n{}\n#import \"Generated/SwiftBridgeCore.h\"\n#import \"Generated/my_crate/my_crate.h\"\n{}\n#import \"Other.h\"\n",
+                BRIDGING_HEADER_BEGIN, BRIDGING_HEADER_END
+            )
+        );
+    }
+
+    /// Verify that we append the block of imports to a bridging header that doesn't have one.
+    #[test]
+    fn append_to_bridging_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let generated_dir = dir.path().join("Generated");
+        write_generated_code(&generated_dir);
+        let bridging_header = dir.path().join("BridgingHeader.h");
+
+        std::fs::write(&bridging_header, "#import \"MyHeader.h\"").unwrap();
+        update_bridging_header(&bridging_header, &generated_dir);
+
+        assert_eq!(
+            std::fs::read_to_string(&bridging_header).unwrap(),
+            format!(
+                "#import \"MyHeader.h\"\n\n{}\n#import \"Generated/SwiftBridgeCore.h\"\n#import \"Generated/my_crate/my_crate.h\"\n{}\n",
+                BRIDGING_HEADER_BEGIN, BRIDGING_HEADER_END
+            )
+        );
+    }
+
+    /// Verify that we find the path between two directories in either direction.
+    #[test]
+    fn relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_dir = dir.path().join("ios/MyApp");
+        std::fs::create_dir_all(&project_dir).unwrap();
+
+        assert_eq!(
+            relative_path(&project_dir, dir.path()),
+            PathBuf::from("../..")
+        );
+        assert_eq!(
+            relative_path(dir.path(), &project_dir),
+            PathBuf::from("ios/MyApp")
+        );
+        assert_eq!(
+            relative_path(&project_dir, &dir.path().join("rust")),
+            PathBuf::from("../../rust")
+        );
+        assert_eq!(
+            relative_path(&project_dir, &project_dir),
+            PathBuf::from(".")
+        );
+    }
+}
@@ -7,6 +7,166 @@ pub fn cli() -> Command<'static> {
         .version(env!("CARGO_PKG_VERSION"))
         .subcommand_required(true)
         .subcommand(create_package_command())
+        .subcommand(xcode_command())
+        .subcommand(generate_command())
+        .subcommand(build_package_command())
+}
+
+/// The command for building a crate into a Swift Package
+fn build_package_command() -> Command<'static> {
+    Command::new("build-package")
+        .about("Build a Rust crate for Apple platforms and create a Swift Package from it.")
+        .arg(
+            Arg::new("crate")
+                .long("crate")
+                .takes_value(true)
+                .value_name("PATH")
+                .default_value(".")
+                .help("The path to the crate's directory, which contains its Cargo.toml"),
+        )
+        .arg(
+            Arg::new("platforms")
+                .long("platforms")
+                .takes_value(true)
+                .value_name("PLATFORMS")
+                .required(true)
+                .help(
+                    "A comma separated list of platforms to build for, such as ios,simulator,macos. Also tvos, tvos-simulator, watchos, watchos-simulator, visionos and visionos-simulator",
+                ),
+        )
+        .arg(
+            Arg::new("release")
+                .long("release")
+                .help("Build the crate using --release"),
+        )
+        .arg(
+            Arg::new("features")
+                .long("features")
+                .takes_value(true)
+                .value_name("FEATURES")
+                .help(
+                    "A comma separated list of the crate features to build and generate code for",
+                ),
+        )
+        .arg(
+            Arg::new("out-dir")
+                .long("out-dir")
+                .takes_value(true)
+                .value_name("PATH")
+                .required(true)
+                .help("The path of the Swift Package"),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .takes_value(true)
+                .value_name("PATH")
+                .required(true)
+                .help("The name for the Swift Package"),
+        )
+        .arg(link_libraries_arg())
+        .arg(link_frameworks_arg())
+}
+
+/// The command for generating the Xcode build script and xcconfig
+fn xcode_command() -> Command<'static> {
+    Command::new("xcode")
+        .about("Write the build script and xcconfig that build and link a Rust crate in Xcode.")
+        .arg(
+            Arg::new("project-dir")
+                .long("project-dir")
+                .takes_value(true)
+                .value_name("PATH")
+                .required(true)
+                .help("The directory containing the .xcodeproj, where the files are written to"),
+        )
+        .arg(
+            Arg::new("crate")
+                .long("crate")
+                .takes_value(true)
+                .value_name("PATH")
+                .default_value(".")
+                .help("The path to the crate's directory, which contains its Cargo.toml"),
+        )
+        .arg(
+            Arg::new("features")
+                .long("features")
+                .takes_value(true)
+                .value_name("FEATURES")
+                .help("A comma separated list of the crate features to build"),
+        )
+        .arg(
+            Arg::new("generated-dir")
+                .long("generated-dir")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("The directory containing the generated Swift and C code. Defaults to the out_dir in the crate's swift-bridge.toml"),
+        )
+        .arg(
+            Arg::new("bridging-header")
+                .long("bridging-header")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("An Objective-C bridging header to add #import lines for the generated headers to"),
+        )
+}
+
+/// The command for generating Swift and C code without a build script
+fn generate_command() -> Command<'static> {
+    Command::new("generate")
+        .alias("gen")
+        .about("Generate the Swift and C code for a crate's bridge modules.")
+        .arg(
+            Arg::new("crate")
+                .long("crate")
+                .takes_value(true)
+                .value_name("PATH")
+                .default_value(".")
+                .help("The path to the crate's directory, which contains its Cargo.toml"),
+        )
+        .arg(
+            Arg::new("out")
+                .long("out")
+                .takes_value(true)
+                .value_name("PATH")
+                .help(
+                    "The directory to write the generated Swift and C code to. Overrides the out_dir in the crate's swift-bridge.toml",
+                ),
+        )
+        .arg(
+            Arg::new("features")
+                .long("features")
+                .takes_value(true)
+                .value_name("FEATURES")
+                .help(
+                    "A comma separated list of the crate features to generate code for. Overrides the features in the crate's swift-bridge.toml",
+                ),
+        )
+        .arg(
+            Arg::new("dep-file")
+                .long("dep-file")
+                .takes_value(true)
+                .value_name("PATH")
+                .help(
+                    "Write the source files that the code was generated from to a Makefile dep-file, or JSON if the path ends in .json. Overrides the dep_file in the crate's swift-bridge.toml",
+                ),
+        )
+        .arg(
+            Arg::new("foundation-free")
+                .long("foundation-free")
+                .help("Generate Swift code that compiles without Foundation, such as on Linux"),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .conflicts_with("watch")
+                .help("Check that the generated code in the out directory is up to date instead of writing it"),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .help("Regenerate the code whenever one of the crate's source files changes"),
+        )
 }
 
 /// The command for creating a Swift Package
@@ -56,6 +216,13 @@ fn create_package_command() -> Command<'static> {
                 .value_name("PATH")
                 .help("The path to the compiled Rust library for tvOS"),
         )
+        .arg(
+            Arg::new("tvos-simulator")
+                .long("tvos-simulator")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("The path to the compiled Rust library for the tvOS Simulator"),
+        )
         .arg(
             Arg::new("watchos")
                 .long("watchos")
@@ -70,6 +237,20 @@ fn create_package_command() -> Command<'static> {
                 .value_name("PATH")
                 .help("The path to the compiled Rust library for WatchOSSimulator"),
         )
+        .arg(
+            Arg::new("visionos")
+                .long("visionos")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("The path to the compiled Rust library for visionOS"),
+        )
+        .arg(
+            Arg::new("visionos-simulator")
+                .long("visionos-simulator")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("The path to the compiled Rust library for the visionOS Simulator"),
+        )
         .arg(
             Arg::new("carplay")
                 .long("carplay")
@@ -100,4 +281,24 @@ fn create_package_command() -> Command<'static> {
                 .required(true)
                 .help("The name for the Swift Package"),
         )
+        .arg(link_libraries_arg())
+        .arg(link_frameworks_arg())
+}
+
+/// The system libraries that the Swift Package's target links against
+fn link_libraries_arg() -> Arg<'static> {
+    Arg::new("link-libraries")
+        .long("link-libraries")
+        .takes_value(true)
+        .value_name("LIBRARIES")
+        .help("A comma separated list of system libraries to link against, such as resolv")
+}
+
+/// The frameworks that the Swift Package's target links against
+fn link_frameworks_arg() -> Arg<'static> {
+    Arg::new("link-frameworks")
+        .long("link-frameworks")
+        .takes_value(true)
+        .value_name("FRAMEWORKS")
+        .help("A comma separated list of frameworks to link against, such as Security")
 }
@@ -1,7 +1,11 @@
 use clap::ArgMatches;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use swift_bridge_build::{create_package, ApplePlatform, CreatePackageConfig};
+use swift_bridge_build::{
+    build_package, create_package, generate_with_config, verify_with_config, watch_with_config,
+    write_xcode_files, ApplePlatform, BuildPackageConfig, CreatePackageConfig, SwiftBridgeConfig,
+    SwiftLinkerSetting, XcodeConfig,
+};
 
 /// Executes the correct function depending on the cli input
 pub fn handle_matches(matches: ArgMatches) {
@@ -9,6 +13,11 @@ pub fn handle_matches(matches: ArgMatches) {
         Some("create-package") => {
             handle_create_package(matches.subcommand_matches("create-package").unwrap())
         }
+        Some("generate") => handle_generate(matches.subcommand_matches("generate").unwrap()),
+        Some("build-package") => {
+            handle_build_package(matches.subcommand_matches("build-package").unwrap())
+        }
+        Some("xcode") => handle_xcode(matches.subcommand_matches("xcode").unwrap()),
         _ => unreachable!("No subcommand or unknown subcommand given"), // Shouldn't happen
     }
 }
@@ -24,6 +33,7 @@ fn handle_create_package(matches: &ArgMatches) {
         paths: HashMap::new(),
         out_dir: out_dir.to_path_buf(),
         package_name: name.to_string(),
+        linker_settings: linker_settings(matches),
     };
 
     for platform in ApplePlatform::ALL {
@@ -34,3 +44,112 @@ fn handle_create_package(matches: &ArgMatches) {
 
     create_package(config);
 }
+
+/// Executes the `generate` command
+fn handle_generate(matches: &ArgMatches) {
+    let crate_dir = Path::new(matches.value_of("crate").unwrap()); // has default
+
+    let mut config = SwiftBridgeConfig::load(crate_dir)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .unwrap_or_default();
+    if let Some(out) = matches.value_of("out") {
+        // `--out` is relative to the current directory, not to the crate's directory.
+        config.out_dir = Some(std::env::current_dir().unwrap().join(out));
+    }
+    if let Some(features) = matches.value_of("features") {
+        config.features = Some(comma_separated(Some(features)));
+    }
+    if let Some(dep_file) = matches.value_of("dep-file") {
+        config.dep_file = Some(std::env::current_dir().unwrap().join(dep_file));
+    }
+    if matches.is_present("foundation-free") {
+        config.foundation_free = true;
+    }
+    if config.out_dir.is_none() {
+        panic!("Pass --out or set out_dir in the crate's swift-bridge.toml");
+    }
+
+    if matches.is_present("watch") {
+        watch_with_config(crate_dir, &config);
+    }
+
+    if matches.is_present("check") {
+        let diffs = verify_with_config(crate_dir, &config);
+        if !diffs.is_empty() {
+            for diff in diffs {
+                eprintln!("{}", diff);
+            }
+            eprintln!(
+                "The generated code is out of date. Run swift-bridge-cli generate to update it."
+            );
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    generate_with_config(crate_dir, &config);
+}
+
+/// Executes the `build-package` command
+fn handle_build_package(matches: &ArgMatches) {
+    let crate_dir = matches.value_of("crate").unwrap(); // has default
+    let out_dir = matches.value_of("out-dir").unwrap(); // required
+    let name = matches.value_of("name").unwrap(); // required
+
+    let platforms = comma_separated(matches.value_of("platforms"))
+        .iter()
+        .map(|dir_name| {
+            *ApplePlatform::ALL
+                .iter()
+                .find(|platform| platform.dir_name() == dir_name)
+                .unwrap_or_else(|| panic!("Unknown platform {:?}", dir_name))
+        })
+        .collect();
+
+    build_package(BuildPackageConfig {
+        crate_dir: PathBuf::from(crate_dir),
+        platforms,
+        release: matches.is_present("release"),
+        features: comma_separated(matches.value_of("features")),
+        out_dir: PathBuf::from(out_dir),
+        package_name: name.to_string(),
+        linker_settings: linker_settings(matches),
+    });
+}
+
+/// The `--link-libraries` and `--link-frameworks` of a packaging command.
+fn linker_settings(matches: &ArgMatches) -> Vec<SwiftLinkerSetting> {
+    let libraries = comma_separated(matches.value_of("link-libraries"))
+        .into_iter()
+        .map(SwiftLinkerSetting::LinkedLibrary);
+    let frameworks = comma_separated(matches.value_of("link-frameworks"))
+        .into_iter()
+        .map(SwiftLinkerSetting::LinkedFramework);
+
+    libraries.chain(frameworks).collect()
+}
+
+/// Executes the `xcode` command
+fn handle_xcode(matches: &ArgMatches) {
+    let project_dir = matches.value_of("project-dir").unwrap(); // required
+    let crate_dir = matches.value_of("crate").unwrap(); // has default
+
+    write_xcode_files(XcodeConfig {
+        project_dir: PathBuf::from(project_dir),
+        crate_dir: PathBuf::from(crate_dir),
+        features: comma_separated(matches.value_of("features")),
+        generated_dir: matches.value_of("generated-dir").map(PathBuf::from),
+        bridging_header: matches.value_of("bridging-header").map(PathBuf::from),
+    });
+}
+
+/// Split a comma separated argument, such as `--features a,b`, into its values.
+fn comma_separated(arg: Option<&str>) -> Vec<String> {
+    arg.map(|arg| {
+        arg.split(',')
+            .map(|val| val.trim().to_string())
+            .filter(|val| !val.is_empty())
+            .collect()
+    })
+    .unwrap_or_default()
+}
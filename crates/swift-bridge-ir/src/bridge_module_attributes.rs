@@ -1,9 +1,12 @@
-use proc_macro2::Ident;
+use crate::parse::SwiftAccessLevel;
+use crate::CodegenConfig;
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
 use syn::LitStr;
 use syn::Token;
 
-/// A `cfg` attribute on a bridge module.
+/// A `cfg` attribute on a bridge module or on one of its extern types or functions.
 ///
 /// ```no_run
 /// #[swift_bridge::bridge]
@@ -12,25 +15,537 @@ use syn::Token;
 /// mod ffi {
 /// }
 /// ```
+#[derive(Clone)]
 pub enum CfgAttr {
     /// #\[cfg(feature = "...")\]
     Feature(LitStr),
+    /// #\[cfg(target_os = "...")\]
+    TargetOs(TargetOs),
+    /// #\[cfg(target_env = "...")\] or #\[cfg(target_abi = "...")\]
+    ///
+    /// Rust sets both to the same value for the simulator and Mac Catalyst targets, so we keep
+    /// the key that was written in order to use it in the generated Rust code.
+    TargetEnv(Ident, TargetEnv),
+    /// #\[cfg(not(...))\]
+    Not(Box<CfgAttr>),
+}
+
+/// An operating system that can be used in a `#[cfg(target_os = "...")]` attribute.
+///
+/// We know the crate's enabled features when we generate Swift and C code, but not the operating
+/// system that the Swift code will be compiled for, so `target_os` attributes become `#if`
+/// blocks in the generated Swift and C code.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TargetOs {
+    Ios,
+    MacOs,
+    TvOs,
+    WatchOs,
+    VisionOs,
+    Linux,
+    Windows,
+    Android,
+}
+
+impl TargetOs {
+    const ALL: [TargetOs; 8] = [
+        TargetOs::Ios,
+        TargetOs::MacOs,
+        TargetOs::TvOs,
+        TargetOs::WatchOs,
+        TargetOs::VisionOs,
+        TargetOs::Linux,
+        TargetOs::Windows,
+        TargetOs::Android,
+    ];
+
+    /// The operating system's name in a Rust `#[cfg(target_os = "...")]` attribute.
+    pub fn rust_name(&self) -> &'static str {
+        match self {
+            TargetOs::Ios => "ios",
+            TargetOs::MacOs => "macos",
+            TargetOs::TvOs => "tvos",
+            TargetOs::WatchOs => "watchos",
+            TargetOs::VisionOs => "visionos",
+            TargetOs::Linux => "linux",
+            TargetOs::Windows => "windows",
+            TargetOs::Android => "android",
+        }
+    }
+
+    /// The condition in a Swift `#if` block, such as `os(iOS)`.
+    pub fn swift_condition(&self) -> &'static str {
+        match self {
+            TargetOs::Ios => "os(iOS)",
+            TargetOs::MacOs => "os(macOS)",
+            TargetOs::TvOs => "os(tvOS)",
+            TargetOs::WatchOs => "os(watchOS)",
+            TargetOs::VisionOs => "os(visionOS)",
+            TargetOs::Linux => "os(Linux)",
+            TargetOs::Windows => "os(Windows)",
+            TargetOs::Android => "os(Android)",
+        }
+    }
+
+    /// The condition in a C `#if` block.
+    ///
+    /// The C header is only ever compiled by clang (via Swift's clang importer), so we can use
+    /// clang's `__is_target_os` for Apple platforms.
+    pub fn c_condition(&self) -> &'static str {
+        match self {
+            TargetOs::Ios => "__is_target_os(ios)",
+            TargetOs::MacOs => "__is_target_os(macos)",
+            TargetOs::TvOs => "__is_target_os(tvos)",
+            TargetOs::WatchOs => "__is_target_os(watchos)",
+            TargetOs::VisionOs => "__is_target_os(xros)",
+            TargetOs::Linux => "defined(__linux__) && !defined(__ANDROID__)",
+            TargetOs::Windows => "defined(_WIN32)",
+            TargetOs::Android => "defined(__ANDROID__)",
+        }
+    }
+}
+
+/// A target environment that can be used in a `#[cfg(target_env = "...")]` attribute.
+///
+/// Like `target_os`, these become `#if` blocks in the generated Swift and C code. Use
+/// `#[cfg(not(target_env = "sim"))]` for APIs that only exist on physical devices.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TargetEnv {
+    Simulator,
+    MacCatalyst,
+}
+
+impl TargetEnv {
+    const ALL: [TargetEnv; 2] = [TargetEnv::Simulator, TargetEnv::MacCatalyst];
+
+    /// The environment's name in a Rust `#[cfg(target_env = "...")]` attribute.
+    pub fn rust_name(&self) -> &'static str {
+        match self {
+            TargetEnv::Simulator => "sim",
+            TargetEnv::MacCatalyst => "macabi",
+        }
+    }
+
+    /// The condition in a Swift `#if` block, such as `targetEnvironment(simulator)`.
+    pub fn swift_condition(&self) -> &'static str {
+        match self {
+            TargetEnv::Simulator => "targetEnvironment(simulator)",
+            TargetEnv::MacCatalyst => "targetEnvironment(macCatalyst)",
+        }
+    }
+
+    /// The condition in a C `#if` block.
+    pub fn c_condition(&self) -> &'static str {
+        match self {
+            TargetEnv::Simulator => "__is_target_environment(simulator)",
+            TargetEnv::MacCatalyst => "__is_target_environment(macabi)",
+        }
+    }
+}
+
+impl CfgAttr {
+    /// Whether or not the crate's enabled features lead to code annotated with this attribute
+    /// being compiled.
+    ///
+    /// Target attributes always return `true` since they get checked by the generated `#if`
+    /// blocks instead.
+    pub(crate) fn is_enabled(&self, config: &CodegenConfig) -> bool {
+        match self {
+            CfgAttr::Feature(feature_name) => (config.crate_feature_lookup)(&feature_name.value()),
+            CfgAttr::TargetOs(_) | CfgAttr::TargetEnv(_, _) => true,
+            CfgAttr::Not(cfg) => cfg.swift_condition().is_some() || !cfg.is_enabled(config),
+        }
+    }
+
+    /// The condition in a Swift `#if` block, or `None` for features.
+    fn swift_condition(&self) -> Option<String> {
+        match self {
+            CfgAttr::Feature(_) => None,
+            CfgAttr::TargetOs(os) => Some(os.swift_condition().to_string()),
+            CfgAttr::TargetEnv(_, env) => Some(env.swift_condition().to_string()),
+            CfgAttr::Not(cfg) => cfg.swift_condition().map(|cond| format!("!{}", cond)),
+        }
+    }
+
+    /// The condition in a C `#if` block, or `None` for features.
+    fn c_condition(&self) -> Option<String> {
+        match self {
+            CfgAttr::Feature(_) => None,
+            CfgAttr::TargetOs(os) => Some(format!("({})", os.c_condition())),
+            CfgAttr::TargetEnv(_, env) => Some(format!("({})", env.c_condition())),
+            CfgAttr::Not(cfg) => cfg.c_condition().map(|cond| format!("!{}", cond)),
+        }
+    }
+
+    /// The condition inside of the `#[cfg(...)]`.
+    fn rust_condition(&self) -> TokenStream {
+        match self {
+            CfgAttr::Feature(feature_name) => quote! { feature = #feature_name },
+            CfgAttr::TargetOs(os) => {
+                let os = os.rust_name();
+                quote! { target_os = #os }
+            }
+            CfgAttr::TargetEnv(key, env) => {
+                let env = env.rust_name();
+                quote! { #key = #env }
+            }
+            CfgAttr::Not(cfg) => {
+                let cfg = cfg.rust_condition();
+                quote! { not(#cfg) }
+            }
+        }
+    }
+
+    /// Used to de-duplicate the attributes of a method and the attributes that it inherits from
+    /// its type.
+    fn is_same_as(&self, other: &CfgAttr) -> bool {
+        match (self, other) {
+            (CfgAttr::Feature(a), CfgAttr::Feature(b)) => a.value() == b.value(),
+            (CfgAttr::TargetOs(a), CfgAttr::TargetOs(b)) => a == b,
+            (CfgAttr::TargetEnv(a_key, a), CfgAttr::TargetEnv(b_key, b)) => {
+                a_key == b_key && a == b
+            }
+            (CfgAttr::Not(a), CfgAttr::Not(b)) => a.is_same_as(b),
+            _ => false,
+        }
+    }
+}
+
+/// Add the `cfg` attributes that aren't already in `cfg_attrs`.
+pub(crate) fn extend_cfg_attrs(cfg_attrs: &mut Vec<CfgAttr>, other: &[CfgAttr]) {
+    for cfg in other {
+        if !cfg_attrs.iter().any(|existing| existing.is_same_as(cfg)) {
+            cfg_attrs.push(cfg.clone());
+        }
+    }
+}
+
+/// Whether or not the crate's enabled features lead to code annotated with all of these
+/// attributes being compiled.
+pub(crate) fn cfg_attrs_are_enabled(cfg_attrs: &[CfgAttr], config: &CodegenConfig) -> bool {
+    cfg_attrs.iter().all(|cfg| cfg.is_enabled(config))
+}
+
+/// Wrap generated Swift code in an `#if` block if any of the attributes are `target_os` or
+/// `target_env` attributes.
+///
+/// ```swift
+/// #if os(iOS) && !targetEnvironment(simulator)
+/// ...
+/// #endif
+/// ```
+pub(crate) fn wrap_in_swift_if(code: String, cfg_attrs: &[CfgAttr], indentation: &str) -> String {
+    let conditions: Vec<String> = cfg_attrs
+        .iter()
+        .filter_map(|cfg| cfg.swift_condition())
+        .collect();
+
+    wrap_in_if(code, &conditions.join(" && "), indentation)
+}
+
+/// Wrap generated C code in an `#if` block if any of the attributes are `target_os` or
+/// `target_env` attributes.
+///
+/// ```c
+/// #if (__is_target_os(ios)) && !(__is_target_environment(simulator))
+/// ...
+/// #endif
+/// ```
+pub(crate) fn wrap_in_c_if(code: String, cfg_attrs: &[CfgAttr]) -> String {
+    let conditions: Vec<String> = cfg_attrs
+        .iter()
+        .filter_map(|cfg| cfg.c_condition())
+        .collect();
+
+    wrap_in_if(code, &conditions.join(" && "), "")
+}
+
+pub(crate) fn wrap_in_if(code: String, condition: &str, indentation: &str) -> String {
+    if condition.is_empty() || code.is_empty() {
+        return code;
+    }
+
+    // Keep any leading and trailing newlines outside of the `#if` block.
+    let code_without_leading_newlines = code.trim_start_matches('\n');
+    let leading_newlines = &code[..code.len() - code_without_leading_newlines.len()];
+    let (code, trailing_newline) = match code_without_leading_newlines.strip_suffix('\n') {
+        Some(code) => (code, "\n"),
+        None => (code_without_leading_newlines, ""),
+    };
+
+    format!(
+        "{leading_newlines}{indentation}#if {condition}\n{code}\n{indentation}#endif{trailing_newline}",
+        leading_newlines = leading_newlines,
+        indentation = indentation,
+        condition = condition,
+        code = code,
+        trailing_newline = trailing_newline
+    )
 }
 
 impl Parse for CfgAttr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let content;
         syn::parenthesized!(content in input);
+
+        CfgAttr::parse_condition(&content)
+    }
+}
+
+impl CfgAttr {
+    /// Parse the condition inside of the `#[cfg(...)]`.
+    fn parse_condition(content: ParseStream) -> syn::Result<Self> {
         let ident: Ident = content.parse()?;
 
-        if &ident == "feature" {
+        if &ident == "not" {
+            let inner;
+            syn::parenthesized!(inner in content);
+
+            Ok(CfgAttr::Not(Box::new(CfgAttr::parse_condition(&inner)?)))
+        } else if &ident == "feature" {
             content.parse::<Token![=]>()?;
 
             let feature_name = content.parse::<LitStr>()?;
 
             Ok(CfgAttr::Feature(feature_name))
+        } else if &ident == "target_os" {
+            content.parse::<Token![=]>()?;
+
+            let os_name = content.parse::<LitStr>()?;
+            let os = TargetOs::ALL
+                .into_iter()
+                .find(|os| os.rust_name() == os_name.value())
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &os_name,
+                        format!(
+                            r#"Unsupported target_os "{}". Expected one of ios, macos, tvos, watchos, visionos, linux, windows or android."#,
+                            os_name.value()
+                        ),
+                    )
+                })?;
+
+            Ok(CfgAttr::TargetOs(os))
+        } else if &ident == "target_env" || &ident == "target_abi" {
+            content.parse::<Token![=]>()?;
+
+            let env_name = content.parse::<LitStr>()?;
+            let env = TargetEnv::ALL
+                .into_iter()
+                .find(|env| env.rust_name() == env_name.value())
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &env_name,
+                        format!(
+                            r#"Unsupported {} "{}". Expected sim or macabi."#,
+                            ident,
+                            env_name.value()
+                        ),
+                    )
+                })?;
+
+            Ok(CfgAttr::TargetEnv(ident, env))
         } else {
-            todo!("Return an unsupported cfg kind error")
+            Err(syn::Error::new_spanned(
+                &ident,
+                r#"Only #[cfg(feature = "...")], #[cfg(target_os = "...")], #[cfg(target_env = "...")] and #[cfg(not(...))] are supported."#,
+            ))
+        }
+    }
+}
+
+/// `#[cfg(feature = "...")]`, `#[cfg(target_os = "...")]`, `#[cfg(target_env = "...")]` or
+/// `#[cfg(not(...))]`
+impl ToTokens for CfgAttr {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let condition = self.rust_condition();
+        let cfg = quote! {
+            #[cfg(#condition)]
+        };
+        cfg.to_tokens(tokens);
+    }
+}
+
+/// What a Rust function that Swift calls does if it panics, instead of unwinding into Swift.
+///
+/// ```ignore
+/// #[swift_bridge::bridge]
+/// #[swift_bridge(panic = "fatal_error")]
+/// mod ffi {
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum PanicPolicy {
+    /// `panic = "abort"`
+    /// Abort the process.
+    Abort,
+    /// `panic = "fatal_error"`
+    /// Call Swift's `fatalError` with the panic's message.
+    FatalError,
+    /// `panic = "throw"`
+    /// Throwing initializers throw a `RustPanic` error with the panic's message. Other functions
+    /// call Swift's `fatalError`, since they have no way to throw.
+    Throw,
+}
+
+impl PanicPolicy {
+    const ALL: [PanicPolicy; 3] = [
+        PanicPolicy::Abort,
+        PanicPolicy::FatalError,
+        PanicPolicy::Throw,
+    ];
+
+    /// The policy's name in a `#[swift_bridge(panic = "...")]` attribute.
+    fn name(&self) -> &'static str {
+        match self {
+            PanicPolicy::Abort => "abort",
+            PanicPolicy::FatalError => "fatal_error",
+            PanicPolicy::Throw => "throw",
+        }
+    }
+}
+
+impl Parse for PanicPolicy {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<LitStr>()?;
+
+        PanicPolicy::ALL
+            .into_iter()
+            .find(|policy| policy.name() == name.value())
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &name,
+                    format!(
+                        r#"Unsupported panic policy "{}". Expected abort, fatal_error or throw."#,
+                        name.value()
+                    ),
+                )
+            })
+    }
+}
+
+/// What to do about opaque types that the module declares but never uses.
+///
+/// ```ignore
+/// #[swift_bridge::bridge]
+/// #[swift_bridge(unused_types = "deny")]
+/// mod ffi {
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub(crate) enum UnusedTypesLevel {
+    /// `unused_types = "allow"`
+    Allow,
+    /// `unused_types = "warn"`
+    /// Emit a compiler warning for each unused type.
+    #[default]
+    Warn,
+    /// `unused_types = "deny"`
+    /// Emit a compiler error for each unused type.
+    Deny,
+}
+
+impl UnusedTypesLevel {
+    const ALL: [UnusedTypesLevel; 3] = [
+        UnusedTypesLevel::Allow,
+        UnusedTypesLevel::Warn,
+        UnusedTypesLevel::Deny,
+    ];
+
+    /// The level's name in a `#[swift_bridge(unused_types = "...")]` attribute.
+    fn name(&self) -> &'static str {
+        match self {
+            UnusedTypesLevel::Allow => "allow",
+            UnusedTypesLevel::Warn => "warn",
+            UnusedTypesLevel::Deny => "deny",
+        }
+    }
+}
+
+impl Parse for UnusedTypesLevel {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<LitStr>()?;
+
+        UnusedTypesLevel::ALL
+            .into_iter()
+            .find(|level| level.name() == name.value())
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &name,
+                    format!(
+                        r#"Unsupported unused_types level "{}". Expected allow, warn or deny."#,
+                        name.value()
+                    ),
+                )
+            })
+    }
+}
+
+/// A `#[swift_bridge(...)]` attribute on a bridge module.
+///
+/// ```ignore
+/// #[swift_bridge::bridge]
+/// #[swift_bridge(swift_access = "internal")]
+/// mod ffi {
+/// }
+/// ```
+pub(crate) enum ModuleSwiftBridgeAttr {
+    /// `#[swift_bridge(swift_access = "...")]`
+    /// The default access level of the module's generated Swift classes and functions.
+    SwiftAccess(SwiftAccessLevel),
+    /// `#[swift_bridge(symbol_prefix = "...")]`
+    /// The prefix to use instead of `__swift_bridge__` for the module's FFI symbols.
+    SymbolPrefix(LitStr),
+    /// `#[swift_bridge(swift_name_prefix = "...")]`
+    /// The prefix to add to the Swift names of the module's types and free functions.
+    SwiftNamePrefix(LitStr),
+    /// `#[swift_bridge(default_visibility)]`
+    /// Annotate the module's C header function declarations with
+    /// `__attribute__((visibility("default")))`.
+    DefaultVisibility,
+    /// `#[swift_bridge(library_evolution)]`
+    /// Generate Swift that can be built with library evolution enabled, such as by marking the
+    /// module's shared structs and enums `@frozen`.
+    LibraryEvolution,
+    /// `#[swift_bridge(panic = "...")]`
+    /// Catch panics in the module's Rust functions instead of letting them unwind into Swift.
+    Panic(PanicPolicy),
+    /// `#[swift_bridge(unused_types = "...")]`
+    /// Whether to warn about, or reject, opaque types that the module never uses.
+    UnusedTypes(UnusedTypesLevel),
+}
+
+impl Parse for ModuleSwiftBridgeAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+
+        match key.to_string().as_str() {
+            "swift_access" => {
+                input.parse::<Token![=]>()?;
+                Ok(ModuleSwiftBridgeAttr::SwiftAccess(input.parse()?))
+            }
+            "symbol_prefix" => {
+                input.parse::<Token![=]>()?;
+                Ok(ModuleSwiftBridgeAttr::SymbolPrefix(input.parse()?))
+            }
+            "swift_name_prefix" => {
+                input.parse::<Token![=]>()?;
+                Ok(ModuleSwiftBridgeAttr::SwiftNamePrefix(input.parse()?))
+            }
+            "default_visibility" => Ok(ModuleSwiftBridgeAttr::DefaultVisibility),
+            "library_evolution" => Ok(ModuleSwiftBridgeAttr::LibraryEvolution),
+            "panic" => {
+                input.parse::<Token![=]>()?;
+                Ok(ModuleSwiftBridgeAttr::Panic(input.parse()?))
+            }
+            "unused_types" => {
+                input.parse::<Token![=]>()?;
+                Ok(ModuleSwiftBridgeAttr::UnusedTypes(input.parse()?))
+            }
+            _ => Err(syn::Error::new_spanned(
+                &key,
+                format!(r#"Unrecognized attribute "{}"."#, key),
+            )),
         }
     }
 }
@@ -10,14 +10,14 @@ use syn::{FnArg, Pat, PatType, Path, ReturnType, Type};
 pub(crate) use self::bridged_opaque_type::OpaqueForeignType;
 use crate::bridged_type::boxed_fn::BridgeableBoxedFnOnce;
 use crate::bridged_type::bridgeable_pointer::{BuiltInPointer, Pointee, PointerKind};
-use crate::bridged_type::bridgeable_result::BuiltInResult;
+pub(crate) use crate::bridged_type::bridgeable_result::BuiltInResult;
 use crate::bridged_type::bridgeable_string::BridgedString;
 use crate::parse::{HostLang, TypeDeclaration, TypeDeclarations};
 use crate::SWIFT_BRIDGE_PREFIX;
 
 use self::bridged_option::BridgedOption;
 pub(crate) use self::shared_enum::{EnumVariant, SharedEnum};
-pub(crate) use self::shared_struct::{SharedStruct, StructFields, StructSwiftRepr};
+pub(crate) use self::shared_struct::{SharedStruct, StructFields, StructLayout, StructSwiftRepr};
 
 pub(crate) mod boxed_fn;
 mod bridgeable_pointer;
@@ -61,7 +61,7 @@ pub(crate) trait BridgeableType: Debug {
     fn to_swift_type(&self, type_pos: TypePosition, types: &TypeDeclarations) -> String;
 
     /// Get the C representation of this type.
-    fn to_c_type(&self) -> String;
+    fn to_c_type(&self, types: &TypeDeclarations) -> String;
 
     /// Generate a C include statement to put in the C header.
     /// For example, for a `u8` we would generate a `#include <stdint.h>` line.
@@ -138,7 +138,11 @@ pub(crate) trait BridgeableType: Debug {
     ) -> TokenStream;
 
     /// Convert an Option<Self> FFI representation to the Rust representation.
-    fn convert_ffi_option_expression_to_rust_type(&self, expression: &TokenStream) -> TokenStream;
+    fn convert_ffi_option_expression_to_rust_type(
+        &self,
+        expression: &TokenStream,
+        swift_bridge_path: &Path,
+    ) -> TokenStream;
 
     /// Convert an FFI expression to this type's Swift representation.
     fn convert_ffi_expression_to_swift_type(
@@ -210,6 +214,20 @@ pub(crate) trait BridgeableType: Debug {
     ///  of checking the type.
     fn is_str(&self) -> bool;
 
+    /// Whether or not this is a shared struct such as `struct SomeStruct { field: u8 }`.
+    fn is_shared_struct(&self) -> bool;
+
+    /// Whether or not this is an integer, a float or a bool.
+    fn is_primitive(&self) -> bool;
+
+    /// Whether or not the type is passed over FFI as a pointer, such as the
+    /// `*mut super::SomeType` of an opaque Rust type.
+    fn is_passed_as_pointer(&self) -> bool;
+
+    /// The size and alignment of the type's FFI representation on 64-bit targets, or `None` if
+    /// they aren't known when generating code.
+    fn ffi_size_and_align(&self, types: &TypeDeclarations) -> Option<(usize, usize)>;
+
     /// Whether or not the type is a `String`, or a type that contains an owned String such as
     /// `Option<String>` or `struct Foo { field: String }`
     /// TODO: This is temporary as we move towards using this trait.. We should look at how
@@ -306,6 +324,10 @@ pub(crate) enum StdLibType {
     I64,
     Usize,
     Isize,
+    /// `u128`, passed over FFI as a `{ uint64_t low; uint64_t high; }` struct.
+    U128,
+    /// `i128`, passed over FFI as a `{ uint64_t low; int64_t high; }` struct.
+    I128,
     F32,
     F64,
     Bool,
@@ -343,10 +365,115 @@ pub(crate) struct BuiltInVec {
     pub ty: Box<BridgedType>,
 }
 
+/// `UInt128` and `Int128` are only available when building with Swift 6, for these OS versions.
+/// Generated Swift code that uses them goes inside of `#if compiler(>=6.0)` and gets this
+/// attribute, the same as `int128_support.swift`.
+pub(crate) const SWIFT_INT128_AVAILABILITY: &str =
+    "@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)";
+
 impl BridgedType {
     pub fn is_null(&self) -> bool {
         matches!(self, BridgedType::StdLib(StdLibType::Null))
     }
+
+    /// Whether or not this is a `u128` or an `i128`.
+    ///
+    /// These can only be used on their own for now, not inside of an `Option`, `Vec`, `Result` or
+    /// boxed function.
+    pub fn is_128_bit_int(&self) -> bool {
+        matches!(
+            self,
+            BridgedType::StdLib(StdLibType::U128 | StdLibType::I128)
+        )
+    }
+
+    /// Whether or not we can generate code for an `Option` of this type.
+    pub fn can_be_wrapped_in_option(&self) -> bool {
+        !matches!(
+            self,
+            BridgedType::StdLib(
+                StdLibType::Null
+                    | StdLibType::U128
+                    | StdLibType::I128
+                    | StdLibType::Pointer(_)
+                    | StdLibType::RefSlice(_)
+                    | StdLibType::Vec(_)
+                    | StdLibType::BoxedFnOnce(_)
+                    | StdLibType::Option(_)
+                    | StdLibType::Result(_)
+            )
+        )
+    }
+
+    /// Whether or not this is an integer, a float or a bool.
+    pub fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            BridgedType::StdLib(
+                StdLibType::U8
+                    | StdLibType::I8
+                    | StdLibType::U16
+                    | StdLibType::I16
+                    | StdLibType::U32
+                    | StdLibType::I32
+                    | StdLibType::U64
+                    | StdLibType::I64
+                    | StdLibType::Usize
+                    | StdLibType::Isize
+                    | StdLibType::F32
+                    | StdLibType::F64
+                    | StdLibType::Bool
+            )
+        )
+    }
+
+    /// Whether or not the type is passed over FFI as a pointer.
+    pub fn is_passed_as_pointer(&self) -> bool {
+        match self {
+            BridgedType::Bridgeable(b) => b.is_passed_as_pointer(),
+            BridgedType::StdLib(StdLibType::Vec(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// The size and alignment of the type's FFI representation on 64-bit targets, or `None` if
+    /// they aren't known when generating code.
+    pub fn ffi_size_and_align(&self, types: &TypeDeclarations) -> Option<(usize, usize)> {
+        match self {
+            BridgedType::Bridgeable(b) => b.ffi_size_and_align(types),
+            BridgedType::StdLib(stdlib_type) => match stdlib_type {
+                StdLibType::Null => Some((0, 1)),
+                StdLibType::U8 | StdLibType::I8 | StdLibType::Bool => Some((1, 1)),
+                StdLibType::U16 | StdLibType::I16 => Some((2, 2)),
+                StdLibType::U32 | StdLibType::I32 | StdLibType::F32 => Some((4, 4)),
+                StdLibType::U64
+                | StdLibType::I64
+                | StdLibType::Usize
+                | StdLibType::Isize
+                | StdLibType::F64 => Some((8, 8)),
+                StdLibType::U128 | StdLibType::I128 => Some((16, 8)),
+                StdLibType::Pointer(_) | StdLibType::Vec(_) | StdLibType::BoxedFnOnce(_) => {
+                    Some((8, 8))
+                }
+                StdLibType::Str | StdLibType::RefSlice(_) => Some((16, 8)),
+                StdLibType::Option(_) | StdLibType::Result(_) => None,
+            },
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
+                shared_struct.ffi_size_and_align(types)
+            }
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(_))) => None,
+        }
+    }
+
+    /// Whether or not this is an owned `String`.
+    pub fn is_string(&self) -> bool {
+        match self {
+            BridgedType::Bridgeable(b) => {
+                b.is_built_in_type() && b.contains_owned_string_recursive()
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -355,6 +482,21 @@ pub(crate) enum SharedType {
     Enum(SharedEnum),
 }
 
+/// The size and alignment of a `#[repr(C)]` struct with fields of the given sizes and
+/// alignments.
+pub(crate) fn c_struct_size_and_align(
+    fields: impl IntoIterator<Item = (usize, usize)>,
+) -> (usize, usize) {
+    let mut size: usize = 0;
+    let mut align = 1;
+    for (field_size, field_align) in fields {
+        size = size.next_multiple_of(field_align) + field_size;
+        align = align.max(field_align);
+    }
+
+    (size.next_multiple_of(align), align)
+}
+
 /// Whether or not a PatType's pattern is `self`.
 ///
 /// `self: &Foo` would be true
@@ -392,12 +534,12 @@ impl BridgeableType for BridgedType {
         self.to_swift_type(type_pos, types)
     }
 
-    fn to_c_type(&self) -> String {
-        self.to_c()
+    fn to_c_type(&self, types: &TypeDeclarations) -> String {
+        self.to_c(types)
     }
 
     fn to_c_include(&self) -> Option<&'static str> {
-        todo!()
+        self.to_c_include()
     }
 
     fn to_ffi_compatible_rust_type(
@@ -430,11 +572,11 @@ impl BridgeableType for BridgedType {
 
     fn convert_rust_expression_to_ffi_type(
         &self,
-        _expression: &TokenStream,
-        _swift_bridge_path: &Path,
-        _types: &TypeDeclarations,
+        expression: &TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
     ) -> TokenStream {
-        todo!()
+        self.convert_rust_expression_to_ffi_type(expression, swift_bridge_path, types)
     }
 
     fn convert_option_rust_expression_to_ffi_type(
@@ -463,15 +605,19 @@ impl BridgeableType for BridgedType {
 
     fn convert_ffi_expression_to_rust_type(
         &self,
-        _expression: &TokenStream,
-        _span: Span,
-        _swift_bridge_path: &Path,
-        _types: &TypeDeclarations,
+        expression: &TokenStream,
+        span: Span,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
     ) -> TokenStream {
-        todo!()
+        self.convert_ffi_expression_to_rust_type(expression, span, swift_bridge_path, types)
     }
 
-    fn convert_ffi_option_expression_to_rust_type(&self, _expression: &TokenStream) -> TokenStream {
+    fn convert_ffi_option_expression_to_rust_type(
+        &self,
+        _expression: &TokenStream,
+        _swift_bridge_path: &Path,
+    ) -> TokenStream {
         todo!()
     }
 
@@ -542,6 +688,25 @@ impl BridgeableType for BridgedType {
         }
     }
 
+    fn is_shared_struct(&self) -> bool {
+        matches!(
+            self,
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_)))
+        )
+    }
+
+    fn is_primitive(&self) -> bool {
+        self.is_primitive()
+    }
+
+    fn is_passed_as_pointer(&self) -> bool {
+        self.is_passed_as_pointer()
+    }
+
+    fn ffi_size_and_align(&self, types: &TypeDeclarations) -> Option<(usize, usize)> {
+        self.ffi_size_and_align(types)
+    }
+
     fn contains_owned_string_recursive(&self) -> bool {
         self.contains_owned_string_recursive()
     }
@@ -586,7 +751,7 @@ impl BridgedType {
                 } else {
                     BridgedType::StdLib(StdLibType::Pointer(BuiltInPointer {
                         kind,
-                        pointee: Pointee::Void(*ptr.elem.clone()),
+                        pointee: Pointee::Void(ptr.elem.clone()),
                     }))
                 };
                 Some(ty)
@@ -641,9 +806,12 @@ impl BridgedType {
             let inner = if let Some(declared_ty) = types.get(inner) {
                 declared_ty.to_bridged_type(false, false)
             } else {
-                let inner: Type = syn::parse2(TokenStream::from_str(inner).unwrap()).unwrap();
+                let inner: Type = syn::parse2(TokenStream::from_str(inner).ok()?).ok()?;
                 BridgedType::new_with_type(&inner, types)?
             };
+            if inner.is_128_bit_int() {
+                return None;
+            }
 
             return Some(BridgedType::StdLib(StdLibType::Vec(BuiltInVec {
                 ty: Box::new(inner),
@@ -661,8 +829,11 @@ impl BridgedType {
                 inner.to_string()
             };
 
-            let inner: Type = syn::parse2(TokenStream::from_str(&inner).unwrap()).unwrap();
+            let inner: Type = syn::parse2(TokenStream::from_str(&inner).ok()?).ok()?;
             let inner = BridgedType::new_with_type(&inner, types)?;
+            if !inner.can_be_wrapped_in_option() {
+                return None;
+            }
 
             return Some(BridgedType::StdLib(StdLibType::Option(BridgedOption {
                 ty: Box::new(inner),
@@ -675,6 +846,19 @@ impl BridgedType {
             return Some(BridgedType::StdLib(StdLibType::BoxedFnOnce(
                 BridgeableBoxedFnOnce::from_str_tokens(&tokens, types)?,
             )));
+        } else if tokens.starts_with("Box < ") {
+            // `Box<T>` where `T` is an opaque Rust type. The existing allocation gets passed across
+            // the boundary instead of being moved into a new box.
+            let inner = tokens.trim_start_matches("Box < ");
+            let inner = inner.trim_end_matches(" >");
+
+            let mut opaque = types.get(inner)?.to_opaque_type(false, false)?;
+            if !opaque.host_lang.is_rust() || opaque.has_swift_bridge_copy_annotation {
+                return None;
+            }
+            opaque.boxed = true;
+
+            return Some(BridgedType::Bridgeable(Box::new(opaque)));
         }
 
         let ty = match tokens {
@@ -688,6 +872,8 @@ impl BridgedType {
             "i64" => BridgedType::StdLib(StdLibType::I64),
             "usize" => BridgedType::StdLib(StdLibType::Usize),
             "isize" => BridgedType::StdLib(StdLibType::Isize),
+            "u128" => BridgedType::StdLib(StdLibType::U128),
+            "i128" => BridgedType::StdLib(StdLibType::I128),
             "f32" => BridgedType::StdLib(StdLibType::F32),
             "f64" => BridgedType::StdLib(StdLibType::F64),
             "bool" => BridgedType::StdLib(StdLibType::Bool),
@@ -727,6 +913,8 @@ impl BridgedType {
                     StdLibType::I64 => quote! { i64 },
                     StdLibType::Usize => quote! { usize },
                     StdLibType::Isize => quote! { isize },
+                    StdLibType::U128 => quote! { u128 },
+                    StdLibType::I128 => quote! { i128 },
                     StdLibType::F32 => quote! { f32 },
                     StdLibType::F64 => quote! { f64 },
                     StdLibType::Bool => quote! { bool },
@@ -801,6 +989,8 @@ impl BridgedType {
                 StdLibType::F64 => quote! { f64 },
                 StdLibType::Usize => quote! { usize },
                 StdLibType::Isize => quote! { isize },
+                StdLibType::U128 => quote! { #swift_bridge_path::int128::FfiU128 },
+                StdLibType::I128 => quote! { #swift_bridge_path::int128::FfiI128 },
                 StdLibType::Bool => quote! { bool },
                 StdLibType::Pointer(ptr) => {
                     let kind = ptr.kind.to_token_stream();
@@ -870,6 +1060,9 @@ impl BridgedType {
                         StdLibType::Isize => {
                             quote! { #swift_bridge_path::option::OptionIsize }
                         }
+                        StdLibType::U128 | StdLibType::I128 => {
+                            unreachable!("Option<u128> and Option<i128> are rejected while parsing")
+                        }
                         StdLibType::F32 => {
                             quote! { #swift_bridge_path::option::OptionF32 }
                         }
@@ -966,6 +1159,25 @@ impl BridgedType {
                 StdLibType::F64 => "Double".to_string(),
                 StdLibType::Usize => "UInt".to_string(),
                 StdLibType::Isize => "Int".to_string(),
+                StdLibType::U128 | StdLibType::I128 => {
+                    let (swift_ty, ffi_ty) = match stdlib_type {
+                        StdLibType::U128 => ("UInt128", types.private_type_name("U128")),
+                        _ => ("Int128", types.private_type_name("I128")),
+                    };
+
+                    match type_pos {
+                        TypePosition::FnArg(func_host_lang, _)
+                        | TypePosition::FnReturn(func_host_lang) => {
+                            if func_host_lang.is_rust() {
+                                swift_ty.to_string()
+                            } else {
+                                ffi_ty
+                            }
+                        }
+                        TypePosition::SharedStructField => swift_ty.to_string(),
+                        TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => ffi_ty,
+                    }
+                }
                 StdLibType::Bool => "Bool".to_string(),
                 StdLibType::Pointer(ptr) => {
                     let maybe_mutable = match ptr.kind {
@@ -991,7 +1203,7 @@ impl BridgedType {
                         TypePosition::FnArg(func_host_lang, _)
                         | TypePosition::FnReturn(func_host_lang) => {
                             if func_host_lang.is_swift() {
-                                "__private__FfiSlice".to_string()
+                                types.private_type_name("FfiSlice")
                             } else {
                                 format!(
                                     "UnsafeBufferPointer<{}>",
@@ -1039,7 +1251,7 @@ impl BridgedType {
                         format!("Optional<{}>", opt.ty.to_swift_type(type_pos, types))
                     }
                     TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
-                        unimplemented!()
+                        opt.to_ffi_swift_type(types)
                     }
                 },
                 StdLibType::Result(result) => result.to_swift_type(type_pos, types),
@@ -1080,9 +1292,9 @@ impl BridgedType {
         }
     }
 
-    pub fn to_c(&self) -> String {
+    pub fn to_c(&self, types: &TypeDeclarations) -> String {
         match self {
-            BridgedType::Bridgeable(b) => b.to_c_type(),
+            BridgedType::Bridgeable(b) => b.to_c_type(types),
             BridgedType::StdLib(stdlib_type) => match stdlib_type {
                 StdLibType::U8 => "uint8_t".to_string(),
                 StdLibType::I8 => "int8_t".to_string(),
@@ -1096,6 +1308,8 @@ impl BridgedType {
                 StdLibType::F64 => "double".to_string(),
                 StdLibType::Usize => "uintptr_t".to_string(),
                 StdLibType::Isize => "intptr_t".to_string(),
+                StdLibType::U128 => format!("struct {}", types.private_type_name("U128")),
+                StdLibType::I128 => format!("struct {}", types.private_type_name("I128")),
                 StdLibType::Bool => "bool".to_string(),
                 StdLibType::Pointer(ptr) => {
                     let maybe_const = match ptr.kind {
@@ -1105,17 +1319,19 @@ impl BridgedType {
 
                     match &ptr.pointee {
                         Pointee::BuiltIn(ty) => {
-                            format!("{}{}*", ty.to_c(), maybe_const)
+                            format!("{}{}*", ty.to_c(types), maybe_const)
                         }
                         Pointee::Void(_) => "void*".to_string(),
                     }
                 }
-                StdLibType::RefSlice(_slice) => "struct __private__FfiSlice".to_string(),
+                StdLibType::RefSlice(_slice) => {
+                    format!("struct {}", types.private_type_name("FfiSlice"))
+                }
                 StdLibType::Str => "struct RustStr".to_string(),
                 StdLibType::Null => "void".to_string(),
                 StdLibType::Vec(_) => "void*".to_string(),
-                StdLibType::Option(opt) => opt.to_c(),
-                StdLibType::Result(result) => result.to_c().to_string(),
+                StdLibType::Option(opt) => opt.to_c(types),
+                StdLibType::Result(result) => result.to_c(types),
                 StdLibType::BoxedFnOnce(_) => "void*".to_string(),
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
@@ -1203,6 +1419,12 @@ impl BridgedType {
                 | StdLibType::Bool => {
                     quote! { #expression }
                 }
+                StdLibType::U128 => {
+                    quote! { #swift_bridge_path::int128::FfiU128::from( #expression ) }
+                }
+                StdLibType::I128 => {
+                    quote! { #swift_bridge_path::int128::FfiI128::from( #expression ) }
+                }
                 StdLibType::Pointer(_) => {
                     quote! {
                         #expression
@@ -1219,17 +1441,16 @@ impl BridgedType {
                     }
                 }
                 StdLibType::Vec(_) => {
-                    quote! { Box::into_raw(Box::new( #expression )) }
+                    quote! { #swift_bridge_path::allocator::box_into_raw( #expression ) }
                 }
                 StdLibType::Option(opt) => {
                     opt.convert_rust_expression_to_ffi_type(expression, swift_bridge_path)
                 }
-                StdLibType::Result(_) => {
-                    todo!("Result<T, E> is not yet supported")
-                }
-                StdLibType::BoxedFnOnce(fn_once) => {
-                    fn_once.convert_rust_value_to_ffi_compatible_value(expression)
+                StdLibType::Result(result) => {
+                    result.convert_rust_expression_to_ffi_type(expression, swift_bridge_path, types)
                 }
+                StdLibType::BoxedFnOnce(fn_once) => fn_once
+                    .convert_rust_value_to_ffi_compatible_value(expression, swift_bridge_path),
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(_shared_struct))) => {
                 quote! {
@@ -1278,6 +1499,12 @@ impl BridgedType {
                 | StdLibType::Bool => {
                     quote_spanned! {span=> #value }
                 }
+                StdLibType::U128 => {
+                    quote_spanned! {span=> u128::from(#value) }
+                }
+                StdLibType::I128 => {
+                    quote_spanned! {span=> i128::from(#value) }
+                }
                 StdLibType::Pointer(_) => {
                     quote_spanned! {span=> #value }
                 }
@@ -1289,11 +1516,11 @@ impl BridgedType {
                 }
                 StdLibType::Vec(_) => {
                     quote_spanned! {span=>
-                        unsafe { * Box::from_raw(#value) }
+                        unsafe { #swift_bridge_path::allocator::take_from_raw(#value) }
                     }
                 }
                 StdLibType::Option(bridged_option) => {
-                    bridged_option.convert_ffi_expression_to_rust_type(value)
+                    bridged_option.convert_ffi_expression_to_rust_type(value, swift_bridge_path)
                 }
                 StdLibType::Result(result) => {
                     result.convert_ffi_value_to_rust_value(value, span, swift_bridge_path, types)
@@ -1354,6 +1581,7 @@ impl BridgedType {
                 | StdLibType::F32
                 | StdLibType::F64
                 | StdLibType::Bool => expression.to_string(),
+                StdLibType::U128 | StdLibType::I128 => format!("{}.intoSwiftRepr()", expression),
                 StdLibType::Pointer(ptr) => match &ptr.pointee {
                     Pointee::BuiltIn(_) => expression.to_string(),
                     Pointee::Void(_ty) => match ptr.kind {
@@ -1390,8 +1618,8 @@ impl BridgedType {
                     format!("RustVec(ptr: {})", expression)
                 }
                 StdLibType::Option(opt) => opt.convert_ffi_expression_to_swift_type(expression),
-                StdLibType::Result(_) => {
-                    todo!("Result<T, E> is not yet supported")
+                StdLibType::Result(result) => {
+                    result.convert_ffi_value_to_swift_value(expression, type_pos, types)
                 }
                 StdLibType::BoxedFnOnce(fn_once) => {
                     fn_once.convert_ffi_value_to_swift_value(type_pos)
@@ -1437,6 +1665,7 @@ impl BridgedType {
                 | StdLibType::F32
                 | StdLibType::F64
                 | StdLibType::Bool => expression.to_string(),
+                StdLibType::U128 | StdLibType::I128 => format!("{}.intoFfiRepr()", expression),
                 StdLibType::RefSlice(_) => {
                     format!("{}.toFfiSlice()", expression)
                 }
@@ -1595,6 +1824,9 @@ impl BridgedType {
                     rust: quote! { bool },
                     swift: "bool".into(),
                 },
+                StdLibType::U128 | StdLibType::I128 => {
+                    unreachable!("Option<u128> and Option<i128> are rejected while parsing")
+                }
                 StdLibType::Pointer(_) => {
                     todo!("Support Option<*const T> and Option<*mut T>")
                 }
@@ -1667,6 +1899,34 @@ impl BridgedType {
         }
     }
 
+    /// Whether or not the type is a `u128` or `i128`, or a type that contains one such as
+    /// `Option<SomeStruct>` where `struct SomeStruct { field: u128 }`.
+    ///
+    /// Swift code that uses such a type needs [`SWIFT_INT128_AVAILABILITY`].
+    pub fn contains_128_bit_int_recursive(&self, types: &TypeDeclarations) -> bool {
+        match self {
+            BridgedType::Bridgeable(_) => false,
+            BridgedType::StdLib(stdlib_type) => match stdlib_type {
+                StdLibType::U128 | StdLibType::I128 => true,
+                StdLibType::Vec(inner) => inner.ty.contains_128_bit_int_recursive(types),
+                StdLibType::Option(inner) => inner.ty.contains_128_bit_int_recursive(types),
+                StdLibType::RefSlice(inner) => inner.ty.contains_128_bit_int_recursive(types),
+                _ => false,
+            },
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
+                shared_struct
+                    .fields
+                    .normalized_fields()
+                    .iter()
+                    .filter_map(|field| BridgedType::new_with_type(&field.ty, types))
+                    .any(|field| field.contains_128_bit_int_recursive(types))
+            }
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(_shared_enum))) => {
+                false
+            }
+        }
+    }
+
     /// Whether or not the type is a `&str`, or a type that contains a &str such as
     /// `Option<&str>` or `struct Foo { field: &'static str } `
     pub fn contains_ref_string_recursive(&self) -> bool {
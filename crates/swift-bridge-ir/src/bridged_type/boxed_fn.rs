@@ -4,7 +4,7 @@ use crate::parsed_extern_fn::SwiftFuncGenerics;
 use crate::TypeDeclarations;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::str::FromStr;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
@@ -45,12 +45,13 @@ impl BridgeableBoxedFnOnce {
     pub fn convert_rust_value_to_ffi_compatible_value(
         &self,
         expression: &TokenStream,
+        swift_bridge_path: &Path,
     ) -> TokenStream {
         let args: Vec<TokenStream> = self.params.iter().map(|a| a.to_rust_type_path()).collect();
         let ret = &self.ret.to_rust_type_path();
 
         quote! {
-            Box::into_raw(Box::new(#expression)) as *mut Box<dyn FnOnce(#(#args),*) -> #ret>
+            #swift_bridge_path::allocator::box_into_raw(#expression) as *mut Box<dyn FnOnce(#(#args),*) -> #ret>
         }
     }
 
@@ -101,12 +102,12 @@ impl BridgeableBoxedFnOnce {
 
     /// Box<dyn FnOnce(u8, SomeRustType)> becomes:
     /// uint8_t arg0, *void arg1
-    pub fn params_to_c_types(&self) -> String {
+    pub fn params_to_c_types(&self, types: &TypeDeclarations) -> String {
         self.params
             .iter()
             .enumerate()
             .map(|(idx, ty)| {
-                let ty = ty.to_c();
+                let ty = ty.to_c(types);
 
                 format!("{ty} arg{idx}")
             })
@@ -117,7 +118,7 @@ impl BridgeableBoxedFnOnce {
     /// Returns each `arg0, arg1, ... argN`.
     ///
     /// For example, `Box<dyn FnOnce(u8, SomeType)>` would give us:
-    /// arg0, unsafe { *Box::from_raw(arg1) }
+    /// arg0, unsafe { swift_bridge::allocator::take_from_raw(arg1) }
     pub fn to_rust_call_args(
         &self,
         swift_bridge_path: &Path,
@@ -198,7 +199,7 @@ impl BridgeableBoxedFnOnce {
     /// For example:
     /// "<GenericRustString: IntoRustString>"
     pub fn maybe_swift_generics(&self) -> String {
-        let mut maybe_generics = HashSet::new();
+        let mut maybe_generics = BTreeSet::new();
 
         for bridged_arg in &self.params {
             if bridged_arg.contains_owned_string_recursive() {
@@ -233,8 +234,8 @@ impl BridgeableBoxedFnOnce {
         // ( A , B , C ) >
         let signature = string.trim_start_matches("Box < dyn FnOnce");
 
-        let open_parens = signature.find("(").unwrap();
-        let closing_parens = signature.find(")").unwrap();
+        let open_parens = signature.find("(")?;
+        let closing_parens = signature.find(")")?;
         // A, B, C
         let args = &signature[open_parens + 1..closing_parens];
 
@@ -243,15 +244,15 @@ impl BridgeableBoxedFnOnce {
         // D
         let ret = return_idx.map(|idx| &signature[(idx + 3)..signature.len() - 2]);
 
-        let args = TokenStream::from_str(args).unwrap();
-        let args: FunctionArguments = syn::parse2(args).unwrap();
+        let args = TokenStream::from_str(args).ok()?;
+        let args: FunctionArguments = syn::parse2(args).ok()?;
 
         let ret = if let Some(ret) = ret {
             // Parse out the comma in:
             //   Box<dyn FnOnce() -> (),>
             let ret = ret.trim_end_matches(",");
 
-            let ret = syn::parse2::<Type>(TokenStream::from_str(ret).unwrap()).unwrap();
+            let ret = syn::parse2::<Type>(TokenStream::from_str(ret).ok()?).ok()?;
             BridgedType::new_with_type(&ret, types)?
         } else {
             BridgedType::StdLib(StdLibType::Null)
@@ -261,6 +262,9 @@ impl BridgeableBoxedFnOnce {
         for arg in args.0 {
             args_bridged_tys.push(BridgedType::new_with_type(&arg, types)?);
         }
+        if ret.is_128_bit_int() || args_bridged_tys.iter().any(|arg| arg.is_128_bit_int()) {
+            return None;
+        }
 
         return Some(BridgeableBoxedFnOnce {
             params: args_bridged_tys,
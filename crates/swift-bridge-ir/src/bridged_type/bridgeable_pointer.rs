@@ -21,7 +21,7 @@ pub(crate) enum Pointee {
     BuiltIn(Box<BridgedType>),
     /// `*const SomeType`
     ///         ^^^^^^^^ This is the Pointee
-    Void(Type),
+    Void(Box<Type>),
 }
 
 impl ToTokens for PointerKind {
@@ -1,6 +1,6 @@
-use crate::bridged_type::{BridgeableType, BridgedType, TypePosition};
-use crate::TypeDeclarations;
-use proc_macro2::{Span, TokenStream};
+use crate::bridged_type::{c_struct_size_and_align, BridgeableType, BridgedType, TypePosition};
+use crate::{TypeDeclarations, SWIFT_BRIDGE_PREFIX};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::Path;
 
@@ -17,19 +17,36 @@ use syn::Path;
 pub(crate) struct BuiltInResult {
     pub ok_ty: Box<dyn BridgeableType>,
     pub err_ty: Box<dyn BridgeableType>,
+    /// The symbol prefix of the module that the result is used in, which names the struct that
+    /// a by-value result is passed as.
+    pub symbol_prefix: String,
+    /// The prefix of the shared C types, such as `__private__ResultPtrAndPtr`.
+    pub private_type_prefix: String,
+    /// `10SomeStructAnd5super8SomeType`, which names the struct of a result that holds a shared
+    /// struct. See [`BuiltInResult::ffi_name_string`].
+    pub ffi_name_suffix: String,
+    /// Whether the struct of a result that holds a shared struct is too large to be passed by
+    /// value, so it gets boxed instead. See [`BuiltInResult::ffi_repr`].
+    pub boxed: bool,
 }
 
 impl BuiltInResult {
     pub(super) fn to_ffi_compatible_rust_type(&self, swift_bridge_path: &Path) -> TokenStream {
-        // TODO: Choose the kind of Result representation based on whether or not the ok and error
-        //  types are primitives.
-        //  See `swift-bridge/src/std_bridge/result`
-        let result_kind = quote! {
-            ResultPtrAndPtr
-        };
-
-        quote! {
-            #swift_bridge_path::result::#result_kind
+        match self.ffi_repr() {
+            ResultFfiRepr::PtrAndPtr => quote! {
+                #swift_bridge_path::result::ResultPtrAndPtr
+            },
+            ResultFfiRepr::Bits64 => quote! {
+                #swift_bridge_path::result::ResultBits64
+            },
+            ResultFfiRepr::ByValue => {
+                let ffi_name = self.ffi_name_ident();
+                quote! { #ffi_name }
+            }
+            ResultFfiRepr::Boxed => {
+                let ffi_name = self.ffi_name_ident();
+                quote! { *mut #ffi_name }
+            }
         }
     }
 
@@ -40,17 +57,102 @@ impl BuiltInResult {
         swift_bridge_path: &Path,
         types: &TypeDeclarations,
     ) -> TokenStream {
-        let convert_ok = self.ok_ty.convert_ffi_result_ok_value_to_rust_value(
-            expression,
-            swift_bridge_path,
-            types,
-        );
+        if self.has_result_struct() {
+            let convert_ok = if self.ok_ty.is_null() {
+                quote! { () }
+            } else {
+                let ok = self.ok_ty.convert_ffi_expression_to_rust_type(
+                    &quote! { ok },
+                    span,
+                    swift_bridge_path,
+                    types,
+                );
+                quote! {{
+                    let ok = unsafe { val.ok.assume_init() };
+                    #ok
+                }}
+            };
+            let convert_err = if self.err_ty.is_null() {
+                quote! { () }
+            } else {
+                let err = self.err_ty.convert_ffi_expression_to_rust_type(
+                    &quote! { err },
+                    span,
+                    swift_bridge_path,
+                    types,
+                );
+                quote! {{
+                    let err = unsafe { val.err.assume_init() };
+                    #err
+                }}
+            };
 
-        let convert_err = self.err_ty.convert_ffi_result_err_value_to_rust_value(
-            expression,
-            swift_bridge_path,
-            types,
-        );
+            let val = if self.boxed {
+                quote! { unsafe { #swift_bridge_path::result::unbox_result_struct(#expression) } }
+            } else {
+                expression.clone()
+            };
+
+            // The expression can be a call to a Swift function, so it only gets evaluated once.
+            return quote_spanned! {span=>
+                {
+                    let val = #val;
+                    if val.is_ok {
+                        std::result::Result::Ok(#convert_ok)
+                    } else {
+                        std::result::Result::Err(#convert_err)
+                    }
+                }
+            };
+        }
+
+        if self.ffi_repr() == ResultFfiRepr::Bits64 {
+            let from_bits = |ty: &dyn BridgeableType, name: TokenStream| {
+                if ty.is_null() {
+                    return quote! { () };
+                }
+
+                let ffi_ty = ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+                let convert =
+                    ty.convert_ffi_expression_to_rust_type(&name, span, swift_bridge_path, types);
+                quote! {{
+                    let #name: #ffi_ty = #swift_bridge_path::result::ResultBits::from_bits(
+                        #expression.ok_or_err
+                    );
+                    #convert
+                }}
+            };
+            let convert_ok = from_bits(self.ok_ty.as_ref(), quote! { ok });
+            let convert_err = from_bits(self.err_ty.as_ref(), quote! { err });
+
+            return quote_spanned! {span=>
+                if #expression.is_ok {
+                    std::result::Result::Ok(#convert_ok)
+                } else {
+                    std::result::Result::Err(#convert_err)
+                }
+            };
+        }
+
+        let convert_ok = if self.ok_ty.is_null() {
+            quote! { () }
+        } else {
+            self.ok_ty.convert_ffi_result_ok_value_to_rust_value(
+                expression,
+                swift_bridge_path,
+                types,
+            )
+        };
+
+        let convert_err = if self.err_ty.is_null() {
+            quote! { () }
+        } else {
+            self.err_ty.convert_ffi_result_err_value_to_rust_value(
+                expression,
+                swift_bridge_path,
+                types,
+            )
+        };
 
         quote_spanned! {span=>
             if #expression.is_ok {
@@ -61,6 +163,105 @@ impl BuiltInResult {
         }
     }
 
+    /// Convert a Rust `Result<T, E>` into a `ResultPtrAndPtr` or a `ResultBits64`, or into the
+    /// struct of a result that holds a shared struct.
+    pub(super) fn convert_rust_expression_to_ffi_type(
+        &self,
+        expression: &TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        if self.has_result_struct() {
+            let ffi_name = self.ffi_name_ident();
+
+            let ok_field = |ok: TokenStream| {
+                if self.ok_ty.is_null() {
+                    quote! {}
+                } else {
+                    quote! { ok: #ok, }
+                }
+            };
+            let err_field = |err: TokenStream| {
+                if self.err_ty.is_null() {
+                    quote! {}
+                } else {
+                    quote! { err: #err, }
+                }
+            };
+
+            let convert_ok = self.ok_ty.convert_rust_expression_to_ffi_type(
+                &quote! { ok },
+                swift_bridge_path,
+                types,
+            );
+            let convert_err = self.err_ty.convert_rust_expression_to_ffi_type(
+                &quote! { err },
+                swift_bridge_path,
+                types,
+            );
+
+            let ok_is_ok = ok_field(quote! { std::mem::MaybeUninit::new(#convert_ok) });
+            let err_is_ok = err_field(quote! { std::mem::MaybeUninit::uninit() });
+            let ok_is_err = ok_field(quote! { std::mem::MaybeUninit::uninit() });
+            let err_is_err = err_field(quote! { std::mem::MaybeUninit::new(#convert_err) });
+
+            let convert = quote! {
+                match #expression {
+                    Ok(ok) => #ffi_name {
+                        is_ok: true,
+                        #ok_is_ok
+                        #err_is_ok
+                    },
+                    Err(err) => #ffi_name {
+                        is_ok: false,
+                        #ok_is_err
+                        #err_is_err
+                    }
+                }
+            };
+
+            return if self.boxed {
+                quote! { #swift_bridge_path::result::box_result_struct(#convert) }
+            } else {
+                convert
+            };
+        }
+
+        let (result_kind, null) = match self.ffi_repr() {
+            ResultFfiRepr::Bits64 => (quote! { ResultBits64 }, quote! { 0 }),
+            _ => (quote! { ResultPtrAndPtr }, quote! { std::ptr::null_mut() }),
+        };
+        let to_ffi = |ty: &dyn BridgeableType, name: TokenStream| {
+            if ty.is_null() {
+                return (quote! { _ }, null.clone());
+            }
+
+            let convert = ty.convert_rust_expression_to_ffi_type(&name, swift_bridge_path, types);
+            let convert = match self.ffi_repr() {
+                ResultFfiRepr::Bits64 => quote! {
+                    #swift_bridge_path::result::ResultBits::into_bits(#convert)
+                },
+                _ => quote! { #convert as *mut std::ffi::c_void },
+            };
+            (name, convert)
+        };
+        let (ok_pattern, convert_ok) = to_ffi(self.ok_ty.as_ref(), quote! { ok });
+        let (err_pattern, convert_err) = to_ffi(self.err_ty.as_ref(), quote! { err });
+
+        quote! {
+            match #expression {
+                Ok(#ok_pattern) => #swift_bridge_path::result::#result_kind {
+                    is_ok: true,
+                    ok_or_err: #convert_ok
+                },
+                Err(#err_pattern) => #swift_bridge_path::result::#result_kind {
+                    is_ok: false,
+                    ok_or_err: #convert_err
+                }
+            }
+        }
+    }
+
     pub fn to_rust_type_path(&self) -> TokenStream {
         let ok = self.ok_ty.to_rust_type_path();
         let err = self.err_ty.to_rust_type_path();
@@ -81,25 +282,317 @@ impl BuiltInResult {
         expression: &str,
         type_pos: TypePosition,
     ) -> String {
-        let convert_ok = self
-            .ok_ty
-            .convert_swift_expression_to_ffi_type("ok", type_pos);
-        let convert_err = self
-            .err_ty
-            .convert_swift_expression_to_ffi_type("err", type_pos);
+        if self.has_result_struct() {
+            let (ok_pattern, set_ok) = if self.ok_ty.is_null() {
+                ("_".to_string(), "".to_string())
+            } else {
+                (
+                    "let ok".to_string(),
+                    format!(
+                        " ffiResult.ok = {};",
+                        self.ok_ty
+                            .convert_swift_expression_to_ffi_type("ok", type_pos)
+                    ),
+                )
+            };
+            let (err_pattern, set_err) = if self.err_ty.is_null() {
+                ("_".to_string(), "".to_string())
+            } else {
+                (
+                    "let err".to_string(),
+                    format!(
+                        " ffiResult.err = {};",
+                        self.err_ty
+                            .convert_swift_expression_to_ffi_type("err", type_pos)
+                    ),
+                )
+            };
+
+            let ffi_name = self.ffi_name_string();
+            let return_ffi_result = if self.boxed {
+                format!(
+                    "let ptr = __swift_bridge__$Result$_alloc(UInt(MemoryLayout<{ffi_name}>.stride), UInt(MemoryLayout<{ffi_name}>.alignment))!.bindMemory(to: {ffi_name}.self, capacity: 1); ptr.initialize(to: ffiResult); return ptr"
+                )
+            } else {
+                "return ffiResult".to_string()
+            };
+
+            return format!(
+                "{{ var ffiResult = {ffi_name}(); switch {expression} {{ case .Ok({ok_pattern}): ffiResult.is_ok = true;{set_ok} case .Err({err_pattern}): ffiResult.is_ok = false;{set_err} }}; {return_ffi_result} }}()",
+            );
+        }
+
+        let (result_kind, null) = match self.ffi_repr() {
+            ResultFfiRepr::Bits64 => ("ResultBits64", "0"),
+            _ => ("ResultPtrAndPtr", "nil"),
+        };
+        let result_kind = format!("{}{}", self.private_type_prefix, result_kind);
+        let to_ffi = |ty: &dyn BridgeableType, name: &str| {
+            if ty.is_null() {
+                return ("_".to_string(), null.to_string());
+            }
+
+            let convert = ty.convert_swift_expression_to_ffi_type(name, type_pos);
+            let convert = match self.ffi_repr() {
+                ResultFfiRepr::Bits64 => {
+                    format!("{}toResultBits({})", self.private_type_prefix, convert)
+                }
+                _ => convert,
+            };
+            (format!("let {}", name), convert)
+        };
+        let (ok_pattern, convert_ok) = to_ffi(self.ok_ty.as_ref(), "ok");
+        let (err_pattern, convert_err) = to_ffi(self.err_ty.as_ref(), "err");
 
         format!(
-            "{{ switch {val} {{ case .Ok(let ok): return __private__ResultPtrAndPtr(is_ok: true, ok_or_err: {convert_ok}) case .Err(let err): return __private__ResultPtrAndPtr(is_ok: false, ok_or_err: {convert_err}) }} }}()",
+            "{{ switch {val} {{ case .Ok({ok_pattern}): return {result_kind}(is_ok: true, ok_or_err: {convert_ok}) case .Err({err_pattern}): return {result_kind}(is_ok: false, ok_or_err: {convert_err}) }} }}()",
             val = expression
         )
     }
 
-    pub fn to_c(&self) -> &'static str {
-        // TODO: Choose the kind of Result representation based on whether or not the ok and error
-        //  types are primitives.
-        //  See `swift-bridge/src/std_bridge/result`
-        "struct __private__ResultPtrAndPtr"
+    /// Convert the FFI representation of a result that Rust handed to Swift into a `RustResult`.
+    pub fn convert_ffi_value_to_swift_value(
+        &self,
+        expression: &str,
+        type_pos: TypePosition,
+        types: &TypeDeclarations,
+    ) -> String {
+        let repr = self.ffi_repr();
+        let to_swift = |ty: &dyn BridgeableType, field: &str| {
+            if ty.is_null() {
+                return "()".to_string();
+            }
+
+            let value = match repr {
+                ResultFfiRepr::PtrAndPtr => "val.ok_or_err!".to_string(),
+                ResultFfiRepr::Bits64 => {
+                    format!(
+                        "({}(val.ok_or_err) as {})",
+                        types.private_type_name("fromResultBits"),
+                        swift_ffi_bits_type(ty, type_pos, types)
+                    )
+                }
+                ResultFfiRepr::ByValue | ResultFfiRepr::Boxed => format!("val.{}", field),
+            };
+            ty.convert_ffi_expression_to_swift_type(&value, type_pos, types)
+        };
+
+        let read_val = if repr == ResultFfiRepr::Boxed {
+            format!(
+                "let ptr: UnsafeMutablePointer<{ffi_name}> = {expression}; let val = ptr.pointee; __swift_bridge__$Result$_free(ptr, UInt(MemoryLayout<{ffi_name}>.stride), UInt(MemoryLayout<{ffi_name}>.alignment))",
+                ffi_name = self.ffi_name_string()
+            )
+        } else {
+            format!("let val = {expression}")
+        };
+
+        format!(
+            "{{ {read_val}; if val.is_ok {{ return {result}.Ok({ok}) }} else {{ return {result}.Err({err}) }} }}()",
+            result = self.to_swift_type(type_pos, types),
+            ok = to_swift(self.ok_ty.as_ref(), "ok"),
+            err = to_swift(self.err_ty.as_ref(), "err"),
+        )
+    }
+
+    pub fn to_c(&self, types: &TypeDeclarations) -> String {
+        match self.ffi_repr() {
+            ResultFfiRepr::PtrAndPtr => {
+                format!("struct {}", types.private_type_name("ResultPtrAndPtr"))
+            }
+            ResultFfiRepr::Bits64 => format!("struct {}", types.private_type_name("ResultBits64")),
+            ResultFfiRepr::ByValue => format!("struct {}", self.ffi_name_string()),
+            ResultFfiRepr::Boxed => format!("struct {}*", self.ffi_name_string()),
+        }
+    }
+
+    /// Results that hold a shared struct get passed using a struct that is generated for the
+    /// bridge module, instead of boxing the shared struct and passing a `ResultPtrAndPtr`.
+    ///
+    /// ```ignore
+    /// // Result<SomeStruct, SomeType>
+    /// #[repr(C)]
+    /// pub struct __swift_bridge__Result_10SomeStructAnd5super8SomeType {
+    ///     is_ok: bool,
+    ///     ok: std::mem::MaybeUninit<__swift_bridge__SomeStruct>,
+    ///     err: std::mem::MaybeUninit<*mut super::SomeType>,
+    /// }
+    /// ```
+    pub(crate) fn has_result_struct(&self) -> bool {
+        self.ok_ty.is_shared_struct() || self.err_ty.is_shared_struct()
+    }
+
+    /// How the result gets passed over FFI.
+    ///
+    /// Results whose `Ok` and `Err` values are pointers or `()` get passed as a
+    /// `ResultPtrAndPtr`. Results that hold a primitive get passed as a `ResultBits64`, which
+    /// stores a primitive or a pointer in its 64 bit `ok_or_err`, so their other value must fit
+    /// in it too. Only results that hold a shared struct need a struct of their own, which gets
+    /// passed by value unless it is larger than [`MAX_BY_VALUE_RESULT_SIZE`].
+    pub(crate) fn ffi_repr(&self) -> ResultFfiRepr {
+        if self.has_result_struct() && self.boxed {
+            ResultFfiRepr::Boxed
+        } else if self.has_result_struct() {
+            ResultFfiRepr::ByValue
+        } else if self.ok_ty.is_primitive() || self.err_ty.is_primitive() {
+            ResultFfiRepr::Bits64
+        } else {
+            ResultFfiRepr::PtrAndPtr
+        }
+    }
+
+    /// `__swift_bridge__Result_10SomeStructAnd5super8SomeType`
+    pub(crate) fn ffi_name_ident(&self) -> Ident {
+        Ident::new(
+            &format!("{}Result_{}", SWIFT_BRIDGE_PREFIX, self.ffi_name_suffix),
+            Span::call_site(),
+        )
+    }
+
+    /// `__swift_bridge__$Result$10SomeStructAnd5super8SomeType`
+    pub(crate) fn ffi_name_string(&self) -> String {
+        format!("{}$Result${}", self.symbol_prefix, self.ffi_name_suffix)
+    }
+
+    /// The Rust definition of the struct that a result that holds a shared struct is passed as.
+    pub(crate) fn to_ffi_struct_definition(
+        &self,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let ffi_name = self.ffi_name_ident();
+
+        let ok_field = if self.ok_ty.is_null() {
+            quote! {}
+        } else {
+            let ok_ty = self
+                .ok_ty
+                .to_ffi_compatible_rust_type(swift_bridge_path, types);
+            quote! { ok: std::mem::MaybeUninit<#ok_ty>, }
+        };
+        let err_field = if self.err_ty.is_null() {
+            quote! {}
+        } else {
+            let err_ty = self
+                .err_ty
+                .to_ffi_compatible_rust_type(swift_bridge_path, types);
+            quote! { err: std::mem::MaybeUninit<#err_ty>, }
+        };
+
+        quote! {
+            #[repr(C)]
+            #[doc(hidden)]
+            pub struct #ffi_name {
+                is_ok: bool,
+                #ok_field
+                #err_field
+            }
+        }
+    }
+
+    /// The C declaration of the struct that a result that holds a shared struct is passed as.
+    ///
+    /// The declaration is guarded since every module that uses the same result declares it, and
+    /// the headers of those modules get included together.
+    pub(crate) fn to_c_struct_declaration(&self, types: &TypeDeclarations) -> String {
+        let mut fields = "bool is_ok; ".to_string();
+        if !self.ok_ty.is_null() {
+            fields += &format!("{} ok; ", self.ok_ty.to_c_type(types));
+        }
+        if !self.err_ty.is_null() {
+            fields += &format!("{} err; ", self.err_ty.to_c_type(types));
+        }
+
+        format!(
+            r#"#ifndef {ffi_name}$Declared
+#define {ffi_name}$Declared
+typedef struct {ffi_name} {{ {fields}}} {ffi_name};
+#endif"#,
+            ffi_name = self.ffi_name_string(),
+            fields = fields
+        )
+    }
+}
+
+/// The largest struct, in bytes, that a result that holds a shared struct gets passed by value
+/// as. Larger structs get boxed, so that each call copies a pointer instead of the whole struct.
+const MAX_BY_VALUE_RESULT_SIZE: usize = 64;
+
+/// The ways that a `Result<T, E>` can be passed over FFI. See [`BuiltInResult::ffi_repr`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum ResultFfiRepr {
+    /// `swift_bridge::result::ResultPtrAndPtr`
+    PtrAndPtr,
+    /// `swift_bridge::result::ResultBits64`
+    Bits64,
+    /// A struct generated for the bridge module, for results that hold a shared struct.
+    ByValue,
+    /// A pointer to a boxed `ByValue` struct, for when that struct is larger than
+    /// [`MAX_BY_VALUE_RESULT_SIZE`].
+    Boxed,
+}
+
+/// The Swift type that a `T` or `E` gets read from a `__private__ResultBits64` as, before it gets
+/// converted into its Swift representation.
+///
+/// `u8` -> `UInt8`, `String` -> `UnsafeMutableRawPointer`
+fn swift_ffi_bits_type(
+    ty: &dyn BridgeableType,
+    type_pos: TypePosition,
+    types: &TypeDeclarations,
+) -> String {
+    if ty.is_primitive() {
+        ty.to_swift_type(type_pos, types)
+    } else {
+        "UnsafeMutableRawPointer".to_string()
+    }
+}
+
+/// Whether a result's `T` or `E` can be stored in the `ok_or_err` bits of a `ResultBits64`,
+/// which is the case for primitives, `()` and the types that are passed as a pointer.
+fn fits_in_result_bits(ty: &dyn BridgeableType) -> bool {
+    ty.is_null() || ty.is_primitive() || ty.is_passed_as_pointer()
+}
+
+/// The name of a result's `T` or `E` within the name of the result's struct.
+///
+/// Every identifier in the type's full path is prefixed with its length, so that different types,
+/// such as `a::Point` and `b::Point`, never share a struct name. A shared struct is named by its
+/// Swift name, which also names its C struct.
+///
+/// `()` -> `Void`, `super::SomeType` -> `5super8SomeType`, `SomeType<u8>` -> `8SomeTypeI2u8E`
+fn ffi_name_part(ty: &dyn BridgeableType, types: &TypeDeclarations) -> String {
+    if ty.is_null() {
+        return "Void".to_string();
+    }
+
+    let path = if ty.is_shared_struct() {
+        ty.to_swift_type(TypePosition::SharedStructField, types)
+    } else {
+        ty.to_rust_type_path().to_string()
+    };
+
+    let mut name = String::new();
+    let mut chars = path.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            let mut ident = c.to_string();
+            while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                ident.push(c);
+            }
+            name += &format!("{}{}", ident.len(), ident);
+        } else {
+            match c {
+                '<' => name.push('I'),
+                '>' => name.push('E'),
+                '&' => name.push('R'),
+                '*' => name.push('P'),
+                '\'' => name.push('L'),
+                _ => {}
+            }
+        }
     }
+
+    name
 }
 
 impl BuiltInResult {
@@ -117,11 +610,42 @@ impl BuiltInResult {
 
         let ok = BridgedType::new_with_str(ok, types)?;
         let err = BridgedType::new_with_str(err, types)?;
+        if ok.is_128_bit_int() || err.is_128_bit_int() {
+            return None;
+        }
+
+        let ffi_name_suffix = format!(
+            "{}And{}",
+            ffi_name_part(&ok, types),
+            ffi_name_part(&err, types)
+        );
+        let struct_size = ok
+            .ffi_size_and_align(types)
+            .zip(err.ffi_size_and_align(types))
+            .map(|(ok, err)| c_struct_size_and_align([(1, 1), ok, err]).0);
+        // A struct whose size isn't known might be large.
+        let boxed = match struct_size {
+            Some(size) => size > MAX_BY_VALUE_RESULT_SIZE,
+            None => true,
+        };
 
-        Some(BuiltInResult {
+        let result = BuiltInResult {
             ok_ty: Box::new(ok),
             err_ty: Box::new(err),
-        })
+            symbol_prefix: types.symbol_prefix().to_string(),
+            private_type_prefix: types.private_type_prefix().to_string(),
+            ffi_name_suffix,
+            boxed,
+        };
+        // A shared enum or a `&str` doesn't fit next to a primitive in a `ResultBits64`.
+        if result.ffi_repr() == ResultFfiRepr::Bits64
+            && !(fits_in_result_bits(result.ok_ty.as_ref())
+                && fits_in_result_bits(result.err_ty.as_ref()))
+        {
+            return None;
+        }
+
+        Some(result)
     }
 }
 
@@ -34,7 +34,7 @@ impl BridgeableType for BridgedString {
         }
     }
 
-    fn to_c_type(&self) -> String {
+    fn to_c_type(&self, _types: &TypeDeclarations) -> String {
         "void*".to_string()
     }
 
@@ -136,20 +136,24 @@ impl BridgeableType for BridgedString {
         &self,
         expression: &TokenStream,
         span: Span,
-        _swift_bridge_path: &Path,
+        swift_bridge_path: &Path,
         _types: &TypeDeclarations,
     ) -> TokenStream {
         quote_spanned! {span=>
-            unsafe { Box::from_raw(#expression).0 }
+            unsafe { #swift_bridge_path::allocator::take_from_raw(#expression).0 }
         }
     }
 
-    fn convert_ffi_option_expression_to_rust_type(&self, expression: &TokenStream) -> TokenStream {
+    fn convert_ffi_option_expression_to_rust_type(
+        &self,
+        expression: &TokenStream,
+        swift_bridge_path: &Path,
+    ) -> TokenStream {
         quote! {
             if #expression.is_null() {
                 None
             } else {
-                Some(unsafe { Box::from_raw(#expression).0 } )
+                Some(unsafe { #swift_bridge_path::allocator::take_from_raw(#expression).0 } )
             }
         }
     }
@@ -184,7 +188,7 @@ impl BridgeableType for BridgedString {
     ) -> TokenStream {
         quote! {
             unsafe {
-                Box::from_raw(#result.ok_or_err as *mut #swift_bridge_path::string::RustString).0
+                #swift_bridge_path::allocator::take_from_raw(#result.ok_or_err as *mut #swift_bridge_path::string::RustString).0
             }
         }
     }
@@ -197,7 +201,7 @@ impl BridgeableType for BridgedString {
     ) -> TokenStream {
         quote! {
             unsafe {
-                Box::from_raw(#result.ok_or_err as *mut #swift_bridge_path::string::RustString).0
+                #swift_bridge_path::allocator::take_from_raw(#result.ok_or_err as *mut #swift_bridge_path::string::RustString).0
             }
         }
     }
@@ -249,6 +253,22 @@ impl BridgeableType for BridgedString {
         false
     }
 
+    fn is_shared_struct(&self) -> bool {
+        false
+    }
+
+    fn is_primitive(&self) -> bool {
+        false
+    }
+
+    fn is_passed_as_pointer(&self) -> bool {
+        true
+    }
+
+    fn ffi_size_and_align(&self, _types: &TypeDeclarations) -> Option<(usize, usize)> {
+        Some((8, 8))
+    }
+
     fn contains_owned_string_recursive(&self) -> bool {
         true
     }
@@ -1,5 +1,6 @@
 use crate::bridged_type::{BridgeableType, TypePosition, UnusedOptionNoneValue};
-use crate::parse::{HostLang, OpaqueRustTypeGenerics};
+use crate::parse::{HostLang, OpaqueRustTypeGenerics, TypeDeclaration};
+use crate::swift_keywords::rename_swift_keyword;
 use crate::{TypeDeclarations, SWIFT_BRIDGE_PREFIX};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
@@ -13,8 +14,17 @@ pub(crate) struct OpaqueForeignType {
     pub host_lang: HostLang,
     pub reference: bool,
     pub mutable: bool,
+    /// Whether or not the type is an already boxed `Box<T>`, whose allocation gets passed across
+    /// the boundary instead of being moved into a new box.
+    pub boxed: bool,
     pub has_swift_bridge_copy_annotation: bool,
     pub generics: OpaqueRustTypeGenerics,
+    /// The `#[swift_bridge(swift_name = "...")]` of the type's declaration, if any.
+    pub swift_name: Option<String>,
+    /// Whether or not the type's declaration has the `#[swift_bridge(protocol)]` attribute.
+    pub is_swift_protocol: bool,
+    /// The symbol prefix of the module that declared the type.
+    pub symbol_prefix: String,
 }
 
 impl BridgeableType for OpaqueForeignType {
@@ -25,7 +35,11 @@ impl BridgeableType for OpaqueForeignType {
     fn to_rust_type_path(&self) -> TokenStream {
         let ty_name = &self.ty;
 
-        if self.host_lang.is_rust() {
+        if self.boxed {
+            quote! {
+                Box<super:: #ty_name>
+            }
+        } else if self.host_lang.is_rust() {
             quote! {
                 super:: #ty_name
             }
@@ -41,7 +55,7 @@ impl BridgeableType for OpaqueForeignType {
             match type_pos {
                 TypePosition::FnArg(func_host_lang, _) | TypePosition::FnReturn(func_host_lang) => {
                     if func_host_lang.is_rust() {
-                        let mut class_name = self.ty.to_string();
+                        let mut class_name = self.swift_name();
 
                         if !self.has_swift_bridge_copy_annotation {
                             if self.reference {
@@ -68,14 +82,18 @@ impl BridgeableType for OpaqueForeignType {
                     unimplemented!()
                 }
                 TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
-                    unimplemented!()
+                    if self.has_swift_bridge_copy_annotation {
+                        self.copy_ffi_repr_type_string()
+                    } else {
+                        "UnsafeMutableRawPointer?".to_string()
+                    }
                 }
             }
         } else {
             match type_pos {
                 TypePosition::FnArg(func_host_lang, _) | TypePosition::FnReturn(func_host_lang) => {
                     if func_host_lang.is_rust() {
-                        self.ty.to_string()
+                        self.swift_name()
                     } else {
                         "UnsafeMutableRawPointer".to_string()
                     }
@@ -91,7 +109,7 @@ impl BridgeableType for OpaqueForeignType {
         }
     }
 
-    fn to_c_type(&self) -> String {
+    fn to_c_type(&self, _types: &TypeDeclarations) -> String {
         if self.host_lang.is_rust() {
             if self.has_swift_bridge_copy_annotation {
                 format!("struct {}", self.copy_ffi_repr_type_string())
@@ -177,7 +195,7 @@ impl BridgeableType for OpaqueForeignType {
     fn convert_rust_expression_to_ffi_type(
         &self,
         expression: &TokenStream,
-        _swift_bridge_path: &Path,
+        swift_bridge_path: &Path,
         types: &TypeDeclarations,
     ) -> TokenStream {
         let ty_name = &self.ty;
@@ -202,8 +220,14 @@ impl BridgeableType for OpaqueForeignType {
                 let generics = self
                     .generics
                     .angle_bracketed_concrete_generics_tokens(types);
-                quote! {
-                    Box::into_raw(Box::new(#expression)) as *mut super::#ty_name #generics
+                if self.boxed {
+                    quote! {
+                        #swift_bridge_path::allocator::boxed_into_raw(#expression) as *mut super::#ty_name #generics
+                    }
+                } else {
+                    quote! {
+                        #swift_bridge_path::allocator::box_into_raw(#expression) as *mut super::#ty_name #generics
+                    }
                 }
             }
         } else {
@@ -216,7 +240,7 @@ impl BridgeableType for OpaqueForeignType {
     fn convert_option_rust_expression_to_ffi_type(
         &self,
         expression: &TokenStream,
-        _swift_bridge_path: &Path,
+        swift_bridge_path: &Path,
     ) -> TokenStream {
         if self.has_swift_bridge_copy_annotation {
             let copy_repr = self.copy_rust_repr_type();
@@ -235,10 +259,18 @@ impl BridgeableType for OpaqueForeignType {
                     }
                 }
             }
+        } else if self.boxed {
+            quote! {
+                if let Some(val) = #expression {
+                    #swift_bridge_path::allocator::boxed_into_raw(val)
+                } else {
+                    std::ptr::null_mut()
+                }
+            }
         } else {
             quote! {
                 if let Some(val) = #expression {
-                    Box::into_raw(Box::new(val))
+                    #swift_bridge_path::allocator::box_into_raw(val)
                 } else {
                     std::ptr::null_mut()
                 }
@@ -251,8 +283,6 @@ impl BridgeableType for OpaqueForeignType {
         expression: &str,
         type_pos: TypePosition,
     ) -> String {
-        let ty_name = &self.ty;
-
         if self.host_lang.is_rust() {
             if self.has_swift_bridge_copy_annotation {
                 format!("{}.intoFfiRepr()", expression)
@@ -283,18 +313,12 @@ impl BridgeableType for OpaqueForeignType {
             match type_pos {
                 TypePosition::FnArg(func_host_lang, _) => {
                     if func_host_lang.is_rust() {
-                        format!("Unmanaged.passRetained({}).toOpaque()", expression)
+                        self.swift_pass_retained(expression)
                     } else {
-                        format!(
-                            "Unmanaged<{type_name}>.fromOpaque({value}).takeRetainedValue()",
-                            type_name = ty_name,
-                            value = expression
-                        )
+                        self.swift_take_retained(expression)
                     }
                 }
-                TypePosition::FnReturn(_func_host_lang) => {
-                    format!("Unmanaged.passRetained({}).toOpaque()", expression)
-                }
+                TypePosition::FnReturn(_func_host_lang) => self.swift_pass_retained(expression),
                 TypePosition::SharedStructField => {
                     todo!("Opaque types in shared struct fields are not yet supported")
                 }
@@ -327,7 +351,7 @@ impl BridgeableType for OpaqueForeignType {
         &self,
         expression: &TokenStream,
         _span: Span,
-        _swift_bridge_path: &Path,
+        swift_bridge_path: &Path,
         _types: &TypeDeclarations,
     ) -> TokenStream {
         if self.host_lang.is_rust() {
@@ -350,9 +374,13 @@ impl BridgeableType for OpaqueForeignType {
                 quote! {
                     unsafe {  & #maybe_mut * #expression }
                 }
+            } else if self.boxed {
+                quote! {
+                    unsafe { #swift_bridge_path::allocator::take_boxed_from_raw(#expression) }
+                }
             } else {
                 quote! {
-                    unsafe { * Box::from_raw(  #expression ) }
+                    unsafe { #swift_bridge_path::allocator::take_from_raw(  #expression ) }
                 }
             }
         } else {
@@ -366,7 +394,11 @@ impl BridgeableType for OpaqueForeignType {
         }
     }
 
-    fn convert_ffi_option_expression_to_rust_type(&self, expression: &TokenStream) -> TokenStream {
+    fn convert_ffi_option_expression_to_rust_type(
+        &self,
+        expression: &TokenStream,
+        swift_bridge_path: &Path,
+    ) -> TokenStream {
         if self.has_swift_bridge_copy_annotation {
             quote! {
                 if #expression.is_some {
@@ -375,12 +407,20 @@ impl BridgeableType for OpaqueForeignType {
                     None
                 }
             }
+        } else if self.boxed {
+            quote! {
+                if #expression.is_null() {
+                    None
+                } else {
+                    Some(unsafe { #swift_bridge_path::allocator::take_boxed_from_raw(#expression) } )
+                }
+            }
         } else {
             quote! {
                 if #expression.is_null() {
                     None
                 } else {
-                    Some(unsafe { * Box::from_raw(#expression) } )
+                    Some(unsafe { #swift_bridge_path::allocator::take_from_raw(#expression) } )
                 }
             }
         }
@@ -392,7 +432,7 @@ impl BridgeableType for OpaqueForeignType {
         type_pos: TypePosition,
         _types: &TypeDeclarations,
     ) -> String {
-        let mut ty_name = self.ty.to_string();
+        let mut ty_name = self.swift_name();
 
         if self.reference {
             ty_name += "Ref";
@@ -413,6 +453,9 @@ impl BridgeableType for OpaqueForeignType {
                     TypePosition::FnReturn(fn_host_lang) if fn_host_lang.is_swift() => {
                         format!("Unmanaged.passRetained({expression}).toOpaque()")
                     }
+                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
+                        format!("{ty_name}(ptr: {expression}!)")
+                    }
                     _ => {
                         format!(
                             "{ty_name}(ptr: {value})",
@@ -423,11 +466,7 @@ impl BridgeableType for OpaqueForeignType {
                 }
             }
         } else {
-            format!(
-                "Unmanaged<{ty_name}>.fromOpaque({value}).takeRetainedValue()",
-                ty_name = ty_name,
-                value = expression
-            )
+            self.swift_take_retained(expression)
         }
     }
 
@@ -452,7 +491,7 @@ impl BridgeableType for OpaqueForeignType {
     fn convert_ffi_result_ok_value_to_rust_value(
         &self,
         result: &TokenStream,
-        _swift_bridge_path: &Path,
+        swift_bridge_path: &Path,
         _types: &TypeDeclarations,
     ) -> TokenStream {
         let ty = &self.ty;
@@ -460,7 +499,7 @@ impl BridgeableType for OpaqueForeignType {
         match self.host_lang {
             HostLang::Rust => {
                 quote! {
-                    unsafe { *Box::from_raw(#result.ok_or_err as *mut super::#ty) }
+                    unsafe { #swift_bridge_path::allocator::take_from_raw(#result.ok_or_err as *mut super::#ty) }
                 }
             }
             HostLang::Swift => {
@@ -474,7 +513,7 @@ impl BridgeableType for OpaqueForeignType {
     fn convert_ffi_result_err_value_to_rust_value(
         &self,
         result: &TokenStream,
-        _swift_bridge_path: &Path,
+        swift_bridge_path: &Path,
         _types: &TypeDeclarations,
     ) -> TokenStream {
         let ty = &self.ty;
@@ -482,7 +521,7 @@ impl BridgeableType for OpaqueForeignType {
         match self.host_lang {
             HostLang::Rust => {
                 quote! {
-                    unsafe { *Box::from_raw(#result.ok_or_err as *mut super::#ty) }
+                    unsafe { #swift_bridge_path::allocator::take_from_raw(#result.ok_or_err as *mut super::#ty) }
                 }
             }
             HostLang::Swift => {
@@ -561,6 +600,32 @@ impl BridgeableType for OpaqueForeignType {
         false
     }
 
+    fn is_shared_struct(&self) -> bool {
+        false
+    }
+
+    fn is_primitive(&self) -> bool {
+        false
+    }
+
+    fn is_passed_as_pointer(&self) -> bool {
+        !self.has_swift_bridge_copy_annotation
+    }
+
+    fn ffi_size_and_align(&self, types: &TypeDeclarations) -> Option<(usize, usize)> {
+        if !self.has_swift_bridge_copy_annotation {
+            return Some((8, 8));
+        }
+
+        // `struct { uint8_t bytes[size]; }`
+        match types.get(&self.ty.to_string())? {
+            TypeDeclaration::Opaque(opaque) => {
+                opaque.attributes.copy.map(|copy| (copy.size_bytes, 1))
+            }
+            TypeDeclaration::Shared(_) => None,
+        }
+    }
+
     fn contains_owned_string_recursive(&self) -> bool {
         false
     }
@@ -576,7 +641,43 @@ impl BridgeableType for OpaqueForeignType {
 
 impl OpaqueForeignType {
     pub fn swift_name(&self) -> String {
-        format!("{}", self.ty)
+        match self.swift_name.as_ref() {
+            Some(swift_name) => swift_name.clone(),
+            None => rename_swift_keyword(&self.ty.to_string()),
+        }
+    }
+
+    /// Retain a Swift class instance and get a pointer to it.
+    ///
+    /// A `#[swift_bridge(protocol)]` value is an existential that `Unmanaged` can't hold, so we
+    /// retain it as an `AnyObject`.
+    fn swift_pass_retained(&self, expression: &str) -> String {
+        if self.is_swift_protocol {
+            format!(
+                "Unmanaged<AnyObject>.passRetained({}).toOpaque()",
+                expression
+            )
+        } else {
+            format!("Unmanaged.passRetained({}).toOpaque()", expression)
+        }
+    }
+
+    /// Take ownership of a Swift class instance from a pointer that was retained using
+    /// [`OpaqueForeignType::swift_pass_retained`].
+    fn swift_take_retained(&self, expression: &str) -> String {
+        if self.is_swift_protocol {
+            format!(
+                "(Unmanaged<AnyObject>.fromOpaque({}).takeRetainedValue() as! {})",
+                expression,
+                self.swift_name()
+            )
+        } else {
+            format!(
+                "Unmanaged<{}>.fromOpaque({}).takeRetainedValue()",
+                self.swift_name(),
+                expression
+            )
+        }
     }
 
     /// The name of the type used to pass a `#[swift_bridge(Copy(...))]` type over FFI
@@ -613,7 +714,7 @@ impl OpaqueForeignType {
     pub fn option_copy_ffi_repr_type_string(&self) -> String {
         format!(
             "{}$Option${}{}",
-            SWIFT_BRIDGE_PREFIX,
+            self.symbol_prefix,
             self.ty,
             self.generics.dollar_prefixed_generics_string()
         )
@@ -623,7 +724,7 @@ impl OpaqueForeignType {
     pub fn copy_ffi_repr_type_string(&self) -> String {
         format!(
             "{}${}{}",
-            SWIFT_BRIDGE_PREFIX,
+            self.symbol_prefix,
             self.ty,
             self.generics.dollar_prefixed_generics_string()
         )
@@ -637,6 +738,7 @@ impl Debug for OpaqueForeignType {
             .field("host_lang", &self.host_lang)
             .field("reference", &self.reference)
             .field("mutable", &self.mutable)
+            .field("boxed", &self.boxed)
             .finish()
     }
 }
@@ -647,6 +749,7 @@ impl PartialEq for OpaqueForeignType {
             && self.host_lang == other.host_lang
             && self.reference == other.reference
             && self.mutable == other.mutable
+            && self.boxed == other.boxed
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::bridged_type::{BridgedType, CustomBridgedType, SharedType, StdLibType, TypePosition};
+use crate::parse::TypeDeclarations;
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::ops::Deref;
@@ -35,6 +36,9 @@ impl BridgedOption {
                 StdLibType::Null => {
                     todo!("Option<()> is not yet supported")
                 }
+                StdLibType::U128 | StdLibType::I128 => {
+                    unreachable!("Option<u128> and Option<i128> are rejected while parsing")
+                }
                 StdLibType::U8 => {
                     option_rust_primitive_to_ffi_primitive(quote! {OptionU8}, quote! {123})
                 }
@@ -120,13 +124,19 @@ impl BridgedOption {
     pub(super) fn convert_ffi_expression_to_rust_type(
         &self,
         expression: &TokenStream,
+        swift_bridge_path: &Path,
     ) -> TokenStream {
         match self.ty.deref() {
-            BridgedType::Bridgeable(b) => b.convert_ffi_option_expression_to_rust_type(expression),
+            BridgedType::Bridgeable(b) => {
+                b.convert_ffi_option_expression_to_rust_type(expression, swift_bridge_path)
+            }
             BridgedType::StdLib(stdlib_ty) => match stdlib_ty {
                 StdLibType::Null => {
                     todo!("Option<()> is not yet supported")
                 }
+                StdLibType::U128 | StdLibType::I128 => {
+                    unreachable!("Option<u128> and Option<i128> are rejected while parsing")
+                }
                 StdLibType::U8
                 | StdLibType::I8
                 | StdLibType::U16
@@ -186,6 +196,9 @@ impl BridgedOption {
                 StdLibType::Null => {
                     todo!("Option<()> is not yet supported")
                 }
+                StdLibType::U128 | StdLibType::I128 => {
+                    unreachable!("Option<u128> and Option<i128> are rejected while parsing")
+                }
                 StdLibType::U8
                 | StdLibType::I8
                 | StdLibType::U16
@@ -240,12 +253,11 @@ impl BridgedOption {
         expression: &str,
         type_pos: TypePosition,
     ) -> String {
-        let convert_primitive = move |primitive_kind: &str, unused_none: &str| {
+        let convert_primitive = move |primitive_kind: &str| {
             format!(
-                "{{ let val = {expression}; return __private__Option{primitive_kind}(val: val ?? {unused_none}, is_some: val != nil); }}()",
+                "{{ if let val = {expression} {{ return __swift_bridge__$Option{primitive_kind}$some(val) }} else {{ return __swift_bridge__$Option{primitive_kind}$none() }} }}()",
                 primitive_kind = primitive_kind,
                 expression = expression,
-                unused_none = unused_none
             )
         };
 
@@ -257,19 +269,22 @@ impl BridgedOption {
                 StdLibType::Null => {
                     todo!("Option<()> is not yet supported")
                 }
-                StdLibType::U8 => convert_primitive("U8", "123"),
-                StdLibType::I8 => convert_primitive("I8", "123"),
-                StdLibType::U16 => convert_primitive("U16", "123"),
-                StdLibType::I16 => convert_primitive("I16", "123"),
-                StdLibType::U32 => convert_primitive("U32", "123"),
-                StdLibType::I32 => convert_primitive("I32", "123"),
-                StdLibType::U64 => convert_primitive("U64", "123"),
-                StdLibType::I64 => convert_primitive("I64", "123"),
-                StdLibType::Usize => convert_primitive("Usize", "123"),
-                StdLibType::Isize => convert_primitive("Isize", "123"),
-                StdLibType::F32 => convert_primitive("F32", "123.4"),
-                StdLibType::F64 => convert_primitive("F64", "123.4"),
-                StdLibType::Bool => convert_primitive("Bool", "false"),
+                StdLibType::U128 | StdLibType::I128 => {
+                    unreachable!("Option<u128> and Option<i128> are rejected while parsing")
+                }
+                StdLibType::U8 => convert_primitive("U8"),
+                StdLibType::I8 => convert_primitive("I8"),
+                StdLibType::U16 => convert_primitive("U16"),
+                StdLibType::I16 => convert_primitive("I16"),
+                StdLibType::U32 => convert_primitive("U32"),
+                StdLibType::I32 => convert_primitive("I32"),
+                StdLibType::U64 => convert_primitive("U64"),
+                StdLibType::I64 => convert_primitive("I64"),
+                StdLibType::Usize => convert_primitive("Usize"),
+                StdLibType::Isize => convert_primitive("Isize"),
+                StdLibType::F32 => convert_primitive("F32"),
+                StdLibType::F64 => convert_primitive("F64"),
+                StdLibType::Bool => convert_primitive("Bool"),
                 StdLibType::Pointer(_) => {
                     todo!("Option<*const T> and Option<*mut T> are not yet supported")
                 }
@@ -313,26 +328,44 @@ impl BridgedOption {
 }
 
 impl BridgedOption {
-    pub fn to_c(&self) -> String {
+    /// The Swift type of the Option's FFI representation, which is how the C header's type gets
+    /// imported into Swift.
+    ///
+    /// For example, `struct __private__OptionU8` is imported as `__private__OptionU8` and
+    /// `void*` as `UnsafeMutableRawPointer?`.
+    pub fn to_ffi_swift_type(&self, types: &TypeDeclarations) -> String {
+        let c_type = self.to_c(types);
+
+        if c_type == "void*" {
+            "UnsafeMutableRawPointer?".to_string()
+        } else {
+            c_type.trim_start_matches("struct ").to_string()
+        }
+    }
+
+    pub fn to_c(&self, types: &TypeDeclarations) -> String {
         match self.ty.deref() {
             BridgedType::Bridgeable(b) => b.to_ffi_compatible_option_c_type(),
             BridgedType::StdLib(stdlib_type) => match stdlib_type {
                 StdLibType::Null => {
                     todo!("Option<()> is not yet supported")
                 }
-                StdLibType::U8 => "struct __private__OptionU8".to_string(),
-                StdLibType::I8 => "struct __private__OptionI8".to_string(),
-                StdLibType::U16 => "struct __private__OptionU16".to_string(),
-                StdLibType::I16 => "struct __private__OptionI16".to_string(),
-                StdLibType::U32 => "struct __private__OptionU32".to_string(),
-                StdLibType::I32 => "struct __private__OptionI32".to_string(),
-                StdLibType::U64 => "struct __private__OptionU64".to_string(),
-                StdLibType::I64 => "struct __private__OptionI64".to_string(),
-                StdLibType::Usize => "struct __private__OptionUsize".to_string(),
-                StdLibType::Isize => "struct __private__OptionIsize".to_string(),
-                StdLibType::F32 => "struct __private__OptionF32".to_string(),
-                StdLibType::F64 => "struct __private__OptionF64".to_string(),
-                StdLibType::Bool => "struct __private__OptionBool".to_string(),
+                StdLibType::U128 | StdLibType::I128 => {
+                    unreachable!("Option<u128> and Option<i128> are rejected while parsing")
+                }
+                StdLibType::U8 => format!("struct {}", types.private_type_name("OptionU8")),
+                StdLibType::I8 => format!("struct {}", types.private_type_name("OptionI8")),
+                StdLibType::U16 => format!("struct {}", types.private_type_name("OptionU16")),
+                StdLibType::I16 => format!("struct {}", types.private_type_name("OptionI16")),
+                StdLibType::U32 => format!("struct {}", types.private_type_name("OptionU32")),
+                StdLibType::I32 => format!("struct {}", types.private_type_name("OptionI32")),
+                StdLibType::U64 => format!("struct {}", types.private_type_name("OptionU64")),
+                StdLibType::I64 => format!("struct {}", types.private_type_name("OptionI64")),
+                StdLibType::Usize => format!("struct {}", types.private_type_name("OptionUsize")),
+                StdLibType::Isize => format!("struct {}", types.private_type_name("OptionIsize")),
+                StdLibType::F32 => format!("struct {}", types.private_type_name("OptionF32")),
+                StdLibType::F64 => format!("struct {}", types.private_type_name("OptionF64")),
+                StdLibType::Bool => format!("struct {}", types.private_type_name("OptionBool")),
                 StdLibType::Pointer(_) => {
                     todo!("Option<*const T> and Option<*mut T> are not yet supported")
                 }
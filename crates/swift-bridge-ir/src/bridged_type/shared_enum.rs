@@ -1,3 +1,4 @@
+use crate::swift_keywords::rename_swift_keyword;
 use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
@@ -13,6 +14,8 @@ pub(crate) struct SharedEnum {
     pub variants: Vec<EnumVariant>,
     pub already_declared: bool,
     pub swift_name: Option<LitStr>,
+    /// The prefix of the module's symbols, such as `__swift_bridge__` in `__swift_bridge__$SomeEnum`.
+    pub symbol_prefix: String,
 }
 
 impl SharedEnum {
@@ -21,13 +24,13 @@ impl SharedEnum {
         if let Some(swift_name) = self.swift_name.as_ref() {
             swift_name.value().to_string()
         } else {
-            format!("{}", self.name)
+            rename_swift_keyword(&self.name.to_string())
         }
     }
 
     /// __swift_bridge__$SomeEnum
     pub fn ffi_name_string(&self) -> String {
-        format!("{}${}", SWIFT_BRIDGE_PREFIX, self.swift_name_string())
+        format!("{}${}", self.symbol_prefix, self.swift_name_string())
     }
 
     /// __swift_bridge__$SomeEnumTag
@@ -55,11 +58,7 @@ impl SharedEnum {
 
     /// __swift_bridge__$Option$SomeEnum
     pub fn ffi_option_name_string(&self) -> String {
-        format!(
-            "{}$Option${}",
-            SWIFT_BRIDGE_PREFIX,
-            self.swift_name_string()
-        )
+        format!("{}$Option${}", self.symbol_prefix, self.swift_name_string())
     }
 }
 
@@ -1,7 +1,8 @@
-use crate::bridged_type::{BridgedType, TypePosition};
+use crate::bridged_type::{c_struct_size_and_align, BridgedType, TypePosition};
 use crate::parse::TypeDeclarations;
+use crate::swift_keywords::{escape_swift_keyword, rename_swift_keyword};
 use crate::SWIFT_BRIDGE_PREFIX;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::quote;
 use std::fmt::{Debug, Formatter};
 use syn::spanned::Spanned;
@@ -19,20 +20,23 @@ pub(crate) struct SharedStruct {
     pub fields: StructFields,
     pub swift_name: Option<LitStr>,
     pub already_declared: bool,
+    pub layout: StructLayout,
+    /// The prefix of the module's symbols, such as `__swift_bridge__` in `__swift_bridge__$SomeStruct`.
+    pub symbol_prefix: String,
 }
 
 impl SharedStruct {
     pub(crate) fn swift_name_string(&self) -> String {
         match self.swift_name.as_ref() {
             Some(ty) => ty.value(),
-            None => self.name.to_string(),
+            None => rename_swift_keyword(&self.name.to_string()),
         }
     }
 
     pub(crate) fn ffi_name_string(&self) -> String {
         let name = self.swift_name_string();
 
-        format!("{}${}", SWIFT_BRIDGE_PREFIX, name)
+        format!("{}${}", self.symbol_prefix, name)
     }
 
     pub(crate) fn ffi_name_tokens(&self) -> TokenStream {
@@ -46,6 +50,28 @@ impl SharedStruct {
         }
     }
 
+    /// The size and alignment of the struct's FFI representation on 64-bit targets, or `None` if
+    /// the layout of one of its fields isn't known when generating code.
+    pub(crate) fn ffi_size_and_align(&self, types: &TypeDeclarations) -> Option<(usize, usize)> {
+        match self.swift_repr {
+            StructSwiftRepr::Class => return None,
+            StructSwiftRepr::Structure => {}
+        };
+
+        // `struct __swift_bridge__$SomeStruct { uint8_t _private; }`
+        if self.fields.is_empty() {
+            return Some((1, 1));
+        }
+
+        let mut fields = vec![];
+        for field in self.fields.normalized_fields() {
+            let ty = BridgedType::new_with_type(&field.ty, types)?;
+            fields.push(ty.ffi_size_and_align(types)?);
+        }
+
+        Some(c_struct_size_and_align(fields))
+    }
+
     /// __swift_bridge__Option_SomeStruct
     pub fn ffi_option_name_tokens(&self) -> TokenStream {
         let name = Ident::new(
@@ -57,11 +83,7 @@ impl SharedStruct {
 
     /// __swift_bridge__$Option$SomeStruct
     pub fn ffi_option_name_string(&self) -> String {
-        format!(
-            "{}$Option${}",
-            SWIFT_BRIDGE_PREFIX,
-            self.swift_name_string()
-        )
+        format!("{}$Option${}", self.symbol_prefix, self.swift_name_string())
     }
 }
 
@@ -161,7 +183,7 @@ impl SharedStruct {
             .normalized_fields()
             .iter()
             .map(|norm_field| {
-                let field_name = norm_field.ffi_field_name();
+                let field_name = escape_swift_keyword(&norm_field.ffi_field_name());
                 let ty = BridgedType::new_with_type(&norm_field.ty, types).unwrap();
                 let access_field = ty.convert_swift_expression_to_ffi_type(
                     &format!("val.{field_name}", field_name = field_name),
@@ -201,7 +223,7 @@ impl SharedStruct {
             .normalized_fields()
             .iter()
             .map(|norm_field| {
-                let field_name = norm_field.ffi_field_name();
+                let field_name = escape_swift_keyword(&norm_field.ffi_field_name());
 
                 let ty = BridgedType::new_with_type(&norm_field.ty, types).unwrap();
                 let access_field = ty.convert_ffi_value_to_swift_value(
@@ -259,6 +281,7 @@ impl PartialEq for SharedStruct {
             && self.swift_name.as_ref().map(|l| l.value())
                 == other.swift_name.as_ref().map(|l| l.value())
             && self.already_declared == other.already_declared
+            && self.layout == other.layout
     }
 }
 
@@ -270,10 +293,48 @@ impl Debug for SharedStruct {
             .field("fields", &self.fields)
             .field("swift_name", &self.swift_name.as_ref().map(|l| l.value()))
             .field("already_declared", &self.already_declared)
+            .field("layout", &self.layout)
             .finish()
     }
 }
 
+/// Layout modifiers that come from a `#[repr(C, packed(N))]` or `#[repr(C, align(N))]`
+/// attribute on a shared struct.
+///
+/// These are applied to the FFI representation of the struct and to its C header typedef so that
+/// both languages agree on the struct's size and field offsets.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub(crate) struct StructLayout {
+    /// `#[repr(C, packed(N))]`. A bare `packed` is stored as `Some(1)`.
+    pub packed: Option<u32>,
+    /// `#[repr(C, align(N))]`
+    pub align: Option<u32>,
+}
+
+impl StructLayout {
+    /// Whether or not the struct uses the default `#[repr(C)]` layout.
+    pub(crate) fn is_default(&self) -> bool {
+        self.packed.is_none() && self.align.is_none()
+    }
+
+    /// #[repr(C)], #[repr(C, packed(N))] or #[repr(C, align(N))]
+    pub(crate) fn to_repr_attribute_tokens(self) -> TokenStream {
+        let modifier = if let Some(packed) = self.packed {
+            let packed = Literal::u32_unsuffixed(packed);
+            quote! { , packed(#packed) }
+        } else if let Some(align) = self.align {
+            let align = Literal::u32_unsuffixed(align);
+            quote! { , align(#align) }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #[repr(C #modifier)]
+        }
+    }
+}
+
 /// Whether to create a class or a structure when creating the Swift representation of a shared
 /// struct.
 ///
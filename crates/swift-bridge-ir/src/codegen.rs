@@ -1,9 +1,12 @@
-use crate::bridge_module_attributes::CfgAttr;
+use crate::bridge_module_attributes::cfg_attrs_are_enabled;
 use crate::SwiftBridgeModule;
 
 mod generate_c_header;
 mod generate_rust_tokens;
 mod generate_swift;
+mod swift_line_breaks;
+
+pub use self::swift_line_breaks::break_long_swift_lines;
 
 #[cfg(test)]
 mod codegen_tests;
@@ -45,17 +48,7 @@ impl SwiftBridgeModule {
     /// Whether or not the module's conditional compilation flags willl lead it to being included
     /// in the final binary.
     /// If not, when we won't generate any C or Swift code for it.
-    fn module_will_be_compiled(&self, config: &CodegenConfig) -> bool {
-        for cfg_attr in &self.cfg_attrs {
-            match cfg_attr {
-                CfgAttr::Feature(feature_name) => {
-                    if !(config.crate_feature_lookup)(&feature_name.value()) {
-                        return false;
-                    }
-                }
-            }
-        }
-
-        true
+    pub(crate) fn module_will_be_compiled(&self, config: &CodegenConfig) -> bool {
+        cfg_attrs_are_enabled(&self.cfg_attrs, config)
     }
 }
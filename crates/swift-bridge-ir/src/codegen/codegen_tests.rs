@@ -29,20 +29,38 @@ use crate::test_utils::{
 
 mod already_declared_attribute_codegen_tests;
 mod async_function_codegen_tests;
+mod async_stream_codegen_tests;
+mod available_attribute_codegen_tests;
 mod boxed_fnonce_codegen_tests;
+mod combine_publisher_codegen_tests;
 mod conditional_compilation_codegen_tests;
+mod const_codegen_tests;
+mod default_visibility_codegen_tests;
+mod doc_comment_codegen_tests;
 mod extern_rust_function_opaque_rust_type_argument_codegen_tests;
 mod extern_rust_function_opaque_rust_type_return_codegen_tests;
 mod extern_rust_method_swift_class_placement_codegen_tests;
 mod extern_swift_function_opaque_swift_type_return_codegen_tests;
 mod function_attribute_codegen_tests;
 mod generic_opaque_rust_type_codegen_tests;
+mod initializer_codegen_tests;
+mod int128_codegen_tests;
+mod library_evolution_codegen_tests;
+mod lock_codegen_tests;
+mod observable_object_codegen_tests;
 mod opaque_rust_type_codegen_tests;
 mod opaque_swift_type_codegen_tests;
 mod option_codegen_tests;
+mod panic_codegen_tests;
 mod result_codegen_tests;
 mod return_into_attribute_codegen_tests;
+mod static_codegen_tests;
 mod string_codegen_tests;
+mod swift_access_codegen_tests;
+mod swift_keyword_codegen_tests;
+mod swift_name_prefix_codegen_tests;
+mod swift_protocol_codegen_tests;
+mod symbol_prefix_codegen_tests;
 mod transparent_enum_codegen_tests;
 mod transparent_struct_codegen_tests;
 mod vec_codegen_tests;
@@ -290,3 +290,114 @@ mod already_declared_rust_copy_type_methods {
         .test();
     }
 }
+
+/// Verify that we do not re-declare an already defined opaque Swift type.
+/// Instead, we import the type from the parent module.
+mod extern_swift_already_declared_type_attribute {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    #[swift_bridge(already_declared)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsManyAndDoesNotContainMany {
+            contains: vec![quote! {
+                use super::SomeType;
+            }],
+            does_not_contain: vec![
+                quote! {
+                    pub struct SomeType
+                },
+                quote! {
+                    impl Drop for SomeType
+                },
+                quote! {
+                    fn __swift_bridge__SomeType__free
+                },
+            ],
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::DoesNotContainAfterTrim(r#"__swift_bridge__SomeType__free"#)
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::DoesNotContainAfterTrim("SomeType")
+    }
+
+    #[test]
+    fn extern_swift_already_declared_type_attribute() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate methods for an already declared opaque Swift type.
+mod extern_swift_already_declared_type_still_generates_methods {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    #[swift_bridge(already_declared)]
+                    type SomeType;
+
+                    fn some_method(&self);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            use super::SomeType;
+
+            impl SomeType {
+                pub fn some_method(&self) {
+                    unsafe { __swift_bridge__SomeType_some_method(swift_bridge::PointerToSwiftType(self.0)) }
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$SomeType$some_method")
+func __swift_bridge__SomeType_some_method (_ this: UnsafeMutableRawPointer) {
+    Unmanaged<SomeType>.fromOpaque(this).takeUnretainedValue().some_method()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn extern_swift_already_declared_type_still_generates_methods() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -533,3 +533,374 @@ void __swift_bridge__$SomeType$some_method(void* callback_wrapper, void __swift_
         .test();
     }
 }
+
+/// Verify that we generate the correct code for extern "Rust" async functions that can be
+/// cancelled from Swift.
+mod extern_rust_cancellable_async_function {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(cancellable)]
+                    async fn some_function(arg: u16) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__some_function(
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, u8) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+                cancellation: *const swift_bridge::async_support::AsyncCancellation,
+                arg: u16
+            ) {
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation = unsafe {
+                    swift_bridge::async_support::AsyncCancellation::from_swift(cancellation)
+                };
+                let fut = super::some_function(arg);
+                let task = async move {
+                    let output = cancellation.run(fut).await;
+
+                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = callback_wrapper.0;
+
+                    match output {
+                        Some(output) => {
+                            let val = output;
+                            (callback)(callback_wrapper, val)
+                        }
+                        None => (on_cancel)(callback_wrapper),
+                    }
+                };
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func some_function(_ arg: UInt16) async throws -> UInt8 {
+    func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UInt8) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.success(rustFnRetVal))
+    }
+    func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
+
+    let cancellation = __swift_bridge__$AsyncCancellation$new()
+    defer { __swift_bridge__$AsyncCancellation$free(cancellation) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<UInt8, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
+
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+            __swift_bridge__$some_function(wrapperPtr, onComplete, onCancelled, cancellation, arg)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncCancellation$cancel(cancellation)
+    })
+}
+class CbWrapper$some_function {
+    var cb: (Result<UInt8, Error>) -> ()
+
+    public init(cb: @escaping (Result<UInt8, Error>) -> ()) {
+        self.cb = cb
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, uint8_t ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper), void* cancellation, uint16_t arg);
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_cancellable_async_function() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that Rust can await an extern "Swift" function's completion handler.
+mod extern_swift_completion_handler {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    #[swift_bridge(completion_handler)]
+                    fn fetch(id: u32, completion: Box<dyn FnOnce(String)>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub fn fetch(id: u32) -> swift_bridge::async_support::SwiftCompletion<String> {
+                    swift_bridge::async_support::SwiftCompletion::new(|completion| {
+                        let completion: Box<dyn FnOnce(String) -> ()> = Box::new(move |arg0| {
+                            completion.send(arg0)
+                        });
+                        unsafe {
+                            __swift_bridge__fetch(
+                                id,
+                                swift_bridge::allocator::box_into_raw(completion) as *mut Box<dyn FnOnce(String) -> ()>
+                            )
+                        }
+                    })
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$fetch"]
+                fn __swift_bridge__fetch(id: u32, completion: *mut Box<dyn FnOnce(String) -> ()>);
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$fetch")
+func __swift_bridge__fetch (_ id: UInt32, _ completion: UnsafeMutableRawPointer) {
+    { let cb1 = __private__RustFnOnceCallback$fetch$param1(ptr: completion); let _ = fetch(id: id, completion: { arg0 in cb1.call(arg0) }) }()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim(
+            r#"
+void __swift_bridge__$fetch$param1(void* fetch_completion, void* arg0);
+void __swift_bridge__$fetch$_free$param1(void* fetch_completion);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_completion_handler() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for extern "Rust" async functions that return an
+/// opaque Rust type.
+mod extern_rust_async_function_returns_opaque_rust_type {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    async fn some_function() -> SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function() async -> SomeType {
+    func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.success(SomeType(ptr: rustFnRetVal!)))
+    }
+
+    return await withCheckedContinuation({ (continuation: CheckedContinuation<SomeType, Never>) in
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_async_function_returns_opaque_rust_type() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that an async `#[swift_bridge(init)]` function generates an async Swift initializer
+/// that initializes `self` with the Rust value once the Rust future completes.
+mod extern_rust_async_initializer {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Database;
+
+                    #[swift_bridge(init)]
+                    async fn new(port: u16) -> Database;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__Database_new(
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, *mut super::Database) -> (),
+                port: u16
+            ) {
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let fut = super::Database::new(port);
+                let task = async move {
+                    let val = swift_bridge::allocator::box_into_raw(fut.await) as *mut super::Database;
+
+                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = callback_wrapper.0;
+
+                    (callback)(callback_wrapper, val)
+                };
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension Database {
+    public convenience init(_ port: UInt16) async {
+        func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UnsafeMutableRawPointer?) {
+            let wrapper = Unmanaged<CbWrapper$Database$new>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+            wrapper.cb(.success(rustFnRetVal!))
+        }
+
+        let ptr = await withCheckedContinuation({ (continuation: CheckedContinuation<UnsafeMutableRawPointer, Never>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
+
+            let wrapper = CbWrapper$Database$new(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+            __swift_bridge__$Database$new(wrapperPtr, onComplete, port)
+        })
+        self.init(ptr: ptr)
+    }
+    class CbWrapper$Database$new {
+        var cb: (Result<UnsafeMutableRawPointer, Never>) -> ()
+    
+        public init(cb: @escaping (Result<UnsafeMutableRawPointer, Never>) -> ()) {
+            self.cb = cb
+        }
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$Database$new(void* callback_wrapper, void __swift_bridge__$Database$new$async(void* callback_wrapper, void* ret), uint16_t port);
+    "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_async_initializer() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a cancellable async initializer throws when it is cancelled.
+mod extern_rust_cancellable_async_initializer {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Database;
+
+                    #[swift_bridge(init, cancellable)]
+                    async fn new() -> Database;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    public convenience init() async throws {
+"#,
+            r#"
+        let ptr = try await withTaskCancellationHandler(operation: {
+            try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<UnsafeMutableRawPointer, Error>) in
+"#,
+            r#"
+        }, onCancel: {
+            __swift_bridge__$AsyncCancellation$cancel(cancellation)
+        })
+        self.init(ptr: ptr)
+    }
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_cancellable_async_initializer() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,97 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate an `AsyncStream` for a `#[swift_bridge(AsyncStream(...))]` type that
+/// awaits values from the Rust channel through a generated `next` method.
+mod async_stream {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(AsyncStream(u32))]
+                    type ProgressReceiver;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$ProgressReceiver$next"]
+            pub extern "C" fn __swift_bridge__ProgressReceiver_next(
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, swift_bridge::option::OptionU32) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+                cancellation: *const swift_bridge::async_support::AsyncCancellation,
+                this: *mut super::ProgressReceiver
+            ) {
+                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                let cancellation = unsafe {
+                    swift_bridge::async_support::AsyncCancellation::from_swift(cancellation)
+                };
+                let fut = (unsafe { &*this }).next();
+                let task = async move {
+                    let output = cancellation.run(fut).await;
+
+                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = callback_wrapper.0;
+
+                    match output {
+                        Some(output) => {
+                            let val = if let Some(val) = output {
+                                swift_bridge::option::OptionU32 { val, is_some: true }
+                            } else {
+                                swift_bridge::option::OptionU32 { val: 123, is_some: false }
+                            };
+                            (callback)(callback_wrapper, val)
+                        }
+                        None => (on_cancel)(callback_wrapper),
+                    }
+                };
+                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    public func next() async throws -> Optional<UInt32> {
+"#,
+            r#"
+extension ProgressReceiver {
+    /// An `AsyncStream` that yields the values sent to this Rust channel, and finishes once every
+    /// Rust sender has been dropped or the task that iterates over it is cancelled.
+    ///
+    /// Streams pull values from the same channel receiver, so each value is delivered to only one
+    /// of them.
+    public func stream() -> AsyncStream<UInt32> {
+        AsyncStream(unfolding: { try? await self.next() })
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$ProgressReceiver$next(void* callback_wrapper, void __swift_bridge__$ProgressReceiver$next$async(void* callback_wrapper, struct __private__OptionU32 ret), void __swift_bridge__$ProgressReceiver$next$cancel(void* callback_wrapper), void* cancellation, void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn async_stream() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
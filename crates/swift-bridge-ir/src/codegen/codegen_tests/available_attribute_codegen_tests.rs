@@ -0,0 +1,132 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we emit `@available` and clang availability attributes for an extern "Rust"
+/// function that uses the `available` attribute.
+mod extern_rust_function_available_attribute {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(available(iOS = "15.0", macOS = "12.0"))]
+                    fn some_function() -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@available(iOS 15.0, macOS 12.0, *)
+public func some_function() -> UInt8 {
+    __swift_bridge__$some_function()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+uint8_t __swift_bridge__$some_function(void) __attribute__((availability(ios,introduced=15.0))) __attribute__((availability(macos,introduced=12.0)));
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_function_available_attribute() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit `@available` attributes on the Swift classes of an extern "Rust" type
+/// that uses the `available` attribute.
+mod extern_rust_type_available_attribute {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(available(iOS = "16.0"))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@available(iOS 16.0, *)
+public class SomeType: SomeTypeRefMut {
+"#,
+            r#"
+@available(iOS 16.0, *)
+public class SomeTypeRefMut: SomeTypeRef {
+"#,
+            r#"
+@available(iOS 16.0, *)
+public class SomeTypeRef {
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_type_available_attribute() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit an `@available` attribute on the Swift struct of a Copy extern "Rust"
+/// type that uses the `available` attribute.
+mod extern_rust_copy_type_available_attribute {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), available(macOS = "13"))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@available(macOS 13, *)
+public struct SomeType {
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_type_available_attribute() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -39,7 +39,7 @@ mod test_swift_takes_no_args_no_return_callback {
                 pub fn some_function (callback: Box<dyn FnOnce() -> ()>) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce() -> ()>
+                            swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce() -> ()>
                         )
                     }
                 }
@@ -98,7 +98,7 @@ mod test_swift_takes_callback_one_primitive_arg {
                 pub fn some_function (callback: Box<dyn FnOnce(u8) -> ()>) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce(u8) -> ()>
+                            swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce(u8) -> ()>
                         )
                     }
                 }
@@ -106,12 +106,12 @@ mod test_swift_takes_callback_one_primitive_arg {
             quote! {
                 #[export_name = "__swift_bridge__$some_function$param0"]
                 pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn FnOnce(u8) -> ()>, arg0: u8) {
-                    unsafe { Box::from_raw(some_function_callback)(arg0) }
+                    unsafe { swift_bridge::allocator::take_from_raw(some_function_callback)(arg0) }
                 }
 
                 #[export_name = "__swift_bridge__$some_function$_free$param0"]
                 pub extern "C" fn free_some_function_param0(some_function_callback: *mut Box<dyn FnOnce(u8) -> ()>) {
-                    let _ = unsafe { Box::from_raw(some_function_callback) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_function_callback) };
                 }
             },
             quote! {
@@ -197,7 +197,7 @@ mod test_swift_takes_callback_primitive_return {
                 pub fn some_function (callback: Box<dyn FnOnce() -> u8>) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce() -> u8>
+                            swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce() -> u8>
                         )
                     }
                 }
@@ -205,12 +205,12 @@ mod test_swift_takes_callback_primitive_return {
             quote! {
                 #[export_name = "__swift_bridge__$some_function$param0"]
                 pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn FnOnce() -> u8>) -> u8 {
-                    unsafe { Box::from_raw(some_function_callback)() }
+                    unsafe { swift_bridge::allocator::take_from_raw(some_function_callback)() }
                 }
 
                 #[export_name = "__swift_bridge__$some_function$_free$param0"]
                 pub extern "C" fn free_some_function_param0(some_function_callback: *mut Box<dyn FnOnce() -> u8>) {
-                    let _ = unsafe { Box::from_raw(some_function_callback) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_function_callback) };
                 }
             },
             quote! {
@@ -300,7 +300,7 @@ mod test_swift_takes_callback_one_opaque_rust_arg {
                 pub fn some_function (callback: Box<dyn FnOnce(super::ARustType) -> ()>) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce(super::ARustType) -> ()>
+                            swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce(super::ARustType) -> ()>
                         )
                     }
                 }
@@ -308,12 +308,12 @@ mod test_swift_takes_callback_one_opaque_rust_arg {
             quote! {
                 #[export_name = "__swift_bridge__$some_function$param0"]
                 pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn FnOnce(super::ARustType) -> ()>, arg0: *mut super::ARustType) {
-                    unsafe { Box::from_raw(some_function_callback)(unsafe { *Box::from_raw(arg0) }) }
+                    unsafe { swift_bridge::allocator::take_from_raw(some_function_callback)(unsafe { swift_bridge::allocator::take_from_raw(arg0) }) }
                 }
 
                 #[export_name = "__swift_bridge__$some_function$_free$param0"]
                 pub extern "C" fn free_some_function_param0(some_function_callback: *mut Box<dyn FnOnce(super::ARustType) -> ()>) {
-                    let _ = unsafe { Box::from_raw(some_function_callback) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_function_callback) };
                 }
             },
             quote! {
@@ -403,7 +403,7 @@ mod test_swift_takes_callback_return_opaque_rust_type {
                 pub fn some_function (callback: Box<dyn FnOnce() -> super::ARustType>) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce() -> super::ARustType>
+                            swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce() -> super::ARustType>
                         )
                     }
                 }
@@ -411,12 +411,12 @@ mod test_swift_takes_callback_return_opaque_rust_type {
             quote! {
                 #[export_name = "__swift_bridge__$some_function$param0"]
                 pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn FnOnce() -> super::ARustType>) -> *mut super::ARustType {
-                    Box::into_raw(Box::new(unsafe { Box::from_raw(some_function_callback)() })) as *mut super::ARustType
+                    swift_bridge::allocator::box_into_raw(unsafe { swift_bridge::allocator::take_from_raw(some_function_callback)() }) as *mut super::ARustType
                 }
 
                 #[export_name = "__swift_bridge__$some_function$_free$param0"]
                 pub extern "C" fn free_some_function_param0(some_function_callback: *mut Box<dyn FnOnce() -> super::ARustType>) {
-                    let _ = unsafe { Box::from_raw(some_function_callback) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_function_callback) };
                 }
             },
             quote! {
@@ -506,7 +506,7 @@ mod test_swift_takes_callback_with_result_arg {
                 pub fn some_function (callback: Box<dyn FnOnce(Result<super::ARustType, super::ARustType>) -> ()>) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce(Result<super::ARustType, super::ARustType>) -> ()>
+                            swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce(Result<super::ARustType, super::ARustType>) -> ()>
                         )
                     }
                 }
@@ -514,18 +514,18 @@ mod test_swift_takes_callback_with_result_arg {
             quote! {
                 #[export_name = "__swift_bridge__$some_function$param0"]
                 pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn FnOnce(Result<super::ARustType, super::ARustType>) -> ()>, arg0: swift_bridge::result::ResultPtrAndPtr) {
-                    unsafe { Box::from_raw(some_function_callback)(
+                    unsafe { swift_bridge::allocator::take_from_raw(some_function_callback)(
                         if arg0.is_ok {
-                            std::result::Result::Ok(unsafe { *Box::from_raw(arg0.ok_or_err as *mut super::ARustType) })
+                            std::result::Result::Ok(unsafe { swift_bridge::allocator::take_from_raw(arg0.ok_or_err as *mut super::ARustType) })
                         } else {
-                            std::result::Result::Err(unsafe { *Box::from_raw(arg0.ok_or_err as *mut super::ARustType) })
+                            std::result::Result::Err(unsafe { swift_bridge::allocator::take_from_raw(arg0.ok_or_err as *mut super::ARustType) })
                         }
                     )}
                 }
 
                 #[export_name = "__swift_bridge__$some_function$_free$param0"]
                 pub extern "C" fn free_some_function_param0(some_function_callback: *mut Box<dyn FnOnce(Result<super::ARustType, super::ARustType>) -> ()>) {
-                    let _ = unsafe { Box::from_raw(some_function_callback) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_function_callback) };
                 }
             },
             quote! {
@@ -630,9 +630,9 @@ mod test_swift_takes_multiple_callbacks {
                 ) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(arg0)) as *mut Box<dyn FnOnce() -> ()>,
-                            Box::into_raw(Box::new(arg1)) as *mut Box<dyn FnOnce(u8) -> ()>,
-                            Box::into_raw(Box::new(arg2)) as *mut Box<dyn FnOnce() -> ()>
+                            swift_bridge::allocator::box_into_raw(arg0) as *mut Box<dyn FnOnce() -> ()>,
+                            swift_bridge::allocator::box_into_raw(arg1) as *mut Box<dyn FnOnce(u8) -> ()>,
+                            swift_bridge::allocator::box_into_raw(arg2) as *mut Box<dyn FnOnce() -> ()>
                         )
                     }
                 }
@@ -640,12 +640,12 @@ mod test_swift_takes_multiple_callbacks {
             quote! {
                 #[export_name = "__swift_bridge__$some_function$param1"]
                 pub extern "C" fn some_function_param1(some_function_arg1: *mut Box<dyn FnOnce(u8) -> ()>, arg0: u8) {
-                    unsafe { Box::from_raw(some_function_arg1)(arg0) }
+                    unsafe { swift_bridge::allocator::take_from_raw(some_function_arg1)(arg0) }
                 }
 
                 #[export_name = "__swift_bridge__$some_function$_free$param1"]
                 pub extern "C" fn free_some_function_param1(some_function_arg1: *mut Box<dyn FnOnce(u8) -> ()>) {
-                    let _ = unsafe { Box::from_raw(some_function_arg1) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_function_arg1) };
                 }
             },
             quote! {
@@ -739,7 +739,7 @@ mod test_swift_takes_callback_multiple_args {
                 pub fn some_function (callback: Box<dyn FnOnce(super::ARustType, u32) -> ()>) {
                     unsafe {
                         __swift_bridge__some_function(
-                            Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce(super::ARustType, u32) -> ()>
+                            swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce(super::ARustType, u32) -> ()>
                         )
                     }
                 }
@@ -747,12 +747,12 @@ mod test_swift_takes_callback_multiple_args {
             quote! {
                 #[export_name = "__swift_bridge__$some_function$param0"]
                 pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn FnOnce(super::ARustType, u32) -> ()>, arg0: *mut super::ARustType, arg1: u32) {
-                    unsafe { Box::from_raw(some_function_callback)(unsafe { *Box::from_raw(arg0) }, arg1) }
+                    unsafe { swift_bridge::allocator::take_from_raw(some_function_callback)(unsafe { swift_bridge::allocator::take_from_raw(arg0) }, arg1) }
                 }
 
                 #[export_name = "__swift_bridge__$some_function$_free$param0"]
                 pub extern "C" fn free_some_function_param0(some_function_callback: *mut Box<dyn FnOnce(super::ARustType, u32) -> ()>) {
-                    let _ = unsafe { Box::from_raw(some_function_callback) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_function_callback) };
                 }
             },
             quote! {
@@ -842,7 +842,7 @@ mod test_swift_method_takes_no_args_no_return_callback {
                         unsafe {
                             __swift_bridge__SomeType_some_method(
                                 swift_bridge::PointerToSwiftType(self.0),
-                                Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce() -> ()>
+                                swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce() -> ()>
                             )
                         }
                     }
@@ -909,7 +909,7 @@ mod test_swift_method_takes_callback_one_primitive_arg {
                         unsafe {
                             __swift_bridge__SomeType_some_method(
                                 swift_bridge::PointerToSwiftType(self.0),
-                                Box::into_raw(Box::new(callback)) as *mut Box<dyn FnOnce(u8) -> ()>
+                                swift_bridge::allocator::box_into_raw(callback) as *mut Box<dyn FnOnce(u8) -> ()>
                             )
                         }
                     }
@@ -918,12 +918,12 @@ mod test_swift_method_takes_callback_one_primitive_arg {
             quote! {
                 #[export_name = "__swift_bridge__$SomeType$some_method$param1"]
                 pub extern "C" fn SomeType_some_method_param1(some_method_callback: *mut Box<dyn FnOnce(u8) -> ()>, arg0: u8) {
-                    unsafe { Box::from_raw(some_method_callback)(arg0) }
+                    unsafe { swift_bridge::allocator::take_from_raw(some_method_callback)(arg0) }
                 }
 
                 #[export_name = "__swift_bridge__$SomeType$some_method$_free$param1"]
                 pub extern "C" fn free_SomeType_some_method_param1(some_method_callback: *mut Box<dyn FnOnce(u8) -> ()>) {
-                    let _ = unsafe { Box::from_raw(some_method_callback) };
+                    let _ = unsafe { swift_bridge::allocator::take_from_raw(some_method_callback) };
                 }
             },
             quote! {
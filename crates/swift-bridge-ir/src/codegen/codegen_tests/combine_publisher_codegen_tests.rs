@@ -0,0 +1,81 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate a Combine publisher for a `#[swift_bridge(Publisher(...))]` type that
+/// pulls values from the Rust broadcast channel through a generated `next_blocking` method.
+mod combine_publisher {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Publisher(u32))]
+                    type ProgressPublisher;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$ProgressPublisher$next_blocking"]
+            pub extern "C" fn __swift_bridge__ProgressPublisher_next_blocking(
+                this: *mut super::ProgressPublisher
+            ) -> swift_bridge::option::OptionU32 {
+                if let Some(val) = (unsafe { &mut *this }).next_blocking() {
+                    swift_bridge::option::OptionU32 { val, is_some: true }
+                } else {
+                    swift_bridge::option::OptionU32 { val: 123, is_some: false }
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+#if canImport(Combine)
+import Combine
+
+extension ProgressPublisher {
+"#,
+            r#"
+    public func publisher() -> AnyPublisher<UInt32, Never> {
+        __swift_bridge__ProgressPublisherPublisher(source: self).eraseToAnyPublisher()
+    }
+}
+fileprivate struct __swift_bridge__ProgressPublisherPublisher: Publisher {
+    typealias Output = UInt32
+    typealias Failure = Never
+"#,
+            r#"
+            guard let value = source.next_blocking() else {
+                cancel()
+                subscriber.receive(completion: .finished)
+                return
+            }
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+struct __private__OptionU32 __swift_bridge__$ProgressPublisher$next_blocking(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn combine_publisher() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
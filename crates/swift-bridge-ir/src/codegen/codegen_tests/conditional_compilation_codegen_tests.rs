@@ -1,4 +1,5 @@
 use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
 use quote::quote;
 
 /// Verify that we properly handle a `#[cfg(feature = "foo")]` for a bridge module when the
@@ -120,3 +121,351 @@ some_function
         .test();
     }
 }
+
+/// Verify that an extern "Rust" function with a `#[cfg(feature = "...")]` attribute has the
+/// attribute on its generated Rust function, and that we generate Swift and C code for it when
+/// the feature is enabled.
+mod cfg_feature_extern_rust_function_feature_enabled {
+    use super::*;
+    use crate::codegen::codegen_tests::BridgeModule;
+
+    fn bridge_module() -> BridgeModule {
+        let tokens = quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[cfg(feature = "pro")]
+                    fn some_function();
+                }
+            }
+        };
+        BridgeModule {
+            tokens,
+            enabled_crate_features: vec!["pro"],
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[cfg(feature = "pro")]
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() {
+                super::some_function()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() {
+    __swift_bridge__$some_function()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void __swift_bridge__$some_function(void);
+    "#,
+    );
+
+    #[test]
+    fn cfg_feature_extern_rust_function_feature_enabled() {
+        CodegenTest {
+            bridge_module: bridge_module(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we do not generate Swift or C code for an extern "Rust" function with a
+/// `#[cfg(feature = "...")]` attribute when the feature is disabled.
+mod cfg_feature_extern_rust_function_feature_disabled {
+    use super::*;
+    use crate::codegen::codegen_tests::BridgeModule;
+
+    fn bridge_module() -> BridgeModule {
+        let tokens = quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[cfg(feature = "pro")]
+                    fn some_function();
+
+                    fn another_function();
+                }
+            }
+        };
+        BridgeModule {
+            tokens,
+            enabled_crate_features: vec![],
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[cfg(feature = "pro")]
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() {
+                super::some_function()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::DoesNotContainAfterTrim(
+            r#"
+some_function
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void __swift_bridge__$another_function(void);
+    "#,
+    );
+
+    #[test]
+    fn cfg_feature_extern_rust_function_feature_disabled() {
+        CodegenTest {
+            bridge_module: bridge_module(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that an extern "Rust" function with a `#[cfg(target_os = "...")]` attribute gets
+/// wrapped in `#if` blocks in the generated Swift and C code.
+mod cfg_target_os_extern_rust_function {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[cfg(target_os = "ios")]
+                    fn some_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[cfg(target_os = "ios")]
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() {
+                super::some_function()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+#if os(iOS)
+public func some_function() {
+    __swift_bridge__$some_function()
+}
+#endif
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#if (__is_target_os(ios))
+void __swift_bridge__$some_function(void);
+#endif
+    "#,
+    );
+
+    #[test]
+    fn cfg_target_os_extern_rust_function() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that an opaque Rust type with a `#[cfg(target_os = "...")]` attribute gets wrapped in
+/// `#if` blocks in the generated Swift and C code, and that its methods inherit the attribute.
+mod cfg_target_os_opaque_rust_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[cfg(target_os = "macos")]
+                    type SomeType;
+
+                    #[cfg(feature = "pro")]
+                    fn some_method(&self);
+                    #[cfg(target_os = "ios")]
+                    fn another_method(&self);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[cfg(target_os = "macos")]
+                #[export_name = "__swift_bridge__$SomeType$_free"]
+                pub extern "C" fn __swift_bridge__SomeType__free (
+                    this: *mut super::SomeType
+                ) {
+                    let this = unsafe { swift_bridge::allocator::take_from_raw(this) };
+                    drop(this);
+                }
+            },
+            quote! {
+                #[cfg(target_os = "ios")]
+                #[cfg(target_os = "macos")]
+                #[export_name = "__swift_bridge__$SomeType$another_method"]
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+#if os(macOS)
+public class SomeType: SomeTypeRefMut {
+"#,
+            r#"
+extension SomeTypeRef {
+    #if os(iOS)
+    public func another_method() {
+        __swift_bridge__$SomeType$another_method(ptr)
+    }
+    #endif
+}
+"#,
+            r#"
+#endif
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"
+#if (__is_target_os(macos))
+typedef struct SomeType SomeType;
+void __swift_bridge__$SomeType$_free(void* self);
+"#,
+            r#"
+#if (__is_target_os(ios)) && (__is_target_os(macos))
+void __swift_bridge__$SomeType$another_method(void* self);
+#endif
+"#,
+        ])
+    }
+
+    #[test]
+    fn cfg_target_os_opaque_rust_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that `#[cfg(target_env = "...")]` and `#[cfg(not(...))]` attributes get wrapped in
+/// `targetEnvironment` `#if` blocks in the generated Swift and C code.
+mod cfg_target_env_extern_rust_function {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[cfg(target_os = "ios")]
+                    #[cfg(not(target_env = "sim"))]
+                    fn request_camera_access();
+
+                    #[cfg(target_abi = "macabi")]
+                    fn open_window();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[cfg(target_os = "ios")]
+                #[cfg(not(target_env = "sim"))]
+                #[export_name = "__swift_bridge__$request_camera_access"]
+                pub extern "C" fn __swift_bridge__request_camera_access() {
+                    super::request_camera_access()
+                }
+            },
+            quote! {
+                #[cfg(target_abi = "macabi")]
+                #[export_name = "__swift_bridge__$open_window"]
+                pub extern "C" fn __swift_bridge__open_window() {
+                    super::open_window()
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+#if os(iOS) && !targetEnvironment(simulator)
+public func request_camera_access() {
+    __swift_bridge__$request_camera_access()
+}
+#endif
+"#,
+            r#"
+#if targetEnvironment(macCatalyst)
+public func open_window() {
+    __swift_bridge__$open_window()
+}
+#endif
+"#,
+        ])
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+#if (__is_target_os(ios)) && !(__is_target_environment(simulator))
+void __swift_bridge__$request_camera_access(void);
+#endif
+#if (__is_target_environment(macabi))
+void __swift_bridge__$open_window(void);
+#endif
+    "#,
+    );
+
+    #[test]
+    fn cfg_target_env_extern_rust_function() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
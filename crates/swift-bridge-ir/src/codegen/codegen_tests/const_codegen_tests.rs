@@ -0,0 +1,141 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate code for a constant declared in the bridge module.
+mod primitive_const {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                const MAX_USERS: u32;
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$MAX_USERS"]
+            #[allow(non_upper_case_globals)]
+            pub static __swift_bridge__MAX_USERS: u32 = super::MAX_USERS;
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public let MAX_USERS: UInt32 = __swift_bridge__$MAX_USERS
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+extern const uint32_t __swift_bridge__$MAX_USERS;
+"#,
+        )
+    }
+
+    #[test]
+    fn primitive_const() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a constant's doc comment, cfg attributes and the module's access level are used
+/// in the generated code.
+mod const_with_attributes {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(swift_access = "internal")]
+            mod ffi {
+                /// Whether or not this is a production build.
+                #[cfg(target_os = "ios")]
+                const IS_PRODUCTION: bool;
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[cfg(target_os = "ios")]
+            #[export_name = "__swift_bridge__$IS_PRODUCTION"]
+            #[allow(non_upper_case_globals)]
+            pub static __swift_bridge__IS_PRODUCTION: bool = super::IS_PRODUCTION;
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+#if os(iOS)
+/// Whether or not this is a production build.
+internal let IS_PRODUCTION: Bool = __swift_bridge__$IS_PRODUCTION
+#endif
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "#include <stdbool.h>",
+            r#"
+#if (__is_target_os(ios))
+/**
+ * Whether or not this is a production build.
+ */
+extern const bool __swift_bridge__$IS_PRODUCTION;
+#endif
+"#,
+        ])
+    }
+
+    #[test]
+    fn const_with_attributes() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we do not generate Swift or C code for a constant whose feature is disabled.
+mod const_with_disabled_feature {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[cfg(feature = "disabled")]
+                const MAX_USERS: u32;
+            }
+        }
+    }
+
+    #[test]
+    fn const_with_disabled_feature() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[cfg(feature = "disabled")]
+                #[export_name = "__swift_bridge__$MAX_USERS"]
+            }),
+            expected_swift_code: ExpectedSwiftCode::DoesNotContainAfterTrim("MAX_USERS"),
+            expected_c_header: ExpectedCHeader::DoesNotContainAfterTrim("MAX_USERS"),
+        }
+        .test();
+    }
+}
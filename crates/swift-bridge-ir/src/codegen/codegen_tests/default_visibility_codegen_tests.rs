@@ -0,0 +1,88 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a module level `default_visibility` attribute annotates the C header's function
+/// declarations with `SWIFT_BRIDGE_EXPORT`.
+mod module_default_visibility {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(default_visibility)]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function(arg: u8) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim(
+            r#"
+#include <stdint.h>
+#ifndef SWIFT_BRIDGE_EXPORT
+#define SWIFT_BRIDGE_EXPORT __attribute__((visibility("default")))
+#endif
+typedef struct SomeType SomeType;
+SWIFT_BRIDGE_EXPORT void __swift_bridge__$SomeType$_free(void* self);
+
+SWIFT_BRIDGE_EXPORT void* __swift_bridge__$Vec_SomeType$new(void);
+SWIFT_BRIDGE_EXPORT void __swift_bridge__$Vec_SomeType$drop(void* vec_ptr);
+SWIFT_BRIDGE_EXPORT void __swift_bridge__$Vec_SomeType$push(void* vec_ptr, void* item_ptr);
+SWIFT_BRIDGE_EXPORT void* __swift_bridge__$Vec_SomeType$pop(void* vec_ptr);
+SWIFT_BRIDGE_EXPORT void* __swift_bridge__$Vec_SomeType$get(void* vec_ptr, uintptr_t index);
+SWIFT_BRIDGE_EXPORT void* __swift_bridge__$Vec_SomeType$get_mut(void* vec_ptr, uintptr_t index);
+SWIFT_BRIDGE_EXPORT uintptr_t __swift_bridge__$Vec_SomeType$len(void* vec_ptr);
+SWIFT_BRIDGE_EXPORT void* __swift_bridge__$Vec_SomeType$as_ptr(void* vec_ptr);
+SWIFT_BRIDGE_EXPORT void __swift_bridge__$Vec_SomeType$insert(void* vec_ptr, uintptr_t index, void* item_ptr);
+SWIFT_BRIDGE_EXPORT void* __swift_bridge__$Vec_SomeType$remove(void* vec_ptr, uintptr_t index);
+SWIFT_BRIDGE_EXPORT void __swift_bridge__$Vec_SomeType$reserve(void* vec_ptr, uintptr_t additional);
+SWIFT_BRIDGE_EXPORT uintptr_t __swift_bridge__$Vec_SomeType$capacity(void* vec_ptr);
+
+SWIFT_BRIDGE_EXPORT uint8_t __swift_bridge__$some_function(uint8_t arg);
+"#,
+        )
+    }
+
+    #[test]
+    fn module_default_visibility() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we do not annotate function declarations if the module does not use the
+/// `default_visibility` attribute.
+mod no_default_visibility {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_default_visibility() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::DoesNotContainAfterTrim("SWIFT_BRIDGE_EXPORT"),
+        }
+        .test();
+    }
+}
@@ -0,0 +1,184 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we emit the doc comments of an opaque Rust type and its methods as Swift
+/// doc comments.
+mod extern_rust_type_doc_comments {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    /// A type that renders things.
+                    ///
+                    /// Create one using `Renderer()`.
+                    type Renderer;
+
+                    /// Render a frame.
+                    fn render(&self, frame: u32);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+/// A type that renders things.
+///
+/// Create one using `Renderer()`.
+public class Renderer: RendererRefMut {
+"#,
+            r#"
+extension RendererRef {
+    /// Render a frame.
+    public func render(_ frame: UInt32) {
+        __swift_bridge__$Renderer$render(ptr, frame)
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_type_doc_comments() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the doc comment of an opaque Copy Rust type as a Swift doc comment.
+mod extern_rust_copy_type_doc_comment {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    /// A user's unique identifier.
+                    #[swift_bridge(Copy(16))]
+                    type UserId;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+/// A user's unique identifier.
+public struct UserId {
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_type_doc_comment() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the doc comment of a freestanding extern "Rust" function as a Swift
+/// doc comment.
+mod extern_rust_function_doc_comment {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    /// Add two numbers.
+                    fn add(a: u8, b: u8) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+/// Add two numbers.
+public func add(_ a: UInt8, _ b: UInt8) -> UInt8 {
+    __swift_bridge__$add(a, b)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_function_doc_comment() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the doc comments of an opaque Rust type and a function as Doxygen style
+/// comments in the C header.
+mod c_header_doc_comments {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    /// A type that renders things.
+                    ///
+                    /// Ends the comment with */ on purpose.
+                    #[swift_bridge(Copy(4))]
+                    type Renderer;
+
+                    /// Render a frame.
+                    fn render(frame: u32);
+                }
+            }
+        }
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"
+/**
+ * A type that renders things.
+ *
+ * Ends the comment with *\/ on purpose.
+ */
+typedef struct __swift_bridge__$Renderer { uint8_t bytes[4]; } __swift_bridge__$Renderer;
+"#,
+            r#"
+/**
+ * Render a frame.
+ */
+void __swift_bridge__$render(uint32_t frame);
+"#,
+        ])
+    }
+
+    #[test]
+    fn c_header_doc_comments() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -25,7 +25,7 @@ mod test_extern_rust_function_owned_opaque_rust_type_argument {
             pub extern "C" fn __swift_bridge__some_function (
                 arg: *mut super::SomeType
             ) {
-                super::some_function(unsafe { * Box::from_raw(arg) })
+                super::some_function(unsafe { swift_bridge::allocator::take_from_raw(arg) })
             }
         })
     }
@@ -293,3 +293,61 @@ void __swift_bridge__$some_function(struct __swift_bridge__$SomeType arg);
         .test();
     }
 }
+
+/// Verify that we hand the allocation of an owned opaque Rust argument to a function that takes
+/// it boxed, instead of moving the value into a new box.
+mod test_extern_rust_function_boxed_opaque_rust_type_argument {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function(arg: Box<SomeType>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function (
+                arg: *mut super::SomeType
+            ) {
+                super::some_function(unsafe { swift_bridge::allocator::take_boxed_from_raw(arg) })
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func some_function(_ arg: SomeType) {
+    __swift_bridge__$some_function({arg.isOwned = false; return arg.ptr;}())
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$some_function(void* arg);
+            "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_boxed_opaque_type_argument() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
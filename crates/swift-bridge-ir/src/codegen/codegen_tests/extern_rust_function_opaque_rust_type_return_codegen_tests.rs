@@ -23,7 +23,7 @@ mod test_extern_rust_function_owned_opaque_rust_type_return {
         ExpectedRustTokens::Contains(quote! {
             #[export_name = "__swift_bridge__$some_function"]
             pub extern "C" fn __swift_bridge__some_function () -> *mut super::SomeType {
-                Box::into_raw(Box::new(super::some_function())) as *mut super::SomeType
+                swift_bridge::allocator::box_into_raw(super::some_function()) as *mut super::SomeType
             }
         })
     }
@@ -226,3 +226,78 @@ struct __swift_bridge__$SomeType __swift_bridge__$some_function(void);
         .test();
     }
 }
+
+/// Verify that we pass the existing allocation of an already boxed opaque Rust type to Swift
+/// instead of boxing it again.
+mod test_extern_rust_function_boxed_opaque_rust_type_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function() -> Box<SomeType>;
+                    fn maybe_some_function() -> Option<Box<SomeType>>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function () -> *mut super::SomeType {
+                    swift_bridge::allocator::boxed_into_raw(super::some_function()) as *mut super::SomeType
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$maybe_some_function"]
+                pub extern "C" fn __swift_bridge__maybe_some_function () -> *mut super::SomeType {
+                    if let Some(val) = super::maybe_some_function() {
+                        swift_bridge::allocator::boxed_into_raw(val)
+                    } else {
+                        std::ptr::null_mut()
+                    }
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+func some_function() -> SomeType {
+    SomeType(ptr: __swift_bridge__$some_function())
+}
+"#,
+            r#"
+func maybe_some_function() -> Optional<SomeType> {
+    { let val = __swift_bridge__$maybe_some_function(); if val != nil { return SomeType(ptr: val!) } else { return nil } }()
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function(void);
+void* __swift_bridge__$maybe_some_function(void);
+            "#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_boxed_opaque_type_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -395,6 +395,104 @@ mod get_with {
     }
 }
 
+/// Verify that we can use the set attribute
+mod set {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(set(field))]
+                    fn set_field(&mut self, value: u16);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__SomeType_set_field(
+                this: *mut super::SomeType,
+                value: u16
+            ) {
+                (unsafe { &mut *this }).field = value
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::SkipTest
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn set() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we can use the set_with attribute
+mod set_with {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(set_with(field = a::b::c))]
+                    fn set_field(&mut self, value: u16);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__SomeType_set_field(
+                this: *mut super::SomeType,
+                value: u16
+            ) {
+                (unsafe { &mut *this }).field = super::a::b::c( value )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::SkipTest
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn set_with() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
 /// Tests that the swift_name function attribute generates the correct code
 /// when using extern "Rust" (calling Rust code from Swift) and when using
 /// extern "Swift" (calling Swift code from Rust).
@@ -423,7 +521,7 @@ mod function_attribute_swift_name_extern_rust {
                 swift_bridge::string::RustString(super::call_rust_from_swift()).box_into_raw()
             }
             pub fn call_swift_from_rust() -> String {
-                unsafe { Box::from_raw(unsafe {__swift_bridge__call_swift_from_rust () }).0 }
+                unsafe { swift_bridge::allocator::take_from_raw(unsafe {__swift_bridge__call_swift_from_rust () }).0 }
             }
             extern "C" {
                 #[link_name = "__swift_bridge__$call_swift_from_rust"]
@@ -461,3 +559,631 @@ func __swift_bridge__call_swift_from_rust () -> UnsafeMutableRawPointer {
         .test();
     }
 }
+
+/// Verify that we use the `#[swift_bridge(argument_labels(...))]` attribute when generating
+/// the Swift function for an extern "Rust" function.
+mod argument_labels_extern_rust {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(argument_labels(user = "for", id = "_"))]
+                    fn fetch(user: u8, id: u32, verbose: bool);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func fetch(for user: UInt8, _ id: UInt32, _ verbose: Bool) {
+    __swift_bridge__$fetch(user, id, verbose)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn argument_labels_extern_rust() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we use the `#[swift_bridge(argument_labels(...))]` attribute when calling
+/// an extern "Swift" function.
+mod argument_labels_extern_swift {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    #[swift_bridge(argument_labels(user = "for", id = "_"))]
+                    fn fetch(user: u8, id: u32, verbose: bool);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$fetch")
+func __swift_bridge__fetch (_ user: UInt8, _ id: UInt32, _ verbose: Bool) {
+    fetch(for: user, id, verbose: verbose)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn argument_labels_extern_swift() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we call a `#[swift_bridge(main_actor)]` extern "Swift" function on the main actor.
+mod main_actor_extern_swift {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    #[swift_bridge(main_actor)]
+                    fn update_progress(percent: u8) -> bool;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$update_progress")
+func __swift_bridge__update_progress (_ percent: UInt8) -> Bool {
+    if Thread.isMainThread {
+        return MainActor.assumeIsolated { update_progress(percent: percent) }
+    } else {
+        return DispatchQueue.main.sync { MainActor.assumeIsolated { update_progress(percent: percent) } }
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn main_actor_extern_swift() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we dispatch a `#[swift_bridge(queue = "...")]` extern "Swift" function to its
+/// queue, retaining the Swift object that a method gets called on.
+mod queue_extern_swift {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    type Player;
+
+                    #[swift_bridge(queue = "main")]
+                    fn show_error(code: i32);
+
+                    #[swift_bridge(queue = "Player.queue")]
+                    fn played(&self, position: u32);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@_cdecl("__swift_bridge__$show_error")
+func __swift_bridge__show_error (_ code: Int32) {
+    DispatchQueue.main.async { show_error(code: code) }
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$Player$played")
+func __swift_bridge__Player_played (_ this: UnsafeMutableRawPointer, _ position: UInt32) {
+    let this = Unmanaged<Player>.fromOpaque(this).takeUnretainedValue()
+    Player.queue.async { this.played(position: position) }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn queue_extern_swift() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we use the `#[swift_bridge(default(...))]` attribute when generating the Swift
+/// function for an extern "Rust" function, and that Rust still receives every argument.
+mod default_arg_values_extern_rust {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(
+                        default(limit = 50, offset = -1, verbose = false, max = None),
+                        argument_labels(limit = "limit")
+                    )]
+                    fn fetch(limit: u32, offset: i64, verbose: bool, max: Option<u8>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__fetch(
+                limit: u32,
+                offset: i64,
+                verbose: bool,
+                max: swift_bridge::option::OptionU8
+            )
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func fetch(limit limit: UInt32 = 50, _ offset: Int64 = -1, _ verbose: Bool = false, _ max: Optional<UInt8> = nil) {
+"#,
+        )
+    }
+
+    #[test]
+    fn default_arg_values_extern_rust() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Swift computed property with a getter and a setter from
+/// `#[swift_bridge(swift_property)]` methods.
+mod swift_property_get_set {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    /// The title.
+                    #[swift_bridge(swift_property)]
+                    fn title(&self) -> String;
+                    #[swift_bridge(swift_property)]
+                    fn set_title(&mut self, title: String);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRefMut {
+    /// The title.
+    public var title: RustString {
+        get {
+            RustString(ptr: __swift_bridge__$SomeType$title(ptr))
+        }
+        set(title) {
+            __swift_bridge__$SomeType$set_title(ptr, { let rustString = title.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+        }
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn swift_property_get_set() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a `#[swift_bridge(swift_property)]` getter without a setter generates a read-only
+/// Swift computed property.
+mod swift_property_get_only {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(swift_property, swift_name = "itemCount")]
+                    fn item_count(&self) -> u32;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef {
+    public var itemCount: UInt32 {
+        get {
+            __swift_bridge__$SomeType$item_count(ptr)
+        }
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn swift_property_get_only() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we carry a `#[deprecated]` attribute on an extern "Rust" function over to the
+/// generated Rust and Swift functions.
+mod deprecated_extern_rust {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[deprecated(note = "use \"foo2\"")]
+                    fn foo();
+
+                    #[deprecated]
+                    fn bar();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$foo"]
+                #[deprecated(note = "use \"foo2\"")]
+                #[allow(deprecated)]
+                pub extern "C" fn __swift_bridge__foo() {
+                    super::foo()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$bar"]
+                #[deprecated]
+                #[allow(deprecated)]
+                pub extern "C" fn __swift_bridge__bar() {
+                    super::bar()
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@available(*, deprecated, message: "use \"foo2\"")
+public func foo() {
+    __swift_bridge__$foo()
+}
+"#,
+            r#"
+@available(*, deprecated)
+public func bar() {
+    __swift_bridge__$bar()
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn deprecated_extern_rust() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we put a `#[deprecated]` attribute on the generated Rust function that calls a
+/// deprecated extern "Swift" function.
+mod deprecated_extern_swift {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    #[deprecated = "use foo2"]
+                    fn foo();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[deprecated = "use foo2"]
+            pub fn foo() {
+                unsafe { __swift_bridge__foo() }
+            }
+        })
+    }
+
+    #[test]
+    fn deprecated_extern_swift() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we don't generate any code for functions and types that use the ignore attribute.
+mod ignore {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(ignore)]
+                    type SomeType;
+
+                    #[swift_bridge(ignore)]
+                    fn some_function(arg: &SomeType) -> HashMap<u8, u8>;
+                    fn another_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsManyAndDoesNotContainMany {
+            contains: vec![quote! {
+                pub extern "C" fn __swift_bridge__another_function()
+            }],
+            does_not_contain: vec![quote! { some_function }, quote! { SomeType }],
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::DoesNotContainManyAfterTrim(vec!["some_function", "SomeType"])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim(
+            r#"
+void __swift_bridge__$another_function(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn ignore() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a `blocking` function is exposed to Swift as an async function that calls Rust on a
+/// `DispatchQueue` thread.
+mod blocking_extern_rust {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Archive;
+
+                    #[swift_bridge(blocking)]
+                    fn compress(level: u8, name: &str) -> u64;
+
+                    #[swift_bridge(blocking)]
+                    fn extract(&self);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func compress<GenericToRustStr: ToRustStr>(_ level: UInt8, _ name: GenericToRustStr) async -> UInt64 {
+    func callRust() -> UInt64 {
+        return name.toRustStr({ nameAsRustStr in
+            __swift_bridge__$compress(level, nameAsRustStr)
+        })
+    }
+
+    return await withCheckedContinuation({ (continuation: CheckedContinuation<UInt64, Never>) in
+        DispatchQueue.global().async {
+            continuation.resume(returning: callRust())
+        }
+    })
+}
+"#,
+            r#"
+extension ArchiveRef {
+    public func extract() async {
+        func callRust() {
+            __swift_bridge__$Archive$extract(ptr)
+        }
+
+        return await withCheckedContinuation({ (continuation: CheckedContinuation<(), Never>) in
+            DispatchQueue.global().async {
+                continuation.resume(returning: callRust())
+            }
+        })
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn blocking_extern_rust() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we ask the Rust and Swift compilers to inline the glue code of `get` and `set`
+/// accessors, but not of `get_with` accessors, which call a user defined function.
+mod inline_trivial_accessors {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Point;
+
+                    #[swift_bridge(get(x))]
+                    fn x(&self) -> f64;
+
+                    #[swift_bridge(set(x))]
+                    fn set_x(&mut self, x: f64);
+
+                    #[swift_bridge(get_with(y = round))]
+                    fn rounded_y(&self) -> f64;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$Point$x"]
+                #[inline(always)]
+                pub extern "C" fn __swift_bridge__Point_x(this: *mut super::Point) -> f64 {
+                    (unsafe { &*this }).x
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$Point$set_x"]
+                #[inline(always)]
+                pub extern "C" fn __swift_bridge__Point_set_x(this: *mut super::Point, x: f64) {
+                    (unsafe { &mut *this }).x = x
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$Point$rounded_y"]
+                pub extern "C" fn __swift_bridge__Point_rounded_y(this: *mut super::Point) -> f64 {
+                    super::round((unsafe { &*this }).y)
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension PointRefMut {
+    @inline(__always)
+    public func set_x(_ x: Double) {
+        __swift_bridge__$Point$set_x(ptr, x)
+    }
+}
+"#,
+            r#"
+extension PointRef {
+    @inline(__always)
+    public func x() -> Double {
+        __swift_bridge__$Point$x(ptr)
+    }
+
+    public func rounded_y() -> Double {
+        __swift_bridge__$Point$rounded_y(ptr)
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn inline_trivial_accessors() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -98,7 +98,7 @@ mod monomorphized_generic_opaque_rust_type {
             pub extern "C" fn __swift_bridge__SomeType_u32__free (
                 this: *mut super::SomeType<u32>
             ) {
-                let this = unsafe { Box::from_raw(this) };
+                let this = unsafe { swift_bridge::allocator::take_from_raw(this) };
                 drop(this);
             }
         })
@@ -161,7 +161,7 @@ mod generic_opaque_rust_type_arg {
             pub extern "C" fn __swift_bridge__some_function (
                 arg: *mut super::SomeType<u32>
             ) {
-                super::some_function(unsafe { * Box::from_raw(arg) })
+                super::some_function(unsafe { swift_bridge::allocator::take_from_raw(arg) })
             }
         })
     }
@@ -218,7 +218,7 @@ mod generic_opaque_rust_type_return {
         ExpectedRustTokens::Contains(quote! {
             #[export_name = "__swift_bridge__$some_function"]
             pub extern "C" fn __swift_bridge__some_function () -> *mut super::SomeType<u32> {
-                 Box::into_raw(Box::new(super::some_function())) as *mut super::SomeType<u32>
+                 swift_bridge::allocator::box_into_raw(super::some_function()) as *mut super::SomeType<u32>
             }
         })
     }
@@ -307,7 +307,7 @@ mod generic_opaque_rust_type_copy {
         ExpectedSwiftCode::ContainsManyAfterTrim(vec![
             r#"
 public struct SomeType<A, B> {
-    fileprivate var bytes: SwiftBridgeGenericCopyTypeFfiRepr
+    var bytes: SwiftBridgeGenericCopyTypeFfiRepr
 }"#,
             r#"
 extension SomeType
@@ -485,7 +485,7 @@ mod generic_opaque_rust_type_inner_opaque_ty {
             pub extern "C" fn __swift_bridge__SomeType_AnotherType__free (
                 this: *mut super::SomeType<super::AnotherType>
             ) {
-                let this = unsafe { Box::from_raw(this) };
+                let this = unsafe { swift_bridge::allocator::take_from_raw(this) };
                 drop(this);
             }
         })
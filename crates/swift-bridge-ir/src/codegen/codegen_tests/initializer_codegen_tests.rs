@@ -0,0 +1,136 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that an initializer that returns an `Option<SomeType>` becomes a failable `init?`.
+mod failable_initializer {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(init)]
+                    fn new(value: u8) -> Option<Foo>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$Foo$new"]
+            pub extern "C" fn __swift_bridge__Foo_new(value: u8) -> *mut super::Foo {
+                if let Some(val) = super::Foo::new(value) {
+                    swift_bridge::allocator::box_into_raw(val)
+                } else {
+                    std::ptr::null_mut()
+                }
+            }
+        })
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+extension Foo {
+    public convenience init?(_ value: UInt8) {
+        guard let val = __swift_bridge__$Foo$new(value) else {
+            return nil
+        }
+        self.init(ptr: val)
+    }
+}
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void* __swift_bridge__$Foo$new(uint8_t value);
+"#,
+    );
+
+    #[test]
+    fn failable_initializer() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that an initializer that returns a `Result<SomeType, SomeError>` becomes a throwing
+/// `init() throws`.
+mod throwing_initializer {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+                    type InitError;
+
+                    #[swift_bridge(init)]
+                    fn new(value: u8) -> Result<Foo, InitError>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$Foo$new"]
+            pub extern "C" fn __swift_bridge__Foo_new(
+                value: u8
+            ) -> swift_bridge::result::ResultPtrAndPtr {
+                match super::Foo::new(value) {
+                    Ok(ok) => swift_bridge::result::ResultPtrAndPtr {
+                        is_ok: true,
+                        ok_or_err: swift_bridge::allocator::box_into_raw(ok) as *mut super::Foo as *mut std::ffi::c_void
+                    },
+                    Err(err) => swift_bridge::result::ResultPtrAndPtr {
+                        is_ok: false,
+                        ok_or_err: swift_bridge::allocator::box_into_raw(err) as *mut super::InitError as *mut std::ffi::c_void
+                    }
+                }
+            }
+        })
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+extension Foo {
+    public convenience init(_ value: UInt8) throws {
+        let val = __swift_bridge__$Foo$new(value)
+        if val.is_ok {
+            self.init(ptr: val.ok_or_err!)
+        } else {
+            throw InitError(ptr: val.ok_or_err!)
+        }
+    }
+}
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+struct __private__ResultPtrAndPtr __swift_bridge__$Foo$new(uint8_t value);
+"#,
+    );
+
+    #[test]
+    fn throwing_initializer() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
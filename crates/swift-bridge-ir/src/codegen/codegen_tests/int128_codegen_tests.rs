@@ -0,0 +1,256 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we pass `u128` and `i128` to and from an extern Rust function as two 64 bit halves.
+mod extern_rust_fn_128_bit_integers {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: u128) -> i128;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::int128::FfiU128
+            ) -> swift_bridge::int128::FfiI128 {
+                swift_bridge::int128::FfiI128::from(super::some_function(u128::from(arg)))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+#if compiler(>=6.0)
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+public func some_function(_ arg: UInt128) -> Int128 {
+    __swift_bridge__$some_function(arg.intoFfiRepr()).intoSwiftRepr()
+}
+#endif
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim(
+            r#"
+struct __private__I128 __swift_bridge__$some_function(struct __private__U128 arg);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_128_bit_integers() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we pass `u128` and `i128` to and from an extern Swift function as two 64 bit
+/// halves.
+mod extern_swift_fn_128_bit_integers {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function(arg: i128) -> u128;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub fn some_function(arg: i128) -> u128 {
+                    u128::from(unsafe {
+                        __swift_bridge__some_function(swift_bridge::int128::FfiI128::from(arg))
+                    })
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$some_function"]
+                fn __swift_bridge__some_function(
+                    arg: swift_bridge::int128::FfiI128
+                ) -> swift_bridge::int128::FfiU128;
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+#if compiler(>=6.0)
+@_cdecl("__swift_bridge__$some_function")
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+func __swift_bridge__some_function (_ arg: __private__I128) -> __private__U128 {
+    some_function(arg: arg.intoSwiftRepr()).intoFfiRepr()
+}
+#endif
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim("")
+    }
+
+    #[test]
+    fn extern_swift_fn_128_bit_integers() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that methods that use a `u128` or `i128` get the same `#if compiler(>=6.0)` and
+/// `@available` guards as `UInt128` and `Int128`.
+mod extern_rust_method_128_bit_integers {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+                    fn some_method(&self, arg: i128);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+    #if compiler(>=6.0)
+    @available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+    public func some_method(_ arg: Int128) {
+        __swift_bridge__$SomeType$some_method(ptr, arg.intoFfiRepr())
+    }
+    #endif
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_method_128_bit_integers() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a shared struct with a `u128` field, and the functions that use the struct, get the
+/// same `#if compiler(>=6.0)` and `@available` guards as `UInt128` and `Int128`.
+mod shared_struct_with_128_bit_integer_field {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u128
+                }
+
+                extern "Rust" {
+                    fn some_function(arg: SomeStruct);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+#if compiler(>=6.0)
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+public struct SomeStruct {
+    public var field: UInt128
+"#,
+            r#"
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+extension __swift_bridge__$SomeStruct {
+"#,
+            r#"
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+extension __swift_bridge__$Option$SomeStruct {
+"#,
+            r#"
+#if compiler(>=6.0)
+@available(macOS 15.0, iOS 18.0, watchOS 11.0, tvOS 18.0, visionOS 2.0, *)
+public func some_function(_ arg: SomeStruct) {
+    __swift_bridge__$some_function(arg.intoFfiRepr())
+}
+#endif
+"#,
+        ])
+    }
+
+    #[test]
+    fn shared_struct_with_128_bit_integer_field() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we don't generate any code for a `u128` or `i128` inside of a `Vec` or `Result`,
+/// since `UInt128` and `Int128` are not `Vectorizable` and can't be unpacked from a result yet.
+/// The module gets a parse error instead.
+mod extern_rust_fn_128_bit_integers_in_containers {
+    use crate::errors::ParseError;
+    use crate::test_utils::parse_errors;
+    use quote::{quote, ToTokens};
+
+    #[test]
+    fn extern_rust_fn_128_bit_integers_in_containers() {
+        let errors = parse_errors(quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: Vec<i128>) -> Vec<u128>;
+                    fn another_function() -> Result<u128, String>;
+                }
+            }
+        });
+        assert_eq!(errors.len(), 3);
+
+        let expected = ["Vec < i128 >", "Vec < u128 >", "Result < u128 , String >"];
+        for (error, expected) in errors.iter().zip(expected) {
+            match error {
+                ParseError::UnsupportedInt128 { ty } => {
+                    assert_eq!(ty.to_token_stream().to_string(), expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+}
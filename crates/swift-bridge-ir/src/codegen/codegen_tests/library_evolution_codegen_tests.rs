@@ -0,0 +1,80 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a module level `library_evolution` attribute freezes the module's shared structs
+/// and enums.
+mod module_library_evolution {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(library_evolution)]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u8,
+                }
+
+                enum SomeEnum {
+                    Variant1,
+                    Variant2,
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn module_library_evolution() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+                r#"
+@frozen public struct SomeStruct {
+    public var field: UInt8
+"#,
+                r#"
+@frozen public enum SomeEnum {
+    case Variant1
+    case Variant2
+}
+"#,
+            ]),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we do not freeze shared structs and enums if the module does not use the
+/// `library_evolution` attribute.
+mod no_library_evolution {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u8,
+                }
+
+                enum SomeEnum {
+                    Variant1,
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_library_evolution() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: ExpectedSwiftCode::DoesNotContainAfterTrim("@frozen"),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,141 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate a `withLock` method for a `#[swift_bridge(Mutex(...))]` type that locks
+/// the Rust mutex through generated `fileprivate` methods.
+mod mutex {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    type AppState;
+
+                    #[swift_bridge(Mutex(AppState))]
+                    type SharedState;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SharedState$swift_lock"]
+                pub extern "C" fn __swift_bridge__SharedState_swift_lock(
+                    this: *mut super::SharedState
+                ) {
+                    (unsafe { &*this }).swift_lock()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SharedState$swift_guarded_mut"]
+                pub extern "C" fn __swift_bridge__SharedState_swift_guarded_mut(
+                    this: *mut super::SharedState
+                ) -> *mut super::AppState {
+                    (unsafe { &*this }).swift_guarded_mut() as *mut super::AppState
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    fileprivate func swift_lock() {
+"#,
+            r#"
+extension SharedStateRef {
+    /// Calls `body` with the value that the Rust `Mutex` guards, holding the lock until
+    /// `body` returns.
+    ///
+    /// The `AppStateRefMut` must not be used after `body` returns.
+    public func withLock<R>(_ body: (AppStateRefMut) throws -> R) rethrows -> R {
+        swift_lock()
+        defer { swift_unlock() }
+        return try body(swift_guarded_mut())
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn mutex() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate `withReadLock` and `withWriteLock` methods for a
+/// `#[swift_bridge(RwLock(...))]` type.
+mod rw_lock {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    type AppState;
+
+                    #[swift_bridge(RwLock(AppState))]
+                    type SharedState;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SharedStateRef {
+    /// Calls `body` with the value that the Rust `RwLock` guards, holding a read lock until
+    /// `body` returns.
+    ///
+    /// The `AppStateRef` must not be used after `body` returns.
+    public func withReadLock<R>(_ body: (AppStateRef) throws -> R) rethrows -> R {
+        swift_read()
+        defer { swift_unlock() }
+        return try body(swift_guarded())
+    }
+
+    /// Calls `body` with the value that the Rust `RwLock` guards, holding the write lock until
+    /// `body` returns.
+    ///
+    /// The `AppStateRefMut` must not be used after `body` returns.
+    public func withWriteLock<R>(_ body: (AppStateRefMut) throws -> R) rethrows -> R {
+        swift_write()
+        defer { swift_unlock() }
+        return try body(swift_guarded_mut())
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$SharedState$swift_read(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn rw_lock() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
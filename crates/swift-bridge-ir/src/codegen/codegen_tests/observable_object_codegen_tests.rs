@@ -0,0 +1,109 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate an `ObservableObject` wrapper with `@Published` properties for a
+/// `#[swift_bridge(ObservableObject)]` type.
+mod observable_object_wrapper {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(ObservableObject)]
+                    type Counter;
+
+                    #[swift_bridge(swift_property)]
+                    fn count(&self) -> u32;
+                    #[swift_bridge(swift_property)]
+                    fn set_count(&mut self, count: u32);
+                    #[swift_bridge(swift_property)]
+                    fn label(&self) -> String;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                impl super::Counter {
+                    /// Notify this value's Swift `ObservableObject` wrappers
+                    /// that it changed, so that they refresh their properties.
+                    pub fn object_will_change(&self) {
+                        unsafe {
+                            __swift_bridge__Counter__object_will_change(
+                                self as *const super::Counter as *mut std::ffi::c_void
+                            )
+                        }
+                    }
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$Counter$_object_will_change"]
+                fn __swift_bridge__Counter__object_will_change(this: *mut std::ffi::c_void);
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public class CounterObservable: ObservableObject {
+    public let inner: Counter
+    private var isRefreshing = false
+
+    @Published public var count: UInt32 {
+        didSet {
+            if !isRefreshing {
+                inner.count = count
+            }
+        }
+    }
+    @Published public var label: String
+
+    public init(_ inner: Counter) {
+        self.inner = inner
+        self._count = Published(initialValue: inner.count)
+        self._label = Published(initialValue: inner.label.toString())
+        __swift_bridge__CounterObservableRegistry[inner.ptr] = __swift_bridge__CounterObservableRef(self)
+    }
+
+    deinit {
+        if __swift_bridge__CounterObservableRegistry[inner.ptr]?.value == nil {
+            __swift_bridge__CounterObservableRegistry.removeValue(forKey: inner.ptr)
+        }
+    }
+
+    public func refresh() {
+        objectWillChange.send()
+        isRefreshing = true
+        count = inner.count
+        label = inner.label.toString()
+        isRefreshing = false
+    }
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$Counter$_object_will_change")
+func __swift_bridge__Counter__object_will_change (_ this: UnsafeMutableRawPointer) {
+    DispatchQueue.main.async {
+        __swift_bridge__CounterObservableRegistry[this]?.value?.refresh()
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn observable_object_wrapper() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
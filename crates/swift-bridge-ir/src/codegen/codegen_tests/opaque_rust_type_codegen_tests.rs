@@ -24,7 +24,7 @@ mod extern_rust_type {
             pub extern "C" fn __swift_bridge__SomeType__free (
                 this: *mut super::SomeType
             ) {
-                let this = unsafe { Box::from_raw(this) };
+                let this = unsafe { swift_bridge::allocator::take_from_raw(this) };
                 drop(this);
             }
         })
@@ -145,6 +145,70 @@ uint64_t __swift_bridge__$HashableType$_hash(void* self);
     }
 }
 
+/// Verify that the generated Swift struct for a Copy extern "Rust" type that implements
+/// Hashable conforms to `Hashable`.
+mod extern_rust_copy_hashable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), Hashable, Equatable)]
+                    type HashableType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+        #[export_name = "__swift_bridge__$HashableType$_hash"]
+        pub extern "C" fn __swift_bridge__HashableType__hash (
+            this: *const super::HashableType,
+        ) -> u64 {
+            use std::hash::{Hash, Hasher};
+            use std::collections::hash_map::DefaultHasher;
+            let mut s = DefaultHasher::new();
+            (unsafe {&*this}).hash(&mut s);
+            s.finish()
+        }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension HashableType: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        var bytes = self.bytes
+        hasher.combine(__swift_bridge__$HashableType$_hash(&bytes))
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+uint64_t __swift_bridge__$HashableType$_hash(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_hashable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
 /// Test code generation for an extern "Rust" type that implements Equatable.
 mod extern_rust_equatable_type {
     use super::*;
@@ -153,56 +217,1005 @@ mod extern_rust_equatable_type {
         quote! {
             mod ffi {
                 extern "Rust" {
-                    #[swift_bridge(Equatable)]
-                    type EquatableType;
+                    #[swift_bridge(Equatable)]
+                    type EquatableType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+        #[export_name = "__swift_bridge__$EquatableType$_partial_eq"]
+        pub extern "C" fn __swift_bridge__EquatableType__partial_eq (
+            lhs: *const super::EquatableType,
+            rhs: *const super::EquatableType
+        ) -> bool {
+            unsafe { &*lhs == &*rhs }
+        }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension EquatableTypeRef: Equatable {
+    public static func == (lhs: EquatableTypeRef, rhs: EquatableTypeRef) -> Bool {
+        __swift_bridge__$EquatableType$_partial_eq(lhs.ptr, rhs.ptr)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"
+bool __swift_bridge__$EquatableType$_partial_eq(void* lhs, void* rhs);
+    "#,
+            r#"
+#include <stdint.h>
+#include <stdbool.h>
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_equatable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the generated Swift struct for a Copy extern "Rust" type that implements
+/// Equatable conforms to `Equatable`.
+mod extern_rust_copy_equatable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), Equatable)]
+                    type EquatableType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+        #[export_name = "__swift_bridge__$EquatableType$_partial_eq"]
+        pub extern "C" fn __swift_bridge__EquatableType__partial_eq (
+            lhs: *const super::EquatableType,
+            rhs: *const super::EquatableType
+        ) -> bool {
+            unsafe { &*lhs == &*rhs }
+        }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension EquatableType: Equatable {
+    public static func == (lhs: EquatableType, rhs: EquatableType) -> Bool {
+        var lhs = lhs.bytes
+        var rhs = rhs.bytes
+        return __swift_bridge__$EquatableType$_partial_eq(&lhs, &rhs)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+bool __swift_bridge__$EquatableType$_partial_eq(void* lhs, void* rhs);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_equatable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we implement `Codable` for an extern "Rust" type by passing its JSON
+/// representation across the FFI boundary.
+mod extern_rust_codable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Codable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeType$_to_json"]
+            pub extern "C" fn __swift_bridge__SomeType__to_json (
+                this: *const super::SomeType,
+            ) -> *mut swift_bridge::string::RustString {
+                match swift_bridge::codable::to_json(unsafe { &*this }) {
+                    Some(json) => swift_bridge::string::RustString(json).box_into_raw(),
+                    None => std::ptr::null_mut(),
+                }
+            }
+
+            #[export_name = "__swift_bridge__$SomeType$_from_json"]
+            pub extern "C" fn __swift_bridge__SomeType__from_json (
+                json: *mut swift_bridge::string::RustString,
+            ) -> *mut super::SomeType {
+                let json = unsafe { swift_bridge::allocator::take_from_raw(json).0 };
+                match swift_bridge::codable::from_json::<super::SomeType>(&json) {
+                    Some(val) => swift_bridge::allocator::box_into_raw(val),
+                    None => std::ptr::null_mut(),
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public class SomeType: SomeTypeRefMut, Decodable {
+    var isOwned: Bool = true
+
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    public required convenience init(from decoder: Decoder) throws {
+        let json = try decoder.singleValueContainer().decode(String.self)
+        guard let ptr = __swift_bridge__$SomeType$_from_json({ let rustString = json.intoRustString(); rustString.isOwned = false; return rustString.ptr }()) else {
+            throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Could not deserialize SomeType"))
+        }
+        self.init(ptr: ptr)
+    }
+"#,
+            r#"
+extension SomeTypeRef: Encodable {
+    public func encode(to encoder: Encoder) throws {
+        guard let json = __swift_bridge__$SomeType$_to_json(self.ptr) else {
+            throw EncodingError.invalidValue(self, EncodingError.Context(codingPath: encoder.codingPath, debugDescription: "Could not serialize SomeType"))
+        }
+        var container = encoder.singleValueContainer()
+        try container.encode(RustString(ptr: json).toString())
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_to_json(void* self);
+void* __swift_bridge__$SomeType$_from_json(void* json);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_codable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we implement `Comparable` using an extern "Rust" type's `Ord` implementation.
+mod extern_rust_comparable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Comparable, Equatable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeType$_cmp"]
+            pub extern "C" fn __swift_bridge__SomeType__cmp (
+                lhs: *const super::SomeType,
+                rhs: *const super::SomeType
+            ) -> i8 {
+                unsafe { (&*lhs).cmp(&*rhs) as i8 }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef: Comparable {
+    public static func < (lhs: SomeTypeRef, rhs: SomeTypeRef) -> Bool {
+        __swift_bridge__$SomeType$_cmp(lhs.ptr, rhs.ptr) < 0
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+int8_t __swift_bridge__$SomeType$_cmp(void* lhs, void* rhs);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_comparable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the generated Swift struct for a Copy extern "Rust" type that implements
+/// Comparable conforms to `Comparable`.
+mod extern_rust_copy_comparable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), Comparable, Equatable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType: Comparable {
+    public static func < (lhs: SomeType, rhs: SomeType) -> Bool {
+        var lhs = lhs.bytes
+        var rhs = rhs.bytes
+        return __swift_bridge__$SomeType$_cmp(&lhs, &rhs) < 0
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_comparable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we implement `CustomStringConvertible` and `CustomDebugStringConvertible` using
+/// an extern "Rust" type's `Display` and `Debug` implementations.
+mod extern_rust_custom_string_convertible_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(CustomStringConvertible, CustomDebugStringConvertible)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_description"]
+                pub extern "C" fn __swift_bridge__SomeType__description (
+                    this: *const super::SomeType,
+                ) -> *mut swift_bridge::string::RustString {
+                    swift_bridge::string::RustString(
+                        format!("{}", unsafe { &*this })
+                    ).box_into_raw()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_debug_description"]
+                pub extern "C" fn __swift_bridge__SomeType__debug_description (
+                    this: *const super::SomeType,
+                ) -> *mut swift_bridge::string::RustString {
+                    swift_bridge::string::RustString(
+                        format!("{:?}", unsafe { &*this })
+                    ).box_into_raw()
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef: CustomStringConvertible {
+    public var description: String {
+        RustString(ptr: __swift_bridge__$SomeType$_description(self.ptr)).toString()
+    }
+}
+extension SomeTypeRef: CustomDebugStringConvertible {
+    public var debugDescription: String {
+        RustString(ptr: __swift_bridge__$SomeType$_debug_description(self.ptr)).toString()
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_description(void* self);
+void* __swift_bridge__$SomeType$_debug_description(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_custom_string_convertible_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the generated Swift struct for a Copy extern "Rust" type conforms to
+/// `CustomStringConvertible` and `CustomDebugStringConvertible`.
+mod extern_rust_copy_custom_string_convertible_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), CustomStringConvertible, CustomDebugStringConvertible)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType: CustomStringConvertible {
+    public var description: String {
+        var bytes = self.bytes
+        return RustString(ptr: __swift_bridge__$SomeType$_description(&bytes)).toString()
+    }
+}
+extension SomeType: CustomDebugStringConvertible {
+    public var debugDescription: String {
+        var bytes = self.bytes
+        return RustString(ptr: __swift_bridge__$SomeType$_debug_description(&bytes)).toString()
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_custom_string_convertible_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we conform an extern "Rust" type that uses the `Error` attribute to
+/// `LocalizedError`, using its `Display` implementation as the error description.
+mod extern_rust_error_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Error)]
+                    type SomeError;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeError$_description"]
+            pub extern "C" fn __swift_bridge__SomeError__description (
+                this: *const super::SomeError,
+            ) -> *mut swift_bridge::string::RustString {
+                swift_bridge::string::RustString(
+                    format!("{}", unsafe { &*this })
+                ).box_into_raw()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeError: @unchecked Sendable {}
+extension SomeError: LocalizedError {
+    public var errorDescription: String? {
+        RustString(ptr: __swift_bridge__$SomeError$_description(self.ptr)).toString()
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeError$_description(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_error_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the generated Swift struct for a Copy extern "Rust" type that uses the `Error`
+/// attribute conforms to `LocalizedError`.
+mod extern_rust_copy_error_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), Error)]
+                    type SomeError;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeError: @unchecked Sendable {}
+extension SomeError: LocalizedError {
+    public var errorDescription: String? {
+        var bytes = self.bytes
+        return RustString(ptr: __swift_bridge__$SomeError$_description(&bytes)).toString()
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_error_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we assert that a `Sendable` extern "Rust" type is `Send + Sync`, and mark its
+/// generated Swift class `@unchecked Sendable`.
+mod extern_rust_sendable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Sendable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            const _: () = {
+                fn _assert_send_sync() {
+                    swift_bridge::assert_send_sync::<super::SomeType>();
+                }
+            };
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+// The Rust type is `Send + Sync`, which is checked when the Rust code is compiled.
+extension SomeType: @unchecked Sendable {}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_sendable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that the generated Swift class of an extern "Rust" type that doesn't use the `Sendable`
+/// attribute is explicitly not `Sendable`.
+mod extern_rust_non_sendable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+// The Rust type might not be `Send + Sync`, so `SomeType` can't be shared between tasks or
+// actors. Use `#[swift_bridge(Sendable)]` on the Rust type if it is `Send + Sync`.
+@available(*, unavailable)
+extension SomeType: Sendable {}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_non_sendable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::DoesNotContain(quote! {
+                assert_send_sync
+            }),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we mark the generated Swift struct for a `Sendable` Copy extern "Rust" type
+/// `@unchecked Sendable`.
+mod extern_rust_copy_sendable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), Sendable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType: @unchecked Sendable {}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_sendable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a type that uses both the `Sendable` and `Error` attributes only conforms to
+/// `Sendable` once.
+mod extern_rust_sendable_error_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Sendable, Error)]
+                    type SomeError;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::DoesNotContainAfterTrim(
+            r#"
+extension SomeError: @unchecked Sendable {}
+extension SomeError: @unchecked Sendable {}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_sendable_error_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that the generated Swift classes for an extern "Rust" type that uses the `objc`
+/// attribute inherit from `NSObject` and expose their members to Objective-C.
+mod extern_rust_objc_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(objc)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+import Foundation
+@objcMembers
+public class SomeType: SomeTypeRefMut {
+    var isOwned: Bool = true
+
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    deinit {
+        if isOwned {
+            __swift_bridge__$SomeType$_free(ptr)
+        }
+    }
+}
+@objcMembers
+public class SomeTypeRefMut: SomeTypeRef {
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+}
+@objcMembers
+public class SomeTypeRef: NSObject {
+    var ptr: UnsafeMutableRawPointer
+
+    public init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+        super.init()
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_objc_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that an extern "Rust" type that uses the `objc` attribute overrides `NSObject`'s
+/// equality, hashing and descriptions instead of conforming to protocols that `NSObject`
+/// already conforms to.
+mod extern_rust_objc_type_nsobject_overrides {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(
+                        objc,
+                        Equatable,
+                        Hashable,
+                        CustomStringConvertible,
+                        CustomDebugStringConvertible
+                    )]
+                    type SomeType;
                 }
             }
         }
     }
 
-    fn expected_rust_tokens() -> ExpectedRustTokens {
-        ExpectedRustTokens::Contains(quote! {
-        #[export_name = "__swift_bridge__$EquatableType$_partial_eq"]
-        pub extern "C" fn __swift_bridge__EquatableType__partial_eq (
-            lhs: *const super::EquatableType,
-            rhs: *const super::EquatableType
-        ) -> bool {
-            unsafe { &*lhs == &*rhs }
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension SomeTypeRef {
+    public override func isEqual(_ object: Any?) -> Bool {
+        guard let other = object as? SomeTypeRef else {
+            return false
+        }
+        return __swift_bridge__$SomeType$_partial_eq(self.ptr, other.ptr)
+    }
+}
+extension SomeTypeRef {
+    public override var hash: Int {
+        Int(truncatingIfNeeded: __swift_bridge__$SomeType$_hash(self.ptr))
+    }
+}
+"#,
+            r#"
+extension SomeTypeRef {
+    public override var description: String {
+        RustString(ptr: __swift_bridge__$SomeType$_description(self.ptr)).toString()
+    }
+}
+extension SomeTypeRef {
+    public override var debugDescription: String {
+        RustString(ptr: __swift_bridge__$SomeType$_debug_description(self.ptr)).toString()
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_objc_type_nsobject_overrides() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that an extern "Rust" type that uses the `open` attribute gets `open` classes with its
+/// methods declared in their bodies, so that Swift code can subclass it and override its methods.
+mod extern_rust_open_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(open)]
+                    type SomeType;
+
+                    #[swift_bridge(init)]
+                    fn new(count: u32) -> SomeType;
+                    fn count(&self) -> u32;
+                    fn increment(&mut self);
+                    fn consume(self);
+                }
+            }
         }
-        })
     }
 
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-extension EquatableTypeRef: Equatable {
-    public static func == (lhs: EquatableTypeRef, rhs: EquatableTypeRef) -> Bool {
-        __swift_bridge__$EquatableType$_partial_eq(rhs.ptr, lhs.ptr)
+open class SomeType: SomeTypeRefMut {
+    var isOwned: Bool = true
+
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    deinit {
+        if isOwned {
+            __swift_bridge__$SomeType$_free(ptr)
+        }
+    }
+
+    public init(_ count: UInt32) {
+        super.init(ptr: __swift_bridge__$SomeType$new(count))
+    }
+
+    open func consume() {
+        __swift_bridge__$SomeType$consume({isOwned = false; return ptr;}())
+    }
+}
+open class SomeTypeRefMut: SomeTypeRef {
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    open func increment() {
+        __swift_bridge__$SomeType$increment(ptr)
+    }
+}
+open class SomeTypeRef {
+    var ptr: UnsafeMutableRawPointer
+
+    public init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+
+    open func count() -> UInt32 {
+        __swift_bridge__$SomeType$count(ptr)
     }
 }
 "#,
         )
     }
 
-    fn expected_c_header() -> ExpectedCHeader {
-        ExpectedCHeader::ContainsManyAfterTrim(vec![
+    #[test]
+    fn extern_rust_open_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we conform an extern "Rust" type that uses the `Identifiable` attribute to
+/// `Identifiable`, using the named getter as its id.
+mod extern_rust_identifiable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Identifiable = "user_id")]
+                    type SomeType;
+
+                    #[swift_bridge(swift_name = "userId")]
+                    fn user_id(&self) -> u64;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-bool __swift_bridge__$EquatableType$_partial_eq(void* lhs, void* rhs);
-    "#,
+extension SomeTypeRef: Identifiable {
+    public typealias ID = UInt64
+    public var id: UInt64 {
+        return self.userId()
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_identifiable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that an `Identifiable` type whose getter is a `String` swift_property uses the
+/// Swift `String` as its `ID`, since `RustString` isn't `Hashable`.
+mod extern_rust_identifiable_type_string_property {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Identifiable = "name")]
+                    type SomeType;
+
+                    #[swift_bridge(swift_property)]
+                    fn name(&self) -> String;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-#include <stdint.h>
-#include <stdbool.h>
+extension SomeTypeRef: Identifiable {
+    public typealias ID = String
+    public var id: String {
+        return self.name.toString()
+    }
+}
 "#,
-        ])
+        )
     }
 
     #[test]
-    fn extern_rust_equatable_type() {
+    fn extern_rust_identifiable_type_string_property() {
         CodegenTest {
             bridge_module: bridge_module_tokens().into(),
-            expected_rust_tokens: expected_rust_tokens(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
             expected_swift_code: expected_swift_code(),
-            expected_c_header: expected_c_header(),
+            expected_c_header: ExpectedCHeader::SkipTest,
         }
         .test();
     }
@@ -274,7 +1287,7 @@ mod extern_rust_copy_type {
         ExpectedSwiftCode::ContainsManyAfterTrim(vec![
             r#"
 public struct SomeType {
-    fileprivate var bytes: __swift_bridge__$SomeType
+    var bytes: __swift_bridge__$SomeType
 
     func intoFfiRepr() -> __swift_bridge__$SomeType {
         bytes
@@ -416,7 +1429,7 @@ mod extern_swift_freestanding_fn_with_owned_opaque_rust_type_arg {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
             pub fn some_function (arg: super::MyType) {
-                unsafe { __swift_bridge__some_function( Box::into_raw(Box::new(arg)) as *mut super::MyType ) }
+                unsafe { __swift_bridge__some_function( swift_bridge::allocator::box_into_raw(arg) as *mut super::MyType ) }
             }
 
             extern "C" {
@@ -452,3 +1465,150 @@ typedef struct MyType MyType;
         .test();
     }
 }
+
+/// Verify that we use the `swift_name` attribute of an extern "Rust" type when naming the
+/// generated Swift classes, while the Rust and C code keep using the Rust type's name.
+mod extern_rust_type_swift_name {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(swift_name = "Renderer")]
+                    type RendererRs;
+
+                    #[swift_bridge(init)]
+                    fn new() -> RendererRs;
+
+                    fn child(&self) -> &RendererRs;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$RendererRs$_free"]
+            pub extern "C" fn __swift_bridge__RendererRs__free (
+                this: *mut super::RendererRs
+            ) {
+                let this = unsafe { swift_bridge::allocator::take_from_raw(this) };
+                drop(this);
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public class Renderer: RendererRefMut {
+    var isOwned: Bool = true
+
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    deinit {
+        if isOwned {
+            __swift_bridge__$RendererRs$_free(ptr)
+        }
+    }
+}
+extension Renderer {
+    public convenience init() {
+        self.init(ptr: __swift_bridge__$RendererRs$new())
+    }
+}
+public class RendererRefMut: RendererRef {
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+}
+public class RendererRef {
+    var ptr: UnsafeMutableRawPointer
+
+    public init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+}
+extension RendererRef {
+    public func child() -> RendererRef {
+        RendererRef(ptr: __swift_bridge__$RendererRs$child(ptr))
+    }
+}
+"#,
+            r#"
+extension Renderer: Vectorizable {
+    public static func vecOfSelfNew() -> UnsafeMutableRawPointer {
+        __swift_bridge__$Vec_RendererRs$new()
+    }
+"#,
+        ])
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+typedef struct RendererRs RendererRs;
+void __swift_bridge__$RendererRs$_free(void* self);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_type_swift_name() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we use the `swift_name` attribute of a Copy extern "Rust" type when naming the
+/// generated Swift struct.
+mod extern_rust_copy_type_swift_name {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), swift_name = "UserId")]
+                    type UserIdRs;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public struct UserId {
+    var bytes: __swift_bridge__$UserIdRs
+
+    func intoFfiRepr() -> __swift_bridge__$UserIdRs {
+        bytes
+    }
+}
+extension __swift_bridge__$UserIdRs {
+    func intoSwiftRepr() -> UserId {
+        UserId(bytes: self)
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_copy_type_swift_name() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -64,3 +64,105 @@ func __swift_bridge__some_function (_ arg: UnsafeMutableRawPointer) {
         .test();
     }
 }
+
+/// Verify that we use the `swift_name` attribute of an extern "Swift" type when referring to the
+/// Swift class.
+mod extern_swift_type_swift_name {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(swift_name = "SwiftLogger")]
+                    type Logger;
+
+                    fn some_function (arg: Logger);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[repr(C)]
+            pub struct Logger(*mut std::ffi::c_void);
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ arg: UnsafeMutableRawPointer) {
+    some_function(arg: Unmanaged<SwiftLogger>.fromOpaque(arg).takeRetainedValue())
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$Logger$_free")
+func __swift_bridge__Logger__free (ptr: UnsafeMutableRawPointer) {
+    let _ = Unmanaged<SwiftLogger>.fromOpaque(ptr).takeRetainedValue()
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_swift_type_swift_name() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we wait for an extern "Swift" actor to run its methods, since Rust calls them
+/// from outside of the actor's isolation.
+mod extern_swift_actor_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(actor)]
+                    type Counter;
+
+                    fn increment(&self, amount: u32);
+                    fn name(&self) -> String;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@_cdecl("__swift_bridge__$Counter$increment")
+func __swift_bridge__Counter_increment (_ this: UnsafeMutableRawPointer, _ amount: UInt32) {
+    __swift_bridge__awaitActor({ await Unmanaged<Counter>.fromOpaque(this).takeUnretainedValue().increment(amount: amount) })
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$Counter$name")
+func __swift_bridge__Counter_name (_ this: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer {
+    { let rustString = __swift_bridge__awaitActor({ await Unmanaged<Counter>.fromOpaque(this).takeUnretainedValue().name() }).intoRustString(); rustString.isOwned = false; return rustString.ptr }()
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_swift_actor_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -44,7 +44,7 @@ mod extern_rust_fn_option_primitive {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
 func some_function(_ arg: Optional<UInt8>) -> Optional<Float> {
-    { let val = __swift_bridge__$some_function({ let val = arg; return __private__OptionU8(val: val ?? 123, is_some: val != nil); }()); if val.is_some { return val.val } else { return nil } }()
+    { let val = __swift_bridge__$some_function({ if let val = arg { return __swift_bridge__$OptionU8$some(val) } else { return __swift_bridge__$OptionU8$none() } }()); if val.is_some { return val.val } else { return nil } }()
 }
 "#,
         )
@@ -92,7 +92,7 @@ mod extern_rust_fn_option_string {
                     if arg.is_null() {
                         None
                     } else {
-                        Some(unsafe { Box::from_raw(arg).0 })
+                        Some(unsafe { swift_bridge::allocator::take_from_raw(arg).0 })
                     }
                 ) {
                     swift_bridge::string::RustString(val).box_into_raw()
@@ -286,7 +286,7 @@ mod extern_rust_fn_return_option_opaque_rust_type {
             #[export_name = "__swift_bridge__$some_function"]
             pub extern "C" fn __swift_bridge__some_function() -> *mut super::SomeType {
                 if let Some(val) = super::some_function() {
-                    Box::into_raw(Box::new(val))
+                    swift_bridge::allocator::box_into_raw(val)
                 } else {
                     std::ptr::null_mut()
                 }
@@ -349,7 +349,7 @@ mod extern_rust_fn_with_option_opaque_rust_type_arg {
                     if arg.is_null() {
                         None
                     } else {
-                        Some( unsafe { * Box::from_raw(arg) } )
+                        Some( unsafe { swift_bridge::allocator::take_from_raw(arg) } )
                     }
                 )
             }
@@ -409,7 +409,7 @@ mod extern_rust_fn_return_option_generic_opaque_rust_type {
             #[export_name = "__swift_bridge__$some_function"]
             pub extern "C" fn __swift_bridge__some_function() -> *mut super::SomeType<u32> {
                 if let Some(val) = super::some_function() {
-                    Box::into_raw(Box::new(val))
+                    swift_bridge::allocator::box_into_raw(val)
                 } else {
                     std::ptr::null_mut()
                 }
@@ -475,7 +475,7 @@ mod extern_rust_fn_with_option_generic_opaque_rust_type_arg {
                     if arg.is_null() {
                         None
                     } else {
-                        Some( unsafe { * Box::from_raw(arg) } )
+                        Some( unsafe { swift_bridge::allocator::take_from_raw(arg) } )
                     }
                 )
             }
@@ -854,7 +854,7 @@ public struct SomeStruct {
 
     @inline(__always)
     func intoFfiRepr() -> __swift_bridge__$SomeStruct {
-        { let val = self; return __swift_bridge__$SomeStruct(field: { let val = val.field; return __private__OptionU8(val: val ?? 123, is_some: val != nil); }()); }()
+        { let val = self; return __swift_bridge__$SomeStruct(field: { if let val = val.field { return __swift_bridge__$OptionU8$some(val) } else { return __swift_bridge__$OptionU8$none() } }()); }()
     }
 }
 extension __swift_bridge__$SomeStruct {
@@ -0,0 +1,211 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a module with `panic = "abort"` aborts if one of its Rust functions panics.
+mod panic_abort {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(panic = "abort")]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: u8) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(arg: u8) -> u8 {
+                swift_bridge::panic_support::catch_panic_or_abort(|| { super::some_function(arg) })
+            }
+        })
+    }
+
+    #[test]
+    fn panic_abort() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::ContainsAfterTrim(
+                r#"
+public func some_function(_ arg: UInt8) -> UInt8 {
+    __swift_bridge__$some_function(arg)
+}
+"#,
+            ),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a module with `panic = "fatal_error"` calls Swift's `fatalError` if one of its
+/// Rust functions panics.
+mod panic_fatal_error {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(panic = "fatal_error")]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_method(&self) -> String;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeType$some_method"]
+            pub extern "C" fn __swift_bridge__SomeType_some_method(
+                this: *mut super::SomeType
+            ) -> *mut swift_bridge::string::RustString {
+                swift_bridge::panic_support::catch_panic_or_fatal_error(|| {
+                    swift_bridge::string::RustString((unsafe { &*this }).some_method()).box_into_raw()
+                })
+            }
+        })
+    }
+
+    #[test]
+    fn panic_fatal_error() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a throwing initializer in a module with `panic = "throw"` throws the panic, and
+/// that the module's other functions call Swift's `fatalError`.
+mod panic_throw {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(panic = "throw")]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+                    type InitError;
+
+                    #[swift_bridge(init)]
+                    fn new(value: u8) -> Result<Foo, InitError>;
+
+                    fn some_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$Foo$new"]
+                pub extern "C" fn __swift_bridge__Foo_new(
+                    value: u8
+                ) -> swift_bridge::result::ResultPtrAndPtr {
+                    swift_bridge::panic_support::catch_panic_or_throw(
+                        || {
+                            match super::Foo::new(value) {
+                                Ok(ok) => swift_bridge::result::ResultPtrAndPtr {
+                                    is_ok: true,
+                                    ok_or_err: swift_bridge::allocator::box_into_raw(ok) as *mut super::Foo as *mut std::ffi::c_void
+                                },
+                                Err(err) => swift_bridge::result::ResultPtrAndPtr {
+                                    is_ok: false,
+                                    ok_or_err: swift_bridge::allocator::box_into_raw(err) as *mut super::InitError as *mut std::ffi::c_void
+                                }
+                            }
+                        },
+                        swift_bridge::result::ResultPtrAndPtr {
+                            is_ok: false,
+                            ok_or_err: std::ptr::null_mut()
+                        }
+                    )
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function() {
+                    swift_bridge::panic_support::catch_panic_or_fatal_error(|| { super::some_function() })
+                }
+            },
+        ])
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+extension Foo {
+    public convenience init(_ value: UInt8) throws {
+        let val = __swift_bridge__$Foo$new(value)
+        try throwIfRustPanicked()
+        if val.is_ok {
+            self.init(ptr: val.ok_or_err!)
+        } else {
+            throw InitError(ptr: val.ok_or_err!)
+        }
+    }
+}
+"#,
+    );
+
+    #[test]
+    fn panic_throw() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we don't catch panics in modules that don't have a `panic` attribute.
+mod no_panic_attribute {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsManyAndDoesNotContainMany {
+            contains: vec![quote! {
+                pub extern "C" fn __swift_bridge__some_function() {
+                    super::some_function()
+                }
+            }],
+            does_not_contain: vec![quote! { panic_support }],
+        }
+    }
+
+    #[test]
+    fn no_panic_attribute() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::DoesNotContainAfterTrim("throwIfRustPanicked"),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
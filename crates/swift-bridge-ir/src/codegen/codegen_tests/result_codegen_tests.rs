@@ -27,9 +27,9 @@ mod extern_rust_fn_result_string {
             ) {
                 super::some_function(
                     if arg.is_ok {
-                        std::result::Result::Ok(unsafe { Box::from_raw(arg.ok_or_err as *mut swift_bridge::string::RustString).0 })
+                        std::result::Result::Ok(unsafe { swift_bridge::allocator::take_from_raw(arg.ok_or_err as *mut swift_bridge::string::RustString).0 })
                     } else {
-                        std::result::Result::Err(unsafe { Box::from_raw(arg.ok_or_err as *mut swift_bridge::string::RustString).0 })
+                        std::result::Result::Err(unsafe { swift_bridge::allocator::take_from_raw(arg.ok_or_err as *mut swift_bridge::string::RustString).0 })
                     }
                 )
             }
@@ -89,9 +89,9 @@ mod extern_rust_fn_result_opaque_rust {
             ) {
                 super::some_function(
                     if arg.is_ok {
-                        std::result::Result::Ok(unsafe { *Box::from_raw(arg.ok_or_err as *mut super::SomeType) })
+                        std::result::Result::Ok(unsafe { swift_bridge::allocator::take_from_raw(arg.ok_or_err as *mut super::SomeType) })
                     } else {
-                        std::result::Result::Err(unsafe { *Box::from_raw(arg.ok_or_err as *mut super::SomeType) })
+                        std::result::Result::Err(unsafe { swift_bridge::allocator::take_from_raw(arg.ok_or_err as *mut super::SomeType) })
                     }
                 )
             }
@@ -190,3 +190,566 @@ void __swift_bridge__$some_function(struct __private__ResultPtrAndPtr arg);
         .test();
     }
 }
+
+/// Test code generation for Rust function that accepts a Result<T, E> where T is a shared struct
+/// and E is an opaque Rust type.
+/// The result gets passed by value instead of boxing the struct.
+mod extern_rust_fn_result_shared_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u8
+                }
+
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function (arg: Result<SomeStruct, SomeType>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[repr(C)]
+                #[doc(hidden)]
+                pub struct __swift_bridge__Result_10SomeStructAnd5super8SomeType {
+                    is_ok: bool,
+                    ok: std::mem::MaybeUninit<__swift_bridge__SomeStruct>,
+                    err: std::mem::MaybeUninit<*mut super::SomeType>,
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function(
+                    arg: __swift_bridge__Result_10SomeStructAnd5super8SomeType
+                ) {
+                    super::some_function({
+                        let val = arg;
+                        if val.is_ok {
+                            std::result::Result::Ok({
+                                let ok = unsafe { val.ok.assume_init() };
+                                ok.into_rust_repr()
+                            })
+                        } else {
+                            std::result::Result::Err({
+                                let err = unsafe { val.err.assume_init() };
+                                unsafe { swift_bridge::allocator::take_from_raw(err) }
+                            })
+                        }
+                    })
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func some_function(_ arg: RustResult<SomeStruct, SomeType>) {
+    __swift_bridge__$some_function({ var ffiResult = __swift_bridge__$Result$10SomeStructAnd5super8SomeType(); switch arg { case .Ok(let ok): ffiResult.is_ok = true; ffiResult.ok = ok.intoFfiRepr(); case .Err(let err): ffiResult.is_ok = false; ffiResult.err = {err.isOwned = false; return err.ptr;}(); }; return ffiResult }())
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"
+#ifndef __swift_bridge__$Result$10SomeStructAnd5super8SomeType$Declared
+#define __swift_bridge__$Result$10SomeStructAnd5super8SomeType$Declared
+typedef struct __swift_bridge__$Result$10SomeStructAnd5super8SomeType { bool is_ok; struct __swift_bridge__$SomeStruct ok; void* err; } __swift_bridge__$Result$10SomeStructAnd5super8SomeType;
+#endif
+    "#,
+            r#"
+void __swift_bridge__$some_function(struct __swift_bridge__$Result$10SomeStructAnd5super8SomeType arg);
+    "#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_fn_result_shared_struct() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Test code generation for Rust function that accepts a Result<T, E> where T is a shared struct
+/// and E is `()`.
+/// We don't generate a field for the `()`.
+mod extern_rust_fn_result_shared_struct_and_null {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u8
+                }
+
+                extern "Rust" {
+                    fn some_function (arg: Result<SomeStruct, ()>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[repr(C)]
+                #[doc(hidden)]
+                pub struct __swift_bridge__Result_10SomeStructAndVoid {
+                    is_ok: bool,
+                    ok: std::mem::MaybeUninit<__swift_bridge__SomeStruct>,
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function(
+                    arg: __swift_bridge__Result_10SomeStructAndVoid
+                ) {
+                    super::some_function({
+                        let val = arg;
+                        if val.is_ok {
+                            std::result::Result::Ok({
+                                let ok = unsafe { val.ok.assume_init() };
+                                ok.into_rust_repr()
+                            })
+                        } else {
+                            std::result::Result::Err(())
+                        }
+                    })
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func some_function(_ arg: RustResult<SomeStruct, ()>) {
+    __swift_bridge__$some_function({ var ffiResult = __swift_bridge__$Result$10SomeStructAndVoid(); switch arg { case .Ok(let ok): ffiResult.is_ok = true; ffiResult.ok = ok.intoFfiRepr(); case .Err(_): ffiResult.is_ok = false; }; return ffiResult }())
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+typedef struct __swift_bridge__$Result$10SomeStructAndVoid { bool is_ok; struct __swift_bridge__$SomeStruct ok; } __swift_bridge__$Result$10SomeStructAndVoid;
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_result_shared_struct_and_null() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Test code generation for Rust functions that accept and return a Result<T, E> where T is a
+/// shared struct that is too large to be passed by value.
+/// The result's struct gets boxed instead.
+mod extern_rust_fn_result_large_shared_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct LargeStruct {
+                    a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64, h: u64
+                }
+
+                extern "Rust" {
+                    fn some_function (arg: Result<LargeStruct, ()>);
+                    fn another_function () -> Result<LargeStruct, ()>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function(
+                    arg: *mut __swift_bridge__Result_11LargeStructAndVoid
+                ) {
+                    super::some_function({
+                        let val = unsafe { swift_bridge::result::unbox_result_struct(arg) };
+                        if val.is_ok {
+                            std::result::Result::Ok({
+                                let ok = unsafe { val.ok.assume_init() };
+                                ok.into_rust_repr()
+                            })
+                        } else {
+                            std::result::Result::Err(())
+                        }
+                    })
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$another_function"]
+                pub extern "C" fn __swift_bridge__another_function(
+                ) -> *mut __swift_bridge__Result_11LargeStructAndVoid {
+                    swift_bridge::result::box_result_struct(match super::another_function() {
+                        Ok(ok) => __swift_bridge__Result_11LargeStructAndVoid {
+                            is_ok: true,
+                            ok: std::mem::MaybeUninit::new(ok.into_ffi_repr()),
+                        },
+                        Err(err) => __swift_bridge__Result_11LargeStructAndVoid {
+                            is_ok: false,
+                            ok: std::mem::MaybeUninit::uninit(),
+                        }
+                    })
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+func some_function(_ arg: RustResult<LargeStruct, ()>) {
+    __swift_bridge__$some_function({ var ffiResult = __swift_bridge__$Result$11LargeStructAndVoid(); switch arg { case .Ok(let ok): ffiResult.is_ok = true; ffiResult.ok = ok.intoFfiRepr(); case .Err(_): ffiResult.is_ok = false; }; let ptr = __swift_bridge__$Result$_alloc(UInt(MemoryLayout<__swift_bridge__$Result$11LargeStructAndVoid>.stride), UInt(MemoryLayout<__swift_bridge__$Result$11LargeStructAndVoid>.alignment))!.bindMemory(to: __swift_bridge__$Result$11LargeStructAndVoid.self, capacity: 1); ptr.initialize(to: ffiResult); return ptr }())
+}
+"#,
+            r#"
+func another_function() -> RustResult<LargeStruct, ()> {
+    { let ptr: UnsafeMutablePointer<__swift_bridge__$Result$11LargeStructAndVoid> = __swift_bridge__$another_function(); let val = ptr.pointee; __swift_bridge__$Result$_free(ptr, UInt(MemoryLayout<__swift_bridge__$Result$11LargeStructAndVoid>.stride), UInt(MemoryLayout<__swift_bridge__$Result$11LargeStructAndVoid>.alignment)); if val.is_ok { return RustResult<LargeStruct, ()>.Ok(val.ok.intoSwiftRepr()) } else { return RustResult<LargeStruct, ()>.Err(()) } }()
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "typedef struct __swift_bridge__$Result$11LargeStructAndVoid { bool is_ok; struct __swift_bridge__$LargeStruct ok; } __swift_bridge__$Result$11LargeStructAndVoid;",
+            "void __swift_bridge__$some_function(struct __swift_bridge__$Result$11LargeStructAndVoid* arg);",
+            "struct __swift_bridge__$Result$11LargeStructAndVoid* __swift_bridge__$another_function(void);",
+        ])
+    }
+
+    #[test]
+    fn extern_rust_fn_result_large_shared_struct() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Test code generation for a Swift function that returns a Result<T, E> where T is a shared
+/// struct.
+/// The call to Swift only happens once, even though the result's fields get read separately.
+mod extern_swift_fn_return_result_shared_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u8
+                }
+
+                extern "Swift" {
+                    fn some_function () -> Result<SomeStruct, ()>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub fn some_function() -> Result<SomeStruct, ()> {
+                {
+                    let val = unsafe { __swift_bridge__some_function() };
+                    if val.is_ok {
+                        std::result::Result::Ok({
+                            let ok = unsafe { val.ok.assume_init() };
+                            ok.into_rust_repr()
+                        })
+                    } else {
+                        std::result::Result::Err(())
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn extern_swift_fn_return_result_shared_struct() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that the struct of a result that holds a shared struct is named using the shared
+/// struct's Swift name, which also names its C struct, and using the full path of other types.
+mod result_struct_name {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", swift_name = "RenamedStruct")]
+                struct SomeStruct {
+                    field: u8
+                }
+
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function (arg: Result<SomeStruct, SomeType>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub struct __swift_bridge__Result_13RenamedStructAnd5super8SomeType
+        })
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            "void __swift_bridge__$some_function(struct __swift_bridge__$Result$13RenamedStructAnd5super8SomeType arg);",
+        )
+    }
+
+    #[test]
+    fn result_struct_name() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Test code generation for Rust function that accepts a Result<T, E> where T is a primitive.
+/// The result gets passed as a `ResultBits64` instead of needing a struct for the combination.
+mod extern_rust_fn_arg_result_primitive_and_string {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function (arg: Result<u32, String>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::result::ResultBits64
+            ) {
+                super::some_function(
+                    if arg.is_ok {
+                        std::result::Result::Ok({
+                            let ok: u32 = swift_bridge::result::ResultBits::from_bits(
+                                arg.ok_or_err
+                            );
+                            ok
+                        })
+                    } else {
+                        std::result::Result::Err({
+                            let err: *mut swift_bridge::string::RustString =
+                                swift_bridge::result::ResultBits::from_bits(arg.ok_or_err);
+                            unsafe { swift_bridge::allocator::take_from_raw(err).0 }
+                        })
+                    }
+                )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func some_function<GenericIntoRustString: IntoRustString>(_ arg: RustResult<UInt32, GenericIntoRustString>) {
+    __swift_bridge__$some_function({ switch arg { case .Ok(let ok): return __private__ResultBits64(is_ok: true, ok_or_err: __private__toResultBits(ok)) case .Err(let err): return __private__ResultBits64(is_ok: false, ok_or_err: __private__toResultBits({ let rustString = err.intoRustString(); rustString.isOwned = false; return rustString.ptr }())) } }())
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(
+        r#"
+void __swift_bridge__$some_function(struct __private__ResultBits64 arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_arg_result_primitive_and_string() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Test code generation for Rust function that returns a Result<T, E> where T is an opaque Rust
+/// type and E is a primitive.
+mod extern_rust_fn_return_result_opaque_rust_and_primitive {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function () -> Result<SomeType, u8>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() -> swift_bridge::result::ResultBits64 {
+                match super::some_function() {
+                    Ok(ok) => swift_bridge::result::ResultBits64 {
+                        is_ok: true,
+                        ok_or_err: swift_bridge::result::ResultBits::into_bits(
+                            swift_bridge::allocator::box_into_raw(ok) as *mut super::SomeType
+                        )
+                    },
+                    Err(err) => swift_bridge::result::ResultBits64 {
+                        is_ok: false,
+                        ok_or_err: swift_bridge::result::ResultBits::into_bits(err)
+                    }
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() -> RustResult<SomeType, UInt8> {
+    { let val = __swift_bridge__$some_function(); if val.is_ok { return RustResult<SomeType, UInt8>.Ok(SomeType(ptr: (__private__fromResultBits(val.ok_or_err) as UnsafeMutableRawPointer))) } else { return RustResult<SomeType, UInt8>.Err((__private__fromResultBits(val.ok_or_err) as UInt8)) } }()
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+struct __private__ResultBits64 __swift_bridge__$some_function(void);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_return_result_opaque_rust_and_primitive() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Test code generation for Rust function that accepts a Result<T, E> where T is `()`.
+/// We don't read the `ok_or_err` pointer for the `()`.
+mod extern_rust_fn_arg_result_null_and_opaque_rust {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function (arg: Result<(), SomeType>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: swift_bridge::result::ResultPtrAndPtr
+            ) {
+                super::some_function(
+                    if arg.is_ok {
+                        std::result::Result::Ok(())
+                    } else {
+                        std::result::Result::Err(unsafe { swift_bridge::allocator::take_from_raw(arg.ok_or_err as *mut super::SomeType) })
+                    }
+                )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func some_function(_ arg: RustResult<(), SomeType>) {
+    __swift_bridge__$some_function({ switch arg { case .Ok(_): return __private__ResultPtrAndPtr(is_ok: true, ok_or_err: nil) case .Err(let err): return __private__ResultPtrAndPtr(is_ok: false, ok_or_err: {err.isOwned = false; return err.ptr;}()) } }())
+}
+"#,
+        )
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void __swift_bridge__$some_function(struct __private__ResultPtrAndPtr arg);
+    "#,
+    );
+
+    #[test]
+    fn extern_rust_fn_arg_result_null_and_opaque_rust() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
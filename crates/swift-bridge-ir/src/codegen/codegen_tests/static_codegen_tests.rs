@@ -0,0 +1,349 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate code for a static whose type is an opaque Rust type.
+mod opaque_rust_type_static {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Registry;
+
+                    static SHARED: Registry;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SHARED"]
+            pub extern "C" fn __swift_bridge__SHARED() -> *const super::Registry {
+                let value: &'static super::Registry = &super::SHARED;
+                value as *const super::Registry
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public let SHARED: RegistryRef = RegistryRef(ptr: __swift_bridge__$SHARED())
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SHARED(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn opaque_rust_type_static() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate code for a static whose type is a primitive.
+mod primitive_static {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    static STARTUP_TIME: u64;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$STARTUP_TIME"]
+            pub extern "C" fn __swift_bridge__STARTUP_TIME() -> u64 {
+                let value: &'static u64 = &super::STARTUP_TIME;
+                *value
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public let STARTUP_TIME: UInt64 = __swift_bridge__$STARTUP_TIME()
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+uint64_t __swift_bridge__$STARTUP_TIME(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn primitive_static() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we can expose a static as a static property on a type.
+mod associated_to_static {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Registry;
+
+                    /// The shared registry.
+                    #[swift_bridge(associated_to = Registry)]
+                    static SHARED: Registry;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension Registry {
+    /// The shared registry.
+    public static let SHARED: RegistryRef = RegistryRef(ptr: __swift_bridge__$SHARED())
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn associated_to_static() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a static's cfg attributes are used in the generated code.
+mod static_with_cfg {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[cfg(target_os = "macos")]
+                    static IS_SANDBOXED: bool;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[cfg(target_os = "macos")]
+            #[export_name = "__swift_bridge__$IS_SANDBOXED"]
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+#if os(macOS)
+public let IS_SANDBOXED: Bool = __swift_bridge__$IS_SANDBOXED()
+#endif
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+#if (__is_target_os(macos))
+bool __swift_bridge__$IS_SANDBOXED(void);
+#endif
+"#,
+        )
+    }
+
+    #[test]
+    fn static_with_cfg() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a function for each of an atomic static's operations, and a Swift enum
+/// that calls them.
+mod atomic_static {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    /// The number of requests sent so far.
+                    static REQUEST_COUNT: std::sync::atomic::AtomicU32;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$REQUEST_COUNT$load"]
+                pub extern "C" fn __swift_bridge__REQUEST_COUNT_load() -> u32 {
+                    let atomic: &'static std::sync::atomic::AtomicU32 = &super::REQUEST_COUNT;
+                    atomic.load(std::sync::atomic::Ordering::Acquire)
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$REQUEST_COUNT$store"]
+                pub extern "C" fn __swift_bridge__REQUEST_COUNT_store(value: u32) {
+                    let atomic: &'static std::sync::atomic::AtomicU32 = &super::REQUEST_COUNT;
+                    atomic.store(value, std::sync::atomic::Ordering::Release)
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$REQUEST_COUNT$fetch_add"]
+                pub extern "C" fn __swift_bridge__REQUEST_COUNT_fetch_add(value: u32) -> u32 {
+                    let atomic: &'static std::sync::atomic::AtomicU32 = &super::REQUEST_COUNT;
+                    atomic.fetch_add(value, std::sync::atomic::Ordering::AcqRel)
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+/// The number of requests sent so far.
+public enum REQUEST_COUNT {
+    /// Atomically loads the value.
+    public static func load() -> UInt32 { __swift_bridge__$REQUEST_COUNT$load() }
+    /// Atomically stores the value.
+    public static func store(_ value: UInt32) { __swift_bridge__$REQUEST_COUNT$store(value) }
+    /// Atomically stores the value, returning the previous value.
+    @discardableResult
+    public static func swap(_ value: UInt32) -> UInt32 { __swift_bridge__$REQUEST_COUNT$swap(value) }
+    /// Atomically adds to the value, wrapping around on overflow, and returns the previous value.
+    @discardableResult
+    public static func fetchAdd(_ value: UInt32) -> UInt32 { __swift_bridge__$REQUEST_COUNT$fetch_add(value) }
+    /// Atomically subtracts from the value, wrapping around on overflow, and returns the previous value.
+    @discardableResult
+    public static func fetchSub(_ value: UInt32) -> UInt32 { __swift_bridge__$REQUEST_COUNT$fetch_sub(value) }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+uint32_t __swift_bridge__$REQUEST_COUNT$load(void);
+void __swift_bridge__$REQUEST_COUNT$store(uint32_t value);
+uint32_t __swift_bridge__$REQUEST_COUNT$swap(uint32_t value);
+uint32_t __swift_bridge__$REQUEST_COUNT$fetch_add(uint32_t value);
+uint32_t __swift_bridge__$REQUEST_COUNT$fetch_sub(uint32_t value);
+"#,
+        )
+    }
+
+    #[test]
+    fn atomic_static() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that an `AtomicBool` static can't be added to, and that an atomic static can be exposed
+/// on a type.
+mod atomic_bool_static {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type Downloader;
+
+                    #[swift_bridge(associated_to = Downloader)]
+                    static IS_PAUSED: AtomicBool;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension Downloader {
+    public enum IS_PAUSED {
+        /// Atomically loads the value.
+        public static func load() -> Bool { __swift_bridge__$IS_PAUSED$load() }
+        /// Atomically stores the value.
+        public static func store(_ value: Bool) { __swift_bridge__$IS_PAUSED$store(value) }
+        /// Atomically stores the value, returning the previous value.
+        @discardableResult
+        public static func swap(_ value: Bool) -> Bool { __swift_bridge__$IS_PAUSED$swap(value) }
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+bool __swift_bridge__$IS_PAUSED$swap(bool value);
+"#,
+        )
+    }
+
+    #[test]
+    fn atomic_bool_static() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::DoesNotContain(quote! { fetch_add }),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
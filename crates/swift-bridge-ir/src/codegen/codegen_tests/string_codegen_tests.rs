@@ -22,7 +22,7 @@ mod extern_rust_fn_with_owned_string_argument {
             pub extern "C" fn __swift_bridge__some_function(
                 arg: *mut swift_bridge::string::RustString
             ) {
-                super::some_function(unsafe { Box::from_raw(arg).0 })
+                super::some_function(unsafe { swift_bridge::allocator::take_from_raw(arg).0 })
             }
         })
     }
@@ -114,6 +114,41 @@ void __swift_bridge__$some_function(struct RustStr arg);
     }
 }
 
+/// Verify that the generic bounds for a Rust function that takes both a String and a &str are
+/// always emitted in the same order, so that the generated code is reproducible.
+mod extern_rust_fn_with_string_and_str_arguments {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    fn some_function (arg1: &str, arg2: String);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func some_function<GenericIntoRustString: IntoRustString, GenericToRustStr: ToRustStr>(_ arg1: GenericToRustStr, _ arg2: GenericIntoRustString) {
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_with_string_and_str_arguments() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
 /// Test code generation for Rust function that returns an owned String argument.
 mod extern_rust_fn_returns_string {
     use super::*;
@@ -234,7 +269,7 @@ mod extern_swift_func_returns_string {
         ExpectedRustTokens::Contains(quote! {
             pub fn some_function () -> String {
                 unsafe {
-                    Box::from_raw(unsafe { __swift_bridge__some_function() }).0
+                    swift_bridge::allocator::take_from_raw(unsafe { __swift_bridge__some_function() }).0
                 }
             }
         })
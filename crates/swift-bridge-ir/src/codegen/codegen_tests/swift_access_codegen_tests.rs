@@ -0,0 +1,182 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a module level `swift_access` attribute sets the access level of the module's
+/// generated Swift classes and functions.
+mod module_swift_access {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(swift_access = "internal")]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_method(&self) -> u8;
+                    fn some_function();
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+internal class SomeType: SomeTypeRefMut {
+"#,
+            r#"
+internal class SomeTypeRefMut: SomeTypeRef {
+"#,
+            r#"
+internal class SomeTypeRef {
+"#,
+            r#"
+extension SomeTypeRef {
+    internal func some_method() -> UInt8 {
+"#,
+            r#"
+internal func some_function() {
+"#,
+        ])
+    }
+
+    #[test]
+    fn module_swift_access() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that an opaque type's `swift_access` attribute sets the access level of the type's
+/// Swift classes and is inherited by the type's methods.
+mod opaque_type_swift_access {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(swift_access = "package")]
+                    type SomeType;
+
+                    #[swift_bridge(init)]
+                    fn new() -> SomeType;
+                    fn some_method(&self);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+package class SomeType: SomeTypeRefMut {
+"#,
+            r#"
+    package convenience init() {
+"#,
+            r#"
+    package func some_method() {
+"#,
+        ])
+    }
+
+    #[test]
+    fn opaque_type_swift_access() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that an opaque Copy type's `swift_access` attribute sets the access level of the
+/// type's Swift struct.
+mod opaque_copy_type_swift_access {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4), swift_access = "internal")]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+internal struct SomeType {
+"#,
+        )
+    }
+
+    #[test]
+    fn opaque_copy_type_swift_access() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a function's `swift_access` attribute overrides the access level that it
+/// would otherwise inherit from its type or module.
+mod function_swift_access {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(swift_access = "internal")]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(swift_access = "fileprivate")]
+                    fn some_method(&self);
+
+                    #[swift_bridge(swift_access = "public")]
+                    fn some_function();
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    fileprivate func some_method() {
+"#,
+            r#"
+public func some_function() {
+"#,
+        ])
+    }
+
+    #[test]
+    fn function_swift_access() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
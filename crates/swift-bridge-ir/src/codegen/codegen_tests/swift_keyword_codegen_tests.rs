@@ -0,0 +1,157 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that extern "Rust" functions and arguments whose names are Swift keywords get escaped
+/// in the generated Swift.
+mod extern_rust_fn_swift_keyword_names {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn default(class: u8, protocol: &str);
+                    fn repeat(&self, var: u8);
+                    #[swift_bridge(swift_property)]
+                    fn subscript(&self) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func `default`<GenericToRustStr: ToRustStr>(_ class_: UInt8, _ protocol_: GenericToRustStr) {
+    protocol_.toRustStr({ protocol_AsRustStr in
+        __swift_bridge__$default(class_, protocol_AsRustStr)
+    })
+}
+"#,
+            r#"
+    public func `repeat`(_ var_: UInt8) {
+        __swift_bridge__$SomeType$repeat(ptr, var_)
+    }
+"#,
+            r#"
+    public var `subscript`: UInt8 {
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_fn_swift_keyword_names() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we call extern "Swift" functions whose names are Swift keywords using their
+/// escaped names, and that arguments named after keywords keep their argument labels.
+mod extern_swift_fn_swift_keyword_names {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn defer(class: u8, inout: u16) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$defer")
+func __swift_bridge__defer (_ class_: UInt8, _ inout_: UInt16) -> UInt8 {
+    `defer`(class: class_, `inout`: inout_)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_fn_swift_keyword_names() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that struct fields and enum variants whose names are Swift keywords get escaped, and
+/// that types whose names are Swift keywords get renamed.
+mod shared_and_opaque_types_swift_keyword_names {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct Options {
+                    class: u8,
+                }
+
+                enum Kind {
+                    Type,
+                }
+
+                extern "Rust" {
+                    type Protocol;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public struct Options {
+    public var `class`: UInt8
+
+    public init(`class`: UInt8) {
+        self.`class` = `class`
+    }
+"#,
+            r#"
+public enum Kind {
+    case `Type`
+}
+"#,
+            r#"
+            case Kind.`Type`:
+                return __swift_bridge__$Kind(tag: __swift_bridge__$Kind$Type)
+"#,
+            r#"
+public class Protocol_: Protocol_RefMut {
+"#,
+            r#"
+            __swift_bridge__$Protocol$_free(ptr)
+"#,
+        ])
+    }
+
+    #[test]
+    fn shared_and_opaque_types_swift_keyword_names() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
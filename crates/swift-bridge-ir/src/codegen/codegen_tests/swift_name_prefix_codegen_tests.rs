@@ -0,0 +1,122 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a module level `swift_name_prefix` attribute is added to the Swift names of the
+/// module's opaque Rust types and free functions.
+mod opaque_type_and_function_swift_name_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(swift_name_prefix = "MC")]
+            mod ffi {
+                extern "Rust" {
+                    type Client;
+
+                    #[swift_bridge(init)]
+                    fn new() -> Client;
+                    fn send(&self);
+                    fn connect() -> Client;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func MCconnect() -> MCClient {
+    MCClient(ptr: __swift_bridge__$connect())
+}
+"#,
+            r#"
+public class MCClient: MCClientRefMut {
+"#,
+            r#"
+extension MCClient {
+    public convenience init() {
+        self.init(ptr: __swift_bridge__$Client$new())
+    }
+}
+"#,
+            r#"
+extension MCClientRef {
+    public func send() {
+        __swift_bridge__$Client$send(ptr)
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn opaque_type_and_function_swift_name_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a module level `swift_name_prefix` attribute is added to the Swift names of the
+/// module's shared structs and enums, and that items with their own `swift_name` keep it.
+mod shared_type_swift_name_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(swift_name_prefix = "MC")]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct Config {
+                    retries: u8,
+                }
+
+                enum Level {
+                    Low,
+                    High,
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(swift_name = "Session")]
+                    type RustSession;
+
+                    #[swift_bridge(swift_name = "makeConfig")]
+                    fn make_config() -> Config;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public struct MCConfig {
+"#,
+            r#"
+public enum MCLevel {
+"#,
+            r#"
+public class Session: SessionRefMut {
+"#,
+            r#"
+public func makeConfig() -> MCConfig {
+"#,
+        ])
+    }
+
+    #[test]
+    fn shared_type_swift_name_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
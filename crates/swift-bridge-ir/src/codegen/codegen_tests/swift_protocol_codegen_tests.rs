@@ -0,0 +1,143 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate a Swift protocol for a `#[swift_bridge(protocol)]` type, and that we
+/// call its methods through the protocol.
+mod swift_protocol_methods {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Swift" {
+                    /// Receives download events.
+                    #[swift_bridge(protocol)]
+                    type DownloadDelegate;
+
+                    /// Called as the download makes progress.
+                    fn on_progress(&self, percent: u8);
+                    #[swift_bridge(argument_labels(attempt = "after"))]
+                    fn should_retry(&self, attempt: u32) -> bool;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[repr(C)]
+            pub struct DownloadDelegate(*mut std::ffi::c_void);
+
+            impl DownloadDelegate {
+                pub fn on_progress(&self, percent: u8) {
+                    unsafe { __swift_bridge__DownloadDelegate_on_progress(swift_bridge::PointerToSwiftType(self.0), percent) }
+                }
+
+                pub fn should_retry(&self, attempt: u32) -> bool {
+                    unsafe { __swift_bridge__DownloadDelegate_should_retry(swift_bridge::PointerToSwiftType(self.0), attempt) }
+                }
+            }
+
+            impl Drop for DownloadDelegate {
+                fn drop (&mut self) {
+                    unsafe { __swift_bridge__DownloadDelegate__free(self.0) }
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@_cdecl("__swift_bridge__$DownloadDelegate$on_progress")
+func __swift_bridge__DownloadDelegate_on_progress (_ this: UnsafeMutableRawPointer, _ percent: UInt8) {
+    (Unmanaged<AnyObject>.fromOpaque(this).takeUnretainedValue() as! DownloadDelegate).on_progress(percent: percent)
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$DownloadDelegate$should_retry")
+func __swift_bridge__DownloadDelegate_should_retry (_ this: UnsafeMutableRawPointer, _ attempt: UInt32) -> Bool {
+    (Unmanaged<AnyObject>.fromOpaque(this).takeUnretainedValue() as! DownloadDelegate).should_retry(after: attempt)
+}
+"#,
+            r#"
+/// Receives download events.
+public protocol DownloadDelegate: AnyObject {
+    /// Called as the download makes progress.
+    func on_progress(percent: UInt8)
+    func should_retry(after attempt: UInt32) -> Bool
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$DownloadDelegate$_free")
+func __swift_bridge__DownloadDelegate__free (ptr: UnsafeMutableRawPointer) {
+    let _ = Unmanaged<AnyObject>.fromOpaque(ptr).takeRetainedValue()
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn swift_protocol_methods() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we pass a `#[swift_bridge(protocol)]` value between Swift and Rust as a retained
+/// `AnyObject`.
+mod swift_protocol_passed_to_and_from_rust {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(protocol)]
+                    type DownloadDelegate;
+                }
+
+                extern "Rust" {
+                    fn set_delegate(delegate: DownloadDelegate);
+                    fn take_delegate() -> DownloadDelegate;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func set_delegate(_ delegate: DownloadDelegate) {
+    __swift_bridge__$set_delegate(Unmanaged<AnyObject>.passRetained(delegate).toOpaque())
+}
+"#,
+            r#"
+public func take_delegate() -> DownloadDelegate {
+    (Unmanaged<AnyObject>.fromOpaque(__swift_bridge__$take_delegate()).takeRetainedValue() as! DownloadDelegate)
+}
+"#,
+            r#"
+public protocol DownloadDelegate: AnyObject {
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn swift_protocol_passed_to_and_from_rust() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,466 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that a module level `symbol_prefix` attribute replaces the `__swift_bridge__` prefix
+/// of the module's FFI symbols in the generated Rust, Swift and C code.
+mod module_symbol_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod ffi {
+                extern "Rust" {
+                    fn rust_function();
+                }
+
+                extern "Swift" {
+                    fn swift_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsManyAndDoesNotContainMany {
+            contains: vec![
+                quote! {
+                    #[export_name = "__my_crate__$rust_function"]
+                    pub extern "C" fn __swift_bridge__rust_function() {
+                        super::rust_function()
+                    }
+                },
+                quote! {
+                    #[link_name = "__my_crate__$swift_function"]
+                    fn __swift_bridge__swift_function();
+                },
+            ],
+            does_not_contain: vec![
+                quote! {
+                    #[export_name = "__swift_bridge__$rust_function"]
+                },
+                quote! {
+                    #[link_name = "__swift_bridge__$swift_function"]
+                },
+            ],
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func rust_function() {
+    __my_crate__$rust_function()
+}
+"#,
+            r#"
+@_cdecl("__my_crate__$swift_function")
+func __swift_bridge__swift_function () {
+    swift_function()
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __my_crate__$rust_function(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn module_symbol_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the `symbol_prefix` attribute is also used for the symbols and C types of the
+/// module's opaque types.
+mod opaque_type_symbol_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_method(&self);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::DoesNotContainAfterTrim("__swift_bridge__$")
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "void __my_crate__$SomeType$_free(void* self);",
+            "void __my_crate__$SomeType$some_method(void* self);",
+        ])
+    }
+
+    #[test]
+    fn opaque_type_symbol_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the structs that by-value results are passed as get the module's symbol prefix,
+/// while results that are passed using the `swift_bridge` runtime's types keep their core names.
+mod result_symbol_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod ffi {
+                struct SomeStruct {
+                    field: u8,
+                }
+
+                extern "Rust" {
+                    type SomeType;
+
+                    fn by_value_result() -> Result<SomeStruct, SomeType>;
+                    fn pointer_result() -> Result<String, SomeType>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__my_crate__$by_value_result"]
+                pub extern "C" fn __swift_bridge__by_value_result() -> __swift_bridge__Result_10SomeStructAnd5super8SomeType
+            },
+            quote! {
+                #[export_name = "__my_crate__$pointer_result"]
+                pub extern "C" fn __swift_bridge__pointer_result() -> swift_bridge::result::ResultPtrAndPtr
+            },
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "typedef struct __my_crate__$Result$10SomeStructAnd5super8SomeType { bool is_ok; struct __my_crate__$SomeStruct ok; void* err; } __my_crate__$Result$10SomeStructAnd5super8SomeType;",
+            "struct __my_crate__$Result$10SomeStructAnd5super8SomeType __my_crate__$by_value_result(void);",
+            "struct __private__ResultPtrAndPtr __my_crate__$pointer_result(void);",
+        ])
+    }
+
+    #[test]
+    fn result_symbol_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the symbol prefix isn't used for the symbols of the `swift_bridge` runtime, which
+/// are declared in the core header instead of the module's header.
+mod core_symbols_keep_swift_bridge_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: String) -> Vec<u8>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__my_crate__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: *mut swift_bridge::string::RustString
+            ) -> *mut Vec<u8>
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function<GenericIntoRustString: IntoRustString>(_ arg: GenericIntoRustString) -> RustVec<UInt8> {
+    RustVec(ptr: __my_crate__$some_function({ let rustString = arg.intoRustString(); rustString.isOwned = false; return rustString.ptr }()))
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __my_crate__$some_function(void* arg);
+"#,
+        )
+    }
+
+    #[test]
+    fn core_symbols_keep_swift_bridge_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a module with a symbol prefix still calls the `Option<u8>` constructors that are
+/// declared in the core C header using their `__swift_bridge__` names.
+mod option_primitive_arg_symbol_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: Option<u8>);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: Optional<UInt8>) {
+    __my_crate__$some_function({ if let val = arg { return __swift_bridge__$OptionU8$some(val) } else { return __swift_bridge__$OptionU8$none() } }())
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __my_crate__$some_function(struct __private__OptionU8 arg);
+"#,
+        )
+    }
+
+    #[test]
+    fn option_primitive_arg_symbol_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a cancellable async function in a module with a symbol prefix uses the prefix for
+/// its own symbol but still calls the async cancellation functions that are declared in the core
+/// C header using their `__swift_bridge__` names.
+mod cancellable_async_fn_symbol_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(cancellable)]
+                    async fn some_function() -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__my_crate__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, u8) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+                cancellation: *const swift_bridge::async_support::AsyncCancellation,
+            )
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    let cancellation = __swift_bridge__$AsyncCancellation$new()
+    defer { __swift_bridge__$AsyncCancellation$free(cancellation) }
+"#,
+            r#"
+            __my_crate__$some_function(wrapperPtr, onComplete, onCancelled, cancellation)
+"#,
+            r#"
+    }, onCancel: {
+        __swift_bridge__$AsyncCancellation$cancel(cancellation)
+    })
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __my_crate__$some_function(void* callback_wrapper, void __my_crate__$some_function$async(void* callback_wrapper, uint8_t ret), void __my_crate__$some_function$cancel(void* callback_wrapper), void* cancellation);
+"#,
+        )
+    }
+
+    #[test]
+    fn cancellable_async_fn_symbol_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the Swift class that wraps a Rust `FnOnce` callback calls and frees the callback
+/// using the module's prefixed symbols.
+mod boxed_fn_once_symbol_prefix {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod ffi {
+                extern "Swift" {
+                    fn some_function(callback: Box<dyn FnOnce(u8)>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__my_crate__$some_function$param0"]
+            },
+            quote! {
+                #[export_name = "__my_crate__$some_function$_free$param0"]
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    deinit {
+        if !called {
+            __my_crate__$some_function$_free$param0(ptr)
+        }
+    }
+"#,
+            r#"
+        return __my_crate__$some_function$param0(ptr, arg0)
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __my_crate__$some_function$param0(void* some_function_callback, uint8_t arg0);
+void __my_crate__$some_function$_free$param0(void* some_function_callback);
+"#,
+        )
+    }
+
+    #[test]
+    fn boxed_fn_once_symbol_prefix() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the configured `private_type_prefix` names the C types that the generated code
+/// shares with the core header, and that the module's own symbols keep their prefix.
+mod private_type_prefix {
+    use crate::test_utils::parse_ok;
+    use crate::CodegenConfig;
+    use quote::quote;
+
+    #[test]
+    fn private_type_prefix() {
+        let mut module = parse_ok(quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn option_arg(arg: Option<u8>);
+                    fn u128_return() -> u128;
+                    fn slice_return() -> &'static [u8];
+                    fn bits_result() -> Result<u8, u16>;
+                    fn pointer_result() -> Result<String, String>;
+                }
+
+                extern "Swift" {
+                    fn with_callback(callback: Box<dyn FnOnce(u8)>);
+                    fn with_no_args_callback(callback: Box<dyn FnOnce()>);
+                }
+            }
+        });
+        module.set_private_type_prefix("__my_crate__private__");
+
+        let generated =
+            module.generate_swift_code_and_c_header(CodegenConfig::no_features_enabled());
+        let c_header = generated.c_header;
+        for declaration in [
+            "void __swift_bridge__$option_arg(struct __my_crate__private__OptionU8 arg);",
+            "struct __my_crate__private__U128 __swift_bridge__$u128_return(void);",
+            "struct __my_crate__private__FfiSlice __swift_bridge__$slice_return(void);",
+            "struct __my_crate__private__ResultBits64 __swift_bridge__$bits_result(void);",
+            "struct __my_crate__private__ResultPtrAndPtr __swift_bridge__$pointer_result(void);",
+        ] {
+            assert!(c_header.contains(declaration), "{}", c_header);
+        }
+
+        let swift = generated.swift;
+        for code in [
+            "class __my_crate__private__RustFnOnceCallback$with_callback$param0 {",
+            "let cb0 = __my_crate__private__RustFnOnceCallback$with_callback$param0(ptr: callback);",
+            "let cb0 = __my_crate__private__RustFnOnceCallbackNoArgsNoRet(ptr: callback);",
+        ] {
+            assert!(swift.contains(code), "{}", swift);
+        }
+
+        assert!(!c_header.contains("struct __private__"), "{}", c_header);
+        assert!(!swift.contains(" __private__"), "{}", swift);
+    }
+}
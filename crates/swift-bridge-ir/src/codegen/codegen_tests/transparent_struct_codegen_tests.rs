@@ -702,3 +702,121 @@ struct __swift_bridge__$Option$SomeStruct __swift_bridge__$some_function(struct
         .test();
     }
 }
+
+/// Verify that a `#[repr(C, packed)]` shared struct gets packed on the Rust side and is wrapped in
+/// `#pragma pack` in the C header.
+mod shared_struct_repr_packed {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[repr(C, packed)]
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field_a: u8,
+                    field_b: u32
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[repr(C, packed(1))]
+            pub struct SomeStruct {
+                pub field_a: u8,
+                pub field_b: u32
+            }
+
+            #[repr(C, packed(1))]
+            #[doc(hidden)]
+            pub struct __swift_bridge__SomeStruct {
+                field_a: u8,
+                field_b: u32
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public struct SomeStruct {
+    public var field_a: UInt8
+    public var field_b: UInt32
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim(
+            r#"
+#include <stdint.h>
+#include <stdbool.h>
+#pragma pack(push, 1)
+typedef struct __swift_bridge__$SomeStruct { uint8_t field_a; uint32_t field_b; } __swift_bridge__$SomeStruct;
+#pragma pack(pop)
+typedef struct __swift_bridge__$Option$SomeStruct { bool is_some; __swift_bridge__$SomeStruct val; } __swift_bridge__$Option$SomeStruct;
+    "#,
+        )
+    }
+
+    #[test]
+    fn shared_struct_repr_packed() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a `#[repr(C, align(N))]` shared struct gets aligned on the Rust side and that the
+/// C header uses `_Alignas` on its first field.
+mod shared_struct_repr_align {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[repr(C, align(16))]
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct(u8, u16);
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[repr(C, align(16))]
+            pub struct SomeStruct(pub u8, pub u16);
+
+            #[repr(C, align(16))]
+            #[doc(hidden)]
+            pub struct __swift_bridge__SomeStruct(u8, u16);
+        })
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+typedef struct __swift_bridge__$SomeStruct { _Alignas(16) uint8_t _0; uint16_t _1; } __swift_bridge__$SomeStruct;
+    "#,
+        )
+    }
+
+    #[test]
+    fn shared_struct_repr_align() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
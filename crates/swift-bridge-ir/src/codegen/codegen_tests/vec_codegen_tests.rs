@@ -25,65 +25,73 @@ mod extern_rust_type_vec_support {
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$new"]
                 pub extern "C" fn _new() -> *mut Vec<super::MyRustType> {
-                    Box::into_raw(Box::new(Vec::new()))
+                    swift_bridge::vec_support::new()
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$drop"]
                 pub extern "C" fn _drop(vec: *mut Vec<super::MyRustType>) {
-                    let vec = unsafe { Box::from_raw(vec) };
-                    drop(vec)
+                    unsafe { swift_bridge::vec_support::free(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$len"]
                 pub extern "C" fn _len(vec: *const Vec<super::MyRustType>) -> usize {
-                    unsafe { &*vec }.len()
+                    unsafe { swift_bridge::vec_support::len(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$get"]
                 pub extern "C" fn _get(vec: *const Vec<super::MyRustType>, index: usize) -> *const super::MyRustType {
-                    let vec = unsafe { & *vec };
-                    if let Some(val) = vec.get(index) {
-                        val as *const super::MyRustType
-                    } else {
-                        std::ptr::null()
-                    }
+                    unsafe { swift_bridge::vec_support::get(vec, index) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$get_mut"]
                 pub extern "C" fn _get_mut(vec: *mut Vec<super::MyRustType>, index: usize) -> *mut super::MyRustType {
-                    let vec = unsafe { &mut *vec };
-                    if let Some(val) = vec.get_mut(index) {
-                        val as *mut super::MyRustType
-                    } else {
-                        std::ptr::null::<super::MyRustType>() as *mut super::MyRustType
-                    }
+                    unsafe { swift_bridge::vec_support::get_mut(vec, index) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$push"]
                 pub extern "C" fn _push(vec: *mut Vec<super::MyRustType>, val: *mut super::MyRustType) {
-                    unsafe { &mut *vec }.push(unsafe { *Box::from_raw(val) })
+                    unsafe { swift_bridge::vec_support::push_boxed(vec, val) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$pop"]
                 pub extern "C" fn _pop(vec: *mut Vec<super::MyRustType>) -> *mut super::MyRustType {
-                    let vec = unsafe { &mut *vec };
-                    if let Some(val) = vec.pop() {
-                        Box::into_raw(Box::new(val))
-                    } else {
-                        std::ptr::null::<super::MyRustType>() as *mut super::MyRustType
-                    }
+                    unsafe { swift_bridge::vec_support::pop_boxed(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_MyRustType$as_ptr"]
                 pub extern "C" fn _as_ptr(vec: *const Vec<super::MyRustType>) -> *const super::MyRustType {
-                    unsafe { & *vec }.as_ptr()
+                    unsafe { swift_bridge::vec_support::as_ptr(vec) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_MyRustType$insert"]
+                pub extern "C" fn _insert(vec: *mut Vec<super::MyRustType>, index: usize, val: *mut super::MyRustType) {
+                    unsafe { swift_bridge::vec_support::insert_boxed(vec, index, val) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_MyRustType$remove"]
+                pub extern "C" fn _remove(vec: *mut Vec<super::MyRustType>, index: usize) -> *mut super::MyRustType {
+                    unsafe { swift_bridge::vec_support::remove_boxed(vec, index) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_MyRustType$reserve"]
+                pub extern "C" fn _reserve(vec: *mut Vec<super::MyRustType>, additional: usize) {
+                    unsafe { swift_bridge::vec_support::reserve(vec, additional) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_MyRustType$capacity"]
+                pub extern "C" fn _capacity(vec: *const Vec<super::MyRustType>) -> usize {
+                    unsafe { swift_bridge::vec_support::capacity(vec) }
                 }
             };
         })
@@ -135,6 +143,22 @@ extension MyRustType: Vectorizable {
     public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {
         __swift_bridge__$Vec_MyRustType$len(vecPtr)
     }
+
+    public static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: MyRustType) {
+        __swift_bridge__$Vec_MyRustType$insert(vecPtr, index, {value.isOwned = false; return value.ptr;}())
+    }
+
+    public static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self {
+        (MyRustType(ptr: __swift_bridge__$Vec_MyRustType$remove(vecPtr, index)) as! Self)
+    }
+
+    public static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt) {
+        __swift_bridge__$Vec_MyRustType$reserve(vecPtr, additional)
+    }
+
+    public static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt {
+        __swift_bridge__$Vec_MyRustType$capacity(vecPtr)
+    }
 }
 "#,
         )
@@ -154,6 +178,10 @@ void* __swift_bridge__$Vec_MyRustType$get(void* vec_ptr, uintptr_t index);
 void* __swift_bridge__$Vec_MyRustType$get_mut(void* vec_ptr, uintptr_t index);
 uintptr_t __swift_bridge__$Vec_MyRustType$len(void* vec_ptr);
 void* __swift_bridge__$Vec_MyRustType$as_ptr(void* vec_ptr);
+void __swift_bridge__$Vec_MyRustType$insert(void* vec_ptr, uintptr_t index, void* item_ptr);
+void* __swift_bridge__$Vec_MyRustType$remove(void* vec_ptr, uintptr_t index);
+void __swift_bridge__$Vec_MyRustType$reserve(void* vec_ptr, uintptr_t additional);
+uintptr_t __swift_bridge__$Vec_MyRustType$capacity(void* vec_ptr);
 "#,
         )
     }
@@ -188,7 +216,7 @@ mod extern_rust_fn_return_vec_of_opaque_rust_type {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
             pub extern "C" fn __swift_bridge__some_function() -> *mut Vec<super::MyRustType> {
-                Box::into_raw(Box::new(super::some_function()))
+                swift_bridge::allocator::box_into_raw(super::some_function())
             }
         })
     }
@@ -244,7 +272,7 @@ mod extern_rust_fn_arg_vec_of_opaque_rust_type {
             pub extern "C" fn __swift_bridge__some_function(
                 arg: *mut Vec<super::MyRustType>
             ) {
-                super::some_function(unsafe { * Box::from_raw(arg) })
+                super::some_function(unsafe { swift_bridge::allocator::take_from_raw(arg) })
             }
         })
     }
@@ -301,56 +329,76 @@ mod transparent_enum_vec_support {
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$new"]
                 pub extern "C" fn _new() -> *mut Vec<SomeEnum> {
-                    Box::into_raw(Box::new(Vec::new()))
+                    swift_bridge::vec_support::new()
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$drop"]
                 pub extern "C" fn _drop(vec: *mut Vec<SomeEnum>) {
-                    let vec = unsafe { Box::from_raw(vec) };
-                    drop(vec)
+                    unsafe { swift_bridge::vec_support::free(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$len"]
                 pub extern "C" fn _len(vec: *const Vec<SomeEnum>) -> usize {
-                    unsafe { &*vec }.len()
+                    unsafe { swift_bridge::vec_support::len(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$get"]
                 pub extern "C" fn _get(vec: *const Vec<SomeEnum>, index: usize) -> __swift_bridge__Option_SomeEnum {
-                    let vec = unsafe { &*vec };
-                    let val = vec.get(index).map(|v| *v);
+                    let val = unsafe { swift_bridge::vec_support::get_copy(vec, index) };
                     __swift_bridge__Option_SomeEnum::from_rust_repr(val)
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$get_mut"]
                 pub extern "C" fn _get_mut(vec: *mut Vec<SomeEnum>, index: usize) -> __swift_bridge__Option_SomeEnum {
-                    let vec = unsafe { &mut *vec };
-                    let val = vec.get_mut(index).map(|v| *v);
+                    let val = unsafe { swift_bridge::vec_support::get_copy(vec, index) };
                     __swift_bridge__Option_SomeEnum::from_rust_repr(val)
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$push"]
                 pub extern "C" fn _push(vec: *mut Vec<SomeEnum>, val: __swift_bridge__SomeEnum) {
-                    unsafe { &mut *vec }.push(val.into_rust_repr())
+                    unsafe { swift_bridge::vec_support::push(vec, val.into_rust_repr()) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$pop"]
                 pub extern "C" fn _pop(vec: *mut Vec<SomeEnum>) -> __swift_bridge__Option_SomeEnum {
-                    let vec = unsafe { &mut *vec };
-                    let val = vec.pop();
+                    let val = unsafe { swift_bridge::vec_support::pop(vec) };
                     __swift_bridge__Option_SomeEnum::from_rust_repr(val)
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_SomeEnum$as_ptr"]
                 pub extern "C" fn _as_ptr(vec: *const Vec<SomeEnum>) -> *const SomeEnum {
-                    unsafe { & *vec }.as_ptr()
+                    unsafe { swift_bridge::vec_support::as_ptr(vec) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_SomeEnum$insert"]
+                pub extern "C" fn _insert(vec: *mut Vec<SomeEnum>, index: usize, val: __swift_bridge__SomeEnum) {
+                    unsafe { swift_bridge::vec_support::insert(vec, index, val.into_rust_repr()) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_SomeEnum$remove"]
+                pub extern "C" fn _remove(vec: *mut Vec<SomeEnum>, index: usize) -> __swift_bridge__SomeEnum {
+                    unsafe { swift_bridge::vec_support::remove(vec, index) }.into_ffi_repr()
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_SomeEnum$reserve"]
+                pub extern "C" fn _reserve(vec: *mut Vec<SomeEnum>, additional: usize) {
+                    unsafe { swift_bridge::vec_support::reserve(vec, additional) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_SomeEnum$capacity"]
+                pub extern "C" fn _capacity(vec: *const Vec<SomeEnum>) -> usize {
+                    unsafe { swift_bridge::vec_support::capacity(vec) }
                 }
             };
         })
@@ -390,6 +438,22 @@ extension SomeEnum: Vectorizable {
     public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {
         __swift_bridge__$Vec_SomeEnum$len(vecPtr)
     }
+
+    public static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: Self) {
+        __swift_bridge__$Vec_SomeEnum$insert(vecPtr, index, value.intoFfiRepr())
+    }
+
+    public static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self {
+        __swift_bridge__$Vec_SomeEnum$remove(vecPtr, index).intoSwiftRepr()
+    }
+
+    public static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt) {
+        __swift_bridge__$Vec_SomeEnum$reserve(vecPtr, additional)
+    }
+
+    public static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt {
+        __swift_bridge__$Vec_SomeEnum$capacity(vecPtr)
+    }
 }
 "#,
         )
@@ -406,6 +470,10 @@ __swift_bridge__$Option$SomeEnum __swift_bridge__$Vec_SomeEnum$get(void* vec_ptr
 __swift_bridge__$Option$SomeEnum __swift_bridge__$Vec_SomeEnum$get_mut(void* vec_ptr, uintptr_t index);
 uintptr_t __swift_bridge__$Vec_SomeEnum$len(void* vec_ptr);
 void* __swift_bridge__$Vec_SomeEnum$as_ptr(void* vec_ptr);
+void __swift_bridge__$Vec_SomeEnum$insert(void* vec_ptr, uintptr_t index, __swift_bridge__$SomeEnum item);
+__swift_bridge__$SomeEnum __swift_bridge__$Vec_SomeEnum$remove(void* vec_ptr, uintptr_t index);
+void __swift_bridge__$Vec_SomeEnum$reserve(void* vec_ptr, uintptr_t additional);
+uintptr_t __swift_bridge__$Vec_SomeEnum$capacity(void* vec_ptr);
 "#,
         )
     }
@@ -443,7 +511,7 @@ mod extern_rust_fn_return_vec_of_transparent_enum {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
             pub extern "C" fn __swift_bridge__some_function() -> *mut Vec<SomeEnum> {
-                Box::into_raw(Box::new(super::some_function()))
+                swift_bridge::allocator::box_into_raw(super::some_function())
             }
         })
     }
@@ -502,7 +570,7 @@ mod extern_rust_fn_arg_vec_of_transparent_enum {
             pub extern "C" fn __swift_bridge__some_function(
                 arg: *mut Vec<SomeEnum>
             ) {
-                super::some_function(unsafe { * Box::from_raw(arg) })
+                super::some_function(unsafe { swift_bridge::allocator::take_from_raw(arg) })
             }
         })
     }
@@ -1,19 +1,20 @@
 //! Tests can be found in src/codegen/codegen_tests.rs and its submodules.
 
+use crate::bridge_module_attributes::{cfg_attrs_are_enabled, wrap_in_c_if};
 use crate::bridged_type::shared_struct::StructField;
 use crate::bridged_type::{BridgedType, StdLibType, StructFields};
 use crate::codegen::CodegenConfig;
 use crate::parse::{SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
 use crate::parsed_extern_fn::ParsedExternFn;
-use crate::{SwiftBridgeModule, SWIFT_BRIDGE_PREFIX};
-use std::collections::{BTreeSet, HashSet};
+use crate::SwiftBridgeModule;
+use std::collections::{BTreeMap, BTreeSet};
 use syn::ReturnType;
 
 const NOTICE: &'static str = "// File automatically generated by swift-bridge.";
 
 struct Bookkeeping {
     includes: BTreeSet<&'static str>,
-    slice_types: HashSet<String>,
+    slice_types: BTreeSet<String>,
 }
 
 impl SwiftBridgeModule {
@@ -38,7 +39,7 @@ impl SwiftBridgeModule {
             includes: BTreeSet::new(),
             // TODO: Delete this.
             //  Don't think we're using it.
-            slice_types: HashSet::new(),
+            slice_types: BTreeSet::new(),
         };
 
         for ty in self.types.types() {
@@ -77,7 +78,7 @@ impl SwiftBridgeModule {
 
                                         let name = field.swift_name_string();
 
-                                        fields.push(format!("{} {}", ty.to_c(), name));
+                                        fields.push(format!("{} {}", ty.to_c(&self.types), name));
                                     }
                                 }
                                 StructFields::Unnamed(types) => {
@@ -90,7 +91,7 @@ impl SwiftBridgeModule {
 
                                         let name = format!("_{}", idx);
 
-                                        fields.push(format!("{} {}", ty.to_c(), name));
+                                        fields.push(format!("{} {}", ty.to_c(&self.types), name));
                                     }
                                 }
                                 StructFields::Unit => {
@@ -101,6 +102,12 @@ impl SwiftBridgeModule {
                             }
                         }
 
+                        // `_Alignas` on the first field raises the alignment of the whole struct,
+                        //  which mirrors Rust's `#[repr(C, align(N))]`.
+                        if let Some(align) = ty_struct.layout.align {
+                            fields[0] = format!("_Alignas({}) {}", align, fields[0]);
+                        }
+
                         let maybe_fields = if fields.len() > 0 {
                             let mut maybe_fields = " ".to_string();
 
@@ -112,14 +119,24 @@ impl SwiftBridgeModule {
                             "".to_string()
                         };
 
+                        let (pack_push, pack_pop) = match ty_struct.layout.packed {
+                            Some(packed) => (
+                                format!("#pragma pack(push, {})\n", packed),
+                                "\n#pragma pack(pop)",
+                            ),
+                            None => ("".to_string(), ""),
+                        };
+
                         let ty_decl = format!(
-                            r#"typedef struct {prefix}${name} {{{maybe_fields}}} {prefix}${name};
+                            r#"{pack_push}typedef struct {prefix}${name} {{{maybe_fields}}} {prefix}${name};{pack_pop}
 typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi_name};"#,
-                            prefix = SWIFT_BRIDGE_PREFIX,
+                            prefix = ty_struct.symbol_prefix,
                             ffi_name = ffi_name,
                             option_ffi_name = option_ffi_name,
                             name = name,
-                            maybe_fields = maybe_fields
+                            maybe_fields = maybe_fields,
+                            pack_push = pack_push,
+                            pack_pop = pack_pop
                         );
 
                         header += &ty_decl;
@@ -148,7 +165,10 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
                         let maybe_vec_support = if ty_enum.has_one_or_more_variants_with_data() {
                             "".to_string()
                         } else {
-                            vec_transparent_enum_c_support(&ty_enum.swift_name_string())
+                            vec_transparent_enum_c_support(
+                                &ty_enum.symbol_prefix,
+                                &ty_enum.swift_name_string(),
+                            )
                         };
 
                         let enum_decl = format!(
@@ -177,23 +197,60 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
                     if ty.attributes.declare_generic {
                         continue;
                     }
+                    if !cfg_attrs_are_enabled(&ty.attributes.cfg_attrs, config) {
+                        continue;
+                    }
+                    let ty_start = header.len();
+
                     if ty.attributes.hashable {
                         let ty_name = ty.ty_name_ident();
-                        let hash_ty =
-                            format!("uint64_t __swift_bridge__${}$_hash(void* self);", ty_name);
+                        let hash_ty = format!(
+                            "uint64_t {}${}$_hash(void* self);",
+                            ty.symbol_prefix, ty_name
+                        );
                         header += &hash_ty;
                     }
                     if ty.attributes.equatable {
                         let ty_name = ty.ty_name_ident();
                         let equal_ty = format!(
-                            "bool __swift_bridge__${}$_partial_eq(void* lhs, void* rhs);",
-                            ty_name
+                            "bool {}${}$_partial_eq(void* lhs, void* rhs);",
+                            ty.symbol_prefix, ty_name
                         );
                         bookkeeping.includes.insert("stdint.h");
                         bookkeeping.includes.insert("stdbool.h");
                         header += &equal_ty;
                         header += "\n";
                     }
+                    if ty.attributes.codable {
+                        let ty_name = ty.ty_name_ident();
+                        header += &format!(
+                            "void* {prefix}${ty_name}$_to_json(void* self);\nvoid* {prefix}${ty_name}$_from_json(void* json);\n",
+                            prefix = ty.symbol_prefix,
+                            ty_name = ty_name
+                        );
+                    }
+                    if ty.attributes.comparable {
+                        let ty_name = ty.ty_name_ident();
+                        bookkeeping.includes.insert("stdint.h");
+                        header += &format!(
+                            "int8_t {}${}$_cmp(void* lhs, void* rhs);\n",
+                            ty.symbol_prefix, ty_name
+                        );
+                    }
+                    if ty.attributes.custom_string_convertible || ty.attributes.error {
+                        let ty_name = ty.ty_name_ident();
+                        header += &format!(
+                            "void* {}${}$_description(void* self);\n",
+                            ty.symbol_prefix, ty_name
+                        );
+                    }
+                    if ty.attributes.custom_debug_string_convertible {
+                        let ty_name = ty.ty_name_ident();
+                        header += &format!(
+                            "void* {}${}$_debug_description(void* self);\n",
+                            ty.symbol_prefix, ty_name
+                        );
+                    }
                     let ty_name = ty.to_string();
 
                     if let Some(copy) = ty.attributes.copy {
@@ -213,6 +270,7 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
                             option_copy_ffi_repr = c_option_ty_name,
                         );
 
+                        header += &c_doc_comment(ty.attributes.doc_comment.as_ref());
                         header += &ty_decl;
                         header += "\n";
                         header += &option_ty_decl;
@@ -220,15 +278,12 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
                     } else {
                         let ty_decl =
                             format!("typedef struct {ty_name} {ty_name};", ty_name = ty_name);
+                        header += &c_doc_comment(ty.attributes.doc_comment.as_ref());
                         header += &ty_decl;
                         header += "\n";
 
-                        let generics = ty.generics.dollar_prefixed_generics_string();
-                        let drop_ty = format!(
-                            r#"void __swift_bridge__${ty_name}{generics}$_free(void* self);"#,
-                            ty_name = ty_name,
-                            generics = generics
-                        );
+                        let drop_ty =
+                            format!("void {}(void* self);", ty.free_rust_opaque_type_ffi_name());
 
                         header += &drop_ty;
                         header += "\n";
@@ -238,30 +293,130 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
                     //  make them pass.
                     // TODO: Support Vec<GenericOpaqueRustType
                     if ty.attributes.copy.is_none() && ty.generics.len() == 0 {
-                        let vec_functions = vec_opaque_rust_type_c_support(&ty_name);
+                        let vec_functions =
+                            vec_opaque_rust_type_c_support(&ty.symbol_prefix, &ty_name);
 
                         header += &vec_functions;
                         header += "\n";
                     }
+
+                    let ty_header = header.split_off(ty_start);
+                    header += &wrap_in_c_if(ty_header, &ty.attributes.cfg_attrs);
+                }
+            }
+        }
+
+        let mut by_value_results = BTreeMap::new();
+        for func in self.functions.iter() {
+            for result in func.result_structs(&self.types) {
+                by_value_results.insert(result.ffi_name_string(), result);
+            }
+        }
+        for result in by_value_results.values() {
+            bookkeeping.includes.insert("stdbool.h");
+            for ty in [&result.ok_ty, &result.err_ty] {
+                if let Some(include) = ty.to_c_include() {
+                    bookkeeping.includes.insert(include);
+                }
+            }
+
+            header += &result.to_c_struct_declaration(&self.types);
+            header += "\n";
+        }
+
+        for parsed_const in self.consts.iter() {
+            if !cfg_attrs_are_enabled(&parsed_const.cfg_attrs, config) {
+                continue;
+            }
+
+            let ty = BridgedType::new_with_type(&parsed_const.ty, &self.types).unwrap();
+            if let Some(include) = ty.to_c_include() {
+                bookkeeping.includes.insert(include);
+            }
+
+            let declaration = format!(
+                "{}extern const {} {};",
+                c_doc_comment(parsed_const.doc_comment.as_ref()),
+                ty.to_c(&self.types),
+                parsed_const.link_name()
+            );
+            header += &wrap_in_c_if(declaration, &parsed_const.cfg_attrs);
+            header += "\n";
+        }
+
+        for parsed_static in self.statics.iter() {
+            if !cfg_attrs_are_enabled(&parsed_static.cfg_attrs, config) {
+                continue;
+            }
+
+            if let Some(value_ty) = parsed_static.atomic_value_type() {
+                let value_ty = BridgedType::new_with_type(&value_ty, &self.types).unwrap();
+                if let Some(include) = value_ty.to_c_include() {
+                    bookkeeping.includes.insert(include);
                 }
+
+                for op in parsed_static.atomic_ops() {
+                    let ret = if op.returns_value() {
+                        value_ty.to_c(&self.types)
+                    } else {
+                        "void".to_string()
+                    };
+                    let param = if op.takes_value() {
+                        format!("{} value", value_ty.to_c(&self.types))
+                    } else {
+                        "void".to_string()
+                    };
+
+                    let declaration = format!(
+                        "{} {}({});",
+                        ret,
+                        parsed_static.atomic_op_link_name(op),
+                        param
+                    );
+                    header += &wrap_in_c_if(declaration, &parsed_static.cfg_attrs);
+                    header += "\n";
+                }
+                continue;
             }
+
+            let ty = parsed_static.bridged_type(&self.types);
+            if let Some(include) = ty.to_c_include() {
+                bookkeeping.includes.insert(include);
+            }
+
+            let declaration = format!(
+                "{}{} {}(void);",
+                c_doc_comment(parsed_static.doc_comment.as_ref()),
+                ty.to_c(&self.types),
+                parsed_static.link_name()
+            );
+            header += &wrap_in_c_if(declaration, &parsed_static.cfg_attrs);
+            header += "\n";
         }
 
         for func in self.functions.iter() {
+            let cfg_attrs = func.all_cfg_attrs();
+            if !cfg_attrs_are_enabled(&cfg_attrs, config) {
+                continue;
+            }
+
             if func.host_lang.is_swift() {
                 for (idx, boxed_fn) in func.args_filtered_to_boxed_fns(&self.types) {
                     if boxed_fn.params.is_empty() && boxed_fn.ret.is_null() {
                         continue;
                     }
 
-                    let fns = func.boxed_fn_to_c_header_fns(idx, &boxed_fn);
-                    header += &format!("{fns}");
+                    let fns = func.boxed_fn_to_c_header_fns(idx, &boxed_fn, &self.types);
+                    header += &wrap_in_c_if(format!("{fns}"), &cfg_attrs);
                     header += "\n";
                 }
                 continue;
             }
 
-            header += &declare_func(&func, &mut bookkeeping, &self.types);
+            header += &wrap_in_c_if(
+                declare_func(&func, &mut bookkeeping, &self.types),
+                &cfg_attrs,
+            );
         }
 
         for slice_ty in bookkeeping.slice_types.iter() {
@@ -273,6 +428,10 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
             )
         }
 
+        if self.default_visibility {
+            header = annotate_with_default_visibility(&header);
+        }
+
         let mut includes = bookkeeping.includes.iter().collect::<Vec<_>>();
         includes.sort();
         for include in includes {
@@ -283,38 +442,76 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
             );
         }
 
-        header
+        wrap_in_c_if(header, &self.cfg_attrs)
     }
 }
 
-fn vec_opaque_rust_type_c_support(ty_name: &str) -> String {
+/// Prefix every function declaration in the header with `SWIFT_BRIDGE_EXPORT`, which expands to
+/// `__attribute__((visibility("default")))`.
+///
+/// This lets the bridge's symbols stay exported when the rest of a library is built with hidden
+/// visibility.
+fn annotate_with_default_visibility(header: &str) -> String {
+    let mut annotated = r#"#ifndef SWIFT_BRIDGE_EXPORT
+#define SWIFT_BRIDGE_EXPORT __attribute__((visibility("default")))
+#endif
+"#
+    .to_string();
+
+    for line in header.lines() {
+        let is_function_declaration = line.ends_with(");")
+            && !line.starts_with('#')
+            && !line.starts_with("typedef")
+            && !line.starts_with(" *");
+        if is_function_declaration {
+            annotated += "SWIFT_BRIDGE_EXPORT ";
+        }
+
+        annotated += line;
+        annotated += "\n";
+    }
+
+    annotated
+}
+
+fn vec_opaque_rust_type_c_support(prefix: &str, ty_name: &str) -> String {
     format!(
         r#"
-void* __swift_bridge__$Vec_{ty_name}$new(void);
-void __swift_bridge__$Vec_{ty_name}$drop(void* vec_ptr);
-void __swift_bridge__$Vec_{ty_name}$push(void* vec_ptr, void* item_ptr);
-void* __swift_bridge__$Vec_{ty_name}$pop(void* vec_ptr);
-void* __swift_bridge__$Vec_{ty_name}$get(void* vec_ptr, uintptr_t index);
-void* __swift_bridge__$Vec_{ty_name}$get_mut(void* vec_ptr, uintptr_t index);
-uintptr_t __swift_bridge__$Vec_{ty_name}$len(void* vec_ptr);
-void* __swift_bridge__$Vec_{ty_name}$as_ptr(void* vec_ptr);
+void* {prefix}$Vec_{ty_name}$new(void);
+void {prefix}$Vec_{ty_name}$drop(void* vec_ptr);
+void {prefix}$Vec_{ty_name}$push(void* vec_ptr, void* item_ptr);
+void* {prefix}$Vec_{ty_name}$pop(void* vec_ptr);
+void* {prefix}$Vec_{ty_name}$get(void* vec_ptr, uintptr_t index);
+void* {prefix}$Vec_{ty_name}$get_mut(void* vec_ptr, uintptr_t index);
+uintptr_t {prefix}$Vec_{ty_name}$len(void* vec_ptr);
+void* {prefix}$Vec_{ty_name}$as_ptr(void* vec_ptr);
+void {prefix}$Vec_{ty_name}$insert(void* vec_ptr, uintptr_t index, void* item_ptr);
+void* {prefix}$Vec_{ty_name}$remove(void* vec_ptr, uintptr_t index);
+void {prefix}$Vec_{ty_name}$reserve(void* vec_ptr, uintptr_t additional);
+uintptr_t {prefix}$Vec_{ty_name}$capacity(void* vec_ptr);
 "#,
+        prefix = prefix,
         ty_name = ty_name
     )
 }
 
-fn vec_transparent_enum_c_support(enum_name: &str) -> String {
+fn vec_transparent_enum_c_support(prefix: &str, enum_name: &str) -> String {
     format!(
         r#"
-void* __swift_bridge__$Vec_{enum_name}$new(void);
-void __swift_bridge__$Vec_{enum_name}$drop(void* vec_ptr);
-void __swift_bridge__$Vec_{enum_name}$push(void* vec_ptr, __swift_bridge__${enum_name} item);
-__swift_bridge__$Option${enum_name} __swift_bridge__$Vec_{enum_name}$pop(void* vec_ptr);
-__swift_bridge__$Option${enum_name} __swift_bridge__$Vec_{enum_name}$get(void* vec_ptr, uintptr_t index);
-__swift_bridge__$Option${enum_name} __swift_bridge__$Vec_{enum_name}$get_mut(void* vec_ptr, uintptr_t index);
-uintptr_t __swift_bridge__$Vec_{enum_name}$len(void* vec_ptr);
-void* __swift_bridge__$Vec_{enum_name}$as_ptr(void* vec_ptr);
+void* {prefix}$Vec_{enum_name}$new(void);
+void {prefix}$Vec_{enum_name}$drop(void* vec_ptr);
+void {prefix}$Vec_{enum_name}$push(void* vec_ptr, {prefix}${enum_name} item);
+{prefix}$Option${enum_name} {prefix}$Vec_{enum_name}$pop(void* vec_ptr);
+{prefix}$Option${enum_name} {prefix}$Vec_{enum_name}$get(void* vec_ptr, uintptr_t index);
+{prefix}$Option${enum_name} {prefix}$Vec_{enum_name}$get_mut(void* vec_ptr, uintptr_t index);
+uintptr_t {prefix}$Vec_{enum_name}$len(void* vec_ptr);
+void* {prefix}$Vec_{enum_name}$as_ptr(void* vec_ptr);
+void {prefix}$Vec_{enum_name}$insert(void* vec_ptr, uintptr_t index, {prefix}${enum_name} item);
+{prefix}${enum_name} {prefix}$Vec_{enum_name}$remove(void* vec_ptr, uintptr_t index);
+void {prefix}$Vec_{enum_name}$reserve(void* vec_ptr, uintptr_t additional);
+uintptr_t {prefix}$Vec_{enum_name}$capacity(void* vec_ptr);
 "#,
+        prefix = prefix,
         enum_name = enum_name
     )
 }
@@ -331,7 +528,7 @@ fn declare_func(
     if let ReturnType::Type(_, ty) = &func.func.sig.output {
         if let Some(ty) = BridgedType::new_with_type(&ty, types) {
             if let BridgedType::StdLib(StdLibType::RefSlice(ref_slice)) = ty {
-                bookkeeping.slice_types.insert(ref_slice.ty.to_c());
+                bookkeeping.slice_types.insert(ref_slice.ty.to_c(types));
             }
         }
     }
@@ -342,12 +539,27 @@ fn declare_func(
         }
     }
 
+    let maybe_availability = func
+        .available
+        .as_ref()
+        .map(|available| available.to_c_attributes())
+        .unwrap_or_default();
+
     let declaration = if func.sig.asyncness.is_some() {
         let maybe_ret = BridgedType::new_with_return_type(&func.sig.output, types).unwrap();
         let maybe_ret = if maybe_ret.is_null() {
             "".to_string()
         } else {
-            format!(", {} ret", maybe_ret.to_c())
+            format!(", {} ret", maybe_ret.to_c(types))
+        };
+
+        let maybe_cancellation = if func.is_cancellable {
+            format!(
+                ", void {name}$cancel(void* callback_wrapper), void* cancellation",
+                name = name
+            )
+        } else {
+            "".to_string()
         };
 
         let maybe_params = if func.sig.inputs.is_empty() {
@@ -357,20 +569,44 @@ fn declare_func(
         };
 
         format!(
-            "void {name}(void* callback_wrapper, void {name}$async(void* callback_wrapper{maybe_ret}){maybe_params});\n",
+            "void {name}(void* callback_wrapper, void {name}$async(void* callback_wrapper{maybe_ret}){maybe_cancellation}{maybe_params}){maybe_availability};\n",
             name = name,
             maybe_ret = maybe_ret
         )
     } else {
         format!(
-            "{ret} {name}({params});\n",
+            "{ret} {name}({params}){maybe_availability};\n",
             ret = ret,
             name = name,
-            params = params
+            params = params,
+            maybe_availability = maybe_availability
         )
     };
 
-    declaration
+    format!(
+        "{}{}",
+        c_doc_comment(func.doc_comment.as_ref()),
+        declaration
+    )
+}
+
+/// Convert a Rust doc comment into a Doxygen style C comment.
+///
+/// `" Some comment"` becomes `"/**\n * Some comment\n */\n"`.
+fn c_doc_comment(doc_comment: Option<&String>) -> String {
+    let doc_comment = match doc_comment {
+        Some(doc_comment) => doc_comment,
+        None => return "".to_string(),
+    };
+
+    let mut c_doc_comment = "/**\n".to_string();
+    for line in doc_comment.lines() {
+        // A `*/` inside of the comment would end it early.
+        c_doc_comment += &format!(" *{}\n", line.replace("*/", "*\\/"));
+    }
+    c_doc_comment += " */\n";
+
+    c_doc_comment
 }
 
 #[cfg(test)]
@@ -385,7 +621,7 @@ mod tests {
         assert_trimmed_generated_contains_trimmed_expected,
         assert_trimmed_generated_equals_trimmed_expected,
     };
-    use crate::SwiftBridgeModule;
+    use crate::{SwiftBridgeModule, SWIFT_BRIDGE_PREFIX};
 
     use super::*;
 
@@ -514,7 +750,7 @@ typedef struct SomeType SomeType;
 void __swift_bridge__$SomeType$_free(void* self);
 {}
 "#,
-            vec_opaque_rust_type_c_support("SomeType")
+            vec_opaque_rust_type_c_support(SWIFT_BRIDGE_PREFIX, "SomeType")
         );
 
         let module = parse_ok(tokens);
@@ -581,7 +817,7 @@ void __swift_bridge__$SomeType$_free(void* self);
 {}
 void __swift_bridge__$SomeType$foo(void* self, uint8_t val);
         "#,
-            vec_opaque_rust_type_c_support("SomeType")
+            vec_opaque_rust_type_c_support(SWIFT_BRIDGE_PREFIX, "SomeType")
         );
 
         let module = parse_ok(tokens);
@@ -612,7 +848,7 @@ void __swift_bridge__$SomeType$_free(void* self);
 {}
 void __swift_bridge__$SomeType$foo(void* self, void* val);
         "#,
-            vec_opaque_rust_type_c_support("SomeType")
+            vec_opaque_rust_type_c_support(SWIFT_BRIDGE_PREFIX, "SomeType")
         );
 
         let module = parse_ok(tokens);
@@ -644,7 +880,7 @@ void __swift_bridge__$SomeType$_free(void* self);
 {}
 uint8_t __swift_bridge__$SomeType$foo(void* self);
         "#,
-            vec_opaque_rust_type_c_support("SomeType")
+            vec_opaque_rust_type_c_support(SWIFT_BRIDGE_PREFIX, "SomeType")
         );
 
         let module = parse_ok(tokens);
@@ -1,16 +1,16 @@
 //! More tests can be found in src/codegen/codegen_tests.rs and its submodules.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use quote::{quote, quote_spanned};
 
 use self::vec::vec_of_opaque_rust_type::generate_vec_of_opaque_rust_type_functions;
-use crate::bridge_module_attributes::CfgAttr;
 use crate::parse::{HostLang, SharedTypeDeclaration, TypeDeclaration};
 use crate::SwiftBridgeModule;
 
+mod ide_stubs;
 mod shared_enum;
 mod shared_struct;
 mod vec;
@@ -26,12 +26,30 @@ impl ToTokens for SwiftBridgeModule {
 
         let mut shared_struct_definitions = vec![];
         let mut shared_enum_definitions = vec![];
+        let mut by_value_result_definitions = BTreeMap::new();
         let mut impl_fn_tokens: HashMap<String, Vec<TokenStream>> = HashMap::new();
         let mut callbacks_support = vec![];
         let mut freestanding_rust_call_swift_fn_tokens = vec![];
         let mut extern_swift_fn_tokens = vec![];
 
+        for parsed_const in &self.consts {
+            extern_rust_fn_tokens.push(parsed_const.to_rust_tokens());
+        }
+
+        for parsed_static in &self.statics {
+            extern_rust_fn_tokens
+                .push(parsed_static.to_rust_tokens(&self.swift_bridge_path, &self.types));
+        }
+
         for func in &self.functions {
+            for result in func.result_structs(&self.types) {
+                by_value_result_definitions
+                    .entry(result.ffi_name_string())
+                    .or_insert_with(|| {
+                        result.to_ffi_struct_definition(&self.swift_bridge_path, &self.types)
+                    });
+            }
+
             match func.host_lang {
                 HostLang::Rust => {
                     extern_rust_fn_tokens.push(
@@ -90,16 +108,18 @@ impl ToTokens for SwiftBridgeModule {
                     let free_mem_func_name = ty.free_rust_opaque_type_ident();
                     let this = &ty.ty;
                     let ty_name = &ty.ty;
+                    let cfg_attrs = &ty.attributes.cfg_attrs;
 
                     match ty.host_lang {
                         HostLang::Rust => {
                             if ty.attributes.hashable {
-                                let export_name = format!("__swift_bridge__${}$_hash", ty_name);
+                                let export_name = format!("{}${}$_hash", ty.symbol_prefix, ty_name);
                                 let function_name = syn::Ident::new(
                                     &format!("__swift_bridge__{}__hash", ty_name),
                                     ty.ty.span(),
                                 );
                                 let tokens = quote! {
+                                #(#cfg_attrs)*
                                 #[export_name = #export_name]
                                 pub extern "C" fn #function_name (
                                     this: *const super::#ty_name,
@@ -115,12 +135,13 @@ impl ToTokens for SwiftBridgeModule {
                             }
                             if ty.attributes.equatable {
                                 let export_name =
-                                    format!("__swift_bridge__${}$_partial_eq", ty_name);
+                                    format!("{}${}$_partial_eq", ty.symbol_prefix, ty_name);
                                 let function_name = syn::Ident::new(
                                     &format!("__swift_bridge__{}__partial_eq", ty_name),
                                     ty.ty.span(),
                                 );
                                 let tokens = quote! {
+                                    #(#cfg_attrs)*
                                     #[export_name = #export_name]
                                     pub extern "C" fn #function_name (
                                         lhs: *const super::#ty_name,
@@ -131,6 +152,146 @@ impl ToTokens for SwiftBridgeModule {
                                 };
                                 extern_rust_fn_tokens.push(tokens);
                             }
+                            if ty.attributes.codable {
+                                let to_json_export_name =
+                                    format!("{}${}$_to_json", ty.symbol_prefix, ty_name);
+                                let to_json_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__to_json", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let from_json_export_name =
+                                    format!("{}${}$_from_json", ty.symbol_prefix, ty_name);
+                                let from_json_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__from_json", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let tokens = quote! {
+                                    #(#cfg_attrs)*
+                                    #[export_name = #to_json_export_name]
+                                    pub extern "C" fn #to_json_name (
+                                        this: *const super::#ty_name,
+                                    ) -> *mut swift_bridge::string::RustString {
+                                        match swift_bridge::codable::to_json(unsafe { &*this }) {
+                                            Some(json) => swift_bridge::string::RustString(json).box_into_raw(),
+                                            None => std::ptr::null_mut(),
+                                        }
+                                    }
+
+                                    #(#cfg_attrs)*
+                                    #[export_name = #from_json_export_name]
+                                    pub extern "C" fn #from_json_name (
+                                        json: *mut swift_bridge::string::RustString,
+                                    ) -> *mut super::#ty_name {
+                                        let json = unsafe { #swift_bridge_path::allocator::take_from_raw(json).0 };
+                                        match swift_bridge::codable::from_json::<super::#ty_name>(&json) {
+                                            Some(val) => #swift_bridge_path::allocator::box_into_raw(val),
+                                            None => std::ptr::null_mut(),
+                                        }
+                                    }
+                                };
+                                extern_rust_fn_tokens.push(tokens);
+                            }
+                            if ty.attributes.comparable {
+                                let export_name = format!("{}${}$_cmp", ty.symbol_prefix, ty_name);
+                                let function_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__cmp", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let tokens = quote! {
+                                    #(#cfg_attrs)*
+                                    #[export_name = #export_name]
+                                    pub extern "C" fn #function_name (
+                                        lhs: *const super::#ty_name,
+                                        rhs: *const super::#ty_name
+                                    ) -> i8 {
+                                        unsafe { (&*lhs).cmp(&*rhs) as i8 }
+                                    }
+                                };
+                                extern_rust_fn_tokens.push(tokens);
+                            }
+                            // Errors use the `Display` implementation for their description.
+                            if ty.attributes.custom_string_convertible || ty.attributes.error {
+                                let export_name =
+                                    format!("{}${}$_description", ty.symbol_prefix, ty_name);
+                                let function_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__description", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let tokens = quote! {
+                                    #(#cfg_attrs)*
+                                    #[export_name = #export_name]
+                                    pub extern "C" fn #function_name (
+                                        this: *const super::#ty_name,
+                                    ) -> *mut swift_bridge::string::RustString {
+                                        swift_bridge::string::RustString(
+                                            format!("{}", unsafe { &*this })
+                                        ).box_into_raw()
+                                    }
+                                };
+                                extern_rust_fn_tokens.push(tokens);
+                            }
+                            if ty.attributes.custom_debug_string_convertible {
+                                let export_name =
+                                    format!("{}${}$_debug_description", ty.symbol_prefix, ty_name);
+                                let function_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__debug_description", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let tokens = quote! {
+                                    #(#cfg_attrs)*
+                                    #[export_name = #export_name]
+                                    pub extern "C" fn #function_name (
+                                        this: *const super::#ty_name,
+                                    ) -> *mut swift_bridge::string::RustString {
+                                        swift_bridge::string::RustString(
+                                            format!("{:?}", unsafe { &*this })
+                                        ).box_into_raw()
+                                    }
+                                };
+                                extern_rust_fn_tokens.push(tokens);
+                            }
+                            if ty.attributes.observable_object {
+                                let link_name =
+                                    format!("{}${}$_object_will_change", ty.symbol_prefix, ty_name);
+                                let function_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__object_will_change", ty_name),
+                                    ty.ty.span(),
+                                );
+                                extern_rust_fn_tokens.push(quote! {
+                                    #(#cfg_attrs)*
+                                    impl super::#ty_name {
+                                        /// Notify this value's Swift `ObservableObject` wrappers
+                                        /// that it changed, so that they refresh their properties.
+                                        pub fn object_will_change(&self) {
+                                            unsafe {
+                                                #function_name(
+                                                    self as *const super::#ty_name as *mut std::ffi::c_void
+                                                )
+                                            }
+                                        }
+                                    }
+                                });
+                                extern_swift_fn_tokens.push(quote! {
+                                    #(#cfg_attrs)*
+                                    #[link_name = #link_name]
+                                    fn #function_name (this: *mut std::ffi::c_void);
+                                });
+                            }
+                            if ty.attributes.sendable {
+                                let generics = ty
+                                    .generics
+                                    .angle_bracketed_concrete_generics_tokens(&self.types);
+
+                                let assert_send_sync = quote_spanned! {ty.ty.span()=>
+                                    #(#cfg_attrs)*
+                                    const _: () = {
+                                        fn _assert_send_sync() {
+                                            #swift_bridge_path::assert_send_sync::<super::#ty_name #generics>();
+                                        }
+                                    };
+                                };
+                                extern_rust_fn_tokens.push(assert_send_sync);
+                            }
                             if let Some(copy) = ty.attributes.copy {
                                 let size = copy.size_bytes;
 
@@ -145,6 +306,7 @@ impl ToTokens for SwiftBridgeModule {
                                 // If compile time assertions are ever supported by Rust.
                                 // https://github.com/rust-lang/rfcs/issues/2790
                                 let assert_size = quote_spanned! {ty.ty.span()=>
+                                    #(#cfg_attrs)*
                                     const _: () = {
                                         let _: [u8; std::mem::size_of::<super::#ty_name #generics>()] = [0; #size];
                                         fn _assert_copy() {
@@ -157,9 +319,11 @@ impl ToTokens for SwiftBridgeModule {
                                 let option_copy_ty_name = ty.ffi_option_copy_repr_ident();
 
                                 let copy_ty = quote! {
+                                    #(#cfg_attrs)*
                                     #[repr(C)]
                                     #[doc(hidden)]
                                     pub struct #copy_ty_name([u8; #size]);
+                                    #(#cfg_attrs)*
                                     impl #copy_ty_name {
                                         #[inline(always)]
                                         fn into_rust_repr(self) -> super:: #ty_name #generics {
@@ -171,6 +335,7 @@ impl ToTokens for SwiftBridgeModule {
                                         }
                                     }
 
+                                    #(#cfg_attrs)*
                                     #[repr(C)]
                                     #[doc(hidden)]
                                     pub struct #option_copy_ty_name {
@@ -190,9 +355,10 @@ impl ToTokens for SwiftBridgeModule {
                                         .angle_bracketed_concrete_generics_tokens(&self.types);
 
                                     let free = quote! {
+                                        #(#cfg_attrs)*
                                         #[export_name = #link_name]
                                         pub extern "C" fn #free_mem_func_name (this: *mut super::#this #generics) {
-                                            let this = unsafe { Box::from_raw(this) };
+                                            let this = unsafe { #swift_bridge_path::allocator::take_from_raw(this) };
                                             drop(this);
                                         }
                                     };
@@ -204,8 +370,15 @@ impl ToTokens for SwiftBridgeModule {
                                     // TODO: Support Vec<GenericOpaqueRustType
                                     if ty.generics.len() == 0 {
                                         let vec_functions =
-                                            generate_vec_of_opaque_rust_type_functions(ty_name);
-                                        extern_rust_fn_tokens.push(vec_functions);
+                                            generate_vec_of_opaque_rust_type_functions(
+                                                ty_name,
+                                                &ty.symbol_prefix,
+                                                swift_bridge_path,
+                                            );
+                                        extern_rust_fn_tokens.push(quote! {
+                                            #(#cfg_attrs)*
+                                            #vec_functions
+                                        });
                                     }
                                 }
                             }
@@ -216,6 +389,7 @@ impl ToTokens for SwiftBridgeModule {
                             let impls = match impl_fn_tokens.get(&ty_name.to_string()) {
                                 Some(impls) if impls.len() > 0 => {
                                     quote! {
+                                        #(#cfg_attrs)*
                                         impl #ty_name {
                                             #(#impls)*
                                         }
@@ -226,12 +400,28 @@ impl ToTokens for SwiftBridgeModule {
                                 }
                             };
 
+                            // The struct and its Drop implementation live in the bridge module
+                            // that originally declared the type, so we import it from the
+                            // parent module instead of redefining it.
+                            if ty.attributes.already_declared {
+                                let struct_tokens = quote! {
+                                    #(#cfg_attrs)*
+                                    use super::#ty_name;
+
+                                    #impls
+                                };
+                                structs_for_swift_classes.push(struct_tokens);
+                                continue;
+                            }
+
                             let struct_tokens = quote! {
+                                #(#cfg_attrs)*
                                 #[repr(C)]
                                 pub struct #ty_name(*mut std::ffi::c_void);
 
                                 #impls
 
+                                #(#cfg_attrs)*
                                 impl Drop for #ty_name {
                                     fn drop (&mut self) {
                                         unsafe { #free_mem_func_name(self.0) }
@@ -241,6 +431,7 @@ impl ToTokens for SwiftBridgeModule {
                             structs_for_swift_classes.push(struct_tokens);
 
                             let free = quote! {
+                                #(#cfg_attrs)*
                                 #[link_name = #link_name]
                                 fn #free_mem_func_name (this: *mut std::ffi::c_void);
                             };
@@ -261,24 +452,17 @@ impl ToTokens for SwiftBridgeModule {
             quote! {}
         };
 
-        let mut module_attributes = vec![];
+        let module_attributes = &self.cfg_attrs;
 
-        for cfg in &self.cfg_attrs {
-            match cfg {
-                CfgAttr::Feature(feature_name) => {
-                    //
-                    module_attributes.push(quote! {
-                        #[cfg(feature = #feature_name)]
-                    });
-                }
-            };
-        }
+        let by_value_result_definitions = by_value_result_definitions.values();
 
         let module_inner = quote! {
             #(#shared_struct_definitions)*
 
             #(#shared_enum_definitions)*
 
+            #(#by_value_result_definitions)*
+
             #(#extern_rust_fn_tokens)*
 
             #(#freestanding_rust_call_swift_fn_tokens)*
@@ -507,7 +691,7 @@ mod tests {
         let expected_func = quote! {
             #[export_name = "__swift_bridge__$some_function"]
             pub extern "C" fn __swift_bridge__some_function () -> *mut super::Foo {
-                Box::into_raw(Box::new(super::another_function())) as *mut super::Foo
+                swift_bridge::allocator::box_into_raw(super::another_function()) as *mut super::Foo
             }
         };
 
@@ -531,7 +715,7 @@ mod tests {
         let expected_func = quote! {
             #[export_name = "__swift_bridge__$some_function"]
             pub extern "C" fn __swift_bridge__some_function () -> *mut super::Foo {
-                Box::into_raw(Box::new(super::some_function().into())) as *mut super::Foo
+                swift_bridge::allocator::box_into_raw(super::some_function().into()) as *mut super::Foo
             }
         };
 
@@ -630,7 +814,7 @@ mod tests {
         let expected = quote! {
             #[export_name = "__swift_bridge__$SomeType$new"]
             pub extern "C" fn __swift_bridge__SomeType_new () -> *mut super::SomeType {
-                Box::into_raw(Box::new(super::SomeType::new())) as *mut super::SomeType
+                swift_bridge::allocator::box_into_raw(super::SomeType::new()) as *mut super::SomeType
             }
         };
 
@@ -922,13 +1106,118 @@ mod tests {
             pub extern "C" fn __swift_bridge__SomeType_consume (
                 this: *mut super::SomeType
             ) {
-                (* unsafe { Box::from_raw(this) }).consume()
+                (unsafe { swift_bridge::allocator::take_from_raw(this) }).consume()
             }
         };
 
         assert_to_extern_c_function_tokens(start, &expected);
     }
 
+    /// Verify that the boxes that cross the boundary get allocated and freed by
+    /// `swift_bridge::allocator`, which counts them for the `leak-detection` feature and uses the
+    /// chosen allocator for the `custom-allocator` feature.
+    #[test]
+    fn boxes_use_swift_bridge_allocator() {
+        let start = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+                    fn new_some_type() -> SomeType;
+                    fn consume (self: SomeType);
+                    fn take_vec(arg: Vec<u8>);
+                }
+            }
+        };
+        let module = parse_ok(start);
+        let tokens = module.to_token_stream();
+
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                swift_bridge::allocator::box_into_raw(super::new_some_type()) as *mut super::SomeType
+            },
+        );
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                (unsafe { swift_bridge::allocator::take_from_raw(this) }).consume()
+            },
+        );
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                let this = unsafe { swift_bridge::allocator::take_from_raw(this) };
+            },
+        );
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                super::take_vec(unsafe { swift_bridge::allocator::take_from_raw(arg) })
+            },
+        );
+        // The vec helpers box through `swift_bridge::allocator` too.
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                swift_bridge::vec_support::new()
+            },
+        );
+        assert!(!tokens.to_string().contains("Box :: into_raw"));
+        assert!(!tokens.to_string().contains("Box :: from_raw"));
+    }
+
+    /// Verify that call tracing reports the calls to the module's Rust functions using
+    /// `swift_bridge::call_tracing`.
+    #[test]
+    fn call_tracing_wraps_rust_function_calls() {
+        let start = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+                    fn some_function();
+                    fn some_method(&self) -> u8;
+                    async fn some_async_function();
+                }
+
+                extern "Swift" {
+                    fn swift_function();
+                }
+            }
+        };
+        let mut module = parse_ok(start);
+        module.set_call_tracing();
+        let tokens = module.to_token_stream();
+
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                pub extern "C" fn __swift_bridge__some_function() {
+                    swift_bridge::call_tracing::trace_call("some_function", || {
+                        super::some_function()
+                    })
+                }
+            },
+        );
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                swift_bridge::call_tracing::trace_call("SomeType::some_method", || {
+                    (unsafe { &*this }).some_method()
+                })
+            },
+        );
+        assert_tokens_contain(
+            &tokens,
+            &quote! {
+                let fut = swift_bridge::call_tracing::trace_future(
+                    "some_async_function",
+                    super::some_async_function()
+                );
+            },
+        );
+        assert!(!tokens.to_string().contains("\"swift_function\""));
+    }
+
     fn parse_ok(tokens: TokenStream) -> SwiftBridgeModule {
         let module_and_errors: SwiftBridgeModuleAndErrors = syn::parse2(tokens).unwrap();
         module_and_errors.module
@@ -0,0 +1,116 @@
+//! Stubs that only rust-analyzer sees, so that IDE features work inside of bridge modules.
+//!
+//! The generated code doesn't use the argument and return types that the user wrote in their
+//! function declarations, so without these stubs rust-analyzer can't show their hover info or
+//! go to their definitions.
+
+use crate::parse::TypeDeclaration;
+use crate::SwiftBridgeModule;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+use syn::Ident;
+
+impl SwiftBridgeModule {
+    /// Generate a `#[cfg(rust_analyzer)]` block that declares the module's function signatures,
+    /// using the tokens that the user wrote.
+    ///
+    /// This goes next to the generated module. The block is never compiled by rustc.
+    pub fn ide_stub_tokens(&self) -> TokenStream {
+        if self.functions.is_empty() {
+            return quote! {};
+        }
+
+        let mod_name = &self.name;
+        let module_cfg_attrs = &self.cfg_attrs;
+
+        // The signatures are grouped into one trait per type, since a trait's items can have a
+        // `self` receiver and don't need a body.
+        let mut signatures: BTreeMap<String, Vec<TokenStream>> = BTreeMap::new();
+        for function in self.functions.iter() {
+            let trait_name = match function.associated_type.as_ref() {
+                Some(TypeDeclaration::Opaque(ty)) => ty.ty.to_string(),
+                Some(TypeDeclaration::Shared(_)) => continue,
+                None => "functions".to_string(),
+            };
+
+            // The name gets a call site span so that going to the definition of a declared
+            // function takes the user to their implementation, not to the stub.
+            let mut sig = function.func.sig.clone();
+            sig.ident = Ident::new(&sig.ident.to_string(), Span::call_site());
+            let cfg_attrs = function.all_cfg_attrs();
+
+            signatures.entry(trait_name).or_default().push(quote! {
+                #(#cfg_attrs)*
+                #sig;
+            });
+        }
+
+        let traits = signatures.into_iter().map(|(name, signatures)| {
+            let trait_name = format_ident!("__swift_bridge__{}", name);
+            quote! {
+                trait #trait_name {
+                    #(#signatures)*
+                }
+            }
+        });
+
+        // The `allow` needs to be on an outer item, otherwise rustc warns about the
+        // `rust_analyzer` cfg in the user's crate.
+        quote! {
+            #(#module_cfg_attrs)*
+            #[allow(unknown_lints, unexpected_cfgs)]
+            const _: () = {
+                #[cfg(rust_analyzer)]
+                #[allow(non_camel_case_types, non_snake_case, dead_code, unused_imports)]
+                const _: () = {
+                    use #mod_name::*;
+
+                    #(#traits)*
+                };
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{assert_tokens_eq, parse_ok};
+    use quote::quote;
+
+    /// Verify that we declare the module's signatures in traits that only rust-analyzer sees.
+    #[test]
+    fn ide_stubs() {
+        let module = parse_ok(quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_method(&self, arg: u8) -> Vec<u32>;
+                    fn some_function(arg: SomeType) -> Option<String>;
+                }
+            }
+        });
+
+        assert_tokens_eq(
+            &module.ide_stub_tokens(),
+            &quote! {
+                #[allow(unknown_lints, unexpected_cfgs)]
+                const _: () = {
+                    #[cfg(rust_analyzer)]
+                    #[allow(non_camel_case_types, non_snake_case, dead_code, unused_imports)]
+                    const _: () = {
+                        use foo::*;
+
+                        trait __swift_bridge__SomeType {
+                            fn some_method(&self, arg: u8) -> Vec<u32>;
+                        }
+                        trait __swift_bridge__functions {
+                            fn some_function(arg: SomeType) -> Option<String>;
+                        }
+                    };
+                };
+            },
+        );
+    }
+}
@@ -76,7 +76,7 @@ impl SwiftBridgeModule {
             // Enums with variants that contain data are not yet supported.
             quote! {}
         } else {
-            generate_vec_of_transparent_enum_functions(&shared_enum)
+            generate_vec_of_transparent_enum_functions(&shared_enum, swift_bridge_path)
         };
 
         let definition = quote! {
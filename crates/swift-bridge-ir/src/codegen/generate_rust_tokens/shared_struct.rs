@@ -71,10 +71,17 @@ impl SwiftBridgeModule {
             &self.types,
         );
 
+        let ffi_repr_attribute = shared_struct.layout.to_repr_attribute_tokens();
+        let struct_repr_attribute = if shared_struct.layout.is_default() {
+            quote! {}
+        } else {
+            ffi_repr_attribute.clone()
+        };
+
         let struct_ffi_repr = if shared_struct.fields.is_empty() {
             // Using a u8 is arbitrary... We just need a field since empty structs aren't FFI safe.
             quote! {
-                #[repr(C)]
+                #ffi_repr_attribute
                 #[doc(hidden)]
                 pub struct #struct_ffi_name {
                     _private: u8
@@ -82,13 +89,14 @@ impl SwiftBridgeModule {
             }
         } else {
             quote! {
-                #[repr(C)]
+                #ffi_repr_attribute
                 #[doc(hidden)]
                 pub struct #struct_ffi_name #repr_c_struct_fields
             }
         };
 
         let definition = quote! {
+            #struct_repr_attribute
             pub struct #struct_name #struct_fields
 
             #struct_ffi_repr
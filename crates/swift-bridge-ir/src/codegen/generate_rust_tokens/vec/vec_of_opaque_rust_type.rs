@@ -1,15 +1,23 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
+use syn::Path;
 
 /// Generate the functions that Swift calls uses inside of the corresponding class for an opaque
 /// Rust type's Vectorizable implementation.
 ///
 /// So inside of `extension MyRustType: Vectorizable {}` on the Swift side.
-pub(in super::super) fn generate_vec_of_opaque_rust_type_functions(ty: &Ident) -> TokenStream {
+///
+/// The functions only convert between the FFI representation and the type, and leave the rest to
+/// the shared `swift_bridge::vec_support` helpers.
+pub(in super::super) fn generate_vec_of_opaque_rust_type_functions(
+    ty: &Ident,
+    symbol_prefix: &str,
+    swift_bridge_path: &Path,
+) -> TokenStream {
     // examples:
     // "__swift_bridge__$Vec_MyRustType$new"
     // "__swift_bridge__$Vec_MyRustType$drop"
-    let make_export_name = |fn_name| format!("__swift_bridge__$Vec_{}${}", ty, fn_name);
+    let make_export_name = |fn_name| format!("{}$Vec_{}${}", symbol_prefix, ty, fn_name);
     let export_name_new = make_export_name("new");
     let export_name_drop = make_export_name("drop");
     let export_name_len = make_export_name("len");
@@ -18,71 +26,83 @@ pub(in super::super) fn generate_vec_of_opaque_rust_type_functions(ty: &Ident) -
     let export_name_push = make_export_name("push");
     let export_name_pop = make_export_name("pop");
     let export_name_as_ptr = make_export_name("as_ptr");
+    let export_name_insert = make_export_name("insert");
+    let export_name_remove = make_export_name("remove");
+    let export_name_reserve = make_export_name("reserve");
+    let export_name_capacity = make_export_name("capacity");
 
     quote! {
         const _: () = {
             #[doc(hidden)]
             #[export_name = #export_name_new]
             pub extern "C" fn _new() -> *mut Vec<super::#ty> {
-                Box::into_raw(Box::new(Vec::new()))
+                #swift_bridge_path::vec_support::new()
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_drop]
             pub extern "C" fn _drop(vec: *mut Vec<super::#ty>) {
-                let vec = unsafe { Box::from_raw(vec) };
-                drop(vec)
+                unsafe { #swift_bridge_path::vec_support::free(vec) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_len]
             pub extern "C" fn _len(vec: *const Vec<super::#ty>) -> usize {
-                unsafe { &*vec }.len()
+                unsafe { #swift_bridge_path::vec_support::len(vec) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_get]
             pub extern "C" fn _get(vec: *const Vec<super::#ty>, index: usize) -> *const super::#ty {
-                let vec = unsafe { & *vec };
-                if let Some(val) = vec.get(index) {
-                    val as *const super::#ty
-                } else {
-                    std::ptr::null()
-                }
+                unsafe { #swift_bridge_path::vec_support::get(vec, index) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_get_mut]
             pub extern "C" fn _get_mut(vec: *mut Vec<super::#ty>, index: usize) -> *mut super::#ty {
-                let vec = unsafe { &mut *vec };
-                if let Some(val) = vec.get_mut(index) {
-                    val as *mut super::#ty
-                } else {
-                    std::ptr::null::<super::#ty>() as *mut super::#ty
-                }
+                unsafe { #swift_bridge_path::vec_support::get_mut(vec, index) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_push]
             pub extern "C" fn _push(vec: *mut Vec<super::#ty>, val: *mut super::#ty) {
-                unsafe { &mut *vec }.push( unsafe { *Box::from_raw(val) } )
+                unsafe { #swift_bridge_path::vec_support::push_boxed(vec, val) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_pop]
             pub extern "C" fn _pop(vec: *mut Vec<super::#ty>) -> *mut super::#ty {
-                let vec = unsafe { &mut *vec };
-                if let Some(val) = vec.pop() {
-                    Box::into_raw(Box::new(val))
-                } else {
-                    std::ptr::null::<super::#ty>() as *mut super::#ty
-                }
+                unsafe { #swift_bridge_path::vec_support::pop_boxed(vec) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_as_ptr]
             pub extern "C" fn _as_ptr(vec: *const Vec<super::#ty>) -> *const super::#ty {
-                unsafe { & *vec }.as_ptr()
+                unsafe { #swift_bridge_path::vec_support::as_ptr(vec) }
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_insert]
+            pub extern "C" fn _insert(vec: *mut Vec<super::#ty>, index: usize, val: *mut super::#ty) {
+                unsafe { #swift_bridge_path::vec_support::insert_boxed(vec, index, val) }
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_remove]
+            pub extern "C" fn _remove(vec: *mut Vec<super::#ty>, index: usize) -> *mut super::#ty {
+                unsafe { #swift_bridge_path::vec_support::remove_boxed(vec, index) }
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_reserve]
+            pub extern "C" fn _reserve(vec: *mut Vec<super::#ty>, additional: usize) {
+                unsafe { #swift_bridge_path::vec_support::reserve(vec, additional) }
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_capacity]
+            pub extern "C" fn _capacity(vec: *const Vec<super::#ty>) -> usize {
+                unsafe { #swift_bridge_path::vec_support::capacity(vec) }
             }
         };
     }
@@ -92,6 +112,7 @@ pub(in super::super) fn generate_vec_of_opaque_rust_type_functions(ty: &Ident) -
 mod tests {
     use super::*;
     use crate::test_utils::assert_tokens_eq;
+    use crate::SWIFT_BRIDGE_PREFIX;
     use proc_macro2::Span;
 
     /// Verify that we can generate the functions for an opaque Rust type that get exposed to Swift
@@ -104,74 +125,83 @@ mod tests {
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$new"]
                 pub extern "C" fn _new() -> *mut Vec<super::ARustType> {
-                    Box::into_raw(Box::new(Vec::new()))
+                    swift_bridge::vec_support::new()
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$drop"]
                 pub extern "C" fn _drop(vec: *mut Vec<super::ARustType>) {
-                    let vec = unsafe { Box::from_raw(vec) };
-                    drop(vec)
+                    unsafe { swift_bridge::vec_support::free(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$len"]
                 pub extern "C" fn _len(vec: *const Vec<super::ARustType>) -> usize {
-                    unsafe { &*vec }.len()
+                    unsafe { swift_bridge::vec_support::len(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$get"]
                 pub extern "C" fn _get(vec: *const Vec<super::ARustType>, index: usize) -> *const super::ARustType {
-                    let vec = unsafe { & *vec };
-                    if let Some(val) = vec.get(index) {
-                        val as *const super::ARustType
-                    } else {
-                        std::ptr::null()
-                    }
+                    unsafe { swift_bridge::vec_support::get(vec, index) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$get_mut"]
                 pub extern "C" fn _get_mut(vec: *mut Vec<super::ARustType>, index: usize) -> *mut super::ARustType {
-                    let vec = unsafe { &mut *vec };
-                    if let Some(val) = vec.get_mut(index) {
-                        val as *mut super::ARustType
-                    } else {
-                        std::ptr::null::<super::ARustType>() as *mut super::ARustType
-                    }
+                    unsafe { swift_bridge::vec_support::get_mut(vec, index) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$push"]
                 pub extern "C" fn _push(vec: *mut Vec<super::ARustType>, val: *mut super::ARustType) {
-                    unsafe { &mut *vec }.push(unsafe { * Box::from_raw(val) })
+                    unsafe { swift_bridge::vec_support::push_boxed(vec, val) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$pop"]
                 pub extern "C" fn _pop(vec: *mut Vec<super::ARustType>) -> *mut super::ARustType {
-                    let vec = unsafe { &mut *vec };
-                    if let Some(val) = vec.pop() {
-                        Box::into_raw(Box::new(val))
-                    } else {
-                        std::ptr::null::<super::ARustType>() as *mut super::ARustType
-                    }
+                    unsafe { swift_bridge::vec_support::pop_boxed(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_ARustType$as_ptr"]
                 pub extern "C" fn _as_ptr(vec: *const Vec<super::ARustType>) -> *const super::ARustType {
-                    unsafe { & *vec }.as_ptr()
+                    unsafe { swift_bridge::vec_support::as_ptr(vec) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_ARustType$insert"]
+                pub extern "C" fn _insert(vec: *mut Vec<super::ARustType>, index: usize, val: *mut super::ARustType) {
+                    unsafe { swift_bridge::vec_support::insert_boxed(vec, index, val) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_ARustType$remove"]
+                pub extern "C" fn _remove(vec: *mut Vec<super::ARustType>, index: usize) -> *mut super::ARustType {
+                    unsafe { swift_bridge::vec_support::remove_boxed(vec, index) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_ARustType$reserve"]
+                pub extern "C" fn _reserve(vec: *mut Vec<super::ARustType>, additional: usize) {
+                    unsafe { swift_bridge::vec_support::reserve(vec, additional) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_ARustType$capacity"]
+                pub extern "C" fn _capacity(vec: *const Vec<super::ARustType>) -> usize {
+                    unsafe { swift_bridge::vec_support::capacity(vec) }
                 }
             };
         };
 
         assert_tokens_eq(
-            &generate_vec_of_opaque_rust_type_functions(&Ident::new(
-                "ARustType",
-                Span::call_site(),
-            )),
+            &generate_vec_of_opaque_rust_type_functions(
+                &Ident::new("ARustType", Span::call_site()),
+                SWIFT_BRIDGE_PREFIX,
+                &syn::parse_quote!(swift_bridge),
+            ),
             &expected,
         );
     }
@@ -1,6 +1,7 @@
 use crate::bridged_type::SharedEnum;
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::Path;
 
 /// Generate the functions that Swift calls uses inside of the corresponding class for a
 /// transparent enum's Vectorizable implementation.
@@ -8,6 +9,7 @@ use quote::quote;
 /// So inside of `extension SomeTransparentEnum: Vectorizable {}` on the Swift side.
 pub(in super::super) fn generate_vec_of_transparent_enum_functions(
     shared_enum: &SharedEnum,
+    swift_bridge_path: &Path,
 ) -> TokenStream {
     let enum_name = &shared_enum.name;
 
@@ -16,7 +18,8 @@ pub(in super::super) fn generate_vec_of_transparent_enum_functions(
     // "__swift_bridge__$Vec_SomeTransparentEnum$drop"
     let make_export_name = |fn_name| {
         format!(
-            "__swift_bridge__$Vec_{}${}",
+            "{}$Vec_{}${}",
+            shared_enum.symbol_prefix,
             shared_enum.swift_name_string(),
             fn_name
         )
@@ -29,6 +32,10 @@ pub(in super::super) fn generate_vec_of_transparent_enum_functions(
     let export_name_push = make_export_name("push");
     let export_name_pop = make_export_name("pop");
     let export_name_as_ptr = make_export_name("as_ptr");
+    let export_name_insert = make_export_name("insert");
+    let export_name_remove = make_export_name("remove");
+    let export_name_reserve = make_export_name("reserve");
+    let export_name_capacity = make_export_name("capacity");
 
     let ffi_enum_repr = &shared_enum.ffi_name_tokens();
     let ffi_option_enum_repr = shared_enum.ffi_option_name_tokens();
@@ -38,56 +45,76 @@ pub(in super::super) fn generate_vec_of_transparent_enum_functions(
             #[doc(hidden)]
             #[export_name = #export_name_new]
             pub extern "C" fn _new() -> *mut Vec<#enum_name> {
-                Box::into_raw(Box::new(Vec::new()))
+                #swift_bridge_path::vec_support::new()
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_drop]
             pub extern "C" fn _drop(vec: *mut Vec<#enum_name>) {
-                let vec = unsafe { Box::from_raw(vec) };
-                drop(vec)
+                unsafe { #swift_bridge_path::vec_support::free(vec) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_len]
             pub extern "C" fn _len(vec: *const Vec<#enum_name>) -> usize {
-                unsafe { &*vec }.len()
+                unsafe { #swift_bridge_path::vec_support::len(vec) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_get]
             pub extern "C" fn _get(vec: *const Vec<#enum_name>, index: usize) -> #ffi_option_enum_repr {
-                let vec = unsafe { &*vec };
-                let val = vec.get(index).map(|v| *v);
+                let val = unsafe { #swift_bridge_path::vec_support::get_copy(vec, index) };
                 #ffi_option_enum_repr::from_rust_repr(val)
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_get_mut]
             pub extern "C" fn _get_mut(vec: *mut Vec<#enum_name>, index: usize) -> #ffi_option_enum_repr {
-                let vec = unsafe { &mut *vec };
-                let val = vec.get_mut(index).map(|v| *v);
+                let val = unsafe { #swift_bridge_path::vec_support::get_copy(vec, index) };
                 #ffi_option_enum_repr::from_rust_repr(val)
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_push]
             pub extern "C" fn _push(vec: *mut Vec<#enum_name>, val: #ffi_enum_repr) {
-                unsafe { &mut *vec }.push( val.into_rust_repr() )
+                unsafe { #swift_bridge_path::vec_support::push(vec, val.into_rust_repr()) }
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_pop]
             pub extern "C" fn _pop(vec: *mut Vec<#enum_name>) -> #ffi_option_enum_repr {
-                let vec = unsafe { &mut *vec };
-                let val = vec.pop();
+                let val = unsafe { #swift_bridge_path::vec_support::pop(vec) };
                 #ffi_option_enum_repr::from_rust_repr(val)
             }
 
             #[doc(hidden)]
             #[export_name = #export_name_as_ptr]
             pub extern "C" fn _as_ptr(vec: *const Vec<#enum_name>) -> *const #enum_name {
-                unsafe { & *vec }.as_ptr()
+                unsafe { #swift_bridge_path::vec_support::as_ptr(vec) }
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_insert]
+            pub extern "C" fn _insert(vec: *mut Vec<#enum_name>, index: usize, val: #ffi_enum_repr) {
+                unsafe { #swift_bridge_path::vec_support::insert(vec, index, val.into_rust_repr()) }
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_remove]
+            pub extern "C" fn _remove(vec: *mut Vec<#enum_name>, index: usize) -> #ffi_enum_repr {
+                unsafe { #swift_bridge_path::vec_support::remove(vec, index) }.into_ffi_repr()
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_reserve]
+            pub extern "C" fn _reserve(vec: *mut Vec<#enum_name>, additional: usize) {
+                unsafe { #swift_bridge_path::vec_support::reserve(vec, additional) }
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_capacity]
+            pub extern "C" fn _capacity(vec: *const Vec<#enum_name>) -> usize {
+                unsafe { #swift_bridge_path::vec_support::capacity(vec) }
             }
         };
     }
@@ -97,6 +124,7 @@ pub(in super::super) fn generate_vec_of_transparent_enum_functions(
 mod tests {
     use super::*;
     use crate::test_utils::assert_tokens_eq;
+    use crate::SWIFT_BRIDGE_PREFIX;
     use proc_macro2::{Ident, Span};
 
     /// Verify that we can generate the functions for an opaque Rust type that get exposed to Swift
@@ -109,56 +137,76 @@ mod tests {
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$new"]
                 pub extern "C" fn _new() -> *mut Vec<AnEnum> {
-                    Box::into_raw(Box::new(Vec::new()))
+                    swift_bridge::vec_support::new()
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$drop"]
                 pub extern "C" fn _drop(vec: *mut Vec<AnEnum>) {
-                    let vec = unsafe { Box::from_raw(vec) };
-                    drop(vec)
+                    unsafe { swift_bridge::vec_support::free(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$len"]
                 pub extern "C" fn _len(vec: *const Vec<AnEnum>) -> usize {
-                    unsafe { &*vec }.len()
+                    unsafe { swift_bridge::vec_support::len(vec) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$get"]
                 pub extern "C" fn _get(vec: *const Vec<AnEnum>, index: usize) -> __swift_bridge__Option_AnEnum {
-                    let vec = unsafe { &*vec };
-                    let val = vec.get(index).map(|v| *v);
+                    let val = unsafe { swift_bridge::vec_support::get_copy(vec, index) };
                     __swift_bridge__Option_AnEnum::from_rust_repr(val)
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$get_mut"]
                 pub extern "C" fn _get_mut(vec: *mut Vec<AnEnum>, index: usize) -> __swift_bridge__Option_AnEnum {
-                    let vec = unsafe { &mut *vec };
-                    let val = vec.get_mut(index).map(|v| *v);
+                    let val = unsafe { swift_bridge::vec_support::get_copy(vec, index) };
                     __swift_bridge__Option_AnEnum::from_rust_repr(val)
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$push"]
                 pub extern "C" fn _push(vec: *mut Vec<AnEnum>, val: __swift_bridge__AnEnum) {
-                    unsafe { &mut *vec }.push(val.into_rust_repr())
+                    unsafe { swift_bridge::vec_support::push(vec, val.into_rust_repr()) }
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$pop"]
                 pub extern "C" fn _pop(vec: *mut Vec<AnEnum>) -> __swift_bridge__Option_AnEnum {
-                    let vec = unsafe { &mut *vec };
-                    let val = vec.pop();
+                    let val = unsafe { swift_bridge::vec_support::pop(vec) };
                     __swift_bridge__Option_AnEnum::from_rust_repr(val)
                 }
 
                 #[doc(hidden)]
                 #[export_name = "__swift_bridge__$Vec_AnEnum$as_ptr"]
                 pub extern "C" fn _as_ptr(vec: *const Vec<AnEnum>) -> *const AnEnum {
-                    unsafe { & *vec }.as_ptr()
+                    unsafe { swift_bridge::vec_support::as_ptr(vec) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_AnEnum$insert"]
+                pub extern "C" fn _insert(vec: *mut Vec<AnEnum>, index: usize, val: __swift_bridge__AnEnum) {
+                    unsafe { swift_bridge::vec_support::insert(vec, index, val.into_rust_repr()) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_AnEnum$remove"]
+                pub extern "C" fn _remove(vec: *mut Vec<AnEnum>, index: usize) -> __swift_bridge__AnEnum {
+                    unsafe { swift_bridge::vec_support::remove(vec, index) }.into_ffi_repr()
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_AnEnum$reserve"]
+                pub extern "C" fn _reserve(vec: *mut Vec<AnEnum>, additional: usize) {
+                    unsafe { swift_bridge::vec_support::reserve(vec, additional) }
+                }
+
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_AnEnum$capacity"]
+                pub extern "C" fn _capacity(vec: *const Vec<AnEnum>) -> usize {
+                    unsafe { swift_bridge::vec_support::capacity(vec) }
                 }
             };
         };
@@ -168,9 +216,13 @@ mod tests {
             variants: vec![],
             already_declared: false,
             swift_name: None,
+            symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
         };
         assert_tokens_eq(
-            &generate_vec_of_transparent_enum_functions(&shared_enum),
+            &generate_vec_of_transparent_enum_functions(
+                &shared_enum,
+                &syn::parse_quote!(swift_bridge),
+            ),
             &expected,
         );
     }
@@ -2,8 +2,15 @@ use std::collections::HashMap;
 
 use syn::Path;
 
-use crate::bridged_type::{BridgeableType, BridgedType, TypePosition};
-use crate::codegen::generate_swift::generate_function_swift_calls_rust::gen_func_swift_calls_rust;
+use crate::bridge_module_attributes::{cfg_attrs_are_enabled, wrap_in_if, wrap_in_swift_if};
+use crate::bridged_type::{fn_arg_name, BridgeableType, BridgedType, TypePosition};
+use crate::codegen::generate_swift::async_stream::generate_async_stream;
+use crate::codegen::generate_swift::combine_publisher::generate_combine_publisher;
+use crate::codegen::generate_swift::generate_function_swift_calls_rust::{
+    gen_func_swift_calls_rust, swift_call_rust,
+};
+use crate::codegen::generate_swift::lock::generate_lock_methods;
+use crate::codegen::generate_swift::observable_object::generate_observable_object;
 use crate::codegen::generate_swift::opaque_copy_type::generate_opaque_copy_struct;
 use crate::codegen::generate_swift::swift_class::generate_swift_class;
 use crate::codegen::generate_swift::vec::generate_vectorizable_extension;
@@ -12,18 +19,38 @@ use crate::parse::{
     HostLang, OpaqueForeignTypeDeclaration, SharedTypeDeclaration, TypeDeclaration,
     TypeDeclarations,
 };
+use crate::parsed_const::ParsedConst;
 use crate::parsed_extern_fn::ParsedExternFn;
+use crate::parsed_extern_static::{AtomicOp, ParsedExternStatic};
+use crate::swift_keywords::{escape_swift_keyword, rename_swift_keyword};
 use crate::SwiftBridgeModule;
 
 mod vec;
 
+mod async_stream;
+mod combine_publisher;
 mod generate_function_swift_calls_rust;
+mod lock;
+mod observable_object;
 mod opaque_copy_type;
 mod shared_enum;
 mod shared_struct;
 mod swift_class;
 
 impl SwiftBridgeModule {
+    /// `@frozen ` for modules that use `#[swift_bridge(library_evolution)]`.
+    ///
+    /// Shared structs and enums have the same layout as their Rust definition, so with library
+    /// evolution enabled we freeze them to let other modules access their fields and switch over
+    /// their cases without going through resilient accessors.
+    pub(super) fn maybe_frozen(&self) -> &'static str {
+        if self.library_evolution {
+            "@frozen "
+        } else {
+            ""
+        }
+    }
+
     /// Generate the corresponding Swift code for the bridging module.
     pub(crate) fn generate_swift(&self, config: &CodegenConfig) -> String {
         let mut swift = "".to_string();
@@ -35,8 +62,31 @@ impl SwiftBridgeModule {
         let mut associated_funcs_and_methods: HashMap<String, Vec<&ParsedExternFn>> =
             HashMap::new();
         let mut class_protocols: HashMap<String, ClassProtocols> = HashMap::new();
+        let mut protocol_requirements: HashMap<String, Vec<&ParsedExternFn>> = HashMap::new();
+
+        for parsed_const in &self.consts {
+            if !cfg_attrs_are_enabled(&parsed_const.cfg_attrs, config) {
+                continue;
+            }
+
+            swift += &generate_swift_const(parsed_const, &self.types);
+            swift += "\n";
+        }
+
+        for parsed_static in &self.statics {
+            if !cfg_attrs_are_enabled(&parsed_static.cfg_attrs, config) {
+                continue;
+            }
+
+            swift += &generate_swift_static(parsed_static, &self.types);
+            swift += "\n";
+        }
 
         for function in &self.functions {
+            if !cfg_attrs_are_enabled(&function.all_cfg_attrs(), config) {
+                continue;
+            }
+
             if function.host_lang.is_rust() {
                 if let Some(ty) = function.associated_type.as_ref() {
                     match ty {
@@ -62,6 +112,53 @@ impl SwiftBridgeModule {
                                         TypePosition::FnReturn(opaque_ty.host_lang),
                                         &self.types,
                                     ),
+                                    is_property: false,
+                                    declare_id_type: false,
+                                    into_id: "",
+                                };
+                                class_protocols
+                                    .entry(opaque_ty.to_string())
+                                    .or_default()
+                                    .identifiable = Some(identifiable_protocol);
+                            }
+
+                            let is_identifiable_getter = opaque_ty
+                                .attributes
+                                .identifiable
+                                .as_ref()
+                                .map(|getter| function.func.sig.ident == getter.value())
+                                .unwrap_or(false);
+                            if is_identifiable_getter {
+                                let return_ty = BridgedType::new_with_return_type(
+                                    &function.func.sig.output,
+                                    &self.types,
+                                )
+                                .unwrap();
+                                let func_name = match function.swift_name_override.as_ref() {
+                                    Some(swift_name) => swift_name.value(),
+                                    None => function.func.sig.ident.to_string(),
+                                };
+                                // A `RustString` isn't `Hashable`, so we identify the
+                                // value by its Swift `String`.
+                                let (return_ty, into_id) =
+                                    if return_ty.is_string() && func_name != "id" {
+                                        ("String".to_string(), ".toString()")
+                                    } else {
+                                        (
+                                            return_ty.to_swift_type(
+                                                TypePosition::FnReturn(opaque_ty.host_lang),
+                                                &self.types,
+                                            ),
+                                            "",
+                                        )
+                                    };
+
+                                let identifiable_protocol = IdentifiableProtocol {
+                                    func_name,
+                                    return_ty,
+                                    is_property: function.is_swift_property_getter(),
+                                    declare_id_type: true,
+                                    into_id,
                                 };
                                 class_protocols
                                     .entry(opaque_ty.to_string())
@@ -74,6 +171,15 @@ impl SwiftBridgeModule {
                 }
             }
 
+            if let Some(TypeDeclaration::Opaque(opaque_ty)) = function.associated_type.as_ref() {
+                if opaque_ty.attributes.protocol && function.is_method() {
+                    protocol_requirements
+                        .entry(opaque_ty.to_string())
+                        .or_default()
+                        .push(function);
+                }
+            }
+
             let func_definition = match function.host_lang {
                 HostLang::Rust => {
                     gen_func_swift_calls_rust(function, &self.types, &self.swift_bridge_path)
@@ -85,7 +191,7 @@ impl SwiftBridgeModule {
                 ),
             };
 
-            swift += &func_definition;
+            swift += &wrap_in_swift_if(func_definition, &function.all_cfg_attrs(), "");
             swift += "\n";
         }
 
@@ -103,50 +209,109 @@ impl SwiftBridgeModule {
                         swift += "\n";
                     }
                 }
-                TypeDeclaration::Opaque(ty) => match ty.host_lang {
-                    HostLang::Rust => {
-                        if let Some(_copy) = ty.attributes.copy {
-                            swift += &generate_opaque_copy_struct(
-                                ty,
-                                &associated_funcs_and_methods,
-                                &self.types,
-                                &self.swift_bridge_path,
-                            );
-                        } else {
-                            let class_protocols = class_protocols.get(&ty.ty.to_string());
-                            let default_cp = ClassProtocols::default();
-                            let class_protocols = class_protocols.unwrap_or(&default_cp);
-
-                            swift += &generate_swift_class(
-                                ty,
-                                &associated_funcs_and_methods,
-                                class_protocols,
-                                &self.types,
-                                &self.swift_bridge_path,
-                            );
-                        }
+                TypeDeclaration::Opaque(ty) => {
+                    let cfg_attrs = &ty.attributes.cfg_attrs;
+                    if !cfg_attrs_are_enabled(cfg_attrs, config) {
+                        continue;
+                    }
+                    let ty_start = swift.len();
+
+                    match ty.host_lang {
+                        HostLang::Rust => {
+                            if let Some(_copy) = ty.attributes.copy {
+                                swift += &generate_opaque_copy_struct(
+                                    ty,
+                                    &associated_funcs_and_methods,
+                                    &self.types,
+                                    &self.swift_bridge_path,
+                                );
+                            } else {
+                                let class_protocols = class_protocols.get(&ty.ty.to_string());
+                                let default_cp = ClassProtocols::default();
+                                let class_protocols = class_protocols.unwrap_or(&default_cp);
+
+                                swift += &generate_swift_class(
+                                    ty,
+                                    &associated_funcs_and_methods,
+                                    class_protocols,
+                                    &self.types,
+                                    &self.swift_bridge_path,
+                                );
+
+                                if ty.attributes.observable_object && ty.generics.is_empty() {
+                                    swift += "\n";
+                                    swift += &generate_observable_object(
+                                        ty,
+                                        &associated_funcs_and_methods,
+                                        &self.types,
+                                    );
+                                }
+
+                                if let Some(output) = ty.attributes.publisher.as_ref() {
+                                    if ty.generics.is_empty() {
+                                        swift += "\n";
+                                        swift +=
+                                            &generate_combine_publisher(ty, output, &self.types);
+                                    }
+                                }
+
+                                if let Some(output) = ty.attributes.async_stream.as_ref() {
+                                    if ty.generics.is_empty() {
+                                        swift += "\n";
+                                        swift += &generate_async_stream(ty, output, &self.types);
+                                    }
+                                }
+
+                                if let Some(lock) = ty.attributes.lock.as_ref() {
+                                    if ty.generics.is_empty() {
+                                        swift += "\n";
+                                        swift += &generate_lock_methods(
+                                            ty,
+                                            lock.kind,
+                                            &lock.guarded,
+                                            &self.types,
+                                        );
+                                    }
+                                }
+                            }
 
-                        swift += "\n";
+                            swift += "\n";
 
-                        if !ty.attributes.already_declared {
-                            // TODO: Support Vec<OpaqueCopyType>. Add codegen tests and then
-                            //  make them pass.
-                            // TODO: Support Vec<GenericOpaqueRustType
-                            if ty.attributes.copy.is_none() && ty.generics.len() == 0 {
-                                swift += &generate_vectorizable_extension(&ty);
+                            if !ty.attributes.already_declared {
+                                // TODO: Support Vec<OpaqueCopyType>. Add codegen tests and then
+                                //  make them pass.
+                                // TODO: Support Vec<GenericOpaqueRustType
+                                if ty.attributes.copy.is_none() && ty.generics.len() == 0 {
+                                    swift += &generate_vectorizable_extension(ty);
+                                    swift += "\n";
+                                }
+                            }
+                        }
+                        HostLang::Swift => {
+                            if !ty.attributes.already_declared {
+                                if ty.attributes.protocol {
+                                    let requirements = protocol_requirements
+                                        .get(&ty.ty.to_string())
+                                        .map(|r| r.as_slice())
+                                        .unwrap_or(&[]);
+                                    swift +=
+                                        &generate_swift_protocol(ty, requirements, &self.types);
+                                    swift += "\n";
+                                }
+
+                                swift += &generate_drop_swift_instance_reference_count(ty);
                                 swift += "\n";
                             }
                         }
                     }
-                    HostLang::Swift => {
-                        swift += &generate_drop_swift_instance_reference_count(ty);
-                        swift += "\n";
-                    }
-                },
+
+                    let ty_swift = swift.split_off(ty_start);
+                    swift += &wrap_in_swift_if(ty_swift, cfg_attrs, "");
+                }
             };
         }
 
-        swift
+        wrap_in_swift_if(swift, &self.cfg_attrs, "")
     }
 }
 
@@ -158,6 +323,217 @@ struct ClassProtocols {
 struct IdentifiableProtocol {
     func_name: String,
     return_ty: String,
+    /// Whether the function is a `swift_property` getter, so Swift reads it as `self.name`.
+    is_property: bool,
+    /// Whether to declare the `ID` associated type instead of letting Swift infer it.
+    declare_id_type: bool,
+    /// Converts the function's return value into the `ID`, such as `.toString()`.
+    into_id: &'static str,
+}
+
+/// Convert a Rust doc comment into a Swift doc comment.
+///
+/// `" Some comment"` becomes `"/// Some comment\n"`.
+pub(super) fn swift_doc_comment(doc_comment: Option<&String>, indentation: &str) -> String {
+    let doc_comment = match doc_comment {
+        Some(doc_comment) => doc_comment,
+        None => return "".to_string(),
+    };
+
+    let mut swift_doc_comment = "".to_string();
+    for line in doc_comment.lines() {
+        swift_doc_comment += &format!("{}///{}\n", indentation, line);
+    }
+
+    swift_doc_comment
+}
+
+/// Generate a Swift constant that reads the value that the Rust side exported.
+///
+/// ```swift
+/// public let MAX_USERS: UInt32 = __swift_bridge__$MAX_USERS
+/// ```
+fn generate_swift_const(parsed_const: &ParsedConst, types: &TypeDeclarations) -> String {
+    let ty = BridgedType::new_with_type(&parsed_const.ty, types).unwrap();
+    let swift_ty = ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+    let swift_const = format!(
+        "{doc_comment}{access} let {ident}: {swift_ty} = {link_name}",
+        doc_comment = swift_doc_comment(parsed_const.doc_comment.as_ref(), ""),
+        access = parsed_const.swift_access.as_str(),
+        ident = parsed_const.ident,
+        swift_ty = swift_ty,
+        link_name = parsed_const.link_name(),
+    );
+
+    wrap_in_swift_if(swift_const, &parsed_const.cfg_attrs, "")
+}
+
+/// Generate a Swift property that holds a Rust static.
+///
+/// Swift lazily initializes global and static properties exactly once, even when they are
+/// first accessed from multiple threads at the same time, so we only call into Rust once.
+///
+/// ```swift
+/// public let SHARED: RegistryRef = RegistryRef(ptr: __swift_bridge__$SHARED())
+///
+/// // #[swift_bridge(associated_to = Registry)]
+/// extension Registry {
+///     public static let SHARED: RegistryRef = RegistryRef(ptr: __swift_bridge__$SHARED())
+/// }
+/// ```
+fn generate_swift_static(parsed_static: &ParsedExternStatic, types: &TypeDeclarations) -> String {
+    if parsed_static.atomic_value_type().is_some() {
+        return generate_swift_atomic_static(parsed_static, types);
+    }
+
+    let ty = parsed_static.bridged_type(types);
+    let swift_ty = ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+    let value = ty.convert_ffi_value_to_swift_value(
+        &format!("{}()", parsed_static.link_name()),
+        TypePosition::FnReturn(HostLang::Rust),
+        types,
+    );
+
+    let (indentation, maybe_static) = match parsed_static.associated_to {
+        Some(_) => ("    ", "static "),
+        None => ("", ""),
+    };
+
+    let swift_static = format!(
+        "{doc_comment}{indentation}{access} {maybe_static}let {ident}: {swift_ty} = {value}",
+        doc_comment = swift_doc_comment(parsed_static.doc_comment.as_ref(), indentation),
+        indentation = indentation,
+        access = parsed_static.swift_access.as_str(),
+        maybe_static = maybe_static,
+        ident = parsed_static.ident,
+        swift_ty = swift_ty,
+        value = value,
+    );
+
+    let swift_static = match parsed_static.associated_to.as_ref() {
+        Some(associated_to) => {
+            let associated_to = match types.get(&associated_to.to_string()) {
+                Some(TypeDeclaration::Opaque(opaque)) => opaque.swift_name_string(),
+                _ => associated_to.to_string(),
+            };
+            format!(
+                "extension {associated_to} {{\n{swift_static}\n}}",
+                associated_to = associated_to,
+                swift_static = swift_static
+            )
+        }
+        None => swift_static,
+    };
+
+    wrap_in_swift_if(swift_static, &parsed_static.cfg_attrs, "")
+}
+
+/// Generate a Swift enum with a static function for each of an atomic Rust static's operations.
+///
+/// ```swift
+/// public enum REQUEST_COUNT {
+///     /// Atomically loads the value.
+///     public static func load() -> UInt32 { __swift_bridge__$REQUEST_COUNT$load() }
+///     // ...
+/// }
+/// ```
+fn generate_swift_atomic_static(
+    parsed_static: &ParsedExternStatic,
+    types: &TypeDeclarations,
+) -> String {
+    let value_ty = parsed_static.atomic_value_type().unwrap();
+    let value_ty = BridgedType::new_with_type(&value_ty, types)
+        .unwrap()
+        .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+    let access = parsed_static.swift_access.as_str();
+
+    let indentation = match parsed_static.associated_to {
+        Some(_) => "    ",
+        None => "",
+    };
+
+    let mut fns = vec![];
+    for op in parsed_static.atomic_ops() {
+        let link_name = parsed_static.atomic_op_link_name(op);
+        let (param, arg) = if op.takes_value() {
+            (format!("_ value: {}", value_ty), "value")
+        } else {
+            ("".to_string(), "")
+        };
+        let ret = if op.returns_value() {
+            format!(" -> {}", value_ty)
+        } else {
+            "".to_string()
+        };
+        // Counters are often only incremented, so the previous value doesn't have to be used.
+        let maybe_discardable = if op.returns_value() && op != AtomicOp::Load {
+            format!("@discardableResult\n{indentation}    ")
+        } else {
+            "".to_string()
+        };
+
+        fns.push(format!(
+            "{indentation}    /// {doc}\n{indentation}    {maybe_discardable}{access} static func {name}({param}){ret} {{ {link_name}({arg}) }}",
+            doc = op.swift_doc_comment(),
+            name = op.swift_name(),
+        ));
+    }
+
+    let swift_static = format!(
+        "{doc_comment}{indentation}{access} enum {ident} {{\n{fns}\n{indentation}}}",
+        doc_comment = swift_doc_comment(parsed_static.doc_comment.as_ref(), indentation),
+        ident = parsed_static.ident,
+        fns = fns.join("\n"),
+    );
+
+    let swift_static = match parsed_static.associated_to.as_ref() {
+        Some(associated_to) => {
+            let associated_to = match types.get(&associated_to.to_string()) {
+                Some(TypeDeclaration::Opaque(opaque)) => opaque.swift_name_string(),
+                _ => associated_to.to_string(),
+            };
+            format!("extension {associated_to} {{\n{swift_static}\n}}")
+        }
+        None => swift_static,
+    };
+
+    wrap_in_swift_if(swift_static, &parsed_static.cfg_attrs, "")
+}
+
+/// Generate the Swift protocol for a `#[swift_bridge(protocol)]` type.
+///
+/// Rust holds on to any class instance that conforms to the protocol, and calls its methods
+/// through the protocol.
+///
+/// ```swift
+/// public protocol DownloadDelegate: AnyObject {
+///     func on_progress(percent: UInt8)
+/// }
+/// ```
+fn generate_swift_protocol(
+    ty: &OpaqueForeignTypeDeclaration,
+    requirements: &[&ParsedExternFn],
+    types: &TypeDeclarations,
+) -> String {
+    let mut body = "".to_string();
+    for requirement in requirements {
+        let func = format!(
+            "{doc_comment}    {requirement}",
+            doc_comment = swift_doc_comment(requirement.doc_comment.as_ref(), "    "),
+            requirement = requirement.to_swift_protocol_requirement(types)
+        );
+        body += &wrap_in_swift_if(func, &requirement.all_cfg_attrs(), "    ");
+        body += "\n";
+    }
+
+    format!(
+        "{doc_comment}{access} protocol {ty_name}: AnyObject {{\n{body}}}",
+        doc_comment = swift_doc_comment(ty.attributes.doc_comment.as_ref(), ""),
+        access = ty.swift_access().as_str(),
+        ty_name = ty.swift_name_string(),
+        body = body
+    )
 }
 
 // Generate functions to drop the reference count on a Swift class instance.
@@ -183,10 +559,22 @@ func {fn_name} (ptr: UnsafeMutableRawPointer) {{
 "##,
         link_name = link_name,
         fn_name = fn_name,
-        ty_name = ty.ty_name_ident()
+        ty_name = swift_unmanaged_type_name(ty)
     )
 }
 
+/// The type to use for an `Unmanaged` reference to an instance of an extern "Swift" type.
+///
+/// A `#[swift_bridge(protocol)]` value is an existential that `Unmanaged` can't hold, so we use
+/// `AnyObject` instead.
+fn swift_unmanaged_type_name(ty: &OpaqueForeignTypeDeclaration) -> String {
+    if ty.attributes.protocol {
+        "AnyObject".to_string()
+    } else {
+        ty.swift_name_string()
+    }
+}
+
 fn gen_function_exposes_swift_to_rust(
     func: &ParsedExternFn,
     types: &TypeDeclarations,
@@ -204,7 +592,8 @@ fn gen_function_exposes_swift_to_rust(
     let ret = func.to_swift_return_type(types);
 
     let args = func.to_swift_call_args(false, true, types, swift_bridge_path);
-    let mut call_fn = format!("{}({})", fn_name, args);
+    let mut call_fn = format!("{}({})", escape_swift_keyword(&fn_name), args);
+    let mut retain_this = "".to_string();
 
     if let Some(built_in) = BridgedType::new_with_return_type(&func.sig.output, types) {
         if let Some(associated_type) = func.associated_type.as_ref() {
@@ -213,15 +602,41 @@ fn gen_function_exposes_swift_to_rust(
                     //
                     todo!()
                 }
-                TypeDeclaration::Opaque(associated_type) => associated_type.to_string(),
+                TypeDeclaration::Opaque(associated_type) => associated_type.swift_name_string(),
             };
 
             if func.is_method() {
-                call_fn = format!(
-                    "Unmanaged<{ty_name}>.fromOpaque(this).takeUnretainedValue().{call_fn}",
-                    ty_name = ty_name,
-                    call_fn = call_fn
-                );
+                let this = match associated_type {
+                    TypeDeclaration::Opaque(associated_type)
+                        if associated_type.attributes.protocol =>
+                    {
+                        format!(
+                            "(Unmanaged<AnyObject>.fromOpaque(this).takeUnretainedValue() as! {})",
+                            ty_name
+                        )
+                    }
+                    _ => format!(
+                        "Unmanaged<{}>.fromOpaque(this).takeUnretainedValue()",
+                        ty_name
+                    ),
+                };
+                // A `queue` function runs after Rust's call has returned, and Rust might have
+                // released the Swift object by then, so we retain it before dispatching.
+                let this = if func.queue.is_some() {
+                    retain_this = format!("let this = {}\n    ", this);
+                    "this".to_string()
+                } else {
+                    this
+                };
+                call_fn = format!("{this}.{call_fn}", this = this, call_fn = call_fn);
+
+                // Rust calls from outside of the actor's isolation, so we wait for the actor to
+                // run the method.
+                if let TypeDeclaration::Opaque(associated_type) = associated_type {
+                    if associated_type.attributes.actor {
+                        call_fn = format!("__swift_bridge__awaitActor({{ await {} }})", call_fn);
+                    }
+                }
 
                 call_fn = built_in.convert_swift_expression_to_ffi_type(
                     &call_fn,
@@ -268,7 +683,8 @@ fn gen_function_exposes_swift_to_rust(
         };
 
         let ret_value = format!(
-            "__swift_bridge__{maybe_associated_ty}${fn_name}$param{idx}(ptr{swift_ffi_call_args})"
+            "{call_boxed_fn}(ptr{swift_ffi_call_args})",
+            call_boxed_fn = func.call_boxed_fn_link_name(idx)
         );
         let ret_value = boxed_fn.ret.convert_ffi_expression_to_swift_type(
             &ret_value,
@@ -280,7 +696,7 @@ fn gen_function_exposes_swift_to_rust(
 
         rust_fn_once_callback_classes += &format!(
             r#"
-class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
+class {callback_class}{maybe_associated_ty}${fn_name}$param{idx} {{
     var ptr: UnsafeMutableRawPointer
     var called = false
 
@@ -290,7 +706,7 @@ class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
 
     deinit {{
         if !called {{
-            __swift_bridge__{maybe_associated_ty}${fn_name}$_free$param{idx}(ptr)
+            {free_boxed_fn}(ptr)
         }}
     }}
 
@@ -301,7 +717,9 @@ class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
         called = true
         return {ret_value}
     }}
-}}"#
+}}"#,
+            callback_class = types.private_type_name("RustFnOnceCallback"),
+            free_boxed_fn = func.free_boxed_fn_link_name(idx)
         );
     }
 
@@ -317,12 +735,31 @@ class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
         call_fn = format!("{{ {callback_initializers} {maybe_ret}{call_fn} }}()")
     }
 
+    if func.is_main_actor {
+        call_fn = format!(
+            r#"if Thread.isMainThread {{
+        return MainActor.assumeIsolated {{ {call_fn} }}
+    }} else {{
+        return DispatchQueue.main.sync {{ MainActor.assumeIsolated {{ {call_fn} }} }}
+    }}"#
+        );
+    }
+
+    if let Some(queue) = func.queue.as_ref() {
+        let queue = match queue.value().as_str() {
+            "main" => "DispatchQueue.main".to_string(),
+            queue => queue.to_string(),
+        };
+        call_fn = format!("{retain_this}{queue}.async {{ {call_fn} }}");
+    }
+
     let generated_func = format!(
         r#"@_cdecl("{link_name}")
-func {prefixed_fn_name} ({params}){ret} {{
+{maybe_available}func {prefixed_fn_name} ({params}){ret} {{
     {call_fn}
 }}{rust_fn_once_callback_classes}
 "#,
+        maybe_available = func.maybe_swift_available_attribute("", types),
         link_name = link_name,
         prefixed_fn_name = prefixed_fn_name,
         params = params,
@@ -330,7 +767,18 @@ func {prefixed_fn_name} ({params}){ret} {{
         call_fn = call_fn
     );
 
-    generated_func
+    if func.uses_128_bit_int(types) {
+        guard_int128(generated_func, "")
+    } else {
+        generated_func
+    }
+}
+
+/// Put Swift code that uses `UInt128` or `Int128` inside of `#if compiler(>=6.0)`, since they are
+/// only available starting with Swift 6. The code also needs a `SWIFT_INT128_AVAILABILITY`
+/// attribute.
+fn guard_int128(swift: String, indentation: &str) -> String {
+    wrap_in_if(swift, "compiler(>=6.0)", indentation)
 }
 
 struct ClassMethods {
@@ -353,7 +801,34 @@ fn generate_swift_class_methods(
 
     if let Some(methods) = associated_funcs_and_methods.get(type_name) {
         for type_method in methods {
+            if type_method.swift_property_setter_name().is_some() {
+                // Generated along with the property's getter.
+                continue;
+            }
+
+            if type_method.is_swift_property_getter() {
+                let setter = methods.iter().find(|setter| {
+                    setter.swift_property_setter_name().as_deref()
+                        == Some(type_method.sig.ident.to_string().as_str())
+                });
+
+                let property =
+                    generate_swift_property(type_method, setter.copied(), types, swift_bridge_path);
+                let property = wrap_in_swift_if(property, &type_method.cfg_attrs, "    ");
+
+                // A property with a setter needs a mutable reference to the Rust type.
+                if setter.is_some() {
+                    ref_mut_self_methods.push(property);
+                } else {
+                    ref_self_methods.push(property);
+                }
+                continue;
+            }
+
             let func_definition = gen_func_swift_calls_rust(type_method, types, swift_bridge_path);
+            // Methods are generated inside of their type's class or extension, which already
+            // handles the type's own `cfg` attributes.
+            let func_definition = wrap_in_swift_if(func_definition, &type_method.cfg_attrs, "    ");
 
             let is_class_func = type_method.func.sig.inputs.is_empty();
 
@@ -383,6 +858,77 @@ fn generate_swift_class_methods(
     }
 }
 
+/// Generate a Swift computed property from `#[swift_bridge(swift_property)]` methods.
+///
+/// ```swift
+/// public var title: RustString {
+///     get {
+///         RustString(ptr: __swift_bridge__$SomeType$title(ptr))
+///     }
+///     set(title) {
+///         __swift_bridge__$SomeType$set_title(ptr, { ... }())
+///     }
+/// }
+/// ```
+fn generate_swift_property(
+    getter: &ParsedExternFn,
+    setter: Option<&ParsedExternFn>,
+    types: &TypeDeclarations,
+    swift_bridge_path: &Path,
+) -> String {
+    let name = match getter.swift_name_override.as_ref() {
+        Some(swift_name) => swift_name.value(),
+        None => getter.sig.ident.to_string(),
+    };
+    let ty = BridgedType::new_with_return_type(&getter.sig.output, types)
+        .unwrap()
+        .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+    let get_body = swift_call_rust(getter, types, swift_bridge_path, "        ");
+
+    let maybe_setter = match setter {
+        Some(setter) => {
+            let arg_name = fn_arg_name(setter.sig.inputs.iter().nth(1).unwrap())
+                .unwrap()
+                .to_string();
+            let arg_name = rename_swift_keyword(&arg_name);
+            let set_body = swift_call_rust(setter, types, swift_bridge_path, "        ");
+
+            format!(
+                r#"
+        set({arg_name}) {{
+            {set_body}
+        }}"#,
+                arg_name = arg_name,
+                set_body = set_body
+            )
+        }
+        None => "".to_string(),
+    };
+
+    let property = format!(
+        r#"{doc_comment}{maybe_deprecated}{maybe_available}    {access} var {name}: {ty} {{
+        get {{
+            {get_body}
+        }}{maybe_setter}
+    }}"#,
+        doc_comment = swift_doc_comment(getter.doc_comment.as_ref(), "    "),
+        maybe_deprecated = getter.maybe_swift_deprecated_attribute("    "),
+        maybe_available = getter.maybe_swift_available_attribute("    ", types),
+        access = getter.swift_access.as_str(),
+        name = escape_swift_keyword(&name),
+        ty = ty,
+        get_body = get_body,
+        maybe_setter = maybe_setter
+    );
+
+    if getter.uses_128_bit_int(types) {
+        guard_int128(property, "    ")
+    } else {
+        property
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! More tests can be found in src/codegen/codegen_tests.rs and its submodules.
@@ -391,6 +937,7 @@ mod tests {
     use quote::quote;
     use syn::parse_quote;
 
+    use crate::swift_keywords::{escape_swift_keyword, rename_swift_keyword};
     use crate::test_utils::assert_trimmed_generated_contains_trimmed_expected;
     use crate::SwiftBridgeModule;
 
@@ -0,0 +1,46 @@
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::parse::{HostLang, OpaqueForeignTypeDeclaration};
+use crate::TypeDeclarations;
+use syn::Type;
+
+/// Generate the `AsyncStream` for a `#[swift_bridge(AsyncStream(SomeType))]` type.
+///
+/// The stream awaits the type's generated `next` method each time that it is iterated, so the
+/// Rust channel only gives up a value when Swift asks for one.
+///
+/// ```swift
+/// extension EventReceiver {
+///     public func stream() -> AsyncStream<Event> {
+///         AsyncStream(unfolding: { try? await self.next() })
+///     }
+/// }
+/// ```
+pub(super) fn generate_async_stream(
+    ty: &OpaqueForeignTypeDeclaration,
+    output: &Type,
+    types: &TypeDeclarations,
+) -> String {
+    let type_name = ty.swift_name_string();
+    let access = ty.swift_access().as_str();
+
+    let output = BridgedType::new_with_type(output, types)
+        .unwrap()
+        .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+    format!(
+        r#"extension {type_name} {{
+    /// An `AsyncStream` that yields the values sent to this Rust channel, and finishes once every
+    /// Rust sender has been dropped or the task that iterates over it is cancelled.
+    ///
+    /// Streams pull values from the same channel receiver, so each value is delivered to only one
+    /// of them.
+    {access} func stream() -> AsyncStream<{output}> {{
+        AsyncStream(unfolding: {{ try? await self.next() }})
+    }}
+}}
+"#,
+        type_name = type_name,
+        access = access,
+        output = output,
+    )
+}
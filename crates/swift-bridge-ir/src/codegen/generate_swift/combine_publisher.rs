@@ -0,0 +1,123 @@
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::parse::{HostLang, OpaqueForeignTypeDeclaration};
+use crate::{TypeDeclarations, SWIFT_BRIDGE_PREFIX};
+use syn::Type;
+
+/// Generate the Combine publisher for a `#[swift_bridge(Publisher(SomeType))]` type.
+///
+/// Each subscription pulls values from the Rust broadcast channel on a background queue, one
+/// value per unit of demand.
+///
+/// ```swift
+/// extension EventSource {
+///     public func publisher() -> AnyPublisher<Event, Never> {
+///         __swift_bridge__EventSourcePublisher(source: self).eraseToAnyPublisher()
+///     }
+/// }
+/// ```
+pub(super) fn generate_combine_publisher(
+    ty: &OpaqueForeignTypeDeclaration,
+    output: &Type,
+    types: &TypeDeclarations,
+) -> String {
+    let type_name = ty.swift_name_string();
+    let rust_type_name = ty.ty.to_string();
+    let access = ty.swift_access().as_str();
+
+    let output = BridgedType::new_with_type(output, types)
+        .unwrap()
+        .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+    let publisher = format!("{}{}Publisher", SWIFT_BRIDGE_PREFIX, rust_type_name);
+    let subscription = format!("{}{}Subscription", SWIFT_BRIDGE_PREFIX, rust_type_name);
+
+    format!(
+        r#"#if canImport(Combine)
+import Combine
+
+extension {type_name} {{
+    /// A publisher that emits the values sent to this Rust broadcast channel, and finishes
+    /// once every Rust sender has been dropped.
+    ///
+    /// Subscriptions pull values from the same channel receiver, so each value is delivered to
+    /// only one of them. Use `share()` to deliver each value to every subscriber.
+    {access} func publisher() -> AnyPublisher<{output}, Never> {{
+        {publisher}(source: self).eraseToAnyPublisher()
+    }}
+}}
+fileprivate struct {publisher}: Publisher {{
+    typealias Output = {output}
+    typealias Failure = Never
+
+    let source: {type_name}
+    let queue = DispatchQueue(label: "swift-bridge.{type_name}.publisher")
+
+    func receive<S: Subscriber>(subscriber: S) where S.Input == {output}, S.Failure == Never {{
+        subscriber.receive(subscription: {subscription}(source: source, queue: queue, subscriber: AnySubscriber(subscriber)))
+    }}
+}}
+fileprivate final class {subscription}: Subscription {{
+    private let source: {type_name}
+    private let queue: DispatchQueue
+    private let lock = NSLock()
+    private var subscriber: AnySubscriber<{output}, Never>?
+    private var demand: Subscribers.Demand = .none
+    private var isPulling = false
+
+    init(source: {type_name}, queue: DispatchQueue, subscriber: AnySubscriber<{output}, Never>) {{
+        self.source = source
+        self.queue = queue
+        self.subscriber = subscriber
+    }}
+
+    func request(_ demand: Subscribers.Demand) {{
+        lock.lock()
+        self.demand += demand
+        let shouldPull = !isPulling && subscriber != nil
+        isPulling = isPulling || shouldPull
+        lock.unlock()
+
+        if shouldPull {{
+            queue.async {{ self.pull() }}
+        }}
+    }}
+
+    func cancel() {{
+        lock.lock()
+        subscriber = nil
+        lock.unlock()
+    }}
+
+    private func pull() {{
+        while true {{
+            lock.lock()
+            guard let subscriber = subscriber, demand > 0 else {{
+                isPulling = false
+                lock.unlock()
+                return
+            }}
+            demand -= 1
+            lock.unlock()
+
+            guard let value = source.next_blocking() else {{
+                cancel()
+                subscriber.receive(completion: .finished)
+                return
+            }}
+
+            let additionalDemand = subscriber.receive(value)
+            lock.lock()
+            demand += additionalDemand
+            lock.unlock()
+        }}
+    }}
+}}
+#endif
+"#,
+        type_name = type_name,
+        access = access,
+        output = output,
+        publisher = publisher,
+        subscription = subscription,
+    )
+}
@@ -1,6 +1,9 @@
+use crate::bridge_module_attributes::PanicPolicy;
 use crate::bridged_type::{fn_arg_name, BridgeableType, BridgedType, StdLibType, TypePosition};
+use crate::codegen::generate_swift::{guard_int128, swift_doc_comment};
 use crate::parse::{HostLang, TypeDeclaration};
-use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
+use crate::swift_keywords::{escape_swift_keyword, rename_swift_keyword};
+use crate::{ParsedExternFn, TypeDeclarations};
 use quote::ToTokens;
 use std::ops::Deref;
 use syn::{Path, ReturnType, Type};
@@ -12,33 +15,7 @@ pub(super) fn gen_func_swift_calls_rust(
 ) -> String {
     let fn_name = function.sig.ident.to_string();
     let params = function.to_swift_param_names_and_types(false, types);
-    let call_args = function.to_swift_call_args(true, false, types, swift_bridge_path);
-
-    let call_fn = if function.sig.asyncness.is_some() {
-        let maybe_args = if function.sig.inputs.is_empty() {
-            "".to_string()
-        } else {
-            format!(", {}", call_args)
-        };
-
-        format!("{}(wrapperPtr, onComplete{})", fn_name, maybe_args)
-    } else {
-        format!("{}({})", fn_name, call_args)
-    };
-
-    let maybe_type_name_segment = if let Some(ty) = function.associated_type.as_ref() {
-        match ty {
-            TypeDeclaration::Shared(_) => {
-                //
-                todo!()
-            }
-            TypeDeclaration::Opaque(ty) => {
-                format!("${}", ty.to_string())
-            }
-        }
-    } else {
-        "".to_string()
-    };
+    let maybe_type_name_segment = swift_type_name_segment(function);
 
     let maybe_static_class_func = if function.associated_type.is_some()
         && (!function.is_method() && !function.is_swift_initializer)
@@ -52,17 +29,47 @@ pub(super) fn gen_func_swift_calls_rust(
         ""
     };
 
-    let public_func_fn_name = if function.is_swift_initializer {
+    // An `open` type's methods can be overridden, and its initializers are designated
+    // initializers so that subclasses can call them.
+    let is_open_class_member = match function.associated_type.as_ref() {
+        Some(TypeDeclaration::Opaque(ty)) => ty.attributes.open,
+        _ => false,
+    };
+
+    let access = if is_open_class_member && function.swift_access.as_str() == "public" {
+        "open"
+    } else {
+        function.swift_access.as_str()
+    };
+    let public_func_fn_name = if function.is_swift_initializer && is_open_class_member {
+        let maybe_failable = if function.is_failable_swift_initializer() {
+            "?"
+        } else {
+            ""
+        };
+
+        format!("{} init{}", function.swift_access.as_str(), maybe_failable)
+    } else if function.is_swift_initializer {
+        let maybe_failable = if function.is_failable_swift_initializer() {
+            "?"
+        } else {
+            ""
+        };
+
         if function.is_copy_method_on_opaque_type() {
-            "public init".to_string()
+            format!("{} init{}", access, maybe_failable)
         } else {
-            "public convenience init".to_string()
+            format!("{} convenience init{}", access, maybe_failable)
         }
     } else {
         if let Some(swift_name) = &function.swift_name_override {
-            format!("public func {}", swift_name.value())
+            format!(
+                "{} func {}",
+                access,
+                escape_swift_keyword(&swift_name.value())
+            )
         } else {
-            format!("public func {}", fn_name.as_str())
+            format!("{} func {}", access, escape_swift_keyword(&fn_name))
         }
     };
 
@@ -72,9 +79,369 @@ pub(super) fn gen_func_swift_calls_rust(
         ""
     };
 
+    let mut call_rust = swift_call_rust(function, types, swift_bridge_path, indentation);
+
+    let init_ptr = if is_open_class_member {
+        "super.init(ptr:"
+    } else {
+        "self.init(ptr:"
+    };
+
+    // Async initializers call `init(ptr:)` once the Rust future completes.
+    if function.is_swift_initializer && function.sig.asyncness.is_none() {
+        if (function.is_failable_swift_initializer() || function.is_throwing_swift_initializer())
+            && function.is_copy_method_on_opaque_type()
+        {
+            unreachable!(
+                "Failable and throwing initializers of Copy types are rejected while parsing"
+            )
+        }
+
+        if function.is_failable_swift_initializer() {
+            call_rust = format!(
+                r#"guard let val = {call_rust} else {{
+{indentation}        return nil
+{indentation}    }}
+{indentation}    {init_ptr} val)"#,
+                call_rust = call_rust,
+                init_ptr = init_ptr,
+                indentation = indentation,
+            )
+        } else if function.is_throwing_swift_initializer() {
+            let throw_err = match function.return_ty_built_in(types) {
+                Some(BridgedType::StdLib(StdLibType::Result(result))) => {
+                    result.err_ty.convert_ffi_expression_to_swift_type(
+                        "val.ok_or_err!",
+                        TypePosition::FnReturn(function.host_lang),
+                        types,
+                    )
+                }
+                _ => unreachable!(),
+            };
+
+            // `#[swift_bridge(panic = "throw")]` modules throw the panic before looking at the
+            // `Result`, which Rust leaves empty when it panics.
+            let maybe_throw_panic = if function.panic_policy == Some(PanicPolicy::Throw) {
+                format!("\n{indentation}    try throwIfRustPanicked()")
+            } else {
+                "".to_string()
+            };
+
+            call_rust = format!(
+                r#"let val = {call_rust}{maybe_throw_panic}
+{indentation}    if val.is_ok {{
+{indentation}        {init_ptr} val.ok_or_err!)
+{indentation}    }} else {{
+{indentation}        throw {throw_err}
+{indentation}    }}"#,
+                call_rust = call_rust,
+                maybe_throw_panic = maybe_throw_panic,
+                init_ptr = init_ptr,
+                throw_err = throw_err,
+                indentation = indentation,
+            )
+        } else if function.is_copy_method_on_opaque_type() {
+            call_rust = format!("self.bytes = {}", call_rust)
+        } else {
+            call_rust = format!("{} {})", init_ptr, call_rust)
+        }
+    }
+
+    let maybe_return = if function.is_throwing_swift_initializer() {
+        " throws".to_string()
+    } else if function.is_swift_initializer {
+        "".to_string()
+    } else {
+        function.to_swift_return_type(types)
+    };
+
+    let maybe_generics = function.maybe_swift_generics(types);
+
+    let doc_comment = swift_doc_comment(function.doc_comment.as_ref(), indentation);
+    let maybe_deprecated = function.maybe_swift_deprecated_attribute(indentation);
+    let maybe_available = function.maybe_swift_available_attribute(indentation, types);
+    // `@inlinable` would let other modules inline the accessor too, but it can't be used here since
+    // the body uses the internal `ptr` property and the C functions from the bridging header.
+    let maybe_inline = if function.is_trivial_accessor() {
+        format!("{indentation}@inline(__always)\n")
+    } else {
+        "".to_string()
+    };
+
+    let func_definition = if function.sig.asyncness.is_some() {
+        let func_ret_ty = function.return_ty_built_in(types).unwrap();
+        // Async initializers resume the continuation with the pointer to the new Rust value, so
+        // that they can initialize `self` with it.
+        let rust_fn_ret_ty = if function.is_swift_initializer {
+            "UnsafeMutableRawPointer".to_string()
+        } else {
+            func_ret_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+        };
+
+        let (maybe_on_complete_sig_ret_val, on_complete_ret_val) = if func_ret_ty.is_null() {
+            ("".to_string(), "()".to_string())
+        } else {
+            (
+                format!(
+                    ", rustFnRetVal: {}",
+                    func_ret_ty
+                        .to_swift_type(TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy, types)
+                ),
+                if function.is_swift_initializer {
+                    "rustFnRetVal!".to_string()
+                } else {
+                    func_ret_ty.convert_ffi_value_to_swift_value(
+                        "rustFnRetVal",
+                        TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy,
+                        types,
+                    )
+                },
+            )
+        };
+
+        let (bind_ret_val, maybe_init) = if function.is_swift_initializer {
+            ("let ptr =", format!("\n{init_ptr} ptr)"))
+        } else {
+            ("return", "".to_string())
+        };
+
+        let callback_wrapper_ty = format!("CbWrapper{}${}", maybe_type_name_segment, fn_name);
+
+        let fn_body = if function.is_cancellable {
+            cancellable_async_fn_body(
+                &callback_wrapper_ty,
+                &rust_fn_ret_ty,
+                &maybe_on_complete_sig_ret_val,
+                &on_complete_ret_val,
+                bind_ret_val,
+                &call_rust,
+            )
+        } else {
+            format!(
+                r#"func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?{maybe_on_complete_sig_ret_val}) {{
+    let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+    wrapper.cb(.success({on_complete_ret_val}))
+}}
+
+{bind_ret_val} await withCheckedContinuation({{ (continuation: CheckedContinuation<{rust_fn_ret_ty}, Never>) in
+    let callback = {{ rustFnRetVal in
+        continuation.resume(with: rustFnRetVal)
+    }}
+
+    let wrapper = {cb_wrapper_ty}(cb: callback)
+    let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+    {call_rust}
+}})"#,
+                rust_fn_ret_ty = rust_fn_ret_ty,
+                maybe_on_complete_sig_ret_val = maybe_on_complete_sig_ret_val,
+                on_complete_ret_val = on_complete_ret_val,
+                bind_ret_val = bind_ret_val,
+                cb_wrapper_ty = callback_wrapper_ty,
+                call_rust = call_rust,
+            )
+        };
+        let fn_body = format!("{fn_body}{maybe_init}");
+
+        let mut fn_body_indented = "".to_string();
+        for line in fn_body.lines() {
+            if line.len() > 0 {
+                fn_body_indented += &format!("{}    {}\n", indentation, line);
+            } else {
+                fn_body_indented += "\n"
+            }
+        }
+        let fn_body_indented = fn_body_indented.trim_end();
+
+        let error_ty = if function.is_cancellable {
+            "Error"
+        } else {
+            "Never"
+        };
+        let callback_wrapper = format!(
+            r#"{indentation}class {cb_wrapper_ty} {{
+{indentation}    var cb: (Result<{rust_fn_ret_ty}, {error_ty}>) -> ()
+{indentation}
+{indentation}    public init(cb: @escaping (Result<{rust_fn_ret_ty}, {error_ty}>) -> ()) {{
+{indentation}        self.cb = cb
+{indentation}    }}
+{indentation}}}"#,
+            indentation = indentation,
+            cb_wrapper_ty = callback_wrapper_ty
+        );
+
+        let maybe_return = if function.is_cancellable {
+            format!(" throws{}", maybe_return)
+        } else {
+            maybe_return
+        };
+
+        format!(
+            r#"{doc_comment}{maybe_deprecated}{maybe_available}{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}) async{maybe_ret} {{
+{fn_body_indented}
+{indentation}}}
+{callback_wrapper}"#,
+            doc_comment = doc_comment,
+            maybe_deprecated = maybe_deprecated,
+            maybe_available = maybe_available,
+            indentation = indentation,
+            maybe_static_class_func = maybe_static_class_func,
+            swift_class_func_name = public_func_fn_name,
+            maybe_generics = maybe_generics,
+            params = params,
+            maybe_ret = maybe_return,
+            fn_body_indented = fn_body_indented,
+            callback_wrapper = callback_wrapper
+        )
+    } else if function.is_blocking {
+        let swift_ret_ty = match function.return_ty_built_in(types) {
+            Some(ret) if !ret.is_null() => {
+                ret.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+            }
+            _ => "()".to_string(),
+        };
+
+        format!(
+            r#"{doc_comment}{maybe_deprecated}{maybe_available}{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}) async{maybe_ret} {{
+{indentation}    func callRust(){maybe_ret} {{
+{indentation}        {call_rust}
+{indentation}    }}
+
+{indentation}    return await withCheckedContinuation({{ (continuation: CheckedContinuation<{swift_ret_ty}, Never>) in
+{indentation}        DispatchQueue.global().async {{
+{indentation}            continuation.resume(returning: callRust())
+{indentation}        }}
+{indentation}    }})
+{indentation}}}"#,
+            doc_comment = doc_comment,
+            maybe_deprecated = maybe_deprecated,
+            maybe_available = maybe_available,
+            indentation = indentation,
+            maybe_static_class_func = maybe_static_class_func,
+            swift_class_func_name = public_func_fn_name,
+            maybe_generics = maybe_generics,
+            params = params,
+            maybe_ret = maybe_return,
+            call_rust = call_rust.replace('\n', "\n    "),
+            swift_ret_ty = swift_ret_ty,
+        )
+    } else {
+        format!(
+            r#"{doc_comment}{maybe_deprecated}{maybe_available}{maybe_inline}{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}){maybe_ret} {{
+{indentation}    {call_rust}
+{indentation}}}"#,
+            doc_comment = doc_comment,
+            maybe_deprecated = maybe_deprecated,
+            maybe_available = maybe_available,
+            maybe_inline = maybe_inline,
+            indentation = indentation,
+            maybe_static_class_func = maybe_static_class_func,
+            swift_class_func_name = public_func_fn_name,
+            maybe_generics = maybe_generics,
+            params = params,
+            maybe_ret = maybe_return,
+            call_rust = call_rust,
+        )
+    };
+
+    if function.uses_128_bit_int(types) {
+        guard_int128(func_definition, indentation)
+    } else {
+        func_definition
+    }
+}
+
+/// The body of a `#[swift_bridge(cancellable)]` async function.
+///
+/// Cancelling the Swift `Task` cancels the Rust future, which then calls `onCancelled` instead of
+/// `onComplete`. The cancellation is freed after the continuation is resumed, at which point
+/// Swift can no longer call the cancellation handler.
+fn cancellable_async_fn_body(
+    cb_wrapper_ty: &str,
+    rust_fn_ret_ty: &str,
+    maybe_on_complete_sig_ret_val: &str,
+    on_complete_ret_val: &str,
+    bind_ret_val: &str,
+    call_rust: &str,
+) -> String {
+    let mut call_rust_indented = "".to_string();
+    for line in call_rust.lines() {
+        call_rust_indented += &format!("        {}\n", line);
+    }
+
+    format!(
+        r#"func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?{maybe_on_complete_sig_ret_val}) {{
+    let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+    wrapper.cb(.success({on_complete_ret_val}))
+}}
+func onCancelled(cbWrapperPtr: UnsafeMutableRawPointer?) {{
+    let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+    wrapper.cb(.failure(CancellationError()))
+}}
+
+let cancellation = __swift_bridge__$AsyncCancellation$new()
+defer {{ __swift_bridge__$AsyncCancellation$free(cancellation) }}
+
+{bind_ret_val} try await withTaskCancellationHandler(operation: {{
+    try await withCheckedThrowingContinuation({{ (continuation: CheckedContinuation<{rust_fn_ret_ty}, Error>) in
+        let callback = {{ rustFnRetVal in
+            continuation.resume(with: rustFnRetVal)
+        }}
+
+        let wrapper = {cb_wrapper_ty}(cb: callback)
+        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+{call_rust}    }})
+}}, onCancel: {{
+    __swift_bridge__$AsyncCancellation$cancel(cancellation)
+}})"#,
+        cb_wrapper_ty = cb_wrapper_ty,
+        rust_fn_ret_ty = rust_fn_ret_ty,
+        maybe_on_complete_sig_ret_val = maybe_on_complete_sig_ret_val,
+        on_complete_ret_val = on_complete_ret_val,
+        bind_ret_val = bind_ret_val,
+        call_rust = call_rust_indented,
+    )
+}
+
+/// Swift code that calls the Rust function and converts its return value into a Swift value.
+///
+/// For example, `__swift_bridge__$SomeType$some_method(ptr, arg)`.
+pub(super) fn swift_call_rust(
+    function: &ParsedExternFn,
+    types: &TypeDeclarations,
+    swift_bridge_path: &Path,
+    indentation: &str,
+) -> String {
+    let fn_name = function.sig.ident.to_string();
+    let call_args = function.to_swift_call_args(true, false, types, swift_bridge_path);
+
+    let call_fn = if function.sig.asyncness.is_some() {
+        let maybe_args = if function.sig.inputs.is_empty() {
+            "".to_string()
+        } else {
+            format!(", {}", call_args)
+        };
+
+        let maybe_cancellation = if function.is_cancellable {
+            ", onCancelled, cancellation"
+        } else {
+            ""
+        };
+
+        format!(
+            "{}(wrapperPtr, onComplete{}{})",
+            fn_name, maybe_cancellation, maybe_args
+        )
+    } else {
+        format!("{}({})", fn_name, call_args)
+    };
+
+    let maybe_type_name_segment = swift_type_name_segment(function);
+
     let call_rust = format!(
         "{prefix}{type_name_segment}${call_fn}",
-        prefix = SWIFT_BRIDGE_PREFIX,
+        prefix = function.symbol_prefix,
         type_name_segment = maybe_type_name_segment,
         call_fn = call_fn
     );
@@ -146,7 +513,7 @@ pub(super) fn gen_func_swift_calls_rust(
         }
         let bridged_arg = bridged_arg.unwrap();
 
-        let arg_name = fn_arg_name(arg).unwrap().to_string();
+        let arg_name = rename_swift_keyword(&fn_arg_name(arg).unwrap().to_string());
 
         // TODO: Refactor to make less duplicative
         match bridged_arg {
@@ -176,119 +543,22 @@ pub(super) fn gen_func_swift_calls_rust(
         }
     }
 
-    if function.is_swift_initializer {
-        if function.is_copy_method_on_opaque_type() {
-            call_rust = format!("self.bytes = {}", call_rust)
-        } else {
-            call_rust = format!("self.init(ptr: {})", call_rust)
-        }
-    }
-
-    let maybe_return = if function.is_swift_initializer {
-        "".to_string()
-    } else {
-        function.to_swift_return_type(types)
-    };
-
-    let maybe_generics = function.maybe_swift_generics(types);
-
-    let func_definition = if function.sig.asyncness.is_some() {
-        let func_ret_ty = function.return_ty_built_in(types).unwrap();
-        let rust_fn_ret_ty =
-            func_ret_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
-
-        let (maybe_on_complete_sig_ret_val, on_complete_ret_val) = if func_ret_ty.is_null() {
-            ("".to_string(), "()".to_string())
-        } else {
-            (
-                format!(
-                    ", rustFnRetVal: {}",
-                    func_ret_ty
-                        .to_swift_type(TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy, types)
-                ),
-                func_ret_ty.convert_ffi_value_to_swift_value(
-                    "rustFnRetVal",
-                    TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy,
-                    types,
-                ),
-            )
-        };
-
-        let callback_wrapper_ty = format!("CbWrapper{}${}", maybe_type_name_segment, fn_name);
-
-        let fn_body = format!(
-            r#"func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?{maybe_on_complete_sig_ret_val}) {{
-    let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
-    wrapper.cb(.success({on_complete_ret_val}))
-}}
-
-return await withCheckedContinuation({{ (continuation: CheckedContinuation<{rust_fn_ret_ty}, Never>) in
-    let callback = {{ rustFnRetVal in
-        continuation.resume(with: rustFnRetVal)
-    }}
-
-    let wrapper = {cb_wrapper_ty}(cb: callback)
-    let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
-
-    {call_rust}
-}})"#,
-            rust_fn_ret_ty = rust_fn_ret_ty,
-            maybe_on_complete_sig_ret_val = maybe_on_complete_sig_ret_val,
-            on_complete_ret_val = on_complete_ret_val,
-            cb_wrapper_ty = callback_wrapper_ty,
-            call_rust = call_rust,
-        );
+    call_rust
+}
 
-        let mut fn_body_indented = "".to_string();
-        for line in fn_body.lines() {
-            if line.len() > 0 {
-                fn_body_indented += &format!("{}    {}\n", indentation, line);
-            } else {
-                fn_body_indented += "\n"
+/// `"$SomeType"` for methods and associated functions, or `""` for freestanding functions.
+fn swift_type_name_segment(function: &ParsedExternFn) -> String {
+    if let Some(ty) = function.associated_type.as_ref() {
+        match ty {
+            TypeDeclaration::Shared(_) => {
+                //
+                todo!()
+            }
+            TypeDeclaration::Opaque(ty) => {
+                format!("${}", ty.to_string())
             }
         }
-        let fn_body_indented = fn_body_indented.trim_end();
-
-        let callback_wrapper = format!(
-            r#"{indentation}class {cb_wrapper_ty} {{
-{indentation}    var cb: (Result<{rust_fn_ret_ty}, Never>) -> ()
-{indentation}
-{indentation}    public init(cb: @escaping (Result<{rust_fn_ret_ty}, Never>) -> ()) {{
-{indentation}        self.cb = cb
-{indentation}    }}
-{indentation}}}"#,
-            indentation = indentation,
-            cb_wrapper_ty = callback_wrapper_ty
-        );
-
-        format!(
-            r#"{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}) async{maybe_ret} {{
-{fn_body_indented}
-{indentation}}}
-{callback_wrapper}"#,
-            indentation = indentation,
-            maybe_static_class_func = maybe_static_class_func,
-            swift_class_func_name = public_func_fn_name,
-            maybe_generics = maybe_generics,
-            params = params,
-            maybe_ret = maybe_return,
-            fn_body_indented = fn_body_indented,
-            callback_wrapper = callback_wrapper
-        )
     } else {
-        format!(
-            r#"{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}){maybe_ret} {{
-{indentation}    {call_rust}
-{indentation}}}"#,
-            indentation = indentation,
-            maybe_static_class_func = maybe_static_class_func,
-            swift_class_func_name = public_func_fn_name,
-            maybe_generics = maybe_generics,
-            params = params,
-            maybe_ret = maybe_return,
-            call_rust = call_rust,
-        )
-    };
-
-    func_definition
+        "".to_string()
+    }
 }
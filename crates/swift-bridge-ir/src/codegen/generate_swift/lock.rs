@@ -0,0 +1,79 @@
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::parse::{HostLang, LockKind, OpaqueForeignTypeDeclaration};
+use crate::TypeDeclarations;
+use syn::{parse_quote, Type};
+
+/// Generate the `withLock` methods for a `#[swift_bridge(Mutex(SomeType))]` or
+/// `#[swift_bridge(RwLock(SomeType))]` type.
+///
+/// The lock is held for as long as the closure runs, and is unlocked even if it throws.
+///
+/// ```swift
+/// extension AppStateLockRef {
+///     public func withLock<R>(_ body: (AppStateRefMut) throws -> R) rethrows -> R {
+///         swift_lock()
+///         defer { swift_unlock() }
+///         return try body(swift_guarded_mut())
+///     }
+/// }
+/// ```
+pub(super) fn generate_lock_methods(
+    ty: &OpaqueForeignTypeDeclaration,
+    kind: LockKind,
+    guarded: &Type,
+    types: &TypeDeclarations,
+) -> String {
+    let type_name = ty.swift_name_string();
+    let access = ty.swift_access().as_str();
+
+    let swift_ref_type = |ty: Type| {
+        BridgedType::new_with_type(&ty, types)
+            .unwrap()
+            .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+    };
+    let guarded_ref = swift_ref_type(parse_quote! { &#guarded });
+    let guarded_ref_mut = swift_ref_type(parse_quote! { &mut #guarded });
+
+    let methods = match kind {
+        LockKind::Mutex => format!(
+            r#"    /// Calls `body` with the value that the Rust `Mutex` guards, holding the lock until
+    /// `body` returns.
+    ///
+    /// The `{guarded_ref_mut}` must not be used after `body` returns.
+    {access} func withLock<R>(_ body: ({guarded_ref_mut}) throws -> R) rethrows -> R {{
+        swift_lock()
+        defer {{ swift_unlock() }}
+        return try body(swift_guarded_mut())
+    }}
+"#
+        ),
+        LockKind::RwLock => format!(
+            r#"    /// Calls `body` with the value that the Rust `RwLock` guards, holding a read lock until
+    /// `body` returns.
+    ///
+    /// The `{guarded_ref}` must not be used after `body` returns.
+    {access} func withReadLock<R>(_ body: ({guarded_ref}) throws -> R) rethrows -> R {{
+        swift_read()
+        defer {{ swift_unlock() }}
+        return try body(swift_guarded())
+    }}
+
+    /// Calls `body` with the value that the Rust `RwLock` guards, holding the write lock until
+    /// `body` returns.
+    ///
+    /// The `{guarded_ref_mut}` must not be used after `body` returns.
+    {access} func withWriteLock<R>(_ body: ({guarded_ref_mut}) throws -> R) rethrows -> R {{
+        swift_write()
+        defer {{ swift_unlock() }}
+        return try body(swift_guarded_mut())
+    }}
+"#
+        ),
+    };
+
+    format!(
+        r#"extension {type_name}Ref {{
+{methods}}}
+"#
+    )
+}
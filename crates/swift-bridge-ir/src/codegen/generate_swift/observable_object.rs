@@ -0,0 +1,191 @@
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::parse::{HostLang, OpaqueForeignTypeDeclaration};
+use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
+use std::collections::HashMap;
+
+/// Generate the `ObservableObject` wrapper for a `#[swift_bridge(ObservableObject)]` type.
+///
+/// ```swift
+/// public class CounterObservable: ObservableObject {
+///     public let inner: Counter
+///     @Published public var count: UInt32 {
+///         didSet {
+///             if !isRefreshing {
+///                 inner.count = count
+///             }
+///         }
+///     }
+///     ...
+/// }
+/// ```
+pub(super) fn generate_observable_object(
+    ty: &OpaqueForeignTypeDeclaration,
+    associated_funcs_and_methods: &HashMap<String, Vec<&ParsedExternFn>>,
+    types: &TypeDeclarations,
+) -> String {
+    let type_name = ty.swift_name_string();
+    let rust_type_name = ty.ty.to_string();
+    let observable_name = format!("{}Observable", type_name);
+    let access = ty.swift_access().as_str();
+
+    let registry = format!(
+        "{}{}ObservableRegistry",
+        SWIFT_BRIDGE_PREFIX, rust_type_name
+    );
+    let weak_ref = format!("{}{}ObservableRef", SWIFT_BRIDGE_PREFIX, rust_type_name);
+
+    let properties = observable_properties(&rust_type_name, associated_funcs_and_methods, types);
+
+    let mut published_vars = "".to_string();
+    let mut initial_values = "".to_string();
+    let mut refreshes = "".to_string();
+
+    for property in properties.iter() {
+        let name = &property.name;
+        let read = property.read_from_inner();
+
+        let did_set = if property.has_setter {
+            let write = if property.is_string {
+                format!("{}.intoRustString()", name)
+            } else {
+                name.to_string()
+            };
+            format!(
+                r#" {{
+        didSet {{
+            if !isRefreshing {{
+                inner.{name} = {write}
+            }}
+        }}
+    }}"#,
+                name = name,
+                write = write
+            )
+        } else {
+            "".to_string()
+        };
+
+        published_vars += &format!(
+            "    @Published {access} var {name}: {ty}{did_set}\n",
+            access = access,
+            name = name,
+            ty = property.swift_ty,
+            did_set = did_set
+        );
+        initial_values += &format!(
+            "        self._{name} = Published(initialValue: {read})\n",
+            name = name,
+            read = read
+        );
+        refreshes += &format!("        {name} = {read}\n", name = name, read = read);
+    }
+
+    format!(
+        r#"{access} class {observable_name}: ObservableObject {{
+    {access} let inner: {type_name}
+    private var isRefreshing = false
+
+{published_vars}
+    {access} init(_ inner: {type_name}) {{
+        self.inner = inner
+{initial_values}        {registry}[inner.ptr] = {weak_ref}(self)
+    }}
+
+    deinit {{
+        if {registry}[inner.ptr]?.value == nil {{
+            {registry}.removeValue(forKey: inner.ptr)
+        }}
+    }}
+
+    {access} func refresh() {{
+        objectWillChange.send()
+        isRefreshing = true
+{refreshes}        isRefreshing = false
+    }}
+}}
+fileprivate class {weak_ref} {{
+    weak var value: {observable_name}?
+
+    init(_ value: {observable_name}) {{
+        self.value = value
+    }}
+}}
+fileprivate var {registry}: [UnsafeMutableRawPointer: {weak_ref}] = [:]
+@_cdecl("{symbol_prefix}${rust_type_name}$_object_will_change")
+func {prefix}{rust_type_name}__object_will_change (_ this: UnsafeMutableRawPointer) {{
+    DispatchQueue.main.async {{
+        {registry}[this]?.value?.refresh()
+    }}
+}}
+"#,
+        access = access,
+        observable_name = observable_name,
+        type_name = type_name,
+        published_vars = published_vars,
+        initial_values = initial_values,
+        refreshes = refreshes,
+        registry = registry,
+        weak_ref = weak_ref,
+        prefix = SWIFT_BRIDGE_PREFIX,
+        symbol_prefix = ty.symbol_prefix,
+        rust_type_name = rust_type_name,
+    )
+}
+
+struct ObservableProperty {
+    name: String,
+    swift_ty: String,
+    is_string: bool,
+    has_setter: bool,
+}
+
+impl ObservableProperty {
+    fn read_from_inner(&self) -> String {
+        if self.is_string {
+            format!("inner.{}.toString()", self.name)
+        } else {
+            format!("inner.{}", self.name)
+        }
+    }
+}
+
+fn observable_properties(
+    rust_type_name: &str,
+    associated_funcs_and_methods: &HashMap<String, Vec<&ParsedExternFn>>,
+    types: &TypeDeclarations,
+) -> Vec<ObservableProperty> {
+    let methods = match associated_funcs_and_methods.get(rust_type_name) {
+        Some(methods) => methods,
+        None => return vec![],
+    };
+
+    methods
+        .iter()
+        .filter(|getter| getter.is_swift_property_getter())
+        .map(|getter| {
+            let name = match getter.swift_name_override.as_ref() {
+                Some(swift_name) => swift_name.value(),
+                None => getter.sig.ident.to_string(),
+            };
+            let has_setter = methods.iter().any(|setter| {
+                setter.swift_property_setter_name().as_deref()
+                    == Some(getter.sig.ident.to_string().as_str())
+            });
+
+            let bridged = BridgedType::new_with_return_type(&getter.sig.output, types).unwrap();
+            let is_string = bridged.is_string();
+            let swift_ty = if is_string {
+                "String".to_string()
+            } else {
+                bridged.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+            };
+
+            ObservableProperty {
+                name,
+                swift_ty,
+                is_string,
+                has_setter,
+            }
+        })
+        .collect()
+}
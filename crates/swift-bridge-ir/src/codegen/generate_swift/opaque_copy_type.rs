@@ -1,6 +1,7 @@
-use crate::codegen::generate_swift::generate_swift_class_methods;
+use crate::codegen::generate_swift::swift_class::sendable_conformance;
+use crate::codegen::generate_swift::{generate_swift_class_methods, swift_doc_comment};
 use crate::parse::OpaqueForeignTypeDeclaration;
-use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
+use crate::{ParsedExternFn, TypeDeclarations};
 use std::collections::HashMap;
 use syn::Path;
 
@@ -10,10 +11,10 @@ pub(super) fn generate_opaque_copy_struct(
     types: &TypeDeclarations,
     swift_bridge_path: &Path,
 ) -> String {
-    let type_name = &ty.ty.to_string();
+    let type_name = &ty.swift_name_string();
 
     let class_methods = generate_swift_class_methods(
-        &type_name,
+        &ty.ty.to_string(),
         associated_funcs_and_methods,
         types,
         swift_bridge_path,
@@ -48,26 +49,43 @@ fn generate_struct_definition(
     ty: &OpaqueForeignTypeDeclaration,
     types: &TypeDeclarations,
 ) -> String {
-    let type_name = ty.ty.to_string();
+    let type_name = ty.swift_name_string();
+    let rust_type_name = ty.ty.to_string();
     let generics = ty.generics.angle_bracketed_generic_placeholders_string();
 
+    let doc_comment = swift_doc_comment(ty.attributes.doc_comment.as_ref(), "");
+    let access = ty.swift_access().as_str();
+    let maybe_available = ty
+        .attributes
+        .available
+        .as_ref()
+        .map(|available| available.to_swift_attribute(""))
+        .unwrap_or_default();
+
     let declare_struct = if ty.generics.is_empty() {
         format!(
-            r#"public struct {type_name} {{
-    fileprivate var bytes: {prefix}${type_name}
+            r#"{doc_comment}{maybe_available}{access} struct {type_name} {{
+    var bytes: {prefix}${rust_type_name}
 
-    func intoFfiRepr() -> {prefix}${type_name} {{
+    func intoFfiRepr() -> {prefix}${rust_type_name} {{
         bytes
     }}
 }}"#,
-            prefix = SWIFT_BRIDGE_PREFIX,
+            doc_comment = doc_comment,
+            maybe_available = maybe_available,
+            access = access,
+            prefix = ty.symbol_prefix,
             type_name = type_name,
+            rust_type_name = rust_type_name,
         )
     } else {
         format!(
-            r#"public struct {type_name}{generics} {{
-    fileprivate var bytes: SwiftBridgeGenericCopyTypeFfiRepr
+            r#"{doc_comment}{maybe_available}{access} struct {type_name}{generics} {{
+    var bytes: SwiftBridgeGenericCopyTypeFfiRepr
 }}"#,
+            doc_comment = doc_comment,
+            maybe_available = maybe_available,
+            access = access,
             type_name = type_name,
             generics = generics
         )
@@ -75,13 +93,14 @@ fn generate_struct_definition(
 
     let ffi_repr_conversion = if ty.generics.is_empty() {
         format!(
-            r#"extension {prefix}${type_name} {{
+            r#"extension {prefix}${rust_type_name} {{
     func intoSwiftRepr() -> {type_name} {{
         {type_name}(bytes: self)
     }}
 }}"#,
-            prefix = SWIFT_BRIDGE_PREFIX,
+            prefix = ty.symbol_prefix,
             type_name = type_name,
+            rust_type_name = rust_type_name,
         )
     } else {
         let ffi_repr_name = ty.ffi_repr_name_string();
@@ -109,11 +128,131 @@ extension {ffi_repr_name}: SwiftBridgeGenericCopyTypeFfiRepr {{}}"#,
         )
     };
 
+    let equatable = if ty.attributes.equatable && ty.generics.is_empty() {
+        format!(
+            r#"
+extension {type_name}: Equatable {{
+    public static func == (lhs: {type_name}, rhs: {type_name}) -> Bool {{
+        var lhs = lhs.bytes
+        var rhs = rhs.bytes
+        return {prefix}${rust_type_name}$_partial_eq(&lhs, &rhs)
+    }}
+}}"#,
+            prefix = ty.symbol_prefix,
+            type_name = type_name,
+            rust_type_name = rust_type_name,
+        )
+    } else {
+        "".to_string()
+    };
+
+    let hashable = if ty.attributes.hashable && ty.generics.is_empty() {
+        format!(
+            r#"
+extension {type_name}: Hashable {{
+    public func hash(into hasher: inout Hasher) {{
+        var bytes = self.bytes
+        hasher.combine({prefix}${rust_type_name}$_hash(&bytes))
+    }}
+}}"#,
+            prefix = ty.symbol_prefix,
+            type_name = type_name,
+            rust_type_name = rust_type_name,
+        )
+    } else {
+        "".to_string()
+    };
+
+    let comparable = if ty.attributes.comparable && ty.generics.is_empty() {
+        format!(
+            r#"
+extension {type_name}: Comparable {{
+    public static func < (lhs: {type_name}, rhs: {type_name}) -> Bool {{
+        var lhs = lhs.bytes
+        var rhs = rhs.bytes
+        return {prefix}${rust_type_name}$_cmp(&lhs, &rhs) < 0
+    }}
+}}"#,
+            prefix = ty.symbol_prefix,
+            type_name = type_name,
+            rust_type_name = rust_type_name,
+        )
+    } else {
+        "".to_string()
+    };
+
+    let mut descriptions = "".to_string();
+    if ty.generics.is_empty() {
+        for (is_enabled, protocol, property, ffi_suffix) in [
+            (
+                ty.attributes.custom_string_convertible,
+                "CustomStringConvertible",
+                "description",
+                "_description",
+            ),
+            (
+                ty.attributes.custom_debug_string_convertible,
+                "CustomDebugStringConvertible",
+                "debugDescription",
+                "_debug_description",
+            ),
+        ] {
+            if !is_enabled {
+                continue;
+            }
+
+            descriptions += &format!(
+                r#"
+extension {type_name}: {protocol} {{
+    public var {property}: String {{
+        var bytes = self.bytes
+        return RustString(ptr: {prefix}${rust_type_name}${ffi_suffix}(&bytes)).toString()
+    }}
+}}"#,
+                prefix = ty.symbol_prefix,
+                type_name = type_name,
+                rust_type_name = rust_type_name,
+                protocol = protocol,
+                property = property,
+                ffi_suffix = ffi_suffix,
+            );
+        }
+    }
+
+    let sendable = if ty.generics.is_empty() {
+        sendable_conformance(ty)
+    } else {
+        "".to_string()
+    };
+
+    let error = if ty.attributes.error && ty.generics.is_empty() {
+        format!(
+            r#"
+extension {type_name}: LocalizedError {{
+    public var errorDescription: String? {{
+        var bytes = self.bytes
+        return RustString(ptr: {prefix}${rust_type_name}$_description(&bytes)).toString()
+    }}
+}}"#,
+            prefix = ty.symbol_prefix,
+            type_name = type_name,
+            rust_type_name = rust_type_name,
+        )
+    } else {
+        "".to_string()
+    };
+
     format!(
         r#"{declare_struct}
-{ffi_repr_conversion}"#,
+{ffi_repr_conversion}{equatable}{hashable}{comparable}{descriptions}{sendable}{error}"#,
         declare_struct = declare_struct,
-        ffi_repr_conversion = ffi_repr_conversion
+        ffi_repr_conversion = ffi_repr_conversion,
+        equatable = equatable,
+        hashable = hashable,
+        comparable = comparable,
+        descriptions = descriptions,
+        sendable = sendable,
+        error = error
     )
 }
 
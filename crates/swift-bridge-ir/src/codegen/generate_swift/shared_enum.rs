@@ -1,4 +1,5 @@
 use crate::bridged_type::SharedEnum;
+use crate::swift_keywords::escape_swift_keyword;
 use crate::SwiftBridgeModule;
 
 impl SwiftBridgeModule {
@@ -20,7 +21,7 @@ impl SwiftBridgeModule {
             let v = format!(
                 r#"
     case {name}"#,
-                name = variant.name
+                name = escape_swift_keyword(&variant.name.to_string())
             );
             variants += &v;
         }
@@ -31,10 +32,11 @@ impl SwiftBridgeModule {
         for variant in shared_enum.variants.iter() {
             let case = format!(
                 r#"
-            case {enum_name}.{variant_name}:
+            case {enum_name}.{swift_variant_name}:
                 return {enum_ffi_name}(tag: {enum_ffi_name}${variant_name})"#,
                 enum_name = enum_name,
                 enum_ffi_name = enum_ffi_name,
+                swift_variant_name = escape_swift_keyword(&variant.name.to_string()),
                 variant_name = variant.name
             );
             convert_swift_to_ffi_repr += &case;
@@ -47,9 +49,10 @@ impl SwiftBridgeModule {
             let case = format!(
                 r#"
             case {enum_ffi_name}${variant_name}:
-                return {enum_name}.{variant_name}"#,
+                return {enum_name}.{swift_variant_name}"#,
                 enum_name = enum_name,
                 enum_ffi_name = enum_ffi_name,
+                swift_variant_name = escape_swift_keyword(&variant.name.to_string()),
                 variant_name = variant.name
             );
             convert_ffi_repr_to_swift += &case;
@@ -66,45 +69,62 @@ impl SwiftBridgeModule {
         let vectorizable_impl = if shared_enum.has_one_or_more_variants_with_data() {
             "".to_string()
         } else {
+            let prefix = &shared_enum.symbol_prefix;
             format!(
                 r#"
 extension {enum_name}: Vectorizable {{
     public static func vecOfSelfNew() -> UnsafeMutableRawPointer {{
-        __swift_bridge__$Vec_{enum_name}$new()
+        {prefix}$Vec_{enum_name}$new()
     }}
 
     public static func vecOfSelfFree(vecPtr: UnsafeMutableRawPointer) {{
-        __swift_bridge__$Vec_{enum_name}$drop(vecPtr)
+        {prefix}$Vec_{enum_name}$drop(vecPtr)
     }}
 
     public static func vecOfSelfPush(vecPtr: UnsafeMutableRawPointer, value: Self) {{
-        __swift_bridge__$Vec_{enum_name}$push(vecPtr, value.intoFfiRepr())
+        {prefix}$Vec_{enum_name}$push(vecPtr, value.intoFfiRepr())
     }}
 
     public static func vecOfSelfPop(vecPtr: UnsafeMutableRawPointer) -> Optional<Self> {{
-        let maybeEnum = __swift_bridge__$Vec_{enum_name}$pop(vecPtr)
+        let maybeEnum = {prefix}$Vec_{enum_name}$pop(vecPtr)
         return maybeEnum.intoSwiftRepr()
     }}
 
     public static func vecOfSelfGet(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Optional<Self> {{
-        let maybeEnum = __swift_bridge__$Vec_{enum_name}$get(vecPtr, index)
+        let maybeEnum = {prefix}$Vec_{enum_name}$get(vecPtr, index)
         return maybeEnum.intoSwiftRepr()
     }}
 
     public static func vecOfSelfGetMut(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Optional<Self> {{
-        let maybeEnum = __swift_bridge__$Vec_{enum_name}$get_mut(vecPtr, index)
+        let maybeEnum = {prefix}$Vec_{enum_name}$get_mut(vecPtr, index)
         return maybeEnum.intoSwiftRepr()
     }}
 
     public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {{
-        __swift_bridge__$Vec_{enum_name}$len(vecPtr)
+        {prefix}$Vec_{enum_name}$len(vecPtr)
+    }}
+
+    public static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: Self) {{
+        {prefix}$Vec_{enum_name}$insert(vecPtr, index, value.intoFfiRepr())
+    }}
+
+    public static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self {{
+        {prefix}$Vec_{enum_name}$remove(vecPtr, index).intoSwiftRepr()
+    }}
+
+    public static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt) {{
+        {prefix}$Vec_{enum_name}$reserve(vecPtr, additional)
+    }}
+
+    public static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt {{
+        {prefix}$Vec_{enum_name}$capacity(vecPtr)
     }}
 }}"#
             )
         };
 
         let swift_enum = format!(
-            r#"public enum {enum_name} {{{variants}}}
+            r#"{maybe_frozen}public enum {enum_name} {{{variants}}}
 extension {enum_name} {{
     func intoFfiRepr() -> {ffi_repr_name} {{
         switch self {{{convert_swift_to_ffi_repr}}}
@@ -134,6 +154,7 @@ extension {option_ffi_name} {{
         }}
     }}
 }}{vectorizable_impl}"#,
+            maybe_frozen = self.maybe_frozen(),
             enum_name = enum_name,
             enum_ffi_name = enum_ffi_name,
             option_ffi_name = option_ffi_name,
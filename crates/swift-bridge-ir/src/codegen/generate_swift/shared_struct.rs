@@ -1,5 +1,10 @@
 use crate::bridged_type::shared_struct::StructField;
-use crate::bridged_type::{BridgedType, SharedStruct, StructFields, StructSwiftRepr, TypePosition};
+use crate::bridged_type::{
+    BridgedType, CustomBridgedType, SharedStruct, SharedType, StructFields, StructSwiftRepr,
+    TypePosition, SWIFT_INT128_AVAILABILITY,
+};
+use crate::codegen::generate_swift::guard_int128;
+use crate::swift_keywords::escape_swift_keyword;
 use crate::SwiftBridgeModule;
 
 impl SwiftBridgeModule {
@@ -47,10 +52,20 @@ impl SwiftBridgeModule {
                 let convert_ffi_repr_to_swift =
                     shared_struct.convert_ffi_expression_to_swift("self", &self.types);
 
+                let uses_128_bit_int = BridgedType::Foreign(CustomBridgedType::Shared(
+                    SharedType::Struct(shared_struct.clone()),
+                ))
+                .contains_128_bit_int_recursive(&self.types);
+                let maybe_available = if uses_128_bit_int {
+                    format!("{}\n", SWIFT_INT128_AVAILABILITY)
+                } else {
+                    "".to_string()
+                };
+
                 // No need to generate any code. Swift will automatically generate a
                 //  struct from our C header typedef that we generate for this struct.
                 let swift_struct = format!(
-                    r#"public struct {struct_name} {{{fields}
+                    r#"{maybe_available}{maybe_frozen}public struct {struct_name} {{{fields}
     public init({initializer_params}) {{{initializer_body}}}
 
     @inline(__always)
@@ -58,13 +73,13 @@ impl SwiftBridgeModule {
         {convert_swift_to_ffi_repr}
     }}
 }}
-extension {ffi_repr_name} {{
+{maybe_available}extension {ffi_repr_name} {{
     @inline(__always)
     func intoSwiftRepr() -> {struct_name} {{
         {convert_ffi_repr_to_swift}
     }}
 }}
-extension {option_ffi_name} {{
+{maybe_available}extension {option_ffi_name} {{
     @inline(__always)
     func intoSwiftRepr() -> Optional<{struct_name}> {{
         if self.is_some {{
@@ -83,6 +98,8 @@ extension {option_ffi_name} {{
         }}
     }}
 }}"#,
+                    maybe_available = maybe_available,
+                    maybe_frozen = self.maybe_frozen(),
                     struct_name = struct_name,
                     initializer_params = initializer_params,
                     initializer_body = initializer_body,
@@ -93,7 +110,11 @@ extension {option_ffi_name} {{
                     convert_ffi_repr_to_swift = convert_ffi_repr_to_swift
                 );
 
-                Some(swift_struct)
+                if uses_128_bit_int {
+                    Some(guard_int128(swift_struct, ""))
+                } else {
+                    Some(swift_struct)
+                }
             }
         }
     }
@@ -112,7 +133,7 @@ extension {option_ffi_name} {{
 
             params += &format!(
                 "{}: {},",
-                field.swift_name_string(),
+                escape_swift_keyword(&field.swift_name_string()),
                 bridged_ty.to_swift_type(TypePosition::SharedStructField, &self.types)
             );
         }
@@ -134,11 +155,8 @@ extension {option_ffi_name} {{
         let mut body = "".to_string();
 
         for field in struct_fields.into_iter() {
-            body += &format!(
-                "        self.{} = {}\n",
-                field.swift_name_string(),
-                field.swift_name_string()
-            );
+            let name = escape_swift_keyword(&field.swift_name_string());
+            body += &format!("        self.{} = {}\n", name, name);
         }
 
         if !body.is_empty() {
@@ -159,7 +177,7 @@ extension {option_ffi_name} {{
 
             fields += &format!(
                 "    public var {}: {}\n",
-                field.swift_name_string(),
+                escape_swift_keyword(&field.swift_name_string()),
                 bridged_ty.to_swift_type(TypePosition::SharedStructField, &self.types)
             );
         }
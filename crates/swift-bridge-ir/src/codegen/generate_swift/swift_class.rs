@@ -1,6 +1,8 @@
-use crate::codegen::generate_swift::{generate_swift_class_methods, ClassProtocols};
+use crate::codegen::generate_swift::{
+    generate_swift_class_methods, swift_doc_comment, ClassProtocols,
+};
 use crate::parse::OpaqueForeignTypeDeclaration;
-use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
+use crate::{ParsedExternFn, TypeDeclarations};
 use std::collections::HashMap;
 use syn::Path;
 
@@ -40,32 +42,91 @@ fn create_class_declaration(
     ref_mut_self_methods: &[String],
     types: &TypeDeclarations,
 ) -> String {
-    let type_name = &ty.ty_name_ident().to_string();
+    let type_name = &ty.swift_name_string();
+    let prefix = &ty.symbol_prefix;
     let generics = ty.generics.angle_bracketed_generic_placeholders_string();
+    let access = if ty.attributes.open {
+        "open"
+    } else {
+        ty.swift_access().as_str()
+    };
+    let maybe_available = ty
+        .attributes
+        .available
+        .as_ref()
+        .map(|available| available.to_swift_attribute(""))
+        .unwrap_or_default();
+    // Objective-C can't see generic classes.
+    let objc = ty.attributes.objc && ty.generics.is_empty();
+    let maybe_objc_members = if objc { "@objcMembers\n" } else { "" };
+
+    // `Decodable` requires a `required` initializer, which can only be declared in the class's
+    // body.
+    let (maybe_decodable, maybe_decodable_init) = if ty.attributes.codable {
+        let decodable_init = format!(
+            r#"
+
+    public required convenience init(from decoder: Decoder) throws {{
+        let json = try decoder.singleValueContainer().decode(String.self)
+        guard let ptr = {prefix}${ty_name}$_from_json({{ let rustString = json.intoRustString(); rustString.isOwned = false; return rustString.ptr }}()) else {{
+            throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Could not deserialize {type_name}"))
+        }}
+        self.init(ptr: ptr)
+    }}"#,
+            ty_name = ty.ty_name_ident(),
+            type_name = type_name,
+        );
+        (", Decodable", decodable_init)
+    } else {
+        ("", "".to_string())
+    };
+
+    // Swift can't override methods that are declared in extensions, so an `open` type's methods
+    // are declared in its classes' bodies.
+    let methods_in_body = ty.attributes.open && !ty.attributes.already_declared;
+    let members_in_body = |members: &[&[String]]| -> String {
+        let members: Vec<&str> = members
+            .iter()
+            .flat_map(|m| m.iter())
+            .map(|m| m.as_str())
+            .collect();
+        if !methods_in_body || members.is_empty() {
+            return "".to_string();
+        }
+
+        format!("\n\n{}", members.join("\n\n"))
+    };
 
     let mut class_decl = {
         let free_func_call = if ty.generics.len() == 0 {
-            format!("{}${}$_free(ptr)", SWIFT_BRIDGE_PREFIX, type_name)
+            format!("{}${}$_free(ptr)", prefix, ty.ty_name_ident())
         } else {
             "(self as! SwiftBridgeGenericFreer).rust_free()".to_string()
         };
 
         format!(
-            r#"public class {type_name}{generics}: {type_name}RefMut{generics} {{
+            r#"{doc_comment}{maybe_available}{maybe_objc_members}{access} class {type_name}{generics}: {type_name}RefMut{generics}{maybe_decodable} {{
     var isOwned: Bool = true
 
     public override init(ptr: UnsafeMutableRawPointer) {{
         super.init(ptr: ptr)
-    }}
+    }}{maybe_decodable_init}
 
     deinit {{
         if isOwned {{
             {free_func_call}
         }}
-    }}
+    }}{members}
 }}"#,
+            members = members_in_body(&[initializers, owned_self_methods]),
+            doc_comment = swift_doc_comment(ty.attributes.doc_comment.as_ref(), ""),
+            maybe_available = maybe_available,
+            maybe_objc_members = maybe_objc_members,
+            access = access,
             type_name = type_name,
             generics = generics,
+            maybe_decodable = maybe_decodable,
+            maybe_decodable_init = maybe_decodable_init,
             free_func_call = free_func_call
         )
     };
@@ -73,41 +134,71 @@ fn create_class_declaration(
     let mut class_ref_mut_decl = {
         format!(
             r#"
-public class {type_name}RefMut{generics}: {type_name}Ref{generics} {{
+{maybe_available}{maybe_objc_members}{access} class {type_name}RefMut{generics}: {type_name}Ref{generics} {{
     public override init(ptr: UnsafeMutableRawPointer) {{
         super.init(ptr: ptr)
-    }}
+    }}{members}
 }}"#,
+            members = members_in_body(&[ref_mut_self_methods]),
+            maybe_available = maybe_available,
+            maybe_objc_members = maybe_objc_members,
+            access = access,
             type_name = type_name,
             generics = generics
         )
     };
     let mut class_ref_decl = {
+        let (maybe_nsobject, maybe_super_init) = if objc {
+            (": NSObject", "\n        super.init()")
+        } else {
+            ("", "")
+        };
+
         format!(
             r#"
-public class {type_name}Ref{generics} {{
+{maybe_available}{maybe_objc_members}{access} class {type_name}Ref{generics}{maybe_nsobject} {{
     var ptr: UnsafeMutableRawPointer
 
     public init(ptr: UnsafeMutableRawPointer) {{
-        self.ptr = ptr
-    }}
+        self.ptr = ptr{maybe_super_init}
+    }}{members}
 }}"#,
+            members = members_in_body(&[ref_self_methods]),
+            maybe_available = maybe_available,
+            maybe_objc_members = maybe_objc_members,
+            access = access,
             type_name = type_name,
-            generics = generics
+            generics = generics,
+            maybe_nsobject = maybe_nsobject,
+            maybe_super_init = maybe_super_init,
         )
     };
     if let Some(identifiable) = class_protocols.identifiable.as_ref() {
-        let identifiable_var = if identifiable.func_name == "id" {
+        let id_type = if identifiable.declare_id_type {
+            format!(
+                r#"
+    public typealias ID = {identifiable_return_ty}
+"#,
+                identifiable_return_ty = identifiable.return_ty
+            )
+        } else {
             "".to_string()
+        };
+        let identifiable_var = if identifiable.func_name == "id" {
+            id_type
         } else {
+            let call = if identifiable.is_property { "" } else { "()" };
             format!(
-                r#"
+                r#"{id_type}
     public var id: {identifiable_return_ty} {{
-        return self.{identifiable_func}()
+        return self.{identifiable_func}{call}{into_id}
     }}
 "#,
+                id_type = id_type.trim_end_matches('\n'),
                 identifiable_func = identifiable.func_name,
-                identifiable_return_ty = identifiable.return_ty
+                identifiable_return_ty = identifiable.return_ty,
+                call = call,
+                into_id = identifiable.into_id,
             )
         };
 
@@ -119,7 +210,7 @@ extension {type_name}Ref: Identifiable {{{identifiable_var}}}"#,
         );
     }
 
-    let initializers = if initializers.len() == 0 {
+    let initializers = if initializers.len() == 0 || methods_in_body {
         "".to_string()
     } else {
         let initializers: String = initializers.join("\n\n");
@@ -133,7 +224,7 @@ extension {type_name} {{
         )
     };
 
-    let owned_instance_methods = if owned_self_methods.len() == 0 {
+    let owned_instance_methods = if owned_self_methods.len() == 0 || methods_in_body {
         "".to_string()
     } else {
         let owned_instance_methods: String = owned_self_methods.join("\n\n");
@@ -147,7 +238,7 @@ extension {type_name} {{
         )
     };
 
-    let ref_instance_methods = if ref_self_methods.len() == 0 {
+    let ref_instance_methods = if ref_self_methods.len() == 0 || methods_in_body {
         "".to_string()
     } else {
         let ref_instance_methods: String = ref_self_methods.join("\n\n");
@@ -161,7 +252,7 @@ extension {type_name}Ref {{
         )
     };
 
-    let ref_mut_instance_methods = if ref_mut_self_methods.len() == 0 {
+    let ref_mut_instance_methods = if ref_mut_self_methods.len() == 0 || methods_in_body {
         "".to_string()
     } else {
         let ref_mut_instance_methods: String = ref_mut_self_methods.join("\n\n");
@@ -198,14 +289,31 @@ where {swift_generic_bounds} {{
             free_func_name = ty.free_rust_opaque_type_ffi_name()
         );
     }
+    // `NSObject` is already `Equatable`, `Hashable`, `CustomStringConvertible` and
+    // `CustomDebugStringConvertible`, so `objc` classes override its implementations instead.
     let equatable_method: String = {
-        if ty.attributes.equatable {
+        if ty.attributes.equatable && objc {
             let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
             format!(
                 r#"
-extension {ty_name}Ref: Equatable {{
-    public static func == (lhs: {ty_name}Ref, rhs: {ty_name}Ref) -> Bool {{
-        __swift_bridge__${ty_name}$_partial_eq(rhs.ptr, lhs.ptr)
+extension {swift_name}Ref {{
+    public override func isEqual(_ object: Any?) -> Bool {{
+        guard let other = object as? {swift_name}Ref else {{
+            return false
+        }}
+        return {prefix}${ty_name}$_partial_eq(self.ptr, other.ptr)
+    }}
+}}"#,
+            )
+        } else if ty.attributes.equatable {
+            let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
+            format!(
+                r#"
+extension {swift_name}Ref: Equatable {{
+    public static func == (lhs: {swift_name}Ref, rhs: {swift_name}Ref) -> Bool {{
+        {prefix}${ty_name}$_partial_eq(lhs.ptr, rhs.ptr)
     }}
 }}"#,
             )
@@ -214,13 +322,26 @@ extension {ty_name}Ref: Equatable {{
         }
     };
     let hashable_method: String = {
-        if ty.attributes.hashable {
+        if ty.attributes.hashable && objc {
             let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
             format!(
                 r#"
-extension {ty_name}Ref: Hashable{{
+extension {swift_name}Ref {{
+    public override var hash: Int {{
+        Int(truncatingIfNeeded: {prefix}${ty_name}$_hash(self.ptr))
+    }}
+}}
+"#,
+            )
+        } else if ty.attributes.hashable {
+            let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
+            format!(
+                r#"
+extension {swift_name}Ref: Hashable{{
     public func hash(into hasher: inout Hasher){{
-        hasher.combine(__swift_bridge__${ty_name}$_hash(self.ptr))
+        hasher.combine({prefix}${ty_name}$_hash(self.ptr))
     }}
 }}
 "#,
@@ -229,9 +350,106 @@ extension {ty_name}Ref: Hashable{{
             "".to_string()
         }
     };
+    let encodable_method: String = {
+        if ty.attributes.codable {
+            let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
+            format!(
+                r#"
+extension {swift_name}Ref: Encodable {{
+    public func encode(to encoder: Encoder) throws {{
+        guard let json = {prefix}${ty_name}$_to_json(self.ptr) else {{
+            throw EncodingError.invalidValue(self, EncodingError.Context(codingPath: encoder.codingPath, debugDescription: "Could not serialize {swift_name}"))
+        }}
+        var container = encoder.singleValueContainer()
+        try container.encode(RustString(ptr: json).toString())
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
+    let comparable_method: String = {
+        if ty.attributes.comparable {
+            let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
+            format!(
+                r#"
+extension {swift_name}Ref: Comparable {{
+    public static func < (lhs: {swift_name}Ref, rhs: {swift_name}Ref) -> Bool {{
+        {prefix}${ty_name}$_cmp(lhs.ptr, rhs.ptr) < 0
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
+    let description_property: String = {
+        if ty.attributes.custom_string_convertible {
+            let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
+            let (conformance, maybe_override) = if objc {
+                ("", "override ")
+            } else {
+                (": CustomStringConvertible", "")
+            };
+            format!(
+                r#"
+extension {swift_name}Ref{conformance} {{
+    public {maybe_override}var description: String {{
+        RustString(ptr: {prefix}${ty_name}$_description(self.ptr)).toString()
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
+    let debug_description_property: String = {
+        if ty.attributes.custom_debug_string_convertible {
+            let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
+            let (conformance, maybe_override) = if objc {
+                ("", "override ")
+            } else {
+                (": CustomDebugStringConvertible", "")
+            };
+            format!(
+                r#"
+extension {swift_name}Ref{conformance} {{
+    public {maybe_override}var debugDescription: String {{
+        RustString(ptr: {prefix}${ty_name}$_debug_description(self.ptr)).toString()
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
+    let sendable_conformance = sendable_conformance(ty);
+    let error_conformance: String = {
+        if ty.attributes.error {
+            let ty_name = ty.ty_name_ident();
+            let swift_name = ty.swift_name_string();
+            format!(
+                r#"
+extension {swift_name}: LocalizedError {{
+    public var errorDescription: String? {{
+        RustString(ptr: {prefix}${ty_name}$_description(self.ptr)).toString()
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
+    let maybe_import_foundation = if objc { "\nimport Foundation" } else { "" };
     let class = format!(
-        r#"
-{class_decl}{initializers}{owned_instance_methods}{class_ref_decl}{ref_mut_instance_methods}{class_ref_mut_decl}{ref_instance_methods}{generic_freer}{equatable_method}{hashable_method}"#,
+        r#"{maybe_import_foundation}
+{class_decl}{initializers}{owned_instance_methods}{class_ref_decl}{ref_mut_instance_methods}{class_ref_mut_decl}{ref_instance_methods}{generic_freer}{equatable_method}{hashable_method}{comparable_method}{encodable_method}{description_property}{debug_description_property}{sendable_conformance}{error_conformance}"#,
+        maybe_import_foundation = maybe_import_foundation,
         class_decl = class_decl,
         class_ref_decl = class_ref_mut_decl,
         class_ref_mut_decl = class_ref_decl,
@@ -241,7 +459,50 @@ extension {ty_name}Ref: Hashable{{
         ref_instance_methods = ref_instance_methods,
         equatable_method = equatable_method,
         hashable_method = hashable_method,
+        comparable_method = comparable_method,
+        encodable_method = encodable_method,
+        description_property = description_property,
+        debug_description_property = debug_description_property,
+        sendable_conformance = sendable_conformance,
+        error_conformance = error_conformance,
     );
 
     return class;
 }
+
+/// Swift requires thrown errors to be `Sendable`, so `Error` types are always `Sendable`.
+///
+/// Other types are explicitly not `Sendable` unless the Rust type is declared to be
+/// `Send + Sync`, so that Swift's concurrency checking doesn't let Swift share them between
+/// threads.
+pub(super) fn sendable_conformance(ty: &OpaqueForeignTypeDeclaration) -> String {
+    let type_name = ty.swift_name_string();
+
+    // The module that declares the type declares its conformances.
+    if ty.attributes.already_declared {
+        return "".to_string();
+    }
+
+    if ty.attributes.sendable {
+        format!(
+            r#"
+// The Rust type is `Send + Sync`, which is checked when the Rust code is compiled.
+extension {type_name}: @unchecked Sendable {{}}"#,
+        )
+    } else if ty.attributes.error {
+        format!(
+            r#"
+extension {type_name}: @unchecked Sendable {{}}"#,
+        )
+    } else if ty.generics.is_empty() {
+        format!(
+            r#"
+// The Rust type might not be `Send + Sync`, so `{type_name}` can't be shared between tasks or
+// actors. Use `#[swift_bridge(Sendable)]` on the Rust type if it is `Send + Sync`.
+@available(*, unavailable)
+extension {type_name}: Sendable {{}}"#,
+        )
+    } else {
+        "".to_string()
+    }
+}
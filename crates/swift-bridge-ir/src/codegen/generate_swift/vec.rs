@@ -1,23 +1,25 @@
-use proc_macro2::Ident;
+use crate::parse::OpaqueForeignTypeDeclaration;
 
 /// Generate the `extension MyRustType: Vectorizable {}` for the Swift side.
-pub(super) fn generate_vectorizable_extension(ty: &Ident) -> String {
+pub(super) fn generate_vectorizable_extension(ty: &OpaqueForeignTypeDeclaration) -> String {
+    let rust_ty = ty.ty.to_string();
+
     format!(
         r#"extension {ty}: Vectorizable {{
     public static func vecOfSelfNew() -> UnsafeMutableRawPointer {{
-        __swift_bridge__$Vec_{ty}$new()
+        {prefix}$Vec_{rust_ty}$new()
     }}
 
     public static func vecOfSelfFree(vecPtr: UnsafeMutableRawPointer) {{
-        __swift_bridge__$Vec_{ty}$drop(vecPtr)
+        {prefix}$Vec_{rust_ty}$drop(vecPtr)
     }}
 
     public static func vecOfSelfPush(vecPtr: UnsafeMutableRawPointer, value: {ty}) {{
-        __swift_bridge__$Vec_{ty}$push(vecPtr, {{value.isOwned = false; return value.ptr;}}())
+        {prefix}$Vec_{rust_ty}$push(vecPtr, {{value.isOwned = false; return value.ptr;}}())
     }}
 
     public static func vecOfSelfPop(vecPtr: UnsafeMutableRawPointer) -> Optional<Self> {{
-        let pointer = __swift_bridge__$Vec_{ty}$pop(vecPtr)
+        let pointer = {prefix}$Vec_{rust_ty}$pop(vecPtr)
         if pointer == nil {{
             return nil
         }} else {{
@@ -26,7 +28,7 @@ pub(super) fn generate_vectorizable_extension(ty: &Ident) -> String {
     }}
 
     public static func vecOfSelfGet(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Optional<{ty}Ref> {{
-        let pointer = __swift_bridge__$Vec_{ty}$get(vecPtr, index)
+        let pointer = {prefix}$Vec_{rust_ty}$get(vecPtr, index)
         if pointer == nil {{
             return nil
         }} else {{
@@ -35,7 +37,7 @@ pub(super) fn generate_vectorizable_extension(ty: &Ident) -> String {
     }}
 
     public static func vecOfSelfGetMut(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Optional<{ty}RefMut> {{
-        let pointer = __swift_bridge__$Vec_{ty}$get_mut(vecPtr, index)
+        let pointer = {prefix}$Vec_{rust_ty}$get_mut(vecPtr, index)
         if pointer == nil {{
             return nil
         }} else {{
@@ -44,19 +46,39 @@ pub(super) fn generate_vectorizable_extension(ty: &Ident) -> String {
     }}
 
     public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {{
-        __swift_bridge__$Vec_{ty}$len(vecPtr)
+        {prefix}$Vec_{rust_ty}$len(vecPtr)
+    }}
+
+    public static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: {ty}) {{
+        {prefix}$Vec_{rust_ty}$insert(vecPtr, index, {{value.isOwned = false; return value.ptr;}}())
+    }}
+
+    public static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self {{
+        ({ty}(ptr: {prefix}$Vec_{rust_ty}$remove(vecPtr, index)) as! Self)
+    }}
+
+    public static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt) {{
+        {prefix}$Vec_{rust_ty}$reserve(vecPtr, additional)
+    }}
+
+    public static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt {{
+        {prefix}$Vec_{rust_ty}$capacity(vecPtr)
     }}
 }}
 "#,
-        ty = ty.to_string()
+        prefix = ty.symbol_prefix,
+        ty = ty.swift_name_string(),
+        rust_ty = rust_ty
     )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::{HostLang, OpaqueRustTypeGenerics};
     use crate::test_utils::assert_trimmed_generated_equals_trimmed_expected;
-    use proc_macro2::Span;
+    use crate::SWIFT_BRIDGE_PREFIX;
+    use proc_macro2::{Ident, Span};
 
     /// Verify that we generate the `extension MyRustType: Vectorizable { }` implementation
     /// for the Swift side.
@@ -106,11 +128,33 @@ extension ARustType: Vectorizable {
     public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {
         __swift_bridge__$Vec_ARustType$len(vecPtr)
     }
+
+    public static func vecOfSelfInsert(vecPtr: UnsafeMutableRawPointer, index: UInt, value: ARustType) {
+        __swift_bridge__$Vec_ARustType$insert(vecPtr, index, {value.isOwned = false; return value.ptr;}())
+    }
+
+    public static func vecOfSelfRemove(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Self {
+        (ARustType(ptr: __swift_bridge__$Vec_ARustType$remove(vecPtr, index)) as! Self)
+    }
+
+    public static func vecOfSelfReserve(vecPtr: UnsafeMutableRawPointer, additional: UInt) {
+        __swift_bridge__$Vec_ARustType$reserve(vecPtr, additional)
+    }
+
+    public static func vecOfSelfCapacity(vecPtr: UnsafeMutableRawPointer) -> UInt {
+        __swift_bridge__$Vec_ARustType$capacity(vecPtr)
+    }
 }
 "#;
 
         assert_trimmed_generated_equals_trimmed_expected(
-            &generate_vectorizable_extension(&Ident::new("ARustType", Span::call_site())),
+            &generate_vectorizable_extension(&OpaqueForeignTypeDeclaration {
+                ty: Ident::new("ARustType", Span::call_site()),
+                host_lang: HostLang::Rust,
+                attributes: Default::default(),
+                generics: OpaqueRustTypeGenerics::new(),
+                symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
+            }),
             &expected,
         );
     }
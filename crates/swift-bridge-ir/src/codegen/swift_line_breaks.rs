@@ -0,0 +1,205 @@
+/// Lines that are longer than this get broken up, if they contain a `{ ...; ... }` block.
+const MAX_LINE_LENGTH: usize = 100;
+
+/// Break up the long single-line blocks in generated Swift code, such as the closures that we
+/// use to convert `Option` return values, so that each statement is on its own indented line.
+///
+/// ```swift
+/// // Before
+/// { let val = __swift_bridge__$some_function(); if val.is_some { return val.val } else { return nil } }()
+///
+/// // After
+/// {
+///     let val = __swift_bridge__$some_function()
+///     if val.is_some { return val.val } else { return nil }
+/// }()
+/// ```
+pub fn break_long_swift_lines(swift: &str) -> String {
+    let mut lines: Vec<String> = swift.lines().map(|line| line.to_string()).collect();
+
+    let mut idx = 0;
+    while idx < lines.len() {
+        match break_line(&lines[idx]) {
+            Some(broken) => {
+                lines.splice(idx..idx + 1, broken);
+            }
+            None => idx += 1,
+        }
+    }
+
+    let mut formatted = lines.join("\n");
+    if swift.ends_with('\n') {
+        formatted.push('\n');
+    }
+    formatted
+}
+
+/// Break the first `{ ...; ... }` block in a long line onto multiple lines.
+fn break_line(line: &str) -> Option<Vec<String>> {
+    if line.chars().count() <= MAX_LINE_LENGTH || line.trim_start().starts_with("//") {
+        return None;
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    let indentation: String = chars.iter().take_while(|c| c.is_whitespace()).collect();
+
+    let (open, close, statements) = chars
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| **c == '{')
+        .filter(|(idx, _)| !is_in_string(&chars, *idx))
+        .find_map(|(open, _)| {
+            let close = matching_brace(&chars, open)?;
+            let statements = split_statements(&chars[open + 1..close]);
+            if statements.len() > 1 {
+                Some((open, close, statements))
+            } else {
+                None
+            }
+        })?;
+
+    let before: String = chars[..=open].iter().collect();
+    let after: String = chars[close..].iter().collect();
+
+    let mut broken = vec![before.trim_end().to_string()];
+    for statement in statements {
+        broken.push(format!("{}    {}", indentation, statement));
+    }
+    broken.push(format!("{}{}", indentation, after));
+
+    Some(broken)
+}
+
+/// The index of the `}` that closes the `{` at `open`, if it is on the same line.
+fn matching_brace(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+
+    let mut idx = open;
+    while idx < chars.len() {
+        match chars[idx] {
+            '\\' if in_string => idx += 1,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+/// Split a block's body on the `;` that aren't nested within parentheses, brackets, braces or
+/// strings.
+fn split_statements(body: &[char]) -> Vec<String> {
+    let mut statements = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut in_string = false;
+
+    let mut idx = 0;
+    while idx < body.len() {
+        let c = body[idx];
+        match c {
+            '\\' if in_string => {
+                current.push(c);
+                idx += 1;
+                if let Some(escaped) = body.get(idx) {
+                    current.push(*escaped);
+                }
+                idx += 1;
+                continue;
+            }
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => depth -= 1,
+            ';' if !in_string && depth == 0 => {
+                statements.push(current.trim().to_string());
+                current = String::new();
+                idx += 1;
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+        idx += 1;
+    }
+    statements.push(current.trim().to_string());
+
+    statements.retain(|statement| !statement.is_empty());
+    statements
+}
+
+/// Whether the character at `idx` is within a string literal.
+fn is_in_string(chars: &[char], idx: usize) -> bool {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in &chars[..idx] {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ => {}
+        }
+    }
+
+    in_string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that we break up a long `Option` return closure, including the closure nested
+    /// within it.
+    #[test]
+    fn breaks_long_option_closure() {
+        let swift = r#"public func rust_reflect_option_u8(_ arg: Optional<UInt8>) -> Optional<UInt8> {
+    { let val = __swift_bridge__$rust_reflect_option_u8({ let val = arg; return __private__OptionU8(val: val ?? 123, is_some: val != nil); }()); if val.is_some { return val.val } else { return nil } }()
+}
+"#;
+
+        let expected = r#"public func rust_reflect_option_u8(_ arg: Optional<UInt8>) -> Optional<UInt8> {
+    {
+        let val = __swift_bridge__$rust_reflect_option_u8({
+            let val = arg
+            return __private__OptionU8(val: val ?? 123, is_some: val != nil)
+        }())
+        if val.is_some { return val.val } else { return nil }
+    }()
+}
+"#;
+
+        assert_eq!(break_long_swift_lines(swift), expected);
+    }
+
+    /// Verify that we leave short lines, comments and blocks without statements alone.
+    #[test]
+    fn leaves_lines_without_long_blocks_alone() {
+        let swift = r#"{ let a = 1; return a }()
+// { let some_long_comment = 1; let that_goes_on_and_on = 2; let and_on_and_on_and_on_and_on = 3 }
+some_function_with_a_long_name(some_argument_with_a_long_name, another_argument_with_a_long_name)"#;
+
+        assert_eq!(break_long_swift_lines(swift), swift);
+    }
+
+    /// Verify that we don't split on semicolons or braces within string literals.
+    #[test]
+    fn ignores_semicolons_and_braces_in_strings() {
+        let swift = r#"    { let message = "a; b { c"; print(message, "with some more text to make this line long enough \" ; ") }()"#;
+
+        let expected = r#"    {
+        let message = "a; b { c"
+        print(message, "with some more text to make this line long enough \" ; ")
+    }()"#;
+
+        assert_eq!(break_long_swift_lines(swift), expected);
+    }
+}
@@ -0,0 +1,229 @@
+//! The `swift-bridge.toml` file that configures how a crate's bridge modules get generated.
+//!
+//! ```toml
+//! out_dir = "Generated"
+//! swift_access = "internal"
+//! symbol_prefix = "__my_app__"
+//! private_type_prefix = "__my_app__private__"
+//! output = "per_module"
+//! format = "line_breaks"
+//! module_map = "RustBridge"
+//! features = ["ios"]
+//! exclude = ["src/generated.rs"]
+//! dep_file = "target/swift-bridge.d"
+//! foundation_free = true
+//! library_evolution = true
+//! swiftc_check = "typecheck"
+//! ```
+
+use crate::parse::{is_valid_symbol_prefix, SwiftAccessLevel};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// The name of the config file, which goes next to the crate's `Cargo.toml`.
+pub const CONFIG_FILE_NAME: &str = "swift-bridge.toml";
+
+/// The contents of a crate's `swift-bridge.toml`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SwiftBridgeConfig {
+    /// The directory to write the generated code to, relative to the crate's directory.
+    pub out_dir: Option<PathBuf>,
+    /// The Swift access level of the generated code in bridge modules that don't have their own
+    /// `#[swift_bridge(swift_access = "...")]` attribute.
+    pub swift_access: Option<String>,
+    /// The symbol prefix of bridge modules that don't have their own
+    /// `#[swift_bridge(symbol_prefix = "...")]` attribute.
+    pub symbol_prefix: Option<String>,
+    /// The prefix of the C types that the generated code shares between bridge modules, such as
+    /// `__private__OptionU8`, instead of `__private__`.
+    pub private_type_prefix: Option<String>,
+    /// Whether to write all of the generated code to one file or one file per bridge module.
+    pub output: ConfigOutput,
+    /// How to format the generated Swift code.
+    pub format: ConfigFormatting,
+    /// The `swift-format` executable, for `format = "swift_format"`.
+    pub swift_format_path: Option<PathBuf>,
+    /// The name of the Clang module to write a `module.modulemap` for.
+    pub module_map: Option<String>,
+    /// The crate features to generate code for, instead of the features that Cargo enabled.
+    pub features: Option<Vec<String>>,
+    /// Source files to skip when looking for bridge modules, relative to the crate's directory.
+    pub exclude: Vec<PathBuf>,
+    /// Where to write a dep-file listing the source files that the code was generated from,
+    /// relative to the crate's directory. It is JSON if it ends in `.json`, otherwise a Makefile
+    /// rule.
+    pub dep_file: Option<PathBuf>,
+    /// Generate code that compiles with only the Swift standard library, without Foundation.
+    pub foundation_free: bool,
+    /// Generate code that can be built with library evolution enabled, as if every bridge module
+    /// had a `#[swift_bridge(library_evolution)]` attribute.
+    pub library_evolution: bool,
+    /// How to check the generated Swift code with `swiftc` after it is written.
+    pub swiftc_check: ConfigSwiftcCheck,
+    /// The `swiftc` executable, for `swiftc_check`.
+    pub swiftc_path: Option<PathBuf>,
+}
+
+/// `output = "..."`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigOutput {
+    /// Write all of the generated Swift and C to one Swift file and one header.
+    #[default]
+    Concatenated,
+    /// Write the generated Swift and C for each bridge module to their own files.
+    PerModule,
+}
+
+/// `format = "..."`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFormatting {
+    /// Leave the generated Swift code as is.
+    #[default]
+    None,
+    /// Break long single-line blocks onto multiple lines.
+    LineBreaks,
+    /// Break long lines and then run `swift-format` on the written Swift files.
+    SwiftFormat,
+}
+
+/// `swiftc_check = "..."`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSwiftcCheck {
+    /// Don't check the generated Swift code.
+    #[default]
+    None,
+    /// Check the syntax of the generated Swift code using `swiftc -parse`.
+    Parse,
+    /// Type check the generated Swift code using `swiftc -typecheck`.
+    Typecheck,
+}
+
+impl SwiftBridgeConfig {
+    /// Read the `swift-bridge.toml` in the crate's directory, or `None` if the crate doesn't
+    /// have one.
+    pub fn load(crate_dir: impl AsRef<Path>) -> Result<Option<Self>, String> {
+        let config_path = crate_dir.as_ref().join(CONFIG_FILE_NAME);
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let config = std::fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read {:?}: {}", config_path, e))?;
+
+        SwiftBridgeConfig::parse(&config)
+            .map(Some)
+            .map_err(|e| format!("Invalid {:?}: {}", config_path, e))
+    }
+
+    /// Parse the contents of a `swift-bridge.toml`.
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let config: SwiftBridgeConfig = toml::from_str(config).map_err(|e| e.to_string())?;
+
+        if let Some(access) = config.swift_access.as_ref() {
+            if SwiftAccessLevel::from_name(access).is_none() {
+                return Err(format!(
+                    r#"swift_access must be "public", "package", "internal" or "fileprivate", not {:?}"#,
+                    access
+                ));
+            }
+        }
+        if let Some(prefix) = config.symbol_prefix.as_ref() {
+            if !is_valid_symbol_prefix(prefix) {
+                return Err(format!(
+                    "symbol_prefix {:?} must be a valid C identifier",
+                    prefix
+                ));
+            }
+        }
+        if let Some(prefix) = config.private_type_prefix.as_ref() {
+            if !is_valid_symbol_prefix(prefix) {
+                return Err(format!(
+                    "private_type_prefix {:?} must be a valid C identifier",
+                    prefix
+                ));
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that we can parse every option in a `swift-bridge.toml`.
+    #[test]
+    fn parses_config() {
+        let config = SwiftBridgeConfig::parse(
+            r#"
+out_dir = "Generated"
+swift_access = "internal"
+symbol_prefix = "__my_app__"
+private_type_prefix = "__my_app__private__"
+output = "per_module"
+format = "swift_format"
+swift_format_path = "/usr/bin/swift-format"
+module_map = "RustBridge"
+features = ["ios"]
+exclude = ["src/generated.rs"]
+dep_file = "target/swift-bridge.d"
+foundation_free = true
+library_evolution = true
+swiftc_check = "typecheck"
+swiftc_path = "/usr/bin/swiftc"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            SwiftBridgeConfig {
+                out_dir: Some(PathBuf::from("Generated")),
+                swift_access: Some("internal".to_string()),
+                symbol_prefix: Some("__my_app__".to_string()),
+                private_type_prefix: Some("__my_app__private__".to_string()),
+                output: ConfigOutput::PerModule,
+                format: ConfigFormatting::SwiftFormat,
+                swift_format_path: Some(PathBuf::from("/usr/bin/swift-format")),
+                module_map: Some("RustBridge".to_string()),
+                features: Some(vec!["ios".to_string()]),
+                exclude: vec![PathBuf::from("src/generated.rs")],
+                dep_file: Some(PathBuf::from("target/swift-bridge.d")),
+                foundation_free: true,
+                library_evolution: true,
+                swiftc_check: ConfigSwiftcCheck::Typecheck,
+                swiftc_path: Some(PathBuf::from("/usr/bin/swiftc")),
+            }
+        );
+    }
+
+    /// Verify that every option in a `swift-bridge.toml` is optional.
+    #[test]
+    fn parses_empty_config() {
+        assert_eq!(
+            SwiftBridgeConfig::parse("").unwrap(),
+            SwiftBridgeConfig::default()
+        );
+    }
+
+    /// Verify that we reject invalid options.
+    #[test]
+    fn error_if_invalid_config() {
+        let tests = vec![
+            r#"out_dirr = "Generated""#,
+            r#"swift_access = "open""#,
+            r#"symbol_prefix = "my-app""#,
+            r#"private_type_prefix = "my-app""#,
+            r#"output = "per_file""#,
+        ];
+
+        for config in tests {
+            assert!(SwiftBridgeConfig::parse(config).is_err(), "{}", config);
+        }
+    }
+}
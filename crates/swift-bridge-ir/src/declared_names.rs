@@ -0,0 +1,184 @@
+//! The names that a bridge module declares for the whole crate.
+//!
+//! The generated Swift types and free functions are declared at the top level of the Swift module
+//! that they are compiled into, and the generated symbols are exported from the crate, so two
+//! bridge modules in the same crate that both declare `type Config` would collide. The
+//! `#[swift_bridge::bridge]` macro only sees one module at a time, so `swift-bridge-build` uses
+//! these names to detect collisions between modules.
+
+use crate::bridge_module_attributes::cfg_attrs_are_enabled;
+use crate::parse::{HostLang, SharedTypeDeclaration, TypeDeclaration};
+use crate::{CodegenConfig, SwiftBridgeModule};
+use proc_macro2::Span;
+
+/// A type or free function that a bridge module declares.
+#[derive(Debug, Clone)]
+pub struct DeclaredName {
+    /// What was declared, such as "type `Config`" or "function `connect`".
+    pub item: String,
+    /// The name of the type or function that gets generated at the top level of the Swift
+    /// module, if one gets generated.
+    pub swift_name: Option<String>,
+    /// The symbol that the item's generated code is named after, such as
+    /// `__my_crate__$connect`.
+    pub symbol: String,
+    /// Where the item was declared.
+    pub span: Span,
+}
+
+impl SwiftBridgeModule {
+    /// The types and free functions that the module declares, skipping the ones that are
+    /// `already_declared` in another module or that are disabled by the crate's features.
+    ///
+    /// Methods aren't included, since their names are scoped to their type.
+    pub fn declared_names(&self, config: &CodegenConfig) -> Vec<DeclaredName> {
+        if !self.module_will_be_compiled(config) {
+            return vec![];
+        }
+
+        let mut names = vec![];
+
+        for ty in self.types.types() {
+            match ty {
+                TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                    if shared_struct.already_declared {
+                        continue;
+                    }
+
+                    names.push(DeclaredName {
+                        item: format!("struct `{}`", shared_struct.name),
+                        swift_name: Some(shared_struct.swift_name_string()),
+                        symbol: shared_struct.ffi_name_string(),
+                        span: shared_struct.name.span(),
+                    });
+                }
+                TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => {
+                    if shared_enum.already_declared {
+                        continue;
+                    }
+
+                    names.push(DeclaredName {
+                        item: format!("enum `{}`", shared_enum.name),
+                        swift_name: Some(shared_enum.swift_name_string()),
+                        symbol: shared_enum.ffi_name_string(),
+                        span: shared_enum.name.span(),
+                    });
+                }
+                TypeDeclaration::Opaque(opaque) => {
+                    if opaque.attributes.already_declared
+                        || !opaque.generics.is_empty()
+                        || !cfg_attrs_are_enabled(&opaque.attributes.cfg_attrs, config)
+                    {
+                        continue;
+                    }
+
+                    // `extern "Swift"` types are implemented by the user's Swift code.
+                    let swift_name = match opaque.host_lang {
+                        HostLang::Rust => Some(opaque.swift_name_string()),
+                        HostLang::Swift => None,
+                    };
+
+                    names.push(DeclaredName {
+                        item: format!("type `{}`", opaque.ty),
+                        swift_name,
+                        symbol: opaque.ffi_repr_name_string(),
+                        span: opaque.ty.span(),
+                    });
+                }
+            }
+        }
+
+        for function in self.functions.iter() {
+            if function.associated_type.is_some()
+                || function.is_method()
+                || !cfg_attrs_are_enabled(&function.all_cfg_attrs(), config)
+            {
+                continue;
+            }
+
+            // `extern "Swift"` functions are implemented by the user's Swift code.
+            let swift_name = match function.host_lang {
+                HostLang::Rust => Some(match function.swift_name_override.as_ref() {
+                    Some(swift_name) => swift_name.value(),
+                    None => function.func.sig.ident.to_string(),
+                }),
+                HostLang::Swift => None,
+            };
+
+            names.push(DeclaredName {
+                item: format!("function `{}`", function.func.sig.ident),
+                swift_name,
+                symbol: function.link_name(),
+                span: function.func.sig.ident.span(),
+            });
+        }
+
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::parse_ok;
+    use quote::quote;
+
+    /// Verify that we list a module's types and free functions, but not its methods or the types
+    /// that are declared in another module.
+    #[test]
+    fn declared_names() {
+        let mut module = parse_ok(quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(swift_name_prefix = "MC")]
+            mod foo {
+                struct SharedStruct;
+
+                extern "Rust" {
+                    type SomeType;
+                    #[swift_bridge(already_declared)]
+                    type AnotherType;
+
+                    fn some_method(self: &SomeType);
+                    fn some_function();
+                }
+
+                extern "Swift" {
+                    fn swift_function();
+                }
+            }
+        });
+        module.set_configured_symbol_prefix("__my_crate__");
+
+        let names: Vec<(String, Option<String>, String)> = module
+            .declared_names(&CodegenConfig::no_features_enabled())
+            .into_iter()
+            .map(|name| (name.item, name.swift_name, name.symbol))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                (
+                    "struct `SharedStruct`".to_string(),
+                    Some("MCSharedStruct".to_string()),
+                    "__my_crate__$MCSharedStruct".to_string()
+                ),
+                (
+                    "type `SomeType`".to_string(),
+                    Some("MCSomeType".to_string()),
+                    "__my_crate__$SomeType".to_string()
+                ),
+                (
+                    "function `some_function`".to_string(),
+                    Some("MCsome_function".to_string()),
+                    "__my_crate__$some_function".to_string()
+                ),
+                (
+                    "function `swift_function`".to_string(),
+                    None,
+                    "__my_crate__$swift_function".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
-use syn::{Error, FnArg, Item, Receiver};
-use syn::{ForeignItemType, LitStr};
+use syn::{Error, FnArg, GenericParam, Item, Pat, PatType, Path, Receiver};
+use syn::{ForeignItemType, LitInt, LitStr};
 use syn::{Token, Type};
 
 // <!-- ANCHOR: mdbook-parse-error-enum -->
@@ -25,6 +25,21 @@ pub(crate) enum ParseError {
     /// If Bar wasn't declared using a `type Bar` declaration.
     UndeclaredType { ty: Type },
     // <!-- ANCHOR_END: mdbook-parse-error-enum -->
+    /// `fn foo (self: [SomeType; 2])`
+    /// The type of `self` must be a declared type or a reference to one.
+    InvalidSelfType { ty: Type },
+    /// `fn foo (&self: SomeType)`
+    /// The `&` belongs in front of the type, i.e. `self: &SomeType`.
+    RefSelfPattern { arg: PatType },
+    /// An argument is not a plain name, such as `fn foo ((a, b): (u8, u8))` or
+    /// `fn foo (_: u8)`.
+    UnsupportedArgPattern { pat: Pat },
+    /// A generic opaque type has a lifetime or const parameter.
+    /// Example: `type SomeType<'a>`
+    UnsupportedGenericParam { param: Box<GenericParam> },
+    /// An opaque type was declared with an unrecognized attribute.
+    /// Example: `#[derive(Debug)] type SomeType;`
+    OpaqueTypeUnrecognizedAttribute { attribute: Path },
     /// Declared a type that we already support.
     /// Example: `type u32`
     DeclaredBuiltInType { ty: ForeignItemType },
@@ -36,6 +51,20 @@ pub(crate) enum ParseError {
     StructInvalidSwiftRepr { swift_repr_attr_value: LitStr },
     /// A struct was declared with an unrecognized attribute.
     StructUnrecognizedAttribute { attribute: Ident },
+    /// A struct was declared with a `#[repr(...)]` other than `C`, `packed`, `packed(N)` or
+    /// `align(N)`.
+    /// Example: `#[repr(transparent)]`
+    StructUnsupportedRepr { repr: Ident },
+    /// A struct has a `#[repr(...)]` attribute that does not include `C`.
+    /// Example: `#[repr(packed)]` instead of `#[repr(C, packed)]`
+    StructReprMissingC { struct_ident: Ident },
+    /// A struct is both `packed` and `align`ed. Rust does not allow this.
+    StructReprPackedAndAligned { struct_ident: Ident },
+    /// The value of a `packed(N)` or `align(N)` was not a power of two.
+    StructReprNotPowerOfTwo { value: LitInt },
+    /// A struct with a custom layout used `swift_repr = "class"`.
+    /// A custom layout only makes sense for structs that get copied across the boundary by value.
+    StructLayoutWithSwiftReprClass { struct_ident: Ident },
     /// An enum was declared with an unrecognized attribute.
     EnumUnrecognizedAttribute { attribute: Ident },
     /// There is no reason to use `swift_repr = "class"` on an empty struct.
@@ -51,12 +80,182 @@ pub(crate) enum ParseError {
     // Would need to Box the copy type and pass a pointer between languages.
     ArgCopyAndRefMut { arg: FnArg },
     /// There was an unsupported item in the module, such as a `use` statement.
-    InvalidModuleItem { item: Item },
+    InvalidModuleItem { item: Box<Item> },
+    /// A `const` was declared with a type that isn't a primitive, such as `const FOO: String;`.
+    UnsupportedConstType { ty: Type },
+    /// `fn foo() -> Option<u128>`
+    /// `u128` and `i128` can't be used inside of an `Option`, `Vec`, `Result` or boxed function yet.
+    UnsupportedInt128 { ty: Type },
+    /// A function uses a type that can't be bridged, such as a tuple, an array or a function
+    /// pointer.
+    /// Example: `fn foo (arg: (u8, u8))`
+    UnsupportedType { ty: Type },
+    /// A function's associated type is a shared struct or enum, such as `fn foo (self: &SomeStruct)`.
+    /// Only opaque types can have methods, associated functions and initializers.
+    SharedTypeAssociatedFunction { ty: Type },
+    /// A `static` was declared in an `extern "Swift"` block.
+    /// Only `extern "Rust"` blocks can declare statics.
+    StaticInExternSwift { ident: Ident },
+    /// A `static mut` was declared. Only immutable statics are supported.
+    MutableStatic { mutability: Token![mut] },
+    /// A `static` was declared with a type that isn't an opaque Rust type or a primitive.
+    UnsupportedStaticType { ty: Type },
+    /// An `async fn` was declared in an `extern "Swift"` block.
+    AsyncExternSwiftFn { asyncness: Token![async] },
+    /// An `extern "Swift"` function returns a reference that isn't a `&str` or a slice, such as
+    /// `-> &SomeType` or `-> Option<&str>`.
+    ExternSwiftReturnsReference { ty: Type },
+    /// An `extern "Swift"` function returns a `Box<dyn FnOnce(A) -> B>`.
+    ExternSwiftReturnsBoxedFn { ty: Type },
+    /// An `extern "Rust"` function takes or returns a `Box<dyn FnOnce(A) -> B>`.
+    /// Only `extern "Swift"` functions can take boxed functions.
+    ExternRustBoxedFn { ty: Type },
+    /// An `extern "Swift"` function takes an argument that borrows an `extern "Swift"` type,
+    /// such as `arg: &SomeSwiftType`.
+    ExternSwiftArgBorrowsSwiftType { ty: Type },
+    /// A `#[swift_bridge(protocol)]` type was declared in an `extern "Rust"` block.
+    /// Only `extern "Swift"` types can be Swift protocols.
+    ProtocolInExternRust { ty: Ident },
+    /// A `#[swift_bridge(ObservableObject)]` type is not a non-Copy opaque Rust type.
+    InvalidObservableObjectType { ty: Ident },
+    /// A `#[swift_bridge(Codable)]` type is not a non-Copy, non-generic opaque Rust type.
+    InvalidCodableType { ty: Ident },
+    /// A `#[swift_bridge(Publisher(...))]` type is not a non-Copy opaque Rust type.
+    InvalidPublisherType { ty: Ident },
+    /// A `#[swift_bridge(AsyncStream(...))]` type is not a non-Copy opaque Rust type.
+    InvalidAsyncStreamType { ty: Ident },
+    /// A `#[swift_bridge(Mutex(...))]` or `#[swift_bridge(RwLock(...))]` type is not a non-Copy,
+    /// non-generic opaque Rust type.
+    InvalidLockType { ty: Ident },
+    /// A `#[swift_bridge(Sendable)]` type is not an opaque Rust type.
+    InvalidSendableType { ty: Ident },
+    /// A `#[swift_bridge(objc)]` type is not a non-Copy, non-generic opaque Rust type.
+    InvalidObjcType { ty: Ident },
+    /// A `#[swift_bridge(actor)]` type is not an opaque Swift type.
+    InvalidActorType { ty: Ident },
+    /// A `#[swift_bridge(open)]` type is not a public, non-Copy, non-generic opaque Rust type.
+    InvalidOpenType { ty: Ident },
+    /// A `#[swift_bridge(Identifiable = "...")]` type is not a non-Copy opaque Rust type.
+    InvalidIdentifiableType { ty: Ident },
+    /// A `#[swift_bridge(Identifiable = "...")]` type does not have a `&self` method with the
+    /// given name that returns a value.
+    IdentifiableGetterNotFound { ty: Ident, getter: LitStr },
+    /// A `#[swift_bridge(ObservableObject)]` type has a `swift_property` that isn't a primitive
+    /// or a `String`.
+    UnsupportedObservablePropertyType { ty: Type },
+    /// A `#[swift_bridge(symbol_prefix = "...")]` module attribute is not a valid C identifier.
+    InvalidSymbolPrefix { prefix: LitStr },
+    /// A type or function was declared more than once in the same bridge module.
+    /// Example: `type Foo;` in two `extern "Rust"` blocks.
+    DuplicateDeclaration {
+        /// The second declaration.
+        duplicate: Ident,
+        /// The first declaration.
+        previous: Ident,
+    },
+    /// A bridged item used a type that has the `#[swift_bridge(ignore)]` attribute.
+    IgnoredTypeUsed {
+        /// The ignored type.
+        ignored: Ident,
+        /// The tokens that used the ignored type, such as a function signature.
+        usage: TokenStream,
+    },
+    /// An opaque type that nothing in the module uses, in a module with an
+    /// `#[swift_bridge(unused_types = "deny")]` attribute.
+    UnusedType { ty: Ident },
 }
 
 /// An error while parsing a function attribute.
 pub(crate) enum FunctionAttributeParseError {
     Identifiable(IdentifiableParseError),
+    /// An `argument_labels` entry refers to an argument that the function does not have.
+    ArgumentLabelUnknownArg {
+        fn_ident: Ident,
+        arg: Ident,
+    },
+    /// A `swift_property` function is not a `fn name(&self) -> T` getter or a
+    /// `fn set_name(&mut self, value: T)` setter.
+    SwiftPropertyInvalidSignature {
+        fn_ident: Ident,
+    },
+    /// A `swift_property` setter does not have a `swift_property` getter with the same type.
+    SwiftPropertySetterWithoutGetter {
+        fn_ident: Ident,
+    },
+    /// A `default` entry refers to an argument that the function does not have.
+    DefaultArgValueUnknownArg {
+        fn_ident: Ident,
+        arg: Ident,
+    },
+    /// A `main_actor` function was declared in an `extern "Rust"` block.
+    MainActorInExternRust {
+        fn_ident: Ident,
+    },
+    /// A `cancellable` function is not an async function in an `extern "Rust"` block.
+    CancellableNotAsyncRust {
+        fn_ident: Ident,
+    },
+    /// A `completion_handler` function is not an `extern "Swift"` function that returns nothing
+    /// and takes a trailing `Box<dyn FnOnce(A, B) -> ()>` argument.
+    InvalidCompletionHandler {
+        fn_ident: Ident,
+    },
+    /// A `blocking` function is `async`.
+    BlockingAsync {
+        fn_ident: Ident,
+    },
+    /// A `blocking` function is not in an `extern "Rust"` block, or is an initializer, a
+    /// `swift_property` or `Identifiable`.
+    InvalidBlocking {
+        fn_ident: Ident,
+    },
+    /// A `queue` function is not an `extern "Swift"` function that returns nothing and only
+    /// takes owned arguments, or is also `main_actor`.
+    InvalidQueue {
+        fn_ident: Ident,
+    },
+    /// A `set` or `set_with` function does not take `&mut self` and a single value, or has a
+    /// return type.
+    /// Example: `#[swift_bridge(set(field))] fn set_field(&self, value: u8) -> u8`
+    InvalidSetter {
+        fn_ident: Ident,
+    },
+    /// An `init` function does not return the type that it initializes.
+    InitializerWithoutReturnType {
+        fn_ident: Ident,
+    },
+    /// An `init` function also uses `associated_to`.
+    /// Example: `#[swift_bridge(init, associated_to = Foo)]`
+    InitializerWithAssociatedTo {
+        associated_to: Ident,
+    },
+    /// An `init` function takes `self`.
+    InitializerWithSelf {
+        fn_ident: Ident,
+    },
+    /// An `async` `init` function returns an `Option` or a `Result`, or initializes a `Copy` type.
+    UnsupportedAsyncInitializer {
+        fn_ident: Ident,
+    },
+    /// An `init` function of a `Copy` type returns an `Option` or a `Result`.
+    /// Example: `#[swift_bridge(init)] fn new() -> Option<SomeCopyType>`
+    UnsupportedCopyInitializer {
+        fn_ident: Ident,
+    },
+    /// A function that takes `self` uses `associated_to`.
+    AssociatedToWithSelf {
+        associated_to: Ident,
+    },
+    /// A `swift_name` is not a valid Swift identifier.
+    /// Example: `#[swift_bridge(swift_name = "some-function")]`
+    InvalidSwiftName {
+        swift_name: LitStr,
+    },
+    /// A `rust_name` is not a valid Rust identifier.
+    /// Example: `#[swift_bridge(rust_name = "path::to::function")]`
+    InvalidRustName {
+        rust_name: LitStr,
+    },
 }
 
 /// An error while parsing a function's `Identifiable` attribute.
@@ -109,6 +308,33 @@ self: &mut SomeType
                 Error::new_spanned(ty, message)
             }
             // <!-- ANCHOR_END: mdbook-parse-error-message -->
+            ParseError::InvalidSelfType { ty } => Error::new_spanned(
+                ty,
+                r#"The type of self must be a declared type. Try one of:
+self: SomeType
+self: &SomeType
+self: &mut SomeType
+"#,
+            ),
+            ParseError::RefSelfPattern { arg } => Error::new_spanned(
+                arg,
+                r#"The `&` belongs in front of the type. Try `self: &SomeType` instead of `&self: SomeType`."#,
+            ),
+            ParseError::UnsupportedArgPattern { pat } => Error::new_spanned(
+                pat,
+                r#"Function arguments must be named, such as `arg: SomeType`."#,
+            ),
+            ParseError::UnsupportedGenericParam { param } => Error::new_spanned(
+                param,
+                r#"Generic opaque types only support type parameters, such as `type SomeType<T>`."#,
+            ),
+            ParseError::OpaqueTypeUnrecognizedAttribute { attribute } => {
+                let message = format!(
+                    r#"Did not recognize type attribute "{}"."#,
+                    attribute.to_token_stream().to_string().replace(' ', "")
+                );
+                Error::new_spanned(attribute, message)
+            }
             ParseError::DeclaredBuiltInType { ty } => {
                 let message = format!(
                     r#"Type {} is already supported
@@ -163,6 +389,38 @@ struct {struct_name};
                 let message = format!(r#"Did not recognize struct attribute "{}"."#, attribute);
                 Error::new_spanned(attribute, message)
             }
+            ParseError::StructUnsupportedRepr { repr } => {
+                let message = format!(
+                    r#"Unsupported struct repr "{}". Shared structs support `C`, `packed`, `packed(N)` and `align(N)`."#,
+                    repr
+                );
+                Error::new_spanned(repr, message)
+            }
+            ParseError::StructReprMissingC { struct_ident } => {
+                let message = format!(
+                    r#"Shared struct {} must use a C representation, such as `#[repr(C, packed)]`."#,
+                    struct_ident
+                );
+                Error::new_spanned(struct_ident, message)
+            }
+            ParseError::StructReprPackedAndAligned { struct_ident } => {
+                let message = format!(
+                    r#"Shared struct {} cannot be both `packed` and `align`."#,
+                    struct_ident
+                );
+                Error::new_spanned(struct_ident, message)
+            }
+            ParseError::StructReprNotPowerOfTwo { value } => {
+                let message = "Struct packing and alignment must be a power of two.";
+                Error::new_spanned(value, message)
+            }
+            ParseError::StructLayoutWithSwiftReprClass { struct_ident } => {
+                let message = format!(
+                    r#"Shared struct {} has a custom layout, so it must use `swift_repr = "struct"`."#,
+                    struct_ident
+                );
+                Error::new_spanned(struct_ident, message)
+            }
             ParseError::EnumUnrecognizedAttribute { attribute } => {
                 let message = format!(r#"Did not recognize enum attribute "{}"."#, attribute);
                 Error::new_spanned(attribute, message)
@@ -184,6 +442,135 @@ struct {struct_name};
                         Error::new_spanned(fn_ident, message)
                     }
                 },
+                FunctionAttributeParseError::SwiftPropertyInvalidSignature { fn_ident } => {
+                    let message = format!(
+                        r#"swift_property function {} must either be a getter such as `fn {}(&self) -> T` or a setter such as `fn set_{}(&mut self, value: T)`."#,
+                        fn_ident, fn_ident, fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::SwiftPropertySetterWithoutGetter { fn_ident } => {
+                    let getter = fn_ident.to_string();
+                    let getter = getter.trim_start_matches("set_");
+                    let message = format!(
+                        r#"swift_property setter {} must have a swift_property getter named {} that returns the same type."#,
+                        fn_ident, getter
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::MainActorInExternRust { fn_ident } => {
+                    let message = format!(
+                        r#"main_actor function {} must be declared in an `extern "Swift"` block."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::CancellableNotAsyncRust { fn_ident } => {
+                    let message = format!(
+                        r#"cancellable function {} must be an async function declared in an `extern "Rust"` block."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::InvalidCompletionHandler { fn_ident } => {
+                    let message = format!(
+                        r#"completion_handler function {} must be declared in an `extern "Swift"` block, must not have a return type and must take a `Box<dyn FnOnce(A, B) -> ()>` as its last argument."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::BlockingAsync { fn_ident } => {
+                    let message = format!(
+                        r#"blocking function {} can't be async. Async Rust functions already run on the async runtime instead of on a Swift thread, so remove `blocking`."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::InvalidBlocking { fn_ident } => {
+                    let message = format!(
+                        r#"blocking function {} must be declared in an `extern "Rust"` block and can't be an initializer, a swift_property or Identifiable, since those can't be async in Swift."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::InvalidQueue { fn_ident } => {
+                    let message = format!(
+                        r#"queue function {} must be declared in an `extern "Swift"` block, must not have a return type, must not take references and can't also be main_actor, since it runs after the Rust call has returned."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::InvalidSetter { fn_ident } => {
+                    let message = format!(
+                        r#"Setter {} must take `&mut self` and the field's new value, and must not have a return type. For example: `fn {}(&mut self, value: u8);`"#,
+                        fn_ident, fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::InitializerWithoutReturnType { fn_ident } => {
+                    let message = format!(
+                        r#"Initializer {} must return the type that it initializes."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::InitializerWithAssociatedTo { associated_to } => {
+                    let message = format!(
+                        r#"Initializers are associated to the type that they return, so they can't use `associated_to`. Remove `associated_to = {}`."#,
+                        associated_to
+                    );
+                    Error::new_spanned(associated_to, message)
+                }
+                FunctionAttributeParseError::InitializerWithSelf { fn_ident } => {
+                    let message = format!(
+                        r#"Initializer {} can't take `self`. Remove the `self` argument, or the `init` attribute if {} is a method."#,
+                        fn_ident, fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::UnsupportedCopyInitializer { fn_ident } => {
+                    let message = format!(
+                        r#"Initializer {} initializes a `Copy` type, so it must return that type. Failable and throwing initializers of `Copy` types are not yet supported."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::UnsupportedAsyncInitializer { fn_ident } => {
+                    let message = format!(
+                        r#"Async initializer {} must return the type that it initializes, and that type can't be `Copy`. Async initializers that return an `Option` or a `Result` are not yet supported."#,
+                        fn_ident
+                    );
+                    Error::new_spanned(fn_ident, message)
+                }
+                FunctionAttributeParseError::AssociatedToWithSelf { associated_to } => {
+                    let message = format!(
+                        r#"Methods are associated to the type of their `self` argument, so they can't use `associated_to`. Remove `associated_to = {}`."#,
+                        associated_to
+                    );
+                    Error::new_spanned(associated_to, message)
+                }
+                FunctionAttributeParseError::InvalidSwiftName { swift_name } => {
+                    let message = format!(
+                        r#""{}" is not a valid Swift name. Names must start with a letter or an underscore and only contain letters, digits and underscores."#,
+                        swift_name.value()
+                    );
+                    Error::new_spanned(swift_name, message)
+                }
+                FunctionAttributeParseError::InvalidRustName { rust_name } => {
+                    let message = format!(
+                        r#""{}" is not a valid Rust function name. Use `return_with` or a wrapper function to call a function from another module."#,
+                        rust_name.value()
+                    );
+                    Error::new_spanned(rust_name, message)
+                }
+                FunctionAttributeParseError::ArgumentLabelUnknownArg { fn_ident, arg }
+                | FunctionAttributeParseError::DefaultArgValueUnknownArg { fn_ident, arg } => {
+                    let message = format!(
+                        r#"Function {} does not have an argument named {}."#,
+                        fn_ident, arg
+                    );
+                    Error::new_spanned(arg, message)
+                }
             },
             ParseError::ArgCopyAndRefMut { arg } => {
                 let message =
@@ -194,6 +581,156 @@ struct {struct_name};
                 let message = format!(r#"Only `extern` blocks, structs and enums are supported."#);
                 Error::new_spanned(item, message)
             }
+            ParseError::UnsupportedConstType { ty } => {
+                let message = "Constants must be a primitive type such as `u32`, `f64` or `bool`.";
+                Error::new_spanned(ty, message)
+            }
+            ParseError::UnsupportedInt128 { ty } => {
+                let message = "`u128` and `i128` are not yet supported inside of an `Option`, `Vec`, `Result` or boxed function.";
+                Error::new_spanned(ty, message)
+            }
+            ParseError::UnsupportedType { ty } => {
+                let is_result = matches!(
+                    &ty,
+                    Type::Path(path) if path.path.segments.last().unwrap().ident == "Result"
+                );
+                let message = match ty {
+                    Type::Tuple(_) => "Tuples are not supported. Use a shared tuple struct instead, such as `struct SomeTuple(u8, u8);`.",
+                    _ if is_result => "A `Result` that holds a primitive can only hold another primitive, `()`, or a type that is passed as a pointer such as an opaque type, a `String` or a `Vec<T>`.",
+                    _ => "This type is not yet supported.",
+                };
+                Error::new_spanned(ty, message)
+            }
+            ParseError::SharedTypeAssociatedFunction { ty } => {
+                let message = "Only opaque types can have methods, associated functions and initializers. Shared structs and enums can't.";
+                Error::new_spanned(ty, message)
+            }
+            ParseError::ExternRustBoxedFn { ty } => {
+                let message = r#"`Box<dyn FnOnce(A) -> B>` can only be passed to `extern "Swift"` functions."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::StaticInExternSwift { ident } => {
+                let message = r#"Statics can only be declared in `extern "Rust"` blocks."#;
+                Error::new_spanned(ident, message)
+            }
+            ParseError::MutableStatic { mutability } => {
+                let message = "Mutable statics are not supported.";
+                Error::new_spanned(mutability, message)
+            }
+            ParseError::UnsupportedStaticType { ty } => {
+                let message = "Statics must be an opaque Rust type or a primitive type such as `u32`, `f64` or `bool`.";
+                Error::new_spanned(ty, message)
+            }
+            ParseError::AsyncExternSwiftFn { asyncness } => {
+                let message = r#"`extern "Swift"` functions can't be async, since Rust can't await a Swift async function. Declare a function that takes a trailing `Box<dyn FnOnce(T)>` completion handler and use `#[swift_bridge(completion_handler)]` to await it from Rust instead."#;
+                Error::new_spanned(asyncness, message)
+            }
+            ParseError::ExternSwiftReturnsReference { ty } => {
+                let message = r#"`extern "Swift"` functions can only return references to `str` or to a slice. Swift can't lend out its values for as long as Rust would borrow them, so return an owned value instead."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::ExternSwiftReturnsBoxedFn { ty } => {
+                let message = r#"`extern "Swift"` functions can't return a `Box<dyn FnOnce>`. Pass the callback to the Swift function as an argument instead."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::ExternSwiftArgBorrowsSwiftType { ty } => {
+                let message = r#"`extern "Swift"` functions can't borrow an `extern "Swift"` type argument, since the type is handed to Swift by value. Take the argument by value instead."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::ProtocolInExternRust { ty } => {
+                let message = r#"Protocols can only be declared in `extern "Swift"` blocks."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidObservableObjectType { ty } => {
+                let message = r#"ObservableObject can only be used on opaque Rust types that don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidCodableType { ty } => {
+                let message = r#"Codable can only be used on opaque Rust types that don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidPublisherType { ty } => {
+                let message = r#"Publisher can only be used on opaque Rust types that don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidAsyncStreamType { ty } => {
+                let message = r#"AsyncStream can only be used on opaque Rust types that don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidLockType { ty } => {
+                let message = r#"Mutex and RwLock can only be used on non-generic opaque Rust types that don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidSendableType { ty } => {
+                let message = r#"Sendable can only be used on opaque Rust types."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidObjcType { ty } => {
+                let message = r#"objc can only be used on opaque Rust types that aren't generic and don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidActorType { ty } => {
+                let message = r#"actor can only be used on opaque Swift types."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidOpenType { ty } => {
+                let message = r#"open can only be used on public opaque Rust types that aren't generic and don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidIdentifiableType { ty } => {
+                let message = r#"Identifiable can only be used on opaque Rust types that don't use the Copy attribute."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::IdentifiableGetterNotFound { ty, getter } => {
+                let message = format!(
+                    r#"Type `{}` does not have a `{}(&self)` method that returns a value to use as its Identifiable id."#,
+                    ty,
+                    getter.value()
+                );
+                Error::new_spanned(getter, message)
+            }
+            ParseError::UnsupportedObservablePropertyType { ty } => {
+                let message = "ObservableObject properties must be a primitive type such as `u32`, `f64` or `bool`, or a `String`.";
+                Error::new_spanned(ty, message)
+            }
+            ParseError::InvalidSymbolPrefix { prefix } => {
+                let message = format!(
+                    r#"Symbol prefix "{}" must start with a letter or underscore and only contain letters, digits and underscores."#,
+                    prefix.value()
+                );
+                Error::new_spanned(prefix, message)
+            }
+            ParseError::DuplicateDeclaration {
+                duplicate,
+                previous,
+            } => {
+                let mut error = Error::new_spanned(
+                    &duplicate,
+                    format!(
+                        "`{}` is declared more than once in this bridge module.",
+                        duplicate
+                    ),
+                );
+                error.combine(Error::new_spanned(
+                    &previous,
+                    format!("`{}` was first declared here.", previous),
+                ));
+                error
+            }
+            ParseError::IgnoredTypeUsed { ignored, usage } => {
+                let message = format!(
+                    r#"Type `{}` uses #[swift_bridge(ignore)], so it cannot be used by bridged items. Either ignore this item too or remove the `ignore` attribute."#,
+                    ignored
+                );
+                Error::new_spanned(usage, message)
+            }
+            ParseError::UnusedType { ty } => {
+                let message = format!(
+                    r#"`{}` is declared in the bridge module but none of the module's functions use it. Remove it, or use `#[swift_bridge(unused_types = "allow")]` on the bridge module."#,
+                    ty
+                );
+                Error::new_spanned(ty, message)
+            }
         }
     }
 }
@@ -12,18 +12,30 @@ use syn::Path;
 
 use crate::bridge_module_attributes::CfgAttr;
 use crate::parse::TypeDeclarations;
+use crate::parsed_const::ParsedConst;
 use crate::parsed_extern_fn::ParsedExternFn;
+use crate::parsed_extern_static::ParsedExternStatic;
 
 pub use self::bridge_macro_attributes::{SwiftBridgeModuleAttr, SwiftBridgeModuleAttrs};
-pub use self::codegen::CodegenConfig;
+pub use self::codegen::{break_long_swift_lines, CodegenConfig};
+pub use self::config::{
+    ConfigFormatting, ConfigOutput, ConfigSwiftcCheck, SwiftBridgeConfig, CONFIG_FILE_NAME,
+};
+pub use self::declared_names::DeclaredName;
 
+mod config;
+mod declared_names;
 mod errors;
 mod parse;
 
 mod bridge_macro_attributes;
 mod bridge_module_attributes;
 mod bridged_type;
+mod parsed_const;
 mod parsed_extern_fn;
+mod parsed_extern_static;
+mod swift_keywords;
+mod unused_types;
 
 mod codegen;
 
@@ -32,6 +44,10 @@ mod test_utils;
 
 const SWIFT_BRIDGE_PREFIX: &'static str = "__swift_bridge__";
 
+/// The default prefix of the C types that are shared by all bridge modules, such as
+/// `__private__OptionU8`.
+pub const PRIVATE_TYPE_PREFIX: &str = "__private__";
+
 /// Represents a type definition within an `extern "Rust"` module, as well as all of its methods.
 ///
 /// ```no_run,ignore
@@ -60,8 +76,19 @@ pub struct SwiftBridgeModule {
     name: Ident,
     types: TypeDeclarations,
     functions: Vec<ParsedExternFn>,
+    consts: Vec<ParsedConst>,
+    statics: Vec<ParsedExternStatic>,
     swift_bridge_path: Path,
     cfg_attrs: Vec<CfgAttr>,
+    /// `#[swift_bridge(symbol_prefix = "...")]`
+    symbol_prefix: Option<String>,
+    /// `#[swift_bridge(default_visibility)]`
+    default_visibility: bool,
+    /// `#[swift_bridge(library_evolution)]`
+    library_evolution: bool,
+    /// The opaque types that nothing in the module uses, which we warn about unless the module
+    /// has an `unused_types = "allow"` or `unused_types = "deny"` attribute.
+    unused_types: Vec<Ident>,
 }
 
 impl SwiftBridgeModule {
@@ -70,6 +97,54 @@ impl SwiftBridgeModule {
     pub fn set_swift_bridge_path(&mut self, path: Path) {
         self.swift_bridge_path = path;
     }
+
+    /// Use the `symbol_prefix` from the crate's `swift-bridge.toml`, unless the module has its own
+    /// `#[swift_bridge(symbol_prefix = "...")]` attribute.
+    pub fn set_configured_symbol_prefix(&mut self, prefix: &str) {
+        if self.symbol_prefix.is_none() {
+            self.set_symbol_prefix(prefix.to_string());
+        }
+    }
+
+    /// Use the prefix for the symbols and C types that the module declares.
+    ///
+    /// The symbols of the `swift_bridge` runtime, such as the `RustString` and `Option<u8>`
+    /// helpers, are declared in the core header and keep their `__swift_bridge__` prefix.
+    fn set_symbol_prefix(&mut self, prefix: String) {
+        self.types.set_symbol_prefix(&prefix);
+        for function in self.functions.iter_mut() {
+            function.set_symbol_prefix(&prefix);
+        }
+        for parsed_const in self.consts.iter_mut() {
+            parsed_const.symbol_prefix = prefix.clone();
+        }
+        for parsed_static in self.statics.iter_mut() {
+            parsed_static.symbol_prefix = prefix.clone();
+        }
+        self.symbol_prefix = Some(prefix);
+    }
+
+    /// Generate Swift that can be built with library evolution enabled, as configured by the
+    /// crate's `swift-bridge.toml`, even if the module doesn't have a
+    /// `#[swift_bridge(library_evolution)]` attribute.
+    pub fn set_library_evolution(&mut self) {
+        self.library_evolution = true;
+    }
+
+    /// Name the shared `__private__` C types, such as `__private__OptionU8`, with the
+    /// `private_type_prefix` from the crate's `swift-bridge.toml` instead.
+    ///
+    /// The core header has to be generated with the same prefix.
+    pub fn set_private_type_prefix(&mut self, prefix: &str) {
+        self.types.set_private_type_prefix(prefix);
+    }
+
+    /// Report the calls to the module's Rust functions using `swift_bridge::call_tracing`.
+    pub fn set_call_tracing(&mut self) {
+        for function in self.functions.iter_mut() {
+            function.call_tracing = function.host_lang.is_rust();
+        }
+    }
 }
 
 #[cfg(test)]
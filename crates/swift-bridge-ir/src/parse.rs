@@ -1,19 +1,34 @@
-use crate::bridge_module_attributes::CfgAttr;
-use crate::bridged_type::BridgedType;
-use crate::errors::{ParseError, ParseErrors};
+use crate::bridge_module_attributes::{CfgAttr, ModuleSwiftBridgeAttr, UnusedTypesLevel};
+use crate::bridged_type::{pat_type_pat_is_self, BridgedType, StdLibType};
+use crate::errors::{FunctionAttributeParseError, ParseError, ParseErrors};
+use crate::parse::parse_const::{ConstDeclaration, ConstDeclarationParser};
 use crate::parse::parse_enum::SharedEnumDeclarationParser;
 use crate::parse::parse_extern_mod::ForeignModParser;
 use crate::parse::parse_struct::SharedStructDeclarationParser;
+use crate::parsed_extern_fn::ParsedExternFn;
+use crate::parsed_extern_static::ParsedExternStatic;
+use crate::unused_types::find_unused_types;
 use crate::SwiftBridgeModule;
-use proc_macro2::TokenTree;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
+use std::ops::Deref;
 use syn::parse::{Parse, ParseStream};
-use syn::{Item, ItemMod, Token};
+use syn::{
+    FnArg, GenericArgument, Item, ItemMod, LitStr, PathArguments, ReturnType, Token, Type,
+    TypeParamBound, TypePath, TypeReference,
+};
 
+mod parse_const;
 mod parse_enum;
 mod parse_extern_mod;
 mod parse_struct;
 
+mod swift_access_level;
+mod swift_availability;
+pub(crate) use self::parse_extern_mod::LockKind;
+pub(crate) use self::swift_access_level::SwiftAccessLevel;
+pub(crate) use self::swift_availability::SwiftAvailability;
+
 mod type_declarations;
 pub(crate) use self::type_declarations::*;
 
@@ -61,7 +76,17 @@ impl Parse for SwiftBridgeModuleAndErrors {
             let mut functions = vec![];
             let mut type_declarations = TypeDeclarations::default();
             let mut unresolved_types = vec![];
+            let mut ignored_types = vec![];
+            let mut consts = vec![];
+            let mut statics = vec![];
             let mut cfg_attrs = vec![];
+            let mut default_swift_access = SwiftAccessLevel::default();
+            let mut symbol_prefix = None;
+            let mut swift_name_prefix = None;
+            let mut default_visibility = false;
+            let mut library_evolution = false;
+            let mut panic_policy = None;
+            let mut unused_types_level = UnusedTypesLevel::default();
 
             for attr in item_mod.attrs {
                 match attr.path.to_token_stream().to_string().as_str() {
@@ -69,6 +94,43 @@ impl Parse for SwiftBridgeModuleAndErrors {
                         let cfg: CfgAttr = syn::parse2(attr.tokens)?;
                         cfg_attrs.push(cfg);
                     }
+                    // `#[swift_bridge(...)]`, as opposed to something like `#[swift_bridge:bridge]`.
+                    "swift_bridge"
+                        if matches!(
+                            attr.tokens.clone().into_iter().next(),
+                            Some(TokenTree::Group(group))
+                                if group.delimiter() == proc_macro2::Delimiter::Parenthesis
+                        ) =>
+                    {
+                        let module_attr: ModuleSwiftBridgeAttr = attr.parse_args()?;
+                        match module_attr {
+                            ModuleSwiftBridgeAttr::SwiftAccess(access) => {
+                                default_swift_access = access;
+                            }
+                            ModuleSwiftBridgeAttr::SymbolPrefix(prefix) => {
+                                if is_valid_symbol_prefix(&prefix.value()) {
+                                    symbol_prefix = Some(prefix.value());
+                                } else {
+                                    errors.push(ParseError::InvalidSymbolPrefix { prefix });
+                                }
+                            }
+                            ModuleSwiftBridgeAttr::SwiftNamePrefix(prefix) => {
+                                swift_name_prefix = Some(prefix);
+                            }
+                            ModuleSwiftBridgeAttr::DefaultVisibility => {
+                                default_visibility = true;
+                            }
+                            ModuleSwiftBridgeAttr::LibraryEvolution => {
+                                library_evolution = true;
+                            }
+                            ModuleSwiftBridgeAttr::Panic(policy) => {
+                                panic_policy = Some(policy);
+                            }
+                            ModuleSwiftBridgeAttr::UnusedTypes(level) => {
+                                unused_types_level = level;
+                            }
+                        }
+                    }
                     _ => {}
                 };
             }
@@ -81,33 +143,81 @@ impl Parse for SwiftBridgeModuleAndErrors {
                             type_declarations: &mut type_declarations,
                             functions: &mut functions,
                             unresolved_types: &mut unresolved_types,
+                            ignored_types: &mut ignored_types,
+                            statics: &mut statics,
+                            default_swift_access,
+                            swift_name_prefix: swift_name_prefix.as_ref(),
+                            panic_policy,
                         }
                         .parse(foreign_mod)?;
                     }
                     Item::Struct(item_struct) => {
-                        let shared_struct = SharedStructDeclarationParser {
+                        let mut shared_struct = SharedStructDeclarationParser {
                             item_struct,
                             errors: &mut errors,
                         }
                         .parse()?;
+                        if let Some(prefix) = swift_name_prefix.as_ref() {
+                            shared_struct.swift_name.get_or_insert_with(|| {
+                                prefixed_swift_name(prefix, &shared_struct.name)
+                            });
+                        }
+                        if let Some(previous) =
+                            type_declarations.get(&shared_struct.name.to_string())
+                        {
+                            errors.push(ParseError::DuplicateDeclaration {
+                                duplicate: shared_struct.name.clone(),
+                                previous: previous.ident().clone(),
+                            });
+                            continue;
+                        }
                         type_declarations.insert(
                             shared_struct.name.to_string(),
                             TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)),
                         );
                     }
                     Item::Enum(item_enum) => {
-                        let shared_enum = SharedEnumDeclarationParser {
+                        let mut shared_enum = SharedEnumDeclarationParser {
                             item_enum,
                             errors: &mut errors,
                         }
                         .parse()?;
+                        if let Some(prefix) = swift_name_prefix.as_ref() {
+                            shared_enum.swift_name.get_or_insert_with(|| {
+                                prefixed_swift_name(prefix, &shared_enum.name)
+                            });
+                        }
+                        if let Some(previous) = type_declarations.get(&shared_enum.name.to_string())
+                        {
+                            errors.push(ParseError::DuplicateDeclaration {
+                                duplicate: shared_enum.name.clone(),
+                                previous: previous.ident().clone(),
+                            });
+                            continue;
+                        }
                         type_declarations.insert(
                             shared_enum.name.to_string(),
                             TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)),
                         );
                     }
+                    // `const MAX_USERS: u32;`
+                    Item::Verbatim(tokens)
+                        if syn::parse2::<ConstDeclaration>(tokens.clone()).is_ok() =>
+                    {
+                        let declaration = syn::parse2(tokens).unwrap();
+                        let parsed_const = ConstDeclarationParser {
+                            declaration,
+                            type_declarations: &type_declarations,
+                            errors: &mut errors,
+                            default_swift_access,
+                        }
+                        .parse()?;
+                        consts.extend(parsed_const);
+                    }
                     invalid_item => {
-                        let error = ParseError::InvalidModuleItem { item: invalid_item };
+                        let error = ParseError::InvalidModuleItem {
+                            item: Box::new(invalid_item),
+                        };
                         errors.push(error);
                     }
                 };
@@ -118,18 +228,198 @@ impl Parse for SwiftBridgeModuleAndErrors {
                     continue;
                 }
 
-                errors.push(ParseError::UndeclaredType {
-                    ty: unresolved_type.clone(),
+                let contains_128_bit_int = ["u128", "i128"].iter().any(|int| {
+                    tokens_contain_ident(
+                        unresolved_type.to_token_stream(),
+                        &Ident::new(int, Span::call_site()),
+                    )
+                });
+                if contains_128_bit_int {
+                    errors.push(ParseError::UnsupportedInt128 {
+                        ty: unresolved_type.clone(),
+                    });
+                    continue;
+                }
+
+                if let Some(unsupported) = find_unsupported_type(&unresolved_type) {
+                    errors.push(ParseError::UnsupportedType {
+                        ty: unsupported.clone(),
+                    });
+                    continue;
+                }
+
+                let ignored = ignored_types.iter().find(|ignored| {
+                    tokens_contain_ident(unresolved_type.to_token_stream(), ignored)
                 });
+                if let Some(ignored) = ignored {
+                    errors.push(ParseError::IgnoredTypeUsed {
+                        ignored: ignored.clone(),
+                        usage: unresolved_type.to_token_stream(),
+                    });
+                    continue;
+                }
+
+                errors.push(unresolved_type_error(&unresolved_type, &type_declarations));
             }
 
-            let module = SwiftBridgeModule {
+            for setter in functions.iter() {
+                let getter_name = match setter.swift_property_setter_name() {
+                    Some(getter_name) => getter_name,
+                    None => continue,
+                };
+
+                let has_getter = functions.iter().any(|getter| {
+                    getter.is_swift_property_getter()
+                        && getter.sig.ident == getter_name
+                        && getter.associated_type_name() == setter.associated_type_name()
+                        && getter.swift_property_type_tokens()
+                            == setter.swift_property_type_tokens()
+                });
+                if !has_getter {
+                    errors.push(ParseError::FunctionAttribute(
+                        FunctionAttributeParseError::SwiftPropertySetterWithoutGetter {
+                            fn_ident: setter.sig.ident.clone(),
+                        },
+                    ));
+                }
+            }
+
+            for function in functions.iter().filter(|f| f.host_lang.is_swift()) {
+                push_extern_swift_signature_errors(function, &type_declarations, &mut errors);
+            }
+
+            // `extern "Rust"` completion handlers already have an error for the attribute.
+            for function in functions
+                .iter()
+                .filter(|f| f.host_lang.is_rust() && !f.is_completion_handler)
+            {
+                push_extern_rust_signature_errors(function, &mut errors);
+            }
+
+            for function in functions.iter().filter(|f| f.is_completion_handler) {
+                if function.completion_handler(&type_declarations).is_none() {
+                    errors.push(ParseError::FunctionAttribute(
+                        FunctionAttributeParseError::InvalidCompletionHandler {
+                            fn_ident: function.func.sig.ident.clone(),
+                        },
+                    ));
+                }
+            }
+
+            for function in functions.iter().filter(|f| f.queue.is_some()) {
+                if !function.can_be_dispatched_to_queue() {
+                    errors.push(ParseError::FunctionAttribute(
+                        FunctionAttributeParseError::InvalidQueue {
+                            fn_ident: function.func.sig.ident.clone(),
+                        },
+                    ));
+                }
+            }
+
+            for getter in functions.iter() {
+                let is_observable_object = match getter.associated_type.as_ref() {
+                    Some(TypeDeclaration::Opaque(ty)) => ty.attributes.observable_object,
+                    _ => false,
+                };
+                if !is_observable_object || !getter.is_swift_property_getter() {
+                    continue;
+                }
+
+                if let ReturnType::Type(_, ty) = &getter.sig.output {
+                    let is_supported = match BridgedType::new_with_type(ty, &type_declarations) {
+                        Some(bridged) => bridged.is_primitive() || bridged.is_string(),
+                        // We've already pushed an error for the undeclared type.
+                        None => true,
+                    };
+                    if !is_supported {
+                        errors.push(ParseError::UnsupportedObservablePropertyType {
+                            ty: ty.deref().clone(),
+                        });
+                    }
+                }
+            }
+
+            for ty in type_declarations.types() {
+                let ty = match ty {
+                    TypeDeclaration::Opaque(ty) => ty,
+                    _ => continue,
+                };
+                let getter = match ty.attributes.identifiable.as_ref() {
+                    Some(getter) => getter,
+                    None => continue,
+                };
+
+                let has_getter = functions.iter().any(|func| {
+                    func.is_method()
+                        && func.self_reference().is_some()
+                        && func.sig.inputs.len() == 1
+                        && !matches!(func.sig.output, ReturnType::Default)
+                        && func.sig.ident == getter.value()
+                        && func.associated_type_name().as_deref()
+                            == Some(ty.ty.to_string().as_str())
+                });
+                if !has_getter {
+                    errors.push(ParseError::IdentifiableGetterNotFound {
+                        ty: ty.ty.clone(),
+                        getter: getter.clone(),
+                    });
+                }
+            }
+
+            statics.retain(|parsed_static: &ParsedExternStatic| {
+                if parsed_static.atomic_value_type().is_some() {
+                    return true;
+                }
+
+                let is_supported =
+                    match BridgedType::new_with_type(&parsed_static.ty, &type_declarations) {
+                        Some(ty) if ty.is_primitive() => true,
+                        Some(_) => matches!(
+                            type_declarations.get(&parsed_static.ty.to_token_stream().to_string()),
+                            Some(TypeDeclaration::Opaque(opaque))
+                                if opaque.host_lang.is_rust()
+                                    && opaque.attributes.copy.is_none()
+                                    && opaque.generics.generics.is_empty()
+                        ),
+                        None => false,
+                    };
+                if !is_supported {
+                    errors.push(ParseError::UnsupportedStaticType {
+                        ty: parsed_static.ty.clone(),
+                    });
+                }
+
+                is_supported
+            });
+
+            let unused_types = find_unused_types(&type_declarations, &functions, &statics);
+            let unused_types = match unused_types_level {
+                UnusedTypesLevel::Allow => vec![],
+                UnusedTypesLevel::Warn => unused_types,
+                UnusedTypesLevel::Deny => {
+                    for ty in unused_types {
+                        errors.push(ParseError::UnusedType { ty });
+                    }
+                    vec![]
+                }
+            };
+
+            let mut module = SwiftBridgeModule {
                 name: module_name,
                 types: type_declarations,
                 functions,
+                consts,
+                statics,
                 swift_bridge_path: syn::parse2(quote! { swift_bridge }).unwrap(),
                 cfg_attrs,
+                symbol_prefix: None,
+                default_visibility,
+                library_evolution,
+                unused_types,
             };
+            if let Some(symbol_prefix) = symbol_prefix {
+                module.set_symbol_prefix(symbol_prefix);
+            }
             Ok(SwiftBridgeModuleAndErrors { module, errors })
         } else {
             return Err(syn::Error::new_spanned(
@@ -140,6 +430,23 @@ impl Parse for SwiftBridgeModuleAndErrors {
     }
 }
 
+/// The Swift name for an item in a module that uses `#[swift_bridge(swift_name_prefix = "...")]`,
+/// such as `MCConfig` for a `Config` type in a module with the `MC` prefix.
+pub(crate) fn prefixed_swift_name(prefix: &LitStr, name: &Ident) -> LitStr {
+    LitStr::new(&format!("{}{}", prefix.value(), name), name.span())
+}
+
+// The prefix ends up at the start of C function and type names, so it must be a valid C
+// identifier.
+pub(crate) fn is_valid_symbol_prefix(prefix: &str) -> bool {
+    let mut chars = prefix.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
+        _ => return false,
+    };
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 // Used to fast-forward our attribute parsing to the next attribute when we've run into an
 // issue parsing the current attribute.
 fn move_input_cursor_to_next_comma(input: ParseStream) {
@@ -161,11 +468,385 @@ fn move_input_cursor_to_next_comma(input: ParseStream) {
     }
 }
 
+/// Push errors for `extern "Swift"` function signatures that we can't generate working code for,
+/// such as `fn get(&self) -> &SomeType`, since the Swift value can't be lent out to Rust.
+///
+/// Types that aren't declared are skipped, since we've already pushed an error for them.
+fn push_extern_swift_signature_errors(
+    function: &ParsedExternFn,
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    if let Some(asyncness) = function.sig.asyncness {
+        errors.push(ParseError::AsyncExternSwiftFn { asyncness });
+    }
+
+    for arg in function.sig.inputs.iter() {
+        let pat_ty = match arg {
+            FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => pat_ty,
+            _ => continue,
+        };
+        if BridgedType::new_with_type(&pat_ty.ty, types).is_none() {
+            continue;
+        }
+
+        for reference in type_references(&pat_ty.ty) {
+            let borrows_swift_type = matches!(
+                types.get_with_type(&reference.elem),
+                Some(TypeDeclaration::Opaque(opaque)) if opaque.host_lang.is_swift()
+            );
+            if borrows_swift_type {
+                errors.push(ParseError::ExternSwiftArgBorrowsSwiftType {
+                    ty: Type::Reference(reference.clone()),
+                });
+            }
+        }
+    }
+
+    if let ReturnType::Type(_, ty) = &function.sig.output {
+        let bridged = match BridgedType::new_with_type(ty, types) {
+            Some(bridged) => bridged,
+            None => return,
+        };
+
+        let is_str_or_slice = match ty.deref() {
+            Type::Reference(reference) => {
+                matches!(reference.elem.deref(), Type::Slice(_))
+                    || reference.elem.to_token_stream().to_string() == "str"
+            }
+            _ => false,
+        };
+        if !is_str_or_slice && !type_references(ty).is_empty() {
+            errors.push(ParseError::ExternSwiftReturnsReference {
+                ty: ty.deref().clone(),
+            });
+        }
+
+        if matches!(bridged, BridgedType::StdLib(StdLibType::BoxedFnOnce(_))) {
+            errors.push(ParseError::ExternSwiftReturnsBoxedFn {
+                ty: ty.deref().clone(),
+            });
+        }
+    }
+}
+
+/// Push errors for `extern "Rust"` function signatures that we can't generate working code for,
+/// such as `fn run(callback: Box<dyn FnOnce()>)`.
+fn push_extern_rust_signature_errors(function: &ParsedExternFn, errors: &mut ParseErrors) {
+    let fn_once = Ident::new("FnOnce", Span::call_site());
+
+    let arg_types = function.sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Typed(pat_ty) => Some(pat_ty.ty.deref()),
+        FnArg::Receiver(_) => None,
+    });
+    let return_type = match &function.sig.output {
+        ReturnType::Type(_, ty) => Some(ty.deref()),
+        ReturnType::Default => None,
+    };
+
+    for ty in arg_types.chain(return_type) {
+        if tokens_contain_ident(ty.to_token_stream(), &fn_once) {
+            errors.push(ParseError::ExternRustBoxedFn { ty: ty.clone() });
+        }
+    }
+}
+
+/// The references in a type, such as the `&SomeType` in `Option<&SomeType>`.
+fn type_references(ty: &Type) -> Vec<&TypeReference> {
+    match ty {
+        Type::Reference(reference) => {
+            let mut references = vec![reference];
+            references.extend(type_references(&reference.elem));
+            references
+        }
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .flat_map(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+                _ => vec![],
+            })
+            .flat_map(|arg| match arg {
+                GenericArgument::Type(ty) => type_references(ty),
+                _ => vec![],
+            })
+            .collect(),
+        Type::Tuple(tuple) => tuple.elems.iter().flat_map(type_references).collect(),
+        Type::Slice(slice) => type_references(&slice.elem),
+        Type::Array(array) => type_references(&array.elem),
+        Type::Paren(paren) => type_references(&paren.elem),
+        Type::Group(group) => type_references(&group.elem),
+        _ => vec![],
+    }
+}
+
+/// Whether or not the tokens, such as the tokens for `Option<&SomeType>`, contain an identifier.
+fn tokens_contain_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(token_ident) => &token_ident == ident,
+        TokenTree::Group(group) => tokens_contain_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Find a type that can never be bridged, such as a tuple, an array or a function pointer, so that
+/// we can point at it instead of reporting the whole type as undeclared.
+fn find_unsupported_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Tuple(tuple) if tuple.elems.is_empty() => None,
+        Type::Tuple(_) | Type::Array(_) | Type::BareFn(_) | Type::ImplTrait(_) => Some(ty),
+        Type::Reference(reference) => find_unsupported_type(&reference.elem),
+        Type::Ptr(ptr) => find_unsupported_type(&ptr.elem),
+        Type::Slice(slice) => find_unsupported_type(&slice.elem),
+        Type::Paren(paren) => find_unsupported_type(&paren.elem),
+        Type::Group(group) => find_unsupported_type(&group.elem),
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .find_map(|segment| find_unsupported_type_in_args(&segment.arguments)),
+        Type::TraitObject(trait_object) => {
+            trait_object.bounds.iter().find_map(|bound| match bound {
+                TypeParamBound::Trait(bound) => bound
+                    .path
+                    .segments
+                    .iter()
+                    .find_map(|segment| find_unsupported_type_in_args(&segment.arguments)),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The error for a type that we couldn't bridge, pointing at the part of the type that we couldn't
+/// bridge. For example, the `Foo` in `Vec<Foo>` if `Foo` wasn't declared, or the whole type for
+/// `Option<Vec<u8>>`, since we can bridge `Vec<u8>` but not an `Option` of it.
+fn unresolved_type_error(ty: &Type, types: &TypeDeclarations) -> ParseError {
+    let inner_types: Vec<&Type> = match ty {
+        Type::Reference(reference) => vec![&reference.elem],
+        Type::Path(path) if is_std_lib_container(path) => path
+            .path
+            .segments
+            .iter()
+            .flat_map(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+                _ => vec![],
+            })
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => return ParseError::UndeclaredType { ty: ty.clone() },
+    };
+
+    match inner_types
+        .into_iter()
+        .find(|inner| BridgedType::new_with_type(inner, types).is_none())
+    {
+        Some(unresolved_inner) => unresolved_type_error(unresolved_inner, types),
+        None => ParseError::UnsupportedType { ty: ty.clone() },
+    }
+}
+
+/// Whether or not the type is an `Option`, `Vec` or `Result`.
+fn is_std_lib_container(ty: &TypePath) -> bool {
+    ty.qself.is_none()
+        && ty.path.segments.len() == 1
+        && ["Option", "Vec", "Result"]
+            .iter()
+            .any(|container| ty.path.segments[0].ident == container)
+}
+
+fn find_unsupported_type_in_args(args: &PathArguments) -> Option<&Type> {
+    match args {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => find_unsupported_type(ty),
+            _ => None,
+        }),
+        PathArguments::Parenthesized(args) => args
+            .inputs
+            .iter()
+            .find_map(find_unsupported_type)
+            .or_else(|| match &args.output {
+                ReturnType::Type(_, ty) => find_unsupported_type(ty),
+                ReturnType::Default => None,
+            }),
+        PathArguments::None => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bridge_module_attributes::{PanicPolicy, TargetEnv, TargetOs};
     use crate::test_utils::{parse_errors, parse_ok};
 
+    /// Verify that a module's `swift_name_prefix` attribute sets the Swift names of its types
+    /// and free functions, but not of its methods or extern "Swift" items.
+    #[test]
+    fn parse_module_swift_name_prefix() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(swift_name_prefix = "MC")]
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_method(&self);
+                    fn some_function();
+                }
+
+                extern "Swift" {
+                    type SwiftType;
+
+                    fn swift_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert_eq!(ty.swift_name_string(), "MCSomeType");
+        let ty = module.types.get("SwiftType").unwrap().unwrap_opaque();
+        assert_eq!(ty.swift_name_string(), "SwiftType");
+
+        let swift_names: Vec<Option<String>> = module
+            .functions
+            .iter()
+            .map(|func| func.swift_name_override.as_ref().map(|name| name.value()))
+            .collect();
+        assert_eq!(
+            swift_names,
+            vec![None, Some("MCsome_function".to_string()), None]
+        );
+    }
+
+    /// Verify that we can parse a module's `default_visibility` attribute.
+    #[test]
+    fn parse_module_default_visibility() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(default_visibility)]
+            mod ffi {}
+        };
+        let module = parse_ok(tokens);
+
+        assert!(module.default_visibility);
+
+        let module = parse_ok(quote! { mod ffi {} });
+        assert!(!module.default_visibility);
+    }
+
+    /// Verify that a module's `panic` attribute gets used for its Rust functions.
+    #[test]
+    fn parse_module_panic_policy() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(panic = "fatal_error")]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function();
+                }
+            }
+        };
+        let module = parse_ok(tokens);
+        assert_eq!(
+            module.functions[0].panic_policy,
+            Some(PanicPolicy::FatalError)
+        );
+
+        let module = parse_ok(quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function();
+                }
+            }
+        });
+        assert_eq!(module.functions[0].panic_policy, None);
+    }
+
+    /// Verify that we push an error for an unknown panic policy.
+    #[test]
+    fn error_if_unknown_panic_policy() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(panic = "unwind")]
+            mod ffi {}
+        };
+
+        let err = syn::parse2::<SwiftBridgeModuleAndErrors>(tokens)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            r#"Unsupported panic policy "unwind". Expected abort, fatal_error or throw."#
+        );
+    }
+
+    /// Verify that we can parse a module's `symbol_prefix` attribute.
+    #[test]
+    fn parse_module_symbol_prefix() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(symbol_prefix = "__my_crate__")]
+            mod foo {}
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(module.symbol_prefix.as_deref(), Some("__my_crate__"));
+    }
+
+    /// Verify that a module uses the configured symbol prefix, unless it has its own
+    /// `symbol_prefix` attribute.
+    #[test]
+    fn configured_symbol_prefix() {
+        let module = parse_ok(quote! {
+            #[swift_bridge::bridge]
+            mod foo {}
+        });
+        assert_eq!(module.symbol_prefix, None);
+
+        let mut module = parse_ok(quote! {
+            #[swift_bridge::bridge]
+            mod foo {}
+        });
+        module.set_configured_symbol_prefix("__my_crate__");
+        assert_eq!(module.symbol_prefix.as_deref(), Some("__my_crate__"));
+
+        let mut module = parse_ok(quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(symbol_prefix = "__custom__")]
+            mod foo {}
+        });
+        module.set_configured_symbol_prefix("__my_crate__");
+        assert_eq!(module.symbol_prefix.as_deref(), Some("__custom__"));
+    }
+
+    /// Verify that we push an error if a module's `symbol_prefix` is not a valid C identifier.
+    #[test]
+    fn error_if_invalid_symbol_prefix() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            #[swift_bridge(symbol_prefix = "my-crate")]
+            mod foo {}
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::InvalidSymbolPrefix { prefix } => {
+                assert_eq!(prefix.value(), "my-crate");
+            }
+            _ => panic!(),
+        }
+    }
+
     /// Verify that we can parse a cfg feature from a module.
     #[test]
     fn parse_module_cfg_feature() {
@@ -183,7 +864,104 @@ mod tests {
             CfgAttr::Feature(feature) => {
                 assert_eq!(feature.value(), "some-feature")
             }
+            _ => panic!(),
+        };
+    }
+
+    /// Verify that we can parse cfg attributes on extern types and functions, and that methods
+    /// inherit the cfg attributes of their type.
+    #[test]
+    fn parse_extern_item_cfg_attributes() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod foo {
+                extern "Rust" {
+                    #[cfg(target_os = "ios")]
+                    type SomeType;
+
+                    #[cfg(feature = "pro")]
+                    fn some_method(&self);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(matches!(
+            ty.attributes.cfg_attrs.as_slice(),
+            [CfgAttr::TargetOs(TargetOs::Ios)]
+        ));
+
+        let func = &module.functions[0];
+        assert_eq!(func.cfg_attrs.len(), 1);
+        assert!(matches!(
+            func.all_cfg_attrs().as_slice(),
+            [CfgAttr::Feature(feature), CfgAttr::TargetOs(TargetOs::Ios)] if feature.value() == "pro"
+        ));
+    }
+
+    /// Verify that we can parse `target_env`, `target_abi` and `not` cfg attributes.
+    #[test]
+    fn parse_target_env_cfg_attributes() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod foo {
+                extern "Rust" {
+                    #[cfg(not(target_env = "sim"))]
+                    fn some_function();
+
+                    #[cfg(target_abi = "macabi")]
+                    fn another_function();
+                }
+            }
         };
+
+        let module = parse_ok(tokens);
+
+        assert!(matches!(
+            module.functions[0].cfg_attrs.as_slice(),
+            [CfgAttr::Not(cfg)] if matches!(cfg.as_ref(), CfgAttr::TargetEnv(key, TargetEnv::Simulator) if key == "target_env")
+        ));
+        assert!(matches!(
+            module.functions[1].cfg_attrs.as_slice(),
+            [CfgAttr::TargetEnv(key, TargetEnv::MacCatalyst)] if key == "target_abi"
+        ));
+    }
+
+    /// Verify that we get an error for unsupported cfg attributes.
+    #[test]
+    fn error_if_unsupported_cfg_attribute() {
+        let tests = vec![
+            quote! {
+                mod foo {
+                    extern "Rust" {
+                        #[cfg(target_os = "plan9")]
+                        fn some_function();
+                    }
+                }
+            },
+            quote! {
+                mod foo {
+                    extern "Rust" {
+                        #[cfg(debug_assertions)]
+                        type SomeType;
+                    }
+                }
+            },
+            quote! {
+                mod foo {
+                    extern "Rust" {
+                        #[cfg(target_env = "gnu")]
+                        fn some_function();
+                    }
+                }
+            },
+        ];
+
+        for tokens in tests {
+            assert!(syn::parse2::<SwiftBridgeModuleAndErrors>(tokens).is_err());
+        }
     }
 
     /// Verify that we get an error when parsing an unsupported module item, such as a
@@ -202,7 +980,7 @@ mod tests {
         assert_eq!(errors.len(), 1);
         match &errors[0] {
             ParseError::InvalidModuleItem { item } => {
-                assert!(matches!(item, Item::Use(_)))
+                assert!(matches!(item.as_ref(), Item::Use(_)))
             }
             _ => panic!(),
         }
@@ -0,0 +1,145 @@
+use crate::bridged_type::BridgedType;
+use crate::errors::{ParseError, ParseErrors};
+use crate::parse::parse_extern_mod::push_doc_comment_line;
+use crate::parse::{SwiftAccessLevel, TypeDeclarations};
+use crate::parsed_const::ParsedConst;
+use crate::SWIFT_BRIDGE_PREFIX;
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Token, Type};
+
+/// `const MAX_USERS: u32;`
+///
+/// `syn` parses a constant without a value as verbatim tokens, so we parse it ourselves.
+pub(crate) struct ConstDeclaration {
+    pub attributes: Vec<Attribute>,
+    #[allow(unused)]
+    pub const_token: Token![const],
+    pub ident: Ident,
+    #[allow(unused)]
+    pub colon: Token![:],
+    pub ty: Type,
+    #[allow(unused)]
+    pub semicolon: Token![;],
+}
+
+pub(crate) struct ConstDeclarationParser<'a> {
+    pub declaration: ConstDeclaration,
+    pub type_declarations: &'a TypeDeclarations,
+    pub errors: &'a mut ParseErrors,
+    /// The module's `#[swift_bridge(swift_access = "...")]`.
+    pub default_swift_access: SwiftAccessLevel,
+}
+
+impl Parse for ConstDeclaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(ConstDeclaration {
+            attributes: input.call(Attribute::parse_outer)?,
+            const_token: input.parse()?,
+            ident: input.parse()?,
+            colon: input.parse()?,
+            ty: input.parse()?,
+            semicolon: input.parse()?,
+        })
+    }
+}
+
+impl<'a> ConstDeclarationParser<'a> {
+    /// Returns `None` if the constant is invalid, in which case an error will have been pushed.
+    pub fn parse(self) -> syn::Result<Option<ParsedConst>> {
+        let declaration = self.declaration;
+
+        let is_primitive = BridgedType::new_with_type(&declaration.ty, self.type_declarations)
+            .map(|ty| ty.is_primitive())
+            .unwrap_or(false);
+        if !is_primitive {
+            self.errors
+                .push(ParseError::UnsupportedConstType { ty: declaration.ty });
+            return Ok(None);
+        }
+
+        let mut doc_comment = None;
+        let mut cfg_attrs = vec![];
+        for attr in declaration.attributes.iter() {
+            if attr.path.is_ident("doc") {
+                push_doc_comment_line(&mut doc_comment, attr)?;
+            } else if attr.path.is_ident("cfg") {
+                cfg_attrs.push(syn::parse2(attr.tokens.clone())?);
+            }
+        }
+
+        Ok(Some(ParsedConst {
+            ident: declaration.ident,
+            ty: declaration.ty,
+            doc_comment,
+            swift_access: self.default_swift_access,
+            cfg_attrs,
+            symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{parse_errors, parse_ok};
+    use quote::quote;
+
+    /// Verify that we can parse a constant that has attributes.
+    #[test]
+    fn parse_const_declaration_with_attributes() {
+        let tokens = quote! {
+            /// Doc attribute.
+            const MAX_USERS: u32;
+        };
+
+        let declaration: ConstDeclaration = syn::parse2(tokens).unwrap();
+
+        assert_eq!(declaration.attributes.len(), 1);
+        assert_eq!(declaration.ident, "MAX_USERS");
+    }
+
+    /// Verify that we can parse a constant from a bridge module.
+    #[test]
+    fn parse_module_const() {
+        let tokens = quote! {
+            mod foo {
+                /// The maximum number of users.
+                #[cfg(feature = "users")]
+                const MAX_USERS: u32;
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(module.consts.len(), 1);
+
+        let parsed = &module.consts[0];
+        assert_eq!(parsed.ident, "MAX_USERS");
+        assert_eq!(
+            parsed.doc_comment.as_deref(),
+            Some(" The maximum number of users.")
+        );
+        assert_eq!(parsed.cfg_attrs.len(), 1);
+    }
+
+    /// Verify that we get an error if a constant does not use a primitive type.
+    #[test]
+    fn error_if_const_type_is_not_primitive() {
+        let tokens = quote! {
+            mod foo {
+                const NAME: String;
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::UnsupportedConstType { ty } => {
+                assert_eq!(quote! {#ty}.to_string(), "String");
+            }
+            _ => panic!(),
+        }
+    }
+}
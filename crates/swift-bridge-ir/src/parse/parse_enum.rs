@@ -1,6 +1,7 @@
 use crate::bridged_type::{EnumVariant, SharedEnum, StructFields};
 use crate::errors::{ParseError, ParseErrors};
 use crate::parse::move_input_cursor_to_next_comma;
+use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
 use syn::{ItemEnum, LitStr, Token};
@@ -104,6 +105,7 @@ impl<'a> SharedEnumDeclarationParser<'a> {
             variants,
             already_declared: attribs.already_declared,
             swift_name: attribs.swift_name,
+            symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
         };
 
         Ok(shared_enum)
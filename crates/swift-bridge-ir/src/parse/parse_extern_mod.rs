@@ -1,25 +1,35 @@
-pub(crate) use self::opaque_type_attributes::OpaqueTypeAllAttributes;
+pub(crate) use self::opaque_type_attributes::{LockKind, OpaqueTypeAllAttributes};
+use crate::bridge_module_attributes::PanicPolicy;
 use crate::bridged_type::{
     bridgeable_type_from_fn_arg, pat_type_pat_is_self, BridgeableType, BridgedType,
 };
 use crate::errors::{FunctionAttributeParseError, IdentifiableParseError, ParseError, ParseErrors};
 use crate::parse::parse_extern_mod::function_attributes::FunctionAttributes;
 use crate::parse::parse_extern_mod::generics::GenericOpaqueType;
+use crate::parse::parse_extern_mod::static_attributes::StaticAllAttributes;
 use crate::parse::type_declarations::{
     OpaqueForeignTypeDeclaration, TypeDeclaration, TypeDeclarations,
 };
-use crate::parse::{HostLang, OpaqueRustTypeGenerics};
+use crate::parse::{prefixed_swift_name, HostLang, OpaqueRustTypeGenerics, SwiftAccessLevel};
 use crate::parsed_extern_fn::fn_arg_is_mutable_reference;
-use crate::ParsedExternFn;
+use crate::parsed_extern_fn::Deprecated;
+use crate::parsed_extern_static::ParsedExternStatic;
+use crate::swift_keywords::is_valid_swift_identifier;
+use crate::{ParsedExternFn, SWIFT_BRIDGE_PREFIX};
+use proc_macro2::Ident;
 use quote::ToTokens;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::ops::Deref;
-use syn::{FnArg, ForeignItem, ForeignItemFn, GenericParam, ItemForeignMod, Pat, ReturnType, Type};
+use syn::{
+    parse_quote, Attribute, FnArg, ForeignItem, ForeignItemFn, ForeignItemStatic, GenericArgument,
+    GenericParam, ItemForeignMod, LitStr, Meta, Pat, PathArguments, ReturnType, Type,
+};
 
 mod function_attributes;
 mod generics;
 mod opaque_type_attributes;
+mod static_attributes;
 
 pub(super) struct ForeignModParser<'a> {
     pub errors: &'a mut ParseErrors,
@@ -28,6 +38,18 @@ pub(super) struct ForeignModParser<'a> {
     pub type_declarations: &'a mut TypeDeclarations,
     pub functions: &'a mut Vec<ParsedExternFn>,
     pub unresolved_types: &'a mut Vec<Type>,
+    /// Types that use `#[swift_bridge(ignore)]`. We don't generate any code for them, but we
+    /// keep track of them so that we can point out when a bridged item uses one.
+    pub ignored_types: &'a mut Vec<Ident>,
+    pub statics: &'a mut Vec<ParsedExternStatic>,
+    /// The module's `#[swift_bridge(swift_access = "...")]`, used for types and functions that
+    /// don't set their own access level.
+    pub default_swift_access: SwiftAccessLevel,
+    /// The module's `#[swift_bridge(swift_name_prefix = "...")]`, used for the Swift names of
+    /// types and free functions that don't set their own `swift_name`.
+    pub swift_name_prefix: Option<&'a LitStr>,
+    /// The module's `#[swift_bridge(panic = "...")]`.
+    pub panic_policy: Option<PanicPolicy>,
 }
 
 impl<'a> ForeignModParser<'a> {
@@ -58,7 +80,15 @@ impl<'a> ForeignModParser<'a> {
             }
         });
 
+        if host_lang.is_rust() {
+            let channel_fns = channel_receiver_fns(&foreign_mod.items)?;
+            let lock_fns = lock_fns(&foreign_mod.items)?;
+            foreign_mod.items.extend(channel_fns);
+            foreign_mod.items.extend(lock_fns);
+        }
+
         let mut local_type_declarations = HashMap::new();
+        let mut local_ignored_types = vec![];
         for foreign_mod_item in foreign_mod.items {
             match foreign_mod_item {
                 ForeignItem::Type(foreign_ty) => {
@@ -77,12 +107,134 @@ impl<'a> ForeignModParser<'a> {
                         }
                     }
 
-                    let foreign_type = OpaqueForeignTypeDeclaration {
+                    let attributes = OpaqueTypeAllAttributes::from_attributes(&foreign_ty.attrs)?;
+                    if attributes.ignore {
+                        self.ignored_types.push(foreign_ty.ident.clone());
+                        local_ignored_types.push(foreign_ty.ident.clone());
+                        continue;
+                    }
+
+                    if attributes.protocol && host_lang.is_rust() {
+                        self.errors.push(ParseError::ProtocolInExternRust {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.observable_object
+                        && (host_lang.is_swift() || attributes.copy.is_some())
+                    {
+                        self.errors.push(ParseError::InvalidObservableObjectType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.sendable && host_lang.is_swift() {
+                        self.errors.push(ParseError::InvalidSendableType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.actor && host_lang.is_rust() {
+                        self.errors.push(ParseError::InvalidActorType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.objc && (host_lang.is_swift() || attributes.copy.is_some()) {
+                        self.errors.push(ParseError::InvalidObjcType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.identifiable.is_some()
+                        && (host_lang.is_swift() || attributes.copy.is_some())
+                    {
+                        self.errors.push(ParseError::InvalidIdentifiableType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.codable && (host_lang.is_swift() || attributes.copy.is_some()) {
+                        self.errors.push(ParseError::InvalidCodableType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.publisher.is_some()
+                        && (host_lang.is_swift() || attributes.copy.is_some())
+                    {
+                        self.errors.push(ParseError::InvalidPublisherType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.async_stream.is_some()
+                        && (host_lang.is_swift() || attributes.copy.is_some())
+                    {
+                        self.errors.push(ParseError::InvalidAsyncStreamType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    if attributes.lock.is_some()
+                        && (host_lang.is_swift() || attributes.copy.is_some())
+                    {
+                        self.errors.push(ParseError::InvalidLockType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+
+                    let mut foreign_type = OpaqueForeignTypeDeclaration {
                         ty: foreign_ty.ident.clone(),
                         host_lang,
-                        attributes: OpaqueTypeAllAttributes::from_attributes(&foreign_ty.attrs)?,
+                        attributes,
                         generics: OpaqueRustTypeGenerics::new(),
+                        symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
                     };
+                    foreign_type
+                        .attributes
+                        .swift_bridge
+                        .swift_access
+                        .get_or_insert(self.default_swift_access);
+
+                    // The generated `AsyncStream` holds on to the type in a Swift closure, and a
+                    // `swift_bridge::async_stream::AsyncStream` is `Send + Sync`.
+                    if foreign_type.attributes.async_stream.is_some() && host_lang.is_rust() {
+                        foreign_type.attributes.swift_bridge.sendable = true;
+                    }
+
+                    // Locks exist to be shared between threads, and a `swift_bridge::sync` lock
+                    // is `Send + Sync` when the value that it guards is `Send`.
+                    if foreign_type.attributes.lock.is_some() && host_lang.is_rust() {
+                        foreign_type.attributes.swift_bridge.sendable = true;
+                    }
+
+                    if foreign_type.attributes.open
+                        && (host_lang.is_swift()
+                            || foreign_type.attributes.copy.is_some()
+                            || foreign_type.swift_access() != SwiftAccessLevel::Public)
+                    {
+                        self.errors.push(ParseError::InvalidOpenType {
+                            ty: foreign_type.ty.clone(),
+                        });
+                    }
+
+                    // Generic types' Swift names are derived from their Rust names, so we leave
+                    // them alone.
+                    if let Some(prefix) = self.swift_name_prefix {
+                        if host_lang.is_rust() && !foreign_type.attributes.declare_generic {
+                            foreign_type
+                                .attributes
+                                .swift_bridge
+                                .swift_name
+                                .get_or_insert_with(|| {
+                                    prefixed_swift_name(prefix, &foreign_type.ty)
+                                });
+                        }
+                    }
+                    if self.is_duplicate_type(&ty_name, &foreign_type.ty) {
+                        continue;
+                    }
                     self.type_declarations.insert(
                         ty_name.clone(),
                         TypeDeclaration::Opaque(foreign_type.clone()),
@@ -91,13 +243,67 @@ impl<'a> ForeignModParser<'a> {
                 }
                 ForeignItem::Fn(func) => {
                     let mut attributes = FunctionAttributes::default();
+                    let mut doc_comment: Option<String> = None;
+                    let mut deprecated = None;
+                    let mut cfg_attrs = vec![];
 
                     for attr in func.attrs.iter() {
-                        attributes = attr.parse_args()?;
+                        if attr.path.is_ident("doc") {
+                            push_doc_comment_line(&mut doc_comment, attr)?;
+                        } else if attr.path.is_ident("cfg") {
+                            cfg_attrs.push(syn::parse2(attr.tokens.clone())?);
+                        } else if attr.path.is_ident("deprecated") {
+                            deprecated = Some(parse_deprecated_attribute(attr)?);
+                        } else {
+                            attributes = attr.parse_args()?;
+                        }
+                    }
+
+                    if attributes.ignore {
+                        continue;
+                    }
+
+                    if let Some(ignored) = self.self_or_associated_ignored_type(
+                        &func,
+                        &attributes,
+                        &local_type_declarations,
+                        &local_ignored_types,
+                    ) {
+                        self.errors.push(ParseError::IgnoredTypeUsed {
+                            ignored,
+                            usage: func.sig.to_token_stream(),
+                        });
+                        continue;
                     }
 
                     for arg in func.sig.inputs.iter() {
                         if let FnArg::Typed(pat_ty) = arg {
+                            match pat_ty.pat.deref() {
+                                Pat::Ident(_) => {}
+                                // `&self: SomeType`
+                                Pat::Reference(pat_ref)
+                                    if matches!(
+                                        pat_ref.pat.deref(),
+                                        Pat::Ident(pat_ident) if pat_ident.ident == "self"
+                                    ) =>
+                                {
+                                    self.errors.push(ParseError::RefSelfPattern {
+                                        arg: pat_ty.clone(),
+                                    });
+                                }
+                                pat => {
+                                    self.errors.push(ParseError::UnsupportedArgPattern {
+                                        pat: pat.clone(),
+                                    });
+                                }
+                            }
+
+                            // The type of `self` gets looked up when we find the function's
+                            // associated type.
+                            if pat_type_pat_is_self(pat_ty) {
+                                continue;
+                            }
+
                             let ty = &pat_ty.ty;
                             if BridgedType::new_with_type(&ty, &self.type_declarations).is_none() {
                                 self.unresolved_types.push(ty.deref().clone());
@@ -161,6 +367,72 @@ impl<'a> ForeignModParser<'a> {
                         }
                     }
 
+                    if let Some(argument_labels) = attributes.argument_labels.as_ref() {
+                        for label in argument_labels {
+                            if !fn_has_arg(&func, &label.arg) {
+                                self.errors.push(ParseError::FunctionAttribute(
+                                    FunctionAttributeParseError::ArgumentLabelUnknownArg {
+                                        fn_ident: func.sig.ident.clone(),
+                                        arg: label.arg.clone(),
+                                    },
+                                ));
+                            }
+                        }
+                    }
+
+                    self.push_attribute_combination_errors(&func, &attributes, host_lang);
+
+                    if attributes.is_main_actor && host_lang.is_rust() {
+                        self.errors.push(ParseError::FunctionAttribute(
+                            FunctionAttributeParseError::MainActorInExternRust {
+                                fn_ident: func.sig.ident.clone(),
+                            },
+                        ));
+                    }
+
+                    if attributes.is_blocking {
+                        if func.sig.asyncness.is_some() {
+                            self.errors.push(ParseError::FunctionAttribute(
+                                FunctionAttributeParseError::BlockingAsync {
+                                    fn_ident: func.sig.ident.clone(),
+                                },
+                            ));
+                        } else if host_lang.is_swift()
+                            || attributes.is_swift_initializer
+                            || attributes.is_swift_property
+                            || attributes.is_swift_identifiable
+                        {
+                            self.errors.push(ParseError::FunctionAttribute(
+                                FunctionAttributeParseError::InvalidBlocking {
+                                    fn_ident: func.sig.ident.clone(),
+                                },
+                            ));
+                        }
+                    }
+
+                    if attributes.is_cancellable
+                        && (host_lang.is_swift() || func.sig.asyncness.is_none())
+                    {
+                        self.errors.push(ParseError::FunctionAttribute(
+                            FunctionAttributeParseError::CancellableNotAsyncRust {
+                                fn_ident: func.sig.ident.clone(),
+                            },
+                        ));
+                    }
+
+                    if let Some(default_arg_values) = attributes.default_arg_values.as_ref() {
+                        for default in default_arg_values {
+                            if !fn_has_arg(&func, &default.arg) {
+                                self.errors.push(ParseError::FunctionAttribute(
+                                    FunctionAttributeParseError::DefaultArgValueUnknownArg {
+                                        fn_ident: func.sig.ident.clone(),
+                                        arg: default.arg.clone(),
+                                    },
+                                ));
+                            }
+                        }
+                    }
+
                     for arg in func.sig.inputs.iter() {
                         let is_mutable_ref = fn_arg_is_mutable_reference(arg);
 
@@ -181,19 +453,75 @@ impl<'a> ForeignModParser<'a> {
                         }
                     }
 
+                    let swift_access = attributes
+                        .swift_access
+                        .or_else(|| {
+                            associated_type
+                                .as_ref()
+                                .and_then(|ty| ty.as_opaque())
+                                .and_then(|ty| ty.attributes.swift_access)
+                        })
+                        .unwrap_or(self.default_swift_access);
+
+                    let swift_name_override = match (self.swift_name_prefix, &associated_type) {
+                        (Some(prefix), None) if host_lang.is_rust() => Some(
+                            attributes
+                                .swift_name
+                                .unwrap_or_else(|| prefixed_swift_name(prefix, &func.sig.ident)),
+                        ),
+                        _ => attributes.swift_name,
+                    };
+
                     let func = ParsedExternFn {
                         func,
                         associated_type,
                         is_swift_initializer: attributes.is_swift_initializer,
                         is_swift_identifiable: attributes.is_swift_identifiable,
+                        is_swift_property: attributes.is_swift_property,
+                        is_main_actor: attributes.is_main_actor,
+                        is_cancellable: attributes.is_cancellable,
+                        is_completion_handler: attributes.is_completion_handler,
+                        is_blocking: attributes.is_blocking,
+                        queue: attributes.queue,
                         host_lang,
                         rust_name_override: attributes.rust_name,
-                        swift_name_override: attributes.swift_name,
+                        swift_name_override,
                         return_into: attributes.return_into,
                         return_with: attributes.return_with,
                         args_into: attributes.args_into,
                         get_field: attributes.get_field,
+                        set_field: attributes.set_field,
+                        argument_labels: attributes.argument_labels,
+                        default_arg_values: attributes.default_arg_values,
+                        doc_comment,
+                        deprecated,
+                        available: attributes.available,
+                        swift_access,
+                        cfg_attrs,
+                        panic_policy: self.panic_policy.filter(|_| host_lang.is_rust()),
+                        call_tracing: false,
+                        symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
                     };
+
+                    if func.is_swift_property
+                        && !func.is_swift_property_getter()
+                        && func.swift_property_setter_name().is_none()
+                    {
+                        self.errors.push(ParseError::FunctionAttribute(
+                            FunctionAttributeParseError::SwiftPropertyInvalidSignature {
+                                fn_ident: func.func.sig.ident.clone(),
+                            },
+                        ));
+                    }
+
+                    if let Some(previous) = self.previously_declared_function(&func) {
+                        self.errors.push(ParseError::DuplicateDeclaration {
+                            duplicate: func.func.sig.ident.clone(),
+                            previous: previous.clone(),
+                        });
+                        continue;
+                    }
+
                     self.functions.push(func);
                 }
                 ForeignItem::Verbatim(foreign_item_verbatim) => {
@@ -202,27 +530,62 @@ impl<'a> ForeignModParser<'a> {
                     {
                         let ty_name = generic_foreign_type.ident.to_string();
 
-                        let foreign_ty = OpaqueForeignTypeDeclaration {
+                        let attributes = OpaqueTypeAllAttributes::from_attributes(
+                            &generic_foreign_type.attributes,
+                        )?;
+                        if attributes.ignore {
+                            self.ignored_types.push(generic_foreign_type.ident.clone());
+                            local_ignored_types.push(generic_foreign_type.ident);
+                            continue;
+                        }
+
+                        if attributes.objc {
+                            self.errors.push(ParseError::InvalidObjcType {
+                                ty: generic_foreign_type.ident.clone(),
+                            });
+                        }
+
+                        if attributes.open {
+                            self.errors.push(ParseError::InvalidOpenType {
+                                ty: generic_foreign_type.ident.clone(),
+                            });
+                        }
+
+                        if attributes.lock.is_some() {
+                            self.errors.push(ParseError::InvalidLockType {
+                                ty: generic_foreign_type.ident.clone(),
+                            });
+                        }
+
+                        let mut foreign_ty = OpaqueForeignTypeDeclaration {
                             ty: generic_foreign_type.ident,
                             host_lang,
-                            attributes: OpaqueTypeAllAttributes::from_attributes(
-                                &generic_foreign_type.attributes,
-                            )?,
+                            attributes,
                             generics: OpaqueRustTypeGenerics {
                                 generics: generic_foreign_type
                                     .generics
                                     .params
                                     .clone()
                                     .into_iter()
-                                    .map(|p| match p {
-                                        GenericParam::Type(generic_ty) => generic_ty,
-                                        _ => todo!(
-                                            "Push a ParseError for non-concrete generic types"
-                                        ),
+                                    .filter_map(|p| match p {
+                                        GenericParam::Type(generic_ty) => Some(generic_ty),
+                                        param => {
+                                            self.errors.push(ParseError::UnsupportedGenericParam {
+                                                param: Box::new(param),
+                                            });
+                                            None
+                                        }
                                     })
                                     .collect(),
                             },
+                            symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
                         };
+                        foreign_ty
+                            .attributes
+                            .swift_bridge
+                            .swift_access
+                            .get_or_insert(self.default_swift_access);
+
                         let generics: Vec<String> = foreign_ty
                             .generics
                             .generics
@@ -231,11 +594,17 @@ impl<'a> ForeignModParser<'a> {
                             .collect();
                         let generics: String = generics.join(",");
                         let ty_name = format!("{}<{}>", ty_name, generics);
+                        if self.is_duplicate_type(&ty_name, &foreign_ty.ty) {
+                            continue;
+                        }
                         self.type_declarations
                             .insert(ty_name.clone(), TypeDeclaration::Opaque(foreign_ty.clone()));
                         local_type_declarations.insert(ty_name, foreign_ty);
                     }
                 }
+                ForeignItem::Static(foreign_static) => {
+                    self.parse_extern_static(foreign_static, host_lang)?;
+                }
                 _ => {}
             }
         }
@@ -243,6 +612,284 @@ impl<'a> ForeignModParser<'a> {
         Ok(())
     }
 
+    /// Async initializers must return the type that they initialize, since they resume the Swift
+    /// continuation with the pointer to the new value. `Copy` types are initialized from their
+    /// bytes instead, so they can't be initialized asynchronously.
+    fn can_be_async_initializer(&self, func: &ForeignItemFn) -> bool {
+        let ty = match &func.sig.output {
+            ReturnType::Default => return true,
+            ReturnType::Type(_, ty) => ty,
+        };
+        if !std::ptr::eq(initializer_type(ty), ty.as_ref()) {
+            return false;
+        }
+
+        match self
+            .type_declarations
+            .get(&ty.to_token_stream().to_string())
+        {
+            Some(TypeDeclaration::Opaque(opaque)) => opaque.attributes.copy.is_none(),
+            _ => true,
+        }
+    }
+
+    /// Whether or not an initializer returns an `Option` or a `Result` of a `Copy` opaque type,
+    /// which we can't generate a failable or throwing Swift `init` for yet.
+    fn is_failable_copy_initializer(&self, func: &ForeignItemFn) -> bool {
+        let ty = match &func.sig.output {
+            ReturnType::Default => return false,
+            ReturnType::Type(_, ty) => ty,
+        };
+        let initialized_ty = initializer_type(ty);
+        if std::ptr::eq(initialized_ty, ty.as_ref()) {
+            return false;
+        }
+
+        matches!(
+            self.type_declarations
+                .get(&initialized_ty.to_token_stream().to_string()),
+            Some(TypeDeclaration::Opaque(opaque)) if opaque.attributes.copy.is_some()
+        )
+    }
+
+    /// Whether or not a function has the `fn set_field(&mut self, value: T)` signature that the
+    /// generated `set` and `set_with` body assigns to.
+    fn is_valid_setter(func: &ForeignItemFn) -> bool {
+        if func.sig.inputs.len() != 2 || !matches!(func.sig.output, ReturnType::Default) {
+            return false;
+        }
+
+        let takes_self = match &func.sig.inputs[0] {
+            FnArg::Receiver(_) => true,
+            FnArg::Typed(pat_ty) => pat_type_pat_is_self(pat_ty),
+        };
+        takes_self && fn_arg_is_mutable_reference(&func.sig.inputs[0])
+    }
+
+    /// Push errors for attributes that can't be used together, or that can't be used with the
+    /// function's signature.
+    fn push_attribute_combination_errors(
+        &mut self,
+        func: &ForeignItemFn,
+        attributes: &FunctionAttributes,
+        host_lang: HostLang,
+    ) {
+        let takes_self = match func.sig.inputs.first() {
+            Some(FnArg::Receiver(_)) => true,
+            Some(FnArg::Typed(pat_ty)) => pat_type_pat_is_self(pat_ty),
+            None => false,
+        };
+
+        if attributes.is_swift_initializer {
+            if let Some(associated_to) = attributes.associated_to.as_ref() {
+                self.errors.push(ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::InitializerWithAssociatedTo {
+                        associated_to: associated_to.clone(),
+                    },
+                ));
+            }
+            if takes_self {
+                self.errors.push(ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::InitializerWithSelf {
+                        fn_ident: func.sig.ident.clone(),
+                    },
+                ));
+            }
+            if func.sig.asyncness.is_some() && !self.can_be_async_initializer(func) {
+                self.errors.push(ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::UnsupportedAsyncInitializer {
+                        fn_ident: func.sig.ident.clone(),
+                    },
+                ));
+            } else if self.is_failable_copy_initializer(func) {
+                self.errors.push(ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::UnsupportedCopyInitializer {
+                        fn_ident: func.sig.ident.clone(),
+                    },
+                ));
+            }
+        } else if takes_self {
+            if let Some(associated_to) = attributes.associated_to.as_ref() {
+                self.errors.push(ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::AssociatedToWithSelf {
+                        associated_to: associated_to.clone(),
+                    },
+                ));
+            }
+        }
+
+        if attributes.set_field.is_some() && !Self::is_valid_setter(func) {
+            self.errors.push(ParseError::FunctionAttribute(
+                FunctionAttributeParseError::InvalidSetter {
+                    fn_ident: func.sig.ident.clone(),
+                },
+            ));
+        }
+
+        if let Some(swift_name) = attributes.swift_name.as_ref() {
+            // `extern "Swift"` functions can call a nested function, such as
+            // `SomeEnum.someFunction`.
+            let name = swift_name.value();
+            let is_valid = match host_lang {
+                HostLang::Rust => is_valid_swift_identifier(&name),
+                HostLang::Swift => name.split('.').all(is_valid_swift_identifier),
+            };
+            if !is_valid {
+                self.errors.push(ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::InvalidSwiftName {
+                        swift_name: swift_name.clone(),
+                    },
+                ));
+            }
+        }
+
+        if let Some(rust_name) = attributes.rust_name.as_ref() {
+            // Raw identifiers such as `r#type` would need `Ident::new_raw`.
+            let is_valid = match syn::parse_str::<Ident>(&rust_name.value()) {
+                Ok(ident) => !ident.to_string().starts_with("r#"),
+                Err(_) => false,
+            };
+            if !is_valid {
+                self.errors.push(ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::InvalidRustName {
+                        rust_name: rust_name.clone(),
+                    },
+                ));
+            }
+        }
+    }
+
+    /// Push a `DuplicateDeclaration` error if a type with the same name was already declared in
+    /// the module.
+    fn is_duplicate_type(&mut self, ty_name: &str, ident: &Ident) -> bool {
+        match self.type_declarations.get(ty_name) {
+            Some(previous) => {
+                self.errors.push(ParseError::DuplicateDeclaration {
+                    duplicate: ident.clone(),
+                    previous: previous.ident().clone(),
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// A function with the same name that was already declared for the same type, under the
+    /// same `#[cfg(...)]` attributes.
+    ///
+    /// Functions with different `#[cfg(...)]` attributes can share a name, since only one of
+    /// them gets compiled.
+    fn previously_declared_function(&self, func: &ParsedExternFn) -> Option<&Ident> {
+        // `SomeType<u32>` and `SomeType<u64>` can both have a `fn len`.
+        let key = |func: &ParsedExternFn| {
+            let associated_type = func
+                .associated_type
+                .as_ref()
+                .map(|ty| match ty.as_opaque() {
+                    Some(opaque) => opaque.ffi_repr_name_string(),
+                    None => ty.ident().to_string(),
+                });
+            let cfg_attrs: Vec<String> = func
+                .all_cfg_attrs()
+                .iter()
+                .map(|attr| attr.to_token_stream().to_string())
+                .collect();
+
+            (associated_type, func.func.sig.ident.to_string(), cfg_attrs)
+        };
+
+        let func_key = key(func);
+        self.functions
+            .iter()
+            .find(|previous| key(previous) == func_key)
+            .map(|previous| &previous.func.sig.ident)
+    }
+
+    /// `static SHARED: Registry;`
+    ///
+    /// The static's type gets validated after all of the module's types have been declared.
+    fn parse_extern_static(
+        &mut self,
+        foreign_static: ForeignItemStatic,
+        host_lang: HostLang,
+    ) -> syn::Result<()> {
+        if host_lang.is_swift() {
+            self.errors.push(ParseError::StaticInExternSwift {
+                ident: foreign_static.ident,
+            });
+            return Ok(());
+        }
+        if let Some(mutability) = foreign_static.mutability {
+            self.errors.push(ParseError::MutableStatic { mutability });
+            return Ok(());
+        }
+
+        let attributes = StaticAllAttributes::from_attributes(&foreign_static.attrs)?;
+
+        if let Some(associated_to) = attributes.swift_bridge.associated_to.as_ref() {
+            self.unresolved_types
+                .push(syn::parse_quote! { #associated_to });
+        }
+
+        self.statics.push(ParsedExternStatic {
+            ident: foreign_static.ident,
+            ty: *foreign_static.ty,
+            doc_comment: attributes.doc_comment,
+            swift_access: attributes
+                .swift_bridge
+                .swift_access
+                .unwrap_or(self.default_swift_access),
+            cfg_attrs: attributes.cfg_attrs,
+            associated_to: attributes.swift_bridge.associated_to,
+            symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
+        });
+
+        Ok(())
+    }
+
+    /// If the function is a method or associated function of a type that uses
+    /// `#[swift_bridge(ignore)]`, return that type.
+    fn self_or_associated_ignored_type(
+        &self,
+        func: &ForeignItemFn,
+        attributes: &FunctionAttributes,
+        local_type_declarations: &HashMap<String, OpaqueForeignTypeDeclaration>,
+        local_ignored_types: &[Ident],
+    ) -> Option<Ident> {
+        let is_ignored = |name: &str| self.ignored_types.iter().find(|ty| *ty == name).cloned();
+
+        if let Some(associated_to) = &attributes.associated_to {
+            return is_ignored(&associated_to.to_string());
+        }
+
+        match func.sig.inputs.iter().next() {
+            // `&self` refers to the only type in the extern block, so it can only refer to an
+            // ignored type if that is the only type that was declared.
+            Some(FnArg::Receiver(_)) => {
+                if local_type_declarations.is_empty() && local_ignored_types.len() == 1 {
+                    Some(local_ignored_types[0].clone())
+                } else {
+                    None
+                }
+            }
+            Some(FnArg::Typed(pat_ty)) if pat_type_pat_is_self(pat_ty) => {
+                let self_ty = match pat_ty.ty.deref() {
+                    Type::Reference(type_ref) => type_ref.elem.deref(),
+                    ty => ty,
+                };
+                match self_ty {
+                    Type::Path(ty_path) => ty_path
+                        .path
+                        .segments
+                        .first()
+                        .and_then(|segment| is_ignored(&segment.ident.to_string())),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     fn get_associated_type(
         &mut self,
         first: Option<&FnArg>,
@@ -263,70 +910,85 @@ impl<'a> ForeignModParser<'a> {
                     return Ok(None);
                 }
             }
-            Some(FnArg::Typed(arg)) => match arg.pat.deref() {
-                Pat::Ident(pat_ident) => {
-                    if pat_ident.ident.to_string() == "self" {
-                        let self_ty = match arg.ty.deref() {
-                            Type::Path(ty_path) => ty_path.path.segments.to_token_stream(),
-                            Type::Reference(type_ref) => type_ref.elem.deref().to_token_stream(),
-                            _ => {
-                                todo!("Add a test that hits this branch")
-                            }
-                        };
+            Some(FnArg::Typed(arg)) if pat_type_pat_is_self(arg) => {
+                let self_ty = match arg.ty.deref() {
+                    Type::Reference(type_ref) => type_ref.elem.deref(),
+                    ty => ty,
+                };
+                let self_ty_tokens = match self_ty {
+                    Type::Path(ty_path) => ty_path.path.segments.to_token_stream(),
+                    ty => {
+                        self.errors
+                            .push(ParseError::InvalidSelfType { ty: ty.clone() });
+                        return Ok(None);
+                    }
+                };
 
-                        let self_ty_string = self_ty.to_string();
-                        // Handles generics. i.e. "SomeType< u32, u64 >" -> "SomeType<u32,u64>";
-                        let self_ty_string = self_ty_string.replace(" ", "");
-
-                        let ty = self.type_declarations.get(&self_ty_string).unwrap();
-                        let associated_type = Some(ty.clone());
-                        associated_type
-                    } else {
-                        let associated_type = self.get_associated_type(
-                            None,
-                            func.clone(),
-                            attributes,
-                            local_type_declarations,
-                        )?;
-                        associated_type
-                    }
-                }
-                _ => {
-                    todo!(
-                        r#"
-One way to hit this block is with a `fn foo (&self: SomeType)`.
-Note that this is an invalid signature since the `&` should be in front of `SomeType`, not `self`.
-i.e., this would be correct: `fn foo (self: &SomeType)`
-We should add a test case like this that hits this block, and verify that we push a parse error
-indicating that the function signature is invalid.
-For common mistakes such as the `&self: SomeType` example, we can have dedicated errors telling you
-exactly how to fix it.
-Otherwise we use a more general error that says that your argument is invalid.
-"#
-                    )
-                }
-            },
+                let self_ty_string = self_ty_tokens.to_string();
+                // Handles generics. i.e. "SomeType< u32, u64 >" -> "SomeType<u32,u64>";
+                let self_ty_string = self_ty_string.replace(" ", "");
+
+                match self.type_declarations.get(&self_ty_string) {
+                    Some(TypeDeclaration::Shared(_)) => {
+                        self.errors.push(ParseError::SharedTypeAssociatedFunction {
+                            ty: self_ty.clone(),
+                        });
+                        return Ok(None);
+                    }
+                    Some(ty) => Some(ty.clone()),
+                    None => {
+                        self.errors.push(ParseError::UndeclaredType {
+                            ty: self_ty.clone(),
+                        });
+                        return Ok(None);
+                    }
+                }
+            }
+            // Arguments that aren't `self`, including ones with unsupported patterns such as
+            // `&self: SomeType` that we've already pushed an error for.
+            Some(FnArg::Typed(_)) => {
+                self.get_associated_type(None, func.clone(), attributes, local_type_declarations)?
+            }
             None => {
                 let associated_type = if let Some(associated_to) = &attributes.associated_to {
-                    let ty = self
-                        .type_declarations
-                        .get(&associated_to.to_string())
-                        .unwrap();
-                    Some(ty.clone())
+                    match self.type_declarations.get(&associated_to.to_string()) {
+                        Some(TypeDeclaration::Shared(_)) => {
+                            self.errors.push(ParseError::SharedTypeAssociatedFunction {
+                                ty: parse_quote! { #associated_to },
+                            });
+                            return Ok(None);
+                        }
+                        Some(ty) => Some(ty.clone()),
+                        None => {
+                            self.errors.push(ParseError::UndeclaredType {
+                                ty: parse_quote! { #associated_to },
+                            });
+                            return Ok(None);
+                        }
+                    }
                 } else if attributes.is_swift_initializer {
-                    let ty_string = match &func.sig.output {
+                    let initialized_ty = match &func.sig.output {
                         ReturnType::Default => {
-                            todo!("Push error if initializer does not return a type")
-                        }
-                        ReturnType::Type(_, ty) => {
-                            let ty_string = ty.deref().to_token_stream().to_string();
-                            ty_string
+                            self.errors.push(ParseError::FunctionAttribute(
+                                FunctionAttributeParseError::InitializerWithoutReturnType {
+                                    fn_ident: func.sig.ident.clone(),
+                                },
+                            ));
+                            return Ok(None);
                         }
+                        ReturnType::Type(_, ty) => initializer_type(ty),
                     };
+                    let ty_string = initialized_ty.to_token_stream().to_string();
 
-                    let ty = self.type_declarations.get(&ty_string);
-
-                    ty.map(|ty| ty.clone())
+                    match self.type_declarations.get(&ty_string) {
+                        Some(TypeDeclaration::Shared(_)) => {
+                            self.errors.push(ParseError::SharedTypeAssociatedFunction {
+                                ty: initialized_ty.clone(),
+                            });
+                            return Ok(None);
+                        }
+                        ty => ty.cloned(),
+                    }
                 } else {
                     None
                 };
@@ -339,9 +1001,209 @@ Otherwise we use a more general error that says that your argument is invalid.
     }
 }
 
+/// The type that an initializer creates.
+///
+/// Failable initializers return `Option<SomeType>` and throwing initializers return
+/// `Result<SomeType, SomeError>`, so we use the first generic argument.
+fn initializer_type(ty: &Type) -> &Type {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if segment.ident == "Option" || segment.ident == "Result" {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                        return inner;
+                    }
+                }
+            }
+        }
+    }
+
+    ty
+}
+
+/// Declare the methods that the generated Swift code uses to pull values from a Rust channel:
+///
+/// - `fn next_blocking(self: &mut Type) -> Option<SomeType>` for each
+///   `#[swift_bridge(Publisher(SomeType))]` type's Combine publisher.
+/// - `async fn next(self: &Type) -> Option<SomeType>` for each
+///   `#[swift_bridge(AsyncStream(SomeType))]` type's `AsyncStream`. It is `cancellable`, so that
+///   cancelling the Swift task that iterates over the stream stops waiting for the next value.
+fn channel_receiver_fns(items: &[ForeignItem]) -> syn::Result<Vec<ForeignItem>> {
+    let mut fns = vec![];
+
+    for item in items {
+        if let ForeignItem::Type(foreign_ty) = item {
+            let attributes = OpaqueTypeAllAttributes::from_attributes(&foreign_ty.attrs)?;
+            if attributes.ignore || attributes.copy.is_some() {
+                continue;
+            }
+
+            let ty = &foreign_ty.ident;
+            let cfg_attrs: Vec<&Attribute> = foreign_ty
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("cfg"))
+                .collect();
+
+            if let Some(output) = attributes.publisher.as_ref() {
+                fns.push(ForeignItem::Fn(parse_quote! {
+                    #(#cfg_attrs)*
+                    fn next_blocking(self: &mut #ty) -> Option<#output>;
+                }));
+            }
+
+            if let Some(output) = attributes.async_stream.as_ref() {
+                fns.push(ForeignItem::Fn(parse_quote! {
+                    #(#cfg_attrs)*
+                    #[swift_bridge(cancellable)]
+                    async fn next(self: &#ty) -> Option<#output>;
+                }));
+            }
+        }
+    }
+
+    Ok(fns)
+}
+
+/// Declare the methods that a `#[swift_bridge(Mutex(SomeType))]` or
+/// `#[swift_bridge(RwLock(SomeType))]` type's generated Swift `withLock` methods use to lock it,
+/// get the guarded value and unlock it again.
+///
+/// They are `fileprivate`, since a lock that Swift doesn't unlock would never be unlocked.
+fn lock_fns(items: &[ForeignItem]) -> syn::Result<Vec<ForeignItem>> {
+    let mut fns = vec![];
+
+    for item in items {
+        if let ForeignItem::Type(foreign_ty) = item {
+            let attributes = OpaqueTypeAllAttributes::from_attributes(&foreign_ty.attrs)?;
+            if attributes.ignore || attributes.copy.is_some() {
+                continue;
+            }
+            let lock = match attributes.lock.as_ref() {
+                Some(lock) => lock,
+                None => continue,
+            };
+
+            let ty = &foreign_ty.ident;
+            let guarded = &lock.guarded;
+            let cfg_attrs: Vec<&Attribute> = foreign_ty
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("cfg"))
+                .collect();
+
+            let lock_fns: Vec<ForeignItemFn> = match lock.kind {
+                LockKind::Mutex => vec![parse_quote! {
+                    fn swift_lock(self: &#ty);
+                }],
+                LockKind::RwLock => vec![
+                    parse_quote! {
+                        fn swift_read(self: &#ty);
+                    },
+                    parse_quote! {
+                        fn swift_write(self: &#ty);
+                    },
+                    parse_quote! {
+                        fn swift_guarded(self: &#ty) -> &#guarded;
+                    },
+                ],
+            };
+            let guarded_fns: Vec<ForeignItemFn> = vec![
+                parse_quote! {
+                    fn swift_guarded_mut(self: &#ty) -> &mut #guarded;
+                },
+                parse_quote! {
+                    fn swift_unlock(self: &#ty);
+                },
+            ];
+
+            for func in lock_fns.into_iter().chain(guarded_fns) {
+                fns.push(ForeignItem::Fn(parse_quote! {
+                    #(#cfg_attrs)*
+                    #[swift_bridge(swift_access = "fileprivate")]
+                    #func
+                }));
+            }
+        }
+    }
+
+    Ok(fns)
+}
+
+/// Whether or not the function has a (non-self) argument with the given name.
+fn fn_has_arg(func: &ForeignItemFn, arg_name: &Ident) -> bool {
+    func.sig.inputs.iter().any(|arg| match arg {
+        FnArg::Typed(pat_ty) => arg_name == &pat_ty.pat.to_token_stream().to_string(),
+        FnArg::Receiver(_) => false,
+    })
+}
+
+/// Append a `#[doc = "..."]` attribute's line to a doc comment.
+///
+/// Each `/// ...` line becomes its own `doc` attribute, so multi-line doc comments are joined
+/// using newlines.
+pub(super) fn push_doc_comment_line(
+    doc_comment: &mut Option<String>,
+    attr: &Attribute,
+) -> syn::Result<()> {
+    let line = match attr.parse_meta()? {
+        Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(comment),
+            ..
+        }) => comment.value(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                r#"Doc comments must use the `#[doc = "..."]` format."#,
+            ))
+        }
+    };
+
+    match doc_comment {
+        Some(doc_comment) => {
+            doc_comment.push('\n');
+            doc_comment.push_str(&line);
+        }
+        None => *doc_comment = Some(line),
+    };
+
+    Ok(())
+}
+
+/// Parse a `#[deprecated]`, `#[deprecated = "..."]` or `#[deprecated(note = "...")]` attribute.
+fn parse_deprecated_attribute(attr: &Attribute) -> syn::Result<Deprecated> {
+    let note = match attr.parse_meta()? {
+        Meta::Path(_) => None,
+        Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(note),
+            ..
+        }) => Some(note.value()),
+        Meta::List(list) => list.nested.iter().find_map(|nested| match nested {
+            syn::NestedMeta::Meta(Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(note),
+                ..
+            })) if path.is_ident("note") => Some(note.value()),
+            _ => None,
+        }),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                r#"Expected `#[deprecated]`, `#[deprecated = "..."]` or `#[deprecated(note = "...")]`."#,
+            ))
+        }
+    };
+
+    Ok(Deprecated {
+        attribute: attr.clone(),
+        note,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::errors::ParseError;
+    use crate::errors::{FunctionAttributeParseError, ParseError};
+    use crate::parse::{LockKind, SwiftAccessLevel, SwiftBridgeModuleAndErrors};
     use crate::test_utils::{parse_errors, parse_ok};
     use crate::SwiftBridgeModule;
     use quote::{quote, ToTokens};
@@ -575,8 +1437,8 @@ mod tests {
                     type Foo;
 
                     fn a () -> Bar;
-                    fn a () -> &Bar;
-                    fn a () -> &mut Bar;
+                    fn b () -> &Bar;
+                    fn c () -> &mut Bar;
                 }
             }
         };
@@ -597,23 +1459,224 @@ mod tests {
         }
     }
 
-    /// Verify that if an extern Rust block has more than one type, we push errors for any methods
-    /// that have an ambiguous self.
+    /// Verify that we push an error for a `u128` or `i128` inside of an `Option`, `Vec` or `Result`,
+    /// since those aren't supported yet.
     #[test]
-    fn error_if_method_has_ambiguous_self() {
+    fn error_if_128_bit_int_inside_container() {
         let tokens = quote! {
             mod foo {
                 extern "Rust" {
-                    type SomeType;
-                    type AnotherType;
-
-                    fn a (self);
-                    fn b (&self);
-                    fn c (&mut self);
+                    fn a () -> Option<u128>;
+                    fn b (arg: Option<i128>);
+                    fn c () -> Vec<u128>;
+                    fn d () -> Result<u128, String>;
+                    fn e (arg: Result<String, i128>);
                 }
             }
         };
-
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 5);
+
+        let expected = [
+            "Option < u128 >",
+            "Option < i128 >",
+            "Vec < u128 >",
+            "Result < u128 , String >",
+            "Result < String , i128 >",
+        ];
+        for (error, expected) in errors.iter().zip(expected) {
+            match error {
+                ParseError::UnsupportedInt128 { ty } => {
+                    assert_eq!(ty.to_token_stream().to_string(), expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error that points at tuples, arrays and other types that can't be
+    /// bridged, instead of telling the user to declare them.
+    #[test]
+    fn error_if_unsupported_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    fn a (arg: (u8, u8));
+                    fn b () -> Vec<(u8, u16)>;
+                    fn c (arg: [u8; 2]);
+                    fn d (arg: Option<Vec<u8>>);
+                    fn e () -> Vec<Option<u8>>;
+                }
+            }
+        };
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 5);
+
+        let expected = [
+            "(u8 , u8)",
+            "(u8 , u16)",
+            "[u8 ; 2]",
+            "Option < Vec < u8 > >",
+            "Vec < Option < u8 > >",
+        ];
+        for (error, expected) in errors.iter().zip(expected) {
+            match error {
+                ParseError::UnsupportedType { ty } => {
+                    assert_eq!(ty.to_token_stream().to_string(), expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that an undeclared type inside of a `Vec`, `Option` or `Result` is reported on its
+    /// own instead of as part of the container type.
+    #[test]
+    fn error_if_undeclared_type_inside_container() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    fn a (arg: Vec<Foo>);
+                    fn b () -> Option<&Bar>;
+                    fn c () -> Result<String, Baz>;
+                }
+            }
+        };
+        let errors = parse_errors(tokens);
+
+        let undeclared: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::UndeclaredType { ty } => ty.to_token_stream().to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(undeclared, vec!["Foo", "Bar", "Baz"]);
+    }
+
+    /// Verify that we push an error for a `Result` that pairs a primitive with a type that does
+    /// not fit in the 64 bits that the primitive is passed in.
+    #[test]
+    fn error_if_result_primitive_paired_with_unsupported_type() {
+        let tokens = quote! {
+            mod foo {
+                enum SomeEnum {
+                    Variant,
+                }
+
+                extern "Rust" {
+                    fn a () -> Result<u32, SomeEnum>;
+                    fn b () -> Result<SomeEnum, bool>;
+                    fn c () -> Result<u32, String>;
+                }
+            }
+        };
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 2);
+
+        let expected = ["Result < u32 , SomeEnum >", "Result < SomeEnum , bool >"];
+        for (error, expected) in errors.iter().zip(expected) {
+            match error {
+                ParseError::UnsupportedType { ty } => {
+                    assert_eq!(ty.to_token_stream().to_string(), expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error for methods, associated functions and initializers of shared
+    /// structs and enums.
+    #[test]
+    fn error_if_function_associated_to_shared_type() {
+        let tokens = quote! {
+            mod foo {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: u8,
+                }
+
+                enum SomeEnum {
+                    Variant,
+                }
+
+                extern "Rust" {
+                    fn a (self: &SomeStruct);
+                    #[swift_bridge(associated_to = SomeEnum)]
+                    fn b () -> u8;
+                    #[swift_bridge(init)]
+                    fn c () -> SomeStruct;
+                }
+
+                extern "Swift" {
+                    fn d (self: SomeEnum);
+                }
+            }
+        };
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 4);
+
+        let expected = ["SomeStruct", "SomeEnum", "SomeStruct", "SomeEnum"];
+        for (error, expected) in errors.iter().zip(expected) {
+            match error {
+                ParseError::SharedTypeAssociatedFunction { ty } => {
+                    assert_eq!(ty.to_token_stream().to_string(), expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error for `extern "Rust"` functions that take or return a boxed
+    /// function, since only `extern "Swift"` functions support them.
+    #[test]
+    fn error_if_extern_rust_fn_uses_boxed_fn() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    fn a (callback: Box<dyn FnOnce(u8, u8) -> u8>);
+                    fn b () -> Box<dyn FnOnce()>;
+                }
+
+                extern "Swift" {
+                    fn c (callback: Box<dyn FnOnce(u8, u8) -> u8>);
+                }
+            }
+        };
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 2);
+
+        let expected = [
+            "Box < dyn FnOnce (u8 , u8) -> u8 >",
+            "Box < dyn FnOnce () >",
+        ];
+        for (error, expected) in errors.iter().zip(expected) {
+            match error {
+                ParseError::ExternRustBoxedFn { ty } => {
+                    assert_eq!(ty.to_token_stream().to_string(), expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that if an extern Rust block has more than one type, we push errors for any methods
+    /// that have an ambiguous self.
+    #[test]
+    fn error_if_method_has_ambiguous_self() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+                    type AnotherType;
+
+                    fn a (self);
+                    fn b (&self);
+                    fn c (&mut self);
+                }
+            }
+        };
+
         let errors = parse_errors(tokens);
 
         assert_eq!(errors.len(), 3);
@@ -739,6 +1802,67 @@ mod tests {
         );
     }
 
+    /// Verify that we can parse the `swift_name` attribute on a type.
+    #[test]
+    fn parse_type_swift_name_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(swift_name = "Renderer")]
+                    type RendererRs;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("RendererRs")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .swift_name
+                .as_ref()
+                .unwrap()
+                .value(),
+            "Renderer"
+        );
+    }
+
+    /// Verify that types and functions inherit the module's Swift access level unless they
+    /// use the `swift_access` attribute, and that methods inherit their type's access level.
+    #[test]
+    fn parse_swift_access_attribute() {
+        let tokens = quote! {
+            #[swift_bridge(swift_access = "internal")]
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(swift_access = "package")]
+                    type SomeType;
+                    type AnotherType;
+
+                    fn some_method(self: &SomeType);
+                    #[swift_bridge(swift_access = "fileprivate")]
+                    fn another_method(self: &AnotherType);
+                    fn some_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let access = |ty: &str| module.types.get(ty).unwrap().unwrap_opaque().swift_access();
+        assert_eq!(access("SomeType"), SwiftAccessLevel::Package);
+        assert_eq!(access("AnotherType"), SwiftAccessLevel::Internal);
+
+        let functions = &module.functions;
+        assert_eq!(functions[0].swift_access, SwiftAccessLevel::Package);
+        assert_eq!(functions[1].swift_access, SwiftAccessLevel::FilePrivate);
+        assert_eq!(functions[2].swift_access, SwiftAccessLevel::Internal);
+    }
+
     //Verify that we can parse the `hashable` attribute.
     #[test]
     fn parse_hashable_attribute() {
@@ -791,13 +1915,13 @@ mod tests {
         );
     }
 
-    /// Verify that we can parse the `copy` attribute.
+    /// Verify that we can parse the `protocol` attribute.
     #[test]
-    fn parse_copy_attribute() {
+    fn parse_protocol_attribute() {
         let tokens = quote! {
             mod foo {
-                extern "Rust" {
-                    #[swift_bridge(Copy(4))]
+                extern "Swift" {
+                    #[swift_bridge(protocol)]
                     type SomeType;
                 }
             }
@@ -812,20 +1936,41 @@ mod tests {
                 .unwrap()
                 .unwrap_opaque()
                 .attributes
-                .copy
-                .unwrap()
-                .size_bytes,
-            4
+                .protocol,
+            true
         );
     }
 
-    /// Verify that we can parse multiple atributes from an opaque type.
+    /// Verify that we push an error if a `protocol` type is declared in an extern "Rust" block.
     #[test]
-    fn parse_multiple_attributes() {
+    fn error_if_protocol_in_extern_rust() {
         let tokens = quote! {
             mod foo {
                 extern "Rust" {
-                    #[swift_bridge(already_declared, Copy(4))]
+                    #[swift_bridge(protocol)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::ProtocolInExternRust { ty } => {
+                assert_eq!(ty, "SomeType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `Codable` attribute.
+    #[test]
+    fn parse_codable_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Codable)]
                     type SomeType;
                 }
             }
@@ -833,65 +1978,1358 @@ mod tests {
 
         let module = parse_ok(tokens);
 
-        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
-        assert!(ty.attributes.copy.is_some());
-        assert!(ty.attributes.already_declared)
+        assert!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .codable
+        );
     }
 
-    /// Verify that we can parse a doc comment from an extern "Rust" opaque type.
+    /// Verify that we push an error if a `Codable` type is an extern "Swift" type or a `Copy`
+    /// type.
     #[test]
-    fn parse_opaque_rust_type_doc_comment() {
+    fn error_if_invalid_codable_type() {
         let tokens = quote! {
             mod foo {
                 extern "Rust" {
-                    /// Some comment
+                    #[swift_bridge(Codable, Copy(4))]
+                    type SomeType;
+                }
+                extern "Swift" {
+                    #[swift_bridge(Codable)]
                     type AnotherType;
                 }
             }
         };
 
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParseError::InvalidCodableType { ty } => {
+                assert_eq!(ty, "SomeType");
+            }
+            _ => panic!(),
+        }
+        match &errors[1] {
+            ParseError::InvalidCodableType { ty } => {
+                assert_eq!(ty, "AnotherType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `Sendable` attribute.
+    #[test]
+    fn parse_sendable_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Sendable)]
+                    type SomeType;
+                }
+            }
+        };
+
         let module = parse_ok(tokens);
 
         assert_eq!(
             module
                 .types
-                .get("AnotherType")
+                .get("SomeType")
                 .unwrap()
                 .unwrap_opaque()
                 .attributes
-                .doc_comment
-                .as_ref()
-                .unwrap(),
-            " Some comment"
+                .sendable,
+            true
         );
     }
 
-    /// Verify that we push errors for function arguments that are both mutable and opaque Copy.
+    /// Verify that we push an error if a `Sendable` type is an extern "Swift" type.
     #[test]
-    fn error_if_mutable_opaque_copy_type() {
+    fn error_if_invalid_sendable_type() {
         let tokens = quote! {
-            #[swift_bridge:bridge]
             mod foo {
-                extern "Rust" {
-                    #[swift_bridge(Copy(2))]
+                extern "Swift" {
+                    #[swift_bridge(Sendable)]
                     type SomeType;
-
-                    fn a(&mut self);
-                    fn b(self: &mut SomeType);
-                    fn c(arg: &mut SomeType);
                 }
             }
         };
 
         let errors = parse_errors(tokens);
 
-        assert_eq!(errors.len(), 3);
-
-        for error in errors.iter() {
-            match error {
-                ParseError::ArgCopyAndRefMut { arg: _ } => {}
-                _ => panic!(),
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::InvalidSendableType { ty } => {
+                assert_eq!(ty, "SomeType");
             }
+            _ => panic!(),
         }
     }
+
+    /// Verify that we can parse the `open` attribute.
+    #[test]
+    fn parse_open_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(open)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .open,
+            true
+        );
+    }
+
+    /// Verify that we push an error if an `open` type is an extern "Swift" type, a Copy type, a
+    /// type that isn't public or a generic type.
+    #[test]
+    fn error_if_invalid_open_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(open)]
+                    type SomeType;
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(open, Copy(4))]
+                    type AnotherType;
+
+                    #[swift_bridge(open, swift_access = "internal")]
+                    type InternalType;
+
+                    #[swift_bridge(open, declare_generic)]
+                    type GenericType<A>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let mut invalid_types: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                ParseError::InvalidOpenType { ty } => ty.to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        invalid_types.sort();
+
+        assert_eq!(
+            invalid_types,
+            vec!["AnotherType", "GenericType", "InternalType", "SomeType"]
+        );
+    }
+
+    /// Verify that we can parse the `actor` attribute.
+    #[test]
+    fn parse_actor_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(actor)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .actor,
+            true
+        );
+    }
+
+    /// Verify that we push an error if an `actor` type is an extern "Rust" type.
+    #[test]
+    fn error_if_invalid_actor_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(actor)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::InvalidActorType { ty } => {
+                assert_eq!(ty, "SomeType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `objc` attribute.
+    #[test]
+    fn parse_objc_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(objc)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .objc,
+            true
+        );
+    }
+
+    /// Verify that we push an error if an `objc` type is an extern "Swift" type, a Copy type or
+    /// a generic type.
+    #[test]
+    fn error_if_invalid_objc_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(objc)]
+                    type SomeType;
+                }
+
+                extern "Rust" {
+                    #[swift_bridge(objc, Copy(4))]
+                    type AnotherType;
+
+                    #[swift_bridge(objc, declare_generic)]
+                    type GenericType<A>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        for (error, expected) in errors
+            .iter()
+            .zip(["SomeType", "AnotherType", "GenericType"])
+        {
+            match error {
+                ParseError::InvalidObjcType { ty } => {
+                    assert_eq!(ty, expected);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we can parse the `Identifiable` attribute.
+    #[test]
+    fn parse_identifiable_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Identifiable = "key")]
+                    type SomeType;
+
+                    fn key(&self) -> u64;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .identifiable
+                .as_ref()
+                .unwrap()
+                .value(),
+            "key"
+        );
+    }
+
+    /// Verify that we push an error if an `Identifiable` type is an extern "Swift" type or a
+    /// `Copy` type.
+    #[test]
+    fn error_if_invalid_identifiable_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Identifiable = "key", Copy(4))]
+                    type SomeType;
+
+                    fn key(self: &SomeType) -> u32;
+                }
+                extern "Swift" {
+                    #[swift_bridge(Identifiable = "key")]
+                    type AnotherType;
+
+                    fn key(self: &AnotherType) -> u32;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParseError::InvalidIdentifiableType { ty } => {
+                assert_eq!(ty, "SomeType");
+            }
+            _ => panic!(),
+        }
+        match &errors[1] {
+            ParseError::InvalidIdentifiableType { ty } => {
+                assert_eq!(ty, "AnotherType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we push an error if an `Identifiable` type does not have a `&self` getter
+    /// with the given name.
+    #[test]
+    fn error_if_identifiable_getter_not_found() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Identifiable = "key")]
+                    type SomeType;
+
+                    fn key(&mut self, value: u64);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::IdentifiableGetterNotFound { ty, getter } => {
+                assert_eq!(ty, "SomeType");
+                assert_eq!(getter.value(), "key");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `Comparable` attribute.
+    #[test]
+    fn parse_comparable_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Comparable)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .comparable
+        );
+    }
+
+    /// Verify that we can parse the `CustomStringConvertible` and `CustomDebugStringConvertible`
+    /// attributes.
+    #[test]
+    fn parse_custom_string_convertible_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(CustomStringConvertible, CustomDebugStringConvertible)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(ty.attributes.custom_string_convertible);
+        assert!(ty.attributes.custom_debug_string_convertible);
+    }
+
+    /// Verify that we can parse the `Error` attribute.
+    #[test]
+    fn parse_error_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Error)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .error
+        );
+    }
+
+    /// Verify that we can parse the `ObservableObject` attribute.
+    #[test]
+    fn parse_observable_object_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(ObservableObject)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .observable_object,
+            true
+        );
+    }
+
+    /// Verify that we push an error if an `ObservableObject` type is an extern "Swift" type or
+    /// a `Copy` type.
+    #[test]
+    fn error_if_invalid_observable_object_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(ObservableObject, Copy(4))]
+                    type SomeType;
+                }
+                extern "Swift" {
+                    #[swift_bridge(ObservableObject)]
+                    type AnotherType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParseError::InvalidObservableObjectType { ty } => {
+                assert_eq!(ty, "SomeType");
+            }
+            _ => panic!(),
+        }
+        match &errors[1] {
+            ParseError::InvalidObservableObjectType { ty } => {
+                assert_eq!(ty, "AnotherType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we push an error if an `ObservableObject` type has a property that isn't a
+    /// primitive or a `String`.
+    #[test]
+    fn error_if_unsupported_observable_property_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(ObservableObject)]
+                    type SomeType;
+
+                    #[swift_bridge(swift_property)]
+                    fn count(&self) -> u32;
+                    #[swift_bridge(swift_property)]
+                    fn tags(&self) -> Vec<u32>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::UnsupportedObservablePropertyType { ty } => {
+                assert_eq!(ty.to_token_stream().to_string(), "Vec < u32 >");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `Publisher` attribute, and that we declare the
+    /// `next_blocking` method that the generated Combine publisher calls.
+    #[test]
+    fn parse_publisher_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Publisher(u32))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let publisher = module
+            .types
+            .get("SomeType")
+            .unwrap()
+            .unwrap_opaque()
+            .attributes
+            .publisher
+            .clone()
+            .unwrap();
+        assert_eq!(publisher.to_token_stream().to_string(), "u32");
+
+        assert_eq!(module.functions.len(), 1);
+        let next = &module.functions[0];
+        assert_eq!(next.func.sig.ident.to_string(), "next_blocking");
+        assert_eq!(
+            next.func.sig.output.to_token_stream().to_string(),
+            "-> Option < u32 >"
+        );
+    }
+
+    /// Verify that we push an error if a `Publisher` type is an extern "Swift" type or a `Copy`
+    /// type.
+    #[test]
+    fn error_if_invalid_publisher_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Publisher(u32), Copy(4))]
+                    type SomeType;
+                }
+                extern "Swift" {
+                    #[swift_bridge(Publisher(u32))]
+                    type AnotherType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParseError::InvalidPublisherType { ty } => {
+                assert_eq!(ty, "SomeType");
+            }
+            _ => panic!(),
+        }
+        match &errors[1] {
+            ParseError::InvalidPublisherType { ty } => {
+                assert_eq!(ty, "AnotherType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `AsyncStream` attribute, and that it declares a cancellable
+    /// `next` method and makes the type `Sendable`.
+    #[test]
+    fn parse_async_stream_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(AsyncStream(u32))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let async_stream = module
+            .types
+            .get("SomeType")
+            .unwrap()
+            .unwrap_opaque()
+            .attributes
+            .async_stream
+            .clone()
+            .unwrap();
+        assert_eq!(async_stream.to_token_stream().to_string(), "u32");
+        assert!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .sendable
+        );
+
+        assert_eq!(module.functions.len(), 1);
+        let next = &module.functions[0];
+        assert_eq!(next.func.sig.ident.to_string(), "next");
+        assert!(next.func.sig.asyncness.is_some());
+        assert!(next.is_cancellable);
+        assert_eq!(
+            next.func.sig.output.to_token_stream().to_string(),
+            "-> Option < u32 >"
+        );
+    }
+
+    /// Verify that we push an error if an `AsyncStream` type is an extern "Swift" type or a
+    /// `Copy` type.
+    #[test]
+    fn error_if_invalid_async_stream_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(AsyncStream(u32), Copy(4))]
+                    type SomeType;
+                }
+                extern "Swift" {
+                    #[swift_bridge(AsyncStream(u32))]
+                    type AnotherType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParseError::InvalidAsyncStreamType { ty } => {
+                assert_eq!(ty, "SomeType");
+            }
+            _ => panic!(),
+        }
+        match &errors[1] {
+            ParseError::InvalidAsyncStreamType { ty } => {
+                assert_eq!(ty, "AnotherType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `Mutex` and `RwLock` attributes, and that they declare the
+    /// methods that the generated Swift code uses to lock the type.
+    #[test]
+    fn parse_lock_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type AppState;
+
+                    #[swift_bridge(Mutex(AppState))]
+                    type SharedState;
+
+                    #[swift_bridge(RwLock(AppState))]
+                    type ReadMostlyState;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let lock = module
+            .types
+            .get("SharedState")
+            .unwrap()
+            .unwrap_opaque()
+            .attributes
+            .lock
+            .clone()
+            .unwrap();
+        assert_eq!(lock.kind, LockKind::Mutex);
+        assert_eq!(lock.guarded.to_token_stream().to_string(), "AppState");
+
+        let rw_lock = module.types.get("ReadMostlyState").unwrap().unwrap_opaque();
+        assert_eq!(
+            rw_lock.attributes.lock.as_ref().unwrap().kind,
+            LockKind::RwLock
+        );
+        assert!(rw_lock.attributes.sendable);
+
+        let fns: Vec<String> = module
+            .functions
+            .iter()
+            .map(|func| {
+                assert_eq!(func.swift_access, SwiftAccessLevel::FilePrivate);
+                format!(
+                    "{} {}",
+                    func.associated_type.as_ref().unwrap().unwrap_opaque().ty,
+                    func.func.sig.ident
+                )
+            })
+            .collect();
+        assert_eq!(
+            fns,
+            vec![
+                "ReadMostlyState swift_read",
+                "ReadMostlyState swift_write",
+                "ReadMostlyState swift_guarded",
+                "ReadMostlyState swift_guarded_mut",
+                "ReadMostlyState swift_unlock",
+                "SharedState swift_lock",
+                "SharedState swift_guarded_mut",
+                "SharedState swift_unlock",
+            ]
+        );
+    }
+
+    /// Verify that we push an error if a `Mutex` or `RwLock` type is an extern "Swift" type, a
+    /// `Copy` type or a generic type.
+    #[test]
+    fn error_if_invalid_lock_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Mutex(u32), Copy(4))]
+                    type SomeType;
+
+                    #[swift_bridge(RwLock(u32))]
+                    type GenericType<A>;
+                }
+                extern "Swift" {
+                    #[swift_bridge(Mutex(u32))]
+                    type AnotherType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        for (error, expected_ty) in errors
+            .iter()
+            .zip(["SomeType", "GenericType", "AnotherType"])
+        {
+            match error {
+                ParseError::InvalidLockType { ty } => {
+                    assert_eq!(ty, expected_ty);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we can parse the `copy` attribute.
+    #[test]
+    fn parse_copy_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .copy
+                .unwrap()
+                .size_bytes,
+            4
+        );
+    }
+
+    /// Verify that we can parse multiple atributes from an opaque type.
+    #[test]
+    fn parse_multiple_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(already_declared, Copy(4))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(ty.attributes.copy.is_some());
+        assert!(ty.attributes.already_declared)
+    }
+
+    /// Verify that we can parse a doc comment from an extern "Rust" opaque type.
+    #[test]
+    fn parse_opaque_rust_type_doc_comment() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    /// Some comment
+                    type AnotherType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("AnotherType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .doc_comment
+                .as_ref()
+                .unwrap(),
+            " Some comment"
+        );
+    }
+
+    /// Verify that we join the lines of a multi-line doc comment on an opaque type.
+    #[test]
+    fn parse_opaque_rust_type_multi_line_doc_comment() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    /// First line
+                    /// Second line
+                    type AnotherType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(
+            module
+                .types
+                .get("AnotherType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .doc_comment
+                .as_ref()
+                .unwrap(),
+            " First line\n Second line"
+        );
+    }
+
+    /// Verify that we can parse a doc comment from a function.
+    #[test]
+    fn parse_function_doc_comment() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    /// Some comment
+                    #[swift_bridge(swift_name = "someFunction")]
+                    fn some_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert_eq!(func.doc_comment.as_ref().unwrap(), " Some comment");
+        assert_eq!(
+            func.swift_name_override.as_ref().unwrap().value(),
+            "someFunction"
+        );
+    }
+
+    /// Verify that we can parse the different forms of the `deprecated` attribute on a function.
+    #[test]
+    fn parse_function_deprecated_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[deprecated]
+                    fn a();
+
+                    #[deprecated = "use a2"]
+                    fn b();
+
+                    #[deprecated(since = "0.2.0", note = "use c2")]
+                    fn c();
+
+                    fn d();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let notes: Vec<Option<Option<&str>>> = module
+            .functions
+            .iter()
+            .map(|func| {
+                func.deprecated
+                    .as_ref()
+                    .map(|deprecated| deprecated.note.as_deref())
+            })
+            .collect();
+        assert_eq!(
+            notes,
+            vec![Some(None), Some(Some("use a2")), Some(Some("use c2")), None]
+        );
+    }
+
+    /// Verify that we push errors for function arguments that are both mutable and opaque Copy.
+    #[test]
+    fn error_if_mutable_opaque_copy_type() {
+        let tokens = quote! {
+            #[swift_bridge:bridge]
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Copy(2))]
+                    type SomeType;
+
+                    fn a(&mut self);
+                    fn b(self: &mut SomeType);
+                    fn c(arg: &mut SomeType);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+
+        for error in errors.iter() {
+            match error {
+                ParseError::ArgCopyAndRefMut { arg: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we don't parse functions or types that use the `ignore` attribute.
+    #[test]
+    fn parse_ignore_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(ignore)]
+                    type SomeType;
+                    type AnotherType;
+
+                    #[swift_bridge(ignore)]
+                    fn some_function(arg: HashMap<String, SomeType>);
+                    #[swift_bridge(ignore)]
+                    fn some_method(self: &SomeType);
+                    fn another_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert!(module.types.get("SomeType").is_none());
+        assert!(module.types.get("AnotherType").is_some());
+
+        assert_eq!(module.functions.len(), 1);
+        assert_eq!(module.functions[0].func.sig.ident, "another_function");
+    }
+
+    /// Verify that we push an error when a bridged function uses a type that has the `ignore`
+    /// attribute.
+    #[test]
+    fn error_if_ignored_type_used() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(ignore)]
+                    type SomeType;
+
+                    fn a(&self);
+                    fn b(self: &SomeType);
+                    #[swift_bridge(associated_to = SomeType)]
+                    fn c();
+                    fn d(arg: Option<SomeType>);
+                    fn e() -> SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 5);
+
+        for error in errors.iter() {
+            match error {
+                ParseError::IgnoredTypeUsed { ignored, .. } => {
+                    assert_eq!(ignored, "SomeType");
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we can parse a static from an extern "Rust" block.
+    #[test]
+    fn parse_extern_static() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    /// The shared registry.
+                    #[swift_bridge(associated_to = Registry, swift_access = "internal")]
+                    static SHARED: Registry;
+                }
+
+                extern "Rust" {
+                    type Registry;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(module.statics.len(), 1);
+
+        let parsed_static = &module.statics[0];
+        assert_eq!(parsed_static.ident, "SHARED");
+        assert_eq!(parsed_static.associated_to.as_ref().unwrap(), "Registry");
+        assert_eq!(parsed_static.swift_access, SwiftAccessLevel::Internal);
+        assert_eq!(
+            parsed_static.doc_comment.as_deref(),
+            Some(" The shared registry.")
+        );
+    }
+
+    /// Verify that we push errors for unsupported statics.
+    #[test]
+    fn error_if_unsupported_static() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    static NAME: String;
+                    static mut COUNTER: u32;
+                }
+
+                extern "Swift" {
+                    static SWIFT_VALUE: u32;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ParseError::UnsupportedStaticType { .. })));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ParseError::MutableStatic { .. })));
+        assert!(errors.iter().any(
+            |e| matches!(e, ParseError::StaticInExternSwift { ident } if ident == "SWIFT_VALUE")
+        ));
+    }
+
+    /// Verify that we push errors for function arguments that aren't plain names.
+    #[test]
+    fn error_if_unsupported_arg_pattern() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn a (&self: SomeType);
+                    fn b (SomeType(inner): SomeType);
+                    fn c (_: u8);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], ParseError::RefSelfPattern { .. }));
+        assert!(matches!(
+            errors[1],
+            ParseError::UnsupportedArgPattern { .. }
+        ));
+        assert!(matches!(
+            errors[2],
+            ParseError::UnsupportedArgPattern { .. }
+        ));
+    }
+
+    /// Verify that we push an error if the type of `self` isn't a type or a reference to one.
+    #[test]
+    fn error_if_invalid_self_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn a (self: [SomeType; 2]);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ParseError::InvalidSelfType { .. })));
+    }
+
+    /// Verify that we push an error if an initializer doesn't return a type.
+    #[test]
+    fn error_if_initializer_without_return_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(init)]
+                    fn new ();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::FunctionAttribute(
+                FunctionAttributeParseError::InitializerWithoutReturnType { fn_ident },
+            ) => {
+                assert_eq!(fn_ident, "new");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we push an error if a generic opaque type has a lifetime or const parameter.
+    #[test]
+    fn error_if_unsupported_generic_param() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(declare_generic)]
+                    type SomeType<'a, const N: usize>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        for error in errors.iter() {
+            assert!(matches!(error, ParseError::UnsupportedGenericParam { .. }));
+        }
+    }
+
+    /// Verify that an unrecognized opaque type attribute is a compile error.
+    #[test]
+    fn error_if_unrecognized_opaque_type_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[derive(Debug)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let result: syn::Result<SwiftBridgeModuleAndErrors> = syn::parse2(tokens);
+
+        let error = result.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"Did not recognize type attribute "derive"."#
+        );
+    }
+
+    /// Verify that we push errors for methods and associated functions of undeclared types, and
+    /// keep parsing the rest of the module.
+    #[test]
+    fn error_if_self_or_associated_type_undeclared() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn a (self: NotDeclared);
+                    fn b (self: &mut AlsoNotDeclared);
+                    #[swift_bridge(associated_to = Missing)]
+                    fn c () -> u8;
+                    fn d (self: &SomeType, arg: UnknownArg);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let undeclared: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::UndeclaredType { ty } => ty.to_token_stream().to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            undeclared,
+            vec!["NotDeclared", "AlsoNotDeclared", "Missing", "UnknownArg"]
+        );
+    }
+
+    /// Verify that we push errors for `extern "Swift"` signatures that we can't generate working
+    /// code for, and not for the ones that we can.
+    #[test]
+    fn error_if_extern_swift_signature_unsupported() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type RustType;
+                }
+
+                extern "Swift" {
+                    type SwiftType;
+
+                    async fn a();
+                    fn b(&self) -> &RustType;
+                    fn c(&self) -> Option<&str>;
+                    fn d(&self) -> Box<dyn FnOnce(u8)>;
+                    fn e(&self, arg: &SwiftType);
+                    fn f(arg: Option<&mut SwiftType>);
+
+                    fn g(&mut self) -> &str;
+                    fn h(self: &SwiftType) -> &[u8];
+                    fn i(&self, arg: &RustType, callback: Box<dyn FnOnce(u8)>);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::AsyncExternSwiftFn { .. } => "async".to_string(),
+                ParseError::ExternSwiftReturnsReference { ty } => {
+                    format!("returns {}", ty.to_token_stream())
+                }
+                ParseError::ExternSwiftReturnsBoxedFn { ty } => {
+                    format!("returns {}", ty.to_token_stream())
+                }
+                ParseError::ExternSwiftArgBorrowsSwiftType { ty } => {
+                    format!("borrows {}", ty.to_token_stream())
+                }
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "async",
+                "returns & RustType",
+                "returns Option < & str >",
+                "returns Box < dyn FnOnce (u8) >",
+                "borrows & SwiftType",
+                "borrows & mut SwiftType",
+            ]
+        );
+    }
+
+    /// Verify that we push an error for types and functions that are declared more than once,
+    /// but not for functions that are enabled under different `#[cfg(...)]` attributes or that
+    /// belong to different types.
+    #[test]
+    fn error_if_declared_more_than_once() {
+        let tokens = quote! {
+            mod foo {
+                struct SharedStruct;
+
+                extern "Rust" {
+                    type SomeType;
+                    type AnotherType;
+
+                    fn a();
+                    fn a();
+
+                    fn b(self: &SomeType);
+                    fn b(self: &AnotherType);
+
+                    #[cfg(feature = "x")]
+                    fn c();
+                    #[cfg(not(feature = "x"))]
+                    fn c();
+                }
+
+                extern "Rust" {
+                    type SomeType;
+                    type SharedStruct;
+
+                    fn b(self: &SomeType);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::DuplicateDeclaration {
+                    duplicate,
+                    previous,
+                } => {
+                    assert_eq!(duplicate, previous);
+                    duplicate.to_string()
+                }
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(errors, vec!["a", "SharedStruct", "SomeType", "b"]);
+    }
 }
@@ -1,4 +1,8 @@
-use crate::parsed_extern_fn::{GetField, GetFieldDirect, GetFieldWith};
+use crate::parse::{SwiftAccessLevel, SwiftAvailability};
+use crate::parsed_extern_fn::{
+    ArgumentLabel, DefaultArgValue, GetField, GetFieldDirect, GetFieldWith, SetField,
+    SetFieldDirect, SetFieldWith,
+};
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
 use syn::{LitStr, Path, Token};
@@ -8,12 +12,24 @@ pub(super) struct FunctionAttributes {
     pub associated_to: Option<Ident>,
     pub is_swift_initializer: bool,
     pub is_swift_identifiable: bool,
+    pub is_swift_property: bool,
+    pub is_main_actor: bool,
+    pub is_cancellable: bool,
+    pub is_completion_handler: bool,
+    pub is_blocking: bool,
+    pub queue: Option<LitStr>,
     pub rust_name: Option<LitStr>,
     pub swift_name: Option<LitStr>,
     pub return_into: bool,
     pub return_with: Option<Path>,
     pub args_into: Option<Vec<Ident>>,
     pub get_field: Option<GetField>,
+    pub set_field: Option<SetField>,
+    pub argument_labels: Option<Vec<ArgumentLabel>>,
+    pub default_arg_values: Option<Vec<DefaultArgValue>>,
+    pub available: Option<SwiftAvailability>,
+    pub swift_access: Option<SwiftAccessLevel>,
+    pub ignore: bool,
 }
 
 impl FunctionAttributes {
@@ -39,10 +55,25 @@ impl FunctionAttributes {
             FunctionAttr::Identifiable => {
                 self.is_swift_identifiable = true;
             }
+            FunctionAttr::SwiftProperty => self.is_swift_property = true,
+            FunctionAttr::MainActor => self.is_main_actor = true,
+            FunctionAttr::Cancellable => self.is_cancellable = true,
+            FunctionAttr::CompletionHandler => self.is_completion_handler = true,
+            FunctionAttr::Blocking => self.is_blocking = true,
+            FunctionAttr::Queue(queue) => self.queue = Some(queue),
             FunctionAttr::GetField(get_field) => self.get_field = Some(GetField::Direct(get_field)),
             FunctionAttr::GetFieldWith(get_field) => {
                 self.get_field = Some(GetField::With(get_field))
             }
+            FunctionAttr::SetField(set_field) => self.set_field = Some(SetField::Direct(set_field)),
+            FunctionAttr::SetFieldWith(set_field) => {
+                self.set_field = Some(SetField::With(set_field))
+            }
+            FunctionAttr::ArgumentLabels(labels) => self.argument_labels = Some(labels),
+            FunctionAttr::Default(values) => self.default_arg_values = Some(values),
+            FunctionAttr::Available(available) => self.available = Some(available),
+            FunctionAttr::SwiftAccess(access) => self.swift_access = Some(access),
+            FunctionAttr::Ignore => self.ignore = true,
         }
     }
 }
@@ -53,11 +84,24 @@ pub(super) enum FunctionAttr {
     RustName(LitStr),
     Init,
     Identifiable,
+    SwiftProperty,
+    MainActor,
+    Cancellable,
+    CompletionHandler,
+    Blocking,
+    Queue(LitStr),
     ReturnInto,
     ReturnWith(Path),
     ArgsInto(Vec<Ident>),
     GetField(GetFieldDirect),
     GetFieldWith(GetFieldWith),
+    SetField(SetFieldDirect),
+    SetFieldWith(SetFieldWith),
+    ArgumentLabels(Vec<ArgumentLabel>),
+    Default(Vec<DefaultArgValue>),
+    Available(SwiftAvailability),
+    SwiftAccess(SwiftAccessLevel),
+    Ignore,
 }
 
 impl Parse for FunctionAttributes {
@@ -94,6 +138,17 @@ impl Parse for FunctionAttr {
             }
             "init" => FunctionAttr::Init,
             "Identifiable" => FunctionAttr::Identifiable,
+            "swift_property" => FunctionAttr::SwiftProperty,
+            "main_actor" => FunctionAttr::MainActor,
+            "cancellable" => FunctionAttr::Cancellable,
+            "completion_handler" => FunctionAttr::CompletionHandler,
+            "blocking" => FunctionAttr::Blocking,
+            "queue" => {
+                input.parse::<Token![=]>()?;
+                let value: LitStr = input.parse()?;
+
+                FunctionAttr::Queue(value)
+            }
             // TODO: Right before we release 0.2.0 we should remove this
             //  "into_return_type" variant since it is deprecated.
             //
@@ -148,6 +203,55 @@ impl Parse for FunctionAttr {
                     path,
                 })
             }
+            "set" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let field_name = content.parse::<Ident>()?;
+
+                FunctionAttr::SetField(SetFieldDirect { field_name })
+            }
+            "set_with" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let field_name = content.parse::<Ident>()?;
+                content.parse::<Token![=]>()?;
+                let path = content.parse::<Path>()?;
+
+                FunctionAttr::SetFieldWith(SetFieldWith { field_name, path })
+            }
+            "argument_labels" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let labels =
+                    syn::punctuated::Punctuated::<ArgumentLabel, Token![,]>::parse_terminated(
+                        &content,
+                    )?;
+                FunctionAttr::ArgumentLabels(labels.into_iter().collect())
+            }
+            "default" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let values =
+                    syn::punctuated::Punctuated::<DefaultArgValue, Token![,]>::parse_terminated(
+                        &content,
+                    )?;
+                FunctionAttr::Default(values.into_iter().collect())
+            }
+            "available" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                FunctionAttr::Available(content.parse()?)
+            }
+            "swift_access" => {
+                input.parse::<Token![=]>()?;
+                FunctionAttr::SwiftAccess(input.parse()?)
+            }
+            "ignore" => FunctionAttr::Ignore,
             _ => {
                 let attrib = key.to_string();
                 Err(syn::Error::new_spanned(
@@ -296,6 +400,252 @@ mod tests {
         assert!(func.is_swift_initializer);
     }
 
+    /// Verify that we can parse the `main_actor` attribute on an extern "Swift" function.
+    #[test]
+    fn main_actor() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(main_actor)]
+                    fn bar ();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert!(func.is_main_actor);
+    }
+
+    /// Verify that we push an error if the `main_actor` attribute is used in an extern "Rust"
+    /// block.
+    #[test]
+    fn error_if_main_actor_in_extern_rust() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(main_actor)]
+                    fn bar ();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::FunctionAttribute(FunctionAttributeParseError::MainActorInExternRust {
+                fn_ident,
+            }) => {
+                assert_eq!(fn_ident, "bar");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `cancellable` attribute on an extern "Rust" async function.
+    #[test]
+    fn cancellable() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(cancellable)]
+                    async fn bar ();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert!(func.is_cancellable);
+    }
+
+    /// Verify that we push an error if the `cancellable` attribute is used on a function that
+    /// isn't async.
+    #[test]
+    fn error_if_cancellable_not_async() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(cancellable)]
+                    fn bar ();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::FunctionAttribute(
+                FunctionAttributeParseError::CancellableNotAsyncRust { fn_ident },
+            ) => {
+                assert_eq!(fn_ident, "bar");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `completion_handler` attribute on an extern "Swift" function.
+    #[test]
+    fn completion_handler() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(completion_handler)]
+                    fn bar (arg: u8, completion: Box<dyn FnOnce(u16)>);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert!(func.is_completion_handler);
+    }
+
+    /// Verify that we push an error if a `completion_handler` function is an extern "Rust"
+    /// function, returns a value or doesn't take a trailing boxed function.
+    #[test]
+    fn error_if_invalid_completion_handler() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(completion_handler)]
+                    fn a (completion: Box<dyn FnOnce(u16)>);
+                }
+                extern "Swift" {
+                    #[swift_bridge(completion_handler)]
+                    fn b (completion: Box<dyn FnOnce(u16)>) -> u8;
+
+                    #[swift_bridge(completion_handler)]
+                    fn c (completion: Box<dyn FnOnce(u16)>, arg: u8);
+
+                    #[swift_bridge(completion_handler)]
+                    fn d (completion: Box<dyn FnOnce(u16) -> u8>);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 4);
+        for (error, expected_fn) in errors.iter().zip(["a", "b", "c", "d"]) {
+            match error {
+                ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::InvalidCompletionHandler { fn_ident },
+                ) => {
+                    assert_eq!(fn_ident, expected_fn);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we can parse the `queue` attribute on an extern "Swift" function.
+    #[test]
+    fn queue() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(queue = "main")]
+                    fn bar (arg: String);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert_eq!(func.queue.as_ref().unwrap().value(), "main");
+    }
+
+    /// Verify that we push an error if a `queue` function is an extern "Rust" function, returns
+    /// a value, borrows an argument or is also `main_actor`.
+    #[test]
+    fn error_if_invalid_queue() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(queue = "main")]
+                    fn a ();
+                }
+                extern "Swift" {
+                    #[swift_bridge(queue = "main")]
+                    fn b () -> u8;
+
+                    #[swift_bridge(queue = "main")]
+                    fn c (arg: &str);
+
+                    #[swift_bridge(queue = "main", main_actor)]
+                    fn d ();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 4);
+        for (error, expected_fn) in errors.iter().zip(["a", "b", "c", "d"]) {
+            match error {
+                ParseError::FunctionAttribute(FunctionAttributeParseError::InvalidQueue {
+                    fn_ident,
+                }) => {
+                    assert_eq!(fn_ident, expected_fn);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that an init function that returns an `Option<T>` is a failable initializer for `T`.
+    #[test]
+    fn failable_initializer() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(init)]
+                    fn bar (bazz: u8) -> Option<Foo>;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert!(func.is_failable_swift_initializer());
+        assert!(!func.is_throwing_swift_initializer());
+        assert_eq!(func.associated_type_name().unwrap(), "Foo");
+    }
+
+    /// Verify that an init function that returns a `Result<T, E>` is a throwing initializer
+    /// for `T`.
+    #[test]
+    fn throwing_initializer() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type Foo;
+                    type InitError;
+
+                    #[swift_bridge(init)]
+                    fn bar (bazz: u8) -> Result<Foo, InitError>;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert!(func.is_throwing_swift_initializer());
+        assert!(!func.is_failable_swift_initializer());
+        assert_eq!(func.associated_type_name().unwrap(), "Foo");
+    }
+
     /// Verify that we push an error if the initialize type is not defined.
     #[test]
     fn error_if_initialized_type_not_defined() {
@@ -513,6 +863,255 @@ mod tests {
         }
     }
 
+    /// Verify that we can parse the `set` attribute.
+    #[test]
+    fn parses_set_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(set(field))]
+                    fn set_field(&mut self, value: u8);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let field = module.functions[0]
+            .set_field
+            .as_ref()
+            .unwrap()
+            .unwrap_direct();
+        assert_eq!(field.field_name.to_string(), "field");
+    }
+
+    /// Verify that we can parse the `set_with` attribute.
+    #[test]
+    fn parses_set_with_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(set_with(field = a::b::c))]
+                    fn set_field(&mut self, value: u8);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let field = module.functions[0]
+            .set_field
+            .as_ref()
+            .unwrap()
+            .unwrap_with();
+        assert_eq!(field.field_name.to_string(), "field");
+        assert_eq!(field.path.to_token_stream().to_string(), "a :: b :: c");
+    }
+
+    /// Verify that we can parse the `argument_labels` attribute.
+    #[test]
+    fn parses_argument_labels_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(argument_labels(user = "for", id = "_"))]
+                    fn fetch(user: String, id: u32);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert_eq!(func.swift_argument_label("user").unwrap(), "for");
+        assert_eq!(func.swift_argument_label("id").unwrap(), "_");
+    }
+
+    /// Verify that we push a parse error if an argument label refers to an argument that
+    /// the function does not have.
+    #[test]
+    fn error_if_argument_label_for_unknown_arg() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(argument_labels(usr = "for"))]
+                    fn fetch(user: String);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0] {
+            ParseError::FunctionAttribute(
+                FunctionAttributeParseError::ArgumentLabelUnknownArg { fn_ident, arg },
+            ) => {
+                assert_eq!(fn_ident, "fetch");
+                assert_eq!(arg, "usr");
+            }
+            _ => panic!(),
+        };
+    }
+
+    /// Verify that we can parse the `default` attribute.
+    #[test]
+    fn parses_default_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(default(limit = 50, scale = -1.5, verbose = true, max = None))]
+                    fn fetch(limit: u32, scale: f64, verbose: bool, max: Option<u8>);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert_eq!(func.swift_default_arg_value("limit").unwrap(), "50");
+        assert_eq!(func.swift_default_arg_value("scale").unwrap(), "-1.5");
+        assert_eq!(func.swift_default_arg_value("verbose").unwrap(), "true");
+        assert_eq!(func.swift_default_arg_value("max").unwrap(), "nil");
+    }
+
+    /// Verify that we push a parse error if a default value refers to an argument that
+    /// the function does not have.
+    #[test]
+    fn error_if_default_for_unknown_arg() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(default(limt = 50))]
+                    fn fetch(limit: u32);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0] {
+            ParseError::FunctionAttribute(
+                FunctionAttributeParseError::DefaultArgValueUnknownArg { fn_ident, arg },
+            ) => {
+                assert_eq!(fn_ident, "fetch");
+                assert_eq!(arg, "limt");
+            }
+            _ => panic!(),
+        };
+    }
+
+    /// Verify that we get an error if a default value is not a literal.
+    #[test]
+    fn error_if_default_is_not_literal() {
+        let tokens = quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(default(limit = compute_limit()))]
+                    fn fetch(limit: u32);
+                }
+            }
+        };
+
+        assert!(syn::parse2::<crate::SwiftBridgeModule>(tokens).is_err());
+    }
+
+    /// Verify that we can parse the `swift_property` attribute.
+    #[test]
+    fn parses_swift_property_attribute() {
+        let tokens = quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(swift_property)]
+                    fn title(&self) -> String;
+                    #[swift_bridge(swift_property)]
+                    fn set_title(&mut self, title: String);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let getter = &module.functions[0];
+        assert!(getter.is_swift_property_getter());
+
+        let setter = &module.functions[1];
+        assert_eq!(setter.swift_property_setter_name().unwrap(), "title");
+    }
+
+    /// Verify that we push parse errors for `swift_property` functions that are not getters or
+    /// setters, and for setters that do not have a matching getter.
+    #[test]
+    fn error_if_invalid_swift_property() {
+        let tokens = quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(swift_property)]
+                    fn title(&self, arg: u8) -> String;
+
+                    #[swift_bridge(swift_property)]
+                    fn count(&self) -> u32;
+                    #[swift_bridge(swift_property)]
+                    fn set_count(&mut self, count: u64);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 2);
+
+        match &errors[0] {
+            ParseError::FunctionAttribute(
+                FunctionAttributeParseError::SwiftPropertyInvalidSignature { fn_ident },
+            ) => {
+                assert_eq!(fn_ident, "title");
+            }
+            _ => panic!(),
+        };
+        match &errors[1] {
+            ParseError::FunctionAttribute(
+                FunctionAttributeParseError::SwiftPropertySetterWithoutGetter { fn_ident },
+            ) => {
+                assert_eq!(fn_ident, "set_count");
+            }
+            _ => panic!(),
+        };
+    }
+
+    /// Verify that we can parse the `available` attribute.
+    #[test]
+    fn parses_available_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(available(iOS = "15.0", macOS = "12.0"))]
+                    fn some_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let available = module.functions[0].available.as_ref().unwrap();
+        assert_eq!(available.platforms.len(), 2);
+    }
+
     /// Verify that we can parse a function that has multiple swift_bridge attributes.
     #[test]
     fn parses_multiple_function_swift_bridge_attributes() {
@@ -533,4 +1132,245 @@ mod tests {
         assert_eq!(func.args_into.as_ref().unwrap().len(), 1);
         assert_eq!(func.return_into, true);
     }
+
+    /// Verify that we push errors for attributes that can't be used together, or that can't be
+    /// used with the function's signature.
+    #[test]
+    fn error_if_invalid_attribute_combination() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(init, associated_to = Foo)]
+                    fn a() -> Foo;
+                    #[swift_bridge(init)]
+                    fn b(&self) -> Foo;
+                    #[swift_bridge(associated_to = Foo)]
+                    fn c(&self);
+                    #[swift_bridge(swift_name = "some-function")]
+                    fn d();
+                    #[swift_bridge(rust_name = "path::to::function")]
+                    fn e();
+                }
+
+                extern "Swift" {
+                    #[swift_bridge(swift_name = "SomeEnum.someFunction")]
+                    fn f();
+                    #[swift_bridge(swift_name = "1function")]
+                    fn g();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::InitializerWithAssociatedTo { associated_to },
+                ) => format!("init associated_to {}", associated_to),
+                ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::InitializerWithSelf { fn_ident },
+                ) => format!("init self {}", fn_ident),
+                ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::AssociatedToWithSelf { associated_to },
+                ) => format!("self associated_to {}", associated_to),
+                ParseError::FunctionAttribute(FunctionAttributeParseError::InvalidSwiftName {
+                    swift_name,
+                }) => format!("swift_name {}", swift_name.value()),
+                ParseError::FunctionAttribute(FunctionAttributeParseError::InvalidRustName {
+                    rust_name,
+                }) => format!("rust_name {}", rust_name.value()),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "init associated_to Foo",
+                "init self b",
+                "self associated_to Foo",
+                "swift_name some-function",
+                "rust_name path::to::function",
+                "swift_name 1function",
+            ]
+        );
+    }
+
+    /// Verify that we can parse the `blocking` attribute.
+    #[test]
+    fn blocking() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(blocking)]
+                    fn some_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert!(func.is_blocking);
+    }
+
+    /// Verify that we push an error if a `blocking` function is async, or can't be exposed as an
+    /// async Swift function.
+    #[test]
+    fn error_if_invalid_blocking() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(blocking)]
+                    async fn a();
+                    #[swift_bridge(blocking, init)]
+                    fn b() -> Foo;
+                    #[swift_bridge(blocking, swift_property)]
+                    fn c(&self) -> u8;
+                }
+
+                extern "Swift" {
+                    #[swift_bridge(blocking)]
+                    fn d();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::FunctionAttribute(FunctionAttributeParseError::BlockingAsync {
+                    fn_ident,
+                }) => format!("async {}", fn_ident),
+                ParseError::FunctionAttribute(FunctionAttributeParseError::InvalidBlocking {
+                    fn_ident,
+                }) => format!("invalid {}", fn_ident),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["async a", "invalid b", "invalid c", "invalid d"]
+        );
+    }
+
+    /// Verify that we push an error for async initializers that return an `Option` or a `Result`,
+    /// or that initialize a `Copy` type.
+    #[test]
+    fn error_if_unsupported_async_initializer() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type Foo;
+                    #[swift_bridge(Copy(4))]
+                    type Bar;
+
+                    #[swift_bridge(init)]
+                    async fn a() -> Foo;
+                    #[swift_bridge(init)]
+                    async fn b() -> Option<Foo>;
+                    #[swift_bridge(init)]
+                    async fn c() -> Result<Foo, String>;
+                    #[swift_bridge(init)]
+                    async fn d() -> Bar;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::UnsupportedAsyncInitializer { fn_ident },
+                ) => fn_ident.to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(errors, vec!["b", "c", "d"]);
+    }
+
+    /// Verify that we push an error for failable and throwing initializers of `Copy` types.
+    #[test]
+    fn error_if_failable_copy_initializer() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Copy(4))]
+                    type Foo;
+
+                    #[swift_bridge(init)]
+                    fn a() -> Foo;
+                    #[swift_bridge(init)]
+                    fn b() -> Option<Foo>;
+                    #[swift_bridge(init)]
+                    fn c() -> Result<Foo, String>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::FunctionAttribute(
+                    FunctionAttributeParseError::UnsupportedCopyInitializer { fn_ident },
+                ) => fn_ident.to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(errors, vec!["b", "c"]);
+    }
+
+    /// Verify that we push an error for `set` and `set_with` functions that don't take
+    /// `&mut self` and a single value, or that have a return type.
+    #[test]
+    fn error_if_invalid_setter() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(set(field))]
+                    fn a(&mut self, value: u8);
+                    #[swift_bridge(set(field))]
+                    fn b(&self, value: u8);
+                    #[swift_bridge(set(field))]
+                    fn c(&mut self);
+                    #[swift_bridge(set(field))]
+                    fn d(&mut self, value: u8, other: u8);
+                    #[swift_bridge(set(field))]
+                    fn e(self: &mut Foo, value: u8);
+                    #[swift_bridge(set(field))]
+                    fn f(&mut self, value: u8) -> u8;
+                    #[swift_bridge(set_with(field = a::b))]
+                    fn g(self, value: u8);
+                    #[swift_bridge(set_with(field = a::b))]
+                    fn h(&mut self, value: u8);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::FunctionAttribute(FunctionAttributeParseError::InvalidSetter {
+                    fn_ident,
+                }) => fn_ident.to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(errors, vec!["b", "c", "d", "f", "g"]);
+    }
 }
@@ -1,17 +1,20 @@
-use crate::parse::OpaqueCopy;
+use crate::bridge_module_attributes::CfgAttr;
+use crate::errors::ParseError;
+use crate::parse::parse_extern_mod::push_doc_comment_line;
+use crate::parse::{OpaqueCopy, SwiftAccessLevel, SwiftAvailability};
 use proc_macro2::Ident;
 use quote::ToTokens;
 use std::ops::Deref;
 use syn::parse::{Parse, ParseStream};
-use syn::{Attribute, LitInt, Meta};
+use syn::{Attribute, LitInt, LitStr, Token, Type};
 
 #[derive(Default, Clone)]
 pub(crate) struct OpaqueTypeAllAttributes {
     pub swift_bridge: OpaqueTypeSwiftBridgeAttributes,
     /// A doc comment.
-    // TODO: Use this to generate doc comment for the generated Swift type.
-    #[allow(unused)]
     pub doc_comment: Option<String>,
+    /// `#[cfg(feature = "...")]` or `#[cfg(target_os = "...")]`
+    pub cfg_attrs: Vec<CfgAttr>,
 }
 
 #[derive(Default, Clone)]
@@ -20,18 +23,103 @@ pub(crate) struct OpaqueTypeSwiftBridgeAttributes {
     /// If it was, we won't generate Swift and C type declarations for this type, since we
     /// will elsewhere.
     pub already_declared: bool,
+    /// `#[swift_bridge(AsyncStream(SomeType))]`
+    /// The type of the values that the generated Swift `AsyncStream` yields.
+    pub async_stream: Option<Box<Type>>,
+    /// `#[swift_bridge(Codable)]`
+    /// Used to implement Swift's `Codable` using the type's serde `Serialize` and `Deserialize`
+    /// implementations.
+    pub codable: bool,
+    /// `#[swift_bridge(Comparable)]`
+    /// Used to implement Swift's `Comparable` using the type's Rust `Ord` implementation.
+    pub comparable: bool,
     /// `#[swift_bridge(Copy(...)]`
     /// Describes the type's Copy semantics.
     pub copy: Option<OpaqueCopy>,
+    /// `#[swift_bridge(CustomStringConvertible)]`
+    /// Used to implement Swift's `CustomStringConvertible` using the type's Rust `Display`
+    /// implementation.
+    pub custom_string_convertible: bool,
+    /// `#[swift_bridge(CustomDebugStringConvertible)]`
+    /// Used to implement Swift's `CustomDebugStringConvertible` using the type's Rust `Debug`
+    /// implementation.
+    pub custom_debug_string_convertible: bool,
     /// `#[swift_bridge(declare_generic)]`
     /// Used to declare a generic type.
     pub declare_generic: bool,
     /// `#[swift_bridge(Equatable)]`
     /// Used to determine if Equatable need to be implemented.
     pub equatable: bool,
+    /// `#[swift_bridge(Error)]`
+    /// Used to conform the type to Swift's `LocalizedError`, using the type's Rust `Display`
+    /// implementation as its description, so that it can be thrown.
+    pub error: bool,
     /// `#[swift_bridge(Hashable)]`
     /// Used to determine if Hashable need to be implemented.
     pub hashable: bool,
+    /// `#[swift_bridge(Identifiable = "id")]`
+    /// The name of the bridged getter to use as the type's Swift `Identifiable` id.
+    pub identifiable: Option<LitStr>,
+    /// `#[swift_bridge(Mutex(SomeType))]` or `#[swift_bridge(RwLock(SomeType))]`
+    /// The lock and the type of the value that the generated Swift `withLock` methods give
+    /// access to.
+    pub lock: Option<OpaqueLock>,
+    /// `#[swift_bridge(ObservableObject)]`
+    /// Used to generate a SwiftUI `ObservableObject` wrapper for the type.
+    pub observable_object: bool,
+    /// `#[swift_bridge(actor)]`
+    /// Used to mark an extern "Swift" type as a Swift `actor`, so that Rust calls its methods
+    /// from outside of the actor's isolation.
+    pub actor: bool,
+    /// `#[swift_bridge(objc)]`
+    /// Used to make the type's generated Swift classes inherit from `NSObject` and expose their
+    /// members to Objective-C.
+    pub objc: bool,
+    /// `#[swift_bridge(open)]`
+    /// Used to make the type's generated Swift classes and methods `open`, so that Swift code can
+    /// subclass the type and override its methods.
+    pub open: bool,
+    /// `#[swift_bridge(Publisher(SomeType))]`
+    /// The type of the values that the generated Combine publisher emits.
+    pub publisher: Option<Box<Type>>,
+    /// `#[swift_bridge(Sendable)]`
+    /// Used to mark the type's generated Swift class or struct `@unchecked Sendable`, after
+    /// asserting at compile time that the Rust type is `Send + Sync`.
+    pub sendable: bool,
+    /// `#[swift_bridge(swift_name = "...")]`
+    /// The name to use for the type's generated Swift class or struct.
+    pub swift_name: Option<LitStr>,
+    /// `#[swift_bridge(available(iOS = "15.0"))]`
+    /// The platform versions that the type's generated Swift class or struct is available on.
+    pub available: Option<SwiftAvailability>,
+    /// `#[swift_bridge(swift_access = "...")]`
+    /// The access level of the type's generated Swift class or struct.
+    /// Set to the module's default access level if the type does not use the attribute.
+    pub swift_access: Option<SwiftAccessLevel>,
+    /// `#[swift_bridge(ignore)]`
+    /// Used to skip the type without deleting it from the bridge module.
+    pub ignore: bool,
+    /// `#[swift_bridge(protocol)]`
+    /// Used to declare an `extern "Swift"` type as a Swift protocol, so that any Swift class that
+    /// conforms to the protocol can be passed to Rust.
+    pub protocol: bool,
+}
+
+/// `#[swift_bridge(Mutex(SomeType))]` or `#[swift_bridge(RwLock(SomeType))]`
+#[derive(Clone)]
+pub(crate) struct OpaqueLock {
+    pub kind: LockKind,
+    /// The type of the value that the lock guards.
+    pub guarded: Box<Type>,
+}
+
+/// The kind of lock that a `swift_bridge::sync` type is.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum LockKind {
+    /// `swift_bridge::sync::Mutex`
+    Mutex,
+    /// `swift_bridge::sync::RwLock`
+    RwLock,
 }
 
 impl OpaqueTypeAllAttributes {
@@ -43,25 +131,20 @@ impl OpaqueTypeAllAttributes {
 
             match attribute_name.as_str() {
                 "doc" => {
-                    let meta = attr.parse_meta()?;
-                    let doc = match meta {
-                        Meta::NameValue(name_val) => match name_val.lit {
-                            syn::Lit::Str(comment) => comment.value(),
-                            _ => {
-                                todo!("Push parse error that doc attribute is in incorrect format")
-                            }
-                        },
-                        _ => {
-                            todo!("Push parse error that doc attribute is in incorrect format")
-                        }
-                    };
-
-                    attributes.doc_comment = Some(doc);
+                    push_doc_comment_line(&mut attributes.doc_comment, attr)?;
                 }
                 "swift_bridge" => {
                     attributes.swift_bridge = attr.parse_args()?;
                 }
-                _ => todo!("Push unsupported attribute error."),
+                "cfg" => {
+                    attributes.cfg_attrs.push(syn::parse2(attr.tokens.clone())?);
+                }
+                _ => {
+                    return Err(ParseError::OpaqueTypeUnrecognizedAttribute {
+                        attribute: attr.path.clone(),
+                    }
+                    .into())
+                }
             };
         }
 
@@ -72,21 +155,61 @@ impl OpaqueTypeAllAttributes {
 impl OpaqueTypeSwiftBridgeAttributes {
     pub(super) fn store_attrib(&mut self, attrib: OpaqueTypeAttr) {
         match attrib {
+            OpaqueTypeAttr::Actor => self.actor = true,
             OpaqueTypeAttr::AlreadyDeclared => self.already_declared = true,
+            OpaqueTypeAttr::AsyncStream(ty) => self.async_stream = Some(ty),
+            OpaqueTypeAttr::Codable => self.codable = true,
+            OpaqueTypeAttr::Comparable => self.comparable = true,
             OpaqueTypeAttr::Copy { size } => self.copy = Some(OpaqueCopy { size_bytes: size }),
+            OpaqueTypeAttr::CustomStringConvertible => self.custom_string_convertible = true,
+            OpaqueTypeAttr::CustomDebugStringConvertible => {
+                self.custom_debug_string_convertible = true
+            }
             OpaqueTypeAttr::DeclareGeneric => self.declare_generic = true,
             OpaqueTypeAttr::Equatable => self.equatable = true,
+            OpaqueTypeAttr::Error => self.error = true,
             OpaqueTypeAttr::Hashable => self.hashable = true,
+            OpaqueTypeAttr::Identifiable(getter) => self.identifiable = Some(getter),
+            OpaqueTypeAttr::Lock(lock) => self.lock = Some(lock),
+            OpaqueTypeAttr::ObservableObject => self.observable_object = true,
+            OpaqueTypeAttr::Objc => self.objc = true,
+            OpaqueTypeAttr::Open => self.open = true,
+            OpaqueTypeAttr::Publisher(ty) => self.publisher = Some(ty),
+            OpaqueTypeAttr::Sendable => self.sendable = true,
+            OpaqueTypeAttr::SwiftName(name) => self.swift_name = Some(name),
+            OpaqueTypeAttr::Available(available) => self.available = Some(available),
+            OpaqueTypeAttr::SwiftAccess(access) => self.swift_access = Some(access),
+            OpaqueTypeAttr::Ignore => self.ignore = true,
+            OpaqueTypeAttr::Protocol => self.protocol = true,
         }
     }
 }
 
 pub(crate) enum OpaqueTypeAttr {
+    Actor,
     AlreadyDeclared,
+    AsyncStream(Box<Type>),
+    Codable,
+    Comparable,
     Copy { size: usize },
+    CustomStringConvertible,
+    CustomDebugStringConvertible,
     DeclareGeneric,
     Equatable,
+    Error,
     Hashable,
+    Identifiable(LitStr),
+    Lock(OpaqueLock),
+    ObservableObject,
+    Objc,
+    Open,
+    Publisher(Box<Type>),
+    Sendable,
+    SwiftName(LitStr),
+    Available(SwiftAvailability),
+    SwiftAccess(SwiftAccessLevel),
+    Ignore,
+    Protocol,
 }
 
 impl Parse for OpaqueTypeSwiftBridgeAttributes {
@@ -109,7 +232,17 @@ impl Parse for OpaqueTypeAttr {
         let key: Ident = input.parse()?;
 
         let attrib = match key.to_string().as_str() {
+            "actor" => OpaqueTypeAttr::Actor,
             "already_declared" => OpaqueTypeAttr::AlreadyDeclared,
+            // AsyncStream(SomeType)
+            "AsyncStream" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                OpaqueTypeAttr::AsyncStream(Box::new(content.parse()?))
+            }
+            "Codable" => OpaqueTypeAttr::Codable,
+            "Comparable" => OpaqueTypeAttr::Comparable,
             // Copy(10)
             "Copy" => {
                 let content;
@@ -121,9 +254,58 @@ impl Parse for OpaqueTypeAttr {
                     size: size.to_string().parse().unwrap(),
                 }
             }
+            "CustomStringConvertible" => OpaqueTypeAttr::CustomStringConvertible,
+            "CustomDebugStringConvertible" => OpaqueTypeAttr::CustomDebugStringConvertible,
             "declare_generic" => OpaqueTypeAttr::DeclareGeneric,
             "Equatable" => OpaqueTypeAttr::Equatable,
+            "Error" => OpaqueTypeAttr::Error,
             "Hashable" => OpaqueTypeAttr::Hashable,
+            "Identifiable" => {
+                input.parse::<Token![=]>()?;
+                OpaqueTypeAttr::Identifiable(input.parse()?)
+            }
+            // Mutex(SomeType) or RwLock(SomeType)
+            "Mutex" | "RwLock" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let kind = if key == "Mutex" {
+                    LockKind::Mutex
+                } else {
+                    LockKind::RwLock
+                };
+                OpaqueTypeAttr::Lock(OpaqueLock {
+                    kind,
+                    guarded: Box::new(content.parse()?),
+                })
+            }
+            "ObservableObject" => OpaqueTypeAttr::ObservableObject,
+            "objc" => OpaqueTypeAttr::Objc,
+            "open" => OpaqueTypeAttr::Open,
+            // Publisher(SomeType)
+            "Publisher" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                OpaqueTypeAttr::Publisher(Box::new(content.parse()?))
+            }
+            "Sendable" => OpaqueTypeAttr::Sendable,
+            "swift_name" => {
+                input.parse::<Token![=]>()?;
+                OpaqueTypeAttr::SwiftName(input.parse()?)
+            }
+            "available" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                OpaqueTypeAttr::Available(content.parse()?)
+            }
+            "swift_access" => {
+                input.parse::<Token![=]>()?;
+                OpaqueTypeAttr::SwiftAccess(input.parse()?)
+            }
+            "ignore" => OpaqueTypeAttr::Ignore,
+            "protocol" => OpaqueTypeAttr::Protocol,
             _ => {
                 let attrib = key.to_string();
                 Err(syn::Error::new_spanned(
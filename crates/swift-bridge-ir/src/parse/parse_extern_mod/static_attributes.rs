@@ -0,0 +1,92 @@
+use crate::bridge_module_attributes::CfgAttr;
+use crate::parse::parse_extern_mod::push_doc_comment_line;
+use crate::parse::SwiftAccessLevel;
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Token};
+
+#[derive(Default)]
+pub(super) struct StaticAllAttributes {
+    pub swift_bridge: StaticSwiftBridgeAttributes,
+    /// A doc comment.
+    pub doc_comment: Option<String>,
+    /// `#[cfg(feature = "...")]` or `#[cfg(target_os = "...")]`
+    pub cfg_attrs: Vec<CfgAttr>,
+}
+
+#[derive(Default)]
+pub(super) struct StaticSwiftBridgeAttributes {
+    /// `#[swift_bridge(associated_to = SomeType)]`
+    /// Expose the static as a static property on a type instead of as a global.
+    pub associated_to: Option<Ident>,
+    /// `#[swift_bridge(swift_access = "...")]`
+    /// The access level of the generated Swift property.
+    pub swift_access: Option<SwiftAccessLevel>,
+}
+
+enum StaticAttr {
+    AssociatedTo(Ident),
+    SwiftAccess(SwiftAccessLevel),
+}
+
+impl StaticAllAttributes {
+    pub(super) fn from_attributes(attribs: &[Attribute]) -> Result<Self, syn::Error> {
+        let mut attributes = StaticAllAttributes::default();
+
+        for attr in attribs.iter() {
+            if attr.path.is_ident("doc") {
+                push_doc_comment_line(&mut attributes.doc_comment, attr)?;
+            } else if attr.path.is_ident("cfg") {
+                attributes.cfg_attrs.push(syn::parse2(attr.tokens.clone())?);
+            } else if attr.path.is_ident("swift_bridge") {
+                attributes.swift_bridge = attr.parse_args()?;
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+impl Parse for StaticSwiftBridgeAttributes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attributes = StaticSwiftBridgeAttributes::default();
+
+        let punctuated =
+            syn::punctuated::Punctuated::<StaticAttr, syn::Token![,]>::parse_terminated(input)?;
+
+        for attr in punctuated.into_iter() {
+            match attr {
+                StaticAttr::AssociatedTo(ty) => attributes.associated_to = Some(ty),
+                StaticAttr::SwiftAccess(access) => attributes.swift_access = Some(access),
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+impl Parse for StaticAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+
+        let attrib = match key.to_string().as_str() {
+            "associated_to" => {
+                input.parse::<Token![=]>()?;
+                StaticAttr::AssociatedTo(input.parse()?)
+            }
+            "swift_access" => {
+                input.parse::<Token![=]>()?;
+                StaticAttr::SwiftAccess(input.parse()?)
+            }
+            _ => {
+                let attrib = key.to_string();
+                Err(syn::Error::new_spanned(
+                    key,
+                    format!(r#"Unrecognized attribute "{}"."#, attrib),
+                ))?
+            }
+        };
+
+        Ok(attrib)
+    }
+}
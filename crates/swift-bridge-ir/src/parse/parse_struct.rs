@@ -1,9 +1,10 @@
-use crate::bridged_type::{SharedStruct, StructFields, StructSwiftRepr};
+use crate::bridged_type::{SharedStruct, StructFields, StructLayout, StructSwiftRepr};
 use crate::errors::{ParseError, ParseErrors};
 use crate::parse::move_input_cursor_to_next_comma;
+use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
-use syn::{ItemStruct, LitStr, Token};
+use syn::{ItemStruct, LitInt, LitStr, Token};
 
 pub(crate) struct SharedStructDeclarationParser<'a> {
     pub item_struct: ItemStruct,
@@ -29,6 +30,53 @@ struct StructAttribs {
     already_declared: bool,
 }
 
+/// An item inside of a `#[repr(...)]` attribute.
+enum ReprAttr {
+    C,
+    Packed(Option<LitInt>),
+    Align(LitInt),
+    Unsupported(Ident),
+}
+
+struct ParsedReprAttribs(Vec<ReprAttr>);
+impl Parse for ParsedReprAttribs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let opts = syn::punctuated::Punctuated::<_, syn::token::Comma>::parse_terminated(input)?;
+
+        Ok(ParsedReprAttribs(opts.into_iter().collect()))
+    }
+}
+
+impl Parse for ReprAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+
+        let attr = match key.to_string().as_str() {
+            "C" => ReprAttr::C,
+            "packed" => {
+                if input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    ReprAttr::Packed(Some(content.parse()?))
+                } else {
+                    ReprAttr::Packed(None)
+                }
+            }
+            "align" => {
+                let content;
+                syn::parenthesized!(content in input);
+                ReprAttr::Align(content.parse()?)
+            }
+            _ => {
+                move_input_cursor_to_next_comma(input);
+                ReprAttr::Unsupported(key)
+            }
+        };
+
+        Ok(attr)
+    }
+}
+
 struct ParsedAttribs(Vec<StructAttr>);
 impl Parse for ParsedAttribs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -80,7 +128,40 @@ impl<'a> SharedStructDeclarationParser<'a> {
 
         let mut attribs = StructAttribs::default();
 
+        let mut layout = StructLayout::default();
+        let mut has_repr_c = true;
+        let mut has_repr_attr = false;
+
         for attr in item_struct.attrs {
+            if attr.path.is_ident("repr") {
+                let reprs: ParsedReprAttribs = attr.parse_args()?;
+
+                has_repr_attr = true;
+                has_repr_c = false;
+
+                for repr in reprs.0 {
+                    match repr {
+                        ReprAttr::C => {
+                            has_repr_c = true;
+                        }
+                        ReprAttr::Packed(value) => {
+                            layout.packed = match value {
+                                Some(value) => parse_power_of_two(value, self.errors),
+                                None => Some(1),
+                            };
+                        }
+                        ReprAttr::Align(value) => {
+                            layout.align = parse_power_of_two(value, self.errors);
+                        }
+                        ReprAttr::Unsupported(repr) => {
+                            self.errors.push(ParseError::StructUnsupportedRepr { repr });
+                        }
+                    }
+                }
+
+                continue;
+            }
+
             let sections: ParsedAttribs = attr.parse_args()?;
 
             for attr in sections.0 {
@@ -131,18 +212,48 @@ impl<'a> SharedStructDeclarationParser<'a> {
             StructSwiftRepr::Structure
         };
 
+        if has_repr_attr && !has_repr_c {
+            self.errors.push(ParseError::StructReprMissingC {
+                struct_ident: item_struct.ident.clone(),
+            });
+        }
+        if layout.packed.is_some() && layout.align.is_some() {
+            self.errors.push(ParseError::StructReprPackedAndAligned {
+                struct_ident: item_struct.ident.clone(),
+            });
+        }
+        if !layout.is_default() && swift_repr == StructSwiftRepr::Class {
+            self.errors
+                .push(ParseError::StructLayoutWithSwiftReprClass {
+                    struct_ident: item_struct.ident.clone(),
+                });
+        }
+
         let shared_struct = SharedStruct {
             name: item_struct.ident,
             swift_repr,
             fields: StructFields::from_syn_fields(item_struct.fields),
             swift_name: attribs.swift_name,
             already_declared: attribs.already_declared,
+            layout,
+            symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
         };
 
         Ok(shared_struct)
     }
 }
 
+/// Parse a `packed(N)` or `align(N)` value, pushing an error if it is not a power of two.
+fn parse_power_of_two(value: LitInt, errors: &mut ParseErrors) -> Option<u32> {
+    match value.base10_parse::<u32>() {
+        Ok(parsed) if parsed.is_power_of_two() => Some(parsed),
+        _ => {
+            errors.push(ParseError::StructReprNotPowerOfTwo { value });
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,4 +478,97 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    /// Verify that we parse `packed` and `align` out of a struct's `#[repr(...)]` attribute.
+    #[test]
+    fn parses_struct_repr_layout() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[repr(C)]
+                struct Plain;
+
+                #[repr(C, packed)]
+                struct Packed;
+
+                #[repr(C, packed(2))]
+                struct PackedTwo;
+
+                #[repr(C, align(8))]
+                #[swift_bridge(swift_repr = "struct")]
+                struct Aligned {
+                    field: u8
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+        let types = module.types.types();
+
+        assert!(types[0].unwrap_shared_struct().layout.is_default());
+        assert_eq!(types[1].unwrap_shared_struct().layout.packed, Some(1));
+        assert_eq!(types[2].unwrap_shared_struct().layout.packed, Some(2));
+        assert_eq!(types[3].unwrap_shared_struct().layout.align, Some(8));
+    }
+
+    /// Verify that we push errors for struct layouts that we can't represent.
+    #[test]
+    fn error_if_invalid_struct_repr() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[repr(C, transparent)]
+                struct Unsupported;
+
+                #[repr(packed)]
+                struct MissingC;
+
+                #[repr(C, packed, align(4))]
+                struct PackedAndAligned;
+
+                #[repr(C, align(3))]
+                struct NotPowerOfTwo;
+
+                #[repr(C, packed)]
+                #[swift_bridge(swift_repr = "class")]
+                struct ReprClass {
+                    field: u8
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 5);
+
+        match &errors[0] {
+            ParseError::StructUnsupportedRepr { repr } => {
+                assert_eq!(repr, "transparent");
+            }
+            _ => panic!(),
+        };
+        match &errors[1] {
+            ParseError::StructReprMissingC { struct_ident } => {
+                assert_eq!(struct_ident, "MissingC");
+            }
+            _ => panic!(),
+        };
+        match &errors[2] {
+            ParseError::StructReprPackedAndAligned { struct_ident } => {
+                assert_eq!(struct_ident, "PackedAndAligned");
+            }
+            _ => panic!(),
+        };
+        match &errors[3] {
+            ParseError::StructReprNotPowerOfTwo { value } => {
+                assert_eq!(value.to_string(), "3");
+            }
+            _ => panic!(),
+        };
+        match &errors[4] {
+            ParseError::StructLayoutWithSwiftReprClass { struct_ident } => {
+                assert_eq!(struct_ident, "ReprClass");
+            }
+            _ => panic!(),
+        };
+    }
 }
@@ -0,0 +1,75 @@
+use syn::parse::{Parse, ParseStream};
+use syn::LitStr;
+
+/// The access level of generated Swift classes, structs and functions.
+///
+/// `#[swift_bridge(swift_access = "internal")]`
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub(crate) enum SwiftAccessLevel {
+    #[default]
+    Public,
+    Package,
+    Internal,
+    FilePrivate,
+}
+
+impl SwiftAccessLevel {
+    /// The Swift access modifier keyword.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SwiftAccessLevel::Public => "public",
+            SwiftAccessLevel::Package => "package",
+            SwiftAccessLevel::Internal => "internal",
+            SwiftAccessLevel::FilePrivate => "fileprivate",
+        }
+    }
+
+    /// The access level for a Swift access modifier keyword, such as `"internal"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let access = match name {
+            "public" => SwiftAccessLevel::Public,
+            "package" => SwiftAccessLevel::Package,
+            "internal" => SwiftAccessLevel::Internal,
+            "fileprivate" => SwiftAccessLevel::FilePrivate,
+            _ => return None,
+        };
+
+        Some(access)
+    }
+}
+
+/// Parses the `"internal"` in `swift_access = "internal"`.
+impl Parse for SwiftAccessLevel {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let value: LitStr = input.parse()?;
+
+        SwiftAccessLevel::from_name(&value.value()).ok_or_else(|| {
+            syn::Error::new_spanned(
+                value,
+                r#"Swift access level must be "public", "package", "internal" or "fileprivate"."#,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that we can parse Swift access levels.
+    #[test]
+    fn parses_swift_access_level() {
+        let tests = vec![
+            (r#""public""#, SwiftAccessLevel::Public),
+            (r#""package""#, SwiftAccessLevel::Package),
+            (r#""internal""#, SwiftAccessLevel::Internal),
+            (r#""fileprivate""#, SwiftAccessLevel::FilePrivate),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(syn::parse_str::<SwiftAccessLevel>(input).unwrap(), expected);
+        }
+
+        assert!(syn::parse_str::<SwiftAccessLevel>(r#""private""#).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream};
+use syn::{LitStr, Token};
+
+/// `#[swift_bridge(available(iOS = "15.0", macOS = "12.0"))]`
+///
+/// The platform versions that a bridged type or function is available on.
+#[derive(Clone)]
+pub(crate) struct SwiftAvailability {
+    pub platforms: Vec<PlatformVersion>,
+}
+
+/// `iOS = "15.0"`
+#[derive(Clone)]
+pub(crate) struct PlatformVersion {
+    pub platform: ApplePlatform,
+    pub version: LitStr,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum ApplePlatform {
+    Ios,
+    MacOS,
+    MacCatalyst,
+    TvOS,
+    WatchOS,
+    VisionOS,
+}
+
+impl ApplePlatform {
+    /// The platform's name in a Swift `@available` attribute.
+    pub fn swift_name(&self) -> &'static str {
+        match self {
+            ApplePlatform::Ios => "iOS",
+            ApplePlatform::MacOS => "macOS",
+            ApplePlatform::MacCatalyst => "macCatalyst",
+            ApplePlatform::TvOS => "tvOS",
+            ApplePlatform::WatchOS => "watchOS",
+            ApplePlatform::VisionOS => "visionOS",
+        }
+    }
+
+    /// The platform's name in a clang `availability` attribute.
+    pub fn clang_name(&self) -> &'static str {
+        match self {
+            ApplePlatform::Ios => "ios",
+            ApplePlatform::MacOS => "macos",
+            ApplePlatform::MacCatalyst => "maccatalyst",
+            ApplePlatform::TvOS => "tvos",
+            ApplePlatform::WatchOS => "watchos",
+            ApplePlatform::VisionOS => "visionos",
+        }
+    }
+
+    fn from_swift_name(name: &str) -> Option<Self> {
+        [
+            ApplePlatform::Ios,
+            ApplePlatform::MacOS,
+            ApplePlatform::MacCatalyst,
+            ApplePlatform::TvOS,
+            ApplePlatform::WatchOS,
+            ApplePlatform::VisionOS,
+        ]
+        .into_iter()
+        .find(|platform| platform.swift_name() == name)
+    }
+}
+
+impl SwiftAvailability {
+    /// `@available(iOS 15.0, macOS 12.0, *)`
+    pub fn to_swift_attribute(&self, indentation: &str) -> String {
+        let platforms: Vec<String> = self
+            .platforms
+            .iter()
+            .map(|p| format!("{} {}", p.platform.swift_name(), p.version.value()))
+            .collect();
+
+        format!("{}@available({}, *)\n", indentation, platforms.join(", "))
+    }
+
+    /// ` __attribute__((availability(ios,introduced=15.0)))`
+    pub fn to_c_attributes(&self) -> String {
+        self.platforms
+            .iter()
+            .map(|p| {
+                format!(
+                    " __attribute__((availability({},introduced={})))",
+                    p.platform.clang_name(),
+                    p.version.value()
+                )
+            })
+            .collect()
+    }
+}
+
+/// Parses the `iOS = "15.0", macOS = "12.0"` in `available(iOS = "15.0", macOS = "12.0")`.
+impl Parse for SwiftAvailability {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let platforms =
+            syn::punctuated::Punctuated::<PlatformVersion, Token![,]>::parse_terminated(input)?;
+
+        Ok(SwiftAvailability {
+            platforms: platforms.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for PlatformVersion {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        let platform = ApplePlatform::from_swift_name(&key.to_string()).ok_or_else(|| {
+            syn::Error::new_spanned(
+                &key,
+                format!(
+                    r#"Unrecognized platform "{}". Expected one of iOS, macOS, macCatalyst, tvOS, watchOS or visionOS."#,
+                    key
+                ),
+            )
+        })?;
+
+        input.parse::<Token![=]>()?;
+
+        let version: LitStr = input.parse()?;
+        let is_valid_version = version
+            .value()
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        if !is_valid_version {
+            return Err(syn::Error::new_spanned(
+                &version,
+                r#"Platform versions must look like "15" or "15.0"."#,
+            ));
+        }
+
+        Ok(PlatformVersion { platform, version })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that we can parse platform availability.
+    #[test]
+    fn parses_availability() {
+        let availability: SwiftAvailability =
+            syn::parse_str(r#"iOS = "15.0", macOS = "12""#).unwrap();
+
+        assert_eq!(availability.platforms.len(), 2);
+        assert_eq!(availability.platforms[0].platform, ApplePlatform::Ios);
+        assert_eq!(availability.platforms[1].version.value(), "12");
+        assert_eq!(
+            availability.to_swift_attribute(""),
+            "@available(iOS 15.0, macOS 12, *)\n"
+        );
+        assert_eq!(
+            availability.to_c_attributes(),
+            " __attribute__((availability(ios,introduced=15.0))) __attribute__((availability(macos,introduced=12)))"
+        );
+    }
+
+    /// Verify that we return an error for unknown platforms and invalid versions.
+    #[test]
+    fn error_if_invalid_availability() {
+        assert!(syn::parse_str::<SwiftAvailability>(r#"Android = "15.0""#).is_err());
+        assert!(syn::parse_str::<SwiftAvailability>(r#"iOS = "15.x""#).is_err());
+    }
+}
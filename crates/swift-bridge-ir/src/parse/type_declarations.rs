@@ -2,8 +2,9 @@ use crate::bridged_type::{
     BridgedType, CustomBridgedType, OpaqueForeignType, SharedEnum, SharedStruct, SharedType,
 };
 use crate::parse::parse_extern_mod::OpaqueTypeAllAttributes;
-use crate::parse::HostLang;
-use crate::SWIFT_BRIDGE_PREFIX;
+use crate::parse::{HostLang, SwiftAccessLevel};
+use crate::swift_keywords::rename_swift_keyword;
+use crate::{PRIVATE_TYPE_PREFIX, SWIFT_BRIDGE_PREFIX};
 use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 use std::collections::HashMap;
@@ -13,10 +14,26 @@ use syn::{PatType, Type, TypePath};
 mod generics;
 pub(crate) use self::generics::*;
 
-#[derive(Default)]
 pub(crate) struct TypeDeclarations {
     decls: HashMap<String, TypeDeclaration>,
     order: Vec<String>,
+    /// The module's symbol prefix, used for the types that get generated for the module such as
+    /// the structs that by-value results are passed as.
+    symbol_prefix: String,
+    /// The prefix of the C types that are shared by all bridge modules, such as
+    /// `__private__OptionU8`.
+    private_type_prefix: String,
+}
+
+impl Default for TypeDeclarations {
+    fn default() -> Self {
+        TypeDeclarations {
+            decls: HashMap::new(),
+            order: vec![],
+            symbol_prefix: SWIFT_BRIDGE_PREFIX.to_string(),
+            private_type_prefix: PRIVATE_TYPE_PREFIX.to_string(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -57,8 +74,16 @@ impl TypeDeclaration {
                 host_lang: opaque.host_lang,
                 reference,
                 mutable,
+                boxed: false,
                 has_swift_bridge_copy_annotation: opaque.attributes.copy.is_some(),
                 generics: opaque.generics.clone(),
+                swift_name: opaque
+                    .attributes
+                    .swift_name
+                    .as_ref()
+                    .map(|name| name.value()),
+                is_swift_protocol: opaque.attributes.protocol,
+                symbol_prefix: opaque.symbol_prefix.clone(),
             }),
             _ => None,
         }
@@ -71,6 +96,8 @@ pub(crate) struct OpaqueForeignTypeDeclaration {
     pub host_lang: HostLang,
     pub attributes: OpaqueTypeAllAttributes,
     pub generics: OpaqueRustTypeGenerics,
+    /// The prefix of the module's symbols, such as `__swift_bridge__` in `__swift_bridge__$SomeType$_free`.
+    pub symbol_prefix: String,
 }
 
 impl OpaqueForeignTypeDeclaration {
@@ -90,7 +117,7 @@ impl OpaqueForeignTypeDeclaration {
     pub(crate) fn ffi_repr_name_string(&self) -> String {
         format!(
             "{}${}{}",
-            SWIFT_BRIDGE_PREFIX,
+            self.symbol_prefix,
             self.ty,
             self.generics.dollar_prefixed_generics_string()
         )
@@ -103,7 +130,7 @@ impl OpaqueForeignTypeDeclaration {
     pub(crate) fn free_rust_opaque_type_ffi_name(&self) -> String {
         format!(
             "{}${}{}$_free",
-            SWIFT_BRIDGE_PREFIX,
+            self.symbol_prefix,
             self.to_string(),
             self.generics.dollar_prefixed_generics_string(),
         )
@@ -159,7 +186,7 @@ impl OpaqueForeignTypeDeclaration {
     pub(crate) fn ffi_option_copy_repr_string(&self) -> String {
         format!(
             "{}$Option${}{}",
-            SWIFT_BRIDGE_PREFIX,
+            self.symbol_prefix,
             self.ty,
             self.generics.dollar_prefixed_generics_string()
         )
@@ -170,7 +197,7 @@ impl OpaqueForeignTypeDeclaration {
     pub(crate) fn ffi_copy_repr_string(&self) -> String {
         format!(
             "{}${}{}",
-            SWIFT_BRIDGE_PREFIX,
+            self.symbol_prefix,
             self.ty,
             self.generics.dollar_prefixed_generics_string()
         )
@@ -194,7 +221,7 @@ impl Deref for OpaqueForeignTypeDeclaration {
 impl OpaqueForeignTypeDeclaration {
     // "__swift_bridge__$TypeName$_free"
     pub fn free_swift_class_link_name(&self) -> String {
-        format!("{}${}$_free", SWIFT_BRIDGE_PREFIX, self.ty.to_string())
+        format!("{}${}$_free", self.symbol_prefix, self.ty.to_string())
     }
 
     // "__swift_bridge__TypeName__free"
@@ -205,6 +232,21 @@ impl OpaqueForeignTypeDeclaration {
     pub fn ty_name_ident(&self) -> &Ident {
         &self.ty
     }
+
+    /// The access level of the type's generated Swift class or struct.
+    pub fn swift_access(&self) -> SwiftAccessLevel {
+        self.attributes.swift_access.unwrap_or_default()
+    }
+
+    /// The name of the type on the Swift side.
+    ///
+    /// For `#[swift_bridge(swift_name = "Renderer")] type RendererRs;` this would be "Renderer".
+    pub fn swift_name_string(&self) -> String {
+        match self.attributes.swift_name.as_ref() {
+            Some(swift_name) => swift_name.value(),
+            None => rename_swift_keyword(&self.ty.to_string()),
+        }
+    }
 }
 
 impl TypeDeclarations {
@@ -233,7 +275,7 @@ impl TypeDeclarations {
         let ty = match ty.deref() {
             Type::Reference(reference) => reference.elem.to_token_stream().to_string(),
             Type::Path(path) => path.to_token_stream().to_string(),
-            _ => todo!("Handle other cases"),
+            _ => return None,
         };
         self.get(&ty)
     }
@@ -243,6 +285,30 @@ impl TypeDeclarations {
         self.order.push(type_name);
     }
 
+    pub(crate) fn symbol_prefix(&self) -> &str {
+        &self.symbol_prefix
+    }
+
+    pub(crate) fn set_symbol_prefix(&mut self, prefix: &str) {
+        for decl in self.decls.values_mut() {
+            decl.set_symbol_prefix(prefix);
+        }
+        self.symbol_prefix = prefix.to_string();
+    }
+
+    pub(crate) fn private_type_prefix(&self) -> &str {
+        &self.private_type_prefix
+    }
+
+    /// The name of a shared C type, such as `__private__OptionU8` for `OptionU8`.
+    pub(crate) fn private_type_name(&self, name: &str) -> String {
+        format!("{}{}", self.private_type_prefix, name)
+    }
+
+    pub(crate) fn set_private_type_prefix(&mut self, prefix: &str) {
+        self.private_type_prefix = prefix.to_string();
+    }
+
     pub fn types(&self) -> Vec<&TypeDeclaration> {
         self.order
             .iter()
@@ -252,6 +318,24 @@ impl TypeDeclarations {
 }
 
 impl TypeDeclaration {
+    /// The name that the type was declared with, such as `SomeType` in `type SomeType;`.
+    pub(crate) fn ident(&self) -> &Ident {
+        match self {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(s)) => &s.name,
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(e)) => &e.name,
+            TypeDeclaration::Opaque(o) => &o.ty,
+        }
+    }
+
+    pub(crate) fn set_symbol_prefix(&mut self, prefix: &str) {
+        let symbol_prefix = match self {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(s)) => &mut s.symbol_prefix,
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(e)) => &mut e.symbol_prefix,
+            TypeDeclaration::Opaque(o) => &mut o.symbol_prefix,
+        };
+        *symbol_prefix = prefix.to_string();
+    }
+
     pub(crate) fn as_opaque(&self) -> Option<&OpaqueForeignTypeDeclaration> {
         match self {
             TypeDeclaration::Opaque(o) => Some(o),
@@ -0,0 +1,72 @@
+use crate::bridge_module_attributes::CfgAttr;
+use crate::parse::SwiftAccessLevel;
+use crate::SWIFT_BRIDGE_PREFIX;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Type;
+
+/// A constant declared in a bridge module. Its value comes from the Rust constant with the
+/// same name in the bridge module's parent module.
+///
+/// ```
+/// # const  _: &str = stringify!(
+/// mod ffi {
+///     const MAX_USERS: u32;
+/// }
+///
+/// // Generated Swift
+/// public let MAX_USERS: UInt32 = __swift_bridge__$MAX_USERS
+/// # );
+/// ```
+pub(crate) struct ParsedConst {
+    pub ident: Ident,
+    /// The constant's type. Only primitive types such as `u32`, `f64` and `bool` are supported.
+    pub ty: Type,
+    /// The constant's doc comment.
+    pub doc_comment: Option<String>,
+    /// The access level of the generated Swift constant.
+    pub swift_access: SwiftAccessLevel,
+    /// `#[cfg(feature = "...")]` or `#[cfg(target_os = "...")]` attributes on the constant.
+    pub cfg_attrs: Vec<CfgAttr>,
+    /// The prefix of the module's symbols, such as `__swift_bridge__` in `__swift_bridge__$MAX_USERS`.
+    pub symbol_prefix: String,
+}
+
+impl ParsedConst {
+    /// "__swift_bridge__$MAX_USERS"
+    pub fn link_name(&self) -> String {
+        format!("{}${}", self.symbol_prefix, self.ident)
+    }
+
+    /// `__swift_bridge__MAX_USERS`
+    fn prefixed_ident(&self) -> Ident {
+        Ident::new(
+            &format!("{}{}", SWIFT_BRIDGE_PREFIX, self.ident),
+            self.ident.span(),
+        )
+    }
+
+    /// Export the constant's value from the Rust side.
+    ///
+    /// ```
+    /// # const  _: &str = stringify!(
+    /// #[export_name = "__swift_bridge__$MAX_USERS"]
+    /// #[allow(non_upper_case_globals)]
+    /// pub static __swift_bridge__MAX_USERS: u32 = super::MAX_USERS;
+    /// # );
+    /// ```
+    pub fn to_rust_tokens(&self) -> TokenStream {
+        let link_name = self.link_name();
+        let prefixed_ident = self.prefixed_ident();
+        let ident = &self.ident;
+        let ty = &self.ty;
+        let cfg_attrs = &self.cfg_attrs;
+
+        quote! {
+            #(#cfg_attrs)*
+            #[export_name = #link_name]
+            #[allow(non_upper_case_globals)]
+            pub static #prefixed_ident: #ty = super::#ident;
+        }
+    }
+}
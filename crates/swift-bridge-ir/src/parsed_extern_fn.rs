@@ -1,13 +1,24 @@
+use crate::bridge_module_attributes::{extend_cfg_attrs, CfgAttr, PanicPolicy};
 use crate::bridged_type::boxed_fn::BridgeableBoxedFnOnce;
-use crate::bridged_type::{pat_type_pat_is_self, BridgedType, StdLibType};
-use crate::parse::{HostLang, SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
+use crate::bridged_type::{
+    pat_type_pat_is_self, BridgedType, BuiltInResult, StdLibType, SWIFT_INT128_AVAILABILITY,
+};
+use crate::parse::{
+    HostLang, SharedTypeDeclaration, SwiftAccessLevel, SwiftAvailability, TypeDeclaration,
+    TypeDeclarations,
+};
+use crate::swift_keywords::rename_swift_keyword;
 use crate::SWIFT_BRIDGE_PREFIX;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Group, Ident, Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::ops::Deref;
+use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::{FnArg, ForeignItemFn, Lifetime, Path, ReturnType, Token, Type};
+use syn::{
+    Attribute, Expr, ExprLit, ExprUnary, FnArg, ForeignItemFn, Lifetime, Lit, LitStr, Path,
+    ReturnType, Token, Type, UnOp,
+};
 
 mod to_extern_c_fn;
 mod to_extern_c_param_names_and_types;
@@ -62,6 +73,29 @@ pub(crate) struct ParsedExternFn {
     /// Whether or not this function should be used for the associated type's Swift
     /// `Identifiable` protocol implementation.
     pub is_swift_identifiable: bool,
+    /// `#[swift_bridge(swift_property)]`
+    /// Whether or not this method is the getter or setter of a Swift computed property.
+    pub is_swift_property: bool,
+    /// `#[swift_bridge(main_actor)]`
+    /// Whether or not this `extern "Swift"` function gets called on the main actor.
+    pub is_main_actor: bool,
+    /// `#[swift_bridge(cancellable)]`
+    /// Whether or not cancelling the Swift `Task` that awaits this async function drops its
+    /// future and throws a `CancellationError`.
+    pub is_cancellable: bool,
+    /// `#[swift_bridge(completion_handler)]`
+    /// Whether or not Rust awaits this `extern "Swift"` function's trailing `Box<dyn FnOnce>`
+    /// argument instead of passing it in.
+    pub is_completion_handler: bool,
+    /// `#[swift_bridge(blocking)]`
+    /// Whether or not this synchronous `extern "Rust"` function is exposed to Swift as an async
+    /// function that runs on a `DispatchQueue` thread, so that it doesn't block a thread of Swift's
+    /// cooperative thread pool.
+    pub is_blocking: bool,
+    /// `#[swift_bridge(queue = "main")]`
+    /// The `DispatchQueue` that this `extern "Swift"` function gets dispatched to, where "main"
+    /// is the main queue and anything else is a Swift expression such as "Player.queue".
+    pub queue: Option<syn::LitStr>,
     pub rust_name_override: Option<syn::LitStr>,
     pub swift_name_override: Option<syn::LitStr>,
     /// If true, we call `.into()` on the expression that the function returns before returning it.
@@ -92,6 +126,126 @@ pub(crate) struct ParsedExternFn {
     pub args_into: Option<Vec<Ident>>,
     /// Get one of the associated type's fields
     pub get_field: Option<GetField>,
+    /// Set one of the associated type's fields
+    pub set_field: Option<SetField>,
+    /// Labels to use for the function's arguments on the Swift side.
+    ///
+    /// ```
+    /// # const  _: &str = stringify!(
+    /// #[swift_bridge(argument_labels(user = "for"))]
+    /// fn fetch(user: String);
+    ///
+    /// // Generated Swift
+    /// func fetch(for user: String) { ... }
+    /// # );
+    /// ```
+    pub argument_labels: Option<Vec<ArgumentLabel>>,
+    /// Default values for the function's arguments on the Swift side.
+    /// The Rust function still receives every argument.
+    ///
+    /// ```
+    /// # const  _: &str = stringify!(
+    /// #[swift_bridge(default(limit = 50))]
+    /// fn fetch(limit: u32);
+    ///
+    /// // Generated Swift
+    /// func fetch(limit: UInt32 = 50) { ... }
+    /// # );
+    /// ```
+    pub default_arg_values: Option<Vec<DefaultArgValue>>,
+    /// The function's doc comment.
+    pub doc_comment: Option<String>,
+    /// `#[deprecated]`, `#[deprecated = "..."]` or `#[deprecated(note = "...")]`
+    pub deprecated: Option<Deprecated>,
+    /// `#[swift_bridge(available(iOS = "15.0"))]`
+    pub available: Option<SwiftAvailability>,
+    /// The access level of the generated Swift function.
+    /// Comes from the function's, associated type's or module's `swift_access` attribute.
+    pub swift_access: SwiftAccessLevel,
+    /// `#[cfg(feature = "...")]` or `#[cfg(target_os = "...")]` attributes on the function.
+    /// Methods also inherit the `cfg` attributes of their type. See [`Self::all_cfg_attrs`].
+    pub cfg_attrs: Vec<CfgAttr>,
+    /// The module's `#[swift_bridge(panic = "...")]`, for functions in `extern "Rust"` blocks.
+    pub panic_policy: Option<PanicPolicy>,
+    /// Whether calls to the function get reported using `swift_bridge::call_tracing`.
+    pub call_tracing: bool,
+    /// The prefix of the module's symbols, such as `__swift_bridge__` in
+    /// `__swift_bridge__$some_function`.
+    pub symbol_prefix: String,
+}
+
+/// A `#[deprecated]` attribute on a function.
+pub(crate) struct Deprecated {
+    /// The attribute, so that we can put it on the generated Rust function.
+    pub attribute: Attribute,
+    /// The deprecation message, if there was one.
+    pub note: Option<String>,
+}
+
+/// `some_arg = "label"`
+pub(crate) struct ArgumentLabel {
+    pub arg: Ident,
+    pub label: LitStr,
+}
+
+impl Parse for ArgumentLabel {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let arg = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let label = input.parse()?;
+
+        Ok(ArgumentLabel { arg, label })
+    }
+}
+
+/// `some_arg = 50`
+pub(crate) struct DefaultArgValue {
+    pub arg: Ident,
+    /// The default value, written as a Swift literal.
+    pub swift_value: String,
+}
+
+impl Parse for DefaultArgValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let arg = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: Expr = input.parse()?;
+
+        let swift_value = match &value {
+            Expr::Lit(lit) => lit_to_swift_literal(&lit.lit),
+            Expr::Unary(ExprUnary {
+                op: UnOp::Neg(_),
+                expr,
+                ..
+            }) => match expr.deref() {
+                Expr::Lit(ExprLit {
+                    lit: lit @ (Lit::Int(_) | Lit::Float(_)),
+                    ..
+                }) => lit_to_swift_literal(lit).map(|lit| format!("-{}", lit)),
+                _ => None,
+            },
+            Expr::Path(path) if path.path.is_ident("None") => Some("nil".to_string()),
+            _ => None,
+        };
+        let swift_value = swift_value.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &value,
+                "Default values must be a number or bool literal, or None.",
+            )
+        })?;
+
+        Ok(DefaultArgValue { arg, swift_value })
+    }
+}
+
+/// `50` -> "50", `true` -> "true"
+fn lit_to_swift_literal(lit: &Lit) -> Option<String> {
+    match lit {
+        Lit::Int(int) => Some(int.base10_digits().to_string()),
+        Lit::Float(float) => Some(float.base10_digits().to_string()),
+        Lit::Bool(bool) => Some(bool.value.to_string()),
+        _ => None,
+    }
 }
 
 pub(crate) enum GetField {
@@ -112,6 +266,20 @@ pub struct GetFieldWith {
     pub(crate) path: Path,
 }
 
+pub(crate) enum SetField {
+    Direct(SetFieldDirect),
+    With(SetFieldWith),
+}
+
+pub struct SetFieldDirect {
+    pub(crate) field_name: Ident,
+}
+
+pub struct SetFieldWith {
+    pub(crate) field_name: Ident,
+    pub(crate) path: Path,
+}
+
 #[cfg(test)]
 impl GetField {
     pub(crate) fn unwrap_direct(&self) -> &GetFieldDirect {
@@ -129,11 +297,116 @@ impl GetField {
     }
 }
 
+#[cfg(test)]
+impl SetField {
+    pub(crate) fn unwrap_direct(&self) -> &SetFieldDirect {
+        match self {
+            SetField::Direct(d) => d,
+            _ => panic!(),
+        }
+    }
+
+    pub(crate) fn unwrap_with(&self) -> &SetFieldWith {
+        match self {
+            SetField::With(d) => d,
+            _ => panic!(),
+        }
+    }
+}
+
 impl ParsedExternFn {
     pub fn is_method(&self) -> bool {
         self.func.sig.receiver().is_some()
     }
 
+    /// Whether or not this is a `#[swift_bridge(init)]` that returns an `Option<SomeType>`,
+    /// which becomes a failable `init?`.
+    pub fn is_failable_swift_initializer(&self) -> bool {
+        self.is_swift_initializer && self.return_ty_wrapper_ident().as_deref() == Some("Option")
+    }
+
+    /// Whether or not this is a `#[swift_bridge(init)]` that returns a
+    /// `Result<SomeType, SomeError>`, which becomes a throwing `init() throws`.
+    pub fn is_throwing_swift_initializer(&self) -> bool {
+        self.is_swift_initializer && self.return_ty_wrapper_ident().as_deref() == Some("Result")
+    }
+
+    /// For a function that returns `Option<T>` this is "Option".
+    fn return_ty_wrapper_ident(&self) -> Option<String> {
+        match &self.sig.output {
+            ReturnType::Type(_, ty) => match ty.deref() {
+                Type::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident.to_string()),
+                _ => None,
+            },
+            ReturnType::Default => None,
+        }
+    }
+
+    /// Whether or not this is a `#[swift_bridge(swift_property)]` getter such as
+    /// `fn title(&self) -> String`.
+    pub fn is_swift_property_getter(&self) -> bool {
+        self.is_swift_property
+            && self.self_reference().is_some()
+            && self.self_mutability().is_none()
+            && self.func.sig.inputs.len() == 1
+            && self.sig.asyncness.is_none()
+            && !matches!(self.sig.output, ReturnType::Default)
+    }
+
+    /// For a `#[swift_bridge(swift_property)]` setter such as
+    /// `fn set_title(&mut self, title: String)` this is the name of the getter, "title".
+    pub fn swift_property_setter_name(&self) -> Option<String> {
+        if !self.is_swift_property
+            || self.self_mutability().is_none()
+            || self.func.sig.inputs.len() != 2
+            || self.sig.asyncness.is_some()
+            || !matches!(self.sig.output, ReturnType::Default)
+        {
+            return None;
+        }
+
+        self.sig
+            .ident
+            .to_string()
+            .strip_prefix("set_")
+            .map(|getter| getter.to_string())
+    }
+
+    /// The type of a `#[swift_bridge(swift_property)]` getter's return value or setter's argument,
+    /// such as "String".
+    pub fn swift_property_type_tokens(&self) -> Option<String> {
+        if self.is_swift_property_getter() {
+            match &self.sig.output {
+                ReturnType::Type(_, ty) => Some(ty.to_token_stream().to_string()),
+                ReturnType::Default => None,
+            }
+        } else if self.swift_property_setter_name().is_some() {
+            match self.sig.inputs.iter().nth(1)? {
+                FnArg::Typed(pat_ty) => Some(pat_ty.ty.to_token_stream().to_string()),
+                FnArg::Receiver(_) => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// The name of the type that this function is associated to, if any.
+    pub fn associated_type_name(&self) -> Option<String> {
+        self.associated_type.as_ref().map(|ty| match ty {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                shared_struct.name.to_string()
+            }
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => {
+                shared_enum.name.to_string()
+            }
+            TypeDeclaration::Opaque(opaque) => opaque.ty.to_string(),
+        })
+    }
+
     pub fn self_reference(&self) -> Option<(Token![&], Option<Lifetime>)> {
         match self.func.sig.receiver()? {
             FnArg::Receiver(receiver) => receiver.reference.clone(),
@@ -166,10 +439,16 @@ impl ParsedExternFn {
             if ty.to_string() == "()" {
                 quote! {}
             } else {
-                quote! { -> #ty }
+                // So that a mismatched return type error points at the declared return type.
+                let span = match &sig.output {
+                    ReturnType::Type(_, ty) => ty.span(),
+                    ReturnType::Default => sig.output.span(),
+                };
+                let ty = respan(ty, span);
+                quote_spanned! {span=> -> #ty }
             }
         } else {
-            todo!("Push to ParseErrors")
+            unreachable!("Types that can't be bridged are rejected while parsing")
         }
     }
 
@@ -188,7 +467,7 @@ impl ParsedExternFn {
                 Some(quote! { , #ty })
             }
         } else {
-            todo!("Push to ParseErrors")
+            unreachable!("Types that can't be bridged are rejected while parsing")
         }
     }
 
@@ -198,7 +477,7 @@ impl ParsedExternFn {
         let prefix = if let Some(associated_ty) = self.associated_type.as_ref() {
             match associated_ty {
                 TypeDeclaration::Shared(_) => {
-                    todo!()
+                    unreachable!("Functions associated to shared types are rejected while parsing")
                 }
                 TypeDeclaration::Opaque(associated_ty) => {
                     format!("{}_", associated_ty.ty)
@@ -214,6 +493,107 @@ impl ParsedExternFn {
         )
     }
 
+    /// Use the module's symbol prefix for the function and its associated type.
+    pub(crate) fn set_symbol_prefix(&mut self, prefix: &str) {
+        if let Some(associated_type) = self.associated_type.as_mut() {
+            associated_type.set_symbol_prefix(prefix);
+        }
+        self.symbol_prefix = prefix.to_string();
+    }
+
+    /// The function's `cfg` attributes along with the `cfg` attributes of its associated type.
+    pub fn all_cfg_attrs(&self) -> Vec<CfgAttr> {
+        let mut cfg_attrs = self.cfg_attrs.clone();
+
+        if let Some(ty) = self.associated_type.as_ref().and_then(|ty| ty.as_opaque()) {
+            extend_cfg_attrs(&mut cfg_attrs, &ty.attributes.cfg_attrs);
+        }
+
+        cfg_attrs
+    }
+
+    /// `#[deprecated]` on the generated Rust function, if the bridged function was deprecated.
+    pub fn maybe_deprecated_attribute(&self) -> Option<&Attribute> {
+        self.deprecated
+            .as_ref()
+            .map(|deprecated| &deprecated.attribute)
+    }
+
+    /// `@available(*, deprecated, ...)` on the generated Swift function, if the bridged function
+    /// was deprecated.
+    pub fn maybe_swift_deprecated_attribute(&self, indentation: &str) -> String {
+        match self.deprecated.as_ref() {
+            Some(Deprecated {
+                note: Some(note), ..
+            }) => {
+                let note = note.replace('\\', "\\\\").replace('"', "\\\"");
+                format!(
+                    "{}@available(*, deprecated, message: \"{}\")\n",
+                    indentation, note
+                )
+            }
+            Some(Deprecated { note: None, .. }) => {
+                format!("{}@available(*, deprecated)\n", indentation)
+            }
+            None => "".to_string(),
+        }
+    }
+
+    /// `@available(iOS 15.0, *)` on the generated Swift function, if the function uses the
+    /// `available` attribute, and the availability of `UInt128` and `Int128` if the function uses
+    /// a 128 bit integer.
+    pub fn maybe_swift_available_attribute(
+        &self,
+        indentation: &str,
+        types: &TypeDeclarations,
+    ) -> String {
+        let mut attributes = self
+            .available
+            .as_ref()
+            .map(|available| available.to_swift_attribute(indentation))
+            .unwrap_or_default();
+        if self.uses_128_bit_int(types) {
+            attributes += &format!("{}{}\n", indentation, SWIFT_INT128_AVAILABILITY);
+        }
+
+        attributes
+    }
+
+    /// Whether or not one of the function's arguments or its return type is or contains a `u128`
+    /// or `i128`, which are `UInt128` and `Int128` on the Swift side.
+    pub fn uses_128_bit_int(&self, types: &TypeDeclarations) -> bool {
+        let args = self
+            .func
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|arg| BridgedType::new_with_fn_arg(arg, types));
+        let ret = BridgedType::new_with_return_type(&self.func.sig.output, types);
+
+        args.chain(ret)
+            .any(|ty| ty.contains_128_bit_int_recursive(types))
+    }
+
+    /// The Swift argument label for the argument with the given name, if one was set using
+    /// `#[swift_bridge(argument_labels(...))]`.
+    pub fn swift_argument_label(&self, arg_name: &str) -> Option<String> {
+        self.argument_labels
+            .as_ref()?
+            .iter()
+            .find(|label| label.arg == arg_name)
+            .map(|label| label.label.value())
+    }
+
+    /// The Swift default value for the argument with the given name, if one was set using
+    /// `#[swift_bridge(default(...))]`.
+    pub fn swift_default_arg_value(&self, arg_name: &str) -> Option<&str> {
+        self.default_arg_values
+            .as_ref()?
+            .iter()
+            .find(|default| default.arg == arg_name)
+            .map(|default| default.swift_value.as_str())
+    }
+
     pub fn args_into_contains_arg(&self, arg: &FnArg) -> bool {
         if self.args_into.is_none() {
             return false;
@@ -237,12 +617,12 @@ impl ParsedExternFn {
     // extern Rust:
     // fn foo (&self, arg1: u8, arg2: u32, bar: &SomeType)
     //  becomes..
-    // arg1, arg2, & unsafe { Box::from_raw(bar) }
+    // arg1, arg2, & unsafe { swift_bridge::allocator::take_from_raw(bar) }
     //
     // extern Swift:
     // fn foo (&self, arg1: u8, arg2: u32, &SomeType)
     //  becomes..
-    // self.0, arg1, arg2, & unsafe { Box::from_raw(bar) }
+    // self.0, arg1, arg2, & unsafe { swift_bridge::allocator::take_from_raw(bar) }
     pub fn to_call_rust_args(
         &self,
         swift_bridge_path: &Path,
@@ -292,7 +672,7 @@ impl ParsedExternFn {
                             );
                         };
                     } else {
-                        todo!("Push to ParsedErrors")
+                        unreachable!("Types that can't be bridged are rejected while parsing")
                     }
 
                     args.push(arg);
@@ -323,7 +703,7 @@ impl ParsedExternFn {
                         self.push_self_param(&mut params);
                     } else {
                         let built_in = BridgedType::new_with_type(&pat_ty.ty, types).unwrap();
-                        let ty = built_in.to_c();
+                        let ty = built_in.to_c(types);
 
                         let arg_name = pat.to_token_stream().to_string();
                         params.push(format!("{} {}", ty, arg_name));
@@ -344,7 +724,7 @@ impl ParsedExternFn {
             ReturnType::Default => "void".to_string(),
             ReturnType::Type(_, ty) => {
                 if let Some(ty) = BridgedType::new_with_type(&ty, types) {
-                    ty.to_c()
+                    ty.to_c(types)
                 } else {
                     let ty_string = match ty.deref() {
                         Type::Reference(reference) => reference.elem.to_token_stream().to_string(),
@@ -403,16 +783,10 @@ impl ParsedExternFn {
 
     fn push_self_param(&self, params: &mut Vec<String>) {
         let param = if self.is_copy_method_on_opaque_type() {
+            let associated_type = self.associated_type.as_ref().unwrap().as_opaque().unwrap();
             format!(
                 "struct {}${} this",
-                SWIFT_BRIDGE_PREFIX,
-                &self
-                    .associated_type
-                    .as_ref()
-                    .unwrap()
-                    .as_opaque()
-                    .unwrap()
-                    .ty
+                associated_type.symbol_prefix, associated_type.ty
             )
         } else {
             "void* self".to_string()
@@ -427,27 +801,33 @@ impl ParsedExternFn {
         let host_type = self
             .associated_type
             .as_ref()
-            .map(|h| {
-                match h {
-                    TypeDeclaration::Shared(_) => {
-                        //
-                        todo!()
-                    }
-                    TypeDeclaration::Opaque(h) => {
-                        format!("${}", h.to_string())
-                    }
+            .map(|h| match h {
+                TypeDeclaration::Shared(_) => {
+                    unreachable!("Functions associated to shared types are rejected while parsing")
+                }
+                TypeDeclaration::Opaque(h) => {
+                    format!("${}", h.to_string())
                 }
             })
             .unwrap_or("".to_string());
 
         format!(
             "{}{}${}",
-            SWIFT_BRIDGE_PREFIX,
+            self.symbol_prefix,
             host_type,
             self.func.sig.ident.to_string()
         )
     }
 
+    /// The span of the name of the Rust function that the generated code calls, which is the
+    /// `rust_name` if the function has one.
+    pub(crate) fn rust_fn_name_span(&self) -> Span {
+        match self.rust_name_override.as_ref() {
+            Some(rust_name) => rust_name.span(),
+            None => self.func.sig.ident.span(),
+        }
+    }
+
     pub fn call_boxed_fn_link_name(&self, boxed_fn_idx: usize) -> String {
         format!("{}$param{}", self.link_name(), boxed_fn_idx)
     }
@@ -458,7 +838,12 @@ impl ParsedExternFn {
     /// Generates something like:
     /// void __swift_bridge__$some_function$param0(void* boxed_fn, uint8_t arg);
     /// void __swift_bridge__$some_function$_free$param0(void* boxed_fn);
-    pub fn boxed_fn_to_c_header_fns(&self, idx: usize, boxed_fn: &BridgeableBoxedFnOnce) -> String {
+    pub fn boxed_fn_to_c_header_fns(
+        &self,
+        idx: usize,
+        boxed_fn: &BridgeableBoxedFnOnce,
+        types: &TypeDeclarations,
+    ) -> String {
         let call_boxed_fn_link_name = self.call_boxed_fn_link_name(idx);
         let free_boxed_fn_link_name = self.free_boxed_fn_link_name(idx);
 
@@ -468,11 +853,11 @@ impl ParsedExternFn {
         let maybe_args = if boxed_fn.params.is_empty() {
             "".to_string()
         } else {
-            let args = boxed_fn.params_to_c_types();
+            let args = boxed_fn.params_to_c_types(types);
             format!(", {args}")
         };
 
-        let ret = boxed_fn.ret.to_c();
+        let ret = boxed_fn.ret.to_c(types);
 
         format!(
             r#"
@@ -485,15 +870,12 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
         let host_type_prefix = self
             .associated_type
             .as_ref()
-            .map(|h| {
-                match h {
-                    TypeDeclaration::Shared(_) => {
-                        //
-                        todo!()
-                    }
-                    TypeDeclaration::Opaque(h) => {
-                        format!("{}_", h.to_token_stream().to_string())
-                    }
+            .map(|h| match h {
+                TypeDeclaration::Shared(_) => {
+                    unreachable!("Functions associated to shared types are rejected while parsing")
+                }
+                TypeDeclaration::Opaque(h) => {
+                    format!("{}_", h.to_token_stream().to_string())
                 }
             })
             .unwrap_or_default();
@@ -511,6 +893,55 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
         prefixed_fn_name
     }
 
+    /// The trailing `Box<dyn FnOnce(A, B) -> ()>` argument that Rust awaits when calling a
+    /// `#[swift_bridge(completion_handler)]` function.
+    ///
+    /// Returns `None` if the function isn't an `extern "Swift"` function that returns nothing and
+    /// takes one.
+    pub(crate) fn completion_handler(
+        &self,
+        type_decls: &TypeDeclarations,
+    ) -> Option<BridgeableBoxedFnOnce> {
+        if self.host_lang.is_rust() || !matches!(self.func.sig.output, ReturnType::Default) {
+            return None;
+        }
+
+        let last_arg = self.func.sig.inputs.last()?;
+        match BridgedType::new_with_fn_arg(last_arg, type_decls)? {
+            BridgedType::StdLib(StdLibType::BoxedFnOnce(boxed_fn)) if boxed_fn.ret.is_null() => {
+                Some(boxed_fn)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether or not a `#[swift_bridge(queue = "...")]` function can run after Rust's call to it
+    /// has returned, which is the case for `extern "Swift"` functions that return nothing, only
+    /// take owned arguments and aren't `main_actor`.
+    pub(crate) fn can_be_dispatched_to_queue(&self) -> bool {
+        let borrows_arg = self.func.sig.inputs.iter().any(|arg| match arg {
+            FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => {
+                tokens_contain_reference(pat_ty.ty.to_token_stream())
+            }
+            _ => false,
+        });
+
+        self.host_lang.is_swift()
+            && matches!(self.func.sig.output, ReturnType::Default)
+            && !self.is_main_actor
+            && !borrows_arg
+    }
+
+    /// Whether or not this is a `#[swift_bridge(get(field))]` or `#[swift_bridge(set(field))]`
+    /// method, which only reads or writes a field.
+    ///
+    /// These are often called in hot loops, so we ask both compilers to inline their glue code.
+    /// This lets cross-language LTO turn a Swift property access into a single field access.
+    pub(crate) fn is_trivial_accessor(&self) -> bool {
+        matches!(self.get_field, Some(GetField::Direct(_)))
+            || matches!(self.set_field, Some(SetField::Direct(_)))
+    }
+
     /// Get all of the `Box<dyn Fn(A, B) -> C>` arguments.
     /// We include the arguments position.
     pub fn args_filtered_to_boxed_fns(
@@ -533,6 +964,24 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
             .collect()
     }
 
+    /// Get all of the `Result<T, E>` arguments and return values that get passed using a struct
+    /// of their own, by value or boxed.
+    pub(crate) fn result_structs(&self, type_decls: &TypeDeclarations) -> Vec<BuiltInResult> {
+        self.func
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|arg| BridgedType::new_with_fn_arg(arg, type_decls))
+            .chain(self.return_ty_built_in(type_decls))
+            .filter_map(|ty| match ty {
+                BridgedType::StdLib(StdLibType::Result(result)) if result.has_result_struct() => {
+                    Some(result)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// `let cb1 = __private__RustFnOnceCallback$some_function$param0(ptr: callback); let cb0 = ...`
     pub fn fnonce_callback_initializers(
         &self,
@@ -544,14 +993,18 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
         let mut maybe_space = "";
 
         for (idx, fn_once) in self.args_filtered_to_boxed_fns(types) {
-            let arg_name = self.arg_name_at_idx(idx).unwrap();
+            let arg_name = rename_swift_keyword(&self.arg_name_at_idx(idx).unwrap());
 
             if fn_once.params.is_empty() && fn_once.ret.is_null() {
                 initializers += &format!(
-                "{maybe_space}let cb{idx} = __private__RustFnOnceCallbackNoArgsNoRet(ptr: {arg_name});"
-            );
+                    "{maybe_space}let cb{idx} = {callback_class}(ptr: {arg_name});",
+                    callback_class = types.private_type_name("RustFnOnceCallbackNoArgsNoRet")
+                );
             } else {
-                initializers += &format!("{maybe_space}let cb{idx} = __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx}(ptr: {arg_name});");
+                initializers += &format!(
+                    "{maybe_space}let cb{idx} = {callback_class}{maybe_associated_ty}${fn_name}$param{idx}(ptr: {arg_name});",
+                    callback_class = types.private_type_name("RustFnOnceCallback")
+                );
             }
 
             maybe_space = " ";
@@ -588,7 +1041,7 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
     /// For example:
     /// "<GenericRustString: IntoRustString>"
     pub fn maybe_swift_generics(&self, types: &TypeDeclarations) -> String {
-        let mut maybe_generics = HashSet::new();
+        let mut maybe_generics = BTreeSet::new();
 
         for arg in self.sig.inputs.iter() {
             let bridged_arg = BridgedType::new_with_fn_arg(arg, types);
@@ -640,6 +1093,33 @@ pub(crate) fn fn_arg_is_mutable_reference(fn_arg: &FnArg) -> bool {
     }
 }
 
+/// Whether or not the tokens, such as the tokens for `Option<&SomeType>`, contain a `&`.
+fn tokens_contain_reference(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Punct(punct) => punct.as_char() == '&',
+        TokenTree::Group(group) => tokens_contain_reference(group.stream()),
+        _ => false,
+    })
+}
+
+/// Give every token the span, so that an error about the tokens points at the user's code
+/// instead of at the `#[swift_bridge::bridge]` attribute.
+pub(crate) fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = TokenTree::Group(respanned);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -693,7 +1173,7 @@ mod tests {
         let module = parse_ok(tokens);
         assert_tokens_eq(
             &module.functions[0].to_call_rust_args(&module.swift_bridge_path, &module.types),
-            &quote! {unsafe { *Box::from_raw(arg) }},
+            &quote! {unsafe { swift_bridge::allocator::take_from_raw(arg) }},
         );
     }
 
@@ -714,7 +1194,7 @@ mod tests {
                 arg1: *mut swift_bridge::string::RustString
             ) -> *mut swift_bridge::string::RustString {
                 swift_bridge::string::RustString(super::some_function(
-                    unsafe { Box::from_raw(arg1).0 }
+                    unsafe { swift_bridge::allocator::take_from_raw(arg1).0 }
                 )).box_into_raw()
             }
         };
@@ -1,8 +1,13 @@
+use crate::bridge_module_attributes::PanicPolicy;
 use crate::bridged_type::BridgedType;
 use crate::parse::{HostLang, OpaqueCopy, TypeDeclaration, TypeDeclarations};
-use crate::parsed_extern_fn::{GetField, GetFieldDirect, GetFieldWith, ParsedExternFn};
+use crate::parsed_extern_fn::{
+    respan, GetField, GetFieldDirect, GetFieldWith, ParsedExternFn, SetField, SetFieldDirect,
+    SetFieldWith,
+};
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::Path;
 
 impl ParsedExternFn {
@@ -35,21 +40,98 @@ impl ParsedExternFn {
 
         let ret = self.rust_fn_sig_return_tokens(swift_bridge_path, types);
 
+        let cfg_attrs = self.all_cfg_attrs();
+
         match self.host_lang {
             HostLang::Rust => {
-                let call_fn = self.call_fn_tokens(swift_bridge_path, types);
+                let call_fn = self.catch_panic_tokens(
+                    self.call_fn_tokens(swift_bridge_path, types),
+                    swift_bridge_path,
+                );
+                let call_fn = self.trace_call_tokens(call_fn, swift_bridge_path);
 
                 let maybe_return_ty = self.maybe_async_rust_fn_return_ty(swift_bridge_path, types);
 
                 let is_async = self.sig.asyncness.is_some();
 
+                // The generated function calls the deprecated function, which is expected, so we
+                // don't want it to emit deprecation warnings.
+                let maybe_deprecated = self.maybe_deprecated_attribute().map(|deprecated| {
+                    quote! {
+                        #deprecated
+                        #[allow(deprecated)]
+                    }
+                });
+
+                let maybe_inline = if self.is_trivial_accessor() {
+                    Some(quote! { #[inline(always)] })
+                } else {
+                    None
+                };
+
                 if !is_async {
                     quote! {
+                        #(#cfg_attrs)*
                         #[export_name = #link_name]
+                        #maybe_deprecated
+                        #maybe_inline
                         pub extern "C" fn #prefixed_fn_name ( #params ) #ret {
                             #call_fn
                         }
                     }
+                } else if self.is_cancellable {
+                    let on_output = if maybe_return_ty.is_some() {
+                        let return_ty = self.return_ty_built_in(types).unwrap();
+                        let val = return_ty.convert_rust_expression_to_ffi_type(
+                            &quote! {output},
+                            swift_bridge_path,
+                            types,
+                        );
+
+                        quote! {
+                            Some(output) => {
+                                let val = #val;
+                                (callback)(callback_wrapper, val)
+                            }
+                        }
+                    } else {
+                        quote! {
+                            Some(()) => (callback)(callback_wrapper),
+                        }
+                    };
+
+                    // Swift cancels the task using `cancellation`. The future is then dropped and
+                    // `on_cancel` tells Swift to throw a `CancellationError`.
+                    quote! {
+                        #(#cfg_attrs)*
+                        #[export_name = #link_name]
+                        #maybe_deprecated
+                        pub extern "C" fn #prefixed_fn_name (
+                            callback_wrapper: *mut std::ffi::c_void,
+                            callback: extern "C" fn(*mut std::ffi::c_void #maybe_return_ty) -> (),
+                            on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+                            cancellation: *const swift_bridge::async_support::AsyncCancellation,
+                            #params
+                        ) {
+                            let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                            let cancellation = unsafe {
+                                swift_bridge::async_support::AsyncCancellation::from_swift(cancellation)
+                            };
+                            let fut = #call_fn;
+                            let task = async move {
+                                let output = cancellation.run(fut).await;
+
+                                let callback_wrapper = callback_wrapper;
+                                let callback_wrapper = callback_wrapper.0;
+
+                                match output {
+                                    #on_output
+                                    None => (on_cancel)(callback_wrapper),
+                                }
+                            };
+                            swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                        }
+                    }
                 } else {
                     let (await_fut, call_callback) = if maybe_return_ty.is_some() {
                         let return_ty = self.return_ty_built_in(types).unwrap();
@@ -79,7 +161,9 @@ impl ParsedExternFn {
                     };
 
                     quote! {
+                        #(#cfg_attrs)*
                         #[export_name = #link_name]
+                        #maybe_deprecated
                         pub extern "C" fn #prefixed_fn_name (
                             callback_wrapper: *mut std::ffi::c_void,
                             callback: extern "C" fn(*mut std::ffi::c_void #maybe_return_ty) -> (),
@@ -102,6 +186,7 @@ impl ParsedExternFn {
             }
             HostLang::Swift => {
                 quote! {
+                    #(#cfg_attrs)*
                     #[link_name = #link_name]
                     fn #prefixed_fn_name ( #params ) #ret;
                 }
@@ -112,25 +197,21 @@ impl ParsedExternFn {
     fn call_fn_tokens(&self, swift_bridge_path: &Path, types: &TypeDeclarations) -> TokenStream {
         let sig = &self.func.sig;
         let fn_name = if let Some(fn_name) = self.rust_name_override.as_ref() {
-            let span = fn_name.span();
-            let fn_name = Ident::new(&fn_name.value(), span);
-
-            quote! { #fn_name }
+            Ident::new(&fn_name.value(), self.rust_fn_name_span())
         } else {
-            let fn_name = &sig.ident;
-            quote! {
-                #fn_name
-            }
+            sig.ident.clone()
         };
 
         let call_args = self.to_call_rust_args(swift_bridge_path, types);
 
-        let call_fn = quote! {
+        // Spanned to the declaration so that rustc points there when the function that we call
+        // doesn't match it, for example when it takes a different number of arguments.
+        let call_fn = quote_spanned! {self.rust_fn_name_span()=>
             #fn_name ( #call_args )
         };
 
         let mut call_fn = if self.is_method() {
-            self.call_method_tokens(&call_fn)
+            self.call_method_tokens(&call_fn, &call_args, swift_bridge_path)
         } else {
             self.call_function_tokens(&call_fn)
         };
@@ -142,7 +223,7 @@ impl ParsedExternFn {
         }
 
         if let Some(return_with) = self.return_with.as_ref() {
-            call_fn = quote! {
+            call_fn = quote_spanned! {return_with.span()=>
                 super:: #return_with ( #call_fn )
             }
         }
@@ -156,8 +237,65 @@ impl ParsedExternFn {
         call_fn
     }
 
+    /// Wrap the call to the Rust function so that a panic doesn't unwind into Swift, for modules
+    /// that use `#[swift_bridge(panic = "...")]`.
+    ///
+    /// For async functions this only covers creating the future, since the future is polled by the
+    /// async runtime and not from Swift.
+    fn catch_panic_tokens(&self, call_fn: TokenStream, swift_bridge_path: &Path) -> TokenStream {
+        match self.panic_policy {
+            None => call_fn,
+            Some(PanicPolicy::Abort) => quote! {
+                #swift_bridge_path::panic_support::catch_panic_or_abort(|| { #call_fn })
+            },
+            // The Swift side checks for the panic before looking at the returned `Result`.
+            Some(PanicPolicy::Throw) if self.is_throwing_swift_initializer() => quote! {
+                #swift_bridge_path::panic_support::catch_panic_or_throw(
+                    || { #call_fn },
+                    #swift_bridge_path::result::ResultPtrAndPtr {
+                        is_ok: false,
+                        ok_or_err: std::ptr::null_mut()
+                    }
+                )
+            },
+            Some(PanicPolicy::FatalError | PanicPolicy::Throw) => quote! {
+                #swift_bridge_path::panic_support::catch_panic_or_fatal_error(|| { #call_fn })
+            },
+        }
+    }
+
+    /// Wrap the call to the Rust function so that it gets reported using
+    /// `swift_bridge::call_tracing`, for the `call-tracing` feature.
+    ///
+    /// For async functions this wraps the future, so that the time spent awaiting it is reported.
+    fn trace_call_tokens(&self, call_fn: TokenStream, swift_bridge_path: &Path) -> TokenStream {
+        if !self.call_tracing {
+            return call_fn;
+        }
+
+        let function = match self.associated_type.as_ref() {
+            Some(TypeDeclaration::Opaque(ty)) => format!("{}::{}", ty.ty, self.func.sig.ident),
+            _ => self.func.sig.ident.to_string(),
+        };
+
+        if self.sig.asyncness.is_some() {
+            quote! {
+                #swift_bridge_path::call_tracing::trace_future(#function, #call_fn)
+            }
+        } else {
+            quote! {
+                #swift_bridge_path::call_tracing::trace_call(#function, || { #call_fn })
+            }
+        }
+    }
+
     /// Generate tokens for calling a method.
-    fn call_method_tokens(&self, call_fn: &TokenStream) -> TokenStream {
+    fn call_method_tokens(
+        &self,
+        call_fn: &TokenStream,
+        call_args: &TokenStream,
+        swift_bridge_path: &Path,
+    ) -> TokenStream {
         let this = if self.is_copy_method_on_opaque_type() {
             quote! {
                 this.into_rust_repr()
@@ -172,11 +310,28 @@ impl ParsedExternFn {
                 }
             } else {
                 quote! {
-                    ( * unsafe { Box::from_raw(this) } )
+                    ( unsafe { #swift_bridge_path::allocator::take_from_raw(this) } )
                 }
             }
         };
 
+        if let Some(set_field) = &self.set_field {
+            return match set_field {
+                SetField::Direct(set_direct) => {
+                    let SetFieldDirect { field_name } = set_direct;
+                    quote! {
+                        #this . #field_name = #call_args
+                    }
+                }
+                SetField::With(set_with) => {
+                    let SetFieldWith { field_name, path } = set_with;
+                    quote_spanned! {path.span()=>
+                        #this . #field_name = super::#path ( #call_args )
+                    }
+                }
+            };
+        }
+
         match &self.get_field {
             Some(GetField::Direct(get_direct)) => {
                 let GetFieldDirect {
@@ -195,12 +350,16 @@ impl ParsedExternFn {
                     field_name,
                     path,
                 } = get_with;
-                quote! {
+                quote_spanned! {path.span()=>
                    super::#path ( #maybe_ref #maybe_mut #this . #field_name )
                 }
             }
             None => {
-                quote! {
+                // The whole method call needs the declaration's span, otherwise rustc points
+                // errors about it at the macro.
+                let span = self.rust_fn_name_span();
+                let this = respan(this, span);
+                quote_spanned! {span=>
                         #this.#call_fn
                 }
             }
@@ -222,7 +381,7 @@ impl ParsedExternFn {
             }
         });
 
-        quote! {
+        quote_spanned! {self.rust_fn_name_span()=>
             super:: #maybe_associated_type #call_fn
         }
     }
@@ -25,7 +25,7 @@ impl ParsedExternFn {
                                 quote! { this: #opaque_ty_ffi_repr }
                             }
                             TypeDeclaration::Shared(_) => {
-                                todo!("Methods on shared types are not yet supported.")
+                                unreachable!("Functions associated to shared types are rejected while parsing")
                             }
                         };
 
@@ -46,7 +46,7 @@ impl ParsedExternFn {
                             params.push(quote! { #pat: #ty});
                             continue;
                         } else {
-                            todo!("Push to ParsedErrors")
+                            unreachable!("Types that can't be bridged are rejected while parsing")
                         }
                     };
 
@@ -75,7 +75,9 @@ impl ParsedExternFn {
                         .unwrap()
                     {
                         TypeDeclaration::Shared(_) => {
-                            todo!("Support methods on shared types.")
+                            unreachable!(
+                                "Functions associated to shared types are rejected while parsing"
+                            )
                         }
                         TypeDeclaration::Opaque(opaque) => opaque.ffi_repr_type_tokens(),
                     };
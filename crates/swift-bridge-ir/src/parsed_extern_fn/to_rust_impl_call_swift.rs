@@ -1,7 +1,7 @@
 use crate::bridged_type::{pat_type_pat_is_self, BridgedType};
 use crate::parse::{SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
 use crate::parsed_extern_fn::ParsedExternFn;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned};
 use std::ops::Deref;
 use syn::spanned::Spanned;
@@ -55,14 +55,60 @@ impl ParsedExternFn {
             }
         };
 
-        let params = self.params_with_explicit_self_types_removed(types);
+        let mut params = self.params_with_explicit_self_types_removed(types);
         let call_args = self.to_call_rust_args(swift_bridge_path, types);
         let linked_fn_name = self.extern_swift_linked_fn_new();
 
+        let maybe_deprecated = self.maybe_deprecated_attribute();
+        let cfg_attrs = self.all_cfg_attrs();
+
         let mut inner = quote! {
             unsafe { #linked_fn_name(#call_args) }
         };
 
+        let completion_handler = if self.is_completion_handler {
+            self.completion_handler(types)
+        } else {
+            None
+        };
+        if let Some(completion_handler) = completion_handler {
+            // Rust creates the completion handler, so it isn't one of the function's params.
+            params.pop();
+
+            let completion_handler_ty = completion_handler.to_rust_type_path();
+            let completion_handler_name =
+                self.arg_name_tokens_at_idx(sig.inputs.len() - 1).unwrap();
+
+            let arg_names: Vec<Ident> = (0..completion_handler.params.len())
+                .map(|idx| Ident::new(&format!("arg{}", idx), Span::call_site()))
+                .collect();
+            let output_tys: Vec<TokenStream> = completion_handler
+                .params
+                .iter()
+                .map(|param| param.to_rust_type_path())
+                .collect();
+            let (output, output_ty) = if arg_names.len() == 1 {
+                (quote! { #(#arg_names)* }, quote! { #(#output_tys)* })
+            } else {
+                (quote! { (#(#arg_names),*) }, quote! { (#(#output_tys),*) })
+            };
+
+            // The Swift function is called right away, and the returned future resolves once
+            // Swift calls the completion handler.
+            return quote! {
+                #(#cfg_attrs)*
+                #maybe_deprecated
+                pub fn #fn_name(#(#params),*) -> #swift_bridge_path::async_support::SwiftCompletion<#output_ty> {
+                    #swift_bridge_path::async_support::SwiftCompletion::new(|#completion_handler_name| {
+                        let #completion_handler_name: #completion_handler_ty = Box::new(move |#(#arg_names),*| {
+                            #completion_handler_name.send(#output)
+                        });
+                        #inner
+                    })
+                }
+            };
+        }
+
         if let Some(built_in) = BridgedType::new_with_return_type(&sig.output, types) {
             inner = built_in.convert_ffi_expression_to_rust_type(
                 &inner,
@@ -71,11 +117,13 @@ impl ParsedExternFn {
                 types,
             );
         } else {
-            todo!("Push to ParsedErrors")
+            unreachable!("Types that can't be bridged are rejected while parsing")
         }
 
         quote! {
-            pub fn #fn_name(#params) #ret {
+            #(#cfg_attrs)*
+            #maybe_deprecated
+            pub fn #fn_name(#(#params),*) #ret {
                 #inner
             }
         }
@@ -83,11 +131,11 @@ impl ParsedExternFn {
 
     /// #\[export_name = "__swift_bridge__$SomeType$some_method$param1"]
     /// pub extern "C" fn SomeType_some_method_param1(boxed_fn: *mut dyn FnOnce(u8) -> (), arg0: u8) {
-    ///     unsafe { Box::from_raw(boxed_fn) }(arg0)
+    ///     unsafe { swift_bridge::allocator::take_from_raw(boxed_fn) }(arg0)
     /// }
     /// #\[export_name = "__swift_bridge__$SomeType$some_method$_free$param1"]
     /// pub extern "C" fn free_SomeType_some_method_param1(boxed_fn: *mut dyn FnOnce(u8) -> ()) {
-    ///     unsafe { Box::from_raw(boxed_fn) }
+    ///     unsafe { swift_bridge::allocator::take_from_raw(boxed_fn) }
     /// }
     pub fn callbacks_support(
         &self,
@@ -97,6 +145,8 @@ impl ParsedExternFn {
         let sig = &self.func.sig;
         let fn_name = &sig.ident;
 
+        let cfg_attrs = self.all_cfg_attrs();
+
         let mut boxed_fn_support = vec![];
         for (idx, boxed_fn) in self.args_filtered_to_boxed_fns(types) {
             if boxed_fn.does_not_have_params_or_return() {
@@ -145,7 +195,7 @@ impl ParsedExternFn {
             let arg_name = Ident::new(&format!("{}_{}", fn_name, arg_name), arg_name.span());
 
             let call_boxed_fn = quote! {
-                unsafe { Box::from_raw(#arg_name)(#(#call_args),*) }
+                unsafe { #swift_bridge_path::allocator::take_from_raw(#arg_name)(#(#call_args),*) }
             };
             let call_boxed_fn = boxed_fn.ret.convert_rust_expression_to_ffi_type(
                 &call_boxed_fn,
@@ -153,6 +203,7 @@ impl ParsedExternFn {
                 types,
             );
             let call_boxed_fn = quote! {
+                #(#cfg_attrs)*
                 #[export_name = #call_boxed_fn_link_name]
                 pub extern "C" fn #boxed_fn_name(#arg_name: #boxed_fn_ffi_repr #maybe_params) #maybe_ret {
                     #call_boxed_fn
@@ -160,9 +211,10 @@ impl ParsedExternFn {
             };
 
             let free_boxed_fn = quote! {
+                #(#cfg_attrs)*
                 #[export_name = #free_boxed_fn_link_name]
                 pub extern "C" fn #free_boxed_fn_name(#arg_name: #boxed_fn_ffi_repr) {
-                    let _ = unsafe { Box::from_raw(#arg_name) };
+                    let _ = unsafe { #swift_bridge_path::allocator::take_from_raw(#arg_name) };
                 }
             };
 
@@ -180,9 +232,11 @@ impl ParsedExternFn {
     // `self: Foo` becomes `self`,
     // `self: &Foo` -> `&self`,
     // `self: &mut Foo` -> `&mut self`
-    fn params_with_explicit_self_types_removed(&self, types: &TypeDeclarations) -> TokenStream {
-        let params = self
-            .sig
+    fn params_with_explicit_self_types_removed(
+        &self,
+        types: &TypeDeclarations,
+    ) -> Vec<TokenStream> {
+        self.sig
             .inputs
             .iter()
             .map(|fn_arg| {
@@ -228,11 +282,7 @@ impl ParsedExternFn {
                     }
                 }
             })
-            .collect::<Vec<_>>();
-
-        quote! {
-            #(#params),*
-        }
+            .collect()
     }
 }
 
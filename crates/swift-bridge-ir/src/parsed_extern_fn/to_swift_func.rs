@@ -1,6 +1,9 @@
 use crate::bridged_type::{pat_type_pat_is_self, BridgedType, TypePosition};
-use crate::parse::TypeDeclarations;
+use crate::parse::{HostLang, TypeDeclarations};
 use crate::parsed_extern_fn::ParsedExternFn;
+use crate::swift_keywords::{
+    escape_swift_argument_label, escape_swift_keyword, rename_swift_keyword,
+};
 use quote::ToTokens;
 use std::ops::Deref;
 use syn::{FnArg, Path, ReturnType, Type};
@@ -36,19 +39,89 @@ impl ParsedExternFn {
                     let ty = if let Some(built_in) = BridgedType::new_with_type(&pat_ty.ty, types) {
                         built_in.to_swift_type(TypePosition::FnArg(self.host_lang, arg_idx), types)
                     } else {
-                        todo!("Push to ParsedErrors")
+                        unreachable!("Types that can't be bridged are rejected while parsing")
                     };
 
-                    format!("{}: {}", arg_name, ty)
+                    let label = match self.swift_argument_label(&arg_name) {
+                        Some(label) if self.host_lang.is_rust() => label,
+                        _ => "_".to_string(),
+                    };
+
+                    let maybe_default = match self.swift_default_arg_value(&arg_name) {
+                        Some(default) if self.host_lang.is_rust() => format!(" = {}", default),
+                        _ => "".to_string(),
+                    };
+
+                    format!(
+                        "{} {}: {}{}",
+                        escape_swift_argument_label(&label),
+                        rename_swift_keyword(&arg_name),
+                        ty,
+                        maybe_default
+                    )
                 }
             };
 
-            params.push(format!("_ {}", param))
+            params.push(param)
         }
 
         params.join(", ")
     }
 
+    /// The requirement that a `#[swift_bridge(protocol)]` type's Swift protocol declares for this
+    /// extern "Swift" method.
+    ///
+    /// `fn on_progress(&self, percent: u8) -> bool` becomes
+    /// `func on_progress(percent: UInt8) -> Bool`.
+    pub fn to_swift_protocol_requirement(&self, types: &TypeDeclarations) -> String {
+        let mut params = vec![];
+
+        for arg in self.func.sig.inputs.iter() {
+            let pat_ty = match arg {
+                FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => pat_ty,
+                _ => continue,
+            };
+
+            let arg_name = pat_ty.pat.to_token_stream().to_string();
+            let ty = BridgedType::new_with_type(&pat_ty.ty, types)
+                .unwrap()
+                .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+            let param = match self.swift_argument_label(&arg_name) {
+                Some(label) => format!(
+                    "{} {}: {}",
+                    escape_swift_argument_label(&label),
+                    rename_swift_keyword(&arg_name),
+                    ty
+                ),
+                None => format!("{}: {}", escape_swift_argument_label(&arg_name), ty),
+            };
+            params.push(param);
+        }
+
+        let ret = match &self.func.sig.output {
+            ReturnType::Default => "".to_string(),
+            ReturnType::Type(_, ty) => format!(
+                " -> {}",
+                BridgedType::new_with_type(ty, types)
+                    .unwrap()
+                    .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+            ),
+        };
+
+        let fn_name = match self.swift_name_override.as_ref() {
+            Some(swift_name) => swift_name.value(),
+            None => self.func.sig.ident.to_string(),
+        };
+
+        format!(
+            "func {}({}){}",
+            escape_swift_keyword(&fn_name),
+            params.join(", "),
+            ret
+        )
+    }
+
     // fn foo (&self, arg1: u8, arg2: u32)
     //  might become (depending on whether we're including the receiver and/or the var name)
     //  - arg1, arg2
@@ -85,9 +158,8 @@ impl ParsedExternFn {
                         continue;
                     }
 
-                    let pat = &pat_ty.pat;
-                    let arg = pat.to_token_stream().to_string();
-                    let arg_name = arg.clone();
+                    let arg_name = pat_ty.pat.to_token_stream().to_string();
+                    let arg = rename_swift_keyword(&arg_name);
 
                     let arg =
                         if let Some(bridged_ty) = BridgedType::new_with_type(&pat_ty.ty, types) {
@@ -104,11 +176,17 @@ impl ParsedExternFn {
                                 )
                             }
                         } else {
-                            todo!("Push to ParsedErrors")
+                            unreachable!("Types that can't be bridged are rejected while parsing")
                         };
 
                     let arg = if include_var_name {
-                        format!("{}: {}", arg_name, arg)
+                        match self.swift_argument_label(&arg_name) {
+                            Some(label) if label == "_" => arg,
+                            Some(label) => {
+                                format!("{}: {}", escape_swift_argument_label(&label), arg)
+                            }
+                            None => format!("{}: {}", escape_swift_argument_label(&arg_name), arg),
+                        }
                     } else {
                         arg
                     };
@@ -131,7 +209,7 @@ impl ParsedExternFn {
                         built_in.to_swift_type(TypePosition::FnReturn(self.host_lang,), types)
                     )
                 } else {
-                    todo!("Push ParsedErrors")
+                    unreachable!("Types that can't be bridged are rejected while parsing")
                 }
             }
         }
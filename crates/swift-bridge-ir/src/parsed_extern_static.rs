@@ -0,0 +1,303 @@
+use crate::bridge_module_attributes::CfgAttr;
+use crate::bridged_type::BridgedType;
+use crate::parse::{SwiftAccessLevel, TypeDeclarations};
+use crate::SWIFT_BRIDGE_PREFIX;
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Path, Type};
+
+/// A static declared in an `extern "Rust"` block.
+///
+/// ```
+/// # const  _: &str = stringify!(
+/// extern "Rust" {
+///     type Registry;
+///     static SHARED: Registry;
+/// }
+///
+/// // Generated Swift
+/// public let SHARED: RegistryRef = RegistryRef(ptr: __swift_bridge__$SHARED())
+/// # );
+/// ```
+///
+/// Statics of a `std::sync::atomic` type such as `AtomicU32` get one function per atomic operation
+/// instead. See [`AtomicOp`].
+pub(crate) struct ParsedExternStatic {
+    pub ident: Ident,
+    /// The static's type. Either an opaque Rust type, a primitive such as `u32` or `bool` or an
+    /// atomic such as `AtomicU32`.
+    pub ty: Type,
+    /// The static's doc comment.
+    pub doc_comment: Option<String>,
+    /// The access level of the generated Swift property.
+    pub swift_access: SwiftAccessLevel,
+    /// `#[cfg(feature = "...")]` or `#[cfg(target_os = "...")]` attributes on the static.
+    pub cfg_attrs: Vec<CfgAttr>,
+    /// `#[swift_bridge(associated_to = SomeType)]`
+    /// Expose the static as a static property on this type instead of as a global.
+    pub associated_to: Option<Ident>,
+    /// The prefix of the module's symbols, such as `__swift_bridge__` in `__swift_bridge__$SHARED`.
+    pub symbol_prefix: String,
+}
+
+impl ParsedExternStatic {
+    /// "__swift_bridge__$SHARED"
+    pub fn link_name(&self) -> String {
+        format!("{}${}", self.symbol_prefix, self.ident)
+    }
+
+    /// `__swift_bridge__SHARED`
+    fn prefixed_ident(&self) -> Ident {
+        Ident::new(
+            &format!("{}{}", SWIFT_BRIDGE_PREFIX, self.ident),
+            self.ident.span(),
+        )
+    }
+
+    /// "__swift_bridge__$COUNT$fetch_add"
+    pub fn atomic_op_link_name(&self, op: AtomicOp) -> String {
+        format!("{}${}", self.link_name(), op.rust_name())
+    }
+
+    /// The type of the value of an atomic static, such as `u32` for an `AtomicU32`, or `None` if
+    /// the static isn't an atomic.
+    pub fn atomic_value_type(&self) -> Option<Type> {
+        let path = match &self.ty {
+            Type::Path(path) if path.qself.is_none() => path,
+            _ => return None,
+        };
+        let last = path.path.segments.last()?;
+        if !last.arguments.is_empty() {
+            return None;
+        }
+
+        let value_ty = match last.ident.to_string().as_str() {
+            "AtomicBool" => "bool",
+            "AtomicU8" => "u8",
+            "AtomicU16" => "u16",
+            "AtomicU32" => "u32",
+            "AtomicU64" => "u64",
+            "AtomicUsize" => "usize",
+            "AtomicI8" => "i8",
+            "AtomicI16" => "i16",
+            "AtomicI32" => "i32",
+            "AtomicI64" => "i64",
+            "AtomicIsize" => "isize",
+            _ => return None,
+        };
+        Some(syn::parse_str(value_ty).unwrap())
+    }
+
+    /// The operations that an atomic static exposes.
+    /// `AtomicBool` can't be added to or subtracted from.
+    pub fn atomic_ops(&self) -> Vec<AtomicOp> {
+        let is_bool = self
+            .atomic_value_type()
+            .map(|ty| ty.to_token_stream().to_string() == "bool")
+            .unwrap_or(false);
+
+        if is_bool {
+            vec![AtomicOp::Load, AtomicOp::Store, AtomicOp::Swap]
+        } else {
+            vec![
+                AtomicOp::Load,
+                AtomicOp::Store,
+                AtomicOp::Swap,
+                AtomicOp::FetchAdd,
+                AtomicOp::FetchSub,
+            ]
+        }
+    }
+
+    /// The type that gets passed over FFI.
+    ///
+    /// Opaque Rust types are exposed as a `&'static SomeType`, since the static is never dropped.
+    /// Primitives are exposed by value.
+    pub fn bridged_type(&self, types: &TypeDeclarations) -> BridgedType {
+        let ty = BridgedType::new_with_type(&self.ty, types).unwrap();
+        if ty.is_primitive() {
+            return ty;
+        }
+
+        let ty = &self.ty;
+        BridgedType::new_with_type(&syn::parse_quote! { &'static #ty }, types).unwrap()
+    }
+
+    /// Export a function that returns the static.
+    ///
+    /// Lazily initialized statics such as `std::sync::LazyLock<T>` get initialized on the
+    /// first call, since we dereference them into a `&'static T`.
+    ///
+    /// ```
+    /// # const  _: &str = stringify!(
+    /// #[export_name = "__swift_bridge__$SHARED"]
+    /// pub extern "C" fn __swift_bridge__SHARED() -> *const super::Registry {
+    ///     let value: &'static super::Registry = &super::SHARED;
+    ///     value as *const super::Registry
+    /// }
+    /// # );
+    /// ```
+    pub fn to_rust_tokens(
+        &self,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        if let Some(value_ty) = self.atomic_value_type() {
+            return self.to_atomic_rust_tokens(&value_ty);
+        }
+
+        let link_name = self.link_name();
+        let prefixed_ident = self.prefixed_ident();
+        let ident = &self.ident;
+        let cfg_attrs = &self.cfg_attrs;
+
+        let ty = BridgedType::new_with_type(&self.ty, types).unwrap();
+        let rust_ty = ty.to_rust_type_path();
+
+        let bridged_ty = self.bridged_type(types);
+        let ffi_ty = bridged_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+        let value = if ty.is_primitive() {
+            quote! { *value }
+        } else {
+            quote! { value }
+        };
+        let value =
+            bridged_ty.convert_rust_expression_to_ffi_type(&value, swift_bridge_path, types);
+
+        quote! {
+            #(#cfg_attrs)*
+            #[export_name = #link_name]
+            pub extern "C" fn #prefixed_ident() -> #ffi_ty {
+                let value: &'static #rust_ty = &super::#ident;
+                #value
+            }
+        }
+    }
+
+    /// Export a function for each of an atomic static's operations, so that Swift can use the
+    /// atomic without calling into the user's code.
+    ///
+    /// ```
+    /// # const  _: &str = stringify!(
+    /// #[export_name = "__swift_bridge__$COUNT$fetch_add"]
+    /// pub extern "C" fn __swift_bridge__COUNT_fetch_add(value: u32) -> u32 {
+    ///     let atomic: &'static std::sync::atomic::AtomicU32 = &super::COUNT;
+    ///     atomic.fetch_add(value, std::sync::atomic::Ordering::AcqRel)
+    /// }
+    /// # );
+    /// ```
+    fn to_atomic_rust_tokens(&self, value_ty: &Type) -> TokenStream {
+        let ident = &self.ident;
+        let cfg_attrs = &self.cfg_attrs;
+        // The bridge module doesn't see the `use` statements of the module that it's declared in,
+        // so we don't use the path that the user wrote.
+        let atomic_ty = match &self.ty {
+            Type::Path(path) => &path.path.segments.last().unwrap().ident,
+            _ => unreachable!("Only path types can be atomics."),
+        };
+
+        let fns = self.atomic_ops().into_iter().map(|op| {
+            let link_name = self.atomic_op_link_name(op);
+            let prefixed_ident = Ident::new(
+                &format!("{}_{}", self.prefixed_ident(), op.rust_name()),
+                self.ident.span(),
+            );
+            let op_name = Ident::new(op.rust_name(), self.ident.span());
+            let ordering = Ident::new(op.ordering(), self.ident.span());
+            let ordering = quote! { std::sync::atomic::Ordering::#ordering };
+
+            let (params, args) = if op.takes_value() {
+                (quote! { value: #value_ty }, quote! { value, #ordering })
+            } else {
+                (quote! {}, quote! { #ordering })
+            };
+            let ret = if op.returns_value() {
+                quote! { -> #value_ty }
+            } else {
+                quote! {}
+            };
+
+            quote! {
+                #(#cfg_attrs)*
+                #[export_name = #link_name]
+                pub extern "C" fn #prefixed_ident(#params) #ret {
+                    let atomic: &'static std::sync::atomic::#atomic_ty = &super::#ident;
+                    atomic.#op_name(#args)
+                }
+            }
+        });
+
+        quote! {
+            #(#fns)*
+        }
+    }
+}
+
+/// An operation on an atomic static.
+///
+/// Loads use `Acquire` and stores use `Release`, so that a value that is stored before a flag is
+/// set is visible to a thread that sees the flag. Read-modify-write operations use `AcqRel`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum AtomicOp {
+    Load,
+    Store,
+    Swap,
+    FetchAdd,
+    FetchSub,
+}
+
+impl AtomicOp {
+    /// The name of the Rust method, such as `fetch_add`.
+    pub fn rust_name(&self) -> &'static str {
+        match self {
+            AtomicOp::Load => "load",
+            AtomicOp::Store => "store",
+            AtomicOp::Swap => "swap",
+            AtomicOp::FetchAdd => "fetch_add",
+            AtomicOp::FetchSub => "fetch_sub",
+        }
+    }
+
+    /// The name of the generated Swift function, such as `fetchAdd`.
+    pub fn swift_name(&self) -> &'static str {
+        match self {
+            AtomicOp::Load => "load",
+            AtomicOp::Store => "store",
+            AtomicOp::Swap => "swap",
+            AtomicOp::FetchAdd => "fetchAdd",
+            AtomicOp::FetchSub => "fetchSub",
+        }
+    }
+
+    /// The `std::sync::atomic::Ordering` variant that the operation uses.
+    fn ordering(&self) -> &'static str {
+        match self {
+            AtomicOp::Load => "Acquire",
+            AtomicOp::Store => "Release",
+            AtomicOp::Swap | AtomicOp::FetchAdd | AtomicOp::FetchSub => "AcqRel",
+        }
+    }
+
+    pub fn takes_value(&self) -> bool {
+        !matches!(self, AtomicOp::Load)
+    }
+
+    pub fn returns_value(&self) -> bool {
+        !matches!(self, AtomicOp::Store)
+    }
+
+    /// A doc comment for the generated Swift function.
+    pub fn swift_doc_comment(&self) -> &'static str {
+        match self {
+            AtomicOp::Load => "Atomically loads the value.",
+            AtomicOp::Store => "Atomically stores the value.",
+            AtomicOp::Swap => "Atomically stores the value, returning the previous value.",
+            AtomicOp::FetchAdd => {
+                "Atomically adds to the value, wrapping around on overflow, and returns the previous value."
+            }
+            AtomicOp::FetchSub => {
+                "Atomically subtracts from the value, wrapping around on overflow, and returns the previous value."
+            }
+        }
+    }
+}
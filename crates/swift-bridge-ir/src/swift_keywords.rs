@@ -0,0 +1,126 @@
+//! Swift's reserved words can't be used as identifiers without escaping them, so a bridged
+//! `fn init()`, an argument named `class` or a type named `Protocol` needs to be escaped in the
+//! generated Swift.
+
+/// Swift keywords that can't be used as identifiers, or that mean something else when used as a
+/// member name, such as `SomeEnum.Type`.
+const SWIFT_KEYWORDS: &[&str] = &[
+    // Used in declarations.
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "fileprivate",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "operator",
+    "precedencegroup",
+    "private",
+    "protocol",
+    "public",
+    "rethrows",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    // Used in statements.
+    "break",
+    "case",
+    "catch",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "switch",
+    "throw",
+    "where",
+    "while",
+    // Used in expressions and types.
+    "Any",
+    "as",
+    "false",
+    "is",
+    "nil",
+    "self",
+    "Self",
+    "super",
+    "throws",
+    "true",
+    "try",
+    // Reserved in particular contexts.
+    "Protocol",
+    "Type",
+];
+
+/// Whether or not the identifier is a reserved word in Swift.
+pub(crate) fn is_swift_keyword(ident: &str) -> bool {
+    SWIFT_KEYWORDS.contains(&ident)
+}
+
+/// Whether or not the name can be used as a Swift identifier, such as `someFunction`.
+///
+/// Keywords count as valid, since we escape them.
+pub(crate) fn is_valid_swift_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first == '_' || first.is_alphabetic() => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c == '_' || c.is_alphanumeric())
+}
+
+/// Escape the identifier with backticks if it is a Swift keyword.
+///
+/// `class` -> "`class`", `foo` -> "foo"
+///
+/// Used for the names of functions, properties, struct fields and enum cases, which the user
+/// refers to by name from their Swift code.
+pub(crate) fn escape_swift_keyword(ident: &str) -> String {
+    if is_swift_keyword(ident) {
+        format!("`{}`", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+/// Escape an argument label with backticks if it needs to be escaped.
+///
+/// Swift allows any keyword other than `inout`, `var` and `let` to be used as an argument label,
+/// so `fn fetch(for: u8)` keeps its natural `fetch(for: 5)` label.
+pub(crate) fn escape_swift_argument_label(label: &str) -> String {
+    if matches!(label, "inout" | "var" | "let") {
+        format!("`{}`", label)
+    } else {
+        label.to_string()
+    }
+}
+
+/// Rename the identifier if it is a Swift keyword, by adding a trailing underscore.
+///
+/// `class` -> "class_", `foo` -> "foo"
+///
+/// Used for function parameter names, which we combine with other identifiers such as
+/// `{param}AsRustStr`, and for type names, which we combine into other type names such as
+/// `{type}Ref`. Neither of these can be escaped using backticks.
+pub(crate) fn rename_swift_keyword(ident: &str) -> String {
+    if is_swift_keyword(ident) {
+        format!("{}_", ident)
+    } else {
+        ident.to_string()
+    }
+}
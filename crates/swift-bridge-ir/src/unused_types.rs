@@ -0,0 +1,206 @@
+//! Opaque types that a bridge module declares but never uses.
+//!
+//! Every opaque type gets a Swift class and an FFI function that frees it, even when none of the
+//! module's functions can hand one to the other language, so an unused `type Foo;` only makes
+//! the library bigger.
+
+use crate::parse::{SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
+use crate::parsed_extern_static::ParsedExternStatic;
+use crate::{ParsedExternFn, SwiftBridgeModule};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{quote_spanned, ToTokens};
+use std::collections::HashSet;
+
+/// The opaque types that none of the module's functions, statics, shared types or other type
+/// declarations refer to.
+///
+/// Generic types, `already_declared` types and `Codable` types, which Swift can create by
+/// decoding them, are never reported.
+pub(crate) fn find_unused_types(
+    types: &TypeDeclarations,
+    functions: &[ParsedExternFn],
+    statics: &[ParsedExternStatic],
+) -> Vec<Ident> {
+    let mut used = HashSet::new();
+
+    for function in functions {
+        collect_idents(function.func.sig.to_token_stream(), &mut used);
+        if let Some(ty) = function.associated_type.as_ref() {
+            used.insert(ty.ident().to_string());
+        }
+    }
+
+    for parsed_static in statics {
+        collect_idents(parsed_static.ty.to_token_stream(), &mut used);
+        if let Some(associated_to) = parsed_static.associated_to.as_ref() {
+            used.insert(associated_to.to_string());
+        }
+    }
+
+    for ty in types.types() {
+        match ty {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                for field in shared_struct.fields.normalized_fields() {
+                    collect_idents(field.ty.to_token_stream(), &mut used);
+                }
+            }
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => {
+                for variant in shared_enum.variants.iter() {
+                    for field in variant.fields.normalized_fields() {
+                        collect_idents(field.ty.to_token_stream(), &mut used);
+                    }
+                }
+            }
+            TypeDeclaration::Opaque(opaque) => {
+                if let Some(publisher) = opaque.attributes.publisher.as_ref() {
+                    collect_idents(publisher.to_token_stream(), &mut used);
+                }
+                if let Some(async_stream) = opaque.attributes.async_stream.as_ref() {
+                    collect_idents(async_stream.to_token_stream(), &mut used);
+                }
+                for generic in opaque.generics.generics.iter() {
+                    used.insert(generic.ident.to_string());
+                }
+            }
+        }
+    }
+
+    types
+        .types()
+        .into_iter()
+        .filter_map(|ty| ty.as_opaque())
+        .filter(|opaque| {
+            !opaque.attributes.already_declared
+                && !opaque.attributes.declare_generic
+                && opaque.generics.generics.is_empty()
+                && !opaque.attributes.codable
+                && !used.contains(&opaque.ty.to_string())
+        })
+        .map(|opaque| opaque.ty.clone())
+        .collect()
+}
+
+fn collect_idents(tokens: TokenStream, idents: &mut HashSet<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+impl SwiftBridgeModule {
+    /// Generate a compiler warning for each of the module's unused opaque types.
+    ///
+    /// Procedural macros can't emit warnings on stable Rust, so we use a deprecated constant
+    /// that is spanned to the type's declaration.
+    pub fn unused_type_warning_tokens(&self) -> TokenStream {
+        let module_cfg_attrs = &self.cfg_attrs;
+
+        self.unused_types
+            .iter()
+            .map(|ty| {
+                let cfg_attrs = self
+                    .types
+                    .get(&ty.to_string())
+                    .and_then(|ty| ty.as_opaque())
+                    .map(|ty| ty.attributes.cfg_attrs.clone())
+                    .unwrap_or_default();
+                let note = format!(
+                    r#"`{}` is declared in the bridge module but none of the module's functions use it, so it only adds an unused Swift class to your library. Remove it, or use `#[swift_bridge(unused_types = "allow")]` on the bridge module."#,
+                    ty
+                );
+
+                quote_spanned! {ty.span()=>
+                    #(#module_cfg_attrs)*
+                    #(#cfg_attrs)*
+                    const _: () = {
+                        #[deprecated(note = #note)]
+                        #[allow(non_upper_case_globals)]
+                        const unused_bridge_type: () = ();
+                        unused_bridge_type
+                    };
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ParseError;
+    use crate::test_utils::{parse_errors, parse_ok};
+    use quote::quote;
+
+    /// Verify that we report the opaque types that nothing in the module uses.
+    #[test]
+    fn unused_types() {
+        let module = parse_ok(quote! {
+            mod foo {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SharedStruct {
+                    field: InStructField,
+                }
+
+                extern "Rust" {
+                    type Unused;
+                    type InSignature;
+                    type InGeneric;
+                    type WithMethod;
+                    type InStructField;
+                    #[swift_bridge(already_declared)]
+                    type AlreadyDeclared;
+                    #[swift_bridge(Codable)]
+                    type Decodable;
+
+                    fn a(arg: Option<Vec<InSignature>>);
+                    fn b(self: &WithMethod);
+                }
+
+                extern "Swift" {
+                    type UnusedSwiftType;
+
+                    fn c(arg: Option<InGeneric>);
+                }
+            }
+        });
+
+        let unused: Vec<String> = module
+            .unused_types
+            .iter()
+            .map(|ty| ty.to_string())
+            .collect();
+        assert_eq!(unused, vec!["Unused", "UnusedSwiftType"]);
+    }
+
+    /// Verify that the `unused_types` module attribute turns the warning off or into an error.
+    #[test]
+    fn unused_types_attribute() {
+        let module = parse_ok(quote! {
+            #[swift_bridge(unused_types = "allow")]
+            mod foo {
+                extern "Rust" {
+                    type Unused;
+                }
+            }
+        });
+        assert!(module.unused_types.is_empty());
+
+        let errors = parse_errors(quote! {
+            #[swift_bridge(unused_types = "deny")]
+            mod foo {
+                extern "Rust" {
+                    type Unused;
+                }
+            }
+        });
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::UnusedType { ty } => assert_eq!(ty, "Unused"),
+            _ => panic!(),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use quote::quote;
-use swift_bridge_ir::{SwiftBridgeModule, SwiftBridgeModuleAttr, SwiftBridgeModuleAttrs};
-use syn::parse_macro_input;
+use swift_bridge_ir::{
+    SwiftBridgeConfig, SwiftBridgeModule, SwiftBridgeModuleAttr, SwiftBridgeModuleAttrs,
+    CONFIG_FILE_NAME,
+};
+use syn::{parse_macro_input, ItemMod};
 
 #[proc_macro_attribute]
 pub fn bridge(
@@ -8,7 +11,21 @@ pub fn bridge(
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     let args = parse_macro_input!(args as SwiftBridgeModuleAttrs);
-    let mut module = parse_macro_input!(input as SwiftBridgeModule);
+    let original = input.clone();
+    let mut module = match syn::parse::<SwiftBridgeModule>(input) {
+        Ok(module) => module,
+        Err(err) => {
+            let err = err.to_compile_error();
+            let fallback = syn::parse::<ItemMod>(original)
+                .map(ide_fallback_module)
+                .unwrap_or_default();
+            return quote! {
+                #err
+                #fallback
+            }
+            .into();
+        }
+    };
 
     for arg in args.attributes {
         match arg {
@@ -18,12 +35,81 @@ pub fn bridge(
         }
     }
 
+    #[cfg(feature = "call-tracing")]
+    module.set_call_tracing();
+
+    // Cargo sets this when compiling the crate that the module is in.
+    let mut track_config = quote! {};
+    if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
+        let config = match SwiftBridgeConfig::load(&manifest_dir) {
+            Ok(config) => config,
+            Err(e) => {
+                return syn::Error::new(proc_macro2::Span::call_site(), e)
+                    .to_compile_error()
+                    .into()
+            }
+        };
+
+        if let Some(config) = config {
+            if let Some(prefix) = config.symbol_prefix.as_ref() {
+                module.set_configured_symbol_prefix(prefix);
+            }
+
+            // Recompile the crate when the config changes, since the symbol prefix may have
+            // changed.
+            let config_path = std::path::Path::new(&manifest_dir).join(CONFIG_FILE_NAME);
+            let config_path = config_path.to_str().unwrap();
+            track_config = quote! {
+                const _: &[u8] = include_bytes!(#config_path);
+            };
+        }
+    }
+
+    let ide_stubs = module.ide_stub_tokens();
+    let unused_type_warnings = module.unused_type_warning_tokens();
+
     let tokens = quote! {
         #module
+        #ide_stubs
+        #unused_type_warnings
+        #track_config
     };
     tokens.into()
 }
 
+/// The module as the user wrote it, with its items only visible to rust-analyzer.
+///
+/// We use this when the module fails to parse, for example while the user is in the middle of
+/// typing a type name, so that completions keep working inside of the module.
+fn ide_fallback_module(module: ItemMod) -> proc_macro2::TokenStream {
+    let ItemMod {
+        attrs,
+        vis,
+        ident,
+        content,
+        ..
+    } = module;
+
+    // `#[swift_bridge(...)]` attributes would be an error outside of the macro.
+    let attrs = attrs
+        .into_iter()
+        .filter(|attr| attr.path.is_ident("cfg") || attr.path.is_ident("doc"));
+    let items = content.map(|(_, items)| items).unwrap_or_default();
+
+    // The `allow` needs to be on an outer item, otherwise rustc warns about the `rust_analyzer`
+    // cfg in the user's crate.
+    quote! {
+        #(#attrs)*
+        #[allow(unknown_lints, unexpected_cfgs)]
+        #vis mod #ident {
+            #(
+                #[cfg(rust_analyzer)]
+                #items
+            )*
+        }
+    }
+}
+
 #[cfg(test)]
 mod ui_tests {
     #[test]
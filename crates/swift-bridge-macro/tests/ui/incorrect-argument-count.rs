@@ -0,0 +1,24 @@
+//! # To Run
+//! cargo test -p swift-bridge-macro -- ui trybuild=incorrect-argument-count.rs
+
+// We declare functions that take a different number of arguments than the real implementation
+// below, and confirm that the errors point at the declarations instead of at the macro.
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type SomeType;
+
+        fn some_function(arg: u8);
+
+        fn some_method(&self, arg: u8);
+    }
+}
+
+fn some_function() {}
+
+pub struct SomeType;
+impl SomeType {
+    fn some_method(&self) {}
+}
+
+fn main() {}
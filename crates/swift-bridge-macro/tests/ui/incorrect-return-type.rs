@@ -0,0 +1,31 @@
+//! # To Run
+//! cargo test -p swift-bridge-macro -- ui trybuild=incorrect-return-type.rs
+
+// We declare functions that return a type that does not match that of the real implementation
+// below, and confirm that the errors point at the declarations instead of at the macro.
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type SomeType;
+
+        #[swift_bridge(rust_name = "some_function")]
+        fn fn1() -> u8;
+        #[swift_bridge(rust_name = "some_function")]
+        fn fn2() -> String;
+
+        fn some_method(&self) -> u32;
+    }
+}
+
+fn some_function() -> u16 {
+    0
+}
+
+pub struct SomeType;
+impl SomeType {
+    fn some_method(&self) -> bool {
+        true
+    }
+}
+
+fn main() {}
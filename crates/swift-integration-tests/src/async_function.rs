@@ -1,3 +1,6 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 #[swift_bridge::bridge]
 mod ffi {
     struct AsyncRustFnReturnStruct;
@@ -8,6 +11,18 @@ mod ffi {
         async fn rust_async_reflect_string(string: String) -> String;
         async fn rust_async_return_struct() -> AsyncRustFnReturnStruct;
 
+        #[swift_bridge(cancellable)]
+        async fn rust_async_cancellable_reflect_u8(arg: u8) -> u8;
+        #[swift_bridge(cancellable)]
+        async fn rust_async_cancellable_pending();
+        fn rust_async_cancellable_pending_was_dropped() -> bool;
+
+        async fn rust_async_awaits_swift_completion_handler(arg: u8) -> u8;
+    }
+
+    extern "Swift" {
+        #[swift_bridge(completion_handler)]
+        fn swift_reflect_u8_with_completion_handler(arg: u8, completion: Box<dyn FnOnce(u8)>);
     }
 
     extern "Rust" {
@@ -17,6 +32,14 @@ mod ffi {
         fn new() -> TestRustAsyncSelf;
         async fn reflect_u16(&self, arg: u16) -> u16;
     }
+
+    extern "Rust" {
+        type TestRustAsyncInit;
+
+        #[swift_bridge(init)]
+        async fn new(value: u16) -> TestRustAsyncInit;
+        fn value(&self) -> u16;
+    }
 }
 
 async fn rust_async_return_null() {}
@@ -33,6 +56,38 @@ async fn rust_async_return_struct() -> ffi::AsyncRustFnReturnStruct {
     ffi::AsyncRustFnReturnStruct
 }
 
+async fn rust_async_cancellable_reflect_u8(arg: u8) -> u8 {
+    arg
+}
+
+static PENDING_FUTURE_DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct SetDroppedOnDrop;
+impl Drop for SetDroppedOnDrop {
+    fn drop(&mut self) {
+        PENDING_FUTURE_DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Never completes, so that Swift has to cancel it.
+// Not an `async fn`, so that the future is marked as dropped even if it gets cancelled before it
+// is ever polled.
+fn rust_async_cancellable_pending() -> impl Future<Output = ()> {
+    let set_dropped = SetDroppedOnDrop;
+    async move {
+        let _set_dropped = set_dropped;
+        std::future::pending::<()>().await
+    }
+}
+
+fn rust_async_cancellable_pending_was_dropped() -> bool {
+    PENDING_FUTURE_DROPPED.load(Ordering::SeqCst)
+}
+
+async fn rust_async_awaits_swift_completion_handler(arg: u8) -> u8 {
+    ffi::swift_reflect_u8_with_completion_handler(arg).await
+}
+
 pub struct TestRustAsyncSelf;
 
 impl TestRustAsyncSelf {
@@ -44,3 +99,17 @@ impl TestRustAsyncSelf {
         arg
     }
 }
+
+pub struct TestRustAsyncInit {
+    value: u16,
+}
+
+impl TestRustAsyncInit {
+    async fn new(value: u16) -> Self {
+        TestRustAsyncInit { value }
+    }
+
+    fn value(&self) -> u16 {
+        self.value
+    }
+}
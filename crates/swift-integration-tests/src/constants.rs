@@ -0,0 +1,10 @@
+#[swift_bridge::bridge]
+mod ffi {
+    const MAX_USERS: u32;
+    const DEFAULT_SCALE: f64;
+    const IS_PRODUCTION: bool;
+}
+
+const MAX_USERS: u32 = 5;
+const DEFAULT_SCALE: f64 = 1.5;
+const IS_PRODUCTION: bool = false;
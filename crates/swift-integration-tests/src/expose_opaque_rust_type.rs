@@ -23,6 +23,26 @@ mod ffi {
 
         fn get_stack_mut(&mut self) -> &mut ARustStack;
     }
+
+    extern "Rust" {
+        fn make_boxed_rust_stack(val: u8) -> Box<ARustStack>;
+        fn maybe_make_boxed_rust_stack(val: Option<u8>) -> Option<Box<ARustStack>>;
+        fn boxed_rust_stack_len(stack: Box<ARustStack>) -> usize;
+    }
+}
+
+fn make_boxed_rust_stack(val: u8) -> Box<ARustStack> {
+    let mut stack = Box::new(ARustStack::new());
+    stack.push(val);
+    stack
+}
+
+fn maybe_make_boxed_rust_stack(val: Option<u8>) -> Option<Box<ARustStack>> {
+    val.map(make_boxed_rust_stack)
+}
+
+fn boxed_rust_stack_len(stack: Box<ARustStack>) -> usize {
+    stack.len()
 }
 
 pub struct StackWrapper(ARustStack);
@@ -1,8 +1,12 @@
 mod args_into;
+mod blocking;
+mod deprecated;
 mod get;
 mod get_with;
 mod identifiable;
+mod init;
 mod return_into;
 mod return_with;
 mod rust_name;
+mod set;
 mod swift_name;
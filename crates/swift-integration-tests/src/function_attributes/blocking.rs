@@ -0,0 +1,31 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type BlockingTestSummer;
+
+        #[swift_bridge(init)]
+        fn new() -> BlockingTestSummer;
+
+        #[swift_bridge(blocking)]
+        fn sum_to(&self, n: u64) -> u64;
+
+        #[swift_bridge(blocking)]
+        fn blocking_sum_to(n: u64) -> u64;
+    }
+}
+
+pub struct BlockingTestSummer;
+
+impl BlockingTestSummer {
+    fn new() -> Self {
+        BlockingTestSummer
+    }
+
+    fn sum_to(&self, n: u64) -> u64 {
+        blocking_sum_to(n)
+    }
+}
+
+fn blocking_sum_to(n: u64) -> u64 {
+    (1..=n).sum()
+}
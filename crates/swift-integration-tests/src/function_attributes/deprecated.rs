@@ -0,0 +1,15 @@
+//! Verify that bridging a deprecated function compiles without deprecation warnings in the
+//! generated code.
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[deprecated(note = "use rust_fn_not_deprecated")]
+        fn rust_fn_deprecated() -> u8;
+    }
+}
+
+#[deprecated(note = "use rust_fn_not_deprecated")]
+fn rust_fn_deprecated() -> u8 {
+    1
+}
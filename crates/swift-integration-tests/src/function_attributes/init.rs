@@ -0,0 +1,63 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/initializer_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type InitTestOpaqueRustType;
+        #[swift_bridge(Error)]
+        type InitTestError;
+
+        #[swift_bridge(init)]
+        fn new_if_positive(val: i32) -> Option<InitTestOpaqueRustType>;
+
+        #[swift_bridge(init)]
+        fn new_if_even(val: u32) -> Result<InitTestOpaqueRustType, InitTestError>;
+
+        fn val(self: &InitTestOpaqueRustType) -> u32;
+        fn message(self: &InitTestError) -> String;
+    }
+}
+
+pub struct InitTestOpaqueRustType {
+    val: u32,
+}
+
+impl InitTestOpaqueRustType {
+    fn new_if_positive(val: i32) -> Option<Self> {
+        if val > 0 {
+            Some(Self { val: val as u32 })
+        } else {
+            None
+        }
+    }
+
+    fn new_if_even(val: u32) -> Result<Self, InitTestError> {
+        if val % 2 == 0 {
+            Ok(Self { val })
+        } else {
+            Err(InitTestError {
+                message: format!("{} is odd", val),
+            })
+        }
+    }
+
+    fn val(&self) -> u32 {
+        self.val
+    }
+}
+
+pub struct InitTestError {
+    message: String,
+}
+
+impl InitTestError {
+    fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl std::fmt::Display for InitTestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
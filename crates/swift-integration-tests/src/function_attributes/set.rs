@@ -0,0 +1,41 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type SomeTypeSet;
+
+        #[swift_bridge(init)]
+        fn new() -> SomeTypeSet;
+
+        #[swift_bridge(get(my_u8))]
+        fn my_u8(&self) -> u8;
+
+        // Sets self.my_u8 = value
+        #[swift_bridge(set(my_u8))]
+        fn set_my_u8(&mut self, value: u8);
+
+        #[swift_bridge(get(my_i16))]
+        fn my_i16(&self) -> i16;
+
+        // Sets self.my_i16 = u8_to_i16(value)
+        #[swift_bridge(set_with(my_i16 = u8_to_i16))]
+        fn set_my_i16(&mut self, value: u8);
+    }
+}
+
+pub struct SomeTypeSet {
+    my_u8: u8,
+    my_i16: i16,
+}
+
+impl SomeTypeSet {
+    fn new() -> SomeTypeSet {
+        SomeTypeSet {
+            my_u8: 0,
+            my_i16: 0,
+        }
+    }
+}
+
+fn u8_to_i16(num: u8) -> i16 {
+    num as i16
+}
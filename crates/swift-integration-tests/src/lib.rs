@@ -4,17 +4,22 @@ mod import_opaque_swift_class;
 mod async_function;
 mod boxed_functions;
 mod conditional_compilation;
+mod constants;
 mod generics;
 mod option;
+mod panic;
 mod pointer;
 mod primitive;
 mod result;
 mod rust_function_uses_opaque_swift_type;
 mod shared_types;
 mod slice;
+mod statics;
 mod string;
 mod swift_function_uses_opaque_rust_type;
 mod swift_function_uses_opaque_swift_type;
+mod swift_name_prefix;
+mod symbol_prefix;
 mod vec;
 
 mod enum_attributes;
@@ -1,4 +1,17 @@
+mod actor;
 mod already_declared;
+mod async_stream;
+mod codable;
+mod comparable;
 mod copy;
+mod custom_string_convertible;
 mod equatable;
 mod hashable;
+mod identifiable;
+mod lock;
+mod objc;
+mod observable_object;
+mod open;
+mod protocol;
+mod publisher;
+mod sendable;
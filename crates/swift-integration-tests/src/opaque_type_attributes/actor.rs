@@ -0,0 +1,28 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/opaque_swift_type_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Swift" {
+        #[swift_bridge(actor)]
+        type ASwiftActor;
+
+        #[swift_bridge(init)]
+        fn new() -> ASwiftActor;
+
+        fn increment(&self, amount: u32);
+        fn count_value(&self) -> u32;
+    }
+
+    extern "Rust" {
+        fn run_swift_actor_tests();
+    }
+}
+
+fn run_swift_actor_tests() {
+    let actor = ffi::ASwiftActor::new();
+
+    actor.increment(5);
+    actor.increment(10);
+
+    assert_eq!(actor.count_value(), 15);
+}
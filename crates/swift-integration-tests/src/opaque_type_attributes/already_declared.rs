@@ -4,7 +4,9 @@
 //! If the Xcode project is able to compile then we know that our attribute works,
 //! because otherwise we would get build time errors that the class was defined twice.
 
+// The types are only used by `ffi2`.
 #[swift_bridge::bridge]
+#[swift_bridge(unused_types = "allow")]
 mod ffi1 {
     extern "Rust" {
         type AlreadyDeclaredTypeTest;
@@ -0,0 +1,53 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/async_stream_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(AsyncStream(u32))]
+        type AsyncStreamTestValues;
+    }
+
+    extern "Rust" {
+        type AsyncStreamTestSource;
+
+        #[swift_bridge(init)]
+        fn new() -> AsyncStreamTestSource;
+
+        fn values(&mut self) -> AsyncStreamTestValues;
+        fn send(&self, value: u32) -> bool;
+        fn close(&mut self);
+    }
+}
+
+pub type AsyncStreamTestValues = swift_bridge::async_stream::AsyncStream<u32>;
+
+pub struct AsyncStreamTestSource {
+    sender: Option<tokio::sync::mpsc::Sender<u32>>,
+    receiver: Option<tokio::sync::mpsc::Receiver<u32>>,
+}
+
+impl AsyncStreamTestSource {
+    fn new() -> Self {
+        let (sender, receiver) = tokio::sync::mpsc::channel(16);
+        AsyncStreamTestSource {
+            sender: Some(sender),
+            receiver: Some(receiver),
+        }
+    }
+
+    fn values(&mut self) -> AsyncStreamTestValues {
+        self.receiver.take().unwrap().into()
+    }
+
+    /// Returns false once the Swift stream has been dropped.
+    fn send(&self, value: u32) -> bool {
+        match self.sender.as_ref() {
+            Some(sender) => sender.try_send(value).is_ok(),
+            None => false,
+        }
+    }
+
+    fn close(&mut self) {
+        self.sender = None;
+    }
+}
@@ -0,0 +1,33 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Codable)]
+        type RustCodableType;
+
+        #[swift_bridge(init)]
+        fn new(name: String, count: u32) -> RustCodableType;
+
+        fn name(&self) -> String;
+        fn count(&self) -> u32;
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct RustCodableType {
+    name: String,
+    count: u32,
+}
+
+impl RustCodableType {
+    fn new(name: String, count: u32) -> Self {
+        RustCodableType { name, count }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn count(&self) -> u32 {
+        self.count
+    }
+}
@@ -0,0 +1,36 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Comparable, Equatable)]
+        type RustComparableType;
+
+        #[swift_bridge(init)]
+        fn new(val: i32) -> RustComparableType;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Copy(4), Comparable, Equatable)]
+        type RustCopyComparableType;
+
+        #[swift_bridge(init)]
+        fn new(val: i32) -> RustCopyComparableType;
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub struct RustComparableType(i32);
+
+impl RustComparableType {
+    fn new(val: i32) -> Self {
+        RustComparableType(val)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RustCopyComparableType(i32);
+
+impl RustCopyComparableType {
+    fn new(val: i32) -> Self {
+        RustCopyComparableType(val)
+    }
+}
@@ -0,0 +1,54 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(CustomStringConvertible, CustomDebugStringConvertible)]
+        type RustDescribedType;
+
+        #[swift_bridge(init)]
+        fn new(x: i32, y: i32) -> RustDescribedType;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Copy(8), CustomStringConvertible, CustomDebugStringConvertible)]
+        type RustCopyDescribedType;
+
+        #[swift_bridge(init)]
+        fn new(x: i32, y: i32) -> RustCopyDescribedType;
+    }
+}
+
+#[derive(Debug)]
+pub struct RustDescribedType {
+    x: i32,
+    y: i32,
+}
+
+impl RustDescribedType {
+    fn new(x: i32, y: i32) -> Self {
+        RustDescribedType { x, y }
+    }
+}
+
+impl std::fmt::Display for RustDescribedType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct RustCopyDescribedType {
+    x: i32,
+    y: i32,
+}
+
+impl RustCopyDescribedType {
+    fn new(x: i32, y: i32) -> Self {
+        RustCopyDescribedType { x, y }
+    }
+}
+
+impl std::fmt::Display for RustCopyDescribedType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
@@ -7,6 +7,14 @@ mod ffi {
         #[swift_bridge(init)]
         fn new(num: isize) -> RustHashableType;
     }
+
+    extern "Rust" {
+        #[swift_bridge(Copy(8), Hashable, Equatable)]
+        type RustCopyHashableType;
+
+        #[swift_bridge(init)]
+        fn new(num: isize) -> RustCopyHashableType;
+    }
 }
 
 #[derive(Hash, PartialEq)]
@@ -17,3 +25,12 @@ impl RustHashableType {
         RustHashableType(num)
     }
 }
+
+#[derive(Copy, Clone, Hash, PartialEq)]
+pub struct RustCopyHashableType(isize);
+
+impl RustCopyHashableType {
+    fn new(num: isize) -> Self {
+        RustCopyHashableType(num)
+    }
+}
@@ -0,0 +1,51 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Identifiable = "user_id")]
+        type RustIdentifiableType;
+
+        #[swift_bridge(init)]
+        fn new(user_id: u64, name: String) -> RustIdentifiableType;
+        fn user_id(&self) -> u64;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Identifiable = "name")]
+        type RustIdentifiableByNameType;
+
+        #[swift_bridge(init)]
+        fn new(name: String) -> RustIdentifiableByNameType;
+        #[swift_bridge(swift_property)]
+        fn name(&self) -> String;
+    }
+}
+
+pub struct RustIdentifiableType {
+    user_id: u64,
+    #[allow(unused)]
+    name: String,
+}
+
+impl RustIdentifiableType {
+    fn new(user_id: u64, name: String) -> Self {
+        RustIdentifiableType { user_id, name }
+    }
+
+    fn user_id(&self) -> u64 {
+        self.user_id
+    }
+}
+
+pub struct RustIdentifiableByNameType {
+    name: String,
+}
+
+impl RustIdentifiableByNameType {
+    fn new(name: String) -> Self {
+        RustIdentifiableByNameType { name }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
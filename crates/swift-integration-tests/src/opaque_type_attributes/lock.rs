@@ -0,0 +1,59 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/lock_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type LockTestCounter;
+
+        fn count(&self) -> u32;
+        fn increment(&mut self);
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Mutex(LockTestCounter))]
+        type MutexTestCounter;
+
+        fn new_mutex_test_counter() -> MutexTestCounter;
+        fn mutex_test_counter_is_locked(counter: &MutexTestCounter) -> bool;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(RwLock(LockTestCounter))]
+        type RwLockTestCounter;
+
+        fn new_rw_lock_test_counter() -> RwLockTestCounter;
+        fn rw_lock_test_counter_is_locked(counter: &RwLockTestCounter) -> bool;
+    }
+}
+
+pub struct LockTestCounter(u32);
+
+impl LockTestCounter {
+    fn count(&self) -> u32 {
+        self.0
+    }
+
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+}
+
+pub type MutexTestCounter = swift_bridge::sync::Mutex<LockTestCounter>;
+pub type RwLockTestCounter = swift_bridge::sync::RwLock<LockTestCounter>;
+
+fn new_mutex_test_counter() -> MutexTestCounter {
+    LockTestCounter(0).into()
+}
+
+fn mutex_test_counter_is_locked(counter: &MutexTestCounter) -> bool {
+    counter.try_lock().is_err()
+}
+
+fn new_rw_lock_test_counter() -> RwLockTestCounter {
+    LockTestCounter(0).into()
+}
+
+/// Whether or not a writer would have to wait for the lock.
+fn rw_lock_test_counter_is_locked(counter: &RwLockTestCounter) -> bool {
+    counter.try_write().is_err()
+}
@@ -0,0 +1,35 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(objc, Equatable, Hashable, CustomStringConvertible)]
+        type RustObjcType;
+
+        #[swift_bridge(init)]
+        fn new(val: u32) -> RustObjcType;
+        fn increment(&mut self);
+        fn val(&self) -> u32;
+    }
+}
+
+#[derive(PartialEq, Hash)]
+pub struct RustObjcType(u32);
+
+impl RustObjcType {
+    fn new(val: u32) -> Self {
+        RustObjcType(val)
+    }
+
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+
+    fn val(&self) -> u32 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for RustObjcType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RustObjcType({})", self.0)
+    }
+}
@@ -0,0 +1,48 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/observable_object_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(ObservableObject)]
+        type ObservableObjectTestCounter;
+
+        #[swift_bridge(init)]
+        fn new() -> ObservableObjectTestCounter;
+
+        #[swift_bridge(swift_property)]
+        fn count(&self) -> u32;
+        #[swift_bridge(swift_property)]
+        fn set_count(&mut self, count: u32);
+        #[swift_bridge(swift_property)]
+        fn label(&self) -> String;
+
+        fn increment(&mut self);
+    }
+}
+
+pub struct ObservableObjectTestCounter {
+    count: u32,
+}
+
+impl ObservableObjectTestCounter {
+    fn new() -> Self {
+        ObservableObjectTestCounter { count: 0 }
+    }
+
+    fn count(&self) -> u32 {
+        self.count
+    }
+
+    fn set_count(&mut self, count: u32) {
+        self.count = count;
+    }
+
+    fn label(&self) -> String {
+        format!("Count: {}", self.count)
+    }
+
+    fn increment(&mut self) {
+        self.count += 1;
+        self.object_will_change();
+    }
+}
@@ -0,0 +1,28 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(open)]
+        type OpenTypeTest;
+
+        #[swift_bridge(init)]
+        fn new(count: u32) -> OpenTypeTest;
+        fn increment(&mut self);
+        fn count(&self) -> u32;
+    }
+}
+
+pub struct OpenTypeTest(u32);
+
+impl OpenTypeTest {
+    fn new(count: u32) -> Self {
+        OpenTypeTest(count)
+    }
+
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+
+    fn count(&self) -> u32 {
+        self.0
+    }
+}
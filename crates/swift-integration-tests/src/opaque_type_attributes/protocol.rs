@@ -0,0 +1,36 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/swift_protocol_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Swift" {
+        #[swift_bridge(protocol)]
+        type ProtocolTestDelegate;
+
+        fn on_value(&self, value: u32);
+        fn should_continue(&self) -> bool;
+    }
+
+    extern "Rust" {
+        type ProtocolTestNotifier;
+
+        #[swift_bridge(init)]
+        fn new(delegate: ProtocolTestDelegate) -> ProtocolTestNotifier;
+
+        fn notify(&self, value: u32) -> bool;
+    }
+}
+
+pub struct ProtocolTestNotifier {
+    delegate: ffi::ProtocolTestDelegate,
+}
+
+impl ProtocolTestNotifier {
+    fn new(delegate: ffi::ProtocolTestDelegate) -> Self {
+        ProtocolTestNotifier { delegate }
+    }
+
+    fn notify(&self, value: u32) -> bool {
+        self.delegate.on_value(value);
+        self.delegate.should_continue()
+    }
+}
@@ -0,0 +1,49 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/combine_publisher_codegen_tests.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Publisher(u32))]
+        type PublisherTestValues;
+    }
+
+    extern "Rust" {
+        type PublisherTestSource;
+
+        #[swift_bridge(init)]
+        fn new() -> PublisherTestSource;
+
+        fn values(&self) -> PublisherTestValues;
+        fn send(&self, value: u32);
+        fn close(&mut self);
+    }
+}
+
+pub type PublisherTestValues = swift_bridge::combine::Publisher<u32>;
+
+pub struct PublisherTestSource {
+    sender: Option<tokio::sync::broadcast::Sender<u32>>,
+}
+
+impl PublisherTestSource {
+    fn new() -> Self {
+        let (sender, _) = tokio::sync::broadcast::channel(16);
+        PublisherTestSource {
+            sender: Some(sender),
+        }
+    }
+
+    fn values(&self) -> PublisherTestValues {
+        self.sender.as_ref().unwrap().subscribe().into()
+    }
+
+    fn send(&self, value: u32) {
+        if let Some(sender) = self.sender.as_ref() {
+            let _ = sender.send(value);
+        }
+    }
+
+    fn close(&mut self) {
+        self.sender = None;
+    }
+}
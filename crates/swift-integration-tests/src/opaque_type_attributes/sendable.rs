@@ -0,0 +1,40 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Sendable)]
+        type RustSendableType;
+
+        #[swift_bridge(init)]
+        fn new(val: u32) -> RustSendableType;
+        fn val(&self) -> u32;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Copy(4), Sendable)]
+        type RustCopySendableType;
+
+        #[swift_bridge(init)]
+        fn new(val: u32) -> RustCopySendableType;
+    }
+}
+
+pub struct RustSendableType(u32);
+
+impl RustSendableType {
+    fn new(val: u32) -> Self {
+        RustSendableType(val)
+    }
+
+    fn val(&self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct RustCopySendableType(u32);
+
+impl RustCopySendableType {
+    fn new(val: u32) -> Self {
+        RustCopySendableType(val)
+    }
+}
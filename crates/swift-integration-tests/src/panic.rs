@@ -0,0 +1,43 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/panic_codegen_tests.rs
+
+#[swift_bridge::bridge]
+#[swift_bridge(panic = "throw")]
+mod ffi {
+    extern "Rust" {
+        type PanicTestType;
+        #[swift_bridge(Error)]
+        type PanicTestError;
+
+        /// Panics if `val` is 0.
+        #[swift_bridge(init)]
+        fn new_panics_if_zero(val: u32) -> Result<PanicTestType, PanicTestError>;
+
+        fn val(self: &PanicTestType) -> u32;
+    }
+}
+
+pub struct PanicTestType {
+    val: u32,
+}
+
+impl PanicTestType {
+    fn new_panics_if_zero(val: u32) -> Result<Self, PanicTestError> {
+        if val == 0 {
+            panic!("val was zero");
+        }
+
+        Ok(Self { val })
+    }
+
+    fn val(&self) -> u32 {
+        self.val
+    }
+}
+
+pub struct PanicTestError;
+
+impl std::fmt::Display for PanicTestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PanicTestError")
+    }
+}
@@ -14,6 +14,11 @@ mod ffi {
         fn rust_double_f32(arg: f32) -> f32;
         fn rust_double_f64(arg: f64) -> f64;
         fn rust_negate_bool(arg: bool) -> bool;
+
+        #[swift_bridge(available(macOS = "15.0", iOS = "18.0"))]
+        fn rust_double_u128(arg: u128) -> u128;
+        #[swift_bridge(available(macOS = "15.0", iOS = "18.0"))]
+        fn rust_double_i128(arg: i128) -> i128;
     }
 
     extern "Swift" {
@@ -89,3 +94,11 @@ fn rust_double_f64(arg: f64) -> f64 {
 fn rust_negate_bool(arg: bool) -> bool {
     !arg
 }
+
+fn rust_double_u128(arg: u128) -> u128 {
+    arg * 2
+}
+
+fn rust_double_i128(arg: i128) -> i128 {
+    arg * 2
+}
@@ -2,6 +2,23 @@
 
 #[swift_bridge::bridge]
 mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct ResultTestSharedStruct {
+        field: u32,
+    }
+
+    #[swift_bridge(swift_repr = "struct")]
+    struct ResultTestLargeSharedStruct {
+        a: u64,
+        b: u64,
+        c: u64,
+        d: u64,
+        e: u64,
+        f: u64,
+        g: u64,
+        h: u64,
+    }
+
     extern "Rust" {
         fn rust_func_takes_result_string(arg: Result<String, String>);
         fn rust_func_takes_result_opaque_rust(
@@ -10,6 +27,17 @@ mod ffi {
         fn rust_func_takes_result_opaque_swift(
             arg: Result<ResultTestOpaqueSwiftType, ResultTestOpaqueSwiftType>,
         );
+        fn rust_func_takes_result_shared_struct(
+            arg: Result<ResultTestSharedStruct, ResultTestOpaqueRustType>,
+        );
+        fn rust_func_reflect_result_large_shared_struct(
+            arg: Result<ResultTestLargeSharedStruct, ResultTestOpaqueRustType>,
+        ) -> Result<ResultTestLargeSharedStruct, ResultTestOpaqueRustType>;
+        fn rust_func_takes_result_primitive_and_string(arg: Result<u32, String>) -> u32;
+        fn rust_func_returns_result_opaque_rust_or_primitive(
+            succeed: bool,
+        ) -> Result<ResultTestOpaqueRustType, u8>;
+        fn rust_func_returns_result_null_or_f64(succeed: bool) -> Result<(), f64>;
     }
 
     extern "Rust" {
@@ -17,6 +45,8 @@ mod ffi {
 
         #[swift_bridge(init)]
         fn new(val: u32) -> ResultTestOpaqueRustType;
+
+        fn val(&self) -> u32;
     }
 
     extern "Swift" {
@@ -63,6 +93,50 @@ fn rust_func_takes_result_opaque_swift(
     }
 }
 
+fn rust_func_takes_result_shared_struct(
+    arg: Result<ffi::ResultTestSharedStruct, ResultTestOpaqueRustType>,
+) {
+    match arg {
+        Ok(ok) => {
+            assert_eq!(ok.field, 333)
+        }
+        Err(err) => {
+            assert_eq!(err.val, 444)
+        }
+    }
+}
+
+fn rust_func_reflect_result_large_shared_struct(
+    arg: Result<ffi::ResultTestLargeSharedStruct, ResultTestOpaqueRustType>,
+) -> Result<ffi::ResultTestLargeSharedStruct, ResultTestOpaqueRustType> {
+    arg
+}
+
+fn rust_func_takes_result_primitive_and_string(arg: Result<u32, String>) -> u32 {
+    match arg {
+        Ok(ok) => ok,
+        Err(err) => err.len() as u32,
+    }
+}
+
+fn rust_func_returns_result_opaque_rust_or_primitive(
+    succeed: bool,
+) -> Result<ResultTestOpaqueRustType, u8> {
+    if succeed {
+        Ok(ResultTestOpaqueRustType::new(777))
+    } else {
+        Err(88)
+    }
+}
+
+fn rust_func_returns_result_null_or_f64(succeed: bool) -> Result<(), f64> {
+    if succeed {
+        Ok(())
+    } else {
+        Err(1.5)
+    }
+}
+
 pub struct ResultTestOpaqueRustType {
     val: u32,
 }
@@ -70,4 +144,8 @@ impl ResultTestOpaqueRustType {
     fn new(val: u32) -> Self {
         Self { val }
     }
+
+    fn val(&self) -> u32 {
+        self.val
+    }
 }
@@ -17,6 +17,13 @@ mod ffi {
     #[swift_bridge(swift_repr = "struct")]
     struct StructReprStructTupleStruct(u8, u32);
 
+    #[repr(C, packed)]
+    #[swift_bridge(swift_repr = "struct")]
+    struct StructReprPacked {
+        tag: u8,
+        length: u32,
+    }
+
     extern "Rust" {
         fn test_rust_calls_swift();
 
@@ -29,6 +36,9 @@ mod ffi {
         fn swift_calls_rust_tuple_struct(
             arg: StructReprStructTupleStruct,
         ) -> StructReprStructTupleStruct;
+
+        fn swift_calls_rust_packed_struct(arg: StructReprPacked) -> StructReprPacked;
+        fn rust_packed_struct_ffi_size() -> usize;
     }
 
     extern "Swift" {
@@ -61,6 +71,14 @@ fn swift_calls_rust_tuple_struct(
     arg
 }
 
+fn swift_calls_rust_packed_struct(arg: ffi::StructReprPacked) -> ffi::StructReprPacked {
+    arg
+}
+
+fn rust_packed_struct_ffi_size() -> usize {
+    std::mem::size_of::<<ffi::StructReprPacked as swift_bridge::SharedStruct>::FfiRepr>()
+}
+
 #[deny(unused)]
 mod tests {
     use super::ffi;
@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::LazyLock;
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type StaticsTestRegistry;
+
+        fn entry_count(&self) -> u32;
+
+        static SHARED_REGISTRY: StaticsTestRegistry;
+
+        #[swift_bridge(associated_to = StaticsTestRegistry)]
+        static DEFAULT_REGISTRY: StaticsTestRegistry;
+
+        static STATICS_TEST_VERSION: u16;
+
+        static STATICS_TEST_COUNTER: AtomicU64;
+
+        #[swift_bridge(associated_to = StaticsTestRegistry)]
+        static IS_FROZEN: std::sync::atomic::AtomicBool;
+    }
+}
+
+pub struct StaticsTestRegistry {
+    entries: Vec<String>,
+}
+
+impl StaticsTestRegistry {
+    fn entry_count(&self) -> u32 {
+        self.entries.len() as u32
+    }
+}
+
+static SHARED_REGISTRY: LazyLock<StaticsTestRegistry> = LazyLock::new(|| StaticsTestRegistry {
+    entries: vec!["first".to_string(), "second".to_string()],
+});
+
+static DEFAULT_REGISTRY: StaticsTestRegistry = StaticsTestRegistry {
+    entries: Vec::new(),
+};
+
+static STATICS_TEST_VERSION: u16 = 3;
+
+static STATICS_TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+static IS_FROZEN: AtomicBool = AtomicBool::new(false);
@@ -4,6 +4,9 @@ mod ffi {
         fn run_string_tests();
 
         fn create_string(str: &str) -> String;
+        fn create_string_with_capacity(str: &str, capacity: usize) -> String;
+        fn reflect_string(string: String) -> String;
+        fn string_capacity(string: String) -> usize;
     }
 
     extern "Swift" {
@@ -20,3 +23,17 @@ fn run_string_tests() {
 fn create_string(str: &str) -> String {
     str.to_string()
 }
+
+fn create_string_with_capacity(str: &str, capacity: usize) -> String {
+    let mut string = String::with_capacity(capacity);
+    string.push_str(str);
+    string
+}
+
+fn reflect_string(string: String) -> String {
+    string
+}
+
+fn string_capacity(string: String) -> usize {
+    string.capacity()
+}
@@ -0,0 +1,36 @@
+#[swift_bridge::bridge]
+#[swift_bridge(swift_name_prefix = "Prefixed")]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    struct SwiftNamePrefixTestStruct {
+        val: u8,
+    }
+
+    extern "Rust" {
+        type SwiftNamePrefixTestType;
+
+        #[swift_bridge(init)]
+        fn new(val: u8) -> SwiftNamePrefixTestType;
+        fn val(&self) -> u8;
+
+        fn swift_name_prefix_test_struct(val: u8) -> SwiftNamePrefixTestStruct;
+    }
+}
+
+use ffi::SwiftNamePrefixTestStruct;
+
+pub struct SwiftNamePrefixTestType(u8);
+
+impl SwiftNamePrefixTestType {
+    fn new(val: u8) -> Self {
+        SwiftNamePrefixTestType(val)
+    }
+
+    fn val(&self) -> u8 {
+        self.0
+    }
+}
+
+fn swift_name_prefix_test_struct(val: u8) -> SwiftNamePrefixTestStruct {
+    SwiftNamePrefixTestStruct { val }
+}
@@ -0,0 +1,31 @@
+#[swift_bridge::bridge]
+#[swift_bridge(symbol_prefix = "__swift_integration_tests__")]
+mod ffi {
+    extern "Rust" {
+        type SymbolPrefixTestType;
+
+        #[swift_bridge(init)]
+        fn new(val: u32) -> SymbolPrefixTestType;
+        fn val(&self) -> u32;
+    }
+
+    extern "Rust" {
+        fn symbol_prefix_test_add(lhs: u32, rhs: u32) -> u32;
+    }
+}
+
+pub struct SymbolPrefixTestType(u32);
+
+impl SymbolPrefixTestType {
+    fn new(val: u32) -> Self {
+        SymbolPrefixTestType(val)
+    }
+
+    fn val(&self) -> u32 {
+        self.0
+    }
+}
+
+fn symbol_prefix_test_add(lhs: u32, rhs: u32) -> u32 {
+    lhs + rhs
+}
@@ -20,6 +20,10 @@ mod ffi {
         ) -> Vec<ARustTypeInsideVecT>;
     }
 
+    extern "Rust" {
+        fn rust_make_vec_f32(len: usize) -> Vec<f32>;
+    }
+
     extern "Rust" {
         fn rust_reflect_vec_transparent_enum(
             arg: Vec<TransparentEnumInsideVecT>,
@@ -47,6 +51,10 @@ fn rust_reflect_vec_opaque_rust_type(arg: Vec<ARustTypeInsideVecT>) -> Vec<ARust
     arg
 }
 
+fn rust_make_vec_f32(len: usize) -> Vec<f32> {
+    (0..len).map(|idx| idx as f32).collect()
+}
+
 fn rust_reflect_vec_transparent_enum(
     arg: Vec<ffi::TransparentEnumInsideVecT>,
 ) -> Vec<ffi::TransparentEnumInsideVecT> {
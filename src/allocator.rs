@@ -0,0 +1,236 @@
+//! Choose the allocator for the boxes that hold the Rust values handed to Swift, such as the
+//! `RustString`s and `RustVec`s that bridged functions return.
+//!
+//! Every value that crosses the boundary is boxed so that Swift can hold a pointer to it. With the
+//! `custom-allocator` feature those boxes are allocated by the [`BridgeAllocator`] that was passed
+//! to [`set_allocator`], for example a pool that keeps the FFI hot path from calling into the
+//! system allocator. Without the feature they are regular `Box`es.
+//!
+//! The buffers that a `String` or `Vec` owns are allocated by Rust's global allocator, which can
+//! be replaced using `#[global_allocator]` (for example with mimalloc).
+//!
+//! ```no_run
+//! # #[cfg(feature = "custom-allocator")]
+//! # {
+//! use std::alloc::{GlobalAlloc, Layout, System};
+//! use swift_bridge::allocator::{set_allocator, BridgeAllocator};
+//!
+//! struct MyAllocator;
+//!
+//! unsafe impl BridgeAllocator for MyAllocator {
+//!     fn allocate(&self, layout: Layout) -> *mut u8 {
+//!         unsafe { System.alloc(layout) }
+//!     }
+//!
+//!     unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+//!         System.dealloc(ptr, layout)
+//!     }
+//! }
+//!
+//! // Before any value is handed to Swift.
+//! set_allocator(&MyAllocator).unwrap();
+//! # }
+//! ```
+
+use std::alloc::Layout;
+#[cfg(feature = "custom-allocator")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "custom-allocator")]
+static ALLOCATOR: OnceLock<&'static dyn BridgeAllocator> = OnceLock::new();
+
+/// Allocates the boxes that hold the Rust values handed to Swift.
+///
+/// # Safety
+///
+/// [`BridgeAllocator::allocate`] must return either null or memory that fits the layout, and the
+/// memory must stay valid until it is passed to [`BridgeAllocator::deallocate`]. Both methods can
+/// be called from any thread.
+#[cfg(feature = "custom-allocator")]
+pub unsafe trait BridgeAllocator: Send + Sync {
+    /// Allocate memory for the layout, or return null if it could not be allocated.
+    ///
+    /// The layout's size is never zero.
+    fn allocate(&self, layout: Layout) -> *mut u8;
+
+    /// Free memory that was returned by [`BridgeAllocator::allocate`] for the same layout.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must have come from this allocator's `allocate` using the same layout.
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout);
+}
+
+/// Use the allocator for all of the values that are handed to Swift from now on.
+///
+/// The allocator can only be chosen once, before the first value is handed to Swift, since the
+/// values that Swift already holds have to be freed by the allocator that allocated them.
+#[cfg(feature = "custom-allocator")]
+pub fn set_allocator(allocator: &'static dyn BridgeAllocator) -> Result<(), AllocatorAlreadySet> {
+    ALLOCATOR.set(allocator).map_err(|_| AllocatorAlreadySet)
+}
+
+/// [`set_allocator`] was called after the allocator had already been set, or after a value was
+/// already handed to Swift using the global allocator.
+#[cfg(feature = "custom-allocator")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocatorAlreadySet;
+
+#[cfg(feature = "custom-allocator")]
+impl std::fmt::Display for AllocatorAlreadySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the swift-bridge allocator was already chosen")
+    }
+}
+
+#[cfg(feature = "custom-allocator")]
+impl std::error::Error for AllocatorAlreadySet {}
+
+/// Used when no allocator was set before the first value was handed to Swift.
+#[cfg(feature = "custom-allocator")]
+struct GlobalAllocator;
+
+#[cfg(feature = "custom-allocator")]
+unsafe impl BridgeAllocator for GlobalAllocator {
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        unsafe { std::alloc::alloc(layout) }
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        std::alloc::dealloc(ptr, layout)
+    }
+}
+
+#[cfg(feature = "custom-allocator")]
+fn allocator() -> &'static dyn BridgeAllocator {
+    *ALLOCATOR.get_or_init(|| &GlobalAllocator)
+}
+
+/// `Box::into_raw(Box::new(val))`, using the chosen allocator and counting the object as live.
+#[doc(hidden)]
+pub fn box_into_raw<T>(val: T) -> *mut T {
+    crate::diagnostics::count_created::<T>();
+
+    #[cfg(feature = "custom-allocator")]
+    {
+        let layout = Layout::new::<T>();
+        if layout.size() == 0 {
+            std::mem::forget(val);
+            return std::ptr::NonNull::dangling().as_ptr();
+        }
+
+        let ptr = allocator().allocate(layout) as *mut T;
+        if ptr.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
+        unsafe { ptr.write(val) };
+        ptr
+    }
+
+    #[cfg(not(feature = "custom-allocator"))]
+    {
+        Box::into_raw(Box::new(val))
+    }
+}
+
+/// `*Box::from_raw(ptr)`, freeing the box using the chosen allocator and counting the object as
+/// no longer live.
+///
+/// # Safety
+///
+/// The pointer must have come from [`box_into_raw`].
+#[doc(hidden)]
+pub unsafe fn take_from_raw<T>(ptr: *mut T) -> T {
+    crate::diagnostics::count_dropped::<T>();
+
+    #[cfg(feature = "custom-allocator")]
+    {
+        let val = ptr.read();
+        let layout = Layout::new::<T>();
+        if layout.size() != 0 {
+            allocator().deallocate(ptr as *mut u8, layout);
+        }
+        val
+    }
+
+    #[cfg(not(feature = "custom-allocator"))]
+    {
+        *Box::from_raw(ptr)
+    }
+}
+
+/// `Box::into_raw(boxed)`, for a value that Rust code already boxed, counting the object as live.
+///
+/// With the `custom-allocator` feature the value gets moved into a box from the chosen allocator,
+/// since Swift's pointer gets freed by [`take_from_raw`].
+#[doc(hidden)]
+#[inline]
+pub fn boxed_into_raw<T>(boxed: Box<T>) -> *mut T {
+    #[cfg(feature = "custom-allocator")]
+    {
+        box_into_raw(*boxed)
+    }
+
+    #[cfg(not(feature = "custom-allocator"))]
+    {
+        crate::diagnostics::count_created::<T>();
+        Box::into_raw(boxed)
+    }
+}
+
+/// `Box::from_raw(ptr)`, for handing a value to Rust code that takes it boxed, counting the object
+/// as no longer live.
+///
+/// With the `custom-allocator` feature the value gets moved out of the chosen allocator's box and
+/// into a new `Box`.
+///
+/// # Safety
+///
+/// The pointer must have come from [`box_into_raw`] or [`boxed_into_raw`].
+#[doc(hidden)]
+#[inline]
+pub unsafe fn take_boxed_from_raw<T>(ptr: *mut T) -> Box<T> {
+    #[cfg(feature = "custom-allocator")]
+    {
+        Box::new(take_from_raw(ptr))
+    }
+
+    #[cfg(not(feature = "custom-allocator"))]
+    {
+        crate::diagnostics::count_dropped::<T>();
+        Box::from_raw(ptr)
+    }
+}
+
+/// Allocate memory for a layout using the chosen allocator, for values that Swift and Rust write
+/// into memory that the other side frees, such as a result that is too large to be passed by
+/// value.
+///
+/// The layout's size must not be zero.
+#[doc(hidden)]
+pub fn allocate(layout: Layout) -> *mut u8 {
+    #[cfg(feature = "custom-allocator")]
+    let ptr = allocator().allocate(layout);
+
+    #[cfg(not(feature = "custom-allocator"))]
+    let ptr = unsafe { std::alloc::alloc(layout) };
+
+    if ptr.is_null() {
+        std::alloc::handle_alloc_error(layout);
+    }
+    ptr
+}
+
+/// Free memory that came from [`allocate`].
+///
+/// # Safety
+///
+/// The pointer must have come from [`allocate`] using the same layout.
+#[doc(hidden)]
+pub unsafe fn deallocate(ptr: *mut u8, layout: Layout) {
+    #[cfg(feature = "custom-allocator")]
+    allocator().deallocate(ptr, layout);
+
+    #[cfg(not(feature = "custom-allocator"))]
+    std::alloc::dealloc(ptr, layout);
+}
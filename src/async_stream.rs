@@ -0,0 +1,105 @@
+//! Support for exposing the receiving end of Rust channels to Swift as `AsyncStream`s.
+//!
+//! See the `#[swift_bridge(AsyncStream(...))]` opaque type attribute.
+
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc, Mutex};
+
+/// The receiving end of a channel that is exposed to Swift as an `AsyncStream<T>`.
+///
+/// Swift awaits one value each time that it iterates over the stream, so a bounded channel's
+/// senders wait while Swift is busy with the values that it already received.
+///
+/// The Swift stream finishes once every sender has been dropped, and the channel is closed once
+/// Swift drops the stream, so the senders can stop producing values.
+///
+/// ```ignore
+/// #[swift_bridge::bridge]
+/// mod ffi {
+///     extern "Rust" {
+///         #[swift_bridge(AsyncStream(u32))]
+///         type ProgressReceiver;
+///     }
+/// }
+///
+/// pub type ProgressReceiver = swift_bridge::async_stream::AsyncStream<u32>;
+/// ```
+pub struct AsyncStream<T> {
+    // Swift can await the next value from more than one task at a time.
+    receiver: Mutex<Box<dyn ChannelReceiver<T>>>,
+}
+
+impl<T: Send + 'static> AsyncStream<T> {
+    /// Create a stream that receives the values sent to a channel.
+    pub fn new(receiver: impl ChannelReceiver<T> + 'static) -> Self {
+        AsyncStream {
+            receiver: Mutex::new(Box::new(receiver)),
+        }
+    }
+
+    /// Wait for the next value that is sent to the channel.
+    ///
+    /// Returns `None` once every sender has been dropped.
+    pub async fn next(&self) -> Option<T> {
+        self.receiver.lock().await.recv().await
+    }
+}
+
+/// The receiving end of a channel that an [`AsyncStream`] can pull values from.
+///
+/// This is implemented for tokio's `mpsc` and `broadcast` receivers.
+pub trait ChannelReceiver<T>: Send {
+    /// Wait for the next value, returning `None` once the channel is closed.
+    ///
+    /// The future gets dropped if Swift cancels the task that is waiting for the value, so it
+    /// must not lose values when it is dropped.
+    fn recv(&mut self) -> Pin<Box<dyn Future<Output = Option<T>> + Send + '_>>;
+}
+
+impl<T: Send> ChannelReceiver<T> for mpsc::Receiver<T> {
+    fn recv(&mut self) -> Pin<Box<dyn Future<Output = Option<T>> + Send + '_>> {
+        Box::pin(mpsc::Receiver::recv(self))
+    }
+}
+
+impl<T: Send> ChannelReceiver<T> for mpsc::UnboundedReceiver<T> {
+    fn recv(&mut self) -> Pin<Box<dyn Future<Output = Option<T>> + Send + '_>> {
+        Box::pin(mpsc::UnboundedReceiver::recv(self))
+    }
+}
+
+/// Values that were dropped because the receiver fell too far behind the channel's capacity are
+/// skipped.
+impl<T: Clone + Send> ChannelReceiver<T> for broadcast::Receiver<T> {
+    fn recv(&mut self) -> Pin<Box<dyn Future<Output = Option<T>> + Send + '_>> {
+        Box::pin(async move {
+            loop {
+                match broadcast::Receiver::recv(self).await {
+                    Ok(value) => return Some(value),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
+
+impl<T: Send + 'static> From<mpsc::Receiver<T>> for AsyncStream<T> {
+    fn from(receiver: mpsc::Receiver<T>) -> Self {
+        AsyncStream::new(receiver)
+    }
+}
+
+impl<T: Send + 'static> From<mpsc::UnboundedReceiver<T>> for AsyncStream<T> {
+    fn from(receiver: mpsc::UnboundedReceiver<T>) -> Self {
+        AsyncStream::new(receiver)
+    }
+}
+
+impl<T: Clone + Send + 'static> From<broadcast::Receiver<T>> for AsyncStream<T> {
+    fn from(receiver: broadcast::Receiver<T>) -> Self {
+        AsyncStream::new(receiver)
+    }
+}
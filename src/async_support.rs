@@ -1,23 +1,108 @@
-use once_cell::sync::Lazy;
+//! Choose how the futures of bridged `async fn`s are spawned.
+//!
+//! When Swift calls an async Rust function, the function's future is handed to the [`Spawner`]
+//! that was passed to [`set_spawner`], which runs it to completion. If no spawner was set, the
+//! futures run on a multi-threaded tokio runtime that `swift-bridge` starts on its own thread the
+//! first time that Swift calls an async Rust function.
+//!
+//! Use the runtime that the rest of your library already uses, for example when your futures
+//! need a tokio runtime with the `time` or `net` drivers enabled:
+//!
+//! ```no_run
+//! # #[cfg(feature = "async")]
+//! # {
+//! use swift_bridge::async_support::set_spawner;
+//!
+//! let runtime = tokio::runtime::Runtime::new().unwrap();
+//! set_spawner(runtime.handle().clone()).unwrap();
+//! # std::mem::forget(runtime);
+//! # }
+//! ```
+//!
+//! Or any other executor, such as async-std:
+//!
+//! ```ignore
+//! set_spawner(|future| {
+//!     async_std::task::spawn(future);
+//! })
+//! .unwrap();
+//! ```
+
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{Receiver, SyncSender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
 
-#[doc(hidden)]
-pub static ASYNC_RUNTIME: Lazy<TokioRuntime> = Lazy::new(|| {
-    let (sender, receiver) = std::sync::mpsc::sync_channel(10_000);
+static SPAWNER: OnceLock<Box<dyn Spawner>> = OnceLock::new();
+
+/// The future of a call from Swift to an async Rust function.
+///
+/// It tells Swift about the function's result when it completes.
+pub type BridgedFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Runs the futures of bridged async functions.
+///
+/// Implemented for closures that take a [`BridgedFuture`] and for [`tokio::runtime::Handle`].
+pub trait Spawner: Send + Sync {
+    /// Run the future to completion in the background.
+    ///
+    /// This is called from the thread that Swift called the async function on, so it should not
+    /// block.
+    fn spawn(&self, future: BridgedFuture);
+}
+
+impl<F> Spawner for F
+where
+    F: Fn(BridgedFuture) + Send + Sync,
+{
+    fn spawn(&self, future: BridgedFuture) {
+        self(future)
+    }
+}
+
+impl Spawner for tokio::runtime::Handle {
+    fn spawn(&self, future: BridgedFuture) {
+        tokio::runtime::Handle::spawn(self, future);
+    }
+}
+
+/// Use the spawner for all of the async function calls from now on.
+///
+/// The spawner can only be chosen once, before Swift calls the first async Rust function.
+pub fn set_spawner(spawner: impl Spawner + 'static) -> Result<(), SpawnerAlreadySet> {
+    SPAWNER
+        .set(Box::new(spawner))
+        .map_err(|_| SpawnerAlreadySet)
+}
+
+/// [`set_spawner`] was called after the spawner had already been set, or after Swift already
+/// called an async Rust function using the default tokio runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnerAlreadySet;
+
+impl std::fmt::Display for SpawnerAlreadySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the swift-bridge async spawner was already chosen")
+    }
+}
 
-    let runtime = TokioRuntime { sender };
+impl std::error::Error for SpawnerAlreadySet {}
 
-    runtime.start_runtime(receiver);
+#[doc(hidden)]
+pub static ASYNC_RUNTIME: AsyncRuntime = AsyncRuntime;
 
-    runtime
-});
-type AsyncFnToSpawn = Pin<Box<dyn Future<Output = ()> + 'static + Send>>;
+#[doc(hidden)]
+pub struct AsyncRuntime;
 
 #[doc(hidden)]
-pub struct TokioRuntime {
-    sender: SyncSender<AsyncFnToSpawn>,
+impl AsyncRuntime {
+    pub fn spawn_task(&self, task: BridgedFuture) {
+        SPAWNER
+            .get_or_init(|| Box::new(TokioRuntime::start()))
+            .spawn(task)
+    }
 }
 
 // TODO: Audit to make sure that this is safe to be Send/Sync.
@@ -32,13 +117,20 @@ pub struct SwiftCallbackWrapper(pub *mut std::ffi::c_void);
 unsafe impl Send for SwiftCallbackWrapper {}
 unsafe impl Sync for SwiftCallbackWrapper {}
 
-#[doc(hidden)]
+/// Used when no spawner was set before Swift called the first async Rust function.
+struct TokioRuntime {
+    sender: SyncSender<BridgedFuture>,
+}
+
 impl TokioRuntime {
-    pub fn spawn_task(&self, task: AsyncFnToSpawn) {
-        self.sender.send(task).unwrap();
+    fn start() -> Self {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(10_000);
+        Self::start_runtime(receiver);
+
+        TokioRuntime { sender }
     }
 
-    fn start_runtime(&self, receiver: Receiver<AsyncFnToSpawn>) {
+    fn start_runtime(receiver: Receiver<BridgedFuture>) {
         std::thread::spawn(move || {
             tokio::runtime::Runtime::new()
                 .unwrap()
@@ -50,3 +142,134 @@ impl TokioRuntime {
         });
     }
 }
+
+impl Spawner for TokioRuntime {
+    fn spawn(&self, future: BridgedFuture) {
+        self.sender.send(future).unwrap();
+    }
+}
+
+/// Lets Swift cancel the future of a `#[swift_bridge(cancellable)]` async function.
+///
+/// The generated Swift code creates one before calling the function and cancels it when the
+/// `Task` that awaits the function is cancelled. Swift and the spawned task each hold a reference.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct AsyncCancellation {
+    cancelled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl AsyncCancellation {
+    /// Take a new reference to a cancellation that Swift created using
+    /// `__swift_bridge__$AsyncCancellation$new`.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must come from `__swift_bridge__$AsyncCancellation$new` and must not have been
+    /// freed.
+    pub unsafe fn from_swift(ptr: *const AsyncCancellation) -> Arc<AsyncCancellation> {
+        Arc::increment_strong_count(ptr);
+        Arc::from_raw(ptr)
+    }
+
+    /// Await the future, or drop it and return `None` as soon as Swift cancels.
+    pub async fn run<F: Future>(self: Arc<Self>, fut: F) -> Option<F::Output> {
+        Cancellable {
+            fut: Box::pin(fut),
+            cancellation: self,
+        }
+        .await
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+struct Cancellable<F: Future> {
+    fut: Pin<Box<F>>,
+    cancellation: Arc<AsyncCancellation>,
+}
+
+impl<F: Future> Future for Cancellable<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The waker is stored before checking the flag, so that a cancellation that happens in
+        // between still wakes us up.
+        *self.cancellation.waker.lock().unwrap() = Some(cx.waker().clone());
+        if self.cancellation.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(None);
+        }
+
+        self.fut.as_mut().poll(cx).map(Some)
+    }
+}
+
+#[export_name = "__swift_bridge__$AsyncCancellation$new"]
+#[doc(hidden)]
+pub extern "C" fn async_cancellation_new() -> *const AsyncCancellation {
+    Arc::into_raw(Arc::new(AsyncCancellation::default()))
+}
+
+#[export_name = "__swift_bridge__$AsyncCancellation$cancel"]
+#[doc(hidden)]
+pub unsafe extern "C" fn async_cancellation_cancel(ptr: *const AsyncCancellation) {
+    (*ptr).cancel()
+}
+
+#[export_name = "__swift_bridge__$AsyncCancellation$free"]
+#[doc(hidden)]
+pub unsafe extern "C" fn async_cancellation_free(ptr: *const AsyncCancellation) {
+    drop(Arc::from_raw(ptr))
+}
+
+/// Resolves to the value that Swift passes to the completion handler of a
+/// `#[swift_bridge(completion_handler)]` function.
+///
+/// The Swift function is called as soon as the Rust function is called, not when the future is
+/// first polled. Dropping the future does not stop the Swift function, and its value is then
+/// dropped once Swift calls the completion handler.
+///
+/// # Panics
+///
+/// Polling panics if Swift releases the completion handler without ever calling it.
+pub struct SwiftCompletion<T> {
+    receiver: tokio::sync::oneshot::Receiver<T>,
+}
+
+impl<T> SwiftCompletion<T> {
+    /// Call the Swift function with the sender that its completion handler sends the value to.
+    #[doc(hidden)]
+    pub fn new(call_swift: impl FnOnce(CompletionSender<T>)) -> Self {
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        call_swift(CompletionSender(sender));
+
+        SwiftCompletion { receiver }
+    }
+}
+
+impl<T> Future for SwiftCompletion<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver)
+            .poll(cx)
+            .map(|value| value.expect("Swift released the completion handler without calling it."))
+    }
+}
+
+/// Sends the value that Swift passes to a completion handler to its [`SwiftCompletion`].
+#[doc(hidden)]
+pub struct CompletionSender<T>(tokio::sync::oneshot::Sender<T>);
+
+impl<T> CompletionSender<T> {
+    pub fn send(self, value: T) {
+        // The future might have been dropped, in which case nobody wants the value.
+        let _ = self.0.send(value);
+    }
+}
@@ -0,0 +1,91 @@
+//! Reuse the buffers of the `Vec`s that Rust hands to Swift, so that a function that returns a
+//! fresh `Vec` on every call, such as once per frame, stops allocating once the pool is warm.
+//!
+//! Pooling is opt-in per element type. After [`set_max_pooled_buffers`] is called, a `RustVec`
+//! that Swift frees in its `deinit` gets cleared and kept in the pool instead of being dropped,
+//! and [`take_vec`] hands it back out with its capacity intact.
+//!
+//! ```no_run
+//! use swift_bridge::buffer_pool::{set_max_pooled_buffers, take_vec};
+//!
+//! // At startup.
+//! set_max_pooled_buffers::<u8>(4);
+//!
+//! // Called by Swift once per frame.
+//! fn render_frame() -> Vec<u8> {
+//!     let mut pixels = take_vec::<u8>();
+//!     pixels.resize(1920 * 1080 * 4, 0);
+//!     pixels
+//! }
+//! ```
+//!
+//! Only `Vec`s of primitives such as `Vec<u8>` or `Vec<f32>` are pooled.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// An element type whose `Vec`s can be pooled.
+///
+/// Implemented for the primitive types that `RustVec` supports.
+pub trait Pooled: Sized + 'static {
+    #[doc(hidden)]
+    fn pool() -> &'static BufferPool<Self>;
+}
+
+/// The pooled buffers for one element type.
+#[doc(hidden)]
+pub struct BufferPool<T> {
+    max_buffers: AtomicUsize,
+    buffers: Mutex<Vec<Vec<T>>>,
+}
+
+impl<T> BufferPool<T> {
+    #[doc(hidden)]
+    pub const fn new() -> Self {
+        BufferPool {
+            max_buffers: AtomicUsize::new(0),
+            buffers: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn buffers(&self) -> std::sync::MutexGuard<'_, Vec<Vec<T>>> {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keep up to `max_buffers` of the `Vec<T>`s that Swift frees, so that [`take_vec`] can reuse
+/// them.
+///
+/// Passing `0` turns pooling off and drops the buffers that are currently pooled.
+pub fn set_max_pooled_buffers<T: Pooled>(max_buffers: usize) {
+    let pool = T::pool();
+    pool.max_buffers.store(max_buffers, Ordering::Relaxed);
+    pool.buffers().truncate(max_buffers);
+}
+
+/// An empty `Vec<T>`, reusing a pooled buffer if there is one.
+pub fn take_vec<T: Pooled>() -> Vec<T> {
+    T::pool().buffers().pop().unwrap_or_default()
+}
+
+/// The number of `Vec<T>` buffers that are waiting in the pool.
+pub fn pooled_buffer_count<T: Pooled>() -> usize {
+    T::pool().buffers().len()
+}
+
+/// Keep the `Vec` in the pool if pooling is on for `T` and the pool isn't full, otherwise drop
+/// it.
+#[doc(hidden)]
+pub fn recycle<T: Pooled>(mut vec: Vec<T>) {
+    let pool = T::pool();
+    let max_buffers = pool.max_buffers.load(Ordering::Relaxed);
+    if max_buffers == 0 || vec.capacity() == 0 {
+        return;
+    }
+
+    let mut buffers = pool.buffers();
+    if buffers.len() < max_buffers {
+        vec.clear();
+        buffers.push(vec);
+    }
+}
@@ -0,0 +1,77 @@
+//! Report the Rust functions that Swift calls and how long they take, for profiling which bridge
+//! calls dominate.
+//!
+//! With the `call-tracing` feature every generated function that Swift calls reports its calls to
+//! the hook that was set using [`set_call_hook`]. With the `tracing` feature each call also runs
+//! inside of a `swift_bridge_call` [tracing](https://docs.rs/tracing) span.
+//!
+//! ```no_run
+//! use swift_bridge::call_tracing::{set_call_hook, BridgeCall};
+//!
+//! fn log_call(call: &BridgeCall) {
+//!     eprintln!("{} took {:?}", call.function, call.duration);
+//! }
+//!
+//! set_call_hook(log_call);
+//! ```
+
+use std::future::Future;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+static CALL_HOOK: RwLock<Option<fn(&BridgeCall)>> = RwLock::new(None);
+
+/// A finished call from Swift to a Rust function.
+#[derive(Debug, Clone, Copy)]
+pub struct BridgeCall {
+    /// The name of the function in the bridge module, such as `some_function` or
+    /// `SomeType::some_method`.
+    pub function: &'static str,
+    /// How long the call took. For async functions this is how long the future took to complete.
+    pub duration: Duration,
+}
+
+/// Call the hook after every call from Swift to a Rust function, replacing the previous hook.
+pub fn set_call_hook(hook: fn(&BridgeCall)) {
+    *CALL_HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(hook);
+}
+
+/// Stop calling the hook that was set using [`set_call_hook`].
+pub fn clear_call_hook() {
+    *CALL_HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Call the function, reporting the call to the hook.
+#[doc(hidden)]
+pub fn trace_call<T>(function: &'static str, f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("swift_bridge_call", function).entered();
+
+    let start = Instant::now();
+    let ret = f();
+    report_call(function, start.elapsed());
+    ret
+}
+
+/// Await the future of an async function, reporting the call to the hook.
+#[doc(hidden)]
+pub async fn trace_future<F: Future>(function: &'static str, fut: F) -> F::Output {
+    let start = Instant::now();
+
+    #[cfg(feature = "tracing")]
+    let output =
+        tracing::Instrument::instrument(fut, tracing::trace_span!("swift_bridge_call", function))
+            .await;
+    #[cfg(not(feature = "tracing"))]
+    let output = fut.await;
+
+    report_call(function, start.elapsed());
+    output
+}
+
+fn report_call(function: &'static str, duration: Duration) {
+    let hook = *CALL_HOOK.read().unwrap_or_else(|e| e.into_inner());
+    if let Some(hook) = hook {
+        hook(&BridgeCall { function, duration });
+    }
+}
@@ -0,0 +1,16 @@
+//! Support for the `#[swift_bridge(Codable)]` opaque type attribute.
+//!
+//! Values cross the FFI boundary as JSON.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Serialize a value into JSON, returning `None` if it cannot be serialized.
+pub fn to_json<T: Serialize>(value: &T) -> Option<String> {
+    serde_json::to_string(value).ok()
+}
+
+/// Deserialize a value from JSON, returning `None` if the JSON is invalid.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Option<T> {
+    serde_json::from_str(json).ok()
+}
@@ -0,0 +1,53 @@
+//! Support for exposing Rust broadcast channels to Swift as Combine publishers.
+//!
+//! See the `#[swift_bridge(Publisher(...))]` opaque type attribute.
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+
+/// The receiving end of a broadcast channel that is exposed to Swift as an
+/// `AnyPublisher<T, Never>`.
+///
+/// ```ignore
+/// #[swift_bridge::bridge]
+/// mod ffi {
+///     extern "Rust" {
+///         #[swift_bridge(Publisher(u32))]
+///         type ProgressPublisher;
+///     }
+/// }
+///
+/// pub type ProgressPublisher = swift_bridge::combine::Publisher<u32>;
+/// ```
+pub struct Publisher<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T: Clone> Publisher<T> {
+    /// Create a publisher that receives the values sent to a broadcast channel.
+    pub fn new(receiver: Receiver<T>) -> Self {
+        Publisher { receiver }
+    }
+
+    /// Block until the next value is sent to the channel.
+    ///
+    /// Returns `None` once every sender has been dropped.
+    ///
+    /// Values that were dropped because this receiver fell too far behind the channel's
+    /// capacity are skipped.
+    pub fn next_blocking(&mut self) -> Option<T> {
+        loop {
+            match self.receiver.blocking_recv() {
+                Ok(value) => return Some(value),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl<T: Clone> From<Receiver<T>> for Publisher<T> {
+    fn from(receiver: Receiver<T>) -> Self {
+        Publisher::new(receiver)
+    }
+}
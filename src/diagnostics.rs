@@ -0,0 +1,74 @@
+//! Count the Rust objects that are boxed and handed to Swift, so that tests can check that
+//! everything created across the boundary was dropped.
+//!
+//! Counting is enabled by the `leak-detection` feature. Without it the counts are always empty.
+//!
+//! ```no_run
+//! // After the Swift side of the test has released all of its bridged objects.
+//! assert!(swift_bridge::diagnostics::live_counts().is_empty());
+//! ```
+
+use crate::string::RustString;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "leak-detection")]
+static LIVE_COUNTS: std::sync::Mutex<BTreeMap<&'static str, usize>> =
+    std::sync::Mutex::new(BTreeMap::new());
+
+/// The number of live boxed opaque Rust types, `RustString`s and `RustVec`s that were handed to
+/// Swift, keyed by type name.
+///
+/// Types without any live objects are left out, so the map is empty when everything that was
+/// created across the boundary has been dropped.
+pub fn live_counts() -> BTreeMap<&'static str, usize> {
+    #[cfg(feature = "leak-detection")]
+    {
+        LIVE_COUNTS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    #[cfg(not(feature = "leak-detection"))]
+    {
+        BTreeMap::new()
+    }
+}
+
+/// Count a `T` that was handed to Swift as live.
+pub(crate) fn count_created<T>() {
+    #[cfg(feature = "leak-detection")]
+    {
+        *LIVE_COUNTS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(std::any::type_name::<T>())
+            .or_insert(0) += 1;
+    }
+}
+
+/// Count a `T` that was handed to Swift as no longer live.
+pub(crate) fn count_dropped<T>() {
+    #[cfg(feature = "leak-detection")]
+    {
+        let mut counts = LIVE_COUNTS.lock().unwrap_or_else(|e| e.into_inner());
+        let name = std::any::type_name::<T>();
+        if let Some(count) = counts.get_mut(name) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(name);
+            }
+        }
+    }
+}
+
+/// The live counts as `type name\tcount` lines, for `rustLiveObjectCounts()` in Swift.
+#[export_name = "__swift_bridge__$live_object_counts"]
+#[doc(hidden)]
+pub extern "C" fn live_object_counts() -> *mut RustString {
+    let lines: String = live_counts()
+        .into_iter()
+        .map(|(name, count)| format!("{}\t{}\n", name, count))
+        .collect();
+    RustString(lines).box_into_raw()
+}
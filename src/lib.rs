@@ -6,7 +6,12 @@ pub use swift_bridge_macro::bridge;
 
 mod std_bridge;
 
-pub use self::std_bridge::{option, result, string};
+pub use self::std_bridge::{int128, option, result, string};
+
+pub mod allocator;
+
+#[cfg(feature = "async")]
+pub mod async_stream;
 
 #[doc(hidden)]
 #[cfg(feature = "async")]
@@ -15,6 +20,28 @@ pub mod async_support;
 #[doc(hidden)]
 pub mod boxed_fn_support;
 
+pub mod buffer_pool;
+
+#[cfg(feature = "call-tracing")]
+pub mod call_tracing;
+
+#[doc(hidden)]
+#[cfg(feature = "codable")]
+pub mod codable;
+
+#[cfg(feature = "combine")]
+pub mod combine;
+
+pub mod diagnostics;
+
+#[doc(hidden)]
+pub mod panic_support;
+
+pub mod sync;
+
+#[doc(hidden)]
+pub mod vec_support;
+
 #[doc(hidden)]
 #[repr(C)]
 pub struct FfiSlice<T> {
@@ -94,3 +121,6 @@ pub extern "C" fn __swift_bridge__null_pointer() -> *const std::ffi::c_void {
 
 #[doc(hidden)]
 pub fn assert_copy<T: Copy>() {}
+
+#[doc(hidden)]
+pub fn assert_send_sync<T: Send + Sync>() {}
@@ -0,0 +1,70 @@
+//! Catch panics in the Rust functions that Swift calls, for bridge modules that use
+//! `#[swift_bridge(panic = "...")]`.
+//!
+//! Unwinding out of an `extern "C"` function into Swift is undefined behavior.
+
+use crate::string::{RustStr, RustString};
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+extern "C" {
+    // Defined in the generated `SwiftBridgeCore.swift`.
+    #[link_name = "__swift_bridge__$rust_panic_fatal_error"]
+    fn swift_fatal_error(message: RustStr);
+}
+
+thread_local! {
+    /// The message of a panic that Swift will throw, for `panic = "throw"`.
+    static PANIC_MESSAGE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Call the function, aborting the process if it panics.
+pub fn catch_panic_or_abort<T>(f: impl FnOnce() -> T) -> T {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| std::process::abort())
+}
+
+/// Call the function, calling Swift's `fatalError` with the panic's message if it panics.
+pub fn catch_panic_or_fatal_error<T>(f: impl FnOnce() -> T) -> T {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let message = panic_message(panic.as_ref());
+        unsafe { swift_fatal_error(RustStr::from_str(&message)) };
+
+        // `fatalError` doesn't return.
+        std::process::abort()
+    })
+}
+
+/// Call the function, returning `on_panic` if it panics.
+///
+/// The panic's message is saved so that the generated Swift code can throw it using
+/// [`take_panic_message`] before it looks at the returned value.
+pub fn catch_panic_or_throw<T>(f: impl FnOnce() -> T, on_panic: T) -> T {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let message = panic_message(panic.as_ref());
+        PANIC_MESSAGE.with(|m| *m.borrow_mut() = Some(message));
+
+        on_panic
+    })
+}
+
+/// Take the message of the last panic that was caught on this thread by
+/// [`catch_panic_or_throw`], or null if there wasn't one.
+#[export_name = "__swift_bridge__$take_panic_message"]
+pub extern "C" fn take_panic_message() -> *mut RustString {
+    match PANIC_MESSAGE.with(|m| m.borrow_mut().take()) {
+        Some(message) => RustString(message).box_into_raw(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// The message that `panic!` was called with.
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Rust panicked".to_string()
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(missing_docs)]
 
+pub mod int128;
 pub mod option;
 pub mod result;
 mod rust_vec;
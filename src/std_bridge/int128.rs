@@ -0,0 +1,48 @@
+// Rust's `u128` and `i128` don't have a portable C representation (`__int128` is a compiler
+// extension that Swift does not import), so we pass them over FFI as two 64 bit halves.
+
+#[repr(C)]
+#[doc(hidden)]
+// Bridges `u128`. Has the same layout as the `__private__U128` C struct.
+pub struct FfiU128 {
+    pub low: u64,
+    pub high: u64,
+}
+
+#[repr(C)]
+#[doc(hidden)]
+// Bridges `i128`. Has the same layout as the `__private__I128` C struct.
+pub struct FfiI128 {
+    pub low: u64,
+    pub high: i64,
+}
+
+impl From<u128> for FfiU128 {
+    fn from(val: u128) -> Self {
+        FfiU128 {
+            low: val as u64,
+            high: (val >> 64) as u64,
+        }
+    }
+}
+
+impl From<FfiU128> for u128 {
+    fn from(val: FfiU128) -> Self {
+        ((val.high as u128) << 64) | val.low as u128
+    }
+}
+
+impl From<i128> for FfiI128 {
+    fn from(val: i128) -> Self {
+        FfiI128 {
+            low: val as u64,
+            high: (val >> 64) as i64,
+        }
+    }
+}
+
+impl From<FfiI128> for i128 {
+    fn from(val: FfiI128) -> Self {
+        ((val.high as i128) << 64) | val.low as i128
+    }
+}
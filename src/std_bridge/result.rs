@@ -1,3 +1,5 @@
+use std::alloc::Layout;
+
 #[repr(C)]
 #[doc(hidden)]
 // Bridges `Result<T, E>` where `T` and `E` are non primitive types.
@@ -6,29 +8,122 @@ pub struct ResultPtrAndPtr {
     pub ok_or_err: *mut std::ffi::c_void,
 }
 
-// TODO: We need to define every combination of primitive and pointer.
-//  Probably low priority since most users are probably using non-primitive types for
-//  `Result<T, E>`.
-//
-// #[repr(C)]
-// #[doc(hidden)]
-// pub struct ResultU8AndU8 {
-//     pub is_ok: bool,
-//     pub ok_or_err: u8,
-// }
-//
-// #[repr(C)]
-// #[doc(hidden)]
-// pub struct ResultU8AndU16 {
-//     pub is_ok: bool,
-//     pub ok: u8,
-//     pub err: u16
-// }
+// Bridges `Result<T, E>` where `T` or `E` is a primitive type.
 //
-// #[repr(C)]
-// #[doc(hidden)]
-// pub struct ResultU8AndPtr {
-//     pub is_ok: bool,
-//     pub ok: u8,
-//     pub err: *mut std::ffi::c_void,
-// }
+// Every primitive and pointer fits into 64 bits, so this covers all of the combinations of
+// primitives, pointers and `()` without needing a struct for each combination.
+#[repr(C)]
+#[doc(hidden)]
+pub struct ResultBits64 {
+    pub is_ok: bool,
+    pub ok_or_err: u64,
+}
+
+/// A type whose FFI representation can be stored in the `ok_or_err` bits of a [`ResultBits64`].
+#[doc(hidden)]
+pub trait ResultBits: Sized {
+    fn into_bits(self) -> u64;
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! result_bits_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ResultBits for $ty {
+                fn into_bits(self) -> u64 {
+                    self as u64
+                }
+
+                fn from_bits(bits: u64) -> Self {
+                    bits as $ty
+                }
+            }
+        )*
+    };
+}
+
+result_bits_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl ResultBits for bool {
+    fn into_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        bits != 0
+    }
+}
+
+impl ResultBits for f32 {
+    fn into_bits(self) -> u64 {
+        self.to_bits() as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        f32::from_bits(bits as u32)
+    }
+}
+
+impl ResultBits for f64 {
+    fn into_bits(self) -> u64 {
+        self.to_bits()
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+}
+
+impl<T> ResultBits for *mut T {
+    fn into_bits(self) -> u64 {
+        self as usize as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        bits as usize as *mut T
+    }
+}
+
+impl<T> ResultBits for *const T {
+    fn into_bits(self) -> u64 {
+        self as usize as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        bits as usize as *const T
+    }
+}
+
+/// Move the struct of a result that is too large to be passed by value into memory that gets
+/// freed by [`unbox_result_struct`] or by Swift's call to `__swift_bridge__$Result$_free`.
+#[doc(hidden)]
+pub fn box_result_struct<T>(val: T) -> *mut T {
+    let ptr = crate::allocator::allocate(Layout::new::<T>()) as *mut T;
+    unsafe { ptr.write(val) };
+    ptr
+}
+
+/// Move the struct of a result that is too large to be passed by value out of the memory that
+/// [`box_result_struct`] or Swift's call to `__swift_bridge__$Result$_alloc` allocated.
+///
+/// # Safety
+///
+/// The pointer must point to an initialized `T` that was allocated for the layout of `T`.
+#[doc(hidden)]
+pub unsafe fn unbox_result_struct<T>(ptr: *mut T) -> T {
+    let val = ptr.read();
+    crate::allocator::deallocate(ptr as *mut u8, Layout::new::<T>());
+    val
+}
+
+#[export_name = "__swift_bridge__$Result$_alloc"]
+#[doc(hidden)]
+pub extern "C" fn result_struct_alloc(size: usize, align: usize) -> *mut u8 {
+    crate::allocator::allocate(Layout::from_size_align(size, align).unwrap())
+}
+
+#[export_name = "__swift_bridge__$Result$_free"]
+#[doc(hidden)]
+pub unsafe extern "C" fn result_struct_free(ptr: *mut u8, size: usize, align: usize) {
+    crate::allocator::deallocate(ptr, Layout::from_size_align(size, align).unwrap())
+}
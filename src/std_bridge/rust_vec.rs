@@ -20,39 +20,44 @@ vec_externs!(bool, OptionBool, false);
 mod macro_ {
     macro_rules! vec_externs {
         ($ty:ty, $option_ty:ident, $unused_none:expr) => {
+            impl crate::buffer_pool::Pooled for $ty {
+                fn pool() -> &'static crate::buffer_pool::BufferPool<$ty> {
+                    static POOL: crate::buffer_pool::BufferPool<$ty> =
+                        crate::buffer_pool::BufferPool::new();
+                    &POOL
+                }
+            }
+
             const _: () = {
                 #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$new")]
                 #[doc(hidden)]
                 pub extern "C" fn _new() -> *mut Vec<$ty> {
-                    Box::into_raw(Box::new(Vec::new()))
+                    crate::vec_support::new()
                 }
 
                 #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$_free")]
                 #[doc(hidden)]
                 pub extern "C" fn _drop(vec: *mut Vec<$ty>) {
-                    let vec = unsafe { Box::from_raw(vec) };
-                    drop(vec)
+                    let vec = unsafe { crate::allocator::take_from_raw(vec) };
+                    crate::buffer_pool::recycle(vec)
                 }
 
                 #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$len")]
                 #[doc(hidden)]
                 pub extern "C" fn _len(vec: *mut Vec<$ty>) -> usize {
-                    let vec = unsafe { &*vec };
-                    vec.len()
+                    unsafe { crate::vec_support::len(vec) }
                 }
 
                 #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$push")]
                 #[doc(hidden)]
                 pub extern "C" fn _push(vec: *mut Vec<$ty>, val: $ty) {
-                    let vec = unsafe { &mut *vec };
-                    vec.push(val);
+                    unsafe { crate::vec_support::push(vec, val) }
                 }
 
                 #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$pop")]
                 #[doc(hidden)]
                 pub extern "C" fn _pop(vec: *mut Vec<$ty>) -> crate::option::$option_ty {
-                    let vec = unsafe { &mut *vec };
-                    if let Some(val) = vec.pop() {
+                    if let Some(val) = unsafe { crate::vec_support::pop(vec) } {
                         crate::option::$option_ty { val, is_some: true }
                     } else {
                         crate::option::$option_ty {
@@ -70,12 +75,8 @@ mod macro_ {
                     vec: *mut Vec<$ty>,
                     index: usize,
                 ) -> crate::option::$option_ty {
-                    let vec = unsafe { &*vec };
-                    if let Some(val) = vec.get(index) {
-                        crate::option::$option_ty {
-                            val: *val,
-                            is_some: true,
-                        }
+                    if let Some(val) = unsafe { crate::vec_support::get_copy(vec, index) } {
+                        crate::option::$option_ty { val, is_some: true }
                     } else {
                         crate::option::$option_ty {
                             val: $unused_none,
@@ -92,12 +93,8 @@ mod macro_ {
                     vec: *mut Vec<$ty>,
                     index: usize,
                 ) -> crate::option::$option_ty {
-                    let vec = unsafe { &mut *vec };
-                    if let Some(val) = vec.get(index) {
-                        crate::option::$option_ty {
-                            val: *val,
-                            is_some: true,
-                        }
+                    if let Some(val) = unsafe { crate::vec_support::get_copy(vec, index) } {
+                        crate::option::$option_ty { val, is_some: true }
                     } else {
                         crate::option::$option_ty {
                             val: $unused_none,
@@ -109,8 +106,31 @@ mod macro_ {
                 #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$as_ptr")]
                 #[doc(hidden)]
                 pub extern "C" fn _as_ptr(vec: *mut Vec<$ty>) -> *const $ty {
-                    let vec = unsafe { &*vec };
-                    vec.as_ptr()
+                    unsafe { crate::vec_support::as_ptr(vec) }
+                }
+
+                #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$insert")]
+                #[doc(hidden)]
+                pub extern "C" fn _insert(vec: *mut Vec<$ty>, index: usize, val: $ty) {
+                    unsafe { crate::vec_support::insert(vec, index, val) }
+                }
+
+                #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$remove")]
+                #[doc(hidden)]
+                pub extern "C" fn _remove(vec: *mut Vec<$ty>, index: usize) -> $ty {
+                    unsafe { crate::vec_support::remove(vec, index) }
+                }
+
+                #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$reserve")]
+                #[doc(hidden)]
+                pub extern "C" fn _reserve(vec: *mut Vec<$ty>, additional: usize) {
+                    unsafe { crate::vec_support::reserve(vec, additional) }
+                }
+
+                #[export_name = concat!("__swift_bridge__$Vec_", stringify!($ty), "$capacity")]
+                #[doc(hidden)]
+                pub extern "C" fn _capacity(vec: *mut Vec<$ty>) -> usize {
+                    unsafe { crate::vec_support::capacity(vec) }
                 }
             };
         };
@@ -54,7 +54,7 @@ impl RustString {
 impl RustString {
     /// Box::into_raw(Box::new(self))
     pub fn box_into_raw(self) -> *mut RustString {
-        Box::into_raw(Box::new(self))
+        crate::allocator::box_into_raw(self)
     }
 }
 
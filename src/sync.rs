@@ -0,0 +1,231 @@
+//! Support for giving Swift scoped access to the values that Rust locks guard.
+//!
+//! See the `#[swift_bridge(Mutex(...))]` and `#[swift_bridge(RwLock(...))]` opaque type
+//! attributes.
+
+use std::cell::RefCell;
+use std::ops::Deref;
+use std::sync::PoisonError;
+
+thread_local! {
+    /// The guards of the locks that Swift `withLock` closures are running under on this thread,
+    /// innermost last.
+    static HELD_GUARDS: RefCell<Vec<HeldGuard>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A lock guard that is held while a Swift `withLock` closure runs.
+struct HeldGuard {
+    /// The lock that the guard belongs to, so that nested `withLock` calls on different locks
+    /// find their own guard.
+    lock: *const (),
+    /// The value that the guard gives access to.
+    value: *mut (),
+    /// Dropping this unlocks the lock.
+    _guard: Box<dyn ErasedGuard>,
+}
+
+/// Lets us store lock guards of any type, since all that we do with them is drop them.
+trait ErasedGuard {}
+
+impl<T> ErasedGuard for T {}
+
+/// A `std::sync::Mutex` whose value Swift can access using the generated `withLock` method.
+///
+/// It derefs to the `std::sync::Mutex`, so Rust code can lock it as usual.
+///
+/// ```ignore
+/// #[swift_bridge::bridge]
+/// mod ffi {
+///     extern "Rust" {
+///         type AppState;
+///
+///         #[swift_bridge(Mutex(AppState))]
+///         type SharedState;
+///     }
+/// }
+///
+/// pub type SharedState = swift_bridge::sync::Mutex<AppState>;
+/// ```
+#[derive(Debug, Default)]
+pub struct Mutex<T> {
+    mutex: std::sync::Mutex<T>,
+}
+
+impl<T> Mutex<T> {
+    /// Create a mutex that guards the value.
+    pub fn new(value: T) -> Self {
+        Mutex {
+            mutex: std::sync::Mutex::new(value),
+        }
+    }
+
+    /// Consume the mutex, returning the value that it guards.
+    pub fn into_inner(self) -> T {
+        self.mutex
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[doc(hidden)]
+    pub fn swift_lock(&self) {
+        let guard = self.mutex.lock().unwrap_or_else(PoisonError::into_inner);
+        hold_guard(self, guard, |guard| &mut **guard);
+    }
+
+    #[doc(hidden)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn swift_guarded_mut(&self) -> &mut T {
+        // SAFETY: The guard that gives exclusive access to the value is held until the Swift
+        //  closure that uses it returns.
+        unsafe { &mut *(held_value(self) as *mut T) }
+    }
+
+    #[doc(hidden)]
+    pub fn swift_unlock(&self) {
+        release_guard(self);
+    }
+}
+
+impl<T> Deref for Mutex<T> {
+    type Target = std::sync::Mutex<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mutex
+    }
+}
+
+impl<T> From<T> for Mutex<T> {
+    fn from(value: T) -> Self {
+        Mutex::new(value)
+    }
+}
+
+/// A `std::sync::RwLock` whose value Swift can access using the generated `withReadLock` and
+/// `withWriteLock` methods.
+///
+/// It derefs to the `std::sync::RwLock`, so Rust code can lock it as usual.
+///
+/// ```ignore
+/// #[swift_bridge::bridge]
+/// mod ffi {
+///     extern "Rust" {
+///         type AppState;
+///
+///         #[swift_bridge(RwLock(AppState))]
+///         type SharedState;
+///     }
+/// }
+///
+/// pub type SharedState = swift_bridge::sync::RwLock<AppState>;
+/// ```
+#[derive(Debug, Default)]
+pub struct RwLock<T> {
+    rw_lock: std::sync::RwLock<T>,
+}
+
+impl<T> RwLock<T> {
+    /// Create a lock that guards the value.
+    pub fn new(value: T) -> Self {
+        RwLock {
+            rw_lock: std::sync::RwLock::new(value),
+        }
+    }
+
+    /// Consume the lock, returning the value that it guards.
+    pub fn into_inner(self) -> T {
+        self.rw_lock
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[doc(hidden)]
+    pub fn swift_read(&self) {
+        let guard = self.rw_lock.read().unwrap_or_else(PoisonError::into_inner);
+        hold_guard(self, guard, |guard| &**guard as *const T as *mut T);
+    }
+
+    #[doc(hidden)]
+    pub fn swift_write(&self) {
+        let guard = self.rw_lock.write().unwrap_or_else(PoisonError::into_inner);
+        hold_guard(self, guard, |guard| &mut **guard);
+    }
+
+    #[doc(hidden)]
+    pub fn swift_guarded(&self) -> &T {
+        // SAFETY: A guard that gives shared access to the value is held until the Swift closure
+        //  that uses it returns.
+        unsafe { &*(held_value(self) as *const T) }
+    }
+
+    #[doc(hidden)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn swift_guarded_mut(&self) -> &mut T {
+        // SAFETY: The generated Swift code only calls this while holding the write lock.
+        unsafe { &mut *(held_value(self) as *mut T) }
+    }
+
+    #[doc(hidden)]
+    pub fn swift_unlock(&self) {
+        release_guard(self);
+    }
+}
+
+impl<T> Deref for RwLock<T> {
+    type Target = std::sync::RwLock<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rw_lock
+    }
+}
+
+impl<T> From<T> for RwLock<T> {
+    fn from(value: T) -> Self {
+        RwLock::new(value)
+    }
+}
+
+/// Hold on to the guard until [`release_guard`] is called on this thread.
+fn hold_guard<L, G, T>(lock: &L, mut guard: G, value: impl FnOnce(&mut G) -> *mut T) {
+    let value = value(&mut guard) as *mut ();
+    let guard: Box<dyn ErasedGuard + '_> = Box::new(guard);
+    // SAFETY: The guard borrows the lock, and the generated Swift code releases it before the
+    //  `withLock` call that is borrowing the lock returns.
+    let guard: Box<dyn ErasedGuard> = unsafe { std::mem::transmute(guard) };
+
+    HELD_GUARDS.with(|guards| {
+        guards.borrow_mut().push(HeldGuard {
+            lock: lock as *const L as *const (),
+            value,
+            _guard: guard,
+        })
+    });
+}
+
+/// The value of the innermost guard that this thread holds for the lock.
+fn held_value<L>(lock: &L) -> *mut () {
+    let lock = lock as *const L as *const ();
+
+    HELD_GUARDS.with(|guards| {
+        guards
+            .borrow()
+            .iter()
+            .rev()
+            .find(|held| held.lock == lock)
+            .map(|held| held.value)
+            .expect("The lock is not held by this thread.")
+    })
+}
+
+/// Unlock the innermost guard that this thread holds for the lock.
+fn release_guard<L>(lock: &L) {
+    let lock = lock as *const L as *const ();
+
+    let held = HELD_GUARDS.with(|guards| {
+        let mut guards = guards.borrow_mut();
+        let idx = guards.iter().rposition(|held| held.lock == lock)?;
+        Some(guards.remove(idx))
+    });
+
+    // Dropped after the `RefCell` borrow ends, since dropping a guard can run arbitrary code.
+    drop(held);
+}
@@ -0,0 +1,629 @@
+//! Helpers that the generated `__swift_bridge__$Vec_*` functions call.
+//!
+//! Swift calls a set of exported functions for every type that can be put in a `RustVec`, such as
+//! `__swift_bridge__$Vec_SomeType$push`. Those functions only convert between the type's FFI
+//! representation and the type, and call these helpers for everything else.
+//!
+//! Growing a vector, inserting and removing values, getting a value's address and dropping the
+//! values only depend on the element type's size, alignment and drop function, so they are
+//! implemented once by [`Buffer`] instead of being compiled again for every element type. The
+//! generic functions in this module only describe the element type with an [`Element`] and hand
+//! the `Vec`'s buffer to a [`Buffer`], so they are small enough to be inlined into the generated
+//! functions.
+
+use std::alloc::Layout;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+
+/// `Box::into_raw(Box::new(Vec::new()))`
+#[inline]
+pub fn new<T>() -> *mut Vec<T> {
+    crate::allocator::box_into_raw(Vec::new())
+}
+
+/// Drop the vector and its values.
+///
+/// # Safety
+///
+/// The pointer must have come from [`new`] or from a `Vec` that was handed to Swift.
+#[inline]
+pub unsafe fn free<T>(vec: *mut Vec<T>) {
+    let mut vec = ManuallyDrop::new(crate::allocator::take_from_raw(vec));
+    // The `Vec` isn't used again, so the buffer frees its values and its allocation.
+    Buffer::from_vec(&mut vec).free()
+}
+
+/// The number of values in the vector.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn len<T>(vec: *const Vec<T>) -> usize {
+    (*vec).len()
+}
+
+/// The number of values that the vector can hold without reallocating.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn capacity<T>(vec: *const Vec<T>) -> usize {
+    (*vec).capacity()
+}
+
+/// A pointer to the vector's first value.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn as_ptr<T>(vec: *const Vec<T>) -> *const T {
+    (*vec).as_ptr()
+}
+
+/// The address of the value at the index, or null if the index is out of bounds.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn get<T>(vec: *const Vec<T>, index: usize) -> *const T {
+    let vec = &*vec;
+    element_or_null(
+        vec.as_ptr() as *mut u8,
+        vec.len(),
+        std::mem::size_of::<T>(),
+        index,
+    ) as *const T
+}
+
+/// The mutable address of the value at the index, or null if the index is out of bounds.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn get_mut<T>(vec: *mut Vec<T>, index: usize) -> *mut T {
+    let vec = &mut *vec;
+    element_or_null(
+        vec.as_mut_ptr() as *mut u8,
+        vec.len(),
+        std::mem::size_of::<T>(),
+        index,
+    ) as *mut T
+}
+
+/// A copy of the value at the index, if the index is in bounds.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn get_copy<T: Copy>(vec: *const Vec<T>, index: usize) -> Option<T> {
+    get(vec, index).as_ref().copied()
+}
+
+/// Make sure that the vector can hold `additional` more values without reallocating.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn reserve<T>(vec: *mut Vec<T>, additional: usize) {
+    ErasedVec::new(&mut *vec).reserve(additional)
+}
+
+/// Push a value onto the end of the vector.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn push<T>(vec: *mut Vec<T>, val: T) {
+    let len = (*vec).len();
+    insert(vec, len, val)
+}
+
+/// Insert a value at the index, shifting the values after it to the right.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn insert<T>(vec: *mut Vec<T>, index: usize, val: T) {
+    let val = ManuallyDrop::new(val);
+    ErasedVec::new(&mut *vec).insert(index, &*val as *const T as *const u8)
+}
+
+/// Remove and return the last value, if there is one.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn pop<T>(vec: *mut Vec<T>) -> Option<T> {
+    match (*vec).len() {
+        0 => None,
+        len => Some(remove(vec, len - 1)),
+    }
+}
+
+/// Remove and return the value at the index, shifting the values after it to the left.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn remove<T>(vec: *mut Vec<T>, index: usize) -> T {
+    let mut val = MaybeUninit::<T>::uninit();
+    ErasedVec::new(&mut *vec).remove(index, val.as_mut_ptr() as *mut u8);
+    val.assume_init()
+}
+
+/// [`push`] a value that Swift is handing back to Rust, such as an opaque Rust type.
+///
+/// # Safety
+///
+/// The vector pointer must point to a live `Vec`, and the value must have come from
+/// `swift_bridge::allocator::box_into_raw`.
+#[inline]
+pub unsafe fn push_boxed<T>(vec: *mut Vec<T>, val: *mut T) {
+    push(vec, crate::allocator::take_from_raw(val))
+}
+
+/// [`insert`] a value that Swift is handing back to Rust, such as an opaque Rust type.
+///
+/// # Safety
+///
+/// The vector pointer must point to a live `Vec`, and the value must have come from
+/// `swift_bridge::allocator::box_into_raw`.
+#[inline]
+pub unsafe fn insert_boxed<T>(vec: *mut Vec<T>, index: usize, val: *mut T) {
+    insert(vec, index, crate::allocator::take_from_raw(val))
+}
+
+/// [`pop`] a value and box it so that it can be handed to Swift, or return null if the vector is
+/// empty.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn pop_boxed<T>(vec: *mut Vec<T>) -> *mut T {
+    match pop(vec) {
+        Some(val) => crate::allocator::box_into_raw(val),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// [`remove`] a value and box it so that it can be handed to Swift.
+///
+/// # Safety
+///
+/// The pointer must point to a live `Vec`.
+#[inline]
+pub unsafe fn remove_boxed<T>(vec: *mut Vec<T>, index: usize) -> *mut T {
+    crate::allocator::box_into_raw(remove(vec, index))
+}
+
+/// `std::ptr::drop_in_place` for a value whose type was erased.
+unsafe fn drop_erased<T>(val: *mut u8) {
+    std::ptr::drop_in_place(val as *mut T)
+}
+
+/// The address of the value at the index, or null if the index is out of bounds.
+///
+/// This is small enough that calling it would cost more than its body, so unlike the [`Buffer`]
+/// methods it gets inlined.
+#[inline]
+unsafe fn element_or_null(start: *mut u8, len: usize, size: usize, index: usize) -> *mut u8 {
+    if index < len {
+        start.add(index * size)
+    } else {
+        std::ptr::null_mut()
+    }
+}
+
+/// Everything that the shared helpers need to know about a vector's value type.
+#[derive(Copy, Clone)]
+struct Element {
+    /// The size and alignment of one value.
+    layout: Layout,
+    /// Drops a value in place, or `None` if the values don't need to be dropped.
+    drop: Option<unsafe fn(*mut u8)>,
+}
+
+impl Element {
+    #[inline]
+    fn of<T>() -> Self {
+        Element {
+            layout: Layout::new::<T>(),
+            drop: if std::mem::needs_drop::<T>() {
+                Some(drop_erased::<T>)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// A `Vec`'s buffer, with the type of its values erased.
+struct Buffer {
+    ptr: *mut u8,
+    len: usize,
+    cap: usize,
+    element: Element,
+}
+
+impl Buffer {
+    /// A copy of the `Vec`'s buffer. The `Vec` must not be used once the buffer changes.
+    #[inline]
+    fn from_vec<T>(vec: &mut Vec<T>) -> Self {
+        Buffer {
+            ptr: vec.as_mut_ptr() as *mut u8,
+            len: vec.len(),
+            cap: vec.capacity(),
+            element: Element::of::<T>(),
+        }
+    }
+
+    /// The address of the value at the index, which can be one past the last value.
+    unsafe fn element(&self, index: usize) -> *mut u8 {
+        self.ptr.add(index * self.element.layout.size())
+    }
+
+    /// The layout of a buffer that holds `cap` values, which is the layout that `Vec` allocates.
+    fn array_layout(&self, cap: usize) -> Layout {
+        self.element
+            .layout
+            .size()
+            .checked_mul(cap)
+            .and_then(|size| Layout::from_size_align(size, self.element.layout.align()).ok())
+            .expect("capacity overflow")
+    }
+
+    #[inline(never)]
+    fn reserve(&mut self, additional: usize) {
+        if self.cap - self.len >= additional {
+            return;
+        }
+
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        // Double the capacity like `Vec` does, so that pushing values one at a time only
+        // reallocates a logarithmic number of times.
+        let min_cap = if self.element.layout.size() == 1 {
+            8
+        } else {
+            4
+        };
+        let new_cap = required.max(self.cap.saturating_mul(2)).max(min_cap);
+
+        let new_layout = self.array_layout(new_cap);
+        // SAFETY: Zero sized values have a capacity of `usize::MAX`, so we only get here for
+        //  values that have a size and `new_layout` isn't zero sized. A zero capacity buffer
+        //  isn't allocated, otherwise the buffer was allocated with the layout for `self.cap`
+        //  values by the global allocator, like all `Vec` buffers are.
+        let ptr = unsafe {
+            if self.cap == 0 {
+                std::alloc::alloc(new_layout)
+            } else {
+                std::alloc::realloc(self.ptr, self.array_layout(self.cap), new_layout.size())
+            }
+        };
+        if ptr.is_null() {
+            std::alloc::handle_alloc_error(new_layout);
+        }
+
+        self.ptr = ptr;
+        self.cap = new_cap;
+    }
+
+    /// Copy the value into the buffer at the index.
+    #[inline(never)]
+    unsafe fn insert(&mut self, index: usize, val: *const u8) {
+        assert!(
+            index <= self.len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            self.len
+        );
+        self.reserve(1);
+
+        let size = self.element.layout.size();
+        let at = self.element(index);
+        std::ptr::copy(at, at.add(size), (self.len - index) * size);
+        std::ptr::copy_nonoverlapping(val, at, size);
+        self.len += 1;
+    }
+
+    /// Move the value at the index out of the buffer and into `out`.
+    #[inline(never)]
+    unsafe fn remove(&mut self, index: usize, out: *mut u8) {
+        assert!(
+            index < self.len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            self.len
+        );
+
+        let size = self.element.layout.size();
+        let at = self.element(index);
+        std::ptr::copy_nonoverlapping(at, out, size);
+        std::ptr::copy(at.add(size), at, (self.len - index - 1) * size);
+        self.len -= 1;
+    }
+
+    /// Drop the values from the index onwards, if there are any.
+    unsafe fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        let old_len = self.len;
+        // If dropping a value panics the rest of the values are leaked instead of being dropped
+        // twice.
+        self.len = len;
+
+        if let Some(drop_value) = self.element.drop {
+            for index in len..old_len {
+                drop_value(self.element(index));
+            }
+        }
+    }
+
+    /// Drop all of the values and free the buffer.
+    #[inline(never)]
+    unsafe fn free(mut self) {
+        // If dropping a value panics the buffer is leaked.
+        self.truncate(0);
+
+        // Zero sized values and empty vectors don't have an allocated buffer.
+        if self.element.layout.size() != 0 && self.cap != 0 {
+            std::alloc::dealloc(self.ptr, self.array_layout(self.cap));
+        }
+    }
+}
+
+/// A `Vec` whose buffer was taken out so that a [`Buffer`] can work on it.
+///
+/// Dropping it puts the buffer back into the `Vec`, even if the `Buffer` panicked.
+struct ErasedVec<'a, T> {
+    vec: &'a mut Vec<T>,
+    buffer: Buffer,
+}
+
+impl<'a, T> ErasedVec<'a, T> {
+    #[inline]
+    fn new(vec: &'a mut Vec<T>) -> Self {
+        let mut taken = ManuallyDrop::new(std::mem::take(vec));
+        let buffer = Buffer::from_vec(&mut taken);
+
+        ErasedVec { vec, buffer }
+    }
+}
+
+impl<T> Deref for ErasedVec<'_, T> {
+    type Target = Buffer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl<T> DerefMut for ErasedVec<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl<T> Drop for ErasedVec<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        let Buffer { ptr, len, cap, .. } = self.buffer;
+        // SAFETY: The buffer either came from the `Vec` or was allocated by the global allocator
+        //  with the layout of `cap` `T`s, and its first `len` values are initialized.
+        *self.vec = unsafe { Vec::from_raw_parts(ptr as *mut T, len, cap) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Counts its drops in the count that it shares with the other counters.
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    fn drop_counters(drops: &Rc<Cell<usize>>, count: usize) -> Vec<DropCounter> {
+        (0..count).map(|_| DropCounter(drops.clone())).collect()
+    }
+
+    thread_local! {
+        static ZERO_SIZED_DROPS: Cell<usize> = Cell::new(0);
+    }
+
+    struct ZeroSized;
+
+    impl Drop for ZeroSized {
+        fn drop(&mut self) {
+            ZERO_SIZED_DROPS.with(|drops| drops.set(drops.get() + 1));
+        }
+    }
+
+    /// Verify that we can push, get, insert, remove and pop zero sized values, which `Vec` never
+    /// allocates a buffer for.
+    #[test]
+    fn zero_sized_values() {
+        let vec = new::<()>();
+
+        unsafe {
+            push(vec, ());
+            push(vec, ());
+            insert(vec, 0, ());
+            assert_eq!(len(vec), 3);
+            assert_eq!(capacity(vec), usize::MAX);
+
+            assert!(!get(vec, 2).is_null());
+            assert!(get(vec, 3).is_null());
+
+            remove(vec, 1);
+            assert_eq!(pop(vec), Some(()));
+            assert_eq!(len(vec), 1);
+
+            free(vec);
+        }
+    }
+
+    /// Verify that zero sized values that implement `Drop` get dropped by `free`.
+    #[test]
+    fn zero_sized_values_are_dropped() {
+        let vec = new::<ZeroSized>();
+
+        unsafe {
+            push(vec, ZeroSized);
+            push(vec, ZeroSized);
+            push(vec, ZeroSized);
+
+            drop(pop(vec));
+            assert_eq!(ZERO_SIZED_DROPS.with(Cell::get), 1);
+
+            free(vec);
+        }
+        assert_eq!(ZERO_SIZED_DROPS.with(Cell::get), 3);
+    }
+
+    /// Verify that popped and removed values are moved out of the vector instead of being
+    /// dropped, and that `free` drops each of the remaining values once.
+    #[test]
+    fn pop_remove_and_free_drop_each_value_once() {
+        let drops = Rc::new(Cell::new(0));
+        let vec = crate::allocator::box_into_raw(drop_counters(&drops, 4));
+
+        unsafe {
+            let popped = pop(vec).unwrap();
+            let removed = remove(vec, 0);
+            assert_eq!(drops.get(), 0);
+            assert_eq!(len(vec), 2);
+
+            drop(popped);
+            drop(removed);
+            assert_eq!(drops.get(), 2);
+
+            free(vec);
+        }
+        assert_eq!(drops.get(), 4);
+    }
+
+    /// Verify that truncating drops the values past the new length and keeps the others.
+    #[test]
+    fn truncate_drops_values_past_the_length() {
+        let drops = Rc::new(Cell::new(0));
+        let mut vec = drop_counters(&drops, 5);
+
+        unsafe { ErasedVec::new(&mut vec).truncate(2) };
+        assert_eq!(drops.get(), 3);
+        assert_eq!(vec.len(), 2);
+
+        unsafe { ErasedVec::new(&mut vec).truncate(4) };
+        assert_eq!(drops.get(), 3);
+        assert_eq!(vec.len(), 2);
+
+        drop(vec);
+        assert_eq!(drops.get(), 5);
+    }
+
+    /// Verify that we can insert at the start and at the end of the vector, and remove the first
+    /// and last values.
+    #[test]
+    fn insert_and_remove_at_the_bounds() {
+        let mut vec = vec![2u32, 3];
+
+        unsafe {
+            insert(&mut vec, 0, 1);
+            insert(&mut vec, 3, 4);
+            assert_eq!(vec, [1, 2, 3, 4]);
+
+            assert_eq!(*get(&vec, 3), 4);
+            assert!(get(&vec, 4).is_null());
+
+            assert_eq!(remove(&mut vec, 3), 4);
+            assert_eq!(remove(&mut vec, 0), 1);
+        }
+        assert_eq!(vec, [2, 3]);
+    }
+
+    /// Verify that inserting past the end of the vector panics like `Vec::insert` does, and that
+    /// the vector is left intact.
+    #[test]
+    fn insert_past_the_end_panics() {
+        let mut vec = vec![1u32, 2];
+
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
+            insert(&mut vec, 3, 3)
+        }));
+        assert!(panic.is_err());
+        assert_eq!(vec, [1, 2]);
+    }
+
+    /// Verify that removing past the end of the vector panics like `Vec::remove` does, and that
+    /// the vector is left intact.
+    #[test]
+    fn remove_past_the_end_panics() {
+        let mut vec = vec![1u32, 2];
+
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
+            remove(&mut vec, 2)
+        }));
+        assert!(panic.is_err());
+        assert_eq!(vec, [1, 2]);
+    }
+
+    /// Verify that we can grow a vector whose capacity is exactly its length, such as one that
+    /// came from a boxed slice, by reallocating the buffer that `Vec` allocated.
+    #[test]
+    fn grow_exact_capacity_vec() {
+        let mut vec = vec![1u64, 2, 3].into_boxed_slice().into_vec();
+        assert_eq!(vec.capacity(), 3);
+
+        unsafe { push(&mut vec, 4) };
+        assert_eq!(vec, [1, 2, 3, 4]);
+        assert!(vec.capacity() >= 4);
+
+        unsafe { reserve(&mut vec, 100) };
+        assert_eq!(vec, [1, 2, 3, 4]);
+        assert!(vec.capacity() >= 104);
+    }
+
+    /// Verify that a vector that the helpers allocated and changed can be used as a regular
+    /// `Vec` again, since the helpers allocate its buffer the way that `Vec` does.
+    #[test]
+    fn round_trip_into_vec() {
+        let vec = new::<String>();
+
+        unsafe {
+            for i in 0..100 {
+                push(vec, i.to_string());
+            }
+            insert(vec, 50, "inserted".to_string());
+            assert_eq!(remove(vec, 0), "0");
+        }
+
+        let mut vec = unsafe { crate::allocator::take_from_raw(vec) };
+        assert_eq!(vec.len(), 100);
+        assert_eq!(vec[49], "inserted");
+        assert_eq!(vec[99], "99");
+
+        vec.push("100".to_string());
+        vec.shrink_to_fit();
+        assert_eq!(vec.last().unwrap(), "100");
+    }
+}